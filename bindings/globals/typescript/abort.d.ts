@@ -1,16 +1,21 @@
-declare class AbortController {
+declare class AbortController implements Disposable {
 	constructor();
 
 	get signal(): AbortSignal;
 
 	abort(reason?: any): void;
+
+	[Symbol.dispose](): void;
 }
 
-declare class AbortSignal {
+declare class AbortSignal extends EventTarget {
 	get aborted(): boolean;
 
 	get reason(): any;
 
+	get onabort(): ((event: Event) => void) | null;
+	set onabort(handler: ((event: Event) => void) | null);
+
 	static abort(reason?: any): AbortSignal;
 
 	static timeout(time: number): AbortSignal;
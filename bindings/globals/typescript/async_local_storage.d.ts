@@ -0,0 +1,11 @@
+declare class AsyncLocalStorage<T> {
+	constructor();
+
+	getStore(): T | undefined;
+
+	run<R, A extends any[]>(store: T, callback: (...args: A) => R, ...args: A): R;
+
+	exit<R, A extends any[]>(callback: (...args: A) => R, ...args: A): R;
+
+	enterWith(store: T): void;
+}
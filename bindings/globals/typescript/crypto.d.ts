@@ -0,0 +1,70 @@
+declare type KeyUsage = "encrypt" | "decrypt" | "sign" | "verify" | "deriveKey" | "deriveBits" | "wrapKey" | "unwrapKey";
+
+declare type HashAlgorithmIdentifier = "SHA-1" | "SHA-256" | "SHA-384" | "SHA-512" | { name: string };
+declare type AlgorithmIdentifier = string | { name: string };
+
+declare interface HmacKeyParams {
+	name: "HMAC";
+	hash: HashAlgorithmIdentifier;
+	length?: number;
+}
+
+declare interface AesKeyGenParams {
+	name: "AES-GCM";
+	length: 128 | 192 | 256;
+}
+
+declare interface AesGcmParams {
+	name: "AES-GCM";
+	iv: BufferSource;
+	additionalData?: BufferSource;
+	tagLength?: 128;
+}
+
+declare interface JsonWebKey {
+	kty: "oct";
+	k?: string;
+	alg?: string;
+	ext?: boolean;
+	key_ops?: KeyUsage[];
+}
+
+declare class CryptoKey {
+	private constructor();
+
+	get type(): "secret";
+	get extractable(): boolean;
+	get algorithm(): { name: string, hash?: { name: string }, length: number };
+	get usages(): KeyUsage[];
+}
+
+declare class SubtleCrypto {
+	private constructor();
+
+	digest(algorithm: HashAlgorithmIdentifier, data: BufferSource): Promise<ArrayBuffer>;
+
+	sign(algorithm: AlgorithmIdentifier, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer>;
+	verify(algorithm: AlgorithmIdentifier, key: CryptoKey, signature: BufferSource, data: BufferSource): Promise<boolean>;
+
+	encrypt(algorithm: AesGcmParams, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer>;
+	decrypt(algorithm: AesGcmParams, key: CryptoKey, data: BufferSource): Promise<ArrayBuffer>;
+
+	importKey(format: "raw", keyData: BufferSource, algorithm: HmacKeyParams | AlgorithmIdentifier, extractable: boolean, keyUsages: KeyUsage[]): Promise<CryptoKey>;
+	importKey(format: "jwk", keyData: JsonWebKey, algorithm: HmacKeyParams | AlgorithmIdentifier, extractable: boolean, keyUsages: KeyUsage[]): Promise<CryptoKey>;
+	exportKey(format: "raw", key: CryptoKey): Promise<ArrayBuffer>;
+	exportKey(format: "jwk", key: CryptoKey): Promise<JsonWebKey>;
+	generateKey(algorithm: HmacKeyParams | AesKeyGenParams, extractable: boolean, keyUsages: KeyUsage[]): Promise<CryptoKey>;
+}
+
+declare type IntegerTypedArray = Int8Array | Uint8Array | Uint8ClampedArray | Int16Array | Uint16Array | Int32Array | Uint32Array | BigInt64Array | BigUint64Array;
+
+declare class Crypto {
+	private constructor();
+
+	get subtle(): SubtleCrypto;
+
+	getRandomValues<T extends IntegerTypedArray>(array: T): T;
+	randomUUID(): string;
+}
+
+declare var crypto: Crypto;
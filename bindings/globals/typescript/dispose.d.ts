@@ -0,0 +1,12 @@
+interface SymbolConstructor {
+	readonly dispose: unique symbol;
+	readonly asyncDispose: unique symbol;
+}
+
+interface Disposable {
+	[Symbol.dispose](): void;
+}
+
+interface AsyncDisposable {
+	[Symbol.asyncDispose](): PromiseLike<void>;
+}
@@ -1,10 +1,10 @@
 declare interface DecoderOptions {
-	fatal: boolean;
-	ignoreBOM: boolean;
+	fatal?: boolean;
+	ignoreBOM?: boolean;
 }
 
 declare interface DecodeOptions {
-	stream: boolean;
+	stream?: boolean;
 }
 
 declare class TextDecoder {
@@ -16,7 +16,7 @@ declare class TextDecoder {
 
 	get ignoreBOM(): boolean;
 
-	decode(buffer: BufferSource, options?: DecodeOptions): string;
+	decode(buffer?: BufferSource, options?: DecodeOptions): string;
 }
 
 declare interface EncodeResult {
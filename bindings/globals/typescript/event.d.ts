@@ -0,0 +1,104 @@
+declare interface EventInit {
+	bubbles?: boolean;
+	cancelable?: boolean;
+	composed?: boolean;
+}
+
+declare class Event {
+	static NONE: number;
+	static CAPTURING_PHASE: number;
+	static AT_TARGET: number;
+	static BUBBLING_PHASE: number;
+
+	constructor(type: string, init?: EventInit);
+
+	get type(): string;
+
+	get target(): EventTarget | null;
+
+	get currentTarget(): EventTarget | null;
+
+	get eventPhase(): number;
+
+	get bubbles(): boolean;
+
+	get cancelable(): boolean;
+
+	get composed(): boolean;
+
+	get defaultPrevented(): boolean;
+
+	get isTrusted(): boolean;
+
+	get timeStamp(): number;
+
+	composedPath(): EventTarget[];
+
+	preventDefault(): void;
+
+	stopPropagation(): void;
+
+	stopImmediatePropagation(): void;
+}
+
+declare interface CustomEventInit<T = any> extends EventInit {
+	detail?: T;
+}
+
+declare class CustomEvent<T = any> extends Event {
+	constructor(type: string, init?: CustomEventInit<T>);
+
+	get detail(): T;
+}
+
+declare interface PromiseRejectionEventInit extends EventInit {
+	promise: Promise<any>;
+	reason?: any;
+}
+
+declare class PromiseRejectionEvent extends Event {
+	constructor(type: string, init: PromiseRejectionEventInit);
+
+	get promise(): Promise<any>;
+
+	get reason(): any;
+}
+
+declare var onunhandledrejection: ((event: PromiseRejectionEvent) => void) | null | undefined;
+
+declare interface EventListener {
+	(event: Event): void;
+}
+
+declare interface EventListenerObject {
+	handleEvent(event: Event): void;
+}
+
+declare type EventListenerOrEventListenerObject = EventListener | EventListenerObject;
+
+declare interface EventListenerOptions {
+	capture?: boolean;
+}
+
+declare interface AddEventListenerOptions extends EventListenerOptions {
+	once?: boolean;
+	passive?: boolean;
+}
+
+declare class EventTarget {
+	constructor();
+
+	addEventListener(
+		type: string,
+		callback: EventListenerOrEventListenerObject | null,
+		options?: AddEventListenerOptions | boolean,
+	): void;
+
+	removeEventListener(
+		type: string,
+		callback: EventListenerOrEventListenerObject | null,
+		options?: EventListenerOptions | boolean,
+	): void;
+
+	dispatchEvent(event: Event): boolean;
+}
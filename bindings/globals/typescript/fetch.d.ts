@@ -5,6 +5,8 @@ declare class Headers implements Iterable<[string, string]> {
 
 	append(name: string, value: string): void;
 
+	appendAll(entries: [string, string][]): void;
+
 	delete(name: string): void;
 
 	get(name: string): string | null;
@@ -15,6 +17,8 @@ declare class Headers implements Iterable<[string, string]> {
 
 	set(name: string, value: string): void;
 
+	setAll(headers: Record<string, string | string[]>): void;
+
 	[Symbol.iterator](): Iterator<[string, string]>;
 }
 
@@ -87,11 +91,15 @@ declare interface RequestInit {
 	duplex?: RequestDuplex;
 	priority?: RequestPriority;
 	window?: null;
+
+	rawHeaderCase?: boolean;
 }
 
 declare class Request {
 	constructor(input: RequestInfo, init?: RequestInit);
 
+	static json(input: RequestInfo, data: any, init?: RequestInit): Request;
+
 	get method(): string;
 
 	get url(): string;
@@ -116,6 +124,8 @@ declare class Request {
 
 	get keepalive(): boolean;
 
+	get rawHeaderCase(): boolean;
+
 	get isReloadNavigation(): string;
 
 	get isHistoryNavigation(): string;
@@ -131,6 +141,37 @@ declare interface ResponseInit {
 	headers?: HeadersInit;
 }
 
+declare interface ServerTiming {
+	name: string;
+	duration: number;
+	description: string;
+}
+
+declare interface ResponseTiming {
+	start: number | null;
+	headersReceived: number | null;
+	bodyComplete: number | null;
+}
+
+declare interface ConnectionTiming {
+	dns: number | null;
+	connect: number | null;
+	tls: number | null;
+	ttfb: number | null;
+}
+
+declare interface ResponseConnection {
+	remoteAddress: string | null;
+	remotePort: number | null;
+	localAddress: string | null;
+	localPort: number | null;
+	protocol: string | null;
+	tlsVersion: string | null;
+	cipherSuite: string | null;
+	proxied: boolean;
+	timing: ConnectionTiming;
+}
+
 declare type ResponseType = "basic" | "cors" | "default" | "error" | "opaque" | "opaqueredirect";
 
 declare class Response {
@@ -150,11 +191,82 @@ declare class Response {
 
 	get headers(): Headers;
 
+	get serverTiming(): ServerTiming[];
+
+	get timing(): ResponseTiming | null;
+
+	get connection(): ResponseConnection | null;
+
 	get bodyUsed(): boolean;
 
 	arrayBuffer(): Promise<ArrayBuffer>;
 
 	text(): Promise<string>;
+
+	withHeaders(headers: HeadersInit): Response;
+}
+
+declare interface DownloadProgress {
+	loaded: number;
+	total: number | null;
+}
+
+declare interface DownloadOptions extends RequestInit {
+	onProgress?: (progress: DownloadProgress) => void;
+}
+
+declare interface PaginateOptions extends RequestInit {
+	nextLink?: (response: Response) => string | URL | null | undefined | Promise<string | URL | null | undefined>;
 }
 
 declare function fetch(input: RequestInfo, init?: RequestInit): Promise<Response>;
+
+declare namespace fetch {
+	function download(input: RequestInfo, path: string, options?: DownloadOptions): Promise<number>;
+
+	function paginate(input: RequestInfo, options?: PaginateOptions): AsyncIterableIterator<Response>;
+}
+
+declare interface FetchEventInit extends EventInit {
+	request: Request;
+}
+
+declare class FetchEvent extends Event {
+	constructor(type: string, init: FetchEventInit);
+
+	get request(): Request;
+
+	respondWith(response: Response | Promise<Response>): void;
+}
+
+declare function addEventListener(type: "fetch", callback: (event: FetchEvent) => void): void;
+
+declare function removeEventListener(type: "fetch", callback: (event: FetchEvent) => void): void;
+
+declare interface CacheQueryOptions {
+	ignoreSearch?: boolean;
+	ignoreMethod?: boolean;
+	ignoreVary?: boolean;
+}
+
+declare interface MultiCacheQueryOptions extends CacheQueryOptions {
+	cacheName?: string;
+}
+
+declare class Cache {
+	match(request: RequestInfo, options?: CacheQueryOptions): Promise<Response | undefined>;
+	matchAll(request?: RequestInfo, options?: CacheQueryOptions): Promise<Response[]>;
+	put(request: RequestInfo, response: Response): Promise<void>;
+	delete(request: RequestInfo, options?: CacheQueryOptions): Promise<boolean>;
+	keys(request?: RequestInfo, options?: CacheQueryOptions): Promise<Request[]>;
+}
+
+declare class CacheStorage {
+	open(cacheName: string): Promise<Cache>;
+	has(cacheName: string): Promise<boolean>;
+	delete(cacheName: string): Promise<boolean>;
+	keys(): Promise<string[]>;
+	match(request: RequestInfo, options?: MultiCacheQueryOptions): Promise<Response | undefined>;
+}
+
+declare var caches: CacheStorage;
@@ -0,0 +1 @@
+declare function gc(shrink?: boolean): void;
@@ -0,0 +1,27 @@
+declare interface MessageEvent<T = any> {
+	readonly type: "message" | "messageerror";
+	readonly data: T;
+	readonly target: MessagePort;
+}
+
+declare class MessageChannel {
+	constructor();
+
+	get port1(): MessagePort;
+
+	get port2(): MessagePort;
+}
+
+declare class MessagePort {
+	postMessage(message: any): void;
+
+	start(): void;
+
+	close(): void;
+
+	get onmessage(): ((event: MessageEvent) => void) | null;
+	set onmessage(handler: ((event: MessageEvent) => void) | null);
+
+	get onmessageerror(): ((event: MessageEvent) => void) | null;
+	set onmessageerror(handler: ((event: MessageEvent) => void) | null);
+}
@@ -0,0 +1,8 @@
+declare interface SpiderfireBuildInfo {
+	readonly version: string;
+	readonly spidermonkey: string;
+	readonly features: readonly string[];
+	readonly target: string;
+}
+
+declare var spiderfire: SpiderfireBuildInfo;
@@ -0,0 +1,44 @@
+declare class PerformanceEntry {
+	private constructor();
+
+	get name(): string;
+	get entryType(): "mark" | "measure";
+	get startTime(): number;
+	get duration(): number;
+	get detail(): any;
+
+	toJSON(): object;
+}
+
+declare interface PerformanceMarkOptions {
+	detail?: any;
+	startTime?: number;
+}
+
+declare interface PerformanceMeasureOptions {
+	detail?: any;
+	start?: string | number;
+	duration?: number;
+	end?: string | number;
+}
+
+declare class Performance {
+	private constructor();
+
+	get timeOrigin(): number;
+
+	now(): number;
+	mark(name: string, options?: PerformanceMarkOptions): PerformanceEntry;
+	measure(name: string, startOrOptions?: string | number | PerformanceMeasureOptions, endMark?: string): PerformanceEntry;
+
+	getEntries(): PerformanceEntry[];
+	getEntriesByType(type: string): PerformanceEntry[];
+	getEntriesByName(name: string, type?: string): PerformanceEntry[];
+
+	clearMarks(name?: string): void;
+	clearMeasures(name?: string): void;
+
+	toJSON(): object;
+}
+
+declare var performance: Performance;
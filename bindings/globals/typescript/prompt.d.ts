@@ -0,0 +1,3 @@
+declare function prompt(message?: string, defaultValue?: string): Promise<string | null>;
+
+declare function confirm(message?: string): Promise<boolean>;
@@ -0,0 +1,9 @@
+type ShadowRealmValue = undefined | null | boolean | number | bigint | string | symbol | ((...args: any[]) => any);
+
+declare class ShadowRealm {
+	constructor();
+
+	evaluate(source: string): ShadowRealmValue;
+
+	importValue(specifier: string, exportName: string): Promise<ShadowRealmValue>;
+}
@@ -0,0 +1,5 @@
+declare interface SpiderfireRuntime {
+	onShutdown(callback: () => void | Promise<void>): void;
+}
+
+declare var runtime: SpiderfireRuntime;
@@ -0,0 +1,10 @@
+declare interface StackFrame {
+	function: string | null;
+	file: string;
+	line: number;
+	column: number;
+}
+
+declare interface ErrorConstructor {
+	stackFrames(error?: object): StackFrame[];
+}
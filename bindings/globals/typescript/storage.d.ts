@@ -0,0 +1,19 @@
+declare class Storage {
+	private constructor();
+
+	readonly length: number;
+
+	key(index: number): string | null;
+
+	getItem(key: string): string | null;
+
+	setItem(key: string, value: string): void;
+
+	removeItem(key: string): void;
+
+	clear(): void;
+}
+
+declare var localStorage: Storage;
+
+declare var sessionStorage: Storage;
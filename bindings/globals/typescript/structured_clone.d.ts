@@ -0,0 +1,5 @@
+declare interface StructuredSerializeOptions {
+	transfer?: object[];
+}
+
+declare function structuredClone<T>(value: T, options?: StructuredSerializeOptions): T;
@@ -7,3 +7,14 @@ declare function clearTimeout(id: number): void;
 declare function clearInterval(id: number): void;
 
 declare function queueMacrotask(callback: () => void): void;
+
+declare namespace timers {
+	interface TimerInfo {
+		id: number;
+		type: "timeout" | "interval";
+		label: string;
+		due: number;
+	}
+
+	function list(): TimerInfo[];
+}
@@ -0,0 +1,29 @@
+declare module "diagnostics" {
+	export type TaskType = "promise" | "microtask" | "timer" | "macrotask" | "signal";
+
+	export interface HookCallbacks {
+		init?(id: number, type: TaskType, triggerId: number): void;
+
+		before?(id: number): void;
+
+		after?(id: number, duration: number): void;
+
+		destroy?(id: number): void;
+	}
+
+	export function createHook(callbacks: HookCallbacks): number;
+
+	export function removeHook(id: number): boolean;
+
+	export function currentTaskId(): number;
+
+	namespace Diagnostics {
+		export {
+			createHook,
+			removeHook,
+			currentTaskId,
+		};
+	}
+
+	export default Diagnostics;
+}
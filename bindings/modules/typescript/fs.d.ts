@@ -1,4 +1,23 @@
 declare module "fs" {
+	export interface FileStat {
+		isFile: boolean,
+		isDirectory: boolean,
+		isSymlink: boolean,
+		size: number,
+		modified: number | null,
+		accessed: number | null,
+		created: number | null,
+	}
+
+	export interface MakeDirOptions {
+		recursive?: boolean,
+	}
+
+	export interface RemoveOptions {
+		recursive?: boolean,
+		force?: boolean,
+	}
+
 	export function readBinary(path: string): Promise<Uint8Array>;
 
 	export function readString(path: string): Promise<string>;
@@ -25,6 +44,17 @@ declare module "fs" {
 
 	export function hardLink(original: string, link: string): Promise<boolean>;
 
+	export function readFile(path: string): Promise<Uint8Array>;
+	export function readFile(path: string, encoding: "utf-8" | "utf8"): Promise<string>;
+
+	export function writeFile(path: string, data: string | BufferSource | Blob): Promise<void>;
+
+	export function stat(path: string): Promise<FileStat>;
+
+	export function mkdir(path: string, options?: MakeDirOptions): Promise<void>;
+
+	export function rm(path: string, options?: RemoveOptions): Promise<void>;
+
 	export const sync: {
 		readBinary(path: string): Uint8Array,
 		readString(path: string): string,
@@ -39,6 +69,12 @@ declare module "fs" {
 		rename(from: string, to: string): boolean,
 		softLink(original: string, link: string): boolean,
 		hardLink(original: string, link: string): boolean,
+		readFile(path: string): Uint8Array,
+		readFile(path: string, encoding: "utf-8" | "utf8"): string,
+		writeFile(path: string, data: string | BufferSource | Blob): void,
+		stat(path: string): FileStat,
+		mkdir(path: string, options?: MakeDirOptions): void,
+		rm(path: string, options?: RemoveOptions): void,
 	};
 
 	namespace Assert {
@@ -56,6 +92,11 @@ declare module "fs" {
 			rename,
 			softLink,
 			hardLink,
+			readFile,
+			writeFile,
+			stat,
+			mkdir,
+			rm,
 
 			sync,
 		};
@@ -0,0 +1,27 @@
+declare module "http" {
+	import { ListenOptions, NetAddr } from "net";
+
+	export type Handler = (request: Request) => Response | Promise<Response>;
+
+	export class Server implements Disposable {
+		private constructor();
+
+		readonly addr: NetAddr;
+		readonly finished: Promise<void>;
+
+		close(): void;
+
+		[Symbol.dispose](): void;
+	}
+
+	export function serve(handler: Handler, options?: ListenOptions): Server;
+
+	namespace Http {
+		export {
+			serve,
+			Server,
+		};
+	}
+
+	export default Http;
+}
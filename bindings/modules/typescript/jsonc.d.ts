@@ -0,0 +1,14 @@
+declare module "jsonc" {
+	export function strip(text: string): string;
+
+	export function parse(text: string, reviver?: (this: any, key: string, value: any) => any): any;
+
+	namespace Jsonc {
+		export {
+			strip,
+			parse,
+		};
+	}
+
+	export default Jsonc;
+}
@@ -0,0 +1,27 @@
+declare module "kv" {
+	export interface ListOptions {
+		prefix?: string;
+		limit?: number;
+	}
+
+	export function get(key: string, type?: "text"): Promise<string | null>;
+	export function get(key: string, type: "arrayBuffer"): Promise<ArrayBuffer | null>;
+
+	export function put(key: string, value: string | BufferSource): Promise<void>;
+
+	function del(key: string): Promise<boolean>;
+	export { del as delete };
+
+	export function list(options?: ListOptions): Promise<string[]>;
+
+	namespace Kv {
+		export {
+			get,
+			put,
+			del as delete,
+			list,
+		};
+	}
+
+	export default Kv;
+}
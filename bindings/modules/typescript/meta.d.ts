@@ -0,0 +1,17 @@
+declare module "meta" {
+	export const version: string;
+	export const spidermonkey: string;
+	export const features: readonly string[];
+	export const target: string;
+
+	namespace Meta {
+		export {
+			version,
+			spidermonkey,
+			features,
+			target,
+		};
+	}
+
+	export default Meta;
+}
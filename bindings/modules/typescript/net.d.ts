@@ -0,0 +1,82 @@
+declare module "net" {
+	export interface NetAddr {
+		transport: "tcp" | "udp";
+		hostname: string;
+		port: number;
+	}
+
+	export interface ConnectOptions {
+		hostname?: string;
+		port: number;
+	}
+
+	export interface ListenOptions {
+		hostname?: string;
+		port?: number;
+	}
+
+	export interface Datagram {
+		data: Uint8Array;
+		addr: NetAddr;
+	}
+
+	export class Connection implements Disposable {
+		private constructor();
+
+		readonly readable: ReadableStream<ArrayBuffer>;
+		readonly writable: WritableStream<BufferSource | string>;
+		readonly localAddr: NetAddr;
+		readonly remoteAddr: NetAddr;
+
+		close(): void;
+
+		[Symbol.dispose](): void;
+	}
+
+	export class Listener implements AsyncIterable<Connection>, Disposable {
+		private constructor();
+
+		readonly addr: NetAddr;
+
+		accept(): Promise<Connection | null>;
+
+		close(): void;
+
+		[Symbol.dispose](): void;
+
+		[Symbol.asyncIterator](): AsyncIterator<Connection>;
+	}
+
+	export class DatagramSocket implements Disposable {
+		private constructor();
+
+		readonly addr: NetAddr;
+
+		send(data: BufferSource | string, target: ConnectOptions): Promise<number>;
+
+		receive(): Promise<Datagram | null>;
+
+		close(): void;
+
+		[Symbol.dispose](): void;
+	}
+
+	export function connect(options: ConnectOptions): Promise<Connection>;
+
+	export function listen(options?: ListenOptions): Promise<Listener>;
+
+	export function udpSocket(options?: ListenOptions): Promise<DatagramSocket>;
+
+	namespace Net {
+		export {
+			connect,
+			listen,
+			udpSocket,
+			Connection,
+			Listener,
+			DatagramSocket,
+		};
+	}
+
+	export default Net;
+}
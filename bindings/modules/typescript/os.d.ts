@@ -0,0 +1,31 @@
+declare module "os" {
+	export interface MemoryInfo {
+		total: number | null;
+		available: number | null;
+	}
+
+	export function hostname(): string;
+
+	export function platform(): string;
+
+	export function arch(): string;
+
+	export function cpuCount(): number;
+
+	export function memory(): MemoryInfo;
+
+	export function uptime(): number;
+
+	namespace OS {
+		export {
+			hostname,
+			platform,
+			arch,
+			cpuCount,
+			memory,
+			uptime,
+		};
+	}
+
+	export default OS;
+}
@@ -0,0 +1,61 @@
+declare module "perf" {
+	export interface MemoryUsage {
+		heapBytes: number;
+		heapLimit: number;
+		chunks: number;
+		unusedChunks: number;
+	}
+
+	export interface GcStats {
+		collections: number;
+		majorCollections: number;
+		minorCollections: number;
+		incremental: boolean;
+	}
+
+	export interface ProfileFrame {
+		name: string;
+		file: string;
+		line: number;
+		col: number;
+	}
+
+	export interface SampledProfile {
+		type: "sampled";
+		name: string;
+		unit: "milliseconds";
+		startValue: number;
+		endValue: number;
+		samples: number[][];
+		weights: number[];
+	}
+
+	export interface Profile {
+		$schema: "https://www.speedscope.app/file-format-schema.json";
+		shared: {
+			frames: ProfileFrame[];
+		};
+		profiles: SampledProfile[];
+		name: string;
+		activeProfileIndex: number;
+		exporter: string;
+	}
+
+	export function start(interval?: number): boolean;
+	export function stop(): Profile;
+	export function isProfiling(): boolean;
+	export function memoryUsage(): MemoryUsage;
+	export function gcStats(): GcStats;
+
+	namespace Perf {
+		export {
+			start,
+			stop,
+			isProfiling,
+			memoryUsage,
+			gcStats,
+		};
+	}
+
+	export default Perf;
+}
@@ -0,0 +1,82 @@
+declare module "store" {
+	export type StoreKey = number | string;
+
+	export interface KeyRangeInit {
+		lower?: StoreKey;
+		upper?: StoreKey;
+		lowerOpen?: boolean;
+		upperOpen?: boolean;
+	}
+
+	export interface QueryOptions {
+		limit?: number;
+		direction?: "next" | "prev";
+	}
+
+	export class Store {
+		private constructor();
+
+		get name(): string;
+
+		get(key: StoreKey): Promise<any>;
+		getAll(range?: StoreKey | KeyRangeInit, options?: QueryOptions): Promise<any[]>;
+		getAllKeys(range?: StoreKey | KeyRangeInit, options?: QueryOptions): Promise<StoreKey[]>;
+		count(range?: StoreKey | KeyRangeInit): Promise<number>;
+
+		put(key: StoreKey, value: any): Promise<StoreKey>;
+		add(key: StoreKey, value: any): Promise<StoreKey>;
+		delete(range: StoreKey | KeyRangeInit): Promise<void>;
+		clear(): Promise<void>;
+
+		openCursor(range?: StoreKey | KeyRangeInit, options?: Omit<QueryOptions, "limit">): Promise<Cursor | null>;
+		transaction<T>(callback: (transaction: Transaction) => T | Promise<T>): Promise<T>;
+	}
+
+	export class Transaction {
+		private constructor();
+
+		get(key: StoreKey): Promise<any>;
+		getAll(range?: StoreKey | KeyRangeInit, options?: QueryOptions): Promise<any[]>;
+		getAllKeys(range?: StoreKey | KeyRangeInit, options?: QueryOptions): Promise<StoreKey[]>;
+		count(range?: StoreKey | KeyRangeInit): Promise<number>;
+
+		put(key: StoreKey, value: any): Promise<StoreKey>;
+		add(key: StoreKey, value: any): Promise<StoreKey>;
+		delete(range: StoreKey | KeyRangeInit): Promise<void>;
+		clear(): Promise<void>;
+	}
+
+	export class Cursor {
+		private constructor();
+
+		get key(): StoreKey | undefined;
+		get value(): any;
+
+		continue(): Promise<boolean>;
+	}
+
+	export const KeyRange: {
+		only(key: StoreKey): KeyRangeInit;
+		lowerBound(lower: StoreKey, open?: boolean): KeyRangeInit;
+		upperBound(upper: StoreKey, open?: boolean): KeyRangeInit;
+		bound(lower: StoreKey, upper: StoreKey, lowerOpen?: boolean, upperOpen?: boolean): KeyRangeInit;
+	};
+
+	export function open(name: string): Promise<Store>;
+	export function deleteStore(name: string): Promise<boolean>;
+	export function stores(): Promise<string[]>;
+
+	namespace StoreModule {
+		export {
+			open,
+			deleteStore,
+			stores,
+			Store,
+			Transaction,
+			Cursor,
+			KeyRange,
+		};
+	}
+
+	export default StoreModule;
+}
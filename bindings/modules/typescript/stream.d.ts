@@ -0,0 +1,21 @@
+declare module "stream" {
+	export interface Transform<I = any, O = any> {
+		readable: ReadableStream<O>;
+		writable: WritableStream<I>;
+	}
+
+	export interface Pipeline {
+		promise: Promise<void>;
+		controller: AbortController;
+	}
+
+	export function pipeline(source: ReadableStream, ...streams: [...Transform[], WritableStream]): Pipeline;
+
+	namespace Stream {
+		export {
+			pipeline,
+		};
+	}
+
+	export default Stream;
+}
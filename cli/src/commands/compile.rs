@@ -0,0 +1,82 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use runtime::config::{Config, CONFIG};
+use runtime::globals::process::{EnvAccess, ProcessOptions};
+use runtime::module::Bundle;
+
+use crate::evaluate::eval_bundle;
+
+pub(crate) struct CompileOptions {
+	pub(crate) output: Option<String>,
+	pub(crate) script: bool,
+	pub(crate) allow_env: bool,
+	pub(crate) allow_net: bool,
+}
+
+pub(crate) fn compile(path: &str, options: CompileOptions) {
+	let bundle = match Bundle::collect(Path::new(path)) {
+		Ok(bundle) => bundle.script(options.script).allow_env(options.allow_env).allow_net(options.allow_net),
+		Err(error) => {
+			eprintln!("Failed to bundle {}: {}", path, error);
+			return;
+		}
+	};
+	let output = options.output.map(PathBuf::from).unwrap_or_else(|| default_output(path));
+
+	let executable = match env::current_exe() {
+		Ok(executable) => executable,
+		Err(error) => {
+			eprintln!("Failed to locate the spiderfire executable: {}", error);
+			return;
+		}
+	};
+	match bundle.embed(&executable, &output) {
+		Ok(()) => println!(
+			"Compiled {} module(s) into {}",
+			bundle.modules().count(),
+			output.display()
+		),
+		Err(error) => eprintln!("Failed to write {}: {}", output.display(), error),
+	}
+}
+
+/// Names the executable after the entry, such as `main` or `main.exe` for `main.js`.
+fn default_output(path: &str) -> PathBuf {
+	Path::new(path).with_extension(env::consts::EXE_EXTENSION)
+}
+
+/// Returns the bundle embedded into the current executable, if it was produced by `spiderfire compile`.
+pub(crate) fn embedded_bundle() -> Option<Bundle> {
+	let executable = env::current_exe().ok()?;
+	Bundle::from_executable(&executable).unwrap_or_else(|error| {
+		eprintln!("Failed to read embedded bundle: {}", error);
+		None
+	})
+}
+
+/// Runs the entry of an embedded bundle, passing all arguments of the executable to `process.argv`.
+pub(crate) async fn run_embedded(bundle: Bundle) {
+	CONFIG
+		.set(
+			Config::default()
+				.script(bundle.script)
+				.allow_env(bundle.allow_env)
+				.allow_net(bundle.allow_net),
+		)
+		.unwrap();
+	let env = if bundle.allow_env {
+		EnvAccess::All
+	} else {
+		EnvAccess::None
+	};
+	let argv = env::args().collect();
+	eval_bundle(Rc::new(bundle), ProcessOptions { argv, env }).await;
+}
@@ -9,10 +9,11 @@ use mozjs::rust::{JSEngine, Runtime};
 use ion::Context;
 use modules::Modules;
 use runtime::RuntimeBuilder;
+use runtime::globals::process::ProcessOptions;
 
 use crate::evaluate::eval_inline;
 
-pub(crate) async fn eval_source(source: &str) {
+pub(crate) async fn eval_source(source: &str, process: ProcessOptions) {
 	let engine = JSEngine::init().unwrap();
 	let rt = Runtime::new(engine.handle());
 
@@ -21,6 +22,7 @@ pub(crate) async fn eval_source(source: &str) {
 		.microtask_queue()
 		.macrotask_queue()
 		.standard_modules(Modules)
+		.process(process)
 		.build(cx);
 	eval_inline(&rt, source).await;
 }
@@ -0,0 +1,21 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::{Path, PathBuf};
+
+use runtime::globals::process::ProcessOptions;
+
+use crate::evaluate::eval_heap_census;
+
+pub(crate) async fn heap_census(path: &str, output: Option<String>, process: ProcessOptions) {
+	let output = output.map(PathBuf::from).unwrap_or_else(|| default_output(path));
+	eval_heap_census(Path::new(path), process, &output).await;
+}
+
+/// Names the heap census after the script, such as `main.census.json` for `main.js`.
+fn default_output(path: &str) -> PathBuf {
+	Path::new(path).with_extension("census.json")
+}
@@ -6,11 +6,17 @@
 
 use runtime::cache::Cache;
 use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::globals::process::{EnvAccess, ProcessOptions};
+use runtime::inspector::InspectorOptions;
+use runtime::security::WritePermission;
 
 use crate::{Cli, Command};
+use crate::commands::compile::CompileOptions;
 
 mod cache;
+pub(crate) mod compile;
 mod eval;
+mod heap_census;
 mod repl;
 mod run;
 
@@ -26,12 +32,52 @@ pub(crate) async fn handle_command(cli: Cli) {
 			}
 		}
 
-		Some(Command::Eval { source }) => {
-			CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
-			eval::eval_source(&source).await;
+		Some(Command::Compile {
+			path,
+			output,
+			script,
+			allow_env,
+			allow_net,
+		}) => {
+			CONFIG.set(Config::default().script(script)).unwrap();
+			let options = CompileOptions { output, script, allow_env, allow_net };
+			compile::compile(&path, options);
 		}
 
-		Some(Command::Run { path, log_level, debug, script }) => {
+		Some(Command::Eval { source, allow_env }) => {
+			CONFIG
+				.set(
+					Config::default()
+						.log_level(LogLevel::Debug)
+						.script(true)
+						.allow_env(allow_env)
+						.allow_net(true),
+				)
+				.unwrap();
+			eval::eval_source(&source, interactive_process(allow_env)).await;
+		}
+
+		Some(Command::HeapCensus { path, output, script }) => {
+			CONFIG.set(Config::default().script(script)).unwrap();
+			let argv = vec![path.clone()];
+			heap_census::heap_census(&path, output, ProcessOptions { argv, env: EnvAccess::None }).await;
+		}
+
+		Some(Command::Run {
+			path,
+			log_level,
+			debug,
+			script,
+			allow_env,
+			allow_net,
+			allow_write,
+			json_console,
+			kv_store,
+			inspect,
+			inspect_wait,
+			allow_env_vars,
+			args,
+		}) => {
 			let log_level = if debug {
 				LogLevel::Debug
 			} else {
@@ -45,13 +91,74 @@ pub(crate) async fn handle_command(cli: Cli) {
 				}
 			};
 
-			CONFIG.set(Config::default().log_level(log_level).script(script)).unwrap();
-			run::run(&path).await;
-		}
+			CONFIG
+				.set(
+					Config::default()
+						.log_level(log_level)
+						.script(script)
+						.allow_env(allow_env)
+						.allow_net(allow_net)
+						.console_json(json_console),
+				)
+				.unwrap();
+			let env = if allow_env {
+				EnvAccess::All
+			} else {
+				EnvAccess::Allow(allow_env_vars)
+			};
+			let write_permission = match allow_write {
+				None => WritePermission::None,
+				Some(paths) if paths.is_empty() => WritePermission::All,
+				Some(paths) => WritePermission::paths(paths.into_iter().filter(|path| !path.is_empty())),
+			};
+			let inspector = match (inspect_wait, inspect) {
+				(Some(address), _) => Some((address, true)),
+				(None, Some(address)) => Some((address, false)),
+				(None, None) => None,
+			};
+			let inspector = match inspector {
+				Some((address, wait)) => match address.parse() {
+					Ok(address) => Some(InspectorOptions { address, wait }),
+					Err(_) => {
+						eprintln!("Invalid inspector address: {}", address);
+						return;
+					}
+				},
+				None => None,
+			};
 
-		Some(Command::Repl) | None => {
-			CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
-			repl::start_repl().await;
+			let argv = [path.clone()].into_iter().chain(args).collect();
+			run::run(
+				&path,
+				ProcessOptions { argv, env },
+				write_permission,
+				kv_store,
+				inspector,
+			)
+			.await;
 		}
+
+		Some(Command::Repl { allow_env }) => start_repl(allow_env).await,
+		None => start_repl(false).await,
 	}
 }
+
+async fn start_repl(allow_env: bool) {
+	CONFIG
+		.set(
+			Config::default()
+				.log_level(LogLevel::Debug)
+				.script(true)
+				.allow_env(allow_env)
+				.allow_net(true),
+		)
+		.unwrap();
+	repl::start_repl(interactive_process(allow_env)).await;
+}
+
+/// Options of the `process` global for evaluating inline code and the REPL.
+/// Environment variables are only exposed if access to the environment is allowed.
+fn interactive_process(allow_env: bool) -> ProcessOptions {
+	let env = if allow_env { EnvAccess::All } else { EnvAccess::None };
+	ProcessOptions { argv: Vec::new(), env }
+}
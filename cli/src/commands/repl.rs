@@ -11,11 +11,12 @@ use rustyline::error::ReadlineError;
 use ion::Context;
 use modules::Modules;
 use runtime::RuntimeBuilder;
+use runtime::globals::process::ProcessOptions;
 
 use crate::evaluate::eval_inline;
 use crate::repl::{ReplHelper, rustyline_config};
 
-pub(crate) async fn start_repl() {
+pub(crate) async fn start_repl(process: ProcessOptions) {
 	let engine = JSEngine::init().unwrap();
 	let rt = Runtime::new(engine.handle());
 
@@ -24,6 +25,7 @@ pub(crate) async fn start_repl() {
 		.microtask_queue()
 		.macrotask_queue()
 		.standard_modules(Modules)
+		.process(process)
 		.build(cx);
 
 	let mut repl = match Editor::with_config(rustyline_config()) {
@@ -4,16 +4,36 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 use runtime::config::Config;
+use runtime::globals::process::ProcessOptions;
+use runtime::inspector::InspectorOptions;
+use runtime::kv::{KvStore, MemoryKvStore};
+use runtime::security::WritePermission;
 
 use crate::evaluate::{eval_module, eval_script};
+use crate::kv::FileKvStore;
+
+pub(crate) async fn run(
+	path: &str, process: ProcessOptions, write_permission: WritePermission, kv_store: Option<String>,
+	inspector: Option<InspectorOptions>,
+) {
+	let kv_store: Rc<dyn KvStore> = match kv_store {
+		Some(kv_path) => match FileKvStore::open(PathBuf::from(&kv_path)) {
+			Ok(store) => Rc::new(store),
+			Err(error) => {
+				eprintln!("Failed to open key-value store {}: {}", kv_path, error);
+				return;
+			}
+		},
+		None => Rc::new(MemoryKvStore::default()),
+	};
 
-pub(crate) async fn run(path: &str) {
 	if Config::global().script {
-		eval_script(Path::new(path)).await;
+		eval_script(Path::new(path), process, write_permission, kv_store, inspector).await;
 	} else {
-		eval_module(Path::new(path)).await;
+		eval_module(Path::new(path), process, write_permission, kv_store, inspector).await;
 	}
 }
@@ -8,6 +8,7 @@ use std::ffi::OsStr;
 use std::fs::read_to_string;
 use std::io::ErrorKind;
 use std::path::Path;
+use std::rc::Rc;
 
 use mozjs::rust::JSEngine;
 use mozjs::rust::Runtime as RustRuntime;
@@ -16,14 +17,19 @@ use sourcemap::SourceMap;
 use ion::Context;
 use ion::format::Config as FormatConfig;
 use ion::format::format_value;
-use ion::module::Module;
+use ion::module::{Module, ModuleLoader};
 use ion::script::Script;
 use modules::Modules;
 use runtime::{Runtime, RuntimeBuilder};
 use runtime::cache::locate_in_cache;
 use runtime::cache::map::{save_sourcemap, transform_error_report_with_sourcemaps};
 use runtime::config::Config;
-use runtime::module::Loader;
+use runtime::globals::process::ProcessOptions;
+use runtime::heap_census::write_heap_census;
+use runtime::inspector::InspectorOptions;
+use runtime::kv::KvStore;
+use runtime::module::{Bundle, Loader, StandardModules};
+use runtime::security::WritePermission;
 
 pub(crate) async fn eval_inline(rt: &Runtime<'_>, source: &str) {
 	let result = Script::compile_and_evaluate(rt.cx(), Path::new("inline.js"), source);
@@ -35,16 +41,23 @@ pub(crate) async fn eval_inline(rt: &Runtime<'_>, source: &str) {
 	run_event_loop(rt).await;
 }
 
-pub(crate) async fn eval_script(path: &Path) {
+pub(crate) async fn eval_script(
+	path: &Path, process: ProcessOptions, write_permission: WritePermission, kv_store: Rc<dyn KvStore>,
+	inspector: Option<InspectorOptions>,
+) {
 	let engine = JSEngine::init().unwrap();
 	let rt = RustRuntime::new(engine.handle());
 
 	let cx = &mut Context::from_runtime(&rt);
-	let rt = RuntimeBuilder::<(), _>::new()
+	let builder = RuntimeBuilder::<(), _>::new()
 		.microtask_queue()
 		.macrotask_queue()
+		.console_input()
 		.standard_modules(Modules)
-		.build(cx);
+		.process(process)
+		.write_permission(write_permission)
+		.kv_store(kv_store);
+	let rt = with_inspector(builder, inspector).build(cx);
 
 	if let Some((script, _)) = read_script(path) {
 		let (script, sourcemap) = cache(path, script);
@@ -64,17 +77,24 @@ pub(crate) async fn eval_script(path: &Path) {
 	}
 }
 
-pub(crate) async fn eval_module(path: &Path) {
+pub(crate) async fn eval_module(
+	path: &Path, process: ProcessOptions, write_permission: WritePermission, kv_store: Rc<dyn KvStore>,
+	inspector: Option<InspectorOptions>,
+) {
 	let engine = JSEngine::init().unwrap();
 	let rt = RustRuntime::new(engine.handle());
 
 	let cx = &mut Context::from_runtime(&rt);
-	let rt = RuntimeBuilder::new()
+	let builder = RuntimeBuilder::new()
 		.microtask_queue()
 		.macrotask_queue()
+		.console_input()
 		.modules(Loader::default())
 		.standard_modules(Modules)
-		.build(cx);
+		.process(process)
+		.write_permission(write_permission)
+		.kv_store(kv_store);
+	let rt = with_inspector(builder, inspector).build(cx);
 
 	if let Some((script, filename)) = read_script(path) {
 		let (script, sourcemap) = cache(path, script);
@@ -91,6 +111,89 @@ pub(crate) async fn eval_module(path: &Path) {
 	}
 }
 
+/// Evaluates the entry of a [Bundle] embedded into a compiled executable, whose imports are read from the bundle.
+pub(crate) async fn eval_bundle(bundle: Rc<Bundle>, process: ProcessOptions) {
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.console_input()
+		.modules(Loader::default().bundle(Rc::clone(&bundle)))
+		.standard_modules(Modules)
+		.process(process)
+		.build(cx);
+
+	let path = bundle.entry.as_path();
+	let Some(script) = bundle.entry_source() else {
+		eprintln!("Embedded bundle does not contain its entry: {}", path.display());
+		return;
+	};
+	let (script, _) = cache(path, String::from(script));
+
+	if bundle.script {
+		if let Err(report) = Script::compile_and_evaluate(rt.cx(), path, &script) {
+			eprintln!("{}", report.format(rt.cx()));
+		}
+	} else {
+		let filename = path.file_name().unwrap().to_string_lossy();
+		if let Err(error) = Module::compile_and_evaluate(rt.cx(), &filename, Some(path), &script) {
+			eprintln!("{}", error.format(rt.cx()));
+		}
+	}
+	run_event_loop(&rt).await;
+}
+
+/// Runs a script or module until its event loop completes, and writes a census of its heap to `output`.
+pub(crate) async fn eval_heap_census(path: &Path, process: ProcessOptions, output: &Path) {
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.modules(Loader::default())
+		.standard_modules(Modules)
+		.process(process)
+		.build(cx);
+
+	let Some((script, filename)) = read_script(path) else {
+		return;
+	};
+	let (script, sourcemap) = cache(path, script);
+	if let Some(sourcemap) = sourcemap {
+		save_sourcemap(path, sourcemap);
+	}
+
+	if Config::global().script {
+		if let Err(mut report) = Script::compile_and_evaluate(rt.cx(), path, &script) {
+			transform_error_report_with_sourcemaps(&mut report);
+			eprintln!("{}", report.format(rt.cx()));
+		}
+	} else if let Err(mut error) = Module::compile_and_evaluate(rt.cx(), &filename, Some(path), &script) {
+		transform_error_report_with_sourcemaps(&mut error.report);
+		eprintln!("{}", error.format(rt.cx()));
+	}
+	run_event_loop(&rt).await;
+
+	match write_heap_census(rt.cx(), output) {
+		Ok(()) => println!("Wrote heap census to {}", output.display()),
+		Err(error) => eprintln!("Failed to write heap census to {}: {}", output.display(), error),
+	}
+}
+
+fn with_inspector<ML: ModuleLoader + 'static, Std: StandardModules + 'static>(
+	builder: RuntimeBuilder<ML, Std>, inspector: Option<InspectorOptions>,
+) -> RuntimeBuilder<ML, Std> {
+	match inspector {
+		Some(options) => builder.inspector(options),
+		None => builder,
+	}
+}
+
 fn read_script(path: &Path) -> Option<(String, String)> {
 	match read_to_string(path) {
 		Ok(script) => {
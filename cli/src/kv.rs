@@ -0,0 +1,99 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+
+use runtime::kv::{KvStore, MemoryKvStore};
+
+/// [KvStore] which keeps its values in memory, and writes all of them to a file after every change.
+///
+/// Each entry is stored as the length of its key and the key, followed by the length of its value and the value,
+/// where lengths are little-endian 32-bit integers.
+pub(crate) struct FileKvStore {
+	path: PathBuf,
+	values: MemoryKvStore,
+}
+
+impl FileKvStore {
+	/// Opens the store at the path, which is created when a value is first stored if it does not exist.
+	pub(crate) fn open(path: PathBuf) -> io::Result<FileKvStore> {
+		let values = MemoryKvStore::default();
+		match fs::read(&path) {
+			Ok(contents) => {
+				let mut contents = &contents[..];
+				while !contents.is_empty() {
+					let key = read_chunk(&mut contents)?;
+					let key = String::from_utf8(key).map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+					let value = read_chunk(&mut contents)?;
+					values.put(&key, value)?;
+				}
+			}
+			Err(error) if error.kind() == ErrorKind::NotFound => {}
+			Err(error) => return Err(error),
+		}
+		Ok(FileKvStore { path, values })
+	}
+
+	fn persist(&self) -> io::Result<()> {
+		let mut contents = Vec::new();
+		for key in self.values.keys()? {
+			let value = self.values.get(&key)?.unwrap_or_default();
+			write_chunk(&mut contents, key.as_bytes());
+			write_chunk(&mut contents, &value);
+		}
+
+		let temporary = self.path.with_extension("tmp");
+		fs::write(&temporary, contents)?;
+		fs::rename(temporary, &self.path)
+	}
+}
+
+impl KvStore for FileKvStore {
+	fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+		self.values.get(key)
+	}
+
+	fn put(&self, key: &str, value: Vec<u8>) -> io::Result<()> {
+		self.values.put(key, value)?;
+		self.persist()
+	}
+
+	fn delete(&self, key: &str) -> io::Result<bool> {
+		let deleted = self.values.delete(key)?;
+		if deleted {
+			self.persist()?;
+		}
+		Ok(deleted)
+	}
+
+	fn keys(&self) -> io::Result<Vec<String>> {
+		self.values.keys()
+	}
+
+	fn clear(&self) -> io::Result<()> {
+		self.values.clear()?;
+		self.persist()
+	}
+}
+
+fn read_chunk(contents: &mut &[u8]) -> io::Result<Vec<u8>> {
+	let invalid = || io::Error::new(ErrorKind::InvalidData, "Truncated key-value store");
+	let (length, rest) = contents.split_first_chunk::<4>().ok_or_else(invalid)?;
+	let length = u32::from_le_bytes(*length) as usize;
+	if rest.len() < length {
+		return Err(invalid());
+	}
+	let (chunk, rest) = rest.split_at(length);
+	*contents = rest;
+	Ok(chunk.to_vec())
+}
+
+fn write_chunk(contents: &mut Vec<u8>, chunk: &[u8]) {
+	contents.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+	contents.extend_from_slice(chunk);
+}
@@ -7,10 +7,11 @@
 use clap::{Parser, Subcommand};
 use tokio::task::LocalSet;
 
-use commands::handle_command;
+use commands::{compile, handle_command};
 
 mod commands;
 mod evaluate;
+mod kv;
 mod repl;
 
 #[derive(Parser)]
@@ -28,14 +29,69 @@ pub(crate) enum Command {
 		clear: bool,
 	},
 
+	#[command(about = "Compiles a JavaScript file and its imports into a standalone executable")]
+	Compile {
+		#[arg(
+			help = "The JavaScript file to compile, Default: 'main.js'",
+			required(false),
+			default_value = "main.js"
+		)]
+		path: String,
+
+		#[arg(
+			help = "Path of the executable, Default: the file name without its extension",
+			short,
+			long
+		)]
+		output: Option<String>,
+
+		#[arg(help = "Disables ES Modules Features", short, long)]
+		script: bool,
+
+		#[arg(
+			help = "Allows the executable to access system information through the os module",
+			long
+		)]
+		allow_env: bool,
+
+		#[arg(help = "Allows the executable to access the network through the net module", long)]
+		allow_net: bool,
+	},
+
 	#[command(about = "Evaluates a line of JavaScript")]
 	Eval {
 		#[arg(help = "Line of JavaScript to be evaluated", required(true))]
 		source: String,
+
+		#[arg(help = "Allows access to system information and environment variables", long)]
+		allow_env: bool,
+	},
+
+	#[command(about = "Runs a JavaScript file, and writes a census of its heap once it completes")]
+	HeapCensus {
+		#[arg(
+			help = "The JavaScript file to run, Default: 'main.js'",
+			required(false),
+			default_value = "main.js"
+		)]
+		path: String,
+
+		#[arg(
+			help = "Path of the heap census, Default: the file name with the extension '.census.json'",
+			short,
+			long
+		)]
+		output: Option<String>,
+
+		#[arg(help = "Disables ES Modules Features", short, long)]
+		script: bool,
 	},
 
 	#[command(about = "Starts a JavaScript Shell")]
-	Repl,
+	Repl {
+		#[arg(help = "Allows access to system information and environment variables", long)]
+		allow_env: bool,
+	},
 
 	#[command(about = "Runs a JavaScript file")]
 	Run {
@@ -60,18 +116,83 @@ pub(crate) enum Command {
 
 		#[arg(help = "Disables ES Modules Features", short, long)]
 		script: bool,
+
+		#[arg(help = "Allows access to system information through the os module", long)]
+		allow_env: bool,
+
+		#[arg(help = "Allows network access through the net module", long)]
+		allow_net: bool,
+
+		#[arg(
+			help = "Allows writing files, or only files within the given comma-separated paths",
+			long,
+			value_name = "PATHS",
+			num_args = 0..=1,
+			require_equals = true,
+			value_delimiter = ','
+		)]
+		allow_write: Option<Vec<String>>,
+
+		#[arg(help = "Emits console messages as lines of JSON", long)]
+		json_console: bool,
+
+		#[arg(
+			help = "Persists localStorage and the kv module to a file",
+			long,
+			value_name = "PATH"
+		)]
+		kv_store: Option<String>,
+
+		#[arg(
+			help = "Starts a debugger server for Chrome DevTools, Default: 127.0.0.1:9229",
+			long,
+			value_name = "ADDRESS",
+			num_args = 0..=1,
+			require_equals = true,
+			default_missing_value = "127.0.0.1:9229"
+		)]
+		inspect: Option<String>,
+
+		#[arg(
+			help = "Starts a debugger server and waits for a client to connect before running the script",
+			long,
+			value_name = "ADDRESS",
+			num_args = 0..=1,
+			require_equals = true,
+			default_missing_value = "127.0.0.1:9229"
+		)]
+		inspect_wait: Option<String>,
+
+		#[arg(
+			help = "Exposes an environment variable through process.env",
+			long = "allow-env-var",
+			value_name = "NAME"
+		)]
+		allow_env_vars: Vec<String>,
+
+		#[arg(
+			help = "Arguments passed to the script through process.argv",
+			trailing_var_arg = true,
+			allow_hyphen_values = true
+		)]
+		args: Vec<String>,
 	},
 }
 
 #[tokio::main(flavor = "current_thread")]
 pub async fn main() {
-	let cli = Cli::parse();
-
 	#[cfg(windows)]
 	{
 		colored::control::set_virtual_terminal(true).unwrap();
 	}
 
 	let local = LocalSet::new();
+	// Executables produced by `spiderfire compile` run their embedded script instead of parsing commands.
+	if let Some(bundle) = compile::embedded_bundle() {
+		local.run_until(compile::run_embedded(bundle)).await;
+		return;
+	}
+
+	let cli = Cli::parse();
 	local.run_until(handle_command(cli)).await;
 }
@@ -36,7 +36,7 @@ pub(crate) struct MethodAttribute {
 impl ParseAttribute for MethodAttribute {
 	fn parse(&mut self, meta: &ParseNestedMeta) -> Result<()> {
 		const METHOD_KIND_ERROR: ArgumentError =
-			ArgumentError::Full("Method cannot have multiple `constructor`, `get`, or `set` attributes.");
+			ArgumentError::Full("Method cannot have multiple `constructor`, `get`, `set`, or `inspect` attributes.");
 
 		self.name.parse_argument(meta, "name", "Method")?;
 		self.alias.parse_argument(meta, "alias", None)?;
@@ -44,6 +44,7 @@ impl ParseAttribute for MethodAttribute {
 			.parse_argument_with(meta, MethodKind::Constructor, "constructor", METHOD_KIND_ERROR)?;
 		self.kind.parse_argument_with(meta, MethodKind::Getter, "get", METHOD_KIND_ERROR)?;
 		self.kind.parse_argument_with(meta, MethodKind::Setter, "set", METHOD_KIND_ERROR)?;
+		self.kind.parse_argument_with(meta, MethodKind::Inspect, "inspect", METHOD_KIND_ERROR)?;
 		self.post_construct.parse_argument(meta, "post_construct", None)?;
 		self.skip.parse_argument(meta, "skip", "Method")?;
 
@@ -49,6 +49,7 @@ impl Parse for DefaultValue {
 pub(crate) struct DataAttribute {
 	pub(crate) tag: Optional<Tag>,
 	pub(crate) inherit: bool,
+	pub(crate) validate: Option<Box<Expr>>,
 }
 
 impl ParseAttribute for DataAttribute {
@@ -59,6 +60,7 @@ impl ParseAttribute for DataAttribute {
 			.parse_argument_with(meta, Tag::Untagged, "untagged", ArgumentError::Full(TAG_ERROR))?;
 		self.tag.parse_argument(meta, "tag", ArgumentError::Full(TAG_ERROR))?;
 		self.inherit.parse_argument(meta, "inherit", "Data")?;
+		self.validate.parse_argument(meta, "validate", "Data")?;
 
 		Ok(())
 	}
@@ -40,6 +40,7 @@ pub(super) fn impl_js_class_impl(r#impl: &mut ItemImpl) -> Result<[ItemImpl; 2]>
 
 	let r#type = *r#impl.self_ty.clone();
 	let mut constructor: Option<Method> = None;
+	let mut inspect: Option<Ident> = None;
 	let mut specs = PrototypeSpecs::default();
 
 	for item in &mut r#impl.items {
@@ -54,7 +55,7 @@ pub(super) fn impl_js_class_impl(r#impl: &mut ItemImpl) -> Result<[ItemImpl; 2]>
 				}
 			}
 			ImplItem::Fn(r#fn) => {
-				if let Some(parsed_constructor) = parse_class_method(ion, r#fn, &mut specs, &r#type)? {
+				if let Some(parsed_constructor) = parse_class_method(ion, r#fn, &mut specs, &mut inspect, &r#type)? {
 					if let Some(constructor) = constructor.as_ref() {
 						return Err(Error::new(
 							r#fn.span(),
@@ -90,11 +91,11 @@ pub(super) fn impl_js_class_impl(r#impl: &mut ItemImpl) -> Result<[ItemImpl; 2]>
 	};
 
 	let ident: Ident = parse2(quote_spanned!(r#type.span() => #r#type))?;
-	class_definition(ion, r#impl.span(), &r#type, &ident, constructor, specs)
+	class_definition(ion, r#impl.span(), &r#type, &ident, constructor, inspect, specs)
 }
 
 fn parse_class_method(
-	ion: &TokenStream, r#fn: &mut ImplItemFn, specs: &mut PrototypeSpecs, r#type: &Type,
+	ion: &TokenStream, r#fn: &mut ImplItemFn, specs: &mut PrototypeSpecs, inspect: &mut Option<Ident>, r#type: &Type,
 ) -> Result<Option<Method>> {
 	match &r#fn.vis {
 		Visibility::Public(_) => (),
@@ -111,6 +112,19 @@ fn parse_class_method(
 	if skip {
 		return Ok(None);
 	}
+	if kind == Some(MethodKind::Inspect) {
+		if let Some(inspect) = inspect {
+			return Err(Error::new(
+				r#fn.span(),
+				format!(
+					"Received multiple inspect implementations: {} and {}.",
+					inspect, r#fn.sig.ident
+				),
+			));
+		}
+		*inspect = Some(r#fn.sig.ident.clone());
+		return Ok(None);
+	}
 
 	let name = name.unwrap_or_else(|| {
 		if kind == Some(MethodKind::Getter) || kind == Some(MethodKind::Setter) {
@@ -164,6 +178,7 @@ fn parse_class_method(
 				insert_accessor(&mut specs.accessors.1, name.as_string(), None, Some(setter));
 			}
 		}
+		Some(MethodKind::Inspect) => unreachable!(),
 		None => {
 			let (method, _) = impl_method(ion, method, r#type, |_| Ok(()))?;
 			let method = Method { names, ..method };
@@ -180,9 +195,15 @@ fn parse_class_method(
 }
 
 fn class_definition(
-	ion: &TokenStream, span: Span, r#type: &Type, ident: &Ident, constructor: Method, specs: PrototypeSpecs,
+	ion: &TokenStream, span: Span, r#type: &Type, ident: &Ident, constructor: Method, inspect: Option<Ident>,
+	specs: PrototypeSpecs,
 ) -> Result<[ItemImpl; 2]> {
 	let (spec_fns, def_fns) = specs.to_impl_fns(ion, span, ident)?;
+	let inspect = inspect.map(|inspect| {
+		quote_spanned!(span => fn inspect<'cx>(&self, cx: &'cx #ion::Context) -> ::core::option::Option<#ion::Object<'cx>> {
+			::core::option::Option::Some(Self::#inspect(self, cx))
+		})
+	});
 	let constructor_function = constructor.method;
 	let functions = specs.into_functions().into_iter().map(|method| method.method);
 
@@ -212,6 +233,8 @@ fn class_definition(
 			Self::__ion_bindings_parent_prototype(cx)
 		}
 
+		#inspect
+
 		#(#def_fns)*
 	}))?;
 
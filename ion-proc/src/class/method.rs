@@ -18,6 +18,7 @@ pub(crate) enum MethodKind {
 	Constructor,
 	Getter,
 	Setter,
+	Inspect,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
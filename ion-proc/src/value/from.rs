@@ -34,7 +34,7 @@ pub(crate) fn impl_from_value(mut input: DeriveInput) -> Result<ItemImpl> {
 	}
 
 	let attribute = DataAttribute::from_attributes("ion", &input.attrs)?;
-	let DataAttribute { tag, inherit } = attribute;
+	let DataAttribute { tag, inherit, validate } = attribute;
 
 	let mut repr = None;
 	for attr in &input.attrs {
@@ -78,6 +78,17 @@ pub(crate) fn impl_from_value(mut input: DeriveInput) -> Result<ItemImpl> {
 		None
 	};
 
+	// The validation function is called with the converted value, and its error is returned instead of the value.
+	let body = if let Some(validate) = validate {
+		quote_spanned!(validate.span() => {
+			let __value: Self = (|| -> #ion::Result<Self> #body)()?;
+			(#validate)(&__value)?;
+			::std::result::Result::Ok(__value)
+		})
+	} else {
+		quote!(#body)
+	};
+
 	parse2(quote_spanned!(input.span() =>
 		#[automatically_derived]
 		impl #impl_generics #ion::conversions::FromValue<'cx> for #name #ty_generics #where_clause {
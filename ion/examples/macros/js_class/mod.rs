@@ -1 +1,2 @@
+pub mod shape;
 pub mod toggle;
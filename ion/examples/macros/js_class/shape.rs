@@ -0,0 +1,27 @@
+use ion::{Arguments, Function, js_class};
+use ion::class::Reflector;
+
+#[js_class]
+pub struct Shape {
+	reflector: Reflector,
+	kind: String,
+}
+
+#[js_class]
+impl Shape {
+	#[ion(constructor)]
+	pub fn constructor(args: &mut Arguments) -> Shape {
+		let cx = args.cx();
+		let kind = args
+			.new_target()
+			.and_then(|new_target| Function::from_object(cx, &new_target))
+			.and_then(|new_target| new_target.name(cx))
+			.unwrap_or_else(|| String::from("Shape"));
+		Shape { reflector: Reflector::default(), kind }
+	}
+
+	#[ion(get)]
+	pub fn get_kind(&self) -> String {
+		self.kind.clone()
+	}
+}
@@ -0,0 +1,17 @@
+use ion::{BigInt, Context, js_fn};
+
+#[js_fn]
+pub fn bigint(_bigint: BigInt) {}
+
+#[js_fn]
+pub fn bigint_integer(cx: &Context, id: u128) -> BigInt {
+	BigInt::from_u128(cx, id)
+}
+
+#[js_fn]
+pub fn bigint_optional(id: Option<i128>) -> i128 {
+	id.unwrap_or_default()
+}
+
+#[js_fn]
+pub fn bigint_vec(_bigints: Vec<num_bigint::BigInt>) {}
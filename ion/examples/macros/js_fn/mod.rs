@@ -1,3 +1,4 @@
+pub mod bigint;
 pub mod complex;
 pub mod context;
 pub mod integer;
@@ -4,6 +4,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::cmp::Ordering;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
@@ -13,6 +14,7 @@ use mozjs::jsapi::{
 };
 use mozjs::jsapi::BigInt as JSBigInt;
 use mozjs::jsapi::mozilla::{Range, RangedPtr};
+use num_bigint::Sign;
 
 use crate::{Context, Exception, Local, String};
 
@@ -36,6 +38,27 @@ impl<'b> BigInt<'b> {
 		BigInt::from(cx.root(unsafe { BigIntFromUint64(cx.as_ptr(), number) }))
 	}
 
+	/// Creates a [BigInt] from a 128-bit signed integer.
+	pub fn from_i128(cx: &Context, number: i128) -> BigInt {
+		match i64::try_from(number) {
+			Ok(number) => BigInt::from_i64(cx, number),
+			Err(_) => BigInt::from_string(cx, &number.to_string()).unwrap(),
+		}
+	}
+
+	/// Creates a [BigInt] from a 128-bit unsigned integer.
+	pub fn from_u128(cx: &Context, number: u128) -> BigInt {
+		match u64::try_from(number) {
+			Ok(number) => BigInt::from_u64(cx, number),
+			Err(_) => BigInt::from_string(cx, &number.to_string()).unwrap(),
+		}
+	}
+
+	/// Creates a [BigInt] from an arbitrary-precision [num_bigint::BigInt].
+	pub fn from_num(cx: &'b Context, number: &num_bigint::BigInt) -> BigInt<'b> {
+		BigInt::from_string(cx, &number.to_string()).unwrap()
+	}
+
 	/// Creates a [BigInt] from a double.
 	/// Returns an error if `number` is `NaN`, `Infinity`, `-Infinity` or contains a fractional component.
 	pub fn from_f64(cx: &Context, number: f64) -> Result<BigInt, Exception> {
@@ -90,6 +113,31 @@ impl<'b> BigInt<'b> {
 		unsafe { BigIntIsUint64(self.get(), &mut result).then_some(result) }
 	}
 
+	/// Converts a [BigInt] to a 128-bit signed integer if possible.
+	pub fn to_i128(&self, cx: &Context) -> Option<i128> {
+		match self.to_i64() {
+			Some(number) => Some(i128::from(number)),
+			None => i128::from_str_radix(&self.to_hex(cx), 16).ok(),
+		}
+	}
+
+	/// Converts a [BigInt] to a 128-bit unsigned integer if possible.
+	pub fn to_u128(&self, cx: &Context) -> Option<u128> {
+		match self.to_u64() {
+			Some(number) => Some(u128::from(number)),
+			None => u128::from_str_radix(&self.to_hex(cx), 16).ok(),
+		}
+	}
+
+	/// Converts a [BigInt] to an arbitrary-precision [num_bigint::BigInt].
+	pub fn to_num(&self, cx: &Context) -> num_bigint::BigInt {
+		num_bigint::BigInt::parse_bytes(self.to_hex(cx).as_bytes(), 16).unwrap()
+	}
+
+	fn to_hex(&self, cx: &Context) -> std::string::String {
+		self.to_string(cx, 16).unwrap().to_owned(cx).unwrap()
+	}
+
 	/// Converts a [BigInt] to a double.
 	/// Returns `Infinity` or `-Infinity` if it does not fit in a double.
 	pub fn to_f64(&self) -> f64 {
@@ -117,6 +165,53 @@ impl<'b> BigInt<'b> {
 	pub fn is_negative(&self) -> bool {
 		unsafe { BigIntIsNegative(self.get()) }
 	}
+
+	/// Compares the values of two [BigInts](BigInt).
+	pub fn compare(&self, cx: &Context, other: &BigInt) -> Ordering {
+		match (self.to_i64(), other.to_i64()) {
+			(Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+			_ => self.to_num(cx).cmp(&other.to_num(cx)),
+		}
+	}
+
+	/// Returns the sum of two [BigInts](BigInt).
+	pub fn add<'cx>(&self, cx: &'cx Context, other: &BigInt) -> BigInt<'cx> {
+		BigInt::from_num(cx, &(self.to_num(cx) + other.to_num(cx)))
+	}
+
+	/// Returns the difference of two [BigInts](BigInt).
+	pub fn sub<'cx>(&self, cx: &'cx Context, other: &BigInt) -> BigInt<'cx> {
+		BigInt::from_num(cx, &(self.to_num(cx) - other.to_num(cx)))
+	}
+
+	/// Returns the product of two [BigInts](BigInt).
+	pub fn mul<'cx>(&self, cx: &'cx Context, other: &BigInt) -> BigInt<'cx> {
+		BigInt::from_num(cx, &(self.to_num(cx) * other.to_num(cx)))
+	}
+
+	/// Returns the quotient of two [BigInts](BigInt), truncated towards zero.
+	/// Returns `None` if `other` is zero.
+	pub fn div<'cx>(&self, cx: &'cx Context, other: &BigInt) -> Option<BigInt<'cx>> {
+		let other = other.to_num(cx);
+		(other.sign() != Sign::NoSign).then(|| BigInt::from_num(cx, &(self.to_num(cx) / other)))
+	}
+
+	/// Returns the remainder of two [BigInts](BigInt), which has the sign of `self`.
+	/// Returns `None` if `other` is zero.
+	pub fn rem<'cx>(&self, cx: &'cx Context, other: &BigInt) -> Option<BigInt<'cx>> {
+		let other = other.to_num(cx);
+		(other.sign() != Sign::NoSign).then(|| BigInt::from_num(cx, &(self.to_num(cx) % other)))
+	}
+
+	/// Returns the [BigInt] raised to the power of `exponent`.
+	pub fn pow<'cx>(&self, cx: &'cx Context, exponent: u32) -> BigInt<'cx> {
+		BigInt::from_num(cx, &self.to_num(cx).pow(exponent))
+	}
+
+	/// Returns the negation of the [BigInt].
+	pub fn neg<'cx>(&self, cx: &'cx Context) -> BigInt<'cx> {
+		BigInt::from_num(cx, &-self.to_num(cx))
+	}
 }
 
 impl<'b> From<Local<'b, *mut JSBigInt>> for BigInt<'b> {
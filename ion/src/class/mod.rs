@@ -12,6 +12,7 @@ use std::ptr;
 use mozjs::gc::HandleObject;
 use mozjs::gc::Traceable;
 use mozjs::glue::JS_GetReservedSlot;
+use mozjs::rust::get_object_class;
 use mozjs::jsapi::{
 	GCContext, Handle, JS_GetConstructor, JS_InitClass, JS_InstanceOf, JS_HasInstance, JS_NewObjectWithGivenProto,
 	JS_SetReservedSlot, JSFunction, JSFunctionSpec, JSObject, JSPropertySpec, Construct, Construct1, HandleValueArray,
@@ -29,6 +30,9 @@ use crate::function::NativeFunction;
 mod native;
 mod reflect;
 
+/// Function which returns the inspection data of a native object, from [ClassDefinition::inspect].
+pub type InspectFn = for<'cx> fn(&'cx Context, &Object<'cx>) -> Option<Object<'cx>>;
+
 /// Stores information about a native class created for JS.
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -36,6 +40,7 @@ pub struct ClassInfo {
 	pub class: &'static NativeClass,
 	pub constructor: *mut JSFunction,
 	pub prototype: *mut JSObject,
+	pub inspect: InspectFn,
 }
 
 fn class_info<'cx>(cx: &'cx Context, type_id: &TypeId) -> &'cx ClassInfo {
@@ -56,6 +61,12 @@ pub trait ClassDefinition: NativeObject {
 
 	fn constructor() -> (NativeFunction, u32);
 
+	/// Returns an object describing the native state of the object, such as the method and URL of a request, which is
+	/// displayed when the object is formatted. Implemented with an `#[ion(inspect)]` method in the class impl.
+	fn inspect<'cx>(&self, _: &'cx Context) -> Option<Object<'cx>> {
+		None
+	}
+
 	fn functions() -> Option<&'static [JSFunctionSpec]> {
 		None
 	}
@@ -118,6 +129,7 @@ pub trait ClassDefinition: NativeObject {
 					class: Self::class(),
 					constructor: constructor.get(),
 					prototype: prototype.get(),
+					inspect: inspect_native::<Self>,
 				};
 
 				(true, entry.insert(class_info))
@@ -271,6 +283,18 @@ pub trait ClassDefinition: NativeObject {
 	}
 }
 
+fn inspect_native<'cx, T: ClassDefinition>(cx: &'cx Context, object: &Object<'cx>) -> Option<Object<'cx>> {
+	T::get_private(cx, object).ok()?.inspect(cx)
+}
+
+/// Returns the inspection data of a native object, if its class implements [ClassDefinition::inspect].
+pub fn inspect_object<'cx>(cx: &'cx Context, object: &Object<'cx>) -> Option<Object<'cx>> {
+	let class = unsafe { get_object_class(object.handle().get()) };
+	let infos = unsafe { &(*cx.get_inner_data().as_ptr()).class_infos };
+	let info = infos.values().find(|info| ptr::eq(&info.class.base, class))?;
+	(info.inspect)(cx, object)
+}
+
 trait SpecZero {
 	fn is_zeroed(&self) -> bool;
 }
@@ -0,0 +1,150 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ptr;
+
+use mozjs::glue::{
+	CopyJSStructuredCloneData, DeleteJSAutoStructuredCloneBuffer, GetLengthOfJSStructuredCloneData,
+	NewJSAutoStructuredCloneBuffer, WriteBytesToJSStructuredCloneData,
+};
+use mozjs::jsapi::{
+	CloneDataPolicy, JS_ReadStructuredClone, JS_STRUCTURED_CLONE_VERSION, JS_WriteStructuredClone, StructuredCloneScope,
+};
+
+use crate::{Array, Context, Error, ErrorKind, Exception, Object, ResultExc, Value};
+use crate::conversions::ToValue;
+
+/// Clones a value with the [structured clone algorithm](https://html.spec.whatwg.org/multipage/structured-data.html#structured-cloning).
+///
+/// Objects in `transfer`, such as [ArrayBuffers](crate::typedarray::ArrayBuffer), are transferred to the clone,
+/// which detaches the originals.
+/// Values which cannot be cloned, such as functions, result in a `DataCloneError`.
+pub fn structured_clone<'cx>(cx: &'cx Context, value: &Value, transfer: &[Object]) -> ResultExc<Value<'cx>> {
+	let transfer = if transfer.is_empty() {
+		Value::undefined(cx)
+	} else {
+		let objects: Vec<_> = transfer.iter().map(|object| object.as_value(cx).get()).collect();
+		Array::from_slice(cx, &objects).as_value(cx)
+	};
+
+	let policy = CLONE_POLICY;
+	let mut clone = Value::undefined(cx);
+
+	let success = unsafe {
+		let buffer = NewJSAutoStructuredCloneBuffer(StructuredCloneScope::SameProcess, ptr::null());
+		let data = &mut (*buffer).data_;
+
+		let success = JS_WriteStructuredClone(
+			cx.as_ptr(),
+			value.handle().into(),
+			data,
+			StructuredCloneScope::SameProcess,
+			&policy,
+			ptr::null(),
+			ptr::null_mut(),
+			transfer.handle().into(),
+		) && JS_ReadStructuredClone(
+			cx.as_ptr(),
+			data,
+			JS_STRUCTURED_CLONE_VERSION,
+			StructuredCloneScope::SameProcess,
+			clone.handle_mut().into(),
+			&policy,
+			ptr::null(),
+			ptr::null_mut(),
+		);
+
+		DeleteJSAutoStructuredCloneBuffer(buffer);
+		success
+	};
+
+	if success {
+		Ok(clone)
+	} else {
+		Err(clone_error(cx))
+	}
+}
+
+/// Serializes a value with the [structured clone algorithm](https://html.spec.whatwg.org/multipage/structured-data.html#structured-cloning),
+/// so that it can be stored and deserialized later with [structured_deserialize].
+///
+/// The serialized data does not refer to memory of the process, so shared memory cannot be serialized.
+pub fn structured_serialize(cx: &Context, value: &Value) -> ResultExc<Vec<u8>> {
+	let transfer = Value::undefined(cx);
+	let policy = CLONE_POLICY;
+
+	let data = unsafe {
+		let buffer = NewJSAutoStructuredCloneBuffer(StructuredCloneScope::DifferentProcess, ptr::null());
+		let data = &mut (*buffer).data_;
+
+		let bytes = JS_WriteStructuredClone(
+			cx.as_ptr(),
+			value.handle().into(),
+			data,
+			StructuredCloneScope::DifferentProcess,
+			&policy,
+			ptr::null(),
+			ptr::null_mut(),
+			transfer.handle().into(),
+		)
+		.then(|| {
+			let mut bytes = vec![0; GetLengthOfJSStructuredCloneData(data)];
+			CopyJSStructuredCloneData(data, bytes.as_mut_ptr());
+			bytes
+		});
+
+		DeleteJSAutoStructuredCloneBuffer(buffer);
+		bytes
+	};
+
+	data.ok_or_else(|| clone_error(cx))
+}
+
+/// Deserializes a value serialized with [structured_serialize].
+pub fn structured_deserialize<'cx>(cx: &'cx Context, bytes: &[u8]) -> ResultExc<Value<'cx>> {
+	let policy = CLONE_POLICY;
+	let mut value = Value::undefined(cx);
+
+	let success = unsafe {
+		let buffer = NewJSAutoStructuredCloneBuffer(StructuredCloneScope::DifferentProcess, ptr::null());
+		let data = &mut (*buffer).data_;
+
+		let success = WriteBytesToJSStructuredCloneData(bytes.as_ptr(), bytes.len(), data)
+			&& JS_ReadStructuredClone(
+				cx.as_ptr(),
+				data,
+				JS_STRUCTURED_CLONE_VERSION,
+				StructuredCloneScope::DifferentProcess,
+				value.handle_mut().into(),
+				&policy,
+				ptr::null(),
+				ptr::null_mut(),
+			);
+
+		DeleteJSAutoStructuredCloneBuffer(buffer);
+		success
+	};
+
+	if success {
+		Ok(value)
+	} else {
+		Err(clone_error(cx))
+	}
+}
+
+const CLONE_POLICY: CloneDataPolicy = CloneDataPolicy {
+	allowIntraClusterClonableSharedObjects_: false,
+	allowSharedMemoryObjects_: false,
+};
+
+fn clone_error(cx: &Context) -> Exception {
+	let message = match Exception::new(cx) {
+		Ok(Some(Exception::Error(error))) => error.message,
+		Ok(Some(Exception::Other(_)) | None) => "Value could not be cloned".into(),
+		Err(error) => return error.into(),
+	};
+	Error::new(format!("DataCloneError: {}", message), ErrorKind::Normal).into()
+}
@@ -4,6 +4,8 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
 use std::marker::PhantomData;
 
 use mozjs::conversions::{ConversionResult, FromJSValConvertible};
@@ -19,11 +21,12 @@ use mozjs::typedarray as jsta;
 use mozjs::typedarray::JSObjectStorage;
 
 use crate::{
-	Array, Context, Date, Error, ErrorKind, Exception, Function, Object, Promise, Result, StringRef, Symbol, Value,
+	Array, BigInt, Context, Date, Error, ErrorKind, Exception, Function, Map, Object, Promise, Result, Set, StringRef,
+	Symbol, Value,
 };
 use crate::object::RegExp;
 use crate::string::byte::{BytePredicate, ByteString};
-use crate::typedarray::{ArrayBuffer, TypedArray, TypedArrayElement};
+use crate::typedarray::{ArrayBuffer, DataView, TypedArray, TypedArrayElement};
 
 /// Represents types that can be converted to from [JavaScript Values](Value).
 pub trait FromValue<'cx>: Sized {
@@ -43,6 +46,18 @@ impl<'cx, T> FromValue<'cx> for PhantomData<T> {
 	}
 }
 
+impl<'cx> FromValue<'cx> for () {
+	type Config = ();
+
+	fn from_value(_: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<()> {
+		if strict && !value.handle().is_undefined() {
+			Err(Error::new("Expected Undefined in Strict Conversion", ErrorKind::Type))
+		} else {
+			Ok(())
+		}
+	}
+}
+
 impl<'cx> FromValue<'cx> for bool {
 	type Config = ();
 
@@ -116,6 +131,63 @@ impl<'cx> FromValue<'cx> for f64 {
 	}
 }
 
+impl<'cx> FromValue<'cx> for BigInt<'cx> {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<BigInt<'cx>> {
+		let handle = value.handle();
+		if handle.is_bigint() {
+			return Ok(BigInt::from(cx.root(handle.to_bigint())));
+		}
+		if strict {
+			return Err(Error::new("Expected BigInt in Strict Conversion", ErrorKind::Type));
+		}
+
+		if handle.is_boolean() {
+			Ok(BigInt::from_bool(cx, handle.to_boolean()))
+		} else if handle.is_number() {
+			BigInt::from_f64(cx, handle.to_number()).map_err(|exception| exception.to_error())
+		} else if handle.is_string() {
+			let string = String::from_value(cx, value, true, ())?;
+			BigInt::from_string(cx, string.trim()).map_err(|exception| match exception {
+				Some(exception) => exception.to_error(),
+				None => Error::new("Unable to Convert String to BigInt", ErrorKind::Syntax),
+			})
+		} else {
+			Err(Error::new("Unable to Convert Value to BigInt", ErrorKind::Type))
+		}
+	}
+}
+
+macro_rules! impl_from_value_for_bigint_integer {
+	($ty:ty, $to:ident) => {
+		impl<'cx> FromValue<'cx> for $ty {
+			type Config = ();
+
+			fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<$ty> {
+				let bigint = BigInt::from_value(cx, value, strict, ())?;
+				bigint.$to(cx).ok_or_else(|| {
+					Error::new(
+						concat!("BigInt is out of range for ", stringify!($ty)),
+						ErrorKind::Range,
+					)
+				})
+			}
+		}
+	};
+}
+
+impl_from_value_for_bigint_integer!(i128, to_i128);
+impl_from_value_for_bigint_integer!(u128, to_u128);
+
+impl<'cx> FromValue<'cx> for num_bigint::BigInt {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<num_bigint::BigInt> {
+		BigInt::from_value(cx, value, strict, ()).map(|bigint| bigint.to_num(cx))
+	}
+}
+
 impl<'cx> FromValue<'cx> for *mut JSString {
 	type Config = ();
 
@@ -162,7 +234,7 @@ impl<'cx, T: BytePredicate> FromValue<'cx> for ByteString<T> {
 				e
 			}
 		})?;
-		ByteString::from(bytes).ok_or_else(|| Error::new(INVALID_CHARACTERS, ErrorKind::Type))
+		ByteString::try_from_bytes(bytes)
 	}
 }
 
@@ -227,6 +299,46 @@ impl<'cx> FromValue<'cx> for Array<'cx> {
 	}
 }
 
+impl<'cx> FromValue<'cx> for Map<'cx> {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, _: bool, _: ()) -> Result<Map<'cx>> {
+		if !value.handle().is_object() {
+			return Err(Error::new("Expected Map", ErrorKind::Type));
+		}
+
+		let object = value.to_object(cx).into_local();
+		if let Some(map) = Map::from(cx, object) {
+			unsafe {
+				AssertSameCompartment(cx.as_ptr(), map.handle().get());
+			}
+			Ok(map)
+		} else {
+			Err(Error::new("Expected Map", ErrorKind::Type))
+		}
+	}
+}
+
+impl<'cx> FromValue<'cx> for Set<'cx> {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, _: bool, _: ()) -> Result<Set<'cx>> {
+		if !value.handle().is_object() {
+			return Err(Error::new("Expected Set", ErrorKind::Type));
+		}
+
+		let object = value.to_object(cx).into_local();
+		if let Some(set) = Set::from(cx, object) {
+			unsafe {
+				AssertSameCompartment(cx.as_ptr(), set.handle().get());
+			}
+			Ok(set)
+		} else {
+			Err(Error::new("Expected Set", ErrorKind::Type))
+		}
+	}
+}
+
 impl<'cx> FromValue<'cx> for Date<'cx> {
 	type Config = ();
 
@@ -342,6 +454,26 @@ impl<'cx, T: TypedArrayElement> FromValue<'cx> for TypedArray<'cx, T> {
 	}
 }
 
+impl<'cx> FromValue<'cx> for DataView<'cx> {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, _: bool, _: ()) -> Result<DataView<'cx>> {
+		if !value.handle().is_object() {
+			return Err(Error::new("Expected DataView", ErrorKind::Type));
+		}
+
+		let object = value.to_object(cx).into_local();
+		if let Some(view) = DataView::from(object) {
+			unsafe {
+				AssertSameCompartment(cx.as_ptr(), view.get());
+			}
+			Ok(view)
+		} else {
+			Err(Error::new("Expected DataView", ErrorKind::Type))
+		}
+	}
+}
+
 impl<'cx> FromValue<'cx> for *mut JSFunction {
 	type Config = ();
 
@@ -495,3 +627,39 @@ where
 		Ok(ret)
 	}
 }
+
+impl<'cx, K, V, S> FromValue<'cx> for HashMap<K, V, S>
+where
+	K: FromValue<'cx, Config = ()> + Eq + Hash,
+	V: FromValue<'cx>,
+	V::Config: Clone,
+	S: BuildHasher + Default,
+{
+	type Config = V::Config;
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, config: V::Config) -> Result<HashMap<K, V, S>> {
+		let map = Map::from_value(cx, value, strict, ())?;
+		map.to_vec(cx)?
+			.into_iter()
+			.map(|(key, value)| {
+				let key = K::from_value(cx, &key, strict, ())?;
+				let value = V::from_value(cx, &value, strict, config.clone())?;
+				Ok((key, value))
+			})
+			.collect()
+	}
+}
+
+impl<'cx, T, S> FromValue<'cx> for HashSet<T, S>
+where
+	T: FromValue<'cx> + Eq + Hash,
+	T::Config: Clone,
+	S: BuildHasher + Default,
+{
+	type Config = T::Config;
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, config: T::Config) -> Result<HashSet<T, S>> {
+		let set = Set::from_value(cx, value, strict, ())?;
+		set.to_vec(cx)?.iter().map(|key| T::from_value(cx, key, strict, config.clone())).collect()
+	}
+}
@@ -5,6 +5,7 @@
  */
 
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::ptr::NonNull;
 use std::rc::Rc;
 
@@ -12,16 +13,16 @@ use mozjs::jsapi::{JS_GetFunctionObject, JS_IdToValue, JS_NewStringCopyN, JS_Wra
 use mozjs::jsapi::PropertyKey as JSPropertyKey;
 use mozjs::jsapi::Symbol as JSSymbol;
 use mozjs::jsval::{
-	BooleanValue, DoubleValue, Int32Value, JSVal, NullValue, ObjectOrNullValue, ObjectValue, StringValue, SymbolValue,
-	UInt32Value, UndefinedValue,
+	BigIntValue, BooleanValue, DoubleValue, Int32Value, JSVal, NullValue, ObjectOrNullValue, ObjectValue, StringValue,
+	SymbolValue, UInt32Value, UndefinedValue,
 };
 use mozjs::rust::{maybe_wrap_object_or_null_value, maybe_wrap_object_value, maybe_wrap_value};
 use mozjs::typedarray as jsta;
 
-use crate::{Array, Context, Date, Function, Object, Promise, PropertyKey, Symbol, Value};
+use crate::{Array, BigInt, Context, Date, Function, Map, Object, Promise, PropertyKey, Set, Symbol, Value};
 use crate::object::RegExp;
 use crate::string::byte::{BytePredicate, ByteStr, ByteString};
-use crate::typedarray::{ArrayBuffer, TypedArray, TypedArrayElement};
+use crate::typedarray::{ArrayBuffer, DataView, TypedArray, TypedArrayElement};
 
 /// Represents types that can be converted to JavaScript [Values](Value).
 pub trait ToValue<'cx> {
@@ -88,6 +89,30 @@ impl_to_value_as_double!(u64);
 impl_to_value_as_double!(f32);
 impl_to_value_as_double!(f64);
 
+impl<'cx> ToValue<'cx> for BigInt<'cx> {
+	fn to_value(&self, _: &'cx Context, value: &mut Value) {
+		value.handle_mut().set(BigIntValue(unsafe { &*self.get() }));
+	}
+}
+
+impl ToValue<'_> for i128 {
+	fn to_value(&self, cx: &Context, value: &mut Value) {
+		BigInt::from_i128(cx, *self).to_value(cx, value);
+	}
+}
+
+impl ToValue<'_> for u128 {
+	fn to_value(&self, cx: &Context, value: &mut Value) {
+		BigInt::from_u128(cx, *self).to_value(cx, value);
+	}
+}
+
+impl ToValue<'_> for num_bigint::BigInt {
+	fn to_value(&self, cx: &Context, value: &mut Value) {
+		BigInt::from_num(cx, self).to_value(cx, value);
+	}
+}
+
 impl ToValue<'_> for *mut JSString {
 	fn to_value(&self, cx: &Context, value: &mut Value) {
 		value.handle_mut().set(StringValue(unsafe { &**self }));
@@ -168,6 +193,18 @@ impl<'cx> ToValue<'cx> for Array<'cx> {
 	}
 }
 
+impl<'cx> ToValue<'cx> for Map<'cx> {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		self.handle().to_value(cx, value)
+	}
+}
+
+impl<'cx> ToValue<'cx> for Set<'cx> {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		self.handle().to_value(cx, value)
+	}
+}
+
 impl<'cx> ToValue<'cx> for Date<'cx> {
 	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
 		self.handle().to_value(cx, value);
@@ -204,6 +241,12 @@ impl<'cx, T: TypedArrayElement> ToValue<'cx> for TypedArray<'cx, T> {
 	}
 }
 
+impl<'cx> ToValue<'cx> for DataView<'cx> {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		self.handle().to_value(cx, value)
+	}
+}
+
 impl<'cx> ToValue<'cx> for *mut JSFunction {
 	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
 		unsafe { JS_GetFunctionObject(*self) }.to_value(cx, value);
@@ -301,3 +344,27 @@ impl<'cx, T: ToValue<'cx>> ToValue<'cx> for Vec<T> {
 		(**self).to_value(cx, value);
 	}
 }
+
+impl<'cx, K: ToValue<'cx>, V: ToValue<'cx>, S> ToValue<'cx> for HashMap<K, V, S> {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let map = Map::new(cx);
+
+		for (k, v) in self {
+			assert!(map.set(cx, &k.as_value(cx), &v.as_value(cx)));
+		}
+
+		map.to_value(cx, value);
+	}
+}
+
+impl<'cx, T: ToValue<'cx>, S> ToValue<'cx> for HashSet<T, S> {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let set = Set::new(cx);
+
+		for t in self {
+			assert!(set.add(cx, &t.as_value(cx)));
+		}
+
+		set.to_value(cx, value);
+	}
+}
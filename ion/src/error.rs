@@ -9,7 +9,10 @@ use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
 use mozjs::error::{throw_internal_error, throw_range_error, throw_type_error};
-use mozjs::jsapi::{CreateError, JS_ReportErrorUTF8, JSExnType, JSObject, JSProtoKey, UndefinedHandleValue};
+use mozjs::jsapi::{
+	CreateError, ExceptionStackBehavior, JS_ReportErrorUTF8, JS_SetPendingException, JSExnType, JSObject, JSProtoKey,
+	UndefinedHandleValue,
+};
 
 use crate::{Context, ErrorReport, Exception, Object, Stack, Value};
 use crate::conversions::ToValue;
@@ -107,9 +110,12 @@ impl Display for ErrorKind {
 /// Contains information about the type of error, the error message and the error location.
 ///
 /// If created from an error object, it also contains the error object.
+///
+/// Errors can have a name which differs from their kind, such as the names of `DOMException`s.
 #[derive(Clone, Debug)]
 pub struct Error {
 	pub kind: ErrorKind,
+	pub name: Option<Cow<'static, str>>,
 	pub message: Cow<'static, str>,
 	pub location: Option<Location>,
 	pub object: Option<*mut JSObject>,
@@ -119,6 +125,7 @@ impl Error {
 	pub fn new<M: Into<Cow<'static, str>>, K: Into<Option<ErrorKind>>>(message: M, kind: K) -> Error {
 		Error {
 			kind: kind.into().unwrap_or(ErrorKind::Normal),
+			name: None,
 			message: message.into(),
 			location: None,
 			object: None,
@@ -128,12 +135,19 @@ impl Error {
 	pub fn none() -> Error {
 		Error {
 			kind: ErrorKind::None,
+			name: None,
 			message: Cow::Borrowed(""),
 			location: None,
 			object: None,
 		}
 	}
 
+	/// Sets the name of the error, which replaces the name of its kind.
+	pub fn with_name<N: Into<Cow<'static, str>>>(mut self, name: N) -> Error {
+		self.name = Some(name.into());
+		self
+	}
+
 	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Option<Object<'cx>> {
 		if let Some(object) = self.object {
 			return Some(cx.root(object).into());
@@ -176,7 +190,11 @@ impl Error {
 					UndefinedHandleValue,
 					error.handle_mut().into(),
 				) {
-					return Some(error.to_object(cx));
+					let error = error.to_object(cx);
+					if let Some(name) = &self.name {
+						error.set_as(cx, "name", &**name);
+					}
+					return Some(error);
 				}
 			}
 		}
@@ -184,7 +202,8 @@ impl Error {
 	}
 
 	pub fn format(&self) -> String {
-		let Error { kind, message, location, .. } = self;
+		let Error { kind, name, message, location, .. } = self;
+		let kind = name.as_deref().map(String::from).unwrap_or_else(|| kind.to_string());
 		let message = (!message.is_empty()).then(|| format!(" - {}", message)).unwrap_or(String::new());
 		if let Some(location) = location {
 			let Location { file, lineno, column } = location;
@@ -228,6 +247,16 @@ impl<E: error::Error> From<E> for Error {
 
 impl ThrowException for Error {
 	fn throw(&self, cx: &Context) {
+		if self.name.is_some() {
+			if let Some(error) = self.to_object(cx) {
+				let error = Value::object(cx, &error);
+				unsafe {
+					JS_SetPendingException(cx.as_ptr(), error.handle().into(), ExceptionStackBehavior::DoNotCapture)
+				}
+			}
+			return;
+		}
+
 		unsafe {
 			use ErrorKind as EK;
 			match self.kind {
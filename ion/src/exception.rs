@@ -6,17 +6,16 @@
 
 use mozjs::conversions::ConversionBehavior;
 use mozjs::jsapi::{
-	ESClass, ExceptionStack, ExceptionStackBehavior, ExceptionStackOrNull, GetPendingExceptionStack,
-	IdentifyStandardInstance, JS_ClearPendingException, JS_GetPendingException, JS_IsExceptionPending,
-	JS_SetPendingException, Rooted,
+	ESClass, ExceptionStack, ExceptionStackBehavior, GetPendingExceptionStack, IdentifyStandardInstance,
+	JS_ClearPendingException, JS_GetPendingException, JS_IsExceptionPending, JS_SetPendingException, JSObject, Rooted,
 };
 use mozjs::jsval::{JSVal, ObjectValue};
 #[cfg(feature = "sourcemap")]
 use sourcemap::SourceMap;
 
-use crate::{Context, Error, ErrorKind, Object, Result, Stack, Value};
+use crate::{Array, Context, Error, ErrorKind, Object, Result, Stack, Value};
 use crate::conversions::{FromValue, ToValue};
-use crate::format::{Config, format_value, NEWLINE};
+use crate::format::{Config, format_value, indent_str, NEWLINE};
 use crate::stack::Location;
 
 pub trait ThrowException {
@@ -74,8 +73,11 @@ impl Exception {
 
 				let location = Location { file, lineno, column };
 				let kind = ErrorKind::from_proto_key(IdentifyStandardInstance(handle.get()));
+				let name: Option<String> = exception.get_as(cx, "name", false, ()).ok().flatten();
+				let name = name.filter(|name| *name != kind.to_string());
 				let error = Error {
 					kind,
+					name: name.map(Into::into),
 					message: message.into(),
 					location: Some(location),
 					object: Some(handle.get()),
@@ -171,24 +173,32 @@ impl<E: Into<Error>> From<E> for Exception {
 	}
 }
 
+/// Maximum number of links of a cause chain which are collected into an [ErrorReport].
+const MAX_CAUSE_DEPTH: usize = 16;
+
 /// Represents an error report, containing an exception and optionally its [stacktrace](Stack).
+///
+/// Reports which are created with a [Context] also contain the reports of the `cause` chain of their error, and the
+/// `errors` of `AggregateError`s, which are formatted below the exception.
 #[derive(Clone, Debug)]
 pub struct ErrorReport {
 	pub exception: Exception,
 	pub stack: Option<Stack>,
+	pub cause: Option<Box<ErrorReport>>,
+	pub errors: Vec<ErrorReport>,
 }
 
 impl ErrorReport {
 	/// Creates a new [ErrorReport] with an [Exception] from the runtime and clears the pending exception.
 	/// Returns [None] if there is no pending exception.
 	pub fn new(cx: &Context) -> Result<Option<ErrorReport>> {
-		Ok(Exception::new(cx)?.map(|exception| ErrorReport { exception, stack: None }))
+		Ok(Exception::new(cx)?.map(|exception| ErrorReport::from(exception, None).with_causes(cx)))
 	}
 
 	/// Creates a new [ErrorReport] with an [Exception] and [Error]'s exception stack.
 	/// Returns [None] if there is no pending exception.
 	pub fn new_with_error_stack(cx: &Context) -> Result<Option<ErrorReport>> {
-		Ok(ErrorReport::new(cx)?.map(|report| ErrorReport::from_exception_with_error_stack(cx, report.exception)))
+		Ok(Exception::new(cx)?.map(|exception| ErrorReport::from_exception_with_error_stack(cx, exception)))
 	}
 
 	/// Creates a new [ErrorReport] with an [Exception] and exception stack from the runtime.
@@ -206,7 +216,7 @@ impl ErrorReport {
 					let exception = Exception::from_value(cx, &exception)?;
 					let stack = Stack::from_object(cx, exception_stack.stack_.ptr);
 					Exception::clear(cx);
-					Ok(Some(ErrorReport { exception, stack }))
+					Ok(Some(ErrorReport::from(exception, stack).with_causes(cx)))
 				} else {
 					Ok(None)
 				}
@@ -216,42 +226,152 @@ impl ErrorReport {
 		}
 	}
 
+	/// Creates an [ErrorReport] for execution which was terminated without an exception, such as when an interrupt
+	/// callback stops a running script.
+	pub fn terminated() -> ErrorReport {
+		ErrorReport::from(
+			Exception::Error(Error::new("Execution was terminated", ErrorKind::Normal)),
+			None,
+		)
+	}
+
 	/// Creates an [ErrorReport] from an existing [Exception] and optionally a [Stack].
 	pub fn from<S: Into<Option<Stack>>>(exception: Exception, stack: S) -> ErrorReport {
-		ErrorReport { exception, stack: stack.into() }
+		ErrorReport {
+			exception,
+			stack: stack.into(),
+			cause: None,
+			errors: Vec::new(),
+		}
 	}
 
 	/// Creates an [ErrorReport] from an existing [Exception], with the [Error]'s exception stack.
 	pub fn from_exception_with_error_stack(cx: &Context, exception: Exception) -> ErrorReport {
+		ErrorReport::with_error_stack(cx, exception).with_causes(cx)
+	}
+
+	fn with_error_stack(cx: &Context, exception: Exception) -> ErrorReport {
 		let stack = if let Exception::Error(Error { object: Some(object), .. }) = exception {
-			unsafe {
-				rooted!(in(cx.as_ptr()) let exc = object);
-				Stack::from_object(cx, ExceptionStackOrNull(exc.handle().into()))
-			}
+			Stack::from_error(cx, &Object::from(cx.root(object)))
 		} else {
 			None
 		};
-		ErrorReport { exception, stack }
+		ErrorReport::from(exception, stack)
+	}
+
+	fn with_causes(mut self, cx: &Context) -> ErrorReport {
+		self.collect_causes(cx, &mut Vec::new());
+		self
+	}
+
+	/// Collects the reports of the `cause` of the error, and the `errors` of an `AggregateError`, along with their
+	/// stacks and own causes. Errors which are already in the chain of `ancestors` are skipped.
+	fn collect_causes(&mut self, cx: &Context, ancestors: &mut Vec<*mut JSObject>) {
+		let Exception::Error(Error { kind, object: Some(object), .. }) = &self.exception else {
+			return;
+		};
+		if ancestors.len() >= MAX_CAUSE_DEPTH {
+			return;
+		}
+
+		ancestors.push(*object);
+		let object = Object::from(cx.root(*object));
+		if object.has_own(cx, "cause") {
+			self.cause = property(cx, &object, "cause")
+				.and_then(|cause| ErrorReport::link(cx, &cause, ancestors))
+				.map(Box::new);
+		}
+		if *kind == ErrorKind::Aggregate {
+			let errors = property(cx, &object, "errors").filter(|errors| errors.handle().is_object());
+			if let Some(errors) = errors.and_then(|errors| Array::from(cx, errors.to_object(cx).into_local())) {
+				self.errors = (0..errors.len(cx))
+					.filter_map(|index| errors.get(cx, index).ok().flatten())
+					.filter_map(|error| ErrorReport::link(cx, &error, ancestors))
+					.collect();
+			}
+		}
+		ancestors.pop();
+	}
+
+	fn link(cx: &Context, value: &Value, ancestors: &mut Vec<*mut JSObject>) -> Option<ErrorReport> {
+		if value.handle().is_object() && ancestors.contains(&value.handle().to_object()) {
+			return None;
+		}
+		let mut report = ErrorReport::with_error_stack(cx, Exception::from_value(cx, value).ok()?);
+		report.collect_causes(cx, ancestors);
+		Some(report)
 	}
 
 	/// Transforms the location of the [Exception] and the [Stack] if it exists, according to the given [SourceMap].
+	/// The causes of the report are transformed as well.
 	#[cfg(feature = "sourcemap")]
 	pub fn transform_with_sourcemap(&mut self, sourcemap: &SourceMap) {
 		self.exception.transform_with_sourcemap(sourcemap);
 		if let Some(stack) = &mut self.stack {
 			stack.transform_with_sourcemap(sourcemap)
 		}
+		for cause in self.cause.iter_mut().map(Box::as_mut).chain(&mut self.errors) {
+			cause.transform_with_sourcemap(sourcemap);
+		}
 	}
 
-	/// Formats the [ErrorReport] as a string for printing.
+	/// Formats the [ErrorReport] as a string for printing, with the stack, cause and errors of each link of the cause
+	/// chain indented below it.
 	pub fn format(&self, cx: &Context) -> String {
 		let mut string = self.exception.format(cx);
+		self.format_details(cx, &mut string, 1);
+		string
+	}
+
+	/// Formats the [ErrorReport] without the `Uncaught` prefix, such as for logging errors which were caught, with its
+	/// details indented by `indentation` levels.
+	pub fn format_caught(&self, cx: &Context, indentation: usize) -> String {
+		let mut string = String::new();
+		self.format_link(cx, &mut string, indentation);
+		string
+	}
+
+	fn format_link(&self, cx: &Context, string: &mut String, indentation: usize) {
+		match &self.exception {
+			Exception::Error(error) => string.push_str(&error.format()),
+			Exception::Other(value) => {
+				let value = cx.root(*value).into();
+				string.push_str(&format_value(cx, Config::default(), &value).to_string());
+			}
+		}
+		self.format_details(cx, string, indentation);
+	}
+
+	fn format_details(&self, cx: &Context, string: &mut String, indentation: usize) {
 		if let Some(stack) = &self.stack {
-			if !stack.is_empty() {
+			for record in &stack.records {
 				string.push_str(NEWLINE);
-				string.push_str(&stack.format());
+				string.push_str(&indent_str(indentation));
+				string.push_str(&record.to_string());
 			}
 		}
-		string
+		if let Some(cause) = &self.cause {
+			string.push_str(NEWLINE);
+			string.push_str(&indent_str(indentation));
+			string.push_str("[cause]: ");
+			cause.format_link(cx, string, indentation + 1);
+		}
+		for (index, error) in self.errors.iter().enumerate() {
+			string.push_str(NEWLINE);
+			string.push_str(&indent_str(indentation));
+			string.push_str(&format!("[errors][{}]: ", index));
+			error.format_link(cx, string, indentation + 1);
+		}
+	}
+}
+
+/// Gets a property of an error, ignoring exceptions thrown by getters.
+fn property<'cx>(cx: &'cx Context, object: &Object<'cx>, key: &str) -> Option<Value<'cx>> {
+	match object.get(cx, key) {
+		Ok(value) => value,
+		Err(_) => {
+			Exception::clear(cx);
+			None
+		}
 	}
 }
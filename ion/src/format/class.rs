@@ -0,0 +1,38 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use mozjs::jsapi::JSProtoKey;
+
+use crate::{Context, Object};
+use crate::format::Config;
+use crate::format::object::{write_entries, write_prefix};
+
+/// Formats an instance of a native class using the given [configuration](Config).
+/// The properties of `inspection`, returned by [ClassDefinition::inspect](crate::ClassDefinition::inspect), are
+/// displayed after the name of the class.
+pub fn format_class_object<'cx>(
+	cx: &'cx Context, cfg: Config, object: &'cx Object<'cx>, inspection: &'cx Object<'cx>,
+) -> ClassObjectDisplay<'cx> {
+	ClassObjectDisplay { cx, object, inspection, cfg }
+}
+
+#[must_use]
+pub struct ClassObjectDisplay<'cx> {
+	cx: &'cx Context,
+	object: &'cx Object<'cx>,
+	inspection: &'cx Object<'cx>,
+	cfg: Config,
+}
+
+impl Display for ClassObjectDisplay<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write_prefix(f, self.cx, self.cfg, self.object, "Object", JSProtoKey::JSProto_Object)?;
+		write_entries(f, self.cx, self.cfg, self.inspection)
+	}
+}
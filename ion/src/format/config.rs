@@ -77,6 +77,8 @@ pub struct Config {
 	pub indentation: u16,
 	pub multiline: bool,
 	pub quoted: bool,
+	/// Formats values as machine-readable JSON instead of coloured text.
+	pub json: bool,
 }
 
 impl Config {
@@ -104,6 +106,10 @@ impl Config {
 	pub fn quoted(self, quoted: bool) -> Config {
 		Config { quoted, ..self }
 	}
+
+	pub fn json(self, json: bool) -> Config {
+		Config { json, ..self }
+	}
 }
 
 impl Default for Config {
@@ -115,6 +121,7 @@ impl Default for Config {
 			indentation: 0,
 			multiline: true,
 			quoted: false,
+			json: false,
 		}
 	}
 }
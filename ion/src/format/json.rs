@@ -0,0 +1,229 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+
+use itoa::Buffer;
+use mozjs::jsapi::{ESClass, PromiseState};
+
+use crate::{Array, Context, Date, Function, Object, OwnedKey, Promise, RegExp, Symbol, Value};
+use crate::bigint::BigInt;
+use crate::conversions::FromValue;
+use crate::format::Config;
+use crate::typedarray::{ArrayBuffer, ArrayBufferView};
+
+/// Maximum depth of nested objects and arrays, past which they are replaced with a truncation marker.
+pub const MAX_JSON_DEPTH: u16 = 4;
+
+/// Formats a [JavaScript Value](Value) as a single line of JSON.
+///
+/// Values which cannot be represented in JSON, such as `undefined`, functions and symbols,
+/// are formatted as objects with a `$type` property, and a `value` property where applicable.
+/// ```js
+/// { "$type": "bigint", "value": "12" }
+/// ```
+pub fn format_json<'cx>(cx: &'cx Context, cfg: Config, value: &'cx Value<'cx>) -> JsonDisplay<'cx> {
+	JsonDisplay { cx, value, cfg }
+}
+
+#[must_use]
+pub struct JsonDisplay<'cx> {
+	cx: &'cx Context,
+	value: &'cx Value<'cx>,
+	cfg: Config,
+}
+
+impl Display for JsonDisplay<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write_value(f, self.cx, self.cfg, self.value)
+	}
+}
+
+fn write_value(f: &mut Formatter, cx: &Context, cfg: Config, value: &Value) -> fmt::Result {
+	let handle = value.handle();
+	if handle.is_boolean() {
+		f.write_str(if handle.to_boolean() { "true" } else { "false" })
+	} else if handle.is_int32() {
+		f.write_str(Buffer::new().format(handle.to_int32()))
+	} else if handle.is_double() {
+		let number = handle.to_double();
+		if number.is_finite() {
+			write!(f, "{}", number)
+		} else if number.is_nan() {
+			write_typed(f, "number", Some("NaN"))
+		} else if number.is_sign_positive() {
+			write_typed(f, "number", Some("Infinity"))
+		} else {
+			write_typed(f, "number", Some("-Infinity"))
+		}
+	} else if handle.is_string() {
+		write_string(f, &String::from_value(cx, value, true, ())?)
+	} else if handle.is_null() {
+		f.write_str("null")
+	} else if handle.is_undefined() {
+		write_typed(f, "undefined", None)
+	} else if handle.is_bigint() {
+		let bigint = BigInt::from(cx.root(handle.to_bigint()));
+		let string = bigint.to_string(cx, 10).unwrap().to_owned(cx)?;
+		write_typed(f, "bigint", Some(&string))
+	} else if handle.is_symbol() {
+		let symbol = Symbol::from(cx.root(handle.to_symbol()));
+		write_typed(f, "symbol", symbol.description(cx).as_deref())
+	} else if handle.is_object() {
+		write_object(f, cx, cfg, &value.to_object(cx))
+	} else {
+		f.write_str("null")
+	}
+}
+
+fn write_object(f: &mut Formatter, cx: &Context, cfg: Config, object: &Object) -> fmt::Result {
+	use ESClass as ESC;
+
+	let local = || cx.root(object.handle().get());
+	match object.get_builtin_class(cx) {
+		ESC::Array => write_array(f, cx, cfg, &Array::from(cx, local()).unwrap()),
+		ESC::Date => match Date::from(cx, local()).unwrap().to_date(cx) {
+			Some(date) => write_typed(f, "date", Some(&date.to_rfc3339())),
+			None => write_typed(f, "date", Some("Invalid Date")),
+		},
+		ESC::RegExp => write_typed(f, "regexp", Some(&RegExp::from(cx, local()).unwrap().to_string(cx)?)),
+		ESC::Function => {
+			let name = Function::from_object(cx, object).unwrap().name(cx);
+			write_typed(f, "function", Some(name.as_deref().unwrap_or_default()))
+		}
+		ESC::Promise => {
+			let promise = Promise::from(local()).unwrap();
+			let state = match promise.state(cx) {
+				PromiseState::Pending => "pending",
+				PromiseState::Fulfilled => "fulfilled",
+				PromiseState::Rejected => "rejected",
+			};
+			f.write_str("{\"$type\":\"promise\",\"state\":")?;
+			write_string(f, state)?;
+			if state != "pending" {
+				f.write_str(",\"value\":")?;
+				write_nested(f, cx, cfg, &promise.result(cx))?;
+			}
+			f.write_char('}')
+		}
+		ESC::Error => {
+			f.write_str("{\"$type\":\"error\"")?;
+			for key in ["name", "message", "stack"] {
+				if let Ok(Some(value)) = object.get_as::<_, String>(cx, key, false, ()) {
+					f.write_char(',')?;
+					write_string(f, key)?;
+					f.write_char(':')?;
+					write_string(f, &value)?;
+				}
+			}
+			f.write_char('}')
+		}
+		ESC::ArrayBuffer => {
+			let buffer = ArrayBuffer::from(local()).unwrap();
+			f.write_str("{\"$type\":\"arraybuffer\",\"byteLength\":")?;
+			f.write_str(Buffer::new().format(buffer.len()))?;
+			f.write_char('}')
+		}
+		ESC::Boolean | ESC::Number | ESC::String | ESC::BigInt => {
+			write_typed(f, "boxed", Some(&object.as_value(cx).to_source(cx).to_owned(cx)?))
+		}
+		ESC::Object => write_raw_object(f, cx, cfg, object),
+		ESC::Other => {
+			if let Some(view) = ArrayBufferView::from(local()) {
+				f.write_str("{\"$type\":\"typedarray\",\"byteLength\":")?;
+				f.write_str(Buffer::new().format(view.byte_length()))?;
+				f.write_char('}')
+			} else {
+				write_raw_object(f, cx, cfg, object)
+			}
+		}
+		_ => write_typed(f, "object", Some(&object.as_value(cx).to_source(cx).to_owned(cx)?)),
+	}
+}
+
+fn write_array(f: &mut Formatter, cx: &Context, cfg: Config, array: &Array) -> fmt::Result {
+	if cfg.depth >= MAX_JSON_DEPTH {
+		return f.write_str("{\"$type\":\"array\",\"truncated\":true}");
+	}
+
+	f.write_char('[')?;
+	for index in 0..array.len(cx) {
+		if index != 0 {
+			f.write_char(',')?;
+		}
+		match array.get(cx, index)? {
+			Some(value) => write_nested(f, cx, cfg, &value)?,
+			None => write_typed(f, "undefined", None)?,
+		}
+	}
+	f.write_char(']')
+}
+
+fn write_raw_object(f: &mut Formatter, cx: &Context, cfg: Config, object: &Object) -> fmt::Result {
+	if cfg.depth >= MAX_JSON_DEPTH {
+		return f.write_str("{\"$type\":\"object\",\"truncated\":true}");
+	}
+
+	f.write_char('{')?;
+	let mut first = true;
+	for key in object.keys(cx, Some(cfg.iteration)) {
+		let name = match key.to_owned_key(cx)? {
+			OwnedKey::Int(int) => int.to_string(),
+			OwnedKey::String(string) => string,
+			OwnedKey::Symbol(_) | OwnedKey::Void => continue,
+		};
+		let Some(descriptor) = object.get_descriptor(cx, &key)? else {
+			continue;
+		};
+
+		if !first {
+			f.write_char(',')?;
+		}
+		first = false;
+
+		write_string(f, &name)?;
+		f.write_char(':')?;
+		match descriptor.value(cx) {
+			Some(value) if descriptor.getter(cx).is_none() && descriptor.setter(cx).is_none() => {
+				write_nested(f, cx, cfg, &value)?
+			}
+			_ => write_typed(f, "accessor", None)?,
+		}
+	}
+	f.write_char('}')
+}
+
+fn write_nested(f: &mut Formatter, cx: &Context, cfg: Config, value: &Value) -> fmt::Result {
+	write_value(f, cx, cfg.depth(cfg.depth + 1), value)
+}
+
+fn write_typed(f: &mut Formatter, kind: &str, value: Option<&str>) -> fmt::Result {
+	f.write_str("{\"$type\":")?;
+	write_string(f, kind)?;
+	if let Some(value) = value {
+		f.write_str(",\"value\":")?;
+		write_string(f, value)?;
+	}
+	f.write_char('}')
+}
+
+/// Writes a string as a quoted and escaped JSON string.
+pub fn write_string(f: &mut impl Write, string: &str) -> fmt::Result {
+	f.write_char('"')?;
+	for char in string.chars() {
+		match char {
+			'"' => f.write_str("\\\"")?,
+			'\\' => f.write_str("\\\\")?,
+			'\n' => f.write_str("\\n")?,
+			'\r' => f.write_str("\\r")?,
+			'\t' => f.write_str("\\t")?,
+			char if u32::from(char) < 0x20 => write!(f, "\\u{:04x}", u32::from(char))?,
+			char => f.write_char(char)?,
+		}
+	}
+	f.write_char('"')
+}
@@ -12,15 +12,18 @@ use std::str;
 pub use config::Config;
 
 use crate::{Context, Value};
+use crate::format::json::format_json;
 use crate::format::object::format_object;
 use crate::format::primitive::format_primitive;
 
 pub mod array;
 pub mod boxed;
+pub mod class;
 mod config;
 pub mod date;
 pub mod descriptor;
 pub mod function;
+pub mod json;
 pub mod key;
 pub mod object;
 pub mod primitive;
@@ -49,6 +52,7 @@ pub fn indent_str(indentation: usize) -> Cow<'static, str> {
 }
 
 /// Formats a [JavaScript Value](Value) with the given [configuration](Config).
+/// If [Config::json] is set, the value is formatted with [format_json].
 pub fn format_value<'cx>(cx: &'cx Context, cfg: Config, value: &'cx Value<'cx>) -> ValueDisplay<'cx> {
 	ValueDisplay { cx, value, cfg }
 }
@@ -62,7 +66,9 @@ pub struct ValueDisplay<'cx> {
 
 impl Display for ValueDisplay<'_> {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-		if self.value.handle().is_object() {
+		if self.cfg.json {
+			format_json(self.cx, self.cfg, self.value).fmt(f)
+		} else if self.value.handle().is_object() {
 			format_object(self.cx, self.cfg, self.value.to_object(self.cx)).fmt(f)
 		} else {
 			format_primitive(self.cx, self.cfg, self.value).fmt(f)
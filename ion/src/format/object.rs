@@ -15,12 +15,15 @@ use mozjs::jsapi::{
 };
 
 use crate::{
-	Array, Context, Date, Exception, Function, Local, Object, Promise, PropertyDescriptor, PropertyKey, RegExp, Result,
+	Array, Context, Date, ErrorReport, Exception, Function, Local, Object, Promise, PropertyDescriptor, PropertyKey,
+	RegExp, Result,
 };
+use crate::class::inspect_object;
 use crate::conversions::ToValue;
 use crate::format::{indent_str, NEWLINE};
 use crate::format::array::format_array;
 use crate::format::boxed::format_boxed_primitive;
+use crate::format::class::format_class_object;
 use crate::format::Config;
 use crate::format::date::format_date;
 use crate::format::descriptor::format_descriptor;
@@ -59,6 +62,12 @@ impl Display for ObjectDisplay<'_> {
 
 		let class = self.object.get_builtin_class(cx);
 
+		if matches!(class, ESC::Object | ESC::Other) {
+			if let Some(inspection) = inspect_object(cx, &self.object) {
+				return format_class_object(cx, cfg, &self.object, &inspection).fmt(f);
+			}
+		}
+
 		match class {
 			ESC::Boolean | ESC::Number | ESC::String | ESC::BigInt => {
 				format_boxed_primitive(cx, cfg, &self.object).fmt(f)
@@ -69,10 +78,11 @@ impl Display for ObjectDisplay<'_> {
 			ESC::RegExp => format_regexp(cx, cfg, &RegExp::from(cx, object.into_local()).unwrap()).fmt(f),
 			ESC::Function => format_function(cx, cfg, &Function::from_object(cx, &self.object).unwrap()).fmt(f),
 			ESC::ArrayBuffer => format_array_buffer(cfg, &ArrayBuffer::from(object.into_local()).unwrap()).fmt(f),
-			ESC::Error => match Exception::from_object(cx, &self.object)? {
-				Exception::Error(error) => error.format().fmt(f),
-				_ => unreachable!("Expected Error"),
-			},
+			ESC::Error => {
+				let exception = Exception::from_object(cx, &self.object)?;
+				let report = ErrorReport::from_exception_with_error_stack(cx, exception);
+				f.write_str(&report.format_caught(cx, (cfg.indentation + cfg.depth + 1) as usize))
+			}
 			ESC::Object => format_raw_object(cx, cfg, &self.object).fmt(f),
 			ESC::Other => {
 				if let Some(view) = ArrayBufferView::from(cx.root(object.handle().get())) {
@@ -129,55 +139,59 @@ pub struct RawObjectDisplay<'cx> {
 
 impl Display for RawObjectDisplay<'_> {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-		let colour = self.cfg.colours.object;
-
 		write_prefix(f, self.cx, self.cfg, self.object, "Object", JSProtoKey::JSProto_Object)?;
+		write_entries(f, self.cx, self.cfg, self.object)
+	}
+}
 
-		if self.cfg.depth < 4 {
-			let keys = self.object.keys(self.cx, Some(self.cfg.iteration));
-			let length = keys.len();
+/// Writes the own enumerable properties of an object, in braces.
+pub(crate) fn write_entries(f: &mut Formatter, cx: &Context, cfg: Config, object: &Object) -> fmt::Result {
+	let colour = cfg.colours.object;
 
-			if length == 0 {
-				"{}".color(colour).fmt(f)
-			} else {
-				"{".color(colour).fmt(f)?;
+	if cfg.depth < 4 {
+		let keys = object.keys(cx, Some(cfg.iteration));
+		let length = keys.len();
 
-				if self.cfg.multiline {
-					f.write_str(NEWLINE)?;
-					let inner = indent_str((self.cfg.indentation + self.cfg.depth + 1) as usize);
+		if length == 0 {
+			"{}".color(colour).fmt(f)
+		} else {
+			"{".color(colour).fmt(f)?;
 
-					for key in keys {
-						inner.fmt(f)?;
-						let desc = self.object.get_descriptor(self.cx, &key)?.unwrap();
-						write_key_descriptor(f, self.cx, self.cfg, &key, &desc, Some(self.object))?;
-						",".color(colour).fmt(f)?;
-						f.write_str(NEWLINE)?;
-					}
+			if cfg.multiline {
+				f.write_str(NEWLINE)?;
+				let inner = indent_str((cfg.indentation + cfg.depth + 1) as usize);
 
-					indent_str((self.cfg.indentation + self.cfg.depth) as usize).fmt(f)?;
-				} else {
-					f.write_char(' ')?;
-					let len = length.clamp(0, 3);
+				for key in keys {
+					inner.fmt(f)?;
+					let desc = object.get_descriptor(cx, &key)?.unwrap();
+					write_key_descriptor(f, cx, cfg, &key, &desc, Some(object))?;
+					",".color(colour).fmt(f)?;
+					f.write_str(NEWLINE)?;
+				}
 
-					for (i, key) in keys.enumerate() {
-						let desc = self.object.get_descriptor(self.cx, &key)?.unwrap();
-						write_key_descriptor(f, self.cx, self.cfg, &key, &desc, Some(self.object))?;
+				indent_str((cfg.indentation + cfg.depth) as usize).fmt(f)?;
+			} else {
+				f.write_char(' ')?;
+				let len = length.clamp(0, 3);
 
-						if i != len - 1 {
-							",".color(colour).fmt(f)?;
-							f.write_char(' ')?;
-						}
-					}
+				for (i, key) in keys.enumerate() {
+					let desc = object.get_descriptor(cx, &key)?.unwrap();
+					write_key_descriptor(f, cx, cfg, &key, &desc, Some(object))?;
 
-					let remaining = length - len;
-					write_remaining(f, remaining, None, colour)?;
+					if i != len - 1 {
+						",".color(colour).fmt(f)?;
+						f.write_char(' ')?;
+					}
 				}
 
-				"}".color(colour).fmt(f)
+				let remaining = length - len;
+				write_remaining(f, remaining, None, colour)?;
 			}
-		} else {
-			"[Object]".color(colour).fmt(f)
+
+			"}".color(colour).fmt(f)
 		}
+	} else {
+		"[Object]".color(colour).fmt(f)
 	}
 }
 
@@ -7,7 +7,7 @@
 use mozjs::jsapi::{CallArgs, JS_GetFunctionId, JS_GetObjectFunction};
 use mozjs::jsval::JSVal;
 
-use crate::{Context, Error, ErrorKind, Local, Object, Result, Value};
+use crate::{Context, Error, ErrorKind, Function, Local, Object, Result, Value};
 use crate::conversions::FromValue;
 use crate::function::{Opt, Rest};
 
@@ -74,6 +74,16 @@ impl<'cx> Arguments<'cx> {
 		Object::from(Local::from_handle(self.callee.handle()))
 	}
 
+	/// Returns the function being called.
+	pub fn callee_function(&self) -> Option<Function<'cx>> {
+		Function::from_object(self.cx, &self.callee)
+	}
+
+	/// Returns the name of the function being called.
+	pub fn callee_name(&self) -> Option<String> {
+		self.callee_function().and_then(|callee| callee.name(self.cx))
+	}
+
 	/// Returns the `this` value of the function.
 	/// Refer to [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/this) for more details.
 	pub fn this(&self) -> Value<'cx> {
@@ -101,6 +111,18 @@ impl<'cx> Arguments<'cx> {
 		self.call_args.constructing_()
 	}
 
+	/// Returns `new.target`, the constructor which `new` was initially applied to, if the function was called with `new`.
+	/// This differs from the callee when the function is called as the constructor of a superclass.
+	/// Refer to [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/new.target) for more details.
+	pub fn new_target(&self) -> Option<Object<'cx>> {
+		self.is_constructing().then(|| {
+			// `new.target` is stored after the arguments when constructing.
+			let argc = self.call_args.argc_ as usize;
+			let new_target = unsafe { Local::from_marked(self.call_args.argv_.add(argc)) };
+			Value::from(new_target).to_object(self.cx)
+		})
+	}
+
 	/// Returns `true` if the function ignores the return value.
 	pub fn ignores_return_value(&self) -> bool {
 		self.call_args.ignoresReturnValue_()
@@ -141,6 +163,16 @@ impl<'cx> Accessor<'_, 'cx> {
 		self.args.callee()
 	}
 
+	/// Returns the function being called.
+	pub fn callee_function(&self) -> Option<Function<'cx>> {
+		self.args.callee_function()
+	}
+
+	/// Returns the name of the function being called.
+	pub fn callee_name(&self) -> Option<String> {
+		self.args.callee_name()
+	}
+
 	/// Returns the `this` value of the function.
 	/// Refer to [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/this) for more details.
 	pub fn this(&self) -> Value<'cx> {
@@ -163,6 +195,11 @@ impl<'cx> Accessor<'_, 'cx> {
 		self.args.is_constructing()
 	}
 
+	/// Returns `new.target`, if the function was called with `new`.
+	pub fn new_target(&self) -> Option<Object<'cx>> {
+		self.args.new_target()
+	}
+
 	/// Returns `true` if the function ignores the return value.
 	pub fn ignores_return_value(&self) -> bool {
 		self.args.ignores_return_value()
@@ -9,14 +9,15 @@ use std::ops::Deref;
 
 use mozjs::conversions::jsstr_to_string;
 use mozjs::jsapi::{
-	HandleValueArray, JS_CallFunction, JS_DecompileFunction, JS_GetFunctionArity, JS_GetFunctionDisplayId,
+	Construct1, HandleValueArray, JS_CallFunction, JS_DecompileFunction, JS_GetFunctionArity, JS_GetFunctionDisplayId,
 	JS_GetFunctionId, JS_GetFunctionLength, JS_GetFunctionObject, JS_GetObjectFunction, JS_IsBuiltinEvalFunction,
 	JS_IsBuiltinFunctionConstructor, JS_IsConstructor, JS_NewFunction, JS_ObjectIsFunction, JSContext, JSFunction,
 	JSFunctionSpec, JSObject, NewFunctionFromSpec1, NewFunctionWithReserved, SetFunctionNativeReserved,
 };
 use mozjs::jsval::{JSVal, ObjectValue};
 
-use crate::{Context, ErrorReport, Local, Object, Value};
+use crate::{Context, ErrorReport, Local, Object, Promise, PromiseFuture, Value};
+use crate::conversions::ToValue;
 use crate::flags::PropertyFlags;
 use crate::function::closure::{
 	call_closure, call_closure_once, Closure, ClosureOnce, create_closure_object, create_closure_once_object,
@@ -25,6 +26,12 @@ use crate::function::closure::{
 /// Native Function that can be used from JavaScript.
 pub type NativeFunction = unsafe extern "C" fn(*mut JSContext, u32, *mut JSVal) -> bool;
 
+/// Result of [Function::apply_async], which is pending if the function returned a promise or another thenable.
+pub enum AsyncCallResult<'cx> {
+	Ready(Value<'cx>),
+	Pending(PromiseFuture),
+}
+
 /// Represents a [Function] within the JavaScript Runtime.
 /// Refer to [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Functions) for more details.
 #[derive(Debug)]
@@ -57,6 +64,7 @@ impl<'f> Function<'f> {
 	pub fn from_closure_once(
 		cx: &'f Context, name: &str, closure: Box<ClosureOnce>, nargs: u32, flags: PropertyFlags,
 	) -> Function<'f> {
+		let name = CString::new(name).unwrap();
 		unsafe {
 			let function = Function {
 				function: cx.root(NewFunctionWithReserved(
@@ -64,7 +72,7 @@ impl<'f> Function<'f> {
 					Some(call_closure_once),
 					nargs,
 					u32::from(flags.bits()),
-					name.as_ptr().cast(),
+					name.as_ptr(),
 				)),
 			};
 			let closure_object = create_closure_once_object(cx, closure);
@@ -81,6 +89,7 @@ impl<'f> Function<'f> {
 	pub fn from_closure(
 		cx: &'f Context, name: &str, closure: Box<Closure>, nargs: u32, flags: PropertyFlags,
 	) -> Function<'f> {
+		let name = CString::new(name).unwrap();
 		unsafe {
 			let function = Function {
 				function: cx.root(NewFunctionWithReserved(
@@ -88,7 +97,7 @@ impl<'f> Function<'f> {
 					Some(call_closure),
 					nargs,
 					u32::from(flags.bits()),
-					name.as_ptr().cast(),
+					name.as_ptr(),
 				)),
 			};
 			let closure_object = create_closure_object(cx, closure);
@@ -185,6 +194,61 @@ impl<'f> Function<'f> {
 		}
 	}
 
+	/// Calls the [Function] with the given `this` [Object], converting each of the arguments to a [Value].
+	/// Returns [Err] if the function call fails or an exception occurs.
+	pub fn call_with<'cx, T: ToValue<'cx>>(
+		&self, cx: &'cx Context, this: &Object, args: impl IntoIterator<Item = T>,
+	) -> Result<Value<'cx>, Option<ErrorReport>> {
+		let args: Vec<_> = args.into_iter().map(|arg| arg.as_value(cx)).collect();
+		self.call(cx, this, &args)
+	}
+
+	/// Calls the [Function] as a constructor with the given arguments, as with `new`.
+	/// Returns [Err] if the function is not a constructor or an exception occurs.
+	pub fn construct<'cx>(&self, cx: &'cx Context, args: &[Value]) -> Result<Object<'cx>, Option<ErrorReport>> {
+		let function = Value::object(cx, &cx.root(unsafe { JS_GetFunctionObject(self.get()) }).into());
+		let args: Vec<_> = args.iter().map(|a| a.get()).collect();
+		let mut rval = Object::null(cx);
+		if unsafe {
+			Construct1(
+				cx.as_ptr(),
+				function.handle().into(),
+				&HandleValueArray::from_rooted_slice(args.as_slice()),
+				rval.handle_mut().into(),
+			)
+		} {
+			Ok(rval)
+		} else {
+			Err(ErrorReport::new_with_exception_stack(cx).unwrap())
+		}
+	}
+
+	/// Calls the [Function] with the given `this` [Object] and arguments.
+	/// If the result is a promise or another thenable, a [PromiseFuture] which waits for it to settle is returned.
+	/// Returns [Err] if the function call fails or an exception occurs.
+	pub fn apply_async<'cx>(
+		&self, cx: &'cx Context, this: &Object, args: &[Value],
+	) -> Result<AsyncCallResult<'cx>, Option<ErrorReport>> {
+		let result = self.call(cx, this, args)?;
+		if !result.handle().is_object() {
+			return Ok(AsyncCallResult::Ready(result));
+		}
+
+		let object = result.to_object(cx);
+		let is_thenable = Promise::is_promise(&object) || {
+			let then = object.get(cx, "then").map_err(|_| ErrorReport::new_with_exception_stack(cx).unwrap())?;
+			then.is_some_and(|then| {
+				then.handle().is_object() && Function::from_object(cx, &then.to_object(cx)).is_some()
+			})
+		};
+		if is_thenable {
+			let promise = Promise::resolved(cx, result);
+			Ok(AsyncCallResult::Pending(PromiseFuture::new(cx.duplicate(), &promise)))
+		} else {
+			Ok(AsyncCallResult::Ready(result))
+		}
+	}
+
 	/// Checks if the [Function] is the built-in eval function.
 	pub fn is_eval(&self) -> bool {
 		unsafe { JS_IsBuiltinEvalFunction(self.get()) }
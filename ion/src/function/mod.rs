@@ -12,7 +12,7 @@ use mozjs::conversions::ConversionBehavior;
 
 pub use arguments::{Accessor, Arguments, FromArgument};
 pub use closure::{Closure, ClosureOnce};
-pub use function::{Function, NativeFunction};
+pub use function::{AsyncCallResult, Function, NativeFunction};
 
 use crate::{Context, Error, Object, ResultExc, ThrowException, Value};
 use crate::conversions::{FromValue, ToValue};
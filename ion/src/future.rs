@@ -5,6 +5,7 @@
  */
 
 use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task;
 use std::task::Poll;
@@ -15,7 +16,8 @@ use futures::Stream;
 use mozjs::jsval::JSVal;
 use mozjs_sys::jsapi::JSContext;
 
-use crate::{Context, Function, Promise, Value, TracedHeap};
+use crate::{Context, Exception, Function, Promise, Result, ResultExc, Value, TracedHeap};
+use crate::conversions::FromValue;
 use crate::flags::PropertyFlags;
 
 pub struct PromiseFuture(*mut JSContext, Receiver<Result<TracedHeap<JSVal>, TracedHeap<JSVal>>>);
@@ -71,3 +73,85 @@ impl Future for PromiseFuture {
 		}
 	}
 }
+
+/// [Promise] which is expected to resolve to a value of type `T`.
+/// When awaited from Rust, the result of the promise is converted to `T`, and rejections are returned as an [Exception].
+pub struct TypedPromise<T> {
+	promise: Promise,
+	_marker: PhantomData<fn() -> T>,
+}
+
+impl<T> TypedPromise<T> {
+	pub fn new(promise: Promise) -> TypedPromise<T> {
+		TypedPromise { promise, _marker: PhantomData }
+	}
+
+	pub fn promise(&self) -> &Promise {
+		&self.promise
+	}
+
+	pub fn into_promise(self) -> Promise {
+		self.promise
+	}
+
+	/// Returns a future which resolves to the converted result of the promise.
+	pub fn wait_with<C>(self, cx: Context, strict: bool, config: C) -> TypedPromiseFuture<T, C>
+	where
+		T: for<'cx> FromValue<'cx, Config = C>,
+	{
+		TypedPromiseFuture {
+			future: PromiseFuture::new(cx, &self.promise),
+			strict,
+			config: Some(config),
+			_marker: PhantomData,
+		}
+	}
+
+	/// Returns a future which resolves to the result of the promise, converted non-strictly.
+	pub fn wait(self, cx: Context) -> TypedPromiseFuture<T, ()>
+	where
+		T: for<'cx> FromValue<'cx, Config = ()>,
+	{
+		self.wait_with(cx, false, ())
+	}
+}
+
+impl<'cx, T> FromValue<'cx> for TypedPromise<T> {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<TypedPromise<T>> {
+		Promise::from_value(cx, value, strict, ()).map(TypedPromise::new)
+	}
+}
+
+pub struct TypedPromiseFuture<T, C> {
+	future: PromiseFuture,
+	strict: bool,
+	config: Option<C>,
+	_marker: PhantomData<fn() -> T>,
+}
+
+impl<T, C> Future for TypedPromiseFuture<T, C>
+where
+	T: for<'cx> FromValue<'cx, Config = C>,
+	C: Unpin,
+{
+	type Output = (Context, ResultExc<T>);
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+		let (cx, result) = match Pin::new(&mut self.future).poll(cx) {
+			Poll::Ready(output) => output,
+			Poll::Pending => return Poll::Pending,
+		};
+		let config = self.config.take().expect("TypedPromiseFuture polled after completion");
+
+		let result = match result {
+			Ok(value) => {
+				let value = Value::from(cx.root(value.get()));
+				T::from_value(&cx, &value, self.strict, config).map_err(Exception::Error)
+			}
+			Err(value) => Err(Exception::Other(value.get())),
+		};
+		Poll::Ready((cx, result))
+	}
+}
@@ -17,18 +17,19 @@ pub use context::{Context, ContextInner};
 pub use error::{Error, ErrorKind};
 pub use exception::{ErrorReport, Exception, ThrowException};
 pub use function::{Arguments, Function};
-pub use future::PromiseFuture;
+pub use future::{PromiseFuture, TypedPromise, TypedPromiseFuture};
 #[cfg(feature = "macros")]
 pub use ion_proc::*;
 pub use object::*;
-pub use root::{Local, Heap, PermanentHeap, TracedHeap, HeapPointer};
+pub use root::{Local, Heap, PermanentHeap, TracedHeap, HeapPointer, WeakHeap};
 pub use stack::{Stack, StackRecord};
 pub use string::{String, StringRef};
 pub use symbol::Symbol;
-pub use value::Value;
+pub use value::{PreferredType, Value};
 
 mod bigint;
 pub mod class;
+pub mod clone;
 mod context;
 pub mod conversions;
 mod error;
@@ -40,6 +41,7 @@ mod future;
 pub mod json;
 pub mod module;
 pub mod object;
+pub mod realm;
 mod root;
 pub mod script;
 pub mod spec;
@@ -15,7 +15,7 @@ use mozjs::jsapi::{
 use mozjs::jsval::JSVal;
 use mozjs::rust::{CompileOptionsWrapper, transform_u16_to_source_text};
 
-use crate::{Context, Error, ErrorReport, Local, Object, Promise, ThrowException, Value};
+use crate::{Context, Error, ErrorKind, ErrorReport, Exception, Function, Local, Object, Promise, ThrowException, Value};
 use crate::conversions::{FromValue, ToValue};
 
 /// Represents private module data
@@ -170,7 +170,7 @@ impl<'cx> Module<'cx> {
 		if unsafe { ModuleEvaluate(cx.as_ptr(), self.0.handle().into(), rval.handle_mut().into()) } {
 			Ok(Promise::from_value(cx, &rval, true, ()).ok())
 		} else {
-			Err(ErrorReport::new_with_exception_stack(cx)?.unwrap())
+			Err(ErrorReport::new_with_exception_stack(cx)?.unwrap_or_else(ErrorReport::terminated))
 		}
 	}
 
@@ -187,6 +187,39 @@ impl<'cx> Module<'cx> {
 		cx.root(unsafe { mozjs::jsapi::GetModuleEnvironment(cx.as_ptr(), self.0.handle().into()) })
 			.into()
 	}
+
+	/// Returns the value of the named export of the [Module], or [None] if it is not exported.
+	/// The module must have been evaluated.
+	pub fn export(&self, cx: &'cx Context, name: &str) -> crate::Result<Option<Value<'cx>>> {
+		self.module_namespace(cx).get(cx, name)
+	}
+
+	/// Returns the named export of the [Module], converted to the desired type.
+	/// Returns [Err] if it is not exported or the conversion fails.
+	pub fn export_as<T: FromValue<'cx>>(&self, cx: &'cx Context, name: &str) -> crate::Result<T>
+	where
+		T::Config: Default,
+	{
+		match self.export(cx, name)? {
+			Some(value) => T::from_value(cx, &value, true, T::Config::default()),
+			None => Err(Error::new(
+				format!("Module does not export {}", name),
+				ErrorKind::Reference,
+			)),
+		}
+	}
+
+	/// Calls the default export of the [Module] with the given arguments.
+	/// Returns [Err] if the default export is not a function, or the call throws.
+	pub fn call_default(&self, cx: &'cx Context, args: &[Value]) -> Result<Value<'cx>, Option<ErrorReport>> {
+		let function: Function = self.export_as(cx, "default").map_err(|error| {
+			Some(ErrorReport::from_exception_with_error_stack(
+				cx,
+				Exception::Error(error),
+			))
+		})?;
+		function.call(cx, &Object::null(cx), args)
+	}
 }
 
 /// Represents an ES module loader.
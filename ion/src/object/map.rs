@@ -11,8 +11,8 @@ use mozjs::jsapi::{
 	MapValues, NewMapObject,
 };
 
-use crate::{Context, Function, Local, Object, Value};
-use crate::conversions::ToValue;
+use crate::{Array, Context, Function, Local, Object, Result, Value};
+use crate::conversions::{FromValue, ToValue};
 
 pub struct Map<'m> {
 	map: Local<'m, *mut JSObject>,
@@ -124,6 +124,18 @@ impl<'m> Map<'m> {
 		entries.to_object(cx)
 	}
 
+	/// Returns the entries of the [Map] as pairs of keys and values, in insertion order.
+	pub fn to_vec<'cx>(&self, cx: &'cx Context) -> Result<Vec<(Value<'cx>, Value<'cx>)>> {
+		let entries = Vec::<Array>::from_value(cx, &self.entries(cx).as_value(cx), false, ())?;
+		Ok(entries
+			.into_iter()
+			.map(|entry| {
+				let mut entry = entry.to_vec(cx).into_iter();
+				(entry.next().unwrap(), entry.next().unwrap())
+			})
+			.collect())
+	}
+
 	/// Runs the given callback for each entry in the [Map].
 	pub fn for_each(&self, cx: &Context, callback: &Function, this: &Object) -> bool {
 		unsafe {
@@ -22,7 +22,11 @@ pub use object::Object;
 pub use promise::Promise;
 pub use regexp::RegExp;
 pub use set::Set;
-pub use stream::{ReadableStream, ReadableStreamReader, WritableStream};
+pub use stream::{
+	ReadableStream, ReadableStreamControllerKind, ReadableStreamDiagnostics, ReadableStreamReader, ReadableStreamState,
+	WritableStream, WritableStreamWriter,
+};
+pub use weak_map::WeakMap;
 
 use crate::Context;
 
@@ -34,10 +38,12 @@ mod key;
 mod map;
 mod object;
 mod promise;
+pub mod proxy;
 mod regexp;
 mod set;
 mod stream;
 pub mod typedarray;
+mod weak_map;
 
 /// Returns the bit-masked representation of reserved slots for a class.
 pub const fn class_reserved_slots(slots: u32) -> u32 {
@@ -12,9 +12,9 @@ use std::slice;
 
 use mozjs::jsapi::{
 	CurrentGlobalOrNull, ESClass, GetBuiltinClass, GetPropertyKeys, JS_DefineFunctionById, JS_DefineFunctions,
-	JS_DefineFunctionsWithHelp, JS_DefineProperties, JS_DefinePropertyById2, JS_DeletePropertyById, JS_GetPropertyById,
-	JS_GetPropertyDescriptorById, JS_HasOwnPropertyById, JS_HasPropertyById, JS_NewPlainObject, JS_SetPropertyById,
-	JSFunctionSpec, JSFunctionSpecWithHelp, JSObject, JSPropertySpec, Unbox,
+	JS_DefineFunctionsWithHelp, JS_DefineProperties, JS_DefinePropertyById2, JS_DefinePropertyById4,
+	JS_DeletePropertyById, JS_GetPropertyById, JS_GetPropertyDescriptorById, JS_HasOwnPropertyById, JS_HasPropertyById,
+	JS_NewPlainObject, JS_SetPropertyById, JSFunctionSpec, JSFunctionSpecWithHelp, JSObject, JSPropertySpec, Unbox,
 };
 use mozjs::jsapi::PropertyKey as JSPropertyKey;
 use mozjs::jsval::NullValue;
@@ -23,7 +23,7 @@ use mozjs::rust::IdVector;
 use crate::{Context, Error, Exception, Function, Local, OwnedKey, PropertyDescriptor, PropertyKey, Result, Value};
 use crate::conversions::{FromValue, ToPropertyKey, ToValue};
 use crate::flags::{IteratorFlags, PropertyFlags};
-use crate::function::NativeFunction;
+use crate::function::{Closure, NativeFunction};
 
 /// Represents an [Object] in the JS Runtime.
 ///
@@ -195,6 +195,62 @@ impl<'o> Object<'o> {
 		self.define(cx, key, &value.as_value(cx), attrs)
 	}
 
+	/// Defines an accessor property at the given key of the [Object] with the given getter, setter and attributes.
+	/// Accessors without a getter return `undefined`, and accessors without a setter ignore assignments.
+	///
+	/// Returns `false` if the property cannot be defined.
+	pub fn define_accessor<'cx, K: ToPropertyKey<'cx>>(
+		&self, cx: &'cx Context, key: K, getter: Option<&Function>, setter: Option<&Function>, attrs: PropertyFlags,
+	) -> bool {
+		let key = key.to_key(cx).unwrap();
+		let getter = getter.map_or_else(|| Object::null(cx), |getter| getter.to_object(cx));
+		let setter = setter.map_or_else(|| Object::null(cx), |setter| setter.to_object(cx));
+		unsafe {
+			JS_DefinePropertyById4(
+				cx.as_ptr(),
+				self.handle().into(),
+				key.handle().into(),
+				getter.handle().into(),
+				setter.handle().into(),
+				u32::from(attrs.bits()),
+			)
+		}
+	}
+
+	/// Defines a getter at the given key of the [Object], which calls the [Closure] with the receiver as `this`.
+	/// The setter of an existing accessor at the key is kept.
+	///
+	/// Returns `false` if the property cannot be defined.
+	pub fn define_getter_closure<'cx, K: ToPropertyKey<'cx>>(
+		&self, cx: &'cx Context, key: K, getter: Box<Closure>, attrs: PropertyFlags,
+	) -> bool {
+		let key = key.to_key(cx).unwrap();
+		let getter = Function::from_closure(cx, &accessor_name(cx, "get", &key), getter, 0, PropertyFlags::empty());
+		let setter = self.own_accessor(cx, &key).and_then(|desc| desc.setter(cx));
+		self.define_accessor(cx, &key, Some(&getter), setter.as_ref(), attrs)
+	}
+
+	/// Defines a setter at the given key of the [Object], which calls the [Closure] with the assigned value as its
+	/// only argument. The getter of an existing accessor at the key is kept.
+	///
+	/// Returns `false` if the property cannot be defined.
+	pub fn define_setter_closure<'cx, K: ToPropertyKey<'cx>>(
+		&self, cx: &'cx Context, key: K, setter: Box<Closure>, attrs: PropertyFlags,
+	) -> bool {
+		let key = key.to_key(cx).unwrap();
+		let setter = Function::from_closure(cx, &accessor_name(cx, "set", &key), setter, 1, PropertyFlags::empty());
+		let getter = self.own_accessor(cx, &key).and_then(|desc| desc.getter(cx));
+		self.define_accessor(cx, &key, getter.as_ref(), Some(&setter), attrs)
+	}
+
+	fn own_accessor<'cx>(&self, cx: &'cx Context, key: &PropertyKey) -> Option<PropertyDescriptor<'cx>> {
+		if self.has_own(cx, key) {
+			self.get_descriptor(cx, key).ok().flatten()
+		} else {
+			None
+		}
+	}
+
 	/// Defines a method with the given name, and the given number of arguments and attributes on the [Object].
 	///
 	/// Parameters are similar to [create_function_spec](crate::spec::create_function_spec).
@@ -447,3 +503,12 @@ impl ExactSizeIterator for ObjectIter<'_, '_> {
 }
 
 impl FusedIterator for ObjectIter<'_, '_> {}
+
+/// Returns the name of an accessor function, such as `get value`.
+fn accessor_name(cx: &Context, kind: &str, key: &PropertyKey) -> String {
+	match key.to_owned_key(cx) {
+		Ok(OwnedKey::Int(int)) => format!("{} {}", kind, int),
+		Ok(OwnedKey::String(string)) => format!("{} {}", kind, string),
+		_ => String::from(kind),
+	}
+}
@@ -0,0 +1,256 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use mozjs::jsapi::{JS_GetClassObject, JSObject, JSProtoKey};
+
+use crate::{
+	Arguments, Array, Context, Error, ErrorKind, ErrorReport, Exception, Function, Local, Object, OwnedKey,
+	PropertyKey, Result, ResultExc, Value,
+};
+use crate::conversions::ToValue;
+use crate::flags::PropertyFlags;
+
+/// Traps of a [Proxy] which are implemented in Rust.
+///
+/// Each trap returns [None] to forward the operation to the target, as with the corresponding method of `Reflect`.
+pub trait ProxyHandler: 'static {
+	/// Trap for getting a property, such as with `proxy.key`.
+	fn get<'cx>(
+		&self, cx: &'cx Context, target: &Object, key: &OwnedKey, receiver: &Value,
+	) -> ResultExc<Option<Value<'cx>>> {
+		let _ = (cx, target, key, receiver);
+		Ok(None)
+	}
+
+	/// Trap for setting a property, such as with `proxy.key = value`.
+	/// Returns whether the property was set, where `false` throws in strict mode.
+	fn set(
+		&self, cx: &Context, target: &Object, key: &OwnedKey, value: &Value, receiver: &Value,
+	) -> ResultExc<Option<bool>> {
+		let _ = (cx, target, key, value, receiver);
+		Ok(None)
+	}
+
+	/// Trap for checking if a property exists, such as with `key in proxy`.
+	fn has(&self, cx: &Context, target: &Object, key: &OwnedKey) -> ResultExc<Option<bool>> {
+		let _ = (cx, target, key);
+		Ok(None)
+	}
+
+	/// Trap for listing the own properties, such as with `Object.keys(proxy)`.
+	///
+	/// Properties which are not own properties of the target are reported as enumerable data properties, with the value
+	/// returned by [get](ProxyHandler::get), if [has](ProxyHandler::has) returns `true` for them.
+	fn own_keys<'cx>(&self, cx: &'cx Context, target: &Object) -> ResultExc<Option<Vec<OwnedKey<'cx>>>> {
+		let _ = (cx, target);
+		Ok(None)
+	}
+
+	/// Trap for calling the proxy, such as with `proxy(...args)`.
+	/// Only called if the target is callable.
+	fn apply<'cx>(
+		&self, cx: &'cx Context, target: &Object, this: &Value, args: &[Value],
+	) -> ResultExc<Option<Value<'cx>>> {
+		let _ = (cx, target, this, args);
+		Ok(None)
+	}
+}
+
+/// Represents a scripted `Proxy` whose traps are implemented by a [ProxyHandler].
+pub struct Proxy<'p> {
+	proxy: Local<'p, *mut JSObject>,
+}
+
+impl<'p> Proxy<'p> {
+	/// Creates a new [Proxy] of the target, with the traps implemented by the handler.
+	pub fn new<H: ProxyHandler>(cx: &'p Context, target: &Object, handler: H) -> ResultExc<Proxy<'p>> {
+		let handler = Rc::new(handler);
+		let traps = Object::new(cx);
+
+		define_trap(cx, &traps, "get", 3, {
+			let handler = Rc::clone(&handler);
+			move |args| {
+				let cx = args.cx();
+				let (target, key, receiver) = (trap_target(args), argument(args, 1), argument(args, 2));
+				match handler.get(cx, &target, &owned_key(cx, &key)?, &receiver)? {
+					Some(value) => Ok(value),
+					None => reflect(cx, "get", &[Value::object(cx, &target), key, receiver]),
+				}
+			}
+		});
+
+		define_trap(cx, &traps, "set", 4, {
+			let handler = Rc::clone(&handler);
+			move |args| {
+				let cx = args.cx();
+				let (target, key) = (trap_target(args), argument(args, 1));
+				let (value, receiver) = (argument(args, 2), argument(args, 3));
+				match handler.set(cx, &target, &owned_key(cx, &key)?, &value, &receiver)? {
+					Some(set) => Ok(Value::bool(cx, set)),
+					None => reflect(cx, "set", &[Value::object(cx, &target), key, value, receiver]),
+				}
+			}
+		});
+
+		define_trap(cx, &traps, "has", 2, {
+			let handler = Rc::clone(&handler);
+			move |args| {
+				let cx = args.cx();
+				let (target, key) = (trap_target(args), argument(args, 1));
+				match handler.has(cx, &target, &owned_key(cx, &key)?)? {
+					Some(has) => Ok(Value::bool(cx, has)),
+					None => reflect(cx, "has", &[Value::object(cx, &target), key]),
+				}
+			}
+		});
+
+		define_trap(cx, &traps, "ownKeys", 1, {
+			let handler = Rc::clone(&handler);
+			move |args| {
+				let cx = args.cx();
+				let target = trap_target(args);
+				match handler.own_keys(cx, &target)? {
+					Some(keys) => {
+						let keys: Vec<_> = keys.iter().filter_map(|key| key_to_value(cx, key)).collect();
+						Ok(keys.as_value(cx))
+					}
+					None => reflect(cx, "ownKeys", &[Value::object(cx, &target)]),
+				}
+			}
+		});
+
+		define_trap(cx, &traps, "getOwnPropertyDescriptor", 2, {
+			let handler = Rc::clone(&handler);
+			move |args| {
+				let cx = args.cx();
+				let (target, key) = (trap_target(args), argument(args, 1));
+				let owned = owned_key(cx, &key)?;
+				let descriptor = reflect(cx, "getOwnPropertyDescriptor", &[Value::object(cx, &target), key])?;
+				if !descriptor.handle().is_undefined() || handler.has(cx, &target, &owned)? != Some(true) {
+					return Ok(descriptor);
+				}
+
+				let receiver = Value::object(cx, &target);
+				let value = handler.get(cx, &target, &owned, &receiver)?.unwrap_or_else(|| Value::undefined(cx));
+				let descriptor = Object::new(cx);
+				descriptor.set(cx, "value", &value);
+				descriptor.set_as(cx, "writable", &true);
+				descriptor.set_as(cx, "enumerable", &true);
+				descriptor.set_as(cx, "configurable", &true);
+				Ok(descriptor.as_value(cx))
+			}
+		});
+
+		define_trap(cx, &traps, "apply", 3, {
+			let handler = Rc::clone(&handler);
+			move |args| {
+				let cx = args.cx();
+				let (target, this, arguments) = (trap_target(args), argument(args, 1), argument(args, 2));
+				let values = if arguments.handle().is_object() {
+					Array::from(cx, arguments.to_object(cx).into_local()).map(|array| array.to_vec(cx))
+				} else {
+					None
+				};
+				match handler.apply(cx, &target, &this, values.as_deref().unwrap_or_default())? {
+					Some(value) => Ok(value),
+					None => reflect(cx, "apply", &[Value::object(cx, &target), this, arguments]),
+				}
+			}
+		});
+
+		let constructor = class_function(cx, JSProtoKey::JSProto_Proxy)?;
+		let proxy = constructor
+			.construct(cx, &[Value::object(cx, target), Value::object(cx, &traps)])
+			.map_err(report_exception)?;
+		Ok(Proxy { proxy: proxy.into_local() })
+	}
+
+	pub fn into_local(self) -> Local<'p, *mut JSObject> {
+		self.proxy
+	}
+}
+
+impl<'p> Deref for Proxy<'p> {
+	type Target = Local<'p, *mut JSObject>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.proxy
+	}
+}
+
+impl<'p> DerefMut for Proxy<'p> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.proxy
+	}
+}
+
+/// Calls the method of `Reflect` with the given name and arguments, which performs the default behaviour of the
+/// corresponding proxy trap, such as `Reflect.get(target, key, receiver)`.
+///
+/// The `Reflect` object of the realm is used, even if the `Reflect` global has been replaced by scripts.
+pub fn reflect<'cx>(cx: &'cx Context, name: &str, args: &[Value]) -> ResultExc<Value<'cx>> {
+	let object = class_object(cx, JSProtoKey::JSProto_Reflect)?;
+	let method = object.get(cx, name)?.filter(|method| method.handle().is_object());
+	let method = method.and_then(|method| Function::from_object(cx, &method.to_object(cx)));
+	match method {
+		Some(method) => method.call(cx, &object, args).map_err(report_exception),
+		None => Err(Error::new(format!("Reflect.{} is not a function", name), ErrorKind::Type).into()),
+	}
+}
+
+fn define_trap<F>(cx: &Context, traps: &Object, name: &str, nargs: u32, trap: F)
+where
+	F: for<'cx> FnMut(&mut Arguments<'cx>) -> ResultExc<Value<'cx>> + 'static,
+{
+	let function = Function::from_closure(cx, name, Box::new(trap), nargs, PropertyFlags::empty());
+	traps.set(cx, name, &Value::object(cx, &function.to_object(cx)));
+}
+
+fn class_object(cx: &Context, key: JSProtoKey) -> Result<Object> {
+	let mut object = Object::null(cx);
+	if unsafe { JS_GetClassObject(cx.as_ptr(), key, object.handle_mut().into()) } && !object.handle().is_null() {
+		Ok(object)
+	} else {
+		Err(Error::new("Failed to get standard class", ErrorKind::Normal))
+	}
+}
+
+fn class_function(cx: &Context, key: JSProtoKey) -> Result<Function> {
+	let object = class_object(cx, key)?;
+	Function::from_object(cx, &object).ok_or_else(|| Error::new("Expected standard constructor", ErrorKind::Type))
+}
+
+fn trap_target<'cx>(args: &Arguments<'cx>) -> Object<'cx> {
+	argument(args, 0).to_object(args.cx())
+}
+
+fn argument<'cx>(args: &Arguments<'cx>, index: u16) -> Value<'cx> {
+	args.value(index).unwrap_or_else(|| Value::undefined(args.cx()))
+}
+
+fn owned_key<'cx>(cx: &'cx Context, key: &Value) -> Result<OwnedKey<'cx>> {
+	let key = PropertyKey::from_value(cx, key).ok_or_else(|| Error::new("Invalid Property Key", ErrorKind::Type))?;
+	key.to_owned_key(cx)
+}
+
+/// Converts a key to a string or symbol, as the `ownKeys` trap must not return integers.
+fn key_to_value<'cx>(cx: &'cx Context, key: &OwnedKey) -> Option<Value<'cx>> {
+	match key {
+		OwnedKey::Int(int) => Some(Value::string(cx, &int.to_string())),
+		OwnedKey::String(string) => Some(Value::string(cx, string)),
+		OwnedKey::Symbol(symbol) => Some(Value::symbol(cx, symbol)),
+		OwnedKey::Void => None,
+	}
+}
+
+fn report_exception(report: Option<ErrorReport>) -> Exception {
+	report
+		.map(|report| report.exception)
+		.unwrap_or_else(|| Exception::Error(Error::new("Unknown failure in proxy trap", ErrorKind::Normal)))
+}
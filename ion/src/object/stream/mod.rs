@@ -2,5 +2,7 @@ mod memory_backed_readable_stream;
 mod readable_stream;
 mod writable_stream;
 
-pub use readable_stream::{ReadableStream, ReadableStreamReader};
-pub use writable_stream::WritableStream;
+pub use readable_stream::{
+	ReadableStream, ReadableStreamControllerKind, ReadableStreamDiagnostics, ReadableStreamReader, ReadableStreamState,
+};
+pub use writable_stream::{WritableStream, WritableStreamWriter};
@@ -7,12 +7,16 @@ use bytes::Bytes;
 use mozjs::jsapi::{
 	JSObject, ReadableStreamIsLocked, ReadableStreamIsDisturbed, ReadableStreamGetReader, ReadableStreamReaderMode,
 	ReadableStreamReaderReleaseLock, ReadableStreamDefaultReaderRead, AutoRequireNoGC, IsReadableStream, ToStringSlow,
-	IsArrayBufferObject, GetArrayBufferByteLength, GetArrayBufferData, ReadableStreamTee,
+	IsArrayBufferObject, GetArrayBufferByteLength, GetArrayBufferData, ReadableStreamTee, ReadableStreamIsErrored,
+	ReadableStreamIsReadable, ReadableStreamGetDesiredSize, ReadableStreamGetMode, ReadableStreamMode,
+	ReadableStreamGetController,
 };
+use mozjs::glue::JS_GetReservedSlot;
+use mozjs::jsval::{JSVal, UndefinedValue};
 use mozjs_sys::jsapi::{JS_IsArrayBufferViewObject, JS_GetArrayBufferViewByteLength, JS_GetArrayBufferViewData};
 
 use crate::{
-	Context, Error, ErrorKind, Object, Promise, TracedHeap, PromiseFuture, ResultExc, Exception,
+	Context, Error, ErrorKind, Object, Promise, TracedHeap, TypedPromise, ResultExc, Exception, Value,
 	conversions::{FromValue, ToValue},
 	Local,
 };
@@ -86,6 +90,66 @@ impl ReadableStream {
 		Object::from(cx.root(self.stream.root(cx).handle().get()))
 	}
 
+	/// Collects the state of the stream and its controller, to diagnose stalled pipelines.
+	pub fn diagnostics(&self, cx: &Context) -> crate::Result<ReadableStreamDiagnostics> {
+		let stream = self.stream.root(cx);
+		let mut errored = false;
+		let mut readable = false;
+		let mut mode = ReadableStreamMode::Default;
+		let mut has_desired_size = false;
+		let mut desired_size = 0.0;
+
+		unsafe {
+			if !ReadableStreamIsErrored(cx.as_ptr(), stream.handle().into(), &mut errored)
+				|| !ReadableStreamIsReadable(cx.as_ptr(), stream.handle().into(), &mut readable)
+				|| !ReadableStreamGetMode(cx.as_ptr(), stream.handle().into(), &mut mode)
+				|| !ReadableStreamGetDesiredSize(cx.as_ptr(), stream.get(), &mut has_desired_size, &mut desired_size)
+			{
+				return Err(Error::none());
+			}
+		}
+
+		let state = if errored {
+			ReadableStreamState::Errored
+		} else if readable {
+			ReadableStreamState::Readable
+		} else {
+			ReadableStreamState::Closed
+		};
+		let (controller, queue_total_size) = match mode {
+			ReadableStreamMode::Default => (ReadableStreamControllerKind::Default, self.queue_total_size(cx)),
+			ReadableStreamMode::Byte => (ReadableStreamControllerKind::Byte, self.queue_total_size(cx)),
+			_ => (ReadableStreamControllerKind::External, None),
+		};
+		let locked = self.is_locked(cx);
+
+		Ok(ReadableStreamDiagnostics {
+			state,
+			controller,
+			locked,
+			disturbed: self.is_disturbed(cx),
+			reader: locked.then_some(ReadableStreamReaderMode::Default),
+			desired_size: has_desired_size.then_some(desired_size),
+			queue_total_size,
+		})
+	}
+
+	/// Reads the total size of the chunks in the queue of the controller.
+	///
+	/// SpiderMonkey does not expose the queue through its API, so this reads the reserved slot in which its controllers
+	/// store the size.
+	fn queue_total_size(&self, cx: &Context) -> Option<f64> {
+		let mut size = UndefinedValue();
+		unsafe {
+			let controller = ReadableStreamGetController(cx.as_ptr(), self.stream.root(cx).handle().into());
+			if controller.is_null() {
+				return None;
+			}
+			JS_GetReservedSlot(controller, CONTROLLER_QUEUE_TOTAL_SIZE_SLOT, &mut size);
+		}
+		size.is_number().then(|| size.to_number())
+	}
+
 	// Lock the stream and acquire a reader
 	pub fn into_reader(self, cx: &Context) -> crate::Result<ReadableStreamReader> {
 		if self.is_locked(cx) || self.is_disturbed(cx) {
@@ -160,6 +224,100 @@ impl DerefMut for ReadableStream {
 	}
 }
 
+/// Index of `StreamController::Slot_TotalSize` in SpiderMonkey.
+const CONTROLLER_QUEUE_TOTAL_SIZE_SLOT: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadableStreamState {
+	Readable,
+	Closed,
+	Errored,
+}
+
+impl ReadableStreamState {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ReadableStreamState::Readable => "readable",
+			ReadableStreamState::Closed => "closed",
+			ReadableStreamState::Errored => "errored",
+		}
+	}
+}
+
+/// Kind of controller which fills the queue of a [ReadableStream].
+/// External controllers read directly from a native source, and do not have a queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadableStreamControllerKind {
+	Default,
+	Byte,
+	External,
+}
+
+impl ReadableStreamControllerKind {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ReadableStreamControllerKind::Default => "default",
+			ReadableStreamControllerKind::Byte => "byte",
+			ReadableStreamControllerKind::External => "external",
+		}
+	}
+}
+
+/// Snapshot of the state of a [ReadableStream], returned by [ReadableStream::diagnostics].
+///
+/// SpiderMonkey does not expose the number of queued chunks or pending read requests, so only the total size of the
+/// queue is reported. The reader is always a default reader, as BYOB readers are not supported.
+#[derive(Clone, Copy, Debug)]
+pub struct ReadableStreamDiagnostics {
+	pub state: ReadableStreamState,
+	pub controller: ReadableStreamControllerKind,
+	pub locked: bool,
+	pub disturbed: bool,
+	pub reader: Option<ReadableStreamReaderMode>,
+	pub desired_size: Option<f64>,
+	pub queue_total_size: Option<f64>,
+}
+
+impl<'cx> ToValue<'cx> for ReadableStreamDiagnostics {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "state", self.state.as_str());
+		object.set_as(cx, "controller", self.controller.as_str());
+		object.set_as(cx, "locked", &self.locked);
+		object.set_as(cx, "disturbed", &self.disturbed);
+		object.set_as(cx, "reader", &self.reader.map(|_| "default"));
+		object.set_as(cx, "desiredSize", &self.desired_size);
+		object.set_as(cx, "queueTotalSize", &self.queue_total_size);
+		object.to_value(cx, value);
+	}
+}
+
+/// Result of reading a chunk from a [ReadableStreamReader].
+pub struct ReadResult {
+	pub done: bool,
+	pub value: TracedHeap<JSVal>,
+}
+
+impl<'cx> FromValue<'cx> for ReadResult {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> crate::Result<ReadResult> {
+		if !value.handle().is_object() {
+			return Err(Error::new(
+				"ReadableStreamDefaultReader.read() should return an object",
+				ErrorKind::Type,
+			));
+		}
+		let object = value.to_object(cx);
+		let done = object.get_as(cx, "done", strict, ())?.unwrap_or_default();
+		let value = object.get(cx, "value")?.unwrap_or_else(|| Value::undefined(cx));
+		Ok(ReadResult {
+			done,
+			value: TracedHeap::new(value.get()),
+		})
+	}
+}
+
 pub struct ReadableStreamReader {
 	stream: TracedHeap<*mut JSObject>,
 	reader: TracedHeap<*mut JSObject>,
@@ -198,38 +356,16 @@ impl ReadableStreamReader {
 
 	// Safety: The returned slice must be consumed before the next
 	// SpiderMonkey API call, which may cause GC to collect the chunk
-	pub async unsafe fn read_chunk<'cx>(&self, mut cx: Context) -> ResultExc<Option<Cow<[u8]>>> {
-		let chunk = self.read_chunk_raw(&cx);
-		let chunk_val;
-		(cx, chunk_val) = PromiseFuture::new(cx, &chunk).await;
-		let chunk = match chunk_val {
-			Ok(v) => {
-				if !v.get().is_object() {
-					return Err(Exception::Error(Error::new(
-						"ReadableStreamDefaultReader.read() should return an object",
-						ErrorKind::Type,
-					)));
-				}
-				Object::from(cx.root(v.get().to_object()))
-			}
-			Err(v) => {
-				return Err(Exception::Other(v.get()));
-			}
-		};
+	pub async unsafe fn read_chunk<'cx>(&self, cx: Context) -> ResultExc<Option<Cow<[u8]>>> {
+		let chunk = TypedPromise::<ReadResult>::new(self.read_chunk_raw(&cx));
+		let (cx, result) = chunk.wait_with(cx, true, ()).await;
+		let result = result?;
 
-		let done = bool::from_value(
-			&cx,
-			&chunk.get(&cx, "done")?.expect("Chunk must have a done property"),
-			true,
-			(),
-		)
-		.expect("chunk.done must be a boolean");
-
-		if done {
+		if result.done {
 			return Ok(None);
 		}
 
-		let obj = chunk.get(&cx, "value")?.expect("Chunk must have a value property");
+		let obj = Value::from(result.value.root(&cx));
 		if obj.get().is_string() {
 			let str = crate::String::from(cx.root(obj.get().to_string()));
 			let str = str.to_owned(&cx)?;
@@ -265,8 +401,14 @@ impl ReadableStreamReader {
 		)))
 	}
 
-	pub async fn read_to_end(&self, mut cx: Context) -> ResultExc<Vec<u8>> {
-		let mut result = vec![];
+	pub async fn read_to_end(&self, cx: Context) -> ResultExc<Vec<u8>> {
+		self.read_to_end_with_capacity(cx, 0).await
+	}
+
+	/// Reads the remaining chunks of the stream, into a buffer which is preallocated with the given capacity.
+	/// This avoids reallocating the buffer as it grows, when the length of the stream is known in advance.
+	pub async fn read_to_end_with_capacity(&self, mut cx: Context, capacity: usize) -> ResultExc<Vec<u8>> {
+		let mut result = Vec::with_capacity(capacity);
 
 		loop {
 			let chunk;
@@ -1,6 +1,12 @@
+use std::ops::{Deref, DerefMut};
+
 use mozjs::jsapi::{JSObject, IsWritableStream, WritableStreamIsLocked};
+use mozjs::jsval::JSVal;
 
-use crate::{TracedHeap, Object, Context, Local};
+use crate::{
+	Context, Error, ErrorKind, Exception, Function, Local, Object, Promise, ResultExc, TracedHeap, TypedPromise, Value,
+	conversions::{FromValue, ToValue},
+};
 
 pub struct WritableStream {
 	// Since streams are async by nature, they cannot be tied to the lifetime
@@ -9,6 +15,14 @@ pub struct WritableStream {
 }
 
 impl WritableStream {
+	pub fn new(obj: *mut JSObject) -> Option<Self> {
+		if unsafe { IsWritableStream(obj) } {
+			Some(Self { stream: TracedHeap::new(obj) })
+		} else {
+			None
+		}
+	}
+
 	pub fn from_local(local: Local<'_, *mut JSObject>) -> Option<Self> {
 		if Self::is_writable_stream(&local) {
 			Some(Self { stream: TracedHeap::from_local(&local) })
@@ -32,4 +46,174 @@ impl WritableStream {
 	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
 		Object::from(cx.root(self.stream.root(cx).handle().get()))
 	}
+
+	// Lock the stream and acquire a writer
+	pub fn into_writer(self, cx: &Context) -> ResultExc<WritableStreamWriter> {
+		if self.is_locked(cx) {
+			return Err(Error::new("Stream is already locked", ErrorKind::Normal).into());
+		}
+
+		let writer = call_method(cx, &self.to_object(cx), "getWriter", &[])?;
+		if !writer.get().is_object() {
+			return Err(Error::new("WritableStream.getWriter() should return an object", ErrorKind::Type).into());
+		}
+
+		Ok(WritableStreamWriter {
+			stream: self.stream,
+			writer: TracedHeap::new(writer.get().to_object()),
+		})
+	}
+
+	pub fn abort(&self, cx: &Context, reason: &Value) -> ResultExc<Promise> {
+		let promise = call_method(cx, &self.to_object(cx), "abort", &[Value::from(cx.root(reason.get()))])?;
+		to_promise(cx, &promise, "WritableStream.abort()")
+	}
+
+	pub fn close(&self, cx: &Context) -> ResultExc<Promise> {
+		let promise = call_method(cx, &self.to_object(cx), "close", &[])?;
+		to_promise(cx, &promise, "WritableStream.close()")
+	}
+}
+
+impl<'cx> FromValue<'cx> for WritableStream {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, _strict: bool, _config: Self::Config) -> crate::Result<Self> {
+		if !value.get().is_object() {
+			return Err(Error::new("Expected object for writable stream", ErrorKind::Type));
+		}
+
+		Self::new((*value.to_object(cx)).get())
+			.ok_or_else(|| Error::new("The given object is not a writable stream", ErrorKind::Type))
+	}
+}
+
+impl<'cx> ToValue<'cx> for WritableStream {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		self.stream.get().to_value(cx, value)
+	}
+}
+
+impl Deref for WritableStream {
+	type Target = TracedHeap<*mut JSObject>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.stream
+	}
+}
+
+impl DerefMut for WritableStream {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.stream
+	}
+}
+
+pub struct WritableStreamWriter {
+	stream: TracedHeap<*mut JSObject>,
+	writer: TracedHeap<*mut JSObject>,
+}
+
+impl WritableStreamWriter {
+	fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		Object::from(cx.root(self.writer.get()))
+	}
+
+	// Release the stream lock and turn the writer back into a stream
+	pub fn into_stream(self, cx: &Context) -> ResultExc<WritableStream> {
+		call_method(cx, &self.to_object(cx), "releaseLock", &[])?;
+		Ok(WritableStream { stream: self.stream })
+	}
+
+	/// Returns the amount of data that can be written before the stream's queue is full.
+	/// Returns [None] if the stream is errored, and 0 if it is closed.
+	pub fn desired_size(&self, cx: &Context) -> ResultExc<Option<f64>> {
+		let size = self.to_object(cx).get(cx, "desiredSize")?;
+		match size {
+			Some(size) if size.get().is_number() => Ok(Some(size.get().to_number())),
+			_ => Ok(None),
+		}
+	}
+
+	/// Returns a promise that resolves when the desired size of the stream becomes positive.
+	pub fn ready_raw(&self, cx: &Context) -> ResultExc<Promise> {
+		let ready = self.to_object(cx).get(cx, "ready")?.unwrap_or_else(|| Value::undefined(cx));
+		to_promise(cx, &ready, "WritableStreamDefaultWriter.ready")
+	}
+
+	/// Returns a promise that resolves when the stream is closed.
+	pub fn closed_raw(&self, cx: &Context) -> ResultExc<Promise> {
+		let closed = self.to_object(cx).get(cx, "closed")?.unwrap_or_else(|| Value::undefined(cx));
+		to_promise(cx, &closed, "WritableStreamDefaultWriter.closed")
+	}
+
+	pub fn write_chunk_raw(&self, cx: &Context, chunk: &Value) -> ResultExc<Promise> {
+		let promise = call_method(cx, &self.to_object(cx), "write", &[Value::from(cx.root(chunk.get()))])?;
+		to_promise(cx, &promise, "WritableStreamDefaultWriter.write()")
+	}
+
+	pub fn close_raw(&self, cx: &Context) -> ResultExc<Promise> {
+		let promise = call_method(cx, &self.to_object(cx), "close", &[])?;
+		to_promise(cx, &promise, "WritableStreamDefaultWriter.close()")
+	}
+
+	pub fn abort_raw(&self, cx: &Context, reason: &Value) -> ResultExc<Promise> {
+		let promise = call_method(cx, &self.to_object(cx), "abort", &[Value::from(cx.root(reason.get()))])?;
+		to_promise(cx, &promise, "WritableStreamDefaultWriter.abort()")
+	}
+
+	/// Waits until the stream can accept more data, honouring back-pressure.
+	pub async fn ready(&self, cx: Context) -> (Context, ResultExc<()>) {
+		match self.ready_raw(&cx) {
+			Ok(promise) => await_promise(cx, promise).await,
+			Err(e) => (cx, Err(e)),
+		}
+	}
+
+	/// Waits for the stream to be ready, then writes a chunk and waits for it to be processed.
+	pub async fn write_chunk(&self, cx: Context, chunk: TracedHeap<JSVal>) -> (Context, ResultExc<()>) {
+		let (cx, result) = self.ready(cx).await;
+		if let Err(e) = result {
+			return (cx, Err(e));
+		}
+
+		let promise = self.write_chunk_raw(&cx, &chunk.root(&cx).into());
+		match promise {
+			Ok(promise) => await_promise(cx, promise).await,
+			Err(e) => (cx, Err(e)),
+		}
+	}
+
+	pub async fn close(&self, cx: Context) -> (Context, ResultExc<()>) {
+		match self.close_raw(&cx) {
+			Ok(promise) => await_promise(cx, promise).await,
+			Err(e) => (cx, Err(e)),
+		}
+	}
+}
+
+fn call_method<'cx>(cx: &'cx Context, object: &Object, name: &str, args: &[Value]) -> ResultExc<Value<'cx>> {
+	let function = object.get_as::<_, Function>(cx, name, true, ())?.ok_or_else(|| {
+		Exception::Error(Error::new(
+			format!("Stream object does not have a {} method", name),
+			ErrorKind::Type,
+		))
+	})?;
+	match function.call(cx, object, args) {
+		Ok(value) => Ok(value),
+		Err(Some(report)) => Err(report.exception),
+		Err(None) => Err(Error::none().into()),
+	}
+}
+
+fn to_promise(cx: &Context, value: &Value, name: &str) -> ResultExc<Promise> {
+	if value.get().is_object() {
+		if let Some(promise) = Promise::from(value.to_object(cx).into_local()) {
+			return Ok(promise);
+		}
+	}
+	Err(Error::new(format!("{} should return a Promise", name), ErrorKind::Type).into())
+}
+
+async fn await_promise(cx: Context, promise: Promise) -> (Context, ResultExc<()>) {
+	TypedPromise::<()>::new(promise).wait(cx).await
 }
@@ -8,6 +8,7 @@ use std::{ptr, slice};
 use std::ffi::c_void;
 use std::ops::{Deref, DerefMut};
 
+use bytes::Bytes;
 use mozjs::jsapi::{
 	ArrayBufferClone, ArrayBufferCopyData, DetachArrayBuffer, IsDetachedArrayBufferObject, JSObject,
 	NewArrayBufferWithContents, NewExternalArrayBuffer, StealArrayBufferContents, GetArrayBufferLengthAndData,
@@ -40,8 +41,35 @@ impl<'ab> ArrayBuffer<'ab> {
 	}
 
 	/// Creates a new [ArrayBuffer] by transferring ownership of the bytes to the JS runtime.
+	/// The allocation of the vector is adopted as is, so excess capacity is not reallocated away.
 	pub fn from_vec(cx: &Context, bytes: Vec<u8>) -> Option<ArrayBuffer> {
-		ArrayBuffer::from_boxed_slice(cx, bytes.into_boxed_slice())
+		unsafe extern "C" fn free_external_vec(_: *mut c_void, data: *mut c_void) {
+			let _ = unsafe { Box::from_raw(data.cast::<Vec<u8>>()) };
+		}
+
+		let mut bytes = Box::new(bytes);
+		let (ptr, len) = (bytes.as_mut_ptr(), bytes.len());
+		let buffer = unsafe {
+			NewExternalArrayBuffer(
+				cx.as_ptr(),
+				len,
+				ptr.cast(),
+				Some(free_external_vec),
+				Box::into_raw(bytes).cast(),
+			)
+		};
+
+		if buffer.is_null() {
+			None
+		} else {
+			Some(ArrayBuffer { buffer: cx.root(buffer) })
+		}
+	}
+
+	/// Creates a new [ArrayBuffer] from [Bytes].
+	/// The allocation is adopted without copying if the [Bytes] uniquely owns it, otherwise the bytes are copied.
+	pub fn from_bytes(cx: &Context, bytes: Bytes) -> Option<ArrayBuffer> {
+		ArrayBuffer::from_vec(cx, Vec::from(bytes))
 	}
 
 	/// Creates a new [ArrayBuffer] by transferring ownership of the bytes to the JS runtime.
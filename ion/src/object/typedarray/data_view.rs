@@ -0,0 +1,122 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{ptr, slice};
+use std::ops::{Deref, DerefMut};
+
+use mozjs::jsapi::{
+	GetArrayBufferViewLengthAndData, JS_GetArrayBufferViewBuffer, JS_GetArrayBufferViewByteLength,
+	JS_GetArrayBufferViewByteOffset, JS_GetArrayBufferViewType, JS_IsArrayBufferViewObject, JS_NewDataView, JSObject,
+	Type,
+};
+
+use crate::{Context, Local};
+use crate::typedarray::buffer::ArrayBuffer;
+
+pub struct DataView<'dv> {
+	view: Local<'dv, *mut JSObject>,
+}
+
+impl<'dv> DataView<'dv> {
+	/// Creates a new [DataView] with a view of the contents of an existing [ArrayBuffer].
+	pub fn new(
+		cx: &'dv Context, buffer: &ArrayBuffer, byte_offset: usize, byte_length: usize,
+	) -> Option<DataView<'dv>> {
+		let view = unsafe { JS_NewDataView(cx.as_ptr(), buffer.handle().into(), byte_offset, byte_length) };
+
+		if view.is_null() {
+			None
+		} else {
+			Some(DataView { view: cx.root(view) })
+		}
+	}
+
+	pub fn from(object: Local<*mut JSObject>) -> Option<DataView> {
+		if DataView::is_data_view(object.get()) {
+			Some(DataView { view: object })
+		} else {
+			None
+		}
+	}
+
+	pub unsafe fn from_unchecked(object: Local<*mut JSObject>) -> DataView {
+		DataView { view: object }
+	}
+
+	/// Returns a pointer and length to the contents of the [DataView].
+	///
+	/// The pointer may be invalidated if the underlying [ArrayBuffer] is detached.
+	pub fn data(&self) -> (*mut u8, usize) {
+		let mut len = 0;
+		let mut shared = false;
+		let mut data = ptr::null_mut();
+		unsafe { GetArrayBufferViewLengthAndData(self.get(), &mut len, &mut shared, &mut data) };
+		(data, len)
+	}
+
+	/// Returns a slice to the contents of the [DataView].
+	///
+	/// The slice may be invalidated if the underlying [ArrayBuffer] is detached.
+	pub unsafe fn as_slice(&self) -> &[u8] {
+		let (ptr, len) = self.data();
+		unsafe { slice::from_raw_parts(ptr, len) }
+	}
+
+	/// Returns a mutable slice to the contents of the [DataView].
+	///
+	/// The slice may be invalidated if the underlying [ArrayBuffer] is detached.
+	#[allow(clippy::mut_from_ref)]
+	pub unsafe fn as_mut_slice(&self) -> &mut [u8] {
+		let (ptr, len) = self.data();
+		unsafe { slice::from_raw_parts_mut(ptr, len) }
+	}
+
+	/// Returns the offset of the [DataView] with respect to the underlying [ArrayBuffer].
+	pub fn offset(&self) -> usize {
+		unsafe { JS_GetArrayBufferViewByteOffset(self.get()) }
+	}
+
+	/// Returns the length of the [DataView] in bytes.
+	pub fn byte_length(&self) -> usize {
+		unsafe { JS_GetArrayBufferViewByteLength(self.get()) }
+	}
+
+	/// Returns the underlying [ArrayBuffer]. The buffer may be shared and/or detached.
+	pub fn buffer<'ab>(&self, cx: &'ab Context) -> ArrayBuffer<'ab> {
+		let mut shared = false;
+		ArrayBuffer::from(
+			cx.root(unsafe { JS_GetArrayBufferViewBuffer(cx.as_ptr(), self.handle().into(), &mut shared) }),
+		)
+		.unwrap()
+	}
+
+	pub fn into_local(self) -> Local<'dv, *mut JSObject> {
+		self.view
+	}
+
+	/// Checks if an object is a data view.
+	/// Data views are the array buffer views which are not typed arrays.
+	#[allow(clippy::not_unsafe_ptr_arg_deref)]
+	pub fn is_data_view(object: *mut JSObject) -> bool {
+		unsafe {
+			JS_IsArrayBufferViewObject(object) && JS_GetArrayBufferViewType(object) == Type::MaxTypedArrayViewType
+		}
+	}
+}
+
+impl<'dv> Deref for DataView<'dv> {
+	type Target = Local<'dv, *mut JSObject>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.view
+	}
+}
+
+impl<'dv> DerefMut for DataView<'dv> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.view
+	}
+}
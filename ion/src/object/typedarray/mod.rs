@@ -6,36 +6,44 @@
 
 use std::ops::Deref;
 
+use bytes::Bytes;
 use mozjs::typedarray::{ArrayBufferU8, ClampedU8, Float32, Float64, Int16, Int32, Int8, Uint16, Uint32, Uint8};
 use mozjs::typedarray as jsta;
 
 pub use buffer::*;
+pub use data_view::*;
 pub use view::*;
 
 use crate::{Context, Value};
 use crate::conversions::{IntoValue, ToValue};
 
 mod buffer;
+mod data_view;
 mod view;
 
 pub struct ArrayBufferWrapper {
-	buf: Box<[<ArrayBufferU8 as jsta::TypedArrayElement>::Element]>,
+	buf: Vec<<ArrayBufferU8 as jsta::TypedArrayElement>::Element>,
 }
 
 impl ArrayBufferWrapper {
+	/// Creates an [ArrayBufferWrapper] which adopts the allocation of the [Bytes] if it is uniquely owned.
+	pub fn from_bytes(bytes: Bytes) -> ArrayBufferWrapper {
+		ArrayBufferWrapper { buf: Vec::from(bytes) }
+	}
+
 	pub fn into_array_buffer(self, cx: &Context) -> Option<ArrayBuffer> {
-		ArrayBuffer::from_boxed_slice(cx, self.buf)
+		ArrayBuffer::from_vec(cx, self.buf)
 	}
 }
 
-impl<B: Into<Box<[<ArrayBufferU8 as jsta::TypedArrayElement>::Element]>>> From<B> for ArrayBufferWrapper {
+impl<B: Into<Vec<<ArrayBufferU8 as jsta::TypedArrayElement>::Element>>> From<B> for ArrayBufferWrapper {
 	fn from(buffer: B) -> ArrayBufferWrapper {
 		ArrayBufferWrapper { buf: buffer.into() }
 	}
 }
 
 impl Deref for ArrayBufferWrapper {
-	type Target = Box<[<ArrayBufferU8 as jsta::TypedArrayElement>::Element]>;
+	type Target = Vec<<ArrayBufferU8 as jsta::TypedArrayElement>::Element>;
 
 	fn deref(&self) -> &Self::Target {
 		&self.buf
@@ -50,52 +58,53 @@ impl<'cx> IntoValue<'cx> for ArrayBufferWrapper {
 	}
 }
 
-macro_rules! impl_typedarray_wrapper {
-	($(($typedarray:ident, $element:ty)$(,)?)*) => {
-		$(
-			pub struct $typedarray {
-				buf: Box<[<$element as jsta::TypedArrayElement>::Element]>,
-			}
-
-			impl $typedarray {
-				pub fn into_typed_array(self, cx: &Context) -> Option<TypedArray<$element>> {
-					TypedArray::from_boxed_slice(cx, self.buf)
-				}
-			}
-
-			impl<B: Into<Box<[<$element as jsta::TypedArrayElement>::Element]>>> From<B> for $typedarray {
-				fn from(buffer: B) -> $typedarray {
-					$typedarray { buf: buffer.into() }
-				}
-			}
-
-			impl Deref for $typedarray {
-				type Target = Box<[<$element as jsta::TypedArrayElement>::Element]>;
-
-				fn deref(&self) -> &Self::Target {
-					&self.buf
-				}
-			}
-
-			impl<'cx> IntoValue<'cx> for $typedarray {
-				fn into_value(self: Box<Self>, cx: &'cx Context, value: &mut Value) {
-					if let Some(array) =  self.into_typed_array(cx) {
-						array.to_value(cx, value);
-					}
-				}
-			}
-		)*
-	};
+/// Owned elements of a [TypedArray], which are transferred to the JS runtime without copying when it is converted to a
+/// value, such as when it is returned from a native function.
+pub struct TypedArrayWrapper<T: TypedArrayElementCreator> {
+	buf: Vec<T::Element>,
+}
+
+impl<T: TypedArrayElementCreator> TypedArrayWrapper<T> {
+	pub fn into_typed_array(self, cx: &Context) -> Option<TypedArray<T>> {
+		TypedArray::from_vec(cx, self.buf)
+	}
+}
+
+impl TypedArrayWrapper<Uint8> {
+	/// Creates a [Uint8ArrayWrapper] which adopts the allocation of the [Bytes] if it is uniquely owned.
+	pub fn from_bytes(bytes: Bytes) -> Uint8ArrayWrapper {
+		TypedArrayWrapper { buf: Vec::from(bytes) }
+	}
+}
+
+impl<T: TypedArrayElementCreator, B: Into<Vec<T::Element>>> From<B> for TypedArrayWrapper<T> {
+	fn from(buffer: B) -> TypedArrayWrapper<T> {
+		TypedArrayWrapper { buf: buffer.into() }
+	}
+}
+
+impl<T: TypedArrayElementCreator> Deref for TypedArrayWrapper<T> {
+	type Target = Vec<T::Element>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.buf
+	}
+}
+
+impl<'cx, T: TypedArrayElementCreator> IntoValue<'cx> for TypedArrayWrapper<T> {
+	fn into_value(self: Box<Self>, cx: &'cx Context, value: &mut Value) {
+		if let Some(array) = self.into_typed_array(cx) {
+			array.to_value(cx, value);
+		}
+	}
 }
 
-impl_typedarray_wrapper!(
-	(Uint8ArrayWrapper, Uint8),
-	(Uint16ArrayWrapper, Uint16),
-	(Uint32ArrayWrapper, Uint32),
-	(Int8ArrayWrapper, Int8),
-	(Int16ArrayWrapper, Int16),
-	(Int32ArrayWrapper, Int32),
-	(Float32ArrayWrapper, Float32),
-	(Float64ArrayWrapper, Float64),
-	(ClampedUint8ArrayWrapper, ClampedU8),
-);
+pub type Uint8ArrayWrapper = TypedArrayWrapper<Uint8>;
+pub type Uint16ArrayWrapper = TypedArrayWrapper<Uint16>;
+pub type Uint32ArrayWrapper = TypedArrayWrapper<Uint32>;
+pub type Int8ArrayWrapper = TypedArrayWrapper<Int8>;
+pub type Int16ArrayWrapper = TypedArrayWrapper<Int16>;
+pub type Int32ArrayWrapper = TypedArrayWrapper<Int32>;
+pub type Float32ArrayWrapper = TypedArrayWrapper<Float32>;
+pub type Float64ArrayWrapper = TypedArrayWrapper<Float64>;
+pub type ClampedUint8ArrayWrapper = TypedArrayWrapper<ClampedU8>;
@@ -10,6 +10,7 @@ use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::{Deref, DerefMut};
 
+use bytes::Bytes;
 use mozjs::jsapi::{
 	GetArrayBufferViewLengthAndData, HandleObject, IsLargeArrayBufferView, JS_GetArrayBufferViewBuffer,
 	JS_GetArrayBufferViewByteLength, JS_GetArrayBufferViewByteOffset, JS_GetArrayBufferViewType,
@@ -110,8 +111,30 @@ impl<'bv, T: TypedArrayElementCreator> TypedArray<'bv, T> {
 	}
 
 	/// Creates a new [TypedArray] by transferring ownership of the values to the JS runtime.
-	pub fn from_vec(cx: &Context, bytes: Vec<T::Element>) -> Option<TypedArray<T>> {
-		TypedArray::from_boxed_slice(cx, bytes.into_boxed_slice())
+	/// The allocation of the vector is adopted as is, so excess capacity is not reallocated away.
+	pub fn from_vec(cx: &Context, values: Vec<T::Element>) -> Option<TypedArray<T>> {
+		unsafe extern "C" fn free_external_vec<T: TypedArrayElementCreator>(_: *mut c_void, data: *mut c_void) {
+			let _ = unsafe { Box::from_raw(data.cast::<Vec<T::Element>>()) };
+		}
+
+		let mut values = Box::new(values);
+		let (ptr, len) = (values.as_mut_ptr(), values.len());
+		let buffer = unsafe {
+			NewExternalArrayBuffer(
+				cx.as_ptr(),
+				len * size_of::<T::Element>(),
+				ptr.cast(),
+				Some(free_external_vec::<T>),
+				Box::into_raw(values).cast(),
+			)
+		};
+
+		if buffer.is_null() {
+			return None;
+		}
+
+		let buffer = ArrayBuffer::from(cx.root(buffer)).unwrap();
+		TypedArray::with_array_buffer(cx, &buffer, 0, len)
 	}
 
 	/// Creates a new [TypedArray] by transferring ownership of the bytes to the JS runtime.
@@ -156,6 +179,28 @@ impl<'bv, T: TypedArrayElementCreator> TypedArray<'bv, T> {
 			})
 		}
 	}
+
+	/// Creates a new [TypedArray] with a view of the elements from `begin` to `end`, without copying them.
+	/// The indices are clamped to the length of the [TypedArray].
+	pub fn subarray<'cx>(&self, cx: &'cx Context, begin: usize, end: usize) -> Option<TypedArray<'cx, T>> {
+		let end = end.min(self.len());
+		let begin = begin.min(end);
+		let buffer = self.buffer(cx);
+		TypedArray::with_array_buffer(
+			cx,
+			&buffer,
+			self.offset() + begin * size_of::<T::Element>(),
+			end - begin,
+		)
+	}
+}
+
+impl<'bv> TypedArray<'bv, Uint8> {
+	/// Creates a new [Uint8Array] from [Bytes].
+	/// The allocation is adopted without copying if the [Bytes] uniquely owns it, otherwise the bytes are copied.
+	pub fn from_bytes(cx: &Context, bytes: Bytes) -> Option<Uint8Array> {
+		TypedArray::from_vec(cx, Vec::from(bytes))
+	}
 }
 
 impl<'bv, T: TypedArrayElement> TypedArray<'bv, T> {
@@ -183,6 +228,15 @@ impl<'bv, T: TypedArrayElement> TypedArray<'bv, T> {
 		(data.cast::<T::Element>(), len / size_of::<T::Element>())
 	}
 
+	/// Returns the number of elements in the [TypedArray].
+	pub fn len(&self) -> usize {
+		self.data().1
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
 	/// Returns a slice to the contents of the [TypedArray].
 	///
 	/// The slice may be invalidated if the underlying [ArrayBuffer] is detached.
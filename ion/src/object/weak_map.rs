@@ -0,0 +1,158 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::ptr;
+use std::rc::Rc;
+
+use mozjs::glue::JS_GetReservedSlot;
+use mozjs::jsapi::{
+	GCContext, GetWeakMapEntry, JS_GetClassPrototype, JS_NewObject, JS_SetReservedSlot, JSClass,
+	JSCLASS_FOREGROUND_FINALIZE, JSClassOps, JSObject, JSProtoKey, NewWeakMapObject, SetWeakMapEntry,
+};
+use mozjs::jsval::{ObjectValue, PrivateValue, UndefinedValue};
+
+use crate::{Context, Function, Object, TracedHeap, Value};
+use crate::object::class_reserved_slots;
+
+const VALUE_SLOT: u32 = 0;
+
+type ValuePrivate = Box<dyn Any>;
+
+/// Represents a map from JavaScript [Objects](Object) to Rust values, which does not keep its keys alive.
+///
+/// Each value is held by an object in a JavaScript WeakMap, and is dropped when that object is finalised,
+/// after its key has been garbage collected or the entry has been replaced.
+/// Values are finalised on the main thread, so they do not need to be [Send].
+pub struct WeakMap<T: 'static> {
+	map: TracedHeap<*mut JSObject>,
+	_marker: PhantomData<T>,
+}
+
+impl<T: 'static> WeakMap<T> {
+	/// Creates a new empty [WeakMap].
+	pub fn new(cx: &Context) -> WeakMap<T> {
+		WeakMap {
+			map: TracedHeap::new(unsafe { NewWeakMapObject(cx.as_ptr()) }),
+			_marker: PhantomData,
+		}
+	}
+
+	/// Checks if the [WeakMap] contains a value for the given key.
+	pub fn has(&self, cx: &Context, key: &Object) -> bool {
+		self.get_holder(cx, key).is_some()
+	}
+
+	/// Returns the value of the [WeakMap] for the given key.
+	pub fn get(&self, cx: &Context, key: &Object) -> Option<Rc<T>> {
+		let holder = self.get_holder(cx, key)?;
+		let value = unsafe { get_value(holder) };
+		value.downcast_ref::<Rc<T>>().cloned()
+	}
+
+	/// Sets the value of the [WeakMap] for the given key.
+	/// The previous value is dropped once it is no longer reachable.
+	pub fn set(&self, cx: &Context, key: &Object, value: T) -> bool {
+		unsafe {
+			let holder = JS_NewObject(cx.as_ptr(), &WEAK_MAP_VALUE_CLASS);
+			if holder.is_null() {
+				return false;
+			}
+			let value: ValuePrivate = Box::new(Rc::new(value));
+			JS_SetReservedSlot(
+				holder,
+				VALUE_SLOT,
+				&PrivateValue(Box::into_raw(Box::new(value)).cast_const().cast()),
+			);
+
+			let holder = Value::from(cx.root(ObjectValue(holder)));
+			SetWeakMapEntry(
+				cx.as_ptr(),
+				self.map.root(cx).handle().into(),
+				key.handle().into(),
+				holder.handle().into(),
+			)
+		}
+	}
+
+	/// Removes the entry of the [WeakMap] for the given key, and returns its value.
+	/// The entry is deleted with `WeakMap.prototype.delete`, as there is no native API to delete entries.
+	pub fn remove(&self, cx: &Context, key: &Object) -> Option<Rc<T>> {
+		let value = self.get(cx, key)?;
+		let delete = weak_map_delete(cx)?;
+		delete.call(cx, &self.to_object(cx), &[Value::object(cx, key)]).ok()?;
+		Some(value)
+	}
+
+	/// Returns the underlying JavaScript WeakMap.
+	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		Object::from(self.map.root(cx))
+	}
+
+	fn get_holder(&self, cx: &Context, key: &Object) -> Option<*mut JSObject> {
+		let mut holder = Value::undefined(cx);
+		let success = unsafe {
+			GetWeakMapEntry(
+				cx.as_ptr(),
+				self.map.root(cx).handle().into(),
+				key.handle().into(),
+				holder.handle_mut().into(),
+			)
+		};
+		(success && holder.handle().is_object()).then(|| holder.handle().to_object())
+	}
+}
+
+/// Returns `WeakMap.prototype.delete` of the current realm.
+fn weak_map_delete<'cx>(cx: &'cx Context) -> Option<Function<'cx>> {
+	let mut prototype = Object::null(cx);
+	if !unsafe { JS_GetClassPrototype(cx.as_ptr(), JSProtoKey::JSProto_WeakMap, prototype.handle_mut().into()) } {
+		return None;
+	}
+	let delete = prototype.get(cx, "delete").ok()??;
+	delete.handle().is_object().then(|| Function::from_object(cx, &delete.to_object(cx)))?
+}
+
+unsafe fn get_value<'a>(holder: *mut JSObject) -> &'a ValuePrivate {
+	let mut value = UndefinedValue();
+	unsafe {
+		JS_GetReservedSlot(holder, VALUE_SLOT, &mut value);
+		&*value.to_private().cast::<ValuePrivate>()
+	}
+}
+
+unsafe extern "C" fn finalise_value(_: *mut GCContext, object: *mut JSObject) {
+	let mut value = UndefinedValue();
+	unsafe {
+		JS_GetReservedSlot(object, VALUE_SLOT, &mut value);
+		if !value.is_undefined() {
+			let _ = Box::from_raw(value.to_private().cast::<ValuePrivate>().cast_mut());
+		}
+	}
+}
+
+static WEAK_MAP_VALUE_OPS: JSClassOps = JSClassOps {
+	addProperty: None,
+	delProperty: None,
+	enumerate: None,
+	newEnumerate: None,
+	resolve: None,
+	mayResolve: None,
+	finalize: Some(finalise_value),
+	call: None,
+	construct: None,
+	trace: None,
+};
+
+static WEAK_MAP_VALUE_CLASS: JSClass = JSClass {
+	name: "WeakMapValue\0".as_ptr().cast(),
+	flags: JSCLASS_FOREGROUND_FINALIZE | class_reserved_slots(1),
+	cOps: &WEAK_MAP_VALUE_OPS,
+	spec: ptr::null_mut(),
+	ext: ptr::null_mut(),
+	oOps: ptr::null_mut(),
+};
@@ -0,0 +1,45 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::{JS_WrapObject, JS_WrapValue, JSAutoRealm};
+
+use crate::{Context, Object, Value};
+
+/// Enters the realm of a global, so that objects are created within it, until it is dropped and the previous realm is
+/// entered again.
+///
+/// Values created within the realm must be [wrapped](wrap_value) before they are used in another realm.
+#[must_use]
+pub struct RealmGuard {
+	_realm: JSAutoRealm,
+}
+
+impl RealmGuard {
+	pub fn enter(cx: &Context, global: &Object) -> RealmGuard {
+		RealmGuard {
+			_realm: JSAutoRealm::new(cx.as_ptr(), global.handle().get()),
+		}
+	}
+}
+
+/// Runs a callback within the realm of a global.
+pub fn in_realm<T, F: FnOnce() -> T>(cx: &Context, global: &Object, callback: F) -> T {
+	let _realm = RealmGuard::enter(cx, global);
+	callback()
+}
+
+/// Wraps a value from another realm for use in the current realm. Objects are replaced with cross-compartment
+/// wrappers, which forward operations to the original object, while primitives are copied if needed.
+/// Returns `false` if the value cannot be wrapped.
+pub fn wrap_value(cx: &Context, value: &mut Value) -> bool {
+	unsafe { JS_WrapValue(cx.as_ptr(), value.handle_mut().into()) }
+}
+
+/// Wraps an object from another realm for use in the current realm. See [wrap_value].
+/// Returns `false` if the object cannot be wrapped.
+pub fn wrap_object(cx: &Context, object: &mut Object) -> bool {
+	unsafe { JS_WrapObject(cx.as_ptr(), object.handle_mut().into()) }
+}
@@ -4,17 +4,27 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::cell::Cell;
 use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ptr;
+use std::rc::Rc;
 
 use mozjs_sys::jsgc::{GCMethods, RootKind};
 use mozjs::{
+	glue::JS_GetReservedSlot,
 	jsapi::Heap as JSHeap,
 	jsapi::{JSObject, JSString, JSScript, PropertyKey, JSFunction, BigInt, Symbol},
-	jsval::JSVal,
+	jsapi::{
+		GCContext, JS_GetClassObject, JS_GetClassPrototype, JS_NewObject, JS_SetReservedSlot, JSClass,
+		JSCLASS_FOREGROUND_FINALIZE, JSClassOps, JSProtoKey, NewWeakMapObject, SetWeakMapEntry,
+	},
+	jsval::{JSVal, ObjectValue, PrivateValue, UndefinedValue},
 	rust::{RootedTraceableSet, Traceable},
 };
 
-use crate::{Context, Local};
+use crate::{Context, Function, Local, Object, Value};
+use crate::object::class_reserved_slots;
 
 macro_rules! impl_heap_root {
 	([$class:ident] $(($pointer:ty)$(,)?)*) => {
@@ -251,6 +261,152 @@ impl_heap_root! {
 	(*mut Symbol),
 }
 
+/// Weak reference to a value stored on the heap, which does not keep
+/// the value alive. Only objects can be referenced weakly, as with
+/// `WeakRef` in JavaScript.
+///
+/// Upgrading the reference keeps the object alive until the end of the
+/// current job, so that it cannot be collected while it is in use.
+pub struct WeakHeap<T> {
+	weak_ref: TracedHeap<*mut JSObject>,
+	finalizer: Option<(TracedHeap<*mut JSObject>, Rc<Cell<bool>>)>,
+	_marker: PhantomData<T>,
+}
+
+impl WeakHeap<*mut JSObject> {
+	/// Creates a weak reference to the object.
+	/// Returns [None] if `WeakRef` is unavailable in the current realm.
+	pub fn new(cx: &Context, object: &Local<'_, *mut JSObject>) -> Option<WeakHeap<*mut JSObject>> {
+		let constructor = standard_class(cx, JSProtoKey::JSProto_WeakRef, false)?;
+		let constructor = Function::from_object(cx, &constructor)?;
+		let target = Value::from(cx.root(ObjectValue(object.get())));
+		let weak_ref = constructor.construct(cx, &[target]).ok()?;
+		Some(WeakHeap {
+			weak_ref: TracedHeap::new(weak_ref.handle().get()),
+			finalizer: None,
+			_marker: PhantomData,
+		})
+	}
+
+	/// Creates a weak reference to the object, which calls the finalizer once the object has been garbage collected.
+	///
+	/// The finalizer is called during garbage collection, so it must not use the JavaScript engine.
+	/// It is not called if the [WeakHeap] is dropped before the object is collected.
+	pub fn with_finalizer<F: FnOnce() + 'static>(
+		cx: &Context, object: &Local<'_, *mut JSObject>, finalizer: F,
+	) -> Option<WeakHeap<*mut JSObject>> {
+		let mut weak = WeakHeap::new(cx, object)?;
+		let cancelled = Rc::new(Cell::new(false));
+
+		// The holder is only reachable through the entry of the object in the map, so it is finalised along with the object.
+		let map = TracedHeap::new(unsafe { NewWeakMapObject(cx.as_ptr()) });
+		let registered = unsafe {
+			let holder = JS_NewObject(cx.as_ptr(), &FINALIZER_CLASS);
+			if holder.is_null() {
+				return None;
+			}
+			let private = FinalizerPrivate {
+				finalizer: Box::new(finalizer),
+				cancelled: Rc::clone(&cancelled),
+			};
+			JS_SetReservedSlot(
+				holder,
+				FINALIZER_SLOT,
+				&PrivateValue(Box::into_raw(Box::new(private)).cast_const().cast()),
+			);
+
+			let holder = Value::from(cx.root(ObjectValue(holder)));
+			SetWeakMapEntry(
+				cx.as_ptr(),
+				map.root(cx).handle().into(),
+				object.handle().into(),
+				holder.handle().into(),
+			)
+		};
+
+		registered.then(|| {
+			weak.finalizer = Some((map, cancelled));
+			weak
+		})
+	}
+
+	/// Returns the object, if it has not been garbage collected.
+	pub fn upgrade<'cx>(&self, cx: &'cx Context) -> Option<Local<'cx, *mut JSObject>> {
+		let prototype = standard_class(cx, JSProtoKey::JSProto_WeakRef, true)?;
+		let deref = prototype.get(cx, "deref").ok()??;
+		if !deref.handle().is_object() {
+			return None;
+		}
+		let deref = Function::from_object(cx, &deref.to_object(cx))?;
+
+		let target = deref.call(cx, &Object::from(self.weak_ref.root(cx)), &[]).ok()?;
+		target.handle().is_object().then(|| cx.root(target.handle().to_object()))
+	}
+}
+
+impl<T> Drop for WeakHeap<T> {
+	fn drop(&mut self) {
+		if let Some((_, cancelled)) = &self.finalizer {
+			cancelled.set(true);
+		}
+	}
+}
+
+fn standard_class(cx: &Context, key: JSProtoKey, prototype: bool) -> Option<Object> {
+	let mut object = Object::null(cx);
+	let success = unsafe {
+		if prototype {
+			JS_GetClassPrototype(cx.as_ptr(), key, object.handle_mut().into())
+		} else {
+			JS_GetClassObject(cx.as_ptr(), key, object.handle_mut().into())
+		}
+	};
+	(success && !object.handle().is_null()).then_some(object)
+}
+
+const FINALIZER_SLOT: u32 = 0;
+
+struct FinalizerPrivate {
+	finalizer: Box<dyn FnOnce()>,
+	cancelled: Rc<Cell<bool>>,
+}
+
+unsafe extern "C" fn finalise_holder(_: *mut GCContext, object: *mut JSObject) {
+	let mut value = UndefinedValue();
+	unsafe {
+		JS_GetReservedSlot(object, FINALIZER_SLOT, &mut value);
+		if !value.is_undefined() {
+			let private = Box::from_raw(value.to_private().cast::<FinalizerPrivate>().cast_mut());
+			let FinalizerPrivate { finalizer, cancelled } = *private;
+			if !cancelled.get() {
+				finalizer();
+			}
+		}
+	}
+}
+
+static FINALIZER_OPS: JSClassOps = JSClassOps {
+	addProperty: None,
+	delProperty: None,
+	enumerate: None,
+	newEnumerate: None,
+	resolve: None,
+	mayResolve: None,
+	finalize: Some(finalise_holder),
+	call: None,
+	construct: None,
+	trace: None,
+};
+
+static FINALIZER_CLASS: JSClass = JSClass {
+	name: "WeakHeapFinalizer\0".as_ptr().cast(),
+	flags: JSCLASS_FOREGROUND_FINALIZE | class_reserved_slots(1),
+	cOps: &FINALIZER_OPS,
+	spec: ptr::null_mut(),
+	ext: ptr::null_mut(),
+	oOps: ptr::null_mut(),
+};
+
 pub trait HeapPointer<T> {
 	fn to_ptr(&self) -> T;
 }
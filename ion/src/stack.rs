@@ -10,13 +10,14 @@ use std::mem::MaybeUninit;
 
 use mozjs::conversions::jsstr_to_string;
 use mozjs::jsapi::{
-	BuildStackString, CaptureCurrentStack, JS_StackCapture_AllFrames, JS_StackCapture_MaxFrames, JSObject, JSString,
-	StackFormat,
+	BuildStackString, CaptureCurrentStack, ExceptionStackOrNull, JS_StackCapture_AllFrames, JS_StackCapture_MaxFrames,
+	JSObject, JSString, StackFormat,
 };
 #[cfg(feature = "sourcemap")]
 use sourcemap::SourceMap;
 
-use crate::{Context, Object};
+use crate::{Context, Object, Value};
+use crate::conversions::ToValue;
 use crate::format::{INDENT, NEWLINE};
 use crate::utils::normalise_path;
 
@@ -112,6 +113,17 @@ impl Stack {
 		})
 	}
 
+	/// Creates a [Stack] from the stack captured when an error object was created.
+	/// Returns [None] if the object is not an error.
+	pub fn from_error(cx: &Context, error: &Object) -> Option<Stack> {
+		let stack = unsafe { ExceptionStackOrNull(error.handle().into()) };
+		if stack.is_null() {
+			None
+		} else {
+			Stack::from_object(cx, stack)
+		}
+	}
+
 	/// Captures the [Stack] of the [Context].
 	pub fn from_capture(cx: &Context) -> Option<Stack> {
 		capture_stack(cx, None).and_then(|stack| Stack::from_object(cx, stack))
@@ -143,6 +155,26 @@ impl Stack {
 	}
 }
 
+/// Converts the record to an object with `function`, `file`, `line` and `column` properties.
+/// `function` is `null` for anonymous functions and top-level code.
+impl<'cx> ToValue<'cx> for StackRecord {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "function", &self.function);
+		object.set_as(cx, "file", &self.location.file);
+		object.set_as(cx, "line", &self.location.lineno);
+		object.set_as(cx, "column", &self.location.column);
+		object.to_value(cx, value);
+	}
+}
+
+/// Converts the stack to an array of its [records](StackRecord), from the innermost frame.
+impl<'cx> ToValue<'cx> for Stack {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		self.records.to_value(cx, value);
+	}
+}
+
 impl Display for Stack {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
 		f.write_str(&self.format())
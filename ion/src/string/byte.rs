@@ -7,6 +7,8 @@
 use std::marker::PhantomData;
 use std::ops::Deref;
 
+use crate::{Error, ErrorKind, Result};
+
 #[derive(Debug)]
 pub enum VisibleAscii {}
 
@@ -31,6 +33,8 @@ mod private {
 	use super::VerbatimBytes;
 
 	pub trait Sealed {
+		const NAME: &'static str;
+
 		fn predicate(_byte: u8) -> bool {
 			true
 		}
@@ -52,14 +56,20 @@ mod private {
 	}
 
 	impl Sealed for VisibleAscii {
+		const NAME: &'static str = "visible ASCII";
+
 		fn predicate(byte: u8) -> bool {
 			(0x20..=0x7E).contains(&byte)
 		}
 	}
 
-	impl Sealed for Latin1 {}
+	impl Sealed for Latin1 {
+		const NAME: &'static str = "Latin-1";
+	}
 
 	impl Sealed for VerbatimBytes {
+		const NAME: &'static str = "verbatim bytes";
+
 		fn bytes_from_value(cx: &Context, value: &Value, strict: bool) -> Result<Vec<u8>> {
 			let str = String::from_value(cx, value, strict, ())?;
 			Ok(str.as_bytes(cx).to_vec())
@@ -74,18 +84,47 @@ pub struct ByteStr<T: BytePredicate> {
 	bytes: [u8],
 }
 
+/// Byte used in place of invalid bytes by lossy conversions, which is valid for every predicate.
+pub const REPLACEMENT_BYTE: u8 = b'?';
+
+/// Validates that every byte matches the predicate, describing the first invalid byte otherwise.
+fn validate<T: BytePredicate>(bytes: &[u8]) -> Result<()> {
+	match bytes.iter().position(|byte| !T::predicate(*byte)) {
+		Some(index) => Err(Error::new(
+			format!(
+				"ByteString contains invalid {} character 0x{:02X} at index {}",
+				T::NAME,
+				bytes[index],
+				index
+			),
+			ErrorKind::Type,
+		)),
+		None => Ok(()),
+	}
+}
+
 impl<T: BytePredicate> ByteStr<T> {
 	pub fn from(bytes: &[u8]) -> Option<&ByteStr<T>> {
 		bytes.iter().copied().all(T::predicate).then(|| unsafe { ByteStr::from_unchecked(bytes) })
 	}
 
-	pub unsafe fn from_unchecked(bytes: &[u8]) -> &ByteStr<T> {
+	/// Creates a [ByteStr], returning an error describing the first invalid byte if the bytes do not match the predicate.
+	pub fn try_from_bytes(bytes: &[u8]) -> Result<&ByteStr<T>> {
+		validate::<T>(bytes)?;
+		Ok(unsafe { ByteStr::from_unchecked(bytes) })
+	}
+
+	pub const unsafe fn from_unchecked(bytes: &[u8]) -> &ByteStr<T> {
 		unsafe { &*(bytes as *const _ as *const ByteStr<T>) }
 	}
 
 	pub fn as_bytes(&self) -> &[u8] {
 		&self.bytes
 	}
+
+	pub fn to_byte_string(&self) -> ByteString<T> {
+		unsafe { ByteString::from_unchecked(self.bytes.to_vec()) }
+	}
 }
 
 impl<T: BytePredicate> Deref for ByteStr<T> {
@@ -119,6 +158,22 @@ impl<T: BytePredicate> ByteString<T> {
 			.then(|| unsafe { ByteString::from_unchecked(bytes) })
 	}
 
+	/// Creates a [ByteString], returning an error describing the first invalid byte if the bytes do not match the predicate.
+	pub fn try_from_bytes(bytes: Vec<u8>) -> Result<ByteString<T>> {
+		validate::<T>(&bytes)?;
+		Ok(unsafe { ByteString::from_unchecked(bytes) })
+	}
+
+	/// Creates a [ByteString], replacing bytes that do not match the predicate with [REPLACEMENT_BYTE].
+	pub fn from_lossy(mut bytes: Vec<u8>) -> ByteString<T> {
+		for byte in &mut bytes {
+			if !T::predicate(*byte) {
+				*byte = REPLACEMENT_BYTE;
+			}
+		}
+		unsafe { ByteString::from_unchecked(bytes) }
+	}
+
 	pub unsafe fn from_unchecked(bytes: Vec<u8>) -> ByteString<T> {
 		ByteString { _predicate: PhantomData, bytes }
 	}
@@ -156,3 +211,81 @@ impl<T: BytePredicate> Clone for ByteString<T> {
 		}
 	}
 }
+
+impl<T: BytePredicate> From<&ByteStr<T>> for ByteString<T> {
+	fn from(str: &ByteStr<T>) -> ByteString<T> {
+		str.to_byte_string()
+	}
+}
+
+/// Names of common HTTP headers, in their lowercase form.
+pub mod header {
+	use super::{ByteStr, VisibleAscii};
+
+	macro_rules! header_names {
+		($($name:ident => $value:literal),* $(,)?) => {
+			$(pub const $name: &ByteStr<VisibleAscii> = unsafe { ByteStr::from_unchecked($value) };)*
+		};
+	}
+
+	header_names! {
+		ACCEPT => b"accept",
+		ACCEPT_LANGUAGE => b"accept-language",
+		AUTHORIZATION => b"authorization",
+		CONTENT_LENGTH => b"content-length",
+		CONTENT_TYPE => b"content-type",
+		COOKIE => b"cookie",
+		HOST => b"host",
+		LOCATION => b"location",
+		ORIGIN => b"origin",
+		REFERER => b"referer",
+		SET_COOKIE => b"set-cookie",
+		USER_AGENT => b"user-agent",
+	}
+}
+
+#[cfg(feature = "http")]
+mod http_conversions {
+	use std::result;
+
+	use http::header::{HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue};
+
+	use crate::Result;
+	use crate::string::byte::{BytePredicate, ByteStr, ByteString, VisibleAscii};
+
+	impl From<&HeaderName> for ByteString<VisibleAscii> {
+		fn from(name: &HeaderName) -> ByteString<VisibleAscii> {
+			unsafe { ByteString::from_unchecked(name.as_str().as_bytes().to_vec()) }
+		}
+	}
+
+	impl From<HeaderName> for ByteString<VisibleAscii> {
+		fn from(name: HeaderName) -> ByteString<VisibleAscii> {
+			(&name).into()
+		}
+	}
+
+	impl<T: BytePredicate> TryFrom<&HeaderValue> for ByteString<T> {
+		type Error = crate::Error;
+
+		fn try_from(value: &HeaderValue) -> Result<ByteString<T>> {
+			ByteString::try_from_bytes(value.as_bytes().to_vec())
+		}
+	}
+
+	impl<T: BytePredicate> TryFrom<&ByteStr<T>> for HeaderName {
+		type Error = InvalidHeaderName;
+
+		fn try_from(name: &ByteStr<T>) -> result::Result<HeaderName, InvalidHeaderName> {
+			HeaderName::from_bytes(name.as_bytes())
+		}
+	}
+
+	impl<T: BytePredicate> TryFrom<&ByteStr<T>> for HeaderValue {
+		type Error = InvalidHeaderValue;
+
+		fn try_from(value: &ByteStr<T>) -> result::Result<HeaderValue, InvalidHeaderValue> {
+			HeaderValue::from_bytes(value.as_bytes())
+		}
+	}
+}
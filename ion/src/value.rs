@@ -6,13 +6,14 @@
 
 use std::ops::{Deref, DerefMut};
 
-use mozjs::jsapi::{JS_ValueToSource, SameValue};
+use mozjs::jsapi::{JS_ValueToSource, JSType, SameValue, ToPrimitive};
 use mozjs::jsval::{
 	BigIntValue, BooleanValue, DoubleValue, Int32Value, JSVal, NullValue, ObjectValue, SymbolValue, UInt32Value,
 	UndefinedValue,
 };
+use mozjs::rust::ToNumber;
 
-use crate::{Array, Context, Local, Object, Symbol};
+use crate::{Array, Context, Error, ErrorKind, Exception, Local, Object, PropertyKey, ResultExc, Symbol};
 use crate::bigint::BigInt;
 use crate::conversions::ToValue;
 
@@ -111,6 +112,92 @@ impl<'v> Value<'v> {
 	}
 }
 
+/// Largest integer which can be represented exactly by a Number, which bounds lengths and indices.
+const MAX_SAFE_INTEGER: f64 = ((1_u64 << 53) - 1) as f64;
+
+/// Type hint passed to [Value::to_primitive], and on to the `Symbol.toPrimitive` method of objects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreferredType {
+	#[default]
+	Default,
+	String,
+	Number,
+}
+
+/// Conversions which follow the [abstract operations](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-type-conversion)
+/// of the specification, and fail with the exception thrown by user code, such as `valueOf`.
+impl<'v> Value<'v> {
+	/// Converts the value to a primitive with [ToPrimitive](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-toprimitive).
+	/// Primitives are returned unchanged.
+	pub fn to_primitive<'cx>(&self, cx: &'cx Context, hint: PreferredType) -> ResultExc<Value<'cx>> {
+		let mut primitive = Value::from(cx.root(self.get()));
+		if !self.handle().is_object() {
+			return Ok(primitive);
+		}
+
+		let object = self.to_object(cx);
+		let hint = match hint {
+			PreferredType::Default => JSType::JSTYPE_UNDEFINED,
+			PreferredType::String => JSType::JSTYPE_STRING,
+			PreferredType::Number => JSType::JSTYPE_NUMBER,
+		};
+		if unsafe { ToPrimitive(cx.as_ptr(), object.handle().into(), hint, primitive.handle_mut().into()) } {
+			Ok(primitive)
+		} else {
+			Err(pending_exception(cx))
+		}
+	}
+
+	/// Converts the value to a number with [ToNumber](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-tonumber).
+	pub fn to_number(&self, cx: &Context) -> ResultExc<f64> {
+		unsafe { ToNumber(cx.as_ptr(), self.handle()) }.map_err(|_| pending_exception(cx))
+	}
+
+	/// Converts the value to an integral number with [ToIntegerOrInfinity](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-tointegerorinfinity).
+	/// `NaN` is converted to 0, and infinities are kept.
+	pub fn to_integer_or_infinity(&self, cx: &Context) -> ResultExc<f64> {
+		let number = self.to_number(cx)?;
+		if number.is_nan() {
+			Ok(0.0)
+		} else {
+			Ok(number.trunc() + 0.0)
+		}
+	}
+
+	/// Converts the value to a length with [ToLength](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-tolength),
+	/// which clamps it between 0 and 2<sup>53</sup> - 1.
+	pub fn to_length(&self, cx: &Context) -> ResultExc<u64> {
+		let length = self.to_integer_or_infinity(cx)?;
+		Ok(length.clamp(0.0, MAX_SAFE_INTEGER) as u64)
+	}
+
+	/// Converts the value to an index with [ToIndex](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-toindex).
+	/// Fails with a [RangeError](ErrorKind::Range) if it is negative or above 2<sup>53</sup> - 1.
+	pub fn to_index(&self, cx: &Context) -> ResultExc<u64> {
+		let index = self.to_integer_or_infinity(cx)?;
+		if !(0.0..=MAX_SAFE_INTEGER).contains(&index) {
+			return Err(Error::new("Index is out of range", ErrorKind::Range).into());
+		}
+		Ok(index as u64)
+	}
+
+	/// Converts the value to a property key with [ToPropertyKey](https://tc39.es/ecma262/multipage/abstract-operations.html#sec-topropertykey).
+	/// Symbols are kept, and other values are converted to strings or integers.
+	pub fn to_property_key<'cx>(&self, cx: &'cx Context) -> ResultExc<PropertyKey<'cx>> {
+		PropertyKey::from_value(cx, self).ok_or_else(|| pending_exception(cx))
+	}
+}
+
+/// Takes the pending exception after a failed conversion.
+/// Uncatchable exceptions, such as running out of memory, leave no exception pending.
+fn pending_exception(cx: &Context) -> Exception {
+	match Exception::new(cx) {
+		Ok(Some(exception)) => exception,
+		Ok(None) => Exception::Error(Error::none()),
+		Err(error) => Exception::Error(error),
+	}
+}
+
 impl<'v> From<Local<'v, JSVal>> for Value<'v> {
 	fn from(val: Local<'v, JSVal>) -> Value<'v> {
 		Value { val }
@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Arguments, ClassDefinition, Context, Function, js_class, Value};
+use ion::class::Reflector;
+use ion::conversions::FromValue;
+use ion::flags::PropertyFlags;
+use ion::object::default_new_global;
+use ion::script::Script;
+
+#[js_class]
+pub struct Shape {
+	reflector: Reflector,
+	kind: String,
+	callee: String,
+}
+
+#[js_class]
+impl Shape {
+	#[ion(constructor)]
+	pub fn constructor(args: &mut Arguments) -> Shape {
+		let cx = args.cx();
+		let kind = args
+			.new_target()
+			.and_then(|new_target| Function::from_object(cx, &new_target))
+			.and_then(|new_target| new_target.name(cx))
+			.unwrap_or_default();
+		let callee = args.callee_name().unwrap_or_default();
+		Shape {
+			reflector: Reflector::default(),
+			kind,
+			callee,
+		}
+	}
+
+	#[ion(get)]
+	pub fn get_kind(&self) -> String {
+		self.kind.clone()
+	}
+
+	#[ion(get)]
+	pub fn get_callee(&self) -> String {
+		self.callee.clone()
+	}
+}
+
+const SCRIPT: &str = r#"
+class Circle extends Shape {}
+const shape = new Shape();
+const circle = new Circle();
+[shape.kind, shape.callee, circle.kind, circle.callee, describe()].join()
+"#;
+
+#[test]
+fn arguments() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(cx.as_ptr(), global.handle().get());
+
+	assert!(Shape::init_class(cx, &global).0);
+
+	// Functions which are called without `new` have no `new.target`.
+	let describe = Function::from_closure(
+		cx,
+		"describe",
+		Box::new(|args| {
+			let cx = args.cx();
+			let name = args.callee_name().unwrap_or_default();
+			let function = args.callee_function().is_some();
+			let constructing = args.new_target().is_some();
+			Ok(Value::string(cx, &format!("{}/{}/{}", name, function, constructing)))
+		}),
+		0,
+		PropertyFlags::empty(),
+	);
+	global.set(cx, "describe", &Value::object(cx, &describe.to_object(cx)));
+
+	let result = Script::compile_and_evaluate(cx, Path::new("arguments.js"), SCRIPT).unwrap();
+	let result = String::from_value(cx, &result, true, ()).unwrap();
+	assert_eq!("Shape,Shape,Circle,Shape,describe/true/false", result);
+}
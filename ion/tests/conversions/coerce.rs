@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, ErrorKind, Exception, OwnedKey, PreferredType, Value};
+use ion::conversions::FromValue;
+use ion::object::default_new_global;
+use ion::script::Script;
+
+const SCRIPT: &str = r#"
+({
+	hinted: { [Symbol.toPrimitive]: hint => hint },
+	seven: { valueOf: () => 7.9 },
+	throwing: { valueOf() { throw new RangeError("valueOf"); } },
+	key: { toString: () => "key" },
+})
+"#;
+
+#[test]
+fn coerce() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let objects = Script::compile_and_evaluate(cx, Path::new("coerce.js"), SCRIPT).unwrap();
+	let objects = objects.to_object(cx);
+	let hinted = objects.get(cx, "hinted").unwrap().unwrap();
+	let seven = objects.get(cx, "seven").unwrap().unwrap();
+	let throwing = objects.get(cx, "throwing").unwrap().unwrap();
+	let key = objects.get(cx, "key").unwrap().unwrap();
+
+	for (hint, expected) in [
+		(PreferredType::Default, "default"),
+		(PreferredType::String, "string"),
+		(PreferredType::Number, "number"),
+	] {
+		let primitive = hinted.to_primitive(cx, hint).unwrap();
+		assert_eq!(expected, String::from_value(cx, &primitive, true, ()).unwrap());
+	}
+	let primitive = Value::i32(cx, 3).to_primitive(cx, PreferredType::String).unwrap();
+	assert_eq!(3, primitive.handle().to_int32());
+
+	assert_eq!(7.9, seven.to_number(cx).unwrap());
+	assert!(Value::undefined(cx).to_number(cx).unwrap().is_nan());
+	assert_eq!(7.0, seven.to_integer_or_infinity(cx).unwrap());
+	assert_eq!(0.0, Value::f64(cx, f64::NAN).to_integer_or_infinity(cx).unwrap());
+
+	assert_eq!(7, seven.to_length(cx).unwrap());
+	assert_eq!(0, Value::i32(cx, -5).to_length(cx).unwrap());
+	assert_eq!((1 << 53) - 1, Value::f64(cx, f64::INFINITY).to_length(cx).unwrap());
+
+	assert_eq!(0, Value::undefined(cx).to_index(cx).unwrap());
+	assert_eq!(7, seven.to_index(cx).unwrap());
+	match Value::i32(cx, -1).to_index(cx) {
+		Err(Exception::Error(error)) => assert_eq!(ErrorKind::Range, error.kind),
+		_ => panic!("Expected RangeError for negative index"),
+	}
+
+	match throwing.to_number(cx) {
+		Err(Exception::Error(error)) => {
+			assert_eq!(ErrorKind::Range, error.kind);
+			assert_eq!("valueOf", error.message);
+		}
+		_ => panic!("Expected exception from valueOf"),
+	}
+	assert!(!Exception::is_pending(cx));
+
+	match key.to_property_key(cx).unwrap().to_owned_key(cx).unwrap() {
+		OwnedKey::String(key) => assert_eq!("key", key),
+		_ => panic!("Expected string key"),
+	}
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::f64::consts::PI;
 
 use chrono::{TimeZone, Utc};
@@ -5,7 +6,7 @@ use mozjs::jsapi::JSAutoRealm;
 use mozjs::jsval::Int32Value;
 use mozjs::rust::{JSEngine, Runtime};
 
-use ion::{Array, Context, Date, Object, Promise, Value};
+use ion::{Array, BigInt, Context, Date, Object, Promise, Value};
 use ion::conversions::{FromValue, ToValue};
 use ion::conversions::ConversionBehavior;
 use ion::object::default_new_global;
@@ -21,6 +22,7 @@ fn from_value() {
 
 	test_booleans(cx);
 	test_integers(cx);
+	test_bigints(cx);
 	test_strings(cx);
 	test_objects(cx);
 	test_options(cx);
@@ -126,6 +128,43 @@ fn test_integers(cx: &Context) {
 	assert!(result.is_err());
 }
 
+fn test_bigints(cx: &Context) {
+	let value = Value::bigint(cx, &BigInt::from_u128(cx, u128::MAX));
+	let result = u128::from_value(cx, &value, true, ());
+	assert_eq!(result.unwrap(), u128::MAX);
+	let result = i128::from_value(cx, &value, true, ());
+	assert!(result.is_err());
+	let result = u64::from_value(cx, &value, true, ConversionBehavior::EnforceRange);
+	assert!(result.is_err());
+
+	let value = i128::MIN.as_value(cx);
+	assert!(value.handle().is_bigint());
+	let result = i128::from_value(cx, &value, true, ());
+	assert_eq!(result.unwrap(), i128::MIN);
+
+	let value = Value::string(cx, "-9007199254740993");
+	let result = i128::from_value(cx, &value, true, ());
+	assert!(result.is_err());
+	let result = i128::from_value(cx, &value, false, ());
+	assert_eq!(result.unwrap(), -9007199254740993);
+
+	let value = Value::f64(cx, PI);
+	let result = BigInt::from_value(cx, &value, false, ());
+	assert!(result.is_err());
+
+	let lhs = BigInt::from_u64(cx, u64::MAX);
+	let rhs = BigInt::from_i64(cx, -1);
+	let sum = lhs.add(cx, &rhs).mul(cx, &BigInt::from_u64(cx, 4));
+	assert_eq!(sum.to_u128(cx), Some(u128::from(u64::MAX - 1) * 4));
+	assert_eq!(lhs.neg(cx).to_i128(cx), Some(-i128::from(u64::MAX)));
+	assert!(lhs.div(cx, &BigInt::from_i64(cx, 0)).is_none());
+	assert_eq!(lhs.compare(cx, &rhs), Ordering::Greater);
+
+	let value = num_bigint::BigInt::from(u128::MAX).pow(2).as_value(cx);
+	let result = num_bigint::BigInt::from_value(cx, &value, true, ());
+	assert_eq!(result.unwrap(), num_bigint::BigInt::from(u128::MAX).pow(2));
+}
+
 fn test_strings(cx: &Context) {
 	let value = Value::bool(cx, false);
 	let result = String::from_value(cx, &value, true, ());
@@ -0,0 +1,78 @@
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Error, ErrorKind, FromValue, Object, Result, Value};
+use ion::conversions::FromValue as _;
+use ion::object::default_new_global;
+
+#[derive(Debug, FromValue)]
+#[ion(validate = Range::validate)]
+struct Range {
+	start: f64,
+	end: f64,
+	#[ion(default)]
+	inclusive: bool,
+}
+
+impl Range {
+	fn validate(&self) -> Result<()> {
+		if self.start.is_nan() || self.end.is_nan() {
+			Err(Error::new("Range bounds cannot be NaN", ErrorKind::Type))
+		} else if self.start > self.end {
+			Err(Error::new("Range start is after its end", ErrorKind::Range))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+#[derive(Debug, FromValue)]
+#[ion(validate = |options: &Options| if options.text.is_some() && options.html.is_some() {
+	Err(Error::new("Options cannot have both text and html", ErrorKind::Type))
+} else {
+	Ok(())
+})]
+struct Options {
+	text: Option<String>,
+	html: Option<String>,
+}
+
+fn range(cx: &Context, start: f64, end: f64) -> Value {
+	let object = Object::new(cx);
+	object.set_as(cx, "start", &start);
+	object.set_as(cx, "end", &end);
+	object.as_value(cx)
+}
+
+#[test]
+fn validate() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let valid = Range::from_value(cx, &range(cx, 1.0, 2.0), true, ()).unwrap();
+	assert_eq!((1.0, 2.0, false), (valid.start, valid.end, valid.inclusive));
+
+	let reversed = Range::from_value(cx, &range(cx, 2.0, 1.0), true, ()).unwrap_err();
+	assert_eq!(ErrorKind::Range, reversed.kind);
+	assert_eq!("Range start is after its end", reversed.message);
+
+	let nan = Range::from_value(cx, &range(cx, f64::NAN, 1.0), false, ()).unwrap_err();
+	assert_eq!(ErrorKind::Type, nan.kind);
+
+	// Conversion errors of fields are returned before the value is validated.
+	let missing = Range::from_value(cx, &Object::new(cx).as_value(cx), true, ()).unwrap_err();
+	assert_eq!(ErrorKind::Type, missing.kind);
+	assert!(missing.message.starts_with("Expected Value at key start"));
+
+	let options = Object::new(cx);
+	options.set_as(cx, "text", "text");
+	assert!(Options::from_value(cx, &options.as_value(cx), true, ()).is_ok());
+
+	options.set_as(cx, "html", "<p>html</p>");
+	let both = Options::from_value(cx, &options.as_value(cx), true, ()).unwrap_err();
+	assert_eq!("Options cannot have both text and html", both.message);
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::format::{Config, format_value};
+use ion::object::default_new_global;
+use ion::script::Script;
+
+const SCRIPT: &str = r#"({
+	string: "quoted \"text\"\n",
+	number: 1.5,
+	nan: NaN,
+	integer: -3,
+	boolean: true,
+	null: null,
+	undefined: undefined,
+	bigint: 12n,
+	symbol: Symbol("tag"),
+	array: [1, "two", [3]],
+	date: new Date(0),
+	regexp: /a+/g,
+	fn: function named() {},
+	nested: { a: { b: { c: { d: 1 } } } },
+	get accessor() { return 1; },
+})"#;
+
+const EXPECTED: &str = concat!(
+	r#"{"string":"quoted \"text\"\n","number":1.5,"nan":{"$type":"number","value":"NaN"},"integer":-3,"#,
+	r#""boolean":true,"null":null,"undefined":{"$type":"undefined"},"bigint":{"$type":"bigint","value":"12"},"#,
+	r#""symbol":{"$type":"symbol","value":"tag"},"array":[1,"two",[3]],"#,
+	r#""date":{"$type":"date","value":"1970-01-01T00:00:00+00:00"},"regexp":{"$type":"regexp","value":"/a+/g"},"#,
+	r#""fn":{"$type":"function","value":"named"},"#,
+	r#""nested":{"a":{"b":{"c":{"$type":"object","truncated":true}}}},"accessor":{"$type":"accessor"}}"#,
+);
+
+#[test]
+fn format_json() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let value = Script::compile_and_evaluate(cx, Path::new("json.js"), SCRIPT).unwrap();
+	let json = format_value(cx, Config::default().json(true), &value).to_string();
+	assert_eq!(EXPECTED, json);
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Function, Object};
+use ion::conversions::FromValue;
+use ion::function::AsyncCallResult;
+use ion::object::default_new_global;
+use ion::script::Script;
+
+const SCRIPT: &str = r#"
+globalThis.join = function (...args) {
+	return `${this.prefix}:${args.join(",")}`;
+};
+globalThis.Point = class {
+	constructor(x, y) {
+		this.sum = x + y;
+	}
+};
+globalThis.thenable = () => ({ then(resolve) { resolve(1); } });
+globalThis.plain = () => ({ value: 1 });
+"#;
+
+#[test]
+fn function_call_helpers() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(cx.as_ptr(), global.handle().get());
+
+	Script::compile_and_evaluate(cx, Path::new("function.js"), SCRIPT).unwrap();
+
+	let join: Function = global.get_as(cx, "join", true, ()).unwrap().unwrap();
+	let this = Object::new(cx);
+	this.set_as(cx, "prefix", "values");
+	let result = join.call_with(cx, &this, [1, 2, 3]).unwrap();
+	assert_eq!("values:1,2,3", String::from_value(cx, &result, true, ()).unwrap());
+
+	let point: Function = global.get_as(cx, "Point", true, ()).unwrap().unwrap();
+	let args = [1.5, 2.0].map(|value| ion::Value::f64(cx, value));
+	let instance = point.construct(cx, &args).unwrap();
+	let sum: f64 = instance.get_as(cx, "sum", true, ()).unwrap().unwrap();
+	assert_eq!(3.5, sum);
+	assert!(join.construct(cx, &[]).is_ok());
+
+	let thenable: Function = global.get_as(cx, "thenable", true, ()).unwrap().unwrap();
+	let result = thenable.apply_async(cx, &Object::null(cx), &[]).unwrap();
+	assert!(matches!(result, AsyncCallResult::Pending(_)));
+
+	let plain: Function = global.get_as(cx, "plain", true, ()).unwrap().unwrap();
+	let result = plain.apply_async(cx, &Object::null(cx), &[]).unwrap();
+	assert!(matches!(result, AsyncCallResult::Ready(_)));
+}
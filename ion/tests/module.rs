@@ -0,0 +1,41 @@
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Function, Object};
+use ion::conversions::{FromValue, ToValue};
+use ion::module::Module;
+use ion::object::default_new_global;
+
+const SOURCE: &str = r#"
+export const name = "module";
+export function handler(value) {
+	return value * 2;
+}
+export default function(value) {
+	return value + 1;
+}
+"#;
+
+#[test]
+fn module_exports() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let (module, _) = Module::compile_and_evaluate(cx, "exports.js", None, SOURCE).unwrap();
+
+	let name: String = module.export_as(cx, "name").unwrap();
+	assert_eq!("module", name);
+	assert!(module.export(cx, "missing").unwrap().is_none());
+	assert!(module.export_as::<String>(cx, "missing").is_err());
+
+	let handler: Function = module.export_as(cx, "handler").unwrap();
+	let result = handler.call(cx, &Object::null(cx), &[5.0.as_value(cx)]).unwrap();
+	assert_eq!(10.0, f64::from_value(cx, &result, true, ()).unwrap());
+
+	let result = module.call_default(cx, &[5.0.as_value(cx)]).unwrap();
+	assert_eq!(6.0, f64::from_value(cx, &result, true, ()).unwrap());
+}
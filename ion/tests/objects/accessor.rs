@@ -0,0 +1,70 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Object, Value};
+use ion::conversions::{ConversionBehavior, FromValue, ToValue};
+use ion::flags::PropertyFlags;
+use ion::object::default_new_global;
+
+#[test]
+fn accessor() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let stored = Rc::new(Cell::new(1));
+	let calls = Rc::new(Cell::new(0));
+
+	let object = Object::new(cx);
+	let (getter_stored, getter_calls) = (Rc::clone(&stored), Rc::clone(&calls));
+	assert!(object.define_getter_closure(
+		cx,
+		"value",
+		Box::new(move |args| {
+			getter_calls.set(getter_calls.get() + 1);
+			Ok(getter_stored.get().as_value(args.cx()))
+		}),
+		PropertyFlags::ENUMERATE | PropertyFlags::CONFIGURABLE,
+	));
+	assert_eq!(calls.get(), 0);
+
+	let value = object.get(cx, "value").unwrap().unwrap();
+	assert_eq!(
+		i32::from_value(cx, &value, true, ConversionBehavior::Default).unwrap(),
+		1
+	);
+	assert_eq!(calls.get(), 1);
+
+	let setter_stored = Rc::clone(&stored);
+	assert!(object.define_setter_closure(
+		cx,
+		"value",
+		Box::new(move |args| {
+			let cx = args.cx();
+			let value = args.value(0).unwrap_or_else(|| Value::undefined(cx));
+			setter_stored.set(i32::from_value(cx, &value, true, ConversionBehavior::Default)?);
+			Ok(Value::undefined(cx))
+		}),
+		PropertyFlags::ENUMERATE | PropertyFlags::CONFIGURABLE,
+	));
+
+	assert!(object.set(cx, "value", &Value::i32(cx, 5)));
+	assert_eq!(stored.get(), 5);
+
+	let value = object.get(cx, "value").unwrap().unwrap();
+	assert_eq!(
+		i32::from_value(cx, &value, true, ConversionBehavior::Default).unwrap(),
+		5
+	);
+	assert_eq!(calls.get(), 2);
+
+	let descriptor = object.get_descriptor(cx, "value").unwrap().unwrap();
+	assert!(descriptor.getter(cx).is_some());
+	assert!(descriptor.setter(cx).is_some());
+}
@@ -0,0 +1,37 @@
+use bytes::Bytes;
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::object::default_new_global;
+use ion::typedarray::{ArrayBuffer, ArrayBufferWrapper};
+
+#[test]
+fn array_buffer_from_vec() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let mut vec = Vec::with_capacity(16);
+	vec.extend_from_slice(b"spiderfire");
+	let pointer = vec.as_ptr();
+	let buffer = ArrayBuffer::from_vec(cx, vec).unwrap();
+	assert_eq!(10, buffer.len());
+	assert_eq!(pointer, buffer.data().0.cast_const());
+	assert_eq!(b"spiderfire", unsafe { buffer.as_slice() });
+
+	let bytes = Bytes::from(b"unique".to_vec());
+	let pointer = bytes.as_ptr();
+	let buffer = ArrayBuffer::from_bytes(cx, bytes).unwrap();
+	assert_eq!(pointer, buffer.data().0.cast_const());
+	assert_eq!(b"unique", unsafe { buffer.as_slice() });
+
+	let shared = Bytes::from(b"shared".to_vec());
+	let buffer = ArrayBufferWrapper::from_bytes(shared.clone()).into_array_buffer(cx).unwrap();
+	unsafe { buffer.as_mut_slice()[0] = b'S' };
+	assert_eq!(b"Shared", unsafe { buffer.as_slice() });
+	assert_eq!(b"shared", shared.as_ref());
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Map, Object, Value};
+use ion::conversions::{FromValue, ToValue};
+use ion::object::default_new_global;
+
+#[test]
+fn map() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let map = Map::new(cx);
+	let key = Value::string(cx, "key");
+	assert!(map.set(cx, &key, &Value::i32(cx, 1)));
+	assert!(map.set(cx, &Value::string(cx, "other"), &Value::i32(cx, 2)));
+	assert!(map.has(cx, &key));
+	assert_eq!(Some(1), map.get(cx, &key).map(|value| value.handle().to_int32()));
+	assert_eq!(2, map.size(cx));
+
+	let entries = map.to_vec(cx).unwrap();
+	let keys: Vec<_> = entries.iter().map(|(key, _)| String::from_value(cx, key, true, ()).unwrap()).collect();
+	assert_eq!(vec!["key", "other"], keys);
+
+	assert!(map.delete(cx, &key));
+	assert!(!map.has(cx, &key));
+	assert!(!map.delete(cx, &key));
+
+	let hashmap = HashMap::from([(String::from("a"), 1), (String::from("b"), 2)]);
+	let value = hashmap.as_value(cx);
+	let map = Map::from_value(cx, &value, true, ()).unwrap();
+	assert_eq!(2, map.size(cx));
+
+	let result = HashMap::<String, f64>::from_value(cx, &value, true, ()).unwrap();
+	assert_eq!(
+		HashMap::from([(String::from("a"), 1.0), (String::from("b"), 2.0)]),
+		result
+	);
+
+	let result = HashMap::<String, f64>::from_value(cx, &Value::object(cx, &Object::new(cx)), true, ());
+	assert!(result.is_err());
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Object, OwnedKey, ResultExc, Value};
+use ion::conversions::FromValue;
+use ion::object::default_new_global;
+use ion::object::proxy::{Proxy, ProxyHandler};
+use ion::script::Script;
+
+struct Environment {
+	variables: HashMap<String, String>,
+}
+
+impl ProxyHandler for Environment {
+	fn get<'cx>(&self, cx: &'cx Context, _: &Object, key: &OwnedKey, _: &Value) -> ResultExc<Option<Value<'cx>>> {
+		Ok(match key {
+			OwnedKey::String(key) => self.variables.get(key).map(|value| Value::string(cx, value)),
+			_ => None,
+		})
+	}
+
+	fn set(&self, _: &Context, _: &Object, _: &OwnedKey, _: &Value, _: &Value) -> ResultExc<Option<bool>> {
+		Ok(Some(false))
+	}
+
+	fn has(&self, _: &Context, _: &Object, key: &OwnedKey) -> ResultExc<Option<bool>> {
+		Ok(match key {
+			OwnedKey::String(key) => Some(self.variables.contains_key(key)),
+			_ => None,
+		})
+	}
+
+	fn own_keys<'cx>(&self, _: &'cx Context, _: &Object) -> ResultExc<Option<Vec<OwnedKey<'cx>>>> {
+		let mut keys: Vec<_> = self.variables.keys().cloned().map(OwnedKey::String).collect();
+		keys.sort_by_key(|key| match key {
+			OwnedKey::String(key) => key.clone(),
+			_ => String::new(),
+		});
+		Ok(Some(keys))
+	}
+}
+
+const SCRIPT: &str = r#"[
+	env.HOME,
+	env.MISSING,
+	"PATH" in env,
+	"MISSING" in env,
+	Object.keys(env).join(","),
+	Reflect.set(env, "HOME", "/tmp"),
+	env.HOME,
+]"#;
+
+#[test]
+fn proxy() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let variables = HashMap::from([
+		(String::from("HOME"), String::from("/home/user")),
+		(String::from("PATH"), String::from("/usr/bin")),
+	]);
+	let env = Proxy::new(cx, &Object::new(cx), Environment { variables }).unwrap();
+	global.set(cx, "env", &Value::object(cx, &Object::from(env.into_local())));
+
+	let value = Script::compile_and_evaluate(cx, Path::new("proxy.js"), SCRIPT).unwrap();
+	let results = Vec::<Value>::from_value(cx, &value, true, ()).unwrap();
+	let strings: Vec<_> = results.iter().map(|value| String::from_value(cx, value, false, ()).unwrap()).collect();
+	assert_eq!(
+		vec![
+			"/home/user",
+			"undefined",
+			"true",
+			"false",
+			"HOME,PATH",
+			"false",
+			"/home/user"
+		],
+		strings
+	);
+}
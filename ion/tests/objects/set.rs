@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Set, Value};
+use ion::conversions::{FromValue, ToValue};
+use ion::object::default_new_global;
+
+#[test]
+fn set() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let set = Set::new(cx);
+	let key = Value::string(cx, "key");
+	assert!(set.add(cx, &key));
+	assert!(set.add(cx, &key));
+	assert!(set.add(cx, &Value::string(cx, "other")));
+	assert!(set.has(cx, &key));
+	assert_eq!(2, set.size(cx));
+
+	let keys: Vec<_> = set
+		.to_vec(cx)
+		.unwrap()
+		.iter()
+		.map(|key| String::from_value(cx, key, true, ()).unwrap())
+		.collect();
+	assert_eq!(vec!["key", "other"], keys);
+
+	assert!(set.delete(cx, &key));
+	assert!(!set.has(cx, &key));
+
+	let hashset = HashSet::from([String::from("a"), String::from("b")]);
+	let value = hashset.as_value(cx);
+	let set = Set::from_value(cx, &value, true, ()).unwrap();
+	assert_eq!(2, set.size(cx));
+
+	let result = HashSet::<String>::from_value(cx, &value, true, ()).unwrap();
+	assert_eq!(hashset, result);
+
+	let result = HashSet::<String>::from_value(cx, &Value::string(cx, "a"), true, ());
+	assert!(result.is_err());
+}
@@ -0,0 +1,48 @@
+use bytes::Bytes;
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::object::default_new_global;
+use ion::typedarray::{ArrayBuffer, DataView, Uint16Array, Uint8Array, Uint8ArrayWrapper};
+
+#[test]
+fn typed_array() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let mut vec = Vec::with_capacity(8);
+	vec.extend_from_slice(&[1u16, 2, 3, 4]);
+	let pointer = vec.as_ptr();
+	let array = Uint16Array::from_vec(cx, vec).unwrap();
+	assert_eq!(4, array.len());
+	assert_eq!(8, array.byte_length());
+	assert_eq!(pointer, array.data().0.cast_const());
+
+	let subarray = array.subarray(cx, 1, 10).unwrap();
+	assert_eq!(&[2, 3, 4], unsafe { subarray.as_slice() });
+	assert_eq!(2, subarray.offset());
+	unsafe { subarray.as_mut_slice()[0] = 20 };
+	assert_eq!(&[1, 20, 3, 4], unsafe { array.as_slice() });
+
+	let bytes = Bytes::from(b"unique".to_vec());
+	let pointer = bytes.as_ptr();
+	let array = Uint8Array::from_bytes(cx, bytes).unwrap();
+	assert_eq!(pointer, array.data().0.cast_const());
+
+	let array = Uint8ArrayWrapper::from(b"copied".as_slice()).into_typed_array(cx).unwrap();
+	assert_eq!(b"copied", unsafe { array.as_slice() });
+
+	let buffer = ArrayBuffer::copy_from_bytes(cx, b"spiderfire").unwrap();
+	let view = DataView::new(cx, &buffer, 6, 4).unwrap();
+	assert!(DataView::is_data_view(view.get()));
+	assert!(!DataView::is_data_view(array.get()));
+	assert_eq!(b"fire", unsafe { view.as_slice() });
+	assert_eq!(6, view.offset());
+	assert_eq!(10, view.buffer(cx).len());
+	assert!(DataView::new(cx, &buffer, 8, 4).is_none());
+}
@@ -0,0 +1,40 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use mozjs::jsapi::{ClearKeptObjects, GCReason, JS_GC, JSAutoRealm};
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Object, WeakHeap};
+use ion::object::default_new_global;
+
+#[test]
+fn weak_heap() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let finalised = Rc::new(Cell::new(false));
+	let weak = {
+		// Objects are rooted until their context is dropped.
+		let cx = &cx.duplicate();
+		let object = Object::new(cx);
+		let weak = WeakHeap::with_finalizer(cx, &object, {
+			let finalised = Rc::clone(&finalised);
+			move || finalised.set(true)
+		})
+		.unwrap();
+		assert_eq!(Some(object.handle().get()), weak.upgrade(cx).map(|object| object.get()));
+		weak
+	};
+	assert!(!finalised.get());
+
+	unsafe {
+		ClearKeptObjects(cx.as_ptr());
+		JS_GC(cx.as_ptr(), GCReason::API);
+	}
+	assert!(weak.upgrade(cx).is_none());
+	assert!(finalised.get());
+}
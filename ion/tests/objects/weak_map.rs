@@ -0,0 +1,38 @@
+use mozjs::jsapi::JSAutoRealm;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Function, Object, Value, WeakMap};
+use ion::object::default_new_global;
+
+#[test]
+fn weak_map() {
+	let engine = JSEngine::init().unwrap();
+	let runtime = Runtime::new(engine.handle());
+
+	let cx = &Context::from_runtime(&runtime);
+	let global = default_new_global(cx);
+	let _realm = JSAutoRealm::new(runtime.cx(), global.handle().get());
+
+	let map = WeakMap::<String>::new(cx);
+	let first = Object::new(cx);
+	let second = Object::new(cx);
+
+	assert!(map.set(cx, &first, String::from("first")));
+	assert!(map.has(cx, &first));
+	assert!(!map.has(cx, &second));
+	assert_eq!(Some("first"), map.get(cx, &first).as_deref().map(String::as_str));
+	assert_eq!(None, map.get(cx, &second));
+
+	assert!(map.set(cx, &first, String::from("replaced")));
+	assert_eq!(Some("replaced"), map.get(cx, &first).as_deref().map(String::as_str));
+
+	assert_eq!(Some("replaced"), map.remove(cx, &first).as_deref().map(String::as_str));
+	assert!(!map.has(cx, &first));
+	assert_eq!(None, map.remove(cx, &first));
+
+	// Removed entries are deleted from the JavaScript WeakMap, rather than set to undefined.
+	let has = map.to_object(cx).get(cx, "has").unwrap().unwrap();
+	let has = Function::from_object(cx, &has.to_object(cx)).unwrap();
+	let result = has.call(cx, &map.to_object(cx), &[Value::object(cx, &first)]).unwrap();
+	assert!(!result.handle().to_boolean());
+}
@@ -0,0 +1,34 @@
+use ion::ErrorKind;
+use ion::string::byte::{ByteStr, ByteString, header, Latin1, REPLACEMENT_BYTE, VisibleAscii};
+
+#[test]
+fn try_from_bytes() {
+	let visible = ByteString::<VisibleAscii>::try_from_bytes(b"text/plain".to_vec()).unwrap();
+	assert_eq!(b"text/plain", visible.as_bytes());
+
+	let error = ByteString::<VisibleAscii>::try_from_bytes(b"line\nbreak".to_vec()).unwrap_err();
+	assert_eq!(ErrorKind::Type, error.kind);
+	assert_eq!(
+		"ByteString contains invalid visible ASCII character 0x0A at index 4",
+		error.message
+	);
+
+	assert!(ByteString::<Latin1>::try_from_bytes(vec![0x00, 0xFF]).is_ok());
+	assert!(ByteStr::<VisibleAscii>::try_from_bytes(&[0x7F]).is_err());
+}
+
+#[test]
+fn from_lossy() {
+	let lossy = ByteString::<VisibleAscii>::from_lossy(b"caf\xC3\xA9\t".to_vec());
+	assert_eq!(b"caf???", lossy.as_bytes());
+	assert_eq!(b'?', REPLACEMENT_BYTE);
+
+	let latin1 = ByteString::<Latin1>::from_lossy(vec![0x00, 0xFF]);
+	assert_eq!(&[0x00, 0xFF], latin1.as_bytes());
+}
+
+#[test]
+fn header_names() {
+	assert_eq!(b"content-type", header::CONTENT_TYPE.as_bytes());
+	assert_eq!(b"set-cookie", header::SET_COOKIE.to_byte_string().as_bytes());
+}
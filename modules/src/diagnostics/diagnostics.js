@@ -0,0 +1,11 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const createHook = ______diagnosticsInternal______.createHook;
+export const removeHook = ______diagnosticsInternal______.removeHook;
+export const currentTaskId = ______diagnosticsInternal______.currentTaskId;
+
+export default Object.freeze(______diagnosticsInternal______);
@@ -0,0 +1,125 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use mozjs::jsapi::{JSFunction, JSFunctionSpec};
+
+use ion::{Context, Function, Object, TracedHeap, Value};
+use ion::conversions::ToValue;
+use ion::function::Enforce;
+use runtime::event_loop::{add_task_hooks, current_task_id, remove_task_hooks, TaskHooks, TaskInfo};
+use runtime::module::NativeModule;
+
+#[derive(FromValue)]
+struct HookCallbacks<'cx> {
+	init: Option<Function<'cx>>,
+	before: Option<Function<'cx>>,
+	after: Option<Function<'cx>>,
+	destroy: Option<Function<'cx>>,
+}
+
+/// Task hooks which call script callbacks.
+///
+/// Tasks scheduled by the callbacks themselves are not reported to them, to avoid recursion.
+struct ScriptTaskHooks {
+	init: Option<TracedHeap<*mut JSFunction>>,
+	before: Option<TracedHeap<*mut JSFunction>>,
+	after: Option<TracedHeap<*mut JSFunction>>,
+	destroy: Option<TracedHeap<*mut JSFunction>>,
+	running: Cell<bool>,
+}
+
+impl ScriptTaskHooks {
+	fn call(&self, cx: &Context, callback: &Option<TracedHeap<*mut JSFunction>>, args: impl FnOnce() -> Vec<Value>) {
+		let Some(callback) = callback else {
+			return;
+		};
+		if self.running.replace(true) {
+			return;
+		}
+
+		let callback = Function::from(callback.root(cx));
+		if let Err(Some(report)) = callback.call(cx, &Object::global(cx), &args()) {
+			eprintln!("Uncaught exception in task hook: {}", report.format(cx));
+		}
+		self.running.set(false);
+	}
+}
+
+impl TaskHooks for ScriptTaskHooks {
+	fn init(&self, cx: &Context, task: &TaskInfo) {
+		self.call(cx, &self.init, || {
+			vec![
+				task.id.as_value(cx),
+				task.kind.as_str().as_value(cx),
+				task.trigger_id.as_value(cx),
+			]
+		});
+	}
+
+	fn before(&self, cx: &Context, task: &TaskInfo) {
+		self.call(cx, &self.before, || vec![task.id.as_value(cx)]);
+	}
+
+	fn after(&self, cx: &Context, task: &TaskInfo, duration: Duration) {
+		self.call(cx, &self.after, || {
+			vec![task.id.as_value(cx), (duration.as_secs_f64() * 1000.0).as_value(cx)]
+		});
+	}
+
+	fn destroy(&self, cx: &Context, task: &TaskInfo) {
+		self.call(cx, &self.destroy, || vec![task.id.as_value(cx)]);
+	}
+}
+
+#[js_fn]
+fn createHook(cx: &Context, callbacks: HookCallbacks) -> u64 {
+	let traced = |callback: Option<Function>| callback.map(|callback| TracedHeap::new(callback.get()));
+	let hooks = ScriptTaskHooks {
+		init: traced(callbacks.init),
+		before: traced(callbacks.before),
+		after: traced(callbacks.after),
+		destroy: traced(callbacks.destroy),
+		running: Cell::new(false),
+	};
+	add_task_hooks(cx, Rc::new(hooks))
+}
+
+#[js_fn]
+fn removeHook(cx: &Context, Enforce(id): Enforce<u64>) -> bool {
+	remove_task_hooks(cx, id)
+}
+
+#[js_fn]
+fn currentTaskId(cx: &Context) -> u64 {
+	current_task_id(cx)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(createHook, 1),
+	function_spec!(removeHook, 1),
+	function_spec!(currentTaskId, 0),
+	JSFunctionSpec::ZERO,
+];
+
+#[derive(Default)]
+pub struct Diagnostics;
+
+impl NativeModule for Diagnostics {
+	const NAME: &'static str = "diagnostics";
+	const SOURCE: &'static str = include_str!("diagnostics.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let diagnostics = Object::new(cx);
+		if unsafe { diagnostics.define_methods(cx, FUNCTIONS) } {
+			return Some(diagnostics);
+		}
+		None
+	}
+}
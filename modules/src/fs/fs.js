@@ -17,6 +17,11 @@ export const copy = ______fsInternal______.copy;
 export const rename = ______fsInternal______.rename;
 export const softLink = ______fsInternal______.softLink;
 export const hardLink = ______fsInternal______.hardLink;
+export const readFile = ______fsInternal______.readFile;
+export const writeFile = ______fsInternal______.writeFile;
+export const stat = ______fsInternal______.stat;
+export const mkdir = ______fsInternal______.mkdir;
+export const rm = ______fsInternal______.rm;
 
 export const sync = ______fsInternal______.sync;
 
@@ -4,19 +4,24 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::{fs, os};
+use std::{fs, io, os};
 use std::iter::Iterator;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use futures::stream::StreamExt;
 use mozjs::jsapi::JSFunctionSpec;
 use tokio_stream::wrappers::ReadDirStream;
 
-use ion::{Context, Error, Object, Promise, Result};
+use ion::{Context, Error, ErrorKind, Object, Promise, Result, Value};
+use ion::conversions::ToValue;
 use ion::flags::PropertyFlags;
+use ion::function::Opt;
 use ion::typedarray::Uint8ArrayWrapper;
+use runtime::globals::file::BlobPart;
 use runtime::module::NativeModule;
 use runtime::promise::future_to_promise;
+use runtime::security::{check_read, check_write};
 
 fn check_exists(path: &Path) -> Result<()> {
 	if path.exists() {
@@ -85,9 +90,10 @@ fn check_is_not_dir(path: &Path) -> Result<()> {
 #[js_fn]
 fn readBinary(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise(cx, move |_| async move {
+		future_to_promise(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_read(&cx, path)?;
 			check_is_file(path)?;
 			if let Ok(bytes) = tokio::fs::read(&path).await {
 				Ok(Uint8ArrayWrapper::from(bytes))
@@ -99,9 +105,10 @@ fn readBinary(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn readBinarySync(path_str: String) -> Result<Uint8ArrayWrapper> {
+fn readBinarySync(cx: &Context, path_str: String) -> Result<Uint8ArrayWrapper> {
 	let path = Path::new(&path_str);
 
+	check_read(cx, path)?;
 	check_is_file(path)?;
 	if let Ok(bytes) = fs::read(path) {
 		Ok(Uint8ArrayWrapper::from(bytes))
@@ -113,9 +120,10 @@ fn readBinarySync(path_str: String) -> Result<Uint8ArrayWrapper> {
 #[js_fn]
 fn readString(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise(cx, move |_| async move {
+		future_to_promise(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_read(&cx, path)?;
 			check_is_file(path)?;
 			if let Ok(str) = tokio::fs::read_to_string(path).await {
 				Ok(str)
@@ -127,9 +135,10 @@ fn readString(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn readStringSync(path_str: String) -> Result<String> {
+fn readStringSync(cx: &Context, path_str: String) -> Result<String> {
 	let path = Path::new(&path_str);
 
+	check_read(cx, path)?;
 	check_is_file(path)?;
 	if let Ok(str) = fs::read_to_string(path) {
 		Ok(str)
@@ -141,9 +150,10 @@ fn readStringSync(path_str: String) -> Result<String> {
 #[js_fn]
 fn readDir(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_read(&cx, path)?;
 			check_is_dir(path)?;
 			if let Ok(dir) = tokio::fs::read_dir(path).await {
 				let mut entries: Vec<_> = ReadDirStream::new(dir)
@@ -162,9 +172,10 @@ fn readDir(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn readDirSync(path_str: String) -> Result<Vec<String>> {
+fn readDirSync(cx: &Context, path_str: String) -> Result<Vec<String>> {
 	let path = Path::new(&path_str);
 
+	check_read(cx, path)?;
 	check_is_dir(path)?;
 	if let Ok(dir) = fs::read_dir(path) {
 		let mut entries: Vec<_> = dir
@@ -182,9 +193,10 @@ fn readDirSync(path_str: String) -> Result<Vec<String>> {
 #[js_fn]
 fn write(cx: &Context, path_str: String, contents: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_write(&cx, path)?;
 			check_is_not_dir(path)?;
 			Ok(tokio::fs::write(path, contents).await.is_ok())
 		})
@@ -192,9 +204,10 @@ fn write(cx: &Context, path_str: String, contents: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn writeSync(path_str: String, contents: String) -> Result<bool> {
+fn writeSync(cx: &Context, path_str: String, contents: String) -> Result<bool> {
 	let path = Path::new(&path_str);
 
+	check_write(cx, path)?;
 	check_is_not_dir(path)?;
 	Ok(fs::write(path, contents).is_ok())
 }
@@ -202,9 +215,10 @@ fn writeSync(path_str: String, contents: String) -> Result<bool> {
 #[js_fn]
 fn createDir(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_write(&cx, path)?;
 			check_is_not_file(path)?;
 			Ok(tokio::fs::create_dir(path).await.is_ok())
 		})
@@ -212,9 +226,10 @@ fn createDir(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn createDirSync(path_str: String) -> Result<bool> {
+fn createDirSync(cx: &Context, path_str: String) -> Result<bool> {
 	let path = Path::new(&path_str);
 
+	check_write(cx, path)?;
 	check_is_not_file(path)?;
 	Ok(fs::create_dir(path).is_ok())
 }
@@ -222,9 +237,10 @@ fn createDirSync(path_str: String) -> Result<bool> {
 #[js_fn]
 fn createDirRecursive(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_write(&cx, path)?;
 			check_is_not_file(path)?;
 			Ok(tokio::fs::create_dir_all(path).await.is_ok())
 		})
@@ -232,9 +248,10 @@ fn createDirRecursive(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn createDirRecursiveSync(path_str: String) -> Result<bool> {
+fn createDirRecursiveSync(cx: &Context, path_str: String) -> Result<bool> {
 	let path = Path::new(&path_str);
 
+	check_write(cx, path)?;
 	check_is_not_file(path)?;
 	Ok(fs::create_dir_all(path).is_ok())
 }
@@ -242,9 +259,10 @@ fn createDirRecursiveSync(path_str: String) -> Result<bool> {
 #[js_fn]
 fn removeFile(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_write(&cx, path)?;
 			check_is_file(path)?;
 			Ok(tokio::fs::remove_file(path).await.is_ok())
 		})
@@ -252,9 +270,10 @@ fn removeFile(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn removeFileSync(path_str: String) -> Result<bool> {
+fn removeFileSync(cx: &Context, path_str: String) -> Result<bool> {
 	let path = Path::new(&path_str);
 
+	check_write(cx, path)?;
 	check_is_file(path)?;
 	Ok(fs::remove_file(path).is_ok())
 }
@@ -262,9 +281,10 @@ fn removeFileSync(path_str: String) -> Result<bool> {
 #[js_fn]
 fn removeDir(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_write(&cx, path)?;
 			check_is_dir(path)?;
 			Ok(tokio::fs::remove_file(path).await.is_ok())
 		})
@@ -272,9 +292,10 @@ fn removeDir(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn removeDirSync(path_str: String) -> Result<bool> {
+fn removeDirSync(cx: &Context, path_str: String) -> Result<bool> {
 	let path = Path::new(&path_str);
 
+	check_write(cx, path)?;
 	check_is_dir(path)?;
 	Ok(fs::remove_file(path).is_ok())
 }
@@ -282,9 +303,10 @@ fn removeDirSync(path_str: String) -> Result<bool> {
 #[js_fn]
 fn removeDirRecursive(cx: &Context, path_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let path = Path::new(&path_str);
 
+			check_write(&cx, path)?;
 			check_is_dir(path)?;
 			Ok(tokio::fs::remove_dir_all(path).await.is_ok())
 		})
@@ -292,9 +314,10 @@ fn removeDirRecursive(cx: &Context, path_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn removeDirRecursiveSync(path_str: String) -> Result<bool> {
+fn removeDirRecursiveSync(cx: &Context, path_str: String) -> Result<bool> {
 	let path = Path::new(&path_str);
 
+	check_write(cx, path)?;
 	check_is_dir(path)?;
 	Ok(fs::remove_dir_all(path).is_ok())
 }
@@ -302,10 +325,12 @@ fn removeDirRecursiveSync(path_str: String) -> Result<bool> {
 #[js_fn]
 fn copy(cx: &Context, from_str: String, to_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let from = Path::new(&from_str);
 			let to = Path::new(&to_str);
 
+			check_read(&cx, from)?;
+			check_write(&cx, to)?;
 			check_is_not_dir(from)?;
 			check_is_not_dir(to)?;
 			Ok(tokio::fs::copy(from, to).await.is_ok())
@@ -314,10 +339,12 @@ fn copy(cx: &Context, from_str: String, to_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn copySync(from_str: String, to_str: String) -> Result<bool> {
+fn copySync(cx: &Context, from_str: String, to_str: String) -> Result<bool> {
 	let from = Path::new(&from_str);
 	let to = Path::new(&to_str);
 
+	check_read(cx, from)?;
+	check_write(cx, to)?;
 	check_is_not_dir(from)?;
 	check_is_not_dir(to)?;
 	Ok(fs::copy(from, to).is_ok())
@@ -326,10 +353,12 @@ fn copySync(from_str: String, to_str: String) -> Result<bool> {
 #[js_fn]
 fn rename(cx: &Context, from_str: String, to_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let from = Path::new(&from_str);
 			let to = Path::new(&to_str);
 
+			check_write(&cx, from)?;
+			check_write(&cx, to)?;
 			check_is_not_dir(from)?;
 			check_is_not_dir(to)?;
 			Ok(tokio::fs::rename(from, to).await.is_ok())
@@ -338,10 +367,12 @@ fn rename(cx: &Context, from_str: String, to_str: String) -> Option<Promise> {
 }
 
 #[js_fn]
-fn renameSync(from_str: String, to_str: String) -> Result<bool> {
+fn renameSync(cx: &Context, from_str: String, to_str: String) -> Result<bool> {
 	let from = Path::new(&from_str);
 	let to = Path::new(&to_str);
 
+	check_write(cx, from)?;
+	check_write(cx, to)?;
 	check_is_not_dir(from)?;
 	check_is_not_dir(to)?;
 	Ok(fs::rename(from, to).is_ok())
@@ -350,11 +381,12 @@ fn renameSync(from_str: String, to_str: String) -> Result<bool> {
 #[js_fn]
 fn softLink(cx: &Context, original_str: String, link_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			#[cfg(not(target_os = "wasi"))]
 			let original = Path::new(&original_str);
 			let link = Path::new(&link_str);
 
+			check_write(&cx, link)?;
 			check_not_exists(link)?;
 			#[cfg(unix)]
 			{
@@ -385,10 +417,11 @@ fn softLink(cx: &Context, original_str: String, link_str: String) -> Option<Prom
 }
 
 #[js_fn]
-fn softLinkSync(original_str: String, link_str: String) -> Result<bool> {
+fn softLinkSync(cx: &Context, original_str: String, link_str: String) -> Result<bool> {
 	let original = Path::new(&original_str);
 	let link = Path::new(&link_str);
 
+	check_write(cx, link)?;
 	check_not_exists(link)?;
 	#[cfg(unix)]
 	{
@@ -413,10 +446,12 @@ fn softLinkSync(original_str: String, link_str: String) -> Result<bool> {
 #[js_fn]
 fn hardLink(cx: &Context, original_str: String, link_str: String) -> Option<Promise> {
 	unsafe {
-		future_to_promise::<_, _, _, Error>(cx, move |_| async move {
+		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 			let original = Path::new(&original_str);
 			let link = Path::new(&link_str);
 
+			check_write(&cx, original)?;
+			check_write(&cx, link)?;
 			check_not_exists(link)?;
 			Ok(tokio::fs::hard_link(original, link).await.is_ok())
 		})
@@ -424,14 +459,245 @@ fn hardLink(cx: &Context, original_str: String, link_str: String) -> Option<Prom
 }
 
 #[js_fn]
-fn hardLinkSync(original_str: String, link_str: String) -> Result<bool> {
+fn hardLinkSync(cx: &Context, original_str: String, link_str: String) -> Result<bool> {
 	let original = Path::new(&original_str);
 	let link = Path::new(&link_str);
 
+	check_write(cx, original)?;
+	check_write(cx, link)?;
 	check_not_exists(link)?;
 	Ok(fs::hard_link(original, link).is_ok())
 }
 
+fn io_error(action: &str, path: &str, error: io::Error) -> Error {
+	Error::new(format!("Could not {} {}: {}", action, path, error), None)
+}
+
+enum FileContents {
+	Bytes(Uint8ArrayWrapper),
+	Text(String),
+}
+
+impl<'cx> ToValue<'cx> for FileContents {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		match self {
+			FileContents::Bytes(bytes) => bytes.to_value(cx, value),
+			FileContents::Text(text) => text.to_value(cx, value),
+		}
+	}
+}
+
+/// Encoding of the contents returned by `readFile`, which are returned as a `Uint8Array` if it has none.
+#[derive(Clone, Copy)]
+enum ReadEncoding {
+	Binary,
+	Utf8,
+}
+
+impl ReadEncoding {
+	fn new(encoding: Option<String>) -> Result<ReadEncoding> {
+		match encoding.as_deref().map(str::to_ascii_lowercase).as_deref() {
+			None => Ok(ReadEncoding::Binary),
+			Some("utf-8" | "utf8") => Ok(ReadEncoding::Utf8),
+			Some(encoding) => Err(Error::new(
+				format!("Unsupported encoding: {}", encoding),
+				ErrorKind::Type,
+			)),
+		}
+	}
+
+	fn decode(self, path: &str, bytes: Vec<u8>) -> Result<FileContents> {
+		match self {
+			ReadEncoding::Binary => Ok(FileContents::Bytes(Uint8ArrayWrapper::from(bytes))),
+			ReadEncoding::Utf8 => String::from_utf8(bytes)
+				.map(FileContents::Text)
+				.map_err(|_| Error::new(format!("File {} is not valid UTF-8", path), None)),
+		}
+	}
+}
+
+struct FileStat {
+	is_file: bool,
+	is_directory: bool,
+	is_symlink: bool,
+	size: u64,
+	modified: Option<f64>,
+	accessed: Option<f64>,
+	created: Option<f64>,
+}
+
+impl FileStat {
+	fn new(metadata: fs::Metadata, is_symlink: bool) -> FileStat {
+		fn millis(time: io::Result<SystemTime>) -> Option<f64> {
+			let duration = time.ok()?.duration_since(UNIX_EPOCH).ok()?;
+			Some(duration.as_secs_f64() * 1000.0)
+		}
+
+		FileStat {
+			is_file: metadata.is_file(),
+			is_directory: metadata.is_dir(),
+			is_symlink,
+			size: metadata.len(),
+			modified: millis(metadata.modified()),
+			accessed: millis(metadata.accessed()),
+			created: millis(metadata.created()),
+		}
+	}
+}
+
+impl<'cx> ToValue<'cx> for FileStat {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "isFile", &self.is_file);
+		object.set_as(cx, "isDirectory", &self.is_directory);
+		object.set_as(cx, "isSymlink", &self.is_symlink);
+		object.set_as(cx, "size", &self.size);
+		object.set_as(cx, "modified", &self.modified);
+		object.set_as(cx, "accessed", &self.accessed);
+		object.set_as(cx, "created", &self.created);
+		object.to_value(cx, value);
+	}
+}
+
+#[derive(Default, FromValue)]
+struct MakeDirOptions {
+	#[ion(default)]
+	recursive: bool,
+}
+
+#[derive(Default, FromValue)]
+struct RemoveOptions {
+	/// Removes directories and their contents. Empty directories are removed regardless.
+	#[ion(default)]
+	recursive: bool,
+	/// Ignores paths which do not exist.
+	#[ion(default)]
+	force: bool,
+}
+
+impl RemoveOptions {
+	fn check(&self, result: io::Result<()>) -> io::Result<()> {
+		match result {
+			Err(error) if self.force && error.kind() == io::ErrorKind::NotFound => Ok(()),
+			result => result,
+		}
+	}
+}
+
+#[js_fn]
+fn readFile(cx: &Context, path_str: String, Opt(encoding): Opt<String>) -> Option<Promise> {
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			check_read(&cx, Path::new(&path_str))?;
+			let encoding = ReadEncoding::new(encoding)?;
+			let bytes = tokio::fs::read(&path_str).await.map_err(|e| io_error("read", &path_str, e))?;
+			encoding.decode(&path_str, bytes)
+		})
+	}
+}
+
+#[js_fn]
+fn readFileSync(cx: &Context, path_str: String, Opt(encoding): Opt<String>) -> Result<FileContents> {
+	check_read(cx, Path::new(&path_str))?;
+	let encoding = ReadEncoding::new(encoding)?;
+	let bytes = fs::read(&path_str).map_err(|e| io_error("read", &path_str, e))?;
+	encoding.decode(&path_str, bytes)
+}
+
+#[js_fn]
+fn writeFile(cx: &Context, path_str: String, data: BlobPart) -> Option<Promise> {
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			check_write(&cx, Path::new(&path_str))?;
+			tokio::fs::write(&path_str, data.0).await.map_err(|e| io_error("write", &path_str, e))
+		})
+	}
+}
+
+#[js_fn]
+fn writeFileSync(cx: &Context, path_str: String, data: BlobPart) -> Result<()> {
+	check_write(cx, Path::new(&path_str))?;
+	fs::write(&path_str, data.0).map_err(|e| io_error("write", &path_str, e))
+}
+
+#[js_fn]
+fn stat(cx: &Context, path_str: String) -> Option<Promise> {
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			check_read(&cx, Path::new(&path_str))?;
+			let metadata = tokio::fs::metadata(&path_str).await.map_err(|e| io_error("stat", &path_str, e))?;
+			let is_symlink = tokio::fs::symlink_metadata(&path_str).await.is_ok_and(|metadata| metadata.is_symlink());
+			Ok::<_, Error>(FileStat::new(metadata, is_symlink))
+		})
+	}
+}
+
+#[js_fn]
+fn statSync(cx: &Context, path_str: String) -> Result<FileStat> {
+	check_read(cx, Path::new(&path_str))?;
+	let metadata = fs::metadata(&path_str).map_err(|e| io_error("stat", &path_str, e))?;
+	let is_symlink = fs::symlink_metadata(&path_str).is_ok_and(|metadata| metadata.is_symlink());
+	Ok(FileStat::new(metadata, is_symlink))
+}
+
+#[js_fn]
+fn mkdir(cx: &Context, path_str: String, Opt(options): Opt<MakeDirOptions>) -> Option<Promise> {
+	let options = options.unwrap_or_default();
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			check_write(&cx, Path::new(&path_str))?;
+			let result = if options.recursive {
+				tokio::fs::create_dir_all(&path_str).await
+			} else {
+				tokio::fs::create_dir(&path_str).await
+			};
+			result.map_err(|e| io_error("create directory", &path_str, e))
+		})
+	}
+}
+
+#[js_fn]
+fn mkdirSync(cx: &Context, path_str: String, Opt(options): Opt<MakeDirOptions>) -> Result<()> {
+	check_write(cx, Path::new(&path_str))?;
+	let options = options.unwrap_or_default();
+	let result = if options.recursive {
+		fs::create_dir_all(&path_str)
+	} else {
+		fs::create_dir(&path_str)
+	};
+	result.map_err(|e| io_error("create directory", &path_str, e))
+}
+
+#[js_fn]
+fn rm(cx: &Context, path_str: String, Opt(options): Opt<RemoveOptions>) -> Option<Promise> {
+	let options = options.unwrap_or_default();
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			check_write(&cx, Path::new(&path_str))?;
+			let result = match tokio::fs::symlink_metadata(&path_str).await {
+				Ok(metadata) if metadata.is_dir() && options.recursive => tokio::fs::remove_dir_all(&path_str).await,
+				Ok(metadata) if metadata.is_dir() => tokio::fs::remove_dir(&path_str).await,
+				Ok(_) => tokio::fs::remove_file(&path_str).await,
+				Err(error) => Err(error),
+			};
+			options.check(result).map_err(|e| io_error("remove", &path_str, e))
+		})
+	}
+}
+
+#[js_fn]
+fn rmSync(cx: &Context, path_str: String, Opt(options): Opt<RemoveOptions>) -> Result<()> {
+	check_write(cx, Path::new(&path_str))?;
+	let options = options.unwrap_or_default();
+	let result = match fs::symlink_metadata(&path_str) {
+		Ok(metadata) if metadata.is_dir() && options.recursive => fs::remove_dir_all(&path_str),
+		Ok(metadata) if metadata.is_dir() => fs::remove_dir(&path_str),
+		Ok(_) => fs::remove_file(&path_str),
+		Err(error) => Err(error),
+	};
+	options.check(result).map_err(|e| io_error("remove", &path_str, e))
+}
+
 const SYNC_FUNCTIONS: &[JSFunctionSpec] = &[
 	function_spec!(readBinarySync, "readBinary", 1),
 	function_spec!(readStringSync, "readString", 1),
@@ -446,6 +712,11 @@ const SYNC_FUNCTIONS: &[JSFunctionSpec] = &[
 	function_spec!(renameSync, "rename", 2),
 	function_spec!(softLinkSync, "softLink", 2),
 	function_spec!(hardLinkSync, "hardLink", 2),
+	function_spec!(readFileSync, "readFile", 1),
+	function_spec!(writeFileSync, "writeFile", 2),
+	function_spec!(statSync, "stat", 1),
+	function_spec!(mkdirSync, "mkdir", 1),
+	function_spec!(rmSync, "rm", 1),
 	JSFunctionSpec::ZERO,
 ];
 
@@ -463,6 +734,11 @@ const ASYNC_FUNCTIONS: &[JSFunctionSpec] = &[
 	function_spec!(rename, 2),
 	function_spec!(softLink, 2),
 	function_spec!(hardLink, 2),
+	function_spec!(readFile, 1),
+	function_spec!(writeFile, 2),
+	function_spec!(stat, 1),
+	function_spec!(mkdir, 1),
+	function_spec!(rm, 1),
 	JSFunctionSpec::ZERO,
 ];
 
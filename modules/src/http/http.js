@@ -0,0 +1,11 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const serve = ______httpInternal______.serve;
+
+export const Server = ______httpInternal______.Server;
+
+export default Object.freeze(______httpInternal______);
@@ -0,0 +1,106 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::net::SocketAddr;
+use std::rc::Rc;
+
+use mozjs::jsapi::{JSFunctionSpec, JSObject};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Function, Heap, Object, Result, TracedHeap};
+use ion::class::Reflector;
+use ion::function::Opt;
+use runtime::globals::dispose::{define_class_disposal, Disposal};
+use runtime::module::NativeModule;
+use runtime::promise::future_to_promise;
+
+use crate::net::{check_net_permission, ListenOptions, net_error, NetAddr};
+
+/// HTTP server, which passes incoming requests to the handler given to [serve].
+#[js_class]
+pub struct Server {
+	reflector: Reflector,
+	finished: Heap<*mut JSObject>,
+	#[trace(no_trace)]
+	addr: SocketAddr,
+	#[trace(no_trace)]
+	shutdown: Rc<Notify>,
+}
+
+#[js_class]
+impl Server {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Server> {
+		Err(Error::new("Server has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_addr(&self) -> NetAddr {
+		NetAddr { transport: "tcp", addr: self.addr }
+	}
+
+	/// Promise which resolves once the server is closed.
+	#[ion(get)]
+	pub fn get_finished(&self) -> *mut JSObject {
+		self.finished.get()
+	}
+
+	/// Stops accepting connections, and closes open connections.
+	pub fn close(&self) {
+		self.shutdown.notify_waiters();
+	}
+}
+
+#[js_fn]
+fn serve(cx: &Context, handler: Function, Opt(options): Opt<ListenOptions>) -> Result<*mut JSObject> {
+	check_net_permission()?;
+	let options = options.unwrap_or_default();
+
+	let listener = std::net::TcpListener::bind((options.hostname.as_str(), options.port))
+		.and_then(|listener| {
+			listener.set_nonblocking(true)?;
+			TcpListener::from_std(listener)
+		})
+		.map_err(|error| net_error("listen", error))?;
+	let addr = listener.local_addr().map_err(|error| net_error("get local address", error))?;
+
+	let shutdown = Rc::new(Notify::new());
+	let handler = TracedHeap::new(handler.get());
+	let finished = {
+		let shutdown = Rc::clone(&shutdown);
+		unsafe { future_to_promise(cx, move |cx| runtime::server::serve(cx, listener, handler, shutdown)) }
+	}
+	.ok_or_else(|| Error::new("Future queue is not running", ErrorKind::Normal))?;
+
+	let server = Server {
+		reflector: Reflector::default(),
+		finished: Heap::new(finished.get()),
+		addr,
+		shutdown,
+	};
+	Ok(Server::new_object(cx, Box::new(server)))
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(serve, 1), JSFunctionSpec::ZERO];
+
+#[derive(Default)]
+pub struct Http;
+
+impl NativeModule for Http {
+	const NAME: &'static str = "http";
+	const SOURCE: &'static str = include_str!("http.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let http = Object::new(cx);
+		if unsafe { http.define_methods(cx, FUNCTIONS) }
+			&& define_class_disposal(cx, Server::init_class(cx, &http), "close", Disposal::Sync)
+		{
+			return Some(http);
+		}
+		None
+	}
+}
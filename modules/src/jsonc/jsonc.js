@@ -0,0 +1,16 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const strip = ______jsoncInternal______.strip;
+
+export function parse(text, reviver) {
+	return JSON.parse(strip(text), reviver);
+}
+
+export default Object.freeze({
+	strip,
+	parse,
+});
@@ -0,0 +1,33 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Object, Result};
+use runtime::module::{NativeModule, strip_jsonc};
+
+#[js_fn]
+fn strip(text: String) -> Result<String> {
+	strip_jsonc(&text)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(strip, 1), JSFunctionSpec::ZERO];
+
+#[derive(Default)]
+pub struct Jsonc;
+
+impl NativeModule for Jsonc {
+	const NAME: &'static str = "jsonc";
+	const SOURCE: &'static str = include_str!("jsonc.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let jsonc = Object::new(cx);
+		if unsafe { jsonc.define_methods(cx, FUNCTIONS) } {
+			return Some(jsonc);
+		}
+		None
+	}
+}
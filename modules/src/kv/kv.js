@@ -0,0 +1,16 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const get = ______kvInternal______.get;
+
+export const put = ______kvInternal______.put;
+
+const del = ______kvInternal______.delete;
+export { del as delete };
+
+export const list = ______kvInternal______.list;
+
+export default Object.freeze(______kvInternal______);
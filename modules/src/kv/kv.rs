@@ -0,0 +1,125 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::conversions::ConversionBehavior;
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Error, Object, Promise, Result, Value};
+use ion::conversions::ToValue;
+use ion::function::Opt;
+use ion::typedarray::ArrayBufferWrapper;
+use runtime::globals::file::BlobPart;
+use runtime::kv::{kv_error, kv_store};
+use runtime::module::NativeModule;
+
+#[derive(Default, FromValue)]
+struct ListOptions {
+	#[ion(default)]
+	prefix: String,
+	#[ion(convert = ConversionBehavior::EnforceRange)]
+	limit: Option<u32>,
+}
+
+/// Type of the values resolved by `get`.
+#[derive(Clone, Copy)]
+enum ValueType {
+	Text,
+	ArrayBuffer,
+}
+
+impl ValueType {
+	fn new(kind: Option<String>) -> Result<ValueType> {
+		match kind.as_deref() {
+			None | Some("text") => Ok(ValueType::Text),
+			Some("arrayBuffer") => Ok(ValueType::ArrayBuffer),
+			Some(kind) => Err(Error::new(format!("Unsupported value type: {}", kind), None)),
+		}
+	}
+}
+
+enum KvValue {
+	Text(String),
+	Buffer(Vec<u8>),
+}
+
+impl<'cx> ToValue<'cx> for KvValue {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		match self {
+			KvValue::Text(text) => text.to_value(cx, value),
+			KvValue::Buffer(bytes) => {
+				if let Some(buffer) = ArrayBufferWrapper::from(bytes.clone()).into_array_buffer(cx) {
+					buffer.to_value(cx, value);
+				}
+			}
+		}
+	}
+}
+
+fn get_value(cx: &Context, key: &str, kind: ValueType) -> Result<Option<KvValue>> {
+	let value = kv_store(cx).get(key).map_err(kv_error)?;
+	Ok(value.map(|value| match kind {
+		ValueType::Text => KvValue::Text(String::from_utf8_lossy(&value).into_owned()),
+		ValueType::ArrayBuffer => KvValue::Buffer(value),
+	}))
+}
+
+/// Resolves with the value stored for the key as a string, or as an `ArrayBuffer` if `type` is `"arrayBuffer"`.
+/// Resolves with `null` if there is none.
+#[js_fn]
+fn get(cx: &Context, key: String, Opt(kind): Opt<String>) -> Promise {
+	let value = ValueType::new(kind).and_then(|kind| get_value(cx, &key, kind));
+	Promise::from_result(cx, value)
+}
+
+#[js_fn]
+fn put(cx: &Context, key: String, value: BlobPart) -> Promise {
+	let result = kv_store(cx).put(&key, Vec::from(value.0)).map_err(kv_error);
+	Promise::from_result(cx, result)
+}
+
+/// Resolves with `false` if there was no value stored for the key.
+#[js_fn]
+fn delete(cx: &Context, key: String) -> Promise {
+	let deleted = kv_store(cx).delete(&key).map_err(kv_error);
+	Promise::from_result(cx, deleted)
+}
+
+/// Resolves with the keys which start with `prefix`, in the order they were first stored.
+#[js_fn]
+fn list(cx: &Context, Opt(options): Opt<ListOptions>) -> Promise {
+	let options = options.unwrap_or_default();
+	let keys = kv_store(cx).keys().map_err(kv_error).map(|keys| {
+		let keys = keys.into_iter().filter(|key| key.starts_with(&options.prefix));
+		match options.limit {
+			Some(limit) => keys.take(limit as usize).collect(),
+			None => keys.collect::<Vec<_>>(),
+		}
+	});
+	Promise::from_result(cx, keys)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(get, 1),
+	function_spec!(put, 2),
+	function_spec!(delete, 1),
+	function_spec!(list, 0),
+	JSFunctionSpec::ZERO,
+];
+
+pub struct Kv;
+
+impl NativeModule for Kv {
+	const NAME: &'static str = "kv";
+	const SOURCE: &'static str = include_str!("kv.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let kv = Object::new(cx);
+		if unsafe { kv.define_methods(cx, FUNCTIONS) } {
+			return Some(kv);
+		}
+		None
+	}
+}
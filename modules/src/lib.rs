@@ -14,29 +14,85 @@ use ion::{Context, Object};
 use runtime::module::{init_global_module, init_module, StandardModules};
 
 pub use crate::assert::Assert;
+pub use crate::diagnostics::Diagnostics;
 pub use crate::fs::FileSystem;
+#[cfg(feature = "http")]
+pub use crate::http::Http;
+pub use crate::jsonc::Jsonc;
+pub use crate::kv::Kv;
+pub use crate::meta::Meta;
+pub use crate::net::Net;
+pub use crate::os::Os;
 pub use crate::path::PathM;
+pub use crate::perf::Perf;
+pub use crate::store::StoreM;
+pub use crate::stream::Stream;
 pub use crate::url::UrlM;
 
 mod assert;
+mod diagnostics;
 mod fs;
+#[cfg(feature = "http")]
+mod http;
+mod jsonc;
+mod kv;
+mod meta;
+mod net;
+mod os;
 mod path;
+mod perf;
+mod store;
+mod stream;
 mod url;
 
 pub struct Modules;
 
 impl StandardModules for Modules {
 	fn init(self, cx: &Context, global: &Object) -> bool {
-		init_module::<Assert>(cx, global)
+		let result = init_module::<Assert>(cx, global)
+			&& init_module::<Diagnostics>(cx, global)
 			&& init_module::<FileSystem>(cx, global)
+			&& init_module::<Jsonc>(cx, global)
+			&& init_module::<Kv>(cx, global)
+			&& init_module::<Meta>(cx, global)
+			&& init_module::<Net>(cx, global)
+			&& init_module::<Os>(cx, global)
 			&& init_module::<PathM>(cx, global)
-			&& init_module::<UrlM>(cx, global)
+			&& init_module::<Perf>(cx, global)
+			&& init_module::<StoreM>(cx, global)
+			&& init_module::<Stream>(cx, global)
+			&& init_module::<UrlM>(cx, global);
+		#[cfg(feature = "http")]
+		{
+			result && init_module::<Http>(cx, global)
+		}
+		#[cfg(not(feature = "http"))]
+		{
+			result
+		}
 	}
 
 	fn init_globals(self, cx: &Context, global: &Object) -> bool {
-		init_global_module::<Assert>(cx, global)
+		let result = init_global_module::<Assert>(cx, global)
+			&& init_global_module::<Diagnostics>(cx, global)
 			&& init_global_module::<FileSystem>(cx, global)
+			&& init_global_module::<Jsonc>(cx, global)
+			&& init_global_module::<Kv>(cx, global)
+			&& init_global_module::<Meta>(cx, global)
+			&& init_global_module::<Net>(cx, global)
+			&& init_global_module::<Os>(cx, global)
 			&& init_global_module::<PathM>(cx, global)
-			&& init_global_module::<UrlM>(cx, global)
+			&& init_global_module::<Perf>(cx, global)
+			&& init_global_module::<StoreM>(cx, global)
+			&& init_global_module::<Stream>(cx, global)
+			&& init_global_module::<UrlM>(cx, global);
+		#[cfg(feature = "http")]
+		{
+			result && init_global_module::<Http>(cx, global)
+		}
+		#[cfg(not(feature = "http"))]
+		{
+			result
+		}
 	}
 }
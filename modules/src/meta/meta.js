@@ -0,0 +1,12 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const version = ______metaInternal______.version;
+export const spidermonkey = ______metaInternal______.spidermonkey;
+export const features = ______metaInternal______.features;
+export const target = ______metaInternal______.target;
+
+export default ______metaInternal______;
@@ -0,0 +1,21 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use ion::{Context, Object};
+use runtime::globals::meta::build_info;
+use runtime::module::NativeModule;
+
+#[derive(Default)]
+pub struct Meta;
+
+impl NativeModule for Meta {
+	const NAME: &'static str = "meta";
+	const SOURCE: &'static str = include_str!("meta.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		build_info(cx)
+	}
+}
@@ -0,0 +1,472 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::net::SocketAddr;
+use std::pin::pin;
+use std::rc::Rc;
+
+use bytes::Bytes;
+use futures::future::{AbortHandle, Either, select};
+use futures::stream::{Stream, abortable, try_unfold};
+use mozjs::conversions::ConversionBehavior;
+use mozjs::jsapi::{JSFunctionSpec, JSObject};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::Notify;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Heap, Object, Promise, Result, ResultExc, Value};
+use ion::class::Reflector;
+use ion::conversions::{FromValue, ToValue};
+use ion::function::Opt;
+use ion::typedarray::Uint8ArrayWrapper;
+use runtime::config::Config;
+use runtime::globals::dispose::{define_class_disposal, Disposal};
+use runtime::globals::file::BlobPart;
+use runtime::globals::streams::{
+	NativeStreamSinkCallbacks, readable_stream_from_byte_stream, writable_stream_from_callbacks,
+};
+use runtime::module::NativeModule;
+use runtime::promise::future_to_promise;
+
+/// Maximum size of the chunks read from connections, and of received datagrams.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+const DEFAULT_HOSTNAME: &str = "127.0.0.1";
+
+pub(crate) fn check_net_permission() -> Result<()> {
+	if Config::global().allow_net {
+		Ok(())
+	} else {
+		Err(Error::new("Access to the network requires the net permission.", None))
+	}
+}
+
+pub(crate) fn net_error(action: &str, error: io::Error) -> Error {
+	Error::new(format!("Failed to {}: {}", action, error), ErrorKind::Normal)
+}
+
+fn closed_error() -> Error {
+	Error::new("Socket is closed", ErrorKind::Normal)
+}
+
+#[derive(FromValue)]
+struct ConnectOptions {
+	#[ion(default = String::from(DEFAULT_HOSTNAME))]
+	hostname: String,
+	#[ion(convert = ConversionBehavior::EnforceRange)]
+	port: u16,
+}
+
+#[derive(FromValue)]
+pub(crate) struct ListenOptions {
+	#[ion(default = String::from(DEFAULT_HOSTNAME))]
+	pub(crate) hostname: String,
+	/// Port to bind to, where 0 binds to any available port.
+	#[ion(default, convert = ConversionBehavior::EnforceRange)]
+	pub(crate) port: u16,
+}
+
+impl Default for ListenOptions {
+	fn default() -> ListenOptions {
+		ListenOptions {
+			hostname: String::from(DEFAULT_HOSTNAME),
+			port: 0,
+		}
+	}
+}
+
+/// Address of a socket, converted to `{ transport, hostname, port }`.
+pub(crate) struct NetAddr {
+	pub(crate) transport: &'static str,
+	pub(crate) addr: SocketAddr,
+}
+
+impl<'cx> ToValue<'cx> for NetAddr {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "transport", self.transport);
+		object.set_as(cx, "hostname", &self.addr.ip().to_string());
+		object.set_as(cx, "port", &self.addr.port());
+		object.to_value(cx, value);
+	}
+}
+
+fn read_stream(half: OwnedReadHalf) -> impl Stream<Item = io::Result<Bytes>> {
+	try_unfold(half, |mut half| async move {
+		let mut chunk = vec![0; READ_CHUNK_SIZE];
+		let read = half.read(&mut chunk).await?;
+		if read == 0 {
+			return Ok(None);
+		}
+		chunk.truncate(read);
+		Ok(Some((Bytes::from(chunk), half)))
+	})
+}
+
+/// Write half of a connection, shared between its [Connection] and the sink of its writable stream.
+struct ConnectionWriter {
+	half: RefCell<Option<OwnedWriteHalf>>,
+	closed: Cell<bool>,
+}
+
+impl ConnectionWriter {
+	fn take(&self) -> Result<OwnedWriteHalf> {
+		self.half.borrow_mut().take().ok_or_else(closed_error)
+	}
+
+	async fn write(&self, bytes: Bytes) -> Result<()> {
+		let mut half = self.take()?;
+		let result = half.write_all(&bytes).await;
+		if !self.closed.get() {
+			*self.half.borrow_mut() = Some(half);
+		}
+		result.map_err(|error| net_error("write to connection", error))
+	}
+
+	async fn shutdown(&self) -> Result<()> {
+		let mut half = self.take()?;
+		self.closed.set(true);
+		half.shutdown().await.map_err(|error| net_error("shut down connection", error))
+	}
+
+	/// Drops the write half, which shuts down the connection for writing.
+	fn close(&self) {
+		self.closed.set(true);
+		self.half.borrow_mut().take();
+	}
+}
+
+struct ConnectionSink {
+	writer: Rc<ConnectionWriter>,
+}
+
+impl NativeStreamSinkCallbacks for ConnectionSink {
+	fn start<'cx>(&self, cx: &'cx Context, _: Object<'cx>) -> ResultExc<Value<'cx>> {
+		Ok(Value::undefined(cx))
+	}
+
+	fn write(&self, cx: &Context, chunk: Value, _: Object) -> ResultExc<Promise> {
+		let BlobPart(bytes) = BlobPart::from_value(cx, &chunk, false, ())?;
+		let writer = Rc::clone(&self.writer);
+		unsafe { future_to_promise(cx, move |_| async move { writer.write(bytes).await }) }
+			.ok_or_else(|| Error::new("Future queue is not running", ErrorKind::Normal).into())
+	}
+
+	fn close(&self, cx: &Context) -> ResultExc<Promise> {
+		let writer = Rc::clone(&self.writer);
+		unsafe { future_to_promise(cx, move |_| async move { writer.shutdown().await }) }
+			.ok_or_else(|| Error::new("Future queue is not running", ErrorKind::Normal).into())
+	}
+
+	fn abort(&self, cx: &Context, _: Value) -> ResultExc<Promise> {
+		self.writer.close();
+		Ok(Promise::resolved(cx, Value::undefined(cx)))
+	}
+}
+
+/// TCP connection, whose incoming and outgoing bytes are exposed as a [ReadableStream](ion::ReadableStream) and a
+/// [WritableStream](ion::object::WritableStream).
+#[js_class]
+pub struct Connection {
+	reflector: Reflector,
+	readable: Heap<*mut JSObject>,
+	writable: Heap<*mut JSObject>,
+	#[trace(no_trace)]
+	local: SocketAddr,
+	#[trace(no_trace)]
+	remote: SocketAddr,
+	#[trace(no_trace)]
+	read_abort: AbortHandle,
+	#[trace(no_trace)]
+	writer: Rc<ConnectionWriter>,
+}
+
+impl Connection {
+	fn new_connection(cx: &Context, stream: TcpStream) -> Result<*mut JSObject> {
+		let local = stream.local_addr().map_err(|error| net_error("get local address", error))?;
+		let remote = stream.peer_addr().map_err(|error| net_error("get remote address", error))?;
+		let (read, write) = stream.into_split();
+
+		let (chunks, read_abort) = abortable(read_stream(read));
+		let readable = readable_stream_from_byte_stream(cx, chunks, READ_CHUNK_SIZE)
+			.ok_or_else(|| Error::new("Failed to create stream for connection", ErrorKind::Normal))?;
+
+		let writer = Rc::new(ConnectionWriter {
+			half: RefCell::new(Some(write)),
+			closed: Cell::new(false),
+		});
+		let sink = ConnectionSink { writer: Rc::clone(&writer) };
+		let writable = writable_stream_from_callbacks(cx, Box::new(sink))
+			.ok_or_else(|| Error::new("Failed to create stream for connection", ErrorKind::Normal))?;
+
+		let connection = Connection {
+			reflector: Reflector::default(),
+			readable: Heap::new(readable.get()),
+			writable: Heap::new(writable.get()),
+			local,
+			remote,
+			read_abort,
+			writer,
+		};
+		Ok(Connection::new_object(cx, Box::new(connection)))
+	}
+}
+
+#[js_class]
+impl Connection {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Connection> {
+		Err(Error::new("Connection has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_readable(&self) -> *mut JSObject {
+		self.readable.get()
+	}
+
+	#[ion(get)]
+	pub fn get_writable(&self) -> *mut JSObject {
+		self.writable.get()
+	}
+
+	#[ion(get)]
+	pub fn get_local_addr(&self) -> NetAddr {
+		NetAddr { transport: "tcp", addr: self.local }
+	}
+
+	#[ion(get)]
+	pub fn get_remote_addr(&self) -> NetAddr {
+		NetAddr { transport: "tcp", addr: self.remote }
+	}
+
+	/// Closes both directions of the connection, ending the readable stream.
+	pub fn close(&self) {
+		self.read_abort.abort();
+		self.writer.close();
+	}
+}
+
+/// TCP listener, which accepts connections through [accept](Listener::accept) or async iteration.
+#[js_class]
+pub struct Listener {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	listener: Option<Rc<tokio::net::TcpListener>>,
+	#[trace(no_trace)]
+	addr: SocketAddr,
+	#[trace(no_trace)]
+	closed: Rc<Notify>,
+}
+
+#[js_class]
+impl Listener {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Listener> {
+		Err(Error::new("Listener has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_addr(&self) -> NetAddr {
+		NetAddr { transport: "tcp", addr: self.addr }
+	}
+
+	/// Resolves with the next connection, or `null` once the listener is closed.
+	pub fn accept(&self, cx: &Context) -> Option<Promise> {
+		let listener = self.listener.clone();
+		let closed = Rc::clone(&self.closed);
+		unsafe {
+			future_to_promise(cx, move |cx| async move {
+				let Some(listener) = listener else {
+					return Ok(None);
+				};
+				let closed = pin!(closed.notified());
+				let accept = pin!(listener.accept());
+				let (cx, accepted) = cx.await_native(select(closed, accept)).await;
+				match accepted {
+					Either::Left(_) => Ok(None),
+					Either::Right((Ok((stream, _)), _)) => Connection::new_connection(&cx, stream).map(Some),
+					Either::Right((Err(error), _)) => Err(net_error("accept connection", error)),
+				}
+			})
+		}
+	}
+
+	/// Stops listening, resolving pending calls to [accept](Listener::accept) with `null`.
+	pub fn close(&mut self) {
+		self.listener = None;
+		self.closed.notify_waiters();
+	}
+}
+
+/// Datagram received by a [DatagramSocket], converted to `{ data, addr }`.
+struct Datagram {
+	data: Vec<u8>,
+	addr: SocketAddr,
+}
+
+impl<'cx> ToValue<'cx> for Datagram {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "data", &Uint8ArrayWrapper::from(self.data.clone()));
+		object.set_as(cx, "addr", &NetAddr { transport: "udp", addr: self.addr });
+		object.to_value(cx, value);
+	}
+}
+
+/// UDP socket, which sends and receives datagrams.
+#[js_class]
+pub struct DatagramSocket {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	socket: Option<Rc<UdpSocket>>,
+	#[trace(no_trace)]
+	addr: SocketAddr,
+	#[trace(no_trace)]
+	closed: Rc<Notify>,
+}
+
+#[js_class]
+impl DatagramSocket {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<DatagramSocket> {
+		Err(Error::new("DatagramSocket has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_addr(&self) -> NetAddr {
+		NetAddr { transport: "udp", addr: self.addr }
+	}
+
+	/// Sends a datagram to the given address, resolving with the number of bytes sent.
+	pub fn send(&self, cx: &Context, data: BlobPart, target: ConnectOptions) -> Option<Promise> {
+		let socket = self.socket.clone();
+		unsafe {
+			future_to_promise(cx, move |_| async move {
+				let socket = socket.ok_or_else(closed_error)?;
+				let target = (target.hostname.as_str(), target.port);
+				let sent = socket.send_to(&data.0, target).await.map_err(|error| net_error("send datagram", error))?;
+				Ok::<_, Error>(sent as u32)
+			})
+		}
+	}
+
+	/// Resolves with the next datagram, or `null` once the socket is closed.
+	pub fn receive(&self, cx: &Context) -> Option<Promise> {
+		let socket = self.socket.clone();
+		let closed = Rc::clone(&self.closed);
+		unsafe {
+			future_to_promise(cx, move |_| async move {
+				let Some(socket) = socket else {
+					return Ok(None);
+				};
+				let mut data = vec![0; READ_CHUNK_SIZE];
+				let received = {
+					let closed = pin!(closed.notified());
+					let receive = pin!(socket.recv_from(&mut data));
+					match select(closed, receive).await {
+						Either::Left(_) => return Ok(None),
+						Either::Right((received, _)) => received,
+					}
+				};
+				let (read, addr) = received.map_err(|error| net_error("receive datagram", error))?;
+				data.truncate(read);
+				Ok::<_, Error>(Some(Datagram { data, addr }))
+			})
+		}
+	}
+
+	/// Closes the socket, resolving pending calls to [receive](DatagramSocket::receive) with `null`.
+	pub fn close(&mut self) {
+		self.socket = None;
+		self.closed.notify_waiters();
+	}
+}
+
+#[js_fn]
+fn connect(cx: &Context, options: ConnectOptions) -> Result<Option<Promise>> {
+	check_net_permission()?;
+	Ok(unsafe {
+		future_to_promise(cx, move |cx| async move {
+			let target = (options.hostname.as_str(), options.port);
+			let (cx, stream) = cx.await_native(TcpStream::connect(target)).await;
+			let stream = stream
+				.map_err(|error| net_error(&format!("connect to {}:{}", options.hostname, options.port), error))?;
+			Connection::new_connection(&cx, stream)
+		})
+	})
+}
+
+#[js_fn]
+fn listen(cx: &Context, Opt(options): Opt<ListenOptions>) -> Result<Option<Promise>> {
+	check_net_permission()?;
+	let options = options.unwrap_or_default();
+	Ok(unsafe {
+		future_to_promise(cx, move |cx| async move {
+			let target = (options.hostname.as_str(), options.port);
+			let (cx, listener) = cx.await_native(tokio::net::TcpListener::bind(target)).await;
+			let listener = listener.map_err(|error| net_error("listen", error))?;
+			let addr = listener.local_addr().map_err(|error| net_error("get local address", error))?;
+			let listener = Listener {
+				reflector: Reflector::default(),
+				listener: Some(Rc::new(listener)),
+				addr,
+				closed: Rc::new(Notify::new()),
+			};
+			Ok::<_, Error>(Listener::new_object(&cx, Box::new(listener)))
+		})
+	})
+}
+
+#[js_fn]
+fn udpSocket(cx: &Context, Opt(options): Opt<ListenOptions>) -> Result<Option<Promise>> {
+	check_net_permission()?;
+	let options = options.unwrap_or_default();
+	Ok(unsafe {
+		future_to_promise(cx, move |cx| async move {
+			let target = (options.hostname.as_str(), options.port);
+			let (cx, socket) = cx.await_native(UdpSocket::bind(target)).await;
+			let socket = socket.map_err(|error| net_error("bind socket", error))?;
+			let addr = socket.local_addr().map_err(|error| net_error("get local address", error))?;
+			let socket = DatagramSocket {
+				reflector: Reflector::default(),
+				socket: Some(Rc::new(socket)),
+				addr,
+				closed: Rc::new(Notify::new()),
+			};
+			Ok::<_, Error>(DatagramSocket::new_object(&cx, Box::new(socket)))
+		})
+	})
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(connect, 1),
+	function_spec!(listen, 0),
+	function_spec!(udpSocket, 0),
+	JSFunctionSpec::ZERO,
+];
+
+#[derive(Default)]
+pub struct Net;
+
+impl NativeModule for Net {
+	const NAME: &'static str = "net";
+	const SOURCE: &'static str = include_str!("net.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let net = Object::new(cx);
+		if unsafe { net.define_methods(cx, FUNCTIONS) }
+			&& define_class_disposal(cx, Connection::init_class(cx, &net), "close", Disposal::Sync)
+			&& define_class_disposal(cx, Listener::init_class(cx, &net), "close", Disposal::Sync)
+			&& define_class_disposal(cx, DatagramSocket::init_class(cx, &net), "close", Disposal::Sync)
+		{
+			return Some(net);
+		}
+		None
+	}
+}
@@ -0,0 +1,14 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const hostname = ______osInternal______.hostname;
+export const platform = ______osInternal______.platform;
+export const arch = ______osInternal______.arch;
+export const cpuCount = ______osInternal______.cpuCount;
+export const memory = ______osInternal______.memory;
+export const uptime = ______osInternal______.uptime;
+
+export default Object.freeze(______osInternal______);
@@ -0,0 +1,138 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::env;
+use std::fs::read_to_string;
+use std::num::NonZeroUsize;
+use std::sync::OnceLock;
+use std::thread::available_parallelism;
+use std::time::Instant;
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Error, Object, Result};
+use runtime::config::Config;
+use runtime::module::NativeModule;
+
+static START: OnceLock<Instant> = OnceLock::new();
+
+fn check_env_permission() -> Result<()> {
+	if Config::global().allow_env {
+		Ok(())
+	} else {
+		Err(Error::new(
+			"Access to system information requires the environment permission.",
+			None,
+		))
+	}
+}
+
+fn read_proc(path: &str) -> Option<String> {
+	if cfg!(target_os = "linux") {
+		read_to_string(path).ok()
+	} else {
+		None
+	}
+}
+
+#[js_fn]
+fn hostname() -> Result<String> {
+	check_env_permission()?;
+	let hostname = read_proc("/proc/sys/kernel/hostname")
+		.or_else(|| read_to_string("/etc/hostname").ok())
+		.map(|hostname| String::from(hostname.trim()))
+		.filter(|hostname| !hostname.is_empty())
+		.or_else(|| env::var("HOSTNAME").ok())
+		.or_else(|| env::var("COMPUTERNAME").ok());
+	Ok(hostname.unwrap_or_else(|| String::from("localhost")))
+}
+
+#[js_fn]
+fn platform() -> Result<&'static str> {
+	check_env_permission()?;
+	Ok(env::consts::OS)
+}
+
+#[js_fn]
+fn arch() -> Result<&'static str> {
+	check_env_permission()?;
+	Ok(env::consts::ARCH)
+}
+
+#[js_fn]
+fn cpuCount() -> Result<u32> {
+	check_env_permission()?;
+	Ok(available_parallelism().map(NonZeroUsize::get).unwrap_or(1) as u32)
+}
+
+/// Returns the total and available memory of the system in bytes, which are [None] if they cannot be determined.
+fn memory_info() -> (Option<u64>, Option<u64>) {
+	let Some(meminfo) = read_proc("/proc/meminfo") else {
+		return (None, None);
+	};
+
+	let mut total = None;
+	let mut available = None;
+	for line in meminfo.lines() {
+		let Some((key, value)) = line.split_once(':') else {
+			continue;
+		};
+		let value = value.trim().trim_end_matches("kB").trim().parse::<u64>().ok().map(|kb| kb * 1024);
+		match key {
+			"MemTotal" => total = value,
+			"MemAvailable" => available = value,
+			_ => {}
+		}
+	}
+	(total, available)
+}
+
+#[js_fn]
+fn memory<'cx>(cx: &'cx Context) -> Result<Object<'cx>> {
+	check_env_permission()?;
+	let (total, available) = memory_info();
+
+	let memory = Object::new(cx);
+	memory.set_as(cx, "total", &total);
+	memory.set_as(cx, "available", &available);
+	Ok(memory)
+}
+
+/// Returns the uptime of the system in seconds, or the time since the module was initialised if it is unavailable.
+#[js_fn]
+fn uptime() -> Result<f64> {
+	check_env_permission()?;
+	let uptime = read_proc("/proc/uptime")
+		.and_then(|uptime| uptime.split_whitespace().next().and_then(|seconds| seconds.parse().ok()));
+	Ok(uptime.unwrap_or_else(|| START.get_or_init(Instant::now).elapsed().as_secs_f64()))
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(hostname, 0),
+	function_spec!(platform, 0),
+	function_spec!(arch, 0),
+	function_spec!(cpuCount, 0),
+	function_spec!(memory, 0),
+	function_spec!(uptime, 0),
+	JSFunctionSpec::ZERO,
+];
+
+#[derive(Default)]
+pub struct Os;
+
+impl NativeModule for Os {
+	const NAME: &'static str = "os";
+	const SOURCE: &'static str = include_str!("os.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		START.get_or_init(Instant::now);
+		let os = Object::new(cx);
+		if unsafe { os.define_methods(cx, FUNCTIONS) } {
+			return Some(os);
+		}
+		None
+	}
+}
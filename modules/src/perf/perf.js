@@ -0,0 +1,19 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const start = ______perfInternal______.start;
+export const stop = ______perfInternal______.stop;
+export const isProfiling = ______perfInternal______.isProfiling;
+export const memoryUsage = ______perfInternal______.memoryUsage;
+export const gcStats = ______perfInternal______.gcStats;
+
+export default Object.freeze({
+	start,
+	stop,
+	isProfiling,
+	memoryUsage,
+	gcStats,
+});
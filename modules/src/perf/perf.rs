@@ -0,0 +1,75 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::time::Duration;
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Error, ErrorKind, Object, Result};
+use ion::function::Opt;
+use runtime::profiler::{
+	DEFAULT_SAMPLE_INTERVAL, gc_stats, GcStats, is_profiling, memory_usage, MemoryUsage, Profile, start_profiler,
+	stop_profiler,
+};
+use runtime::module::NativeModule;
+
+/// Starts the sampling profiler, which samples the stack every `interval` milliseconds. Returns `false` if it is
+/// already running.
+#[js_fn]
+fn start(cx: &Context, Opt(interval): Opt<f64>) -> Result<bool> {
+	let interval = match interval {
+		Some(interval) if interval.is_finite() && interval > 0.0 => Duration::from_secs_f64(interval / 1000.0),
+		Some(_) => return Err(Error::new("Interval must be a positive number", ErrorKind::Range)),
+		None => DEFAULT_SAMPLE_INTERVAL,
+	};
+	Ok(start_profiler(cx, interval))
+}
+
+/// Stops the sampling profiler, and returns its samples in the speedscope file format.
+#[js_fn]
+fn stop(cx: &Context) -> Result<Profile> {
+	stop_profiler(cx).ok_or_else(|| Error::new("Profiler is not running", ErrorKind::Normal))
+}
+
+#[js_fn]
+fn isProfiling(cx: &Context) -> bool {
+	is_profiling(cx)
+}
+
+#[js_fn]
+fn memoryUsage(cx: &Context) -> MemoryUsage {
+	memory_usage(cx)
+}
+
+#[js_fn]
+fn gcStats(cx: &Context) -> GcStats {
+	gc_stats(cx)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(start, 0),
+	function_spec!(stop, 0),
+	function_spec!(isProfiling, 0),
+	function_spec!(memoryUsage, 0),
+	function_spec!(gcStats, 0),
+	JSFunctionSpec::ZERO,
+];
+
+#[derive(Default)]
+pub struct Perf;
+
+impl NativeModule for Perf {
+	const NAME: &'static str = "perf";
+	const SOURCE: &'static str = include_str!("perf.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let perf = Object::new(cx);
+		if unsafe { perf.define_methods(cx, FUNCTIONS) } {
+			return Some(perf);
+		}
+		None
+	}
+}
@@ -0,0 +1,31 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+export const open = ______storeInternal______.open;
+
+export const deleteStore = ______storeInternal______.deleteStore;
+
+export const stores = ______storeInternal______.stores;
+
+export const KeyRange = Object.freeze({
+	only(key) {
+		return { lower: key, upper: key };
+	},
+	lowerBound(lower, open = false) {
+		return { lower, lowerOpen: open };
+	},
+	upperBound(upper, open = false) {
+		return { upper, upperOpen: open };
+	},
+	bound(lower, upper, lowerOpen = false, upperOpen = false) {
+		return { lower, upper, lowerOpen, upperOpen };
+	},
+});
+
+export default Object.freeze({
+	...______storeInternal______,
+	KeyRange,
+});
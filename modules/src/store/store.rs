@@ -0,0 +1,648 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::rc::Rc;
+
+use mozjs::conversions::ConversionBehavior;
+use mozjs::jsapi::{JSFunctionSpec, JSObject};
+
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Function, Object, Promise, Result, ResultExc, TracedHeap,
+	Value,
+};
+use ion::class::Reflector;
+use ion::clone::{structured_deserialize, structured_serialize};
+use ion::conversions::{FromValue, ToValue};
+use ion::flags::PropertyFlags;
+use ion::function::Opt;
+use runtime::module::NativeModule;
+
+type Entries = BTreeMap<StoreKey, Vec<u8>>;
+
+thread_local! {
+	static STORES: RefCell<HashMap<String, Rc<RefCell<Entries>>>> = RefCell::new(HashMap::new());
+}
+
+fn data_error(message: &str) -> Error {
+	Error::new(format!("DataError: {}", message), ErrorKind::Normal)
+}
+
+/// Key of a value in a [Store], which is a number or a string.
+/// Numbers are ordered before strings.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StoreKey {
+	Number(f64),
+	String(String),
+}
+
+impl Eq for StoreKey {}
+
+impl PartialOrd for StoreKey {
+	fn partial_cmp(&self, other: &StoreKey) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for StoreKey {
+	fn cmp(&self, other: &StoreKey) -> Ordering {
+		match (self, other) {
+			(StoreKey::Number(a), StoreKey::Number(b)) => a.total_cmp(b),
+			(StoreKey::Number(_), StoreKey::String(_)) => Ordering::Less,
+			(StoreKey::String(_), StoreKey::Number(_)) => Ordering::Greater,
+			(StoreKey::String(a), StoreKey::String(b)) => a.cmp(b),
+		}
+	}
+}
+
+impl<'cx> FromValue<'cx> for StoreKey {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, _: bool, _: ()) -> Result<StoreKey> {
+		let handle = value.handle();
+		if handle.is_number() {
+			let number = handle.to_number();
+			if !number.is_nan() {
+				// Negative zero is normalised, so that it is the same key as positive zero.
+				return Ok(StoreKey::Number(number + 0.0));
+			}
+		} else if handle.is_string() {
+			return String::from_value(cx, value, true, ()).map(StoreKey::String);
+		}
+		Err(data_error("Key must be a number or a string"))
+	}
+}
+
+impl<'cx> ToValue<'cx> for StoreKey {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		match self {
+			StoreKey::Number(number) => number.to_value(cx, value),
+			StoreKey::String(string) => string.to_value(cx, value),
+		}
+	}
+}
+
+#[derive(FromValue)]
+struct KeyRangeInit {
+	lower: Option<StoreKey>,
+	upper: Option<StoreKey>,
+	#[ion(default)]
+	lower_open: bool,
+	#[ion(default)]
+	upper_open: bool,
+}
+
+/// Range of keys, which is passed as a single key, or as an object with `lower`, `upper`, `lowerOpen` and `upperOpen`.
+#[derive(Clone, Debug)]
+pub struct KeyRange {
+	lower: Bound<StoreKey>,
+	upper: Bound<StoreKey>,
+}
+
+impl KeyRange {
+	fn all() -> KeyRange {
+		KeyRange {
+			lower: Bound::Unbounded,
+			upper: Bound::Unbounded,
+		}
+	}
+
+	fn only(key: StoreKey) -> KeyRange {
+		KeyRange {
+			lower: Bound::Included(key.clone()),
+			upper: Bound::Included(key),
+		}
+	}
+
+	fn new(init: KeyRangeInit) -> Result<KeyRange> {
+		if let (Some(lower), Some(upper)) = (&init.lower, &init.upper) {
+			match lower.cmp(upper) {
+				Ordering::Greater => return Err(data_error("Lower bound is greater than upper bound")),
+				Ordering::Equal if init.lower_open || init.upper_open => {
+					return Err(data_error("Range with equal bounds cannot be open"));
+				}
+				_ => {}
+			}
+		}
+		let bound = |key: Option<StoreKey>, open| match key {
+			Some(key) if open => Bound::Excluded(key),
+			Some(key) => Bound::Included(key),
+			None => Bound::Unbounded,
+		};
+		Ok(KeyRange {
+			lower: bound(init.lower, init.lower_open),
+			upper: bound(init.upper, init.upper_open),
+		})
+	}
+
+	fn contains(&self, key: &StoreKey) -> bool {
+		(&self.lower, &self.upper).contains(key)
+	}
+}
+
+impl<'cx> FromValue<'cx> for KeyRange {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<KeyRange> {
+		if value.handle().is_object() {
+			KeyRange::new(KeyRangeInit::from_value(cx, value, strict, ())?)
+		} else {
+			StoreKey::from_value(cx, value, strict, ()).map(KeyRange::only)
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Direction {
+	#[default]
+	Next,
+	Prev,
+}
+
+impl<'cx> FromValue<'cx> for Direction {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<Direction> {
+		match String::from_value(cx, value, strict, ())?.as_str() {
+			"next" => Ok(Direction::Next),
+			"prev" => Ok(Direction::Prev),
+			direction => Err(Error::new(
+				format!("Invalid cursor direction: {}", direction),
+				ErrorKind::Type,
+			)),
+		}
+	}
+}
+
+#[derive(Default, FromValue)]
+struct QueryOptions {
+	#[ion(convert = ConversionBehavior::EnforceRange)]
+	limit: Option<u32>,
+	#[ion(default)]
+	direction: Direction,
+}
+
+/// Returns the entries in the range, in the order of the direction.
+fn query(entries: &Entries, range: &KeyRange, options: &QueryOptions) -> Vec<(StoreKey, Vec<u8>)> {
+	let entries = entries.range((range.lower.clone(), range.upper.clone()));
+	let limit = options.limit.map(|limit| limit as usize).unwrap_or(usize::MAX);
+	let entries: Box<dyn Iterator<Item = _>> = match options.direction {
+		Direction::Next => Box::new(entries),
+		Direction::Prev => Box::new(entries.rev()),
+	};
+	entries.take(limit).map(|(key, value)| (key.clone(), value.clone())).collect()
+}
+
+/// Change to the entries of a store, which is applied when a [Transaction] is committed.
+#[derive(Clone, Debug)]
+enum Write {
+	Put(StoreKey, Vec<u8>),
+	Delete(KeyRange),
+	Clear,
+}
+
+impl Write {
+	fn apply(self, entries: &mut Entries) {
+		match self {
+			Write::Put(key, value) => {
+				entries.insert(key, value);
+			}
+			Write::Delete(range) => entries.retain(|key, _| !range.contains(key)),
+			Write::Clear => entries.clear(),
+		}
+	}
+}
+
+/// Entries which operations of a [Store] or [Transaction] read and write.
+/// Writes to a transaction are also logged, so that they can be applied to the store when it is committed.
+trait Target {
+	fn entries(&self) -> Result<std::cell::Ref<'_, Entries>>;
+
+	fn write(&mut self, write: Write) -> Result<()>;
+
+	fn get<'cx>(&self, cx: &'cx Context, key: &StoreKey) -> ResultExc<Value<'cx>> {
+		match self.entries()?.get(key) {
+			Some(value) => structured_deserialize(cx, value),
+			None => Ok(Value::undefined(cx)),
+		}
+	}
+
+	fn get_all<'cx>(&self, cx: &'cx Context, range: &KeyRange, options: &QueryOptions) -> ResultExc<Vec<Value<'cx>>> {
+		let entries = query(&self.entries()?, range, options);
+		entries.into_iter().map(|(_, value)| structured_deserialize(cx, &value)).collect()
+	}
+
+	fn get_all_keys(&self, range: &KeyRange, options: &QueryOptions) -> Result<Vec<StoreKey>> {
+		Ok(query(&self.entries()?, range, options).into_iter().map(|(key, _)| key).collect())
+	}
+
+	fn count(&self, range: &KeyRange) -> Result<u32> {
+		Ok(self.entries()?.range((range.lower.clone(), range.upper.clone())).count() as u32)
+	}
+
+	fn put(&mut self, cx: &Context, key: StoreKey, value: &Value, overwrite: bool) -> ResultExc<StoreKey> {
+		if !overwrite && self.entries()?.contains_key(&key) {
+			return Err(Error::new("ConstraintError: Key already exists in the store", ErrorKind::Normal).into());
+		}
+		let value = structured_serialize(cx, value)?;
+		self.write(Write::Put(key.clone(), value))?;
+		Ok(key)
+	}
+}
+
+/// Named store of structured-cloneable values, opened with `open` from `spiderfire:store`.
+/// Stores are kept in memory for the lifetime of the thread.
+#[js_class]
+pub struct Store {
+	reflector: Reflector,
+	name: String,
+	#[trace(no_trace)]
+	entries: Rc<RefCell<Entries>>,
+}
+
+impl Target for Store {
+	fn entries(&self) -> Result<std::cell::Ref<'_, Entries>> {
+		Ok(self.entries.borrow())
+	}
+
+	fn write(&mut self, write: Write) -> Result<()> {
+		write.apply(&mut self.entries.borrow_mut());
+		Ok(())
+	}
+}
+
+#[js_class]
+impl Store {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Store> {
+		Err(Error::new("Store has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+
+	/// Resolves with a clone of the value stored for the key, or `undefined` if there is none.
+	pub fn get(&self, cx: &Context, key: StoreKey) -> Promise {
+		Promise::from_result(cx, Target::get(self, cx, &key))
+	}
+
+	#[ion(name = "getAll")]
+	pub fn get_all(&self, cx: &Context, Opt(range): Opt<KeyRange>, Opt(options): Opt<QueryOptions>) -> Promise {
+		let values = Target::get_all(
+			self,
+			cx,
+			&range.unwrap_or_else(KeyRange::all),
+			&options.unwrap_or_default(),
+		);
+		Promise::from_result(cx, values)
+	}
+
+	#[ion(name = "getAllKeys")]
+	pub fn get_all_keys(&self, cx: &Context, Opt(range): Opt<KeyRange>, Opt(options): Opt<QueryOptions>) -> Promise {
+		let keys = Target::get_all_keys(self, &range.unwrap_or_else(KeyRange::all), &options.unwrap_or_default());
+		Promise::from_result(cx, keys)
+	}
+
+	pub fn count(&self, cx: &Context, Opt(range): Opt<KeyRange>) -> Promise {
+		Promise::from_result(cx, Target::count(self, &range.unwrap_or_else(KeyRange::all)))
+	}
+
+	/// Stores a clone of the value for the key, replacing the value already stored for it.
+	pub fn put(&mut self, cx: &Context, key: StoreKey, value: Value) -> Promise {
+		Promise::from_result(cx, Target::put(self, cx, key, &value, true))
+	}
+
+	/// Stores a clone of the value for the key, rejecting with a `ConstraintError` if a value is already stored for it.
+	pub fn add(&mut self, cx: &Context, key: StoreKey, value: Value) -> Promise {
+		Promise::from_result(cx, Target::put(self, cx, key, &value, false))
+	}
+
+	/// Deletes the values stored for the key, or for the keys in the range.
+	pub fn delete(&mut self, cx: &Context, range: KeyRange) -> Promise {
+		Promise::from_result(cx, self.write(Write::Delete(range)))
+	}
+
+	pub fn clear(&mut self, cx: &Context) -> Promise {
+		Promise::from_result(cx, self.write(Write::Clear))
+	}
+
+	/// Resolves with a [Cursor] positioned at the first entry in the range, or `null` if the range is empty.
+	#[ion(name = "openCursor")]
+	pub fn open_cursor(&self, cx: &Context, Opt(range): Opt<KeyRange>, Opt(options): Opt<QueryOptions>) -> Promise {
+		let direction = options.unwrap_or_default().direction;
+		let mut cursor = Cursor {
+			reflector: Reflector::default(),
+			entries: Rc::clone(&self.entries),
+			range: range.unwrap_or_else(KeyRange::all),
+			direction,
+			current: None,
+		};
+		if cursor.advance() {
+			let cursor = Object::from(cx.root(Cursor::new_object(cx, Box::new(cursor))));
+			Promise::resolved(cx, cursor)
+		} else {
+			Promise::resolved(cx, Value::null(cx))
+		}
+	}
+
+	/// Calls the callback with a [Transaction], whose writes are applied to the store at once if the promise returned by
+	/// the callback resolves, and discarded if it rejects. Resolves with the result of the callback.
+	pub fn transaction(&self, cx: &Context, callback: Function) -> Promise {
+		let transaction = Transaction {
+			reflector: Reflector::default(),
+			store: Rc::clone(&self.entries),
+			entries: RefCell::new(self.entries.borrow().clone()),
+			log: Vec::new(),
+			active: true,
+		};
+		let transaction = Object::from(cx.root(Transaction::new_object(cx, Box::new(transaction))));
+		let result = callback.call(cx, &Object::global(cx), &[Value::object(cx, &transaction)]);
+		let heap = TracedHeap::new(transaction.handle().get());
+		let result = match result {
+			Ok(result) => result,
+			Err(report) => {
+				let _ = Transaction::finish(cx, &heap, false);
+				return match report {
+					Some(report) => Promise::rejected(cx, report.exception),
+					None => Promise::rejected(cx, Value::undefined(cx)),
+				};
+			}
+		};
+
+		let commit_heap = heap.clone();
+		let on_resolved = Function::from_closure_once(
+			cx,
+			"",
+			Box::new(move |args| {
+				let cx = args.cx();
+				let result = args.access().value();
+				Transaction::finish(cx, &commit_heap, true)?;
+				Ok(result)
+			}),
+			1,
+			PropertyFlags::empty(),
+		);
+		let on_rejected = Function::from_closure_once(
+			cx,
+			"",
+			Box::new(move |args| {
+				let cx = args.cx();
+				let error = args.access().value();
+				Transaction::finish(cx, &heap, false)?;
+				Err(Exception::Other(error.get()))
+			}),
+			1,
+			PropertyFlags::empty(),
+		);
+		let promise = Promise::resolved(cx, result);
+		promise
+			.then(cx, Some(on_resolved), Some(on_rejected))
+			.unwrap_or_else(|| Promise::rejected_with_pending_exception(cx))
+	}
+}
+
+/// Group of operations on a [Store], which see a snapshot of the store and their own writes.
+#[js_class]
+pub struct Transaction {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	store: Rc<RefCell<Entries>>,
+	#[trace(no_trace)]
+	entries: RefCell<Entries>,
+	#[trace(no_trace)]
+	log: Vec<Write>,
+	#[trace(no_trace)]
+	active: bool,
+}
+
+impl Transaction {
+	/// Commits or discards the writes of the transaction, after which it can no longer be used.
+	fn finish(cx: &Context, transaction: &TracedHeap<*mut JSObject>, commit: bool) -> Result<()> {
+		let transaction = Transaction::get_mut_private(cx, &Object::from(transaction.root(cx)))?;
+		transaction.active = false;
+		if commit {
+			let mut store = transaction.store.borrow_mut();
+			for write in transaction.log.drain(..) {
+				write.apply(&mut store);
+			}
+		}
+		Ok(())
+	}
+
+	fn check_active(&self) -> Result<()> {
+		if self.active {
+			Ok(())
+		} else {
+			Err(Error::new(
+				"TransactionInactiveError: Transaction has already finished",
+				ErrorKind::Normal,
+			))
+		}
+	}
+}
+
+impl Target for Transaction {
+	fn entries(&self) -> Result<std::cell::Ref<'_, Entries>> {
+		self.check_active()?;
+		Ok(self.entries.borrow())
+	}
+
+	fn write(&mut self, write: Write) -> Result<()> {
+		self.check_active()?;
+		self.log.push(write.clone());
+		write.apply(&mut self.entries.borrow_mut());
+		Ok(())
+	}
+}
+
+#[js_class]
+impl Transaction {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Transaction> {
+		Err(Error::new("Transaction has no constructor.", ErrorKind::Type))
+	}
+
+	pub fn get(&self, cx: &Context, key: StoreKey) -> Promise {
+		Promise::from_result(cx, Target::get(self, cx, &key))
+	}
+
+	#[ion(name = "getAll")]
+	pub fn get_all(&self, cx: &Context, Opt(range): Opt<KeyRange>, Opt(options): Opt<QueryOptions>) -> Promise {
+		let values = Target::get_all(
+			self,
+			cx,
+			&range.unwrap_or_else(KeyRange::all),
+			&options.unwrap_or_default(),
+		);
+		Promise::from_result(cx, values)
+	}
+
+	#[ion(name = "getAllKeys")]
+	pub fn get_all_keys(&self, cx: &Context, Opt(range): Opt<KeyRange>, Opt(options): Opt<QueryOptions>) -> Promise {
+		let keys = Target::get_all_keys(self, &range.unwrap_or_else(KeyRange::all), &options.unwrap_or_default());
+		Promise::from_result(cx, keys)
+	}
+
+	pub fn count(&self, cx: &Context, Opt(range): Opt<KeyRange>) -> Promise {
+		Promise::from_result(cx, Target::count(self, &range.unwrap_or_else(KeyRange::all)))
+	}
+
+	pub fn put(&mut self, cx: &Context, key: StoreKey, value: Value) -> Promise {
+		Promise::from_result(cx, Target::put(self, cx, key, &value, true))
+	}
+
+	pub fn add(&mut self, cx: &Context, key: StoreKey, value: Value) -> Promise {
+		Promise::from_result(cx, Target::put(self, cx, key, &value, false))
+	}
+
+	pub fn delete(&mut self, cx: &Context, range: KeyRange) -> Promise {
+		Promise::from_result(cx, self.write(Write::Delete(range)))
+	}
+
+	pub fn clear(&mut self, cx: &Context) -> Promise {
+		Promise::from_result(cx, self.write(Write::Clear))
+	}
+}
+
+/// Position in the entries of a [Store], which sees the writes made to the store while it is iterated.
+#[js_class]
+pub struct Cursor {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	entries: Rc<RefCell<Entries>>,
+	#[trace(no_trace)]
+	range: KeyRange,
+	#[trace(no_trace)]
+	direction: Direction,
+	#[trace(no_trace)]
+	current: Option<(StoreKey, Vec<u8>)>,
+}
+
+impl Cursor {
+	/// Moves to the entry after the current one in the direction of the cursor.
+	/// Returns `false` if there are no more entries in the range.
+	fn advance(&mut self) -> bool {
+		let entries = self.entries.borrow();
+		let (lower, upper) = match (&self.current, self.direction) {
+			(Some((key, _)), Direction::Next) => (Bound::Excluded(key.clone()), self.range.upper.clone()),
+			(Some((key, _)), Direction::Prev) => (self.range.lower.clone(), Bound::Excluded(key.clone())),
+			(None, _) => (self.range.lower.clone(), self.range.upper.clone()),
+		};
+		// BTreeMap::range panics if the lower bound is after the upper bound, which happens once the cursor has passed
+		// the end of the range.
+		let empty = match (&lower, &upper) {
+			(Bound::Included(lower), Bound::Included(upper)) => lower > upper,
+			(Bound::Included(lower) | Bound::Excluded(lower), Bound::Included(upper) | Bound::Excluded(upper)) => {
+				lower >= upper
+			}
+			_ => false,
+		};
+		let next = if empty {
+			None
+		} else {
+			let mut range = entries.range((lower, upper));
+			match self.direction {
+				Direction::Next => range.next(),
+				Direction::Prev => range.next_back(),
+			}
+		};
+		let next = next.map(|(key, value)| (key.clone(), value.clone()));
+		drop(entries);
+		self.current = next;
+		self.current.is_some()
+	}
+}
+
+#[js_class]
+impl Cursor {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Cursor> {
+		Err(Error::new("Cursor has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_key(&self) -> Option<StoreKey> {
+		self.current.as_ref().map(|(key, _)| key.clone())
+	}
+
+	/// Clone of the value of the current entry, or `undefined` if the cursor has moved past the last entry.
+	#[ion(get)]
+	pub fn get_value<'cx>(&self, cx: &'cx Context) -> ResultExc<Value<'cx>> {
+		match &self.current {
+			Some((_, value)) => structured_deserialize(cx, value),
+			None => Ok(Value::undefined(cx)),
+		}
+	}
+
+	/// Moves to the next entry. Resolves with `false` if there are no more entries in the range.
+	#[ion(name = "continue")]
+	pub fn next(&mut self, cx: &Context) -> Promise {
+		Promise::resolved(cx, self.advance())
+	}
+}
+
+fn store_entries(name: &str) -> Rc<RefCell<Entries>> {
+	STORES.with_borrow_mut(|stores| Rc::clone(stores.entry(String::from(name)).or_default()))
+}
+
+/// Resolves with the store with the given name, which is created if it does not exist.
+#[js_fn]
+fn open(cx: &Context, name: String) -> Promise {
+	let entries = store_entries(&name);
+	let store = Store {
+		reflector: Reflector::default(),
+		name,
+		entries,
+	};
+	let store = Object::from(cx.root(Store::new_object(cx, Box::new(store))));
+	Promise::resolved(cx, store)
+}
+
+/// Deletes the store with the given name. Resolves with `false` if it did not exist.
+/// Stores which are already open keep their entries, but are no longer returned by `open`.
+#[js_fn]
+fn deleteStore(cx: &Context, name: String) -> Promise {
+	let deleted = STORES.with_borrow_mut(|stores| stores.remove(&name).is_some());
+	Promise::resolved(cx, deleted)
+}
+
+#[js_fn]
+fn stores(cx: &Context) -> Promise {
+	let mut names: Vec<_> = STORES.with_borrow(|stores| stores.keys().cloned().collect());
+	names.sort();
+	Promise::resolved(cx, names)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(open, 1),
+	function_spec!(deleteStore, 1),
+	function_spec!(stores, 0),
+	JSFunctionSpec::ZERO,
+];
+
+pub struct StoreM;
+
+impl NativeModule for StoreM {
+	const NAME: &'static str = "store";
+	const SOURCE: &'static str = include_str!("store.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let store = Object::new(cx);
+		if unsafe { store.define_methods(cx, FUNCTIONS) }
+			&& Store::init_class(cx, &store).0
+			&& Transaction::init_class(cx, &store).0
+			&& Cursor::init_class(cx, &store).0
+		{
+			return Some(store);
+		}
+		None
+	}
+}
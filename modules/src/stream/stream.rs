@@ -0,0 +1,40 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Error, ErrorKind, Object, Result};
+use ion::function::Rest;
+use runtime::globals::streams::{pipeline as pipe, Pipeline};
+use runtime::module::NativeModule;
+
+/// Pipes a readable stream through any number of transforms into a writable stream.
+/// Returns the promise of the pipeline, and the `AbortController` which aborts it.
+#[js_fn]
+fn pipeline<'cx>(cx: &'cx Context, source: Object<'cx>, Rest(streams): Rest<Object<'cx>>) -> Result<Pipeline> {
+	let Some((destination, transforms)) = streams.split_last() else {
+		return Err(Error::new("Pipeline requires a destination", ErrorKind::Type));
+	};
+	pipe(cx, &source, transforms, destination)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(pipeline, 2), JSFunctionSpec::ZERO];
+
+#[derive(Default)]
+pub struct Stream;
+
+impl NativeModule for Stream {
+	const NAME: &'static str = "stream";
+	const SOURCE: &'static str = include_str!("stream.js");
+
+	fn module(cx: &Context) -> Option<Object> {
+		let stream = Object::new(cx);
+		if unsafe { stream.define_methods(cx, FUNCTIONS) } {
+			return Some(stream);
+		}
+		None
+	}
+}
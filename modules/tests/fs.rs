@@ -0,0 +1,88 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{env, fs, process};
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::FileSystem;
+use runtime::RuntimeBuilder;
+use runtime::module::Loader;
+use runtime::security::WritePermission;
+
+const SCRIPT: &str = r#"
+import { readFile, writeFile, stat, mkdir, rm, sync } from "spiderfire:fs";
+
+globalThis.results = [];
+
+async function run(dir) {
+	await mkdir(`${dir}/nested/deeper`, { recursive: true });
+	await writeFile(`${dir}/nested/file.txt`, "hello");
+	await writeFile(`${dir}/nested/bytes.bin`, new Uint8Array([1, 2, 3]));
+
+	results.push(await readFile(`${dir}/nested/file.txt`, "utf-8"));
+	results.push((await readFile(`${dir}/nested/bytes.bin`)).join("-"));
+
+	const stats = await stat(`${dir}/nested/file.txt`);
+	results.push(stats.isFile, stats.isDirectory, stats.size, typeof stats.modified);
+	results.push(sync.stat(`${dir}/nested`).isDirectory);
+
+	try {
+		await rm(`${dir}/nested`);
+	} catch {
+		results.push("not empty");
+	}
+	await rm(`${dir}/nested`, { recursive: true });
+	await rm(`${dir}/nested`, { force: true });
+	try {
+		await stat(`${dir}/nested`);
+	} catch {
+		results.push("removed");
+	}
+}
+"#;
+
+#[test]
+fn fs() {
+	let dir = env::temp_dir().join(format!("spiderfire-fs-{}", process::id()));
+	fs::create_dir_all(&dir).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(FileSystem)
+		.microtask_queue()
+		.write_permission(WritePermission::paths([dir.clone()]))
+		.build(cx);
+
+	let source = format!(
+		"{}\nrun({:?}).catch(error => results.push(String(error)));",
+		SCRIPT,
+		dir.to_str().unwrap()
+	);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Module::compile_and_evaluate(rt.cx(), "fs.js", Some(Path::new("./tests/fs.js")), &source);
+		assert!(result.is_ok(), "Exception was thrown in fs.js");
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("fs.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	assert_eq!("hello,1-2-3,true,false,5,number,true,not empty,removed", result);
+}
@@ -0,0 +1,136 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{env, fs, process};
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::FileSystem;
+use runtime::RuntimeBuilder;
+use runtime::module::Loader;
+use runtime::security::{ReadPermission, WritePermission};
+
+const SCRIPT: &str = r#"
+import { readFile, writeFile, stat, mkdir, rm, readString, copy, hardLink, sync } from "spiderfire:fs";
+
+globalThis.results = [];
+
+async function attempt(name, action) {
+	try {
+		await action();
+		results.push(`${name}:ok`);
+	} catch (error) {
+		results.push(`${name}:${error.message.split(":")[0]}`);
+	}
+}
+
+async function run(readable, denied) {
+	await attempt("readFile", () => readFile(`${readable}/file.txt`, "utf-8"));
+	await attempt("readFile", () => readFile(`${denied}/secret.txt`));
+	await attempt("readFile", () => readFile(`${readable}/../denied/secret.txt`));
+	await attempt("stat", () => stat(`${denied}/secret.txt`));
+	await attempt("writeFile", () => writeFile(`${readable}/file.txt`, "overwritten"));
+	await attempt("mkdir", () => mkdir(`${readable}/nested/deeper`, { recursive: true }));
+	await attempt("rm", () => rm(readable, { recursive: true }));
+
+	await attempt("sync.readFile", () => sync.readFile(`${denied}/secret.txt`));
+	await attempt("sync.stat", () => sync.stat(`${denied}/secret.txt`));
+	await attempt("sync.writeFile", () => sync.writeFile(`${denied}/new.txt`, "new"));
+	await attempt("sync.mkdir", () => sync.mkdir(`${denied}/nested`));
+	await attempt("sync.rm", () => sync.rm(`${denied}/secret.txt`));
+
+	await attempt("readString", () => readString(`${denied}/secret.txt`));
+	await attempt("copy", () => copy(`${readable}/file.txt`, `${denied}/copy.txt`));
+	await attempt("hardLink", () => hardLink(`${denied}/secret.txt`, `${readable}/link.txt`));
+	await attempt("sync.write", () => sync.write(`${readable}/file.txt`, "overwritten"));
+	await attempt("sync.removeDirRecursive", () => sync.removeDirRecursive(denied));
+}
+"#;
+
+#[test]
+fn fs_permission() {
+	let dir = env::temp_dir().join(format!("spiderfire-fs-permission-{}", process::id()));
+	let readable = dir.join("readable");
+	let denied = dir.join("denied");
+	fs::create_dir_all(&readable).unwrap();
+	fs::create_dir_all(&denied).unwrap();
+	fs::write(readable.join("file.txt"), "readable").unwrap();
+	fs::write(denied.join("secret.txt"), "secret").unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(FileSystem)
+		.microtask_queue()
+		.read_permission(ReadPermission::paths([readable.clone()]))
+		.write_permission(WritePermission::None)
+		.build(cx);
+
+	let source = format!(
+		"{}\nrun({:?}, {:?}).catch(error => results.push(String(error)));",
+		SCRIPT,
+		readable.to_str().unwrap(),
+		denied.to_str().unwrap()
+	);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Module::compile_and_evaluate(
+			rt.cx(),
+			"fs-permission.js",
+			Some(Path::new("./tests/fs-permission.js")),
+			&source,
+		);
+		assert!(result.is_ok(), "Exception was thrown in fs-permission.js");
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("fs-permission.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+
+	assert_eq!("readable", fs::read_to_string(readable.join("file.txt")).unwrap());
+	assert!(!readable.join("nested").exists());
+	assert!(denied.join("secret.txt").exists());
+	assert!(!denied.join("new.txt").exists());
+	assert!(!denied.join("nested").exists());
+	assert!(!denied.join("copy.txt").exists());
+	assert!(!readable.join("link.txt").exists());
+	fs::remove_dir_all(&dir).unwrap();
+
+	let read_denied = "Read access denied";
+	let write_denied = "Write access denied";
+	let expected = [
+		("readFile", "ok"),
+		("readFile", read_denied),
+		("readFile", read_denied),
+		("stat", read_denied),
+		("writeFile", write_denied),
+		("mkdir", write_denied),
+		("rm", write_denied),
+		("sync.readFile", read_denied),
+		("sync.stat", read_denied),
+		("sync.writeFile", write_denied),
+		("sync.mkdir", write_denied),
+		("sync.rm", write_denied),
+		("readString", read_denied),
+		("copy", write_denied),
+		("hardLink", write_denied),
+		("sync.write", write_denied),
+		("sync.removeDirRecursive", write_denied),
+	];
+	let expected: Vec<_> = expected.iter().map(|(name, result)| format!("{}:{}", name, result)).collect();
+	assert_eq!(expected.join(","), result);
+}
@@ -0,0 +1,94 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "http")]
+
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Http;
+use runtime::RuntimeBuilder;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import { serve } from "spiderfire:http";
+
+globalThis.results = [];
+
+function handler(request) {
+	const url = new URL(request.url);
+	if (url.pathname === "/echo") {
+		return request.text().then(text => new Response(text, { headers: { "X-Method": request.method } }));
+	}
+	if (url.pathname === "/stream") {
+		const encoder = new TextEncoder();
+		const stream = new ReadableStream({
+			start(controller) {
+				controller.enqueue(encoder.encode("a"));
+				controller.enqueue(encoder.encode("b"));
+				controller.close();
+			},
+		});
+		return new Response(stream);
+	}
+	throw new Error(`Not found: ${url.pathname}`);
+}
+
+async function run() {
+	const server = serve(handler, { port: 0 });
+	const base = `http://127.0.0.1:${server.addr.port}`;
+
+	let response = await fetch(`${base}/echo`, { method: "POST", body: "hello" });
+	results.push(response.headers.get("X-Method"), await response.text());
+
+	response = await fetch(`${base}/stream`);
+	results.push(await response.text());
+
+	response = await fetch(`${base}/missing`);
+	results.push(response.status);
+
+	server.close();
+	await server.finished;
+	results.push("closed");
+}
+"#;
+
+#[test]
+fn http() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).allow_net(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(Http)
+		.microtask_queue()
+		.macrotask_queue()
+		.build(cx);
+
+	let source = format!("{}\nrun().catch(error => results.push(String(error)));", SCRIPT);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Module::compile_and_evaluate(rt.cx(), "http.js", Some(Path::new("./tests/http.js")), &source);
+		assert!(result.is_ok(), "Exception was thrown in http.js");
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("http.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("POST,hello,ab,500,closed", result);
+}
@@ -0,0 +1,72 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+use std::rc::Rc;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Kv;
+use runtime::RuntimeBuilder;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::kv::{KvStore, MemoryKvStore};
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import kv, { get, put, list } from "spiderfire:kv";
+
+globalThis.results = [];
+
+localStorage.setItem("local", "1");
+sessionStorage.setItem("session", "2");
+results.push(localStorage.length, localStorage.key(0), localStorage.getItem("missing"));
+
+async function run() {
+	await put("user:1", "alice");
+	await put("user:2", new TextEncoder().encode("bob"));
+	await put("config", "{}");
+
+	results.push(await get("local"), await get("user:1"), await get("missing"));
+	const buffer = await get("user:2", "arrayBuffer");
+	results.push(new TextDecoder().decode(buffer));
+	results.push((await list({ prefix: "user:" })).join(" "), (await list({ limit: 2 })).join(" "));
+	results.push(await kv.delete("config"), await kv.delete("config"));
+	results.push(localStorage.length, sessionStorage.getItem("local"));
+}
+"#;
+
+#[tokio::test]
+async fn kv() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let store = Rc::new(MemoryKvStore::default());
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(Kv)
+		.microtask_queue()
+		.kv_store(Rc::clone(&store) as Rc<dyn KvStore>)
+		.build(cx);
+
+	let source = format!("{}\nrun().catch(error => results.push(String(error)));", SCRIPT);
+	let result = Module::compile_and_evaluate(rt.cx(), "kv.js", Some(Path::new("./tests/kv.js")), &source);
+	assert!(result.is_ok(), "Exception was thrown in kv.js");
+	assert!(rt.run_event_loop().await.is_ok());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("kv.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("1,local,,1,alice,,bob,user:1 user:2,local user:1,true,false,3,", result);
+	assert_eq!(vec!["local", "user:1", "user:2"], store.keys().unwrap());
+	assert_eq!(Some(b"bob".to_vec()), store.get("user:2").unwrap());
+}
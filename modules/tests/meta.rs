@@ -0,0 +1,51 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Meta;
+use runtime::{RuntimeBuilder, VERSION};
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::globals::meta::TARGET;
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import meta, { version, spidermonkey, features, target } from "spiderfire:meta";
+
+globalThis.results = [
+	meta.version === spiderfire.version,
+	Object.isFrozen(meta) && Object.isFrozen(features),
+	version,
+	spidermonkey.length > 0,
+	Array.isArray(features),
+	target,
+];
+"#;
+
+#[test]
+fn meta() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new().modules(Loader::default()).standard_modules(Meta).build(cx);
+
+	let result = Module::compile_and_evaluate(rt.cx(), "meta.js", Some(Path::new("./tests/meta.js")), SCRIPT);
+	assert!(result.is_ok(), "Exception was thrown in meta.js");
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("meta.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(format!("true,true,{},true,true,{}", VERSION, TARGET), result);
+}
@@ -0,0 +1,99 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Net;
+use runtime::RuntimeBuilder;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import { connect, listen, udpSocket } from "spiderfire:net";
+
+globalThis.results = [];
+
+async function serve(listener) {
+	for await (const connection of listener) {
+		const reader = connection.readable.getReader();
+		const writer = connection.writable.getWriter();
+		const { value } = await reader.read();
+		await writer.write(new Uint8Array(value));
+		await writer.close();
+		reader.releaseLock();
+	}
+	results.push("listener closed");
+}
+
+async function run() {
+	const listener = await listen({ port: 0 });
+	const served = serve(listener);
+
+	const connection = await connect({ port: listener.addr.port });
+	results.push(connection.remoteAddr.port === listener.addr.port, connection.localAddr.transport);
+
+	const writer = connection.writable.getWriter();
+	await writer.write("ping");
+	let echoed = "";
+	const decoder = new TextDecoder();
+	const reader = connection.readable.getReader();
+	for (let chunk = await reader.read(); !chunk.done; chunk = await reader.read()) {
+		echoed += decoder.decode(chunk.value, { stream: true });
+	}
+	results.push(echoed);
+
+	listener.close();
+	await served;
+
+	const receiver = await udpSocket();
+	const sender = await udpSocket();
+	results.push(await sender.send("datagram", { port: receiver.addr.port }));
+	const datagram = await receiver.receive();
+	results.push(new TextDecoder().decode(datagram.data), datagram.addr.port === sender.addr.port);
+
+	const pending = receiver.receive();
+	receiver.close();
+	sender.close();
+	results.push(await pending);
+}
+"#;
+
+#[test]
+fn net() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).allow_net(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(Net)
+		.microtask_queue()
+		.macrotask_queue()
+		.build(cx);
+
+	let source = format!("{}\nrun().catch(error => results.push(String(error)));", SCRIPT);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Module::compile_and_evaluate(rt.cx(), "net.js", Some(Path::new("./tests/net.js")), &source);
+		assert!(result.is_ok(), "Exception was thrown in net.js");
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("net.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("true,tcp,ping,listener closed,8,datagram,true,null", result);
+}
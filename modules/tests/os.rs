@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::env;
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Os;
+use runtime::RuntimeBuilder;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import { hostname, platform, arch, cpuCount, memory, uptime } from "spiderfire:os";
+
+globalThis.results = [
+	typeof hostname(),
+	platform(),
+	arch(),
+	cpuCount() >= 1,
+	typeof memory(),
+	uptime() >= 0,
+];
+"#;
+
+#[test]
+fn os() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).allow_env(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(Os)
+		.microtask_queue()
+		.macrotask_queue()
+		.build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Module::compile_and_evaluate(rt.cx(), "os.js", Some(Path::new("./tests/os.js")), SCRIPT);
+		assert!(result.is_ok(), "Exception was thrown in os.js");
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("os.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		format!("string,{},{},true,object,true", env::consts::OS, env::consts::ARCH),
+		result
+	);
+}
@@ -0,0 +1,76 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Perf;
+use runtime::RuntimeBuilder;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import perf, { start, stop, isProfiling, memoryUsage, gcStats } from "spiderfire:perf";
+
+function busy() {
+	const end = Date.now() + 50;
+	let count = 0;
+	while (Date.now() < end) {
+		count++;
+	}
+	return count;
+}
+
+const started = start(1);
+const restarted = start();
+const profiling = isProfiling();
+busy();
+const profile = stop();
+
+const frames = profile.shared.frames;
+const samples = profile.profiles[0].samples;
+
+globalThis.results = [
+	Object.isFrozen(perf),
+	started,
+	restarted,
+	profiling,
+	isProfiling(),
+	profile.$schema,
+	profile.profiles[0].type,
+	samples.length === profile.profiles[0].weights.length,
+	samples.some(sample => sample.some(index => frames[index].name === "busy")),
+	memoryUsage().heapBytes > 0,
+	gcStats().collections >= 0,
+];
+"#;
+
+#[test]
+fn perf() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new().modules(Loader::default()).standard_modules(Perf).build(cx);
+
+	let result = Module::compile_and_evaluate(rt.cx(), "perf.js", Some(Path::new("./tests/perf.js")), SCRIPT);
+	assert!(result.is_ok(), "Exception was thrown in perf.js");
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("perf.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		"true,true,false,true,false,https://www.speedscope.app/file-format-schema.json,sampled,true,true,true,true",
+		result
+	);
+}
@@ -0,0 +1,105 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::StoreM;
+use runtime::RuntimeBuilder;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import { open, deleteStore, stores, KeyRange } from "spiderfire:store";
+
+globalThis.results = [];
+
+async function run() {
+	const users = await open("users");
+	await users.put(2, { name: "bob", tags: ["b"] });
+	await users.put(1, { name: "alice", joined: new Date(0) });
+	await users.put("admin", new Map([["role", "root"]]));
+	results.push(users.name, await users.count());
+
+	const alice = await users.get(1);
+	results.push(alice.name, alice.joined.getTime(), (await users.get("admin")).get("role"), await users.get(3));
+	results.push((await users.getAllKeys()).join(" "), (await users.getAllKeys(undefined, { direction: "prev", limit: 2 })).join(" "));
+	results.push((await users.getAll(KeyRange.bound(1, 2, true))).map(user => user.name).join(" "));
+
+	await users.add(1, {}).catch(error => results.push(error.message));
+	try {
+		await users.get({});
+	} catch (error) {
+		results.push(error.message);
+	}
+
+	const keys = [];
+	const cursor = await users.openCursor(KeyRange.lowerBound(1), { direction: "prev" });
+	do {
+		keys.push(cursor.key);
+	} while (await cursor.continue());
+	results.push(keys.join(" "), cursor.key);
+
+	await users.transaction(async transaction => {
+		await transaction.delete(KeyRange.upperBound(2));
+		await transaction.put(3, "carol");
+		results.push(await transaction.count(), await users.count());
+	});
+	results.push((await users.getAllKeys()).join(" "));
+
+	let saved;
+	await users.transaction(async transaction => {
+		saved = transaction;
+		await transaction.clear();
+		throw new Error("aborted");
+	}).catch(error => results.push(error.message));
+	results.push(await users.count());
+	await saved.get(3).catch(error => results.push(error.message));
+
+	await open("settings");
+	results.push((await stores()).join(" "), await deleteStore("settings"), await deleteStore("settings"));
+}
+"#;
+
+#[tokio::test]
+async fn store() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(StoreM)
+		.microtask_queue()
+		.build(cx);
+
+	let source = format!("{}\nrun().catch(error => results.push(String(error)));", SCRIPT);
+	let result = Module::compile_and_evaluate(rt.cx(), "store.js", Some(Path::new("./tests/store.js")), &source);
+	assert!(result.is_ok(), "Exception was thrown in store.js");
+	assert!(rt.run_event_loop().await.is_ok());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("store.js"), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	let expected = [
+		"users,3,alice,0,root,",
+		"1 2 admin,admin 2,bob",
+		"ConstraintError: Key already exists in the store,DataError: Key must be a number or a string",
+		"admin 2 1,",
+		"2,3",
+		"3 admin",
+		"aborted,2,TransactionInactiveError: Transaction has already finished",
+		"settings users,true,false",
+	];
+	assert_eq!(expected.join(","), result);
+}
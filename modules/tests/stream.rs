@@ -0,0 +1,123 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::JSEngine;
+use mozjs::rust::Runtime as RustRuntime;
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::module::Module;
+use ion::script::Script;
+use modules::Stream;
+use runtime::RuntimeBuilder;
+use runtime::module::Loader;
+
+const SCRIPT: &str = r#"
+import { pipeline } from "spiderfire:stream";
+
+globalThis.results = [];
+
+function source(chunks) {
+	return new ReadableStream({
+		start(controller) {
+			chunks.forEach(chunk => controller.enqueue(chunk));
+			controller.close();
+		},
+	});
+}
+
+function upperCase() {
+	return new TransformStream({
+		transform(chunk, controller) {
+			controller.enqueue(chunk.toUpperCase());
+		},
+	});
+}
+
+function failing(error) {
+	return new TransformStream({
+		transform() {
+			throw error;
+		},
+	});
+}
+
+function sink(written, events) {
+	return new WritableStream({
+		write(chunk) {
+			written.push(chunk);
+		},
+		close() {
+			events.push("closed");
+		},
+		abort() {
+			events.push("aborted");
+		},
+	});
+}
+
+async function run() {
+	const written = [];
+	const events = [];
+	const { promise, controller } = pipeline(source(["a", "b"]), upperCase(), sink(written, events));
+	await promise;
+	results.push(written.join(""), events.join(), controller instanceof AbortController);
+
+	const error = new Error("transform failed");
+	const failed = [];
+	try {
+		await pipeline(source(["a"]), upperCase(), failing(error), sink([], failed)).promise;
+	} catch (e) {
+		results.push(e === error, failed.join());
+	}
+
+	const reason = new Error("aborted");
+	const aborted = [];
+	const never = new ReadableStream();
+	const running = pipeline(never, upperCase(), sink([], aborted));
+	running.controller.abort(reason);
+	try {
+		await running.promise;
+	} catch (e) {
+		results.push(e === reason, aborted.join(), never.locked);
+	}
+
+	try {
+		pipeline(source([]));
+	} catch (e) {
+		results.push(e instanceof TypeError);
+	}
+}
+"#;
+
+#[test]
+fn stream() {
+	let engine = JSEngine::init().unwrap();
+	let rt = RustRuntime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::new()
+		.modules(Loader::default())
+		.standard_modules(Stream)
+		.microtask_queue()
+		.build(cx);
+
+	let source = format!("{}\nrun().catch(error => results.push(String(error)));", SCRIPT);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Module::compile_and_evaluate(rt.cx(), "stream.js", Some(Path::new("./tests/stream.js")), &source);
+		assert!(result.is_ok(), "Exception was thrown in stream.js");
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new("stream.js"), "results.join(';')").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("AB;closed;true;true;aborted;true;aborted;false;true", result);
+}
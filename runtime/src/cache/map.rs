@@ -11,14 +11,14 @@ use std::path::{Path, PathBuf};
 
 use sourcemap::SourceMap;
 
-use ion::{Error, ErrorReport, Exception};
+use ion::{Context, Error, ErrorReport, Exception, Stack};
 use ion::utils::normalise_path;
 
 thread_local!(static SOURCEMAP_CACHE: RefCell<HashMap<PathBuf, SourceMap>> = RefCell::new(HashMap::new()));
 
 pub fn find_sourcemap<P: AsRef<Path>>(path: P) -> Option<SourceMap> {
 	SOURCEMAP_CACHE.with_borrow_mut(|cache| {
-		let path = path.as_ref().to_path_buf();
+		let path = normalise_path(path);
 		match cache.entry(path) {
 			Entry::Occupied(o) => Some(o.get().clone()),
 			Entry::Vacant(_) => None,
@@ -46,10 +46,24 @@ pub fn transform_error_report_with_sourcemaps(report: &mut ErrorReport) {
 		}
 	}
 	if let Some(stack) = &mut report.stack {
-		for record in &mut stack.records {
-			if let Some(sourcemap) = find_sourcemap(&record.location.file) {
-				record.transform_with_sourcemap(&sourcemap);
-			}
+		transform_stack_with_sourcemaps(stack);
+	}
+	for cause in report.cause.iter_mut().map(Box::as_mut).chain(&mut report.errors) {
+		transform_error_report_with_sourcemaps(cause);
+	}
+}
+
+/// Transforms each record of the stack with the sourcemap saved for its file, if there is one.
+pub fn transform_stack_with_sourcemaps(stack: &mut Stack) {
+	for record in &mut stack.records {
+		if let Some(sourcemap) = find_sourcemap(&record.location.file) {
+			record.transform_with_sourcemap(&sourcemap);
 		}
 	}
 }
+
+/// Formats the report for printing, after transforming it with the saved sourcemaps.
+pub fn format_error_report(cx: &Context, mut report: ErrorReport) -> String {
+	transform_error_report_with_sourcemaps(&mut report);
+	report.format(cx)
+}
@@ -35,6 +35,12 @@ pub struct Config {
 	pub log_level: LogLevel,
 	pub script: bool,
 	pub typescript: bool,
+	/// Allows scripts to read information about the environment, such as the hostname and memory of the system.
+	pub allow_env: bool,
+	/// Allows scripts to open network connections and listen on sockets through the net module.
+	pub allow_net: bool,
+	/// Emits each console message as a line of JSON, for capture by log aggregation systems.
+	pub console_json: bool,
 }
 
 impl Config {
@@ -50,6 +56,18 @@ impl Config {
 		Config { typescript, ..self }
 	}
 
+	pub fn allow_env(self, allow_env: bool) -> Config {
+		Config { allow_env, ..self }
+	}
+
+	pub fn allow_net(self, allow_net: bool) -> Config {
+		Config { allow_net, ..self }
+	}
+
+	pub fn console_json(self, console_json: bool) -> Config {
+		Config { console_json, ..self }
+	}
+
 	pub fn global() -> &'static Config {
 		CONFIG.get().expect("Configuration not initialised")
 	}
@@ -61,6 +79,9 @@ impl Default for Config {
 			log_level: LogLevel::Error,
 			script: false,
 			typescript: true,
+			allow_env: false,
+			allow_net: false,
+			console_json: false,
 		}
 	}
 }
@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::mem;
+use std::rc::Rc;
+
+use mozjs::jsval::JSVal;
+
+use ion::{Context, TracedHeap};
+
+use super::EventLoop;
+
+/// Snapshot of the values of all async-local storages, which is propagated to the tasks scheduled while it is current.
+///
+/// Promise jobs capture the snapshot when they are enqueued, which is when the promise is settled, timers capture it
+/// when they are scheduled, and futures capture it when they are spawned.
+#[derive(Clone, Debug, Default)]
+pub struct AsyncContext(Option<Rc<Vec<(u64, TracedHeap<JSVal>)>>>);
+
+impl AsyncContext {
+	/// Returns the current snapshot of the event loop.
+	pub fn current(cx: &Context) -> AsyncContext {
+		EventLoop::from_context(cx).async_context.clone()
+	}
+
+	/// Makes the snapshot current, and returns the previous snapshot.
+	pub fn enter(self, cx: &Context) -> AsyncContext {
+		mem::replace(&mut EventLoop::from_context(cx).async_context, self)
+	}
+
+	/// Makes the snapshot current while running the callback.
+	pub fn run<T, F: FnOnce() -> T>(self, cx: &Context, callback: F) -> T {
+		let previous = self.enter(cx);
+		let result = callback();
+		previous.enter(cx);
+		result
+	}
+
+	/// Returns the value of the storage with the given identifier.
+	pub fn get(&self, storage: u64) -> Option<JSVal> {
+		let values = self.0.as_ref()?;
+		values.iter().find(|(id, _)| *id == storage).map(|(_, value)| value.get())
+	}
+
+	/// Returns a copy of the snapshot with the value of the storage replaced.
+	pub fn with(&self, storage: u64, value: Option<JSVal>) -> AsyncContext {
+		let mut values: Vec<_> = self
+			.0
+			.iter()
+			.flat_map(|values| values.iter())
+			.filter(|(id, _)| *id != storage)
+			.cloned()
+			.collect();
+		if let Some(value) = value {
+			values.push((storage, TracedHeap::new(value)));
+		}
+		AsyncContext((!values.is_empty()).then(|| Rc::new(values)))
+	}
+}
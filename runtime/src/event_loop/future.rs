@@ -4,27 +4,39 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::future::Future;
+use std::rc::Rc;
 use std::task;
 use std::task::Poll;
 
+use futures::channel::oneshot;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use mozjs::jsapi::JSObject;
-use tokio::task::JoinHandle;
 
 use ion::{Context, Error, ErrorKind, ErrorReport, Promise, ThrowException, Value, TracedHeap};
 use ion::conversions::BoxedIntoValue;
 
 use super::{EventLoop, EventLoopPollResult};
+use super::async_context::AsyncContext;
+use super::spawner::{Spawner, TokioSpawner};
 
-type FutureOutput = (Result<BoxedIntoValue, BoxedIntoValue>, TracedHeap<*mut JSObject>);
+pub(crate) type FutureOutput = (
+	Result<BoxedIntoValue, BoxedIntoValue>,
+	TracedHeap<*mut JSObject>,
+	AsyncContext,
+);
 
-#[derive(Default)]
 pub struct FutureQueue {
-	queue: FuturesUnordered<JoinHandle<FutureOutput>>,
+	queue: FuturesUnordered<oneshot::Receiver<FutureOutput>>,
+	spawner: Rc<dyn Spawner>,
 }
 
 impl FutureQueue {
+	pub fn new(spawner: Rc<dyn Spawner>) -> FutureQueue {
+		FutureQueue { queue: FuturesUnordered::new(), spawner }
+	}
+
 	pub fn poll_futures(
 		&mut self, cx: &Context, wcx: &mut task::Context,
 	) -> Result<EventLoopPollResult, Option<ErrorReport>> {
@@ -33,8 +45,8 @@ impl FutureQueue {
 		while let Poll::Ready(Some(item)) = self.queue.poll_next_unpin(wcx) {
 			match item {
 				Ok(item) => results.push(item),
-				Err(error) => {
-					Error::new(error.to_string(), ErrorKind::Normal).throw(cx);
+				Err(_) => {
+					Error::new("Future was dropped before it completed", ErrorKind::Normal).throw(cx);
 					return Err(None);
 				}
 			}
@@ -42,11 +54,12 @@ impl FutureQueue {
 
 		let result = EventLoopPollResult::from_bool(!results.is_empty());
 
-		for (result, promise) in results {
+		for (result, promise, context) in results {
 			let mut value = Value::undefined(cx);
 			let promise = Promise::from(promise.root(cx)).unwrap();
 
-			let result = match result {
+			// Reactions to the promise are enqueued with the context the future was spawned in.
+			let result = context.run(cx, || match result {
 				Ok(o) => {
 					o.into_value(cx, &mut value);
 					promise.resolve(cx, &value)
@@ -55,7 +68,7 @@ impl FutureQueue {
 					e.into_value(cx, &mut value);
 					promise.reject(cx, &value)
 				}
-			};
+			});
 
 			if !result {
 				return Err(ErrorReport::new_with_exception_stack(cx).unwrap());
@@ -65,12 +78,31 @@ impl FutureQueue {
 		Ok(result)
 	}
 
-	pub fn enqueue(&self, cx: &Context, handle: JoinHandle<FutureOutput>) {
-		self.queue.push(handle);
+	/// Spawns the future with the [Spawner], and enqueues its output to settle its promise.
+	pub fn spawn<F>(&self, cx: &Context, future: F)
+	where
+		F: Future<Output = FutureOutput> + 'static,
+	{
+		let (sender, receiver) = oneshot::channel();
+		self.spawner.spawn_local(Box::pin(async move {
+			let _ = sender.send(future.await);
+		}));
+		self.queue.push(receiver);
 		EventLoop::from_context(cx).wake();
 	}
 
+	/// Discards the pending futures, whose promises are never settled.
+	pub(crate) fn clear(&mut self) {
+		self.queue = FuturesUnordered::new();
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.queue.is_empty()
 	}
 }
+
+impl Default for FutureQueue {
+	fn default() -> FutureQueue {
+		FutureQueue::new(Rc::new(TokioSpawner))
+	}
+}
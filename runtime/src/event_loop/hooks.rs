@@ -0,0 +1,144 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ion::Context;
+
+use super::EventLoop;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskKind {
+	/// Promise reaction job.
+	Promise,
+	/// Callback queued with `queueMicrotask`.
+	Microtask,
+	/// Callback of `setTimeout` or `setInterval`.
+	Timer,
+	/// Callback queued with `queueMacrotask`.
+	Macrotask,
+	/// Native callback, such as the one used by `AbortSignal.timeout`.
+	Signal,
+}
+
+impl TaskKind {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			TaskKind::Promise => "promise",
+			TaskKind::Microtask => "microtask",
+			TaskKind::Timer => "timer",
+			TaskKind::Macrotask => "macrotask",
+			TaskKind::Signal => "signal",
+		}
+	}
+}
+
+/// Identifies a task in the event loop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaskInfo {
+	/// Unique identifier of the task, which is never `0`.
+	pub id: u64,
+	pub kind: TaskKind,
+	/// Identifier of the task that was running when this task was scheduled, or `0` if it was scheduled outside a task.
+	pub trigger_id: u64,
+}
+
+/// Callbacks invoked around the tasks of the event loop, such as for tracing or detecting long tasks.
+///
+/// `init` is called when a task is scheduled and `destroy` is called when it will not run again.
+/// `before` and `after` are called around every run of the task, so they are called repeatedly for intervals.
+/// Tasks scheduled by the hooks themselves are reported to the hooks as well.
+pub trait TaskHooks {
+	fn init(&self, _cx: &Context, _task: &TaskInfo) {}
+
+	fn before(&self, _cx: &Context, _task: &TaskInfo) {}
+
+	fn after(&self, _cx: &Context, _task: &TaskInfo, _duration: Duration) {}
+
+	fn destroy(&self, _cx: &Context, _task: &TaskInfo) {}
+}
+
+#[derive(Default)]
+pub(crate) struct TaskHookRegistry {
+	hooks: Vec<(u64, Rc<dyn TaskHooks>)>,
+	next_hook: u64,
+	next_task: u64,
+	current: u64,
+}
+
+impl TaskHookRegistry {
+	fn hooks(cx: &Context) -> Vec<Rc<dyn TaskHooks>> {
+		let registry = &EventLoop::from_context(cx).hooks;
+		registry.hooks.iter().map(|(_, hooks)| Rc::clone(hooks)).collect()
+	}
+
+	/// Allocates an identifier for a newly scheduled task and reports it to the hooks.
+	pub(crate) fn init(cx: &Context, kind: TaskKind) -> TaskInfo {
+		let registry = &mut EventLoop::from_context(cx).hooks;
+		registry.next_task += 1;
+		let task = TaskInfo {
+			id: registry.next_task,
+			kind,
+			trigger_id: registry.current,
+		};
+		if !registry.hooks.is_empty() {
+			for hooks in TaskHookRegistry::hooks(cx) {
+				hooks.init(cx, &task);
+			}
+		}
+		task
+	}
+
+	/// Runs a task, reporting it to the hooks before and after it runs.
+	pub(crate) fn run<T, F: FnOnce() -> T>(cx: &Context, task: &TaskInfo, callback: F) -> T {
+		let hooks = TaskHookRegistry::hooks(cx);
+		for hooks in &hooks {
+			hooks.before(cx, task);
+		}
+
+		let previous = std::mem::replace(&mut EventLoop::from_context(cx).hooks.current, task.id);
+		let start = Instant::now();
+		let result = callback();
+		let duration = start.elapsed();
+		EventLoop::from_context(cx).hooks.current = previous;
+
+		for hooks in &hooks {
+			hooks.after(cx, task, duration);
+		}
+		result
+	}
+
+	/// Reports to the hooks that a task will not run again.
+	pub(crate) fn destroy(cx: &Context, task: &TaskInfo) {
+		if !EventLoop::from_context(cx).hooks.hooks.is_empty() {
+			for hooks in TaskHookRegistry::hooks(cx) {
+				hooks.destroy(cx, task);
+			}
+		}
+	}
+}
+
+/// Registers hooks for the tasks of the event loop, and returns an identifier to remove them with [remove_task_hooks].
+pub fn add_task_hooks(cx: &Context, hooks: Rc<dyn TaskHooks>) -> u64 {
+	let registry = &mut EventLoop::from_context(cx).hooks;
+	registry.next_hook += 1;
+	registry.hooks.push((registry.next_hook, hooks));
+	registry.next_hook
+}
+
+/// Removes previously registered hooks. Returns `false` if they were not registered.
+pub fn remove_task_hooks(cx: &Context, id: u64) -> bool {
+	let registry = &mut EventLoop::from_context(cx).hooks;
+	let length = registry.hooks.len();
+	registry.hooks.retain(|(hook, _)| *hook != id);
+	registry.hooks.len() != length
+}
+
+/// Returns the identifier of the task that is currently running, or `0` if no task is running.
+pub fn current_task_id(cx: &Context) -> u64 {
+	EventLoop::from_context(cx).hooks.current
+}
@@ -18,7 +18,11 @@ use mozjs::jsval::JSVal;
 
 use ion::{Context, ErrorReport, Function, Object, Value, TracedHeap};
 
+use crate::limits::limit_task;
+
 use super::{EventLoop, EventLoopPollResult};
+use super::async_context::AsyncContext;
+use super::hooks::{TaskHookRegistry, TaskInfo, TaskKind};
 
 #[allow(clippy::type_complexity)]
 pub struct SignalMacrotask {
@@ -46,6 +50,43 @@ impl Debug for SignalMacrotask {
 	}
 }
 
+/// Configures the clamping and rescheduling of timers created by `setTimeout` and `setInterval`.
+#[derive(Clone, Copy, Debug)]
+pub struct TimerOptions {
+	/// Minimum delay of timers.
+	pub minimum_delay: Duration,
+	/// Minimum delay of timers nested deeper than [nesting_threshold](TimerOptions::nesting_threshold).
+	pub nested_minimum_delay: Duration,
+	/// Nesting level after which [nested_minimum_delay](TimerOptions::nested_minimum_delay) is applied.
+	pub nesting_threshold: u8,
+	/// Whether intervals are rescheduled from their previous deadline, rather than from when their callback finished.
+	/// Intervals that fall behind skip the missed runs instead of running them in a burst.
+	pub drift_correction: bool,
+}
+
+impl TimerOptions {
+	/// Clamps the delay of a timer created at the given nesting level.
+	pub fn clamp(&self, delay: Option<i32>, nesting: u8) -> Duration {
+		let minimum = if nesting > self.nesting_threshold {
+			self.nested_minimum_delay
+		} else {
+			self.minimum_delay
+		};
+		delay.map(|delay| Duration::milliseconds(delay.into()).max(minimum)).unwrap_or(minimum)
+	}
+}
+
+impl Default for TimerOptions {
+	fn default() -> TimerOptions {
+		TimerOptions {
+			minimum_delay: Duration::zero(),
+			nested_minimum_delay: Duration::milliseconds(4),
+			nesting_threshold: 5,
+			drift_correction: true,
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct TimerMacrotask {
 	callback: TracedHeap<*mut JSFunction>,
@@ -54,6 +95,8 @@ pub struct TimerMacrotask {
 	scheduled: DateTime<Utc>,
 	duration: Duration,
 	nesting: u8,
+	drift_correction: bool,
+	context: AsyncContext,
 }
 
 impl TimerMacrotask {
@@ -65,21 +108,42 @@ impl TimerMacrotask {
 			duration,
 			scheduled: Utc::now(),
 			nesting: 0,
+			drift_correction: true,
+			context: AsyncContext::default(),
 		}
 	}
 
 	pub fn reset(&mut self) -> bool {
 		if self.repeat {
-			self.scheduled = Utc::now();
+			let now = Utc::now();
+			let interval = self.duration.num_milliseconds();
+			if self.drift_correction && interval > 0 {
+				let deadline = self.scheduled + self.duration;
+				let missed = (now - deadline).num_milliseconds().max(0) / interval;
+				self.scheduled = deadline + Duration::milliseconds(missed * interval);
+			} else {
+				self.scheduled = now;
+			}
 		}
 		self.repeat
 	}
 }
 
+/// Pending timer reported by [MacrotaskQueue::timers].
+#[derive(Clone, Debug)]
+pub struct TimerInfo {
+	pub id: u32,
+	pub repeat: bool,
+	/// Name of the callback, which is empty if it is anonymous.
+	pub label: String,
+	pub due: DateTime<Utc>,
+}
+
 #[derive(Debug)]
 pub struct UserMacrotask {
 	callback: TracedHeap<*mut JSFunction>,
 	scheduled: DateTime<Utc>,
+	context: AsyncContext,
 }
 
 impl UserMacrotask {
@@ -87,6 +151,7 @@ impl UserMacrotask {
 		UserMacrotask {
 			callback: TracedHeap::new(callback.get()),
 			scheduled: Utc::now(),
+			context: AsyncContext::default(),
 		}
 	}
 }
@@ -102,6 +167,8 @@ pub enum Macrotask {
 pub struct MacrotaskQueue {
 	pub(crate) map: HashMap<u32, Macrotask>,
 	pub(crate) nesting: u8,
+	pub(crate) timer_options: TimerOptions,
+	tasks: HashMap<u32, TaskInfo>,
 	latest: Option<u32>,
 	timer: Option<Pin<Box<tokio::time::Sleep>>>,
 }
@@ -114,9 +181,14 @@ impl Macrotask {
 			}
 			return Ok(());
 		}
-		let (callback, args, my_nesting) = match &self {
-			Macrotask::Timer(timer) => (&timer.callback, timer.arguments.clone(), timer.nesting),
-			Macrotask::User(user) => (&user.callback, Vec::new(), 0),
+		let (callback, args, my_nesting, context) = match &self {
+			Macrotask::Timer(timer) => (
+				&timer.callback,
+				timer.arguments.clone(),
+				timer.nesting,
+				timer.context.clone(),
+			),
+			Macrotask::User(user) => (&user.callback, Vec::new(), 0, user.context.clone()),
 			_ => unreachable!(),
 		};
 
@@ -126,7 +198,7 @@ impl Macrotask {
 		let callback = Function::from(callback.root(cx));
 		let args: Vec<_> = args.into_iter().map(|value| Value::from(value.root(cx))).collect();
 
-		let res = callback.call(cx, &Object::global(cx), args.as_slice());
+		let res = context.run(cx, || callback.call(cx, &Object::global(cx), args.as_slice()));
 
 		*nesting = prev_nesting;
 
@@ -141,6 +213,14 @@ impl Macrotask {
 		}
 	}
 
+	fn kind(&self) -> TaskKind {
+		match self {
+			Macrotask::Signal(_) => TaskKind::Signal,
+			Macrotask::Timer(_) => TaskKind::Timer,
+			Macrotask::User(_) => TaskKind::Macrotask,
+		}
+	}
+
 	fn terminate(&self) -> bool {
 		match self {
 			Macrotask::Signal(signal) => signal.terminate.load(Ordering::SeqCst),
@@ -158,19 +238,31 @@ impl Macrotask {
 }
 
 impl MacrotaskQueue {
+	pub fn new(timer_options: TimerOptions) -> MacrotaskQueue {
+		MacrotaskQueue {
+			timer_options,
+			..MacrotaskQueue::default()
+		}
+	}
+
 	pub fn poll_jobs(
 		&mut self, cx: &Context, wcx: &mut task::Context,
 	) -> Result<EventLoopPollResult, Option<ErrorReport>> {
 		let mut result = EventLoopPollResult::NothingToDo;
 
-		while let Some((next, remaining)) = self.find_earliest(&Utc::now()) {
+		while let Some((next, remaining)) = self.find_earliest(cx, &Utc::now()) {
 			if remaining <= Duration::zero() {
 				result = EventLoopPollResult::DidWork;
 
 				{
 					let macrotask = self.map.get_mut(&next);
 					if let Some(macrotask) = macrotask {
-						macrotask.run(cx, &mut self.nesting)?;
+						match self.tasks.get(&next).copied() {
+							Some(task) => TaskHookRegistry::run(cx, &task, || {
+								limit_task(cx, || macrotask.run(cx, &mut self.nesting))
+							})?,
+							None => limit_task(cx, || macrotask.run(cx, &mut self.nesting))?,
+						}
 					}
 				}
 
@@ -178,7 +270,7 @@ impl MacrotaskQueue {
 				let macrotask = self.map.get_mut(&next);
 				if let Some(macrotask) = macrotask {
 					if macrotask.remove() {
-						self.map.remove(&next);
+						self.remove(cx, next);
 					}
 				}
 			} else {
@@ -203,8 +295,19 @@ impl MacrotaskQueue {
 	pub fn enqueue(&mut self, cx: &Context, mut macrotask: Macrotask, id: Option<u32>) -> u32 {
 		let index = id.unwrap_or_else(|| self.latest.map(|l| l + 1).unwrap_or(0));
 
-		if let Macrotask::Timer(timer) = &mut macrotask {
-			timer.nesting = self.nesting.saturating_add(1);
+		match &mut macrotask {
+			Macrotask::Timer(timer) => {
+				timer.nesting = self.nesting.saturating_add(1);
+				timer.drift_correction = self.timer_options.drift_correction;
+				timer.context = AsyncContext::current(cx);
+			}
+			Macrotask::User(user) => user.context = AsyncContext::current(cx),
+			Macrotask::Signal(_) => {}
+		}
+
+		let task = TaskHookRegistry::init(cx, macrotask.kind());
+		if let Some(previous) = self.tasks.insert(index, task) {
+			TaskHookRegistry::destroy(cx, &previous);
 		}
 
 		self.latest = Some(index);
@@ -216,11 +319,22 @@ impl MacrotaskQueue {
 		index
 	}
 
-	pub fn remove(&mut self, id: u32) {
+	pub fn remove(&mut self, cx: &Context, id: u32) {
 		self.map.remove(&id);
+		if let Some(task) = self.tasks.remove(&id) {
+			TaskHookRegistry::destroy(cx, &task);
+		}
 	}
 
-	fn find_earliest(&mut self, now: &DateTime<Utc>) -> Option<(u32, Duration)> {
+	pub(crate) fn clear(&mut self, cx: &Context) {
+		self.map.clear();
+		self.timer = None;
+		for (_, task) in self.tasks.drain() {
+			TaskHookRegistry::destroy(cx, &task);
+		}
+	}
+
+	fn find_earliest(&mut self, cx: &Context, now: &DateTime<Utc>) -> Option<(u32, Duration)> {
 		let mut next: Option<(u32, Duration)> = None;
 		let mut to_remove = Vec::new();
 		for (id, macrotask) in &self.map {
@@ -237,13 +351,32 @@ impl MacrotaskQueue {
 			}
 		}
 
-		for id in to_remove.iter_mut() {
-			self.map.remove(id);
+		for id in to_remove {
+			self.remove(cx, id);
 		}
 
 		next
 	}
 
+	/// Returns the pending timers created by `setTimeout` and `setInterval`, in the order they are due.
+	pub fn timers(&self, cx: &Context) -> Vec<TimerInfo> {
+		let mut timers: Vec<_> = self
+			.map
+			.iter()
+			.filter_map(|(id, macrotask)| match macrotask {
+				Macrotask::Timer(timer) => Some(TimerInfo {
+					id: *id,
+					repeat: timer.repeat,
+					label: Function::from(timer.callback.root(cx)).name(cx).unwrap_or_default(),
+					due: timer.scheduled + timer.duration,
+				}),
+				_ => None,
+			})
+			.collect();
+		timers.sort_by_key(|timer| (timer.due, timer.id));
+		timers
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.map.is_empty()
 	}
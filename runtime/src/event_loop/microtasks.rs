@@ -13,8 +13,11 @@ use mozjs::jsapi::{CurrentGlobalOrNull, Handle, JobQueueIsEmpty, JobQueueMayNotB
 use ion::{Context, ErrorReport, Function, Local, Object, TracedHeap};
 
 use crate::ContextExt;
+use crate::limits::limit_task;
 
 use super::{EventLoop, EventLoopPollResult};
+use super::async_context::AsyncContext;
+use super::hooks::{TaskHookRegistry, TaskInfo, TaskKind};
 
 #[derive(Clone, Debug)]
 pub enum Microtask {
@@ -25,7 +28,7 @@ pub enum Microtask {
 
 #[derive(Clone, Debug, Default)]
 pub struct MicrotaskQueue {
-	queue: VecDeque<Microtask>,
+	queue: VecDeque<(Microtask, AsyncContext, TaskInfo)>,
 	draining: bool,
 }
 
@@ -45,11 +48,19 @@ impl Microtask {
 			Microtask::None => Ok(()),
 		}
 	}
+
+	fn kind(&self) -> TaskKind {
+		match self {
+			Microtask::User(_) => TaskKind::Microtask,
+			_ => TaskKind::Promise,
+		}
+	}
 }
 
 impl MicrotaskQueue {
 	pub fn enqueue(&mut self, cx: &Context, microtask: Microtask) {
-		self.queue.push_back(microtask);
+		let task = TaskHookRegistry::init(cx, microtask.kind());
+		self.queue.push_back((microtask, AsyncContext::current(cx), task));
 		EventLoop::from_context(cx).wake();
 		unsafe { JobQueueMayNotBeEmpty(cx.as_ptr()) }
 	}
@@ -63,9 +74,11 @@ impl MicrotaskQueue {
 
 		self.draining = true;
 
-		while let Some(microtask) = self.queue.pop_front() {
+		while let Some((microtask, context, task)) = self.queue.pop_front() {
 			result = EventLoopPollResult::DidWork;
-			if let Err(e) = microtask.run(cx) {
+			let run = TaskHookRegistry::run(cx, &task, || limit_task(cx, || context.run(cx, || microtask.run(cx))));
+			TaskHookRegistry::destroy(cx, &task);
+			if let Err(e) = run {
 				self.draining = false;
 				return Err(e);
 			}
@@ -77,6 +90,13 @@ impl MicrotaskQueue {
 		Ok(result)
 	}
 
+	pub(crate) fn clear(&mut self, cx: &Context) {
+		for (_, _, task) in self.queue.drain(..) {
+			TaskHookRegistry::destroy(cx, &task);
+		}
+		unsafe { JobQueueIsEmpty(cx.as_ptr()) };
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.queue.is_empty()
 	}
@@ -6,22 +6,41 @@
 
 use std::collections::VecDeque;
 use std::ffi::c_void;
+use std::rc::Rc;
 use std::task::{self, Waker};
 use std::task::Poll;
 
 use mozjs::jsapi::{Handle, JSContext, JSObject, PromiseRejectionHandlingState};
 
-use ion::{Context, ErrorReport, Local, Promise, TracedHeap};
+use ion::{ClassDefinition, Context, ErrorReport, Function, Local, Object, Promise, TracedHeap, Value};
 use ion::format::{Config, format_value};
 
+use crate::cache::map::format_error_report;
 use crate::ContextExt;
+use crate::event_loop::async_context::AsyncContext;
 use crate::event_loop::future::FutureQueue;
+use crate::event_loop::hooks::TaskHookRegistry;
 use crate::event_loop::macrotasks::MacrotaskQueue;
 use crate::event_loop::microtasks::MicrotaskQueue;
+use crate::globals::event::{Event, PromiseRejectionEvent};
+use crate::globals::host_events::HostEventQueue;
+use crate::handle;
+#[cfg(feature = "inspector")]
+use crate::inspector::InspectorSession;
 
+pub(crate) mod async_context;
 pub(crate) mod future;
+pub(crate) mod hooks;
 pub(crate) mod macrotasks;
 pub(crate) mod microtasks;
+pub(crate) mod spawner;
+
+pub use hooks::{add_task_hooks, current_task_id, remove_task_hooks, TaskHooks, TaskInfo, TaskKind};
+pub use macrotasks::TimerOptions;
+pub use spawner::{Spawner, TokioSpawner};
+
+/// Handler for promises which were rejected without a handler, which receives the reason of the rejection.
+pub type UnhandledRejectionHandler = dyn for<'cx> Fn(&'cx Context, Value<'cx>);
 
 pub enum EventLoopPollResult {
 	NothingToDo,
@@ -55,8 +74,14 @@ pub struct EventLoop {
 	pub(crate) futures: Option<FutureQueue>,
 	pub(crate) microtasks: Option<MicrotaskQueue>,
 	pub(crate) macrotasks: Option<MacrotaskQueue>,
+	pub(crate) host_events: Option<HostEventQueue>,
+	#[cfg(feature = "inspector")]
+	pub(crate) inspector: Option<InspectorSession>,
 	pub(crate) unhandled_rejections: VecDeque<TracedHeap<*mut JSObject>>,
+	pub(crate) unhandled_rejection_handler: Option<Rc<UnhandledRejectionHandler>>,
 	pub(crate) waker: Option<Waker>,
+	pub(crate) async_context: AsyncContext,
+	pub(crate) hooks: TaskHookRegistry,
 }
 
 impl EventLoop {
@@ -76,7 +101,15 @@ impl EventLoop {
 	}
 
 	pub(crate) fn step(&mut self, cx: &Context, wcx: &mut task::Context) -> Result<(), Option<ErrorReport>> {
+		if handle::poll_terminated(cx, wcx.waker()) {
+			self.clear(cx);
+			return Err(Some(handle::terminated_report()));
+		}
 		let res = self.step_inner(cx, wcx);
+		if handle::poll_terminated(cx, wcx.waker()) {
+			self.clear(cx);
+			return res.and(Err(Some(handle::terminated_report())));
+		}
 
 		match self.waker {
 			Some(ref w) if w.will_wake(wcx.waker()) => (),
@@ -106,6 +139,15 @@ impl EventLoop {
 			}
 		}
 
+		if let Some(host_events) = &mut self.host_events {
+			poll_result.compound_with(&host_events.poll_events(cx, wcx));
+		}
+
+		#[cfg(feature = "inspector")]
+		if let Some(inspector) = &self.inspector {
+			poll_result.compound_with(&EventLoopPollResult::from_bool(inspector.poll_messages(cx, wcx)));
+		}
+
 		if let Some(macrotasks) = &mut self.macrotasks {
 			if !macrotasks.is_empty() {
 				poll_result.compound_with(&macrotasks.poll_jobs(cx, wcx)?);
@@ -114,11 +156,7 @@ impl EventLoop {
 
 		while let Some(promise) = self.unhandled_rejections.pop_front() {
 			let promise = Promise::from(promise.to_local()).unwrap();
-			let result = promise.result(cx);
-			eprintln!(
-				"Unhandled Promise Rejection: {}",
-				format_value(cx, Config::default(), &result)
-			);
+			report_unhandled_rejection(cx, &promise);
 		}
 
 		// TODO: Is it necessary to run the entire event loop again? Just running new
@@ -132,11 +170,64 @@ impl EventLoop {
 		}
 	}
 
+	/// Discards the pending tasks of every queue, as the runtime was terminated or shut down.
+	pub(crate) fn clear(&mut self, cx: &Context) {
+		if let Some(futures) = &mut self.futures {
+			futures.clear();
+		}
+		if let Some(microtasks) = &mut self.microtasks {
+			microtasks.clear(cx);
+		}
+		if let Some(macrotasks) = &mut self.macrotasks {
+			macrotasks.clear(cx);
+		}
+		if let Some(host_events) = &mut self.host_events {
+			host_events.close();
+		}
+		self.unhandled_rejections.clear();
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.microtasks.as_ref().map(|m| m.is_empty()).unwrap_or(true)
 			&& self.futures.as_ref().map(|f| f.is_empty()).unwrap_or(true)
 			&& self.macrotasks.as_ref().map(|m| m.is_empty()).unwrap_or(true)
+			&& self.host_events.as_ref().map(|h| !h.is_active()).unwrap_or(true)
+	}
+}
+
+/// Reports an unhandled rejection to the `onunhandledrejection` handler of the global, and then to the handler of the
+/// runtime, or to stderr if it does not have one, unless the event was cancelled.
+fn report_unhandled_rejection(cx: &Context, promise: &Promise) {
+	let reason = promise.result(cx);
+	if !dispatch_unhandled_rejection(cx, promise, &reason) {
+		return;
+	}
+
+	match EventLoop::from_context(cx).unhandled_rejection_handler.clone() {
+		Some(handler) => handler(cx, reason),
+		None => eprintln!(
+			"Unhandled Promise Rejection: {}",
+			format_value(cx, Config::default(), &reason)
+		),
+	}
+}
+
+/// Returns `false` if the `onunhandledrejection` handler cancelled the event.
+fn dispatch_unhandled_rejection(cx: &Context, promise: &Promise, reason: &Value) -> bool {
+	let global = Object::global(cx);
+	let Ok(Some(handler)) = global.get_as::<_, Function>(cx, "onunhandledrejection", true, ()) else {
+		return true;
+	};
+
+	let event = PromiseRejectionEvent::new_trusted("unhandledrejection", promise.get(), reason.get());
+	let event = Object::from(cx.root(PromiseRejectionEvent::new_object(cx, Box::new(event))));
+	if let Err(Some(report)) = handler.call_with(cx, &global, [event]) {
+		eprintln!(
+			"Uncaught exception in unhandled rejection handler: {}",
+			format_error_report(cx, report)
+		);
 	}
+	Event::get_private(cx, &event).map_or(true, |event| !event.canceled)
 }
 
 pub struct RunToEnd<'e> {
@@ -0,0 +1,30 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use futures::future::LocalBoxFuture;
+
+/// Executor which drives the native futures of the event loop, such as those created by
+/// [future_to_promise](crate::promise::future_to_promise).
+///
+/// Futures are not [Send], so they must be polled to completion on the thread of the runtime.
+/// The result of a future is sent back to the event loop, so the spawner can drop the task once it completes.
+/// Dropping a task before it completes rejects its promise.
+///
+/// [TokioSpawner] is used by default. Hosts which are not using tokio, such as those with their own single-threaded
+/// executor, can implement this trait to embed the runtime.
+pub trait Spawner {
+	fn spawn_local(&self, future: LocalBoxFuture<'static, ()>);
+}
+
+/// [Spawner] which spawns futures onto the current [LocalSet](tokio::task::LocalSet).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioSpawner;
+
+impl Spawner for TokioSpawner {
+	fn spawn_local(&self, future: LocalBoxFuture<'static, ()>) {
+		tokio::task::spawn_local(future);
+	}
+}
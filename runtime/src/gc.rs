@@ -0,0 +1,153 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use mozjs::jsapi::{
+	GCDescription, GCOptions, GCProgress, GCReason, JS_GC, JS_SetGCParameter, JSContext, JSFunctionSpec, JSGCParamKey,
+	NonIncrementalGC, PrepareForFullGC, SetGCSliceCallback,
+};
+
+use ion::{Context, Object};
+use ion::function::Opt;
+
+use crate::ContextExt;
+
+/// Configures the garbage collector. Parameters which are [None] keep the defaults of SpiderMonkey.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GcOptions {
+	/// Whether collections are split into slices, which are interleaved with scripts to shorten pauses.
+	pub incremental: Option<bool>,
+	/// Whether collections can be limited to the zones which allocated the most, instead of the whole heap.
+	pub per_zone: Option<bool>,
+	/// Maximum duration of each slice of an incremental collection.
+	pub slice_budget: Option<Duration>,
+	/// Size of the heap in megabytes after which the first collection is triggered.
+	pub allocation_threshold: Option<u32>,
+	/// Maximum size of the nursery in bytes, in which new objects are allocated before they are tenured.
+	pub max_nursery_size: Option<u32>,
+}
+
+impl GcOptions {
+	pub(crate) fn apply(&self, cx: &Context) {
+		let parameters = [
+			(
+				JSGCParamKey::JSGC_INCREMENTAL_GC_ENABLED,
+				self.incremental.map(u32::from),
+			),
+			(JSGCParamKey::JSGC_PER_ZONE_GC_ENABLED, self.per_zone.map(u32::from)),
+			(
+				JSGCParamKey::JSGC_SLICE_TIME_BUDGET_MS,
+				self.slice_budget.map(|budget| budget.as_millis().clamp(1, u128::from(u32::MAX)) as u32),
+			),
+			(JSGCParamKey::JSGC_ALLOCATION_THRESHOLD, self.allocation_threshold),
+			(JSGCParamKey::JSGC_MAX_NURSERY_BYTES, self.max_nursery_size),
+		];
+		for (key, value) in parameters {
+			if let Some(value) = value {
+				unsafe { JS_SetGCParameter(cx.as_ptr(), key, value) };
+			}
+		}
+	}
+}
+
+/// Reason for an explicit collection, which determines how thoroughly the heap is collected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GcReason {
+	/// Collects the garbage of the whole heap.
+	#[default]
+	Api,
+	/// Collects the garbage of the whole heap and releases unused memory to the system, such as while the embedder is
+	/// idle or low on memory. This is slower than [GcReason::Api].
+	MemoryPressure,
+}
+
+/// Runs a full, non-incremental collection.
+pub fn gc(cx: &Context, reason: GcReason) {
+	unsafe {
+		match reason {
+			GcReason::Api => JS_GC(cx.as_ptr(), GCReason::API),
+			GcReason::MemoryPressure => {
+				PrepareForFullGC(cx.as_ptr());
+				NonIncrementalGC(cx.as_ptr(), GCOptions::Shrink, GCReason::MEM_PRESSURE);
+			}
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcPhase {
+	CycleBegin,
+	SliceBegin,
+	SliceEnd,
+	CycleEnd,
+}
+
+/// Progress of a collection, which is reported to the [handler](crate::Runtime::set_gc_slice_handler) at the start
+/// and end of every slice and cycle.
+#[derive(Clone, Copy, Debug)]
+pub struct GcSlice {
+	pub phase: GcPhase,
+	/// Duration of the slice or cycle which ended. [None] at the start of slices and cycles.
+	pub duration: Option<Duration>,
+}
+
+/// Handler for the slices of collections. It is called while the garbage collector is running, so it must not run
+/// scripts or create objects.
+pub type GcSliceHandler = dyn Fn(&GcSlice);
+
+#[derive(Default)]
+pub(crate) struct GcState {
+	handler: Option<Rc<GcSliceHandler>>,
+	cycle_start: Option<Instant>,
+	slice_start: Option<Instant>,
+}
+
+pub(crate) fn set_slice_handler(cx: &Context, handler: Option<Rc<GcSliceHandler>>) {
+	let callback = handler.is_some().then_some(slice_callback as _);
+	unsafe { cx.get_private() }.gc = GcState { handler, ..GcState::default() };
+	unsafe { SetGCSliceCallback(cx.as_ptr(), callback) };
+}
+
+unsafe extern "C" fn slice_callback(cx: *mut JSContext, progress: GCProgress, _: *const GCDescription) {
+	let cx = unsafe { &Context::new_unchecked(cx) };
+	let state = &mut unsafe { cx.get_private() }.gc;
+	let now = Instant::now();
+	let (phase, duration) = match progress {
+		GCProgress::GC_CYCLE_BEGIN => {
+			state.cycle_start = Some(now);
+			(GcPhase::CycleBegin, None)
+		}
+		GCProgress::GC_SLICE_BEGIN => {
+			state.slice_start = Some(now);
+			(GcPhase::SliceBegin, None)
+		}
+		GCProgress::GC_SLICE_END => (GcPhase::SliceEnd, state.slice_start.take().map(|start| now - start)),
+		GCProgress::GC_CYCLE_END => (GcPhase::CycleEnd, state.cycle_start.take().map(|start| now - start)),
+	};
+	if let Some(handler) = state.handler.clone() {
+		handler(&GcSlice { phase, duration });
+	}
+}
+
+/// Runs a full collection. Pass `true` to also release unused memory to the system.
+#[js_fn]
+fn gc_global(cx: &Context, Opt(shrink): Opt<bool>) {
+	let reason = if shrink.unwrap_or_default() {
+		GcReason::MemoryPressure
+	} else {
+		GcReason::Api
+	};
+	gc(cx, reason);
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(gc_global, "gc", 0), JSFunctionSpec::ZERO];
+
+/// Defines the `gc(shrink)` global, which lets scripts trigger collections, such as in benchmarks and tests.
+pub fn define(cx: &Context, global: &Object) -> bool {
+	unsafe { global.define_methods(cx, FUNCTIONS) }
+}
@@ -16,13 +16,18 @@ use mozjs::jsapi::JSObject;
 use mozjs::jsval::{JSVal, UndefinedValue};
 use tokio::sync::watch::{channel, Receiver, Sender};
 
-use ion::{ClassDefinition, Context, Error, ErrorKind, Exception, Object, Result, ResultExc, TracedHeap, Value};
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Function, Heap, Object, Result, ResultExc, TracedHeap, Value,
+};
 use ion::class::Reflector;
 use ion::conversions::{FromValue, ToValue};
 use ion::function::{Enforce, Opt};
 
 use crate::ContextExt;
 use crate::event_loop::macrotasks::{Macrotask, SignalMacrotask};
+use crate::globals::dispose::{define_class_disposal, Disposal};
+use crate::globals::event::{Event, EventInit};
+use crate::globals::event_target::EventTarget;
 
 #[derive(Clone, Debug, Default)]
 pub enum Signal {
@@ -82,42 +87,60 @@ pub struct AbortController {
 	reflector: Reflector,
 	#[trace(no_trace)]
 	sender: Sender<Option<TracedHeap<JSVal>>>,
+	signal: Heap<*mut JSObject>,
 }
 
 #[js_class]
 impl AbortController {
 	#[ion(constructor)]
-	pub fn constructor() -> AbortController {
-		let (sender, _) = channel(None);
-		AbortController { reflector: Reflector::default(), sender }
-	}
-
-	// TODO: Return the same signal object
-	#[ion(get)]
-	pub fn get_signal(&self, cx: &Context) -> *mut JSObject {
-		AbortSignal::new_object(
+	pub fn constructor(cx: &Context) -> AbortController {
+		let (sender, receiver) = channel(None);
+		let signal = AbortSignal::new_object(
 			cx,
 			Box::new(AbortSignal {
-				reflector: Reflector::default(),
-				signal: Signal::Receiver(self.sender.subscribe()),
+				event_target: EventTarget::default(),
+				signal: Signal::Receiver(receiver),
 			}),
-		)
+		);
+		AbortController {
+			reflector: Reflector::default(),
+			sender,
+			signal: Heap::new(signal),
+		}
 	}
 
-	pub fn abort<'cx>(&self, cx: &'cx Context, Opt(reason): Opt<Value<'cx>>) {
+	#[ion(get)]
+	pub fn get_signal(&self) -> *mut JSObject {
+		self.signal.get()
+	}
+
+	pub fn abort<'cx>(&self, cx: &'cx Context, Opt(reason): Opt<Value<'cx>>) -> ResultExc<()> {
+		if self.sender.borrow().is_some() {
+			return Ok(());
+		}
 		let reason = reason.unwrap_or_else(|| Error::new("AbortError", None).as_value(cx));
 		self.sender.send_replace(Some(TracedHeap::from_local(&reason)));
+		AbortSignal::dispatch_abort(cx, &Object::from(self.signal.root(cx)))
 	}
 }
 
 #[js_class]
 #[derive(Default)]
 pub struct AbortSignal {
-	reflector: Reflector,
+	event_target: EventTarget,
 	#[trace(no_trace)]
 	pub(crate) signal: Signal,
 }
 
+impl AbortSignal {
+	/// Dispatches the `abort` event to the listeners of a signal.
+	fn dispatch_abort(cx: &Context, signal: &Object) -> ResultExc<()> {
+		let event = Event::new_trusted("abort", EventInit::default());
+		let event = Object::from(cx.root(Event::new_object(cx, Box::new(event))));
+		EventTarget::dispatch(cx, signal, &event).map(|_| ())
+	}
+}
+
 #[js_class]
 impl AbortSignal {
 	#[ion(constructor)]
@@ -156,7 +179,7 @@ impl AbortSignal {
 		AbortSignal::new_object(
 			cx,
 			Box::new(AbortSignal {
-				reflector: Reflector::default(),
+				event_target: EventTarget::default(),
 				signal: Signal::Abort(TracedHeap::from_local(&reason)),
 			}),
 		)
@@ -167,26 +190,32 @@ impl AbortSignal {
 		let terminate = Arc::new(AtomicBool::new(false));
 		let terminate2 = Arc::clone(&terminate);
 
-		let callback = Box::new(move |cx: &_| {
-			let error = Error::new(format!("Timeout Error: {}ms", time), None).as_value(cx).get();
-			sender.send_replace(Some(TracedHeap::new(error)));
-		});
-
-		let duration = Duration::milliseconds(time as i64);
 		let event_loop = unsafe { &mut cx.get_private().event_loop };
 		if let Some(queue) = &mut event_loop.macrotasks {
+			let signal = AbortSignal::new_object(
+				cx,
+				Box::new(AbortSignal {
+					event_target: EventTarget::default(),
+					signal: Signal::Timeout(receiver, terminate2),
+				}),
+			);
+			let signal_heap = TracedHeap::new(signal);
+
+			let callback = Box::new(move |cx: &Context| {
+				let error = Error::new(format!("Timeout Error: {}ms", time), None).as_value(cx).get();
+				sender.send_replace(Some(TracedHeap::new(error)));
+				if let Err(exception) = AbortSignal::dispatch_abort(cx, &Object::from(signal_heap.root(cx))) {
+					eprintln!("Uncaught exception in abort event: {}", exception.format(cx));
+				}
+			});
+
+			let duration = Duration::milliseconds(time as i64);
 			queue.enqueue(
 				cx,
 				Macrotask::Signal(SignalMacrotask::new(callback, terminate, duration)),
 				None,
 			);
-			AbortSignal::new_object(
-				cx,
-				Box::new(AbortSignal {
-					reflector: Reflector::default(),
-					signal: Signal::Timeout(receiver, terminate2),
-				}),
-			)
+			signal
 		} else {
 			ptr::null_mut()
 		}
@@ -199,7 +228,7 @@ impl<'cx> FromValue<'cx> for AbortSignal {
 		let object = Object::from_value(cx, value, strict, ())?;
 		if AbortSignal::instance_of(cx, &object) {
 			Ok(AbortSignal {
-				reflector: Reflector::default(),
+				event_target: EventTarget::default(),
 				signal: AbortSignal::get_private(cx, &object)?.signal.clone(),
 			})
 		} else {
@@ -209,5 +238,6 @@ impl<'cx> FromValue<'cx> for AbortSignal {
 }
 
 pub fn define(cx: &Context, global: &Object) -> bool {
-	AbortController::init_class(cx, global).0 && AbortSignal::init_class(cx, global).0
+	define_class_disposal(cx, AbortController::init_class(cx, global), "abort", Disposal::Sync)
+		&& AbortSignal::init_class(cx, global).0
 }
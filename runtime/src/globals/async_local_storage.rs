@@ -0,0 +1,79 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use mozjs::jsval::{JSVal, UndefinedValue};
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Exception, Function, Object, ResultExc, Value};
+use ion::class::Reflector;
+use ion::function::Rest;
+
+use crate::event_loop::async_context::AsyncContext;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Stores a value which is propagated through promise reactions, timers and futures started while it is set.
+#[js_class]
+pub struct AsyncLocalStorage {
+	reflector: Reflector,
+	id: u64,
+}
+
+impl AsyncLocalStorage {
+	fn run_with(
+		&self, cx: &Context, store: Option<JSVal>, callback: Function, arguments: Vec<JSVal>,
+	) -> ResultExc<JSVal> {
+		let context = AsyncContext::current(cx).with(self.id, store);
+		let arguments: Vec<_> = arguments.into_iter().map(|argument| Value::from(cx.root(argument))).collect();
+		let result = context.run(cx, || callback.call(cx, &Object::null(cx), &arguments));
+		result.map(|value| value.get()).map_err(|report| {
+			report
+				.map(|report| report.exception)
+				.unwrap_or_else(|| Exception::Error(Error::new("Unknown failure in callback", ErrorKind::Normal)))
+		})
+	}
+}
+
+#[js_class]
+impl AsyncLocalStorage {
+	#[ion(constructor)]
+	pub fn constructor() -> AsyncLocalStorage {
+		AsyncLocalStorage {
+			reflector: Reflector::default(),
+			id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+		}
+	}
+
+	/// Returns the value of the storage in the current context, or `undefined` if it is not set.
+	#[ion(name = "getStore")]
+	pub fn get_store(&self, cx: &Context) -> JSVal {
+		AsyncContext::current(cx).get(self.id).unwrap_or_else(UndefinedValue)
+	}
+
+	/// Calls the callback with the storage set to `store`, which is also visible to any tasks it schedules.
+	pub fn run(
+		&self, cx: &Context, store: JSVal, callback: Function, Rest(arguments): Rest<JSVal>,
+	) -> ResultExc<JSVal> {
+		self.run_with(cx, Some(store), callback, arguments.into_vec())
+	}
+
+	/// Calls the callback with the storage unset.
+	pub fn exit(&self, cx: &Context, callback: Function, Rest(arguments): Rest<JSVal>) -> ResultExc<JSVal> {
+		self.run_with(cx, None, callback, arguments.into_vec())
+	}
+
+	/// Sets the storage for the remainder of the current task, and the tasks it schedules.
+	#[ion(name = "enterWith")]
+	pub fn enter_with(&self, cx: &Context, store: JSVal) {
+		let context = AsyncContext::current(cx).with(self.id, Some(store));
+		context.enter(cx);
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	AsyncLocalStorage::init_class(cx, global).0
+}
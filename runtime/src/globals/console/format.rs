@@ -119,13 +119,17 @@ pub(crate) fn format_args<'cx>(cx: &'cx Context, args: &'cx [Value<'cx>]) -> Vec
 					index += 1;
 
 					match next {
+						// Symbols cannot be converted to strings, and objects are more useful when inspected.
+						b's' if arg.get().is_symbol() || arg.get().is_object() => {
+							write!(output, "{}", format_value(cx, format_config(), arg)).unwrap()
+						}
 						b's' => output.push_str(&String::from_value(cx, arg, false, ())?),
 						b'o' | b'O' => {
 							outputs.push(FormatArg::String(output));
 							output = String::with_capacity(format.len() - index);
 
 							outputs.push(FormatArg::Value {
-								value: format_value(cx, FormatConfig::default().indentation(INDENTS.get()), arg),
+								value: format_value(cx, format_config(), arg),
 								spaced: false,
 							});
 						}
@@ -133,6 +137,8 @@ pub(crate) fn format_args<'cx>(cx: &'cx Context, args: &'cx [Value<'cx>]) -> Vec
 					}
 				}
 				Some(b'c') => {
+					// CSS styles are consumed, but not applied.
+					args.next();
 					index += 1;
 				}
 				Some(b) => {
@@ -161,11 +167,31 @@ pub(crate) fn format_args<'cx>(cx: &'cx Context, args: &'cx [Value<'cx>]) -> Vec
 	})
 }
 
+/// Joins formatted arguments, separating them with a space if either side is a value.
+pub(crate) fn join_args<'cx>(args: impl Iterator<Item = FormatArg<'cx>>) -> String {
+	let mut output = String::new();
+	let mut prev_spaced = None;
+	for arg in args {
+		let spaced = arg.spaced();
+		if prev_spaced.is_some_and(|prev_spaced| prev_spaced || spaced) {
+			output.push(' ');
+		}
+		write!(output, "{}", arg).unwrap();
+		prev_spaced = Some(spaced);
+	}
+	output
+}
+
+/// Returns the configuration used to format values logged to the console.
+pub(crate) fn format_config() -> FormatConfig {
+	FormatConfig::default().indentation(INDENTS.get()).json(Config::global().console_json)
+}
+
 pub(crate) fn format_value_args<'cx>(
 	cx: &'cx Context, args: impl Iterator<Item = &'cx Value<'cx>>,
 ) -> impl Iterator<Item = FormatArg<'cx>> {
 	args.map(|arg| FormatArg::Value {
-		value: format_value(cx, FormatConfig::default().indentation(INDENTS.get()), arg),
+		value: format_value(cx, format_config(), arg),
 		spaced: true,
 	})
 }
@@ -193,3 +219,60 @@ fn write_printf<D: Display>(
 		(None, None) => write!(output, "{}", display),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+
+	use mozjs::rust::{JSEngine, Runtime};
+
+	use ion::{Array, Context};
+	use ion::conversions::FromValue;
+	use ion::script::Script;
+
+	use crate::config::{Config, CONFIG};
+	use crate::globals::console::format::{format_args, join_args};
+	use crate::RuntimeBuilder;
+
+	fn format(cx: &Context, args: &str) -> String {
+		let args = Script::compile_and_evaluate(cx, Path::new("format.js"), args).unwrap();
+		let args = Array::from_value(cx, &args, true, ()).unwrap().to_vec(cx);
+		join_args(format_args(cx, &args).into_iter())
+	}
+
+	#[test]
+	fn substitutions() {
+		CONFIG.get_or_init(Config::default);
+
+		let engine = JSEngine::init().unwrap();
+		let rt = Runtime::new(engine.handle());
+
+		let cx = &mut Context::from_runtime(&rt);
+		let rt = RuntimeBuilder::<()>::new().build(cx);
+		let cx = rt.cx();
+
+		assert_eq!(
+			"Spiderfire is 4 years and 12.250000 days old",
+			format(cx, r#"["%s is %d years and %.6f days old", "Spiderfire", 4.5, 12.25]"#)
+		);
+		assert_eq!("  3.1|  7", format(cx, r#"["%5.1f|%3d", Math.PI, 7]"#));
+
+		// Styles are consumed by %c, rather than being logged after the message.
+		assert_eq!(
+			format!("Styled text {}", format(cx, r#"["extra"]"#)),
+			format(cx, r#"["%cStyled%c text", "color: red", "font-weight: bold", "extra"]"#)
+		);
+
+		// Symbols and objects are inspected by %s, rather than being converted to strings.
+		assert_eq!(
+			format(cx, r#"[Symbol.for("symbol")]"#),
+			format(cx, r#"["%s", Symbol.for("symbol")]"#)
+		);
+		assert_eq!(format(cx, "[{ a: 1 }]"), format(cx, r#"["%s", { a: 1 }]"#));
+
+		assert_eq!(
+			format!("100% done {}", format(cx, r#"["%s"]"#)),
+			format(cx, r#"["100%% done", "%s"]"#)
+		);
+	}
+}
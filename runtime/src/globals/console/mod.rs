@@ -8,8 +8,8 @@ mod format;
 
 use std::cell::{Cell, RefCell};
 use std::collections::hash_map::{Entry, HashMap};
+use std::fmt::Write;
 
-use chrono::{DateTime, offset::Utc};
 use indent::indent_all_by;
 use indexmap::IndexSet;
 use mozjs::jsapi::JSFunctionSpec;
@@ -26,9 +26,10 @@ use ion::format::key::format_key;
 use ion::format::primitive::format_primitive;
 use ion::function::{Opt, Rest};
 
-use crate::cache::map::find_sourcemap;
+use crate::cache::map::transform_stack_with_sourcemaps;
 use crate::config::{Config, LogLevel};
-use crate::globals::console::format::{format_args, format_value_args, FormatArg};
+use crate::globals::console::format::{format_args, format_config, format_value_args, join_args, FormatArg};
+use crate::globals::performance::PerformanceTimeline;
 
 const ANSI_CLEAR: &str = "\x1b[1;1H";
 const ANSI_CLEAR_SCREEN_DOWN: &str = "\x1b[0J";
@@ -37,7 +38,8 @@ const DEFAULT_LABEL: &str = "default";
 
 thread_local! {
 	static COUNT_MAP: RefCell<HashMap<String, u32>> = RefCell::new(HashMap::new());
-	static TIMER_MAP: RefCell<HashMap<String, DateTime<Utc>>> = RefCell::new(HashMap::new());
+	/// Start times of timers, in milliseconds on the clock of the performance timeline.
+	static TIMER_MAP: RefCell<HashMap<String, f64>> = RefCell::new(HashMap::new());
 
 	static INDENTS: Cell<u16> = const { Cell::new(0) };
 }
@@ -47,6 +49,11 @@ fn log_args(cx: &Context, args: &[Value], log_level: LogLevel) {
 		return;
 	}
 
+	if Config::global().console_json {
+		print_json(cx, args, log_level);
+		return;
+	}
+
 	if args.len() == 1 {
 		print_args(format_value_args(cx, args.iter()), log_level);
 	} else {
@@ -59,33 +66,45 @@ fn print_args<'cx>(args: impl Iterator<Item = FormatArg<'cx>>, log_level: LogLev
 		return;
 	}
 
-	let mut first = true;
+	let output = join_args(args);
+	match log_level {
+		LogLevel::Info | LogLevel::Debug => print!("{}", output),
+		LogLevel::Warn | LogLevel::Error => eprint!("{}", output),
+		LogLevel::None => unreachable!(),
+	}
+}
 
-	let mut prev_spaced = false;
-	for arg in args {
-		let spaced = arg.spaced();
-		let to_space = !first && (prev_spaced || spaced);
-		match log_level {
-			LogLevel::Info | LogLevel::Debug => {
-				if to_space {
-					print!(" ");
-				}
-				print!("{}", arg);
-			}
-			LogLevel::Warn | LogLevel::Error => {
-				if to_space {
-					eprint!(" ");
-				}
-				eprint!("{}", arg);
-			}
-			LogLevel::None => unreachable!(),
-		};
-		first = false;
-		prev_spaced = spaced;
+/// Prints the arguments as a single JSON record, with the level, group indentation and formatted values.
+fn print_json(cx: &Context, args: &[Value], log_level: LogLevel) {
+	let level = match log_level {
+		LogLevel::Info => "info",
+		LogLevel::Warn => "warn",
+		LogLevel::Error => "error",
+		LogLevel::Debug => "debug",
+		LogLevel::None => return,
+	};
+
+	let mut record = format!(r#"{{"level":"{}","indent":{},"values":["#, level, INDENTS.get());
+	for (i, arg) in args.iter().enumerate() {
+		if i != 0 {
+			record.push(',');
+		}
+		write!(record, "{}", format_value(cx, format_config(), arg)).unwrap();
+	}
+	record.push_str("]}");
+
+	if log_level.is_stdout() {
+		print!("{}", record);
+	} else {
+		eprint!("{}", record);
 	}
 }
 
 fn print_indent(log_level: LogLevel) {
+	if Config::global().console_json {
+		return;
+	}
+
 	let indentation = usize::from(INDENTS.get());
 	match log_level {
 		LogLevel::Info | LogLevel::Debug => print!("{}", indent_str(indentation)),
@@ -194,11 +213,7 @@ fn trace(cx: &Context, Rest(values): Rest<Value>) {
 		let indents = ((INDENTS.get() + 1) * 2) as usize;
 
 		if let Some(stack) = &mut stack {
-			for record in &mut stack.records {
-				if let Some(sourcemap) = find_sourcemap(&record.location.file) {
-					record.transform_with_sourcemap(&sourcemap);
-				}
-			}
+			transform_stack_with_sourcemaps(stack);
 
 			println!("{}", &indent_all_by(indents, stack.format()));
 		} else {
@@ -254,11 +269,11 @@ fn countReset(Opt(label): Opt<String>) {
 }
 
 #[js_fn]
-fn time(Opt(label): Opt<String>) {
+fn time(cx: &Context, Opt(label): Opt<String>) {
 	let label = get_label(label);
 	TIMER_MAP.with_borrow_mut(|timers| match timers.entry(label.clone()) {
 		Entry::Vacant(v) => {
-			v.insert(Utc::now());
+			v.insert(PerformanceTimeline::from_context(cx).now());
 		}
 		Entry::Occupied(_) => {
 			if Config::global().log_level >= LogLevel::Warn {
@@ -275,9 +290,9 @@ fn timeLog(cx: &Context, Opt(label): Opt<String>, Rest(values): Rest<Value>) {
 	TIMER_MAP.with_borrow(|timers| match timers.get(&label) {
 		Some(start) => {
 			if Config::global().log_level >= LogLevel::Info {
-				let duration = Utc::now().timestamp_millis() - start.timestamp_millis();
+				let duration = PerformanceTimeline::from_context(cx).now() - start;
 				print_indent(LogLevel::Info);
-				print!("{}: {}ms ", label, duration);
+				print!("{}: {:.3}ms ", label, duration);
 				log_args(cx, &values, LogLevel::Info);
 				println!();
 			}
@@ -292,14 +307,14 @@ fn timeLog(cx: &Context, Opt(label): Opt<String>, Rest(values): Rest<Value>) {
 }
 
 #[js_fn]
-fn timeEnd(Opt(label): Opt<String>) {
+fn timeEnd(cx: &Context, Opt(label): Opt<String>) {
 	let label = get_label(label);
 	TIMER_MAP.with_borrow_mut(|timers| match timers.remove(&label) {
 		Some(start_time) => {
 			if Config::global().log_level >= LogLevel::Info {
-				let duration = Utc::now().timestamp_millis() - start_time.timestamp_millis();
+				let duration = PerformanceTimeline::from_context(cx).now() - start_time;
 				print_indent(LogLevel::Info);
-				print!("{}: {}ms - Timer Ended", label, duration);
+				print!("{}: {:.3}ms - Timer Ended", label, duration);
 				println!();
 			}
 		}
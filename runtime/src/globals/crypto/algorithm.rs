@@ -0,0 +1,279 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::borrow::Cow;
+
+use aes_gcm::{AesGcm, KeyInit};
+use aes_gcm::aead::{Aead, Nonce, Payload};
+use aes_gcm::aead::consts::{U12, U13, U14, U15, U16, U8};
+use aes_gcm::aead::generic_array::ArrayLength;
+use aes_gcm::aes::{Aes128, Aes192, Aes256};
+use aes_gcm::aes::cipher::{BlockCipher, BlockEncrypt, BlockSizeUser};
+use hmac::{Hmac, Mac};
+use mozjs::conversions::ConversionBehavior;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use ion::{Context, Error, ErrorKind, Object, Result, Value};
+use ion::conversions::FromValue;
+
+use crate::globals::file::BufferSource;
+
+/// Lengths of the initialisation vectors supported for AES-GCM, in bytes.
+pub const AES_GCM_IV_LENGTHS: [usize; 3] = [8, 12, 16];
+/// Lengths of the authentication tags supported for AES-GCM, in bits.
+pub const AES_GCM_TAG_LENGTHS: [u8; 5] = [96, 104, 112, 120, 128];
+/// Lengths of the authentication tags allowed by Web Crypto for AES-GCM, in bits.
+const AES_GCM_VALID_TAG_LENGTHS: [u8; 7] = [32, 64, 96, 104, 112, 120, 128];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+	Sha1,
+	Sha256,
+	Sha384,
+	Sha512,
+}
+
+impl HashAlgorithm {
+	pub fn from_name(name: &str) -> Result<HashAlgorithm> {
+		match name.to_ascii_uppercase().as_str() {
+			"SHA-1" => Ok(HashAlgorithm::Sha1),
+			"SHA-256" => Ok(HashAlgorithm::Sha256),
+			"SHA-384" => Ok(HashAlgorithm::Sha384),
+			"SHA-512" => Ok(HashAlgorithm::Sha512),
+			_ => Err(not_supported(name)),
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			HashAlgorithm::Sha1 => "SHA-1",
+			HashAlgorithm::Sha256 => "SHA-256",
+			HashAlgorithm::Sha384 => "SHA-384",
+			HashAlgorithm::Sha512 => "SHA-512",
+		}
+	}
+
+	/// Returns the name of the algorithm in JSON Web Keys for HMAC with this hash.
+	pub fn jwk_hmac_name(&self) -> &'static str {
+		match self {
+			HashAlgorithm::Sha1 => "HS1",
+			HashAlgorithm::Sha256 => "HS256",
+			HashAlgorithm::Sha384 => "HS384",
+			HashAlgorithm::Sha512 => "HS512",
+		}
+	}
+
+	/// Returns the block size of the hash in bits, which is the default length of HMAC keys.
+	pub fn block_size(&self) -> usize {
+		match self {
+			HashAlgorithm::Sha1 | HashAlgorithm::Sha256 => 512,
+			HashAlgorithm::Sha384 | HashAlgorithm::Sha512 => 1024,
+		}
+	}
+
+	pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+		match self {
+			HashAlgorithm::Sha1 => Sha1::digest(data).to_vec(),
+			HashAlgorithm::Sha256 => Sha256::digest(data).to_vec(),
+			HashAlgorithm::Sha384 => Sha384::digest(data).to_vec(),
+			HashAlgorithm::Sha512 => Sha512::digest(data).to_vec(),
+		}
+	}
+
+	pub fn hmac_sign(&self, key: &[u8], data: &[u8]) -> Vec<u8> {
+		fn sign<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+			let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+			mac.update(data);
+			mac.finalize().into_bytes().to_vec()
+		}
+
+		match self {
+			HashAlgorithm::Sha1 => sign::<Hmac<Sha1>>(key, data),
+			HashAlgorithm::Sha256 => sign::<Hmac<Sha256>>(key, data),
+			HashAlgorithm::Sha384 => sign::<Hmac<Sha384>>(key, data),
+			HashAlgorithm::Sha512 => sign::<Hmac<Sha512>>(key, data),
+		}
+	}
+
+	/// Verifies an HMAC signature in constant time.
+	pub fn hmac_verify(&self, key: &[u8], signature: &[u8], data: &[u8]) -> bool {
+		fn verify<M: Mac + KeyInit>(key: &[u8], signature: &[u8], data: &[u8]) -> bool {
+			let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+			mac.update(data);
+			mac.verify_slice(signature).is_ok()
+		}
+
+		match self {
+			HashAlgorithm::Sha1 => verify::<Hmac<Sha1>>(key, signature, data),
+			HashAlgorithm::Sha256 => verify::<Hmac<Sha256>>(key, signature, data),
+			HashAlgorithm::Sha384 => verify::<Hmac<Sha384>>(key, signature, data),
+			HashAlgorithm::Sha512 => verify::<Hmac<Sha512>>(key, signature, data),
+		}
+	}
+}
+
+impl<'cx> FromValue<'cx> for HashAlgorithm {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, _: bool, _: ()) -> Result<HashAlgorithm> {
+		HashAlgorithm::from_name(&algorithm_name(cx, value)?)
+	}
+}
+
+/// Returns the name of an algorithm identifier, which is either a string or an object with a `name` property.
+pub fn algorithm_name(cx: &Context, value: &Value) -> Result<String> {
+	if value.handle().is_string() {
+		return String::from_value(cx, value, true, ());
+	}
+	let object = Object::from_value(cx, value, true, ())?;
+	object
+		.get_as(cx, "name", true, ())?
+		.ok_or_else(|| Error::new("Algorithm does not have a name", ErrorKind::Type))
+}
+
+/// Creates an error which is thrown as a `DOMException` with the given name.
+pub fn dom_exception<M: Into<Cow<'static, str>>>(name: &'static str, message: M) -> Error {
+	Error::new(message, ErrorKind::Normal).with_name(name)
+}
+
+pub fn not_supported(name: &str) -> Error {
+	dom_exception("NotSupportedError", format!("Algorithm {} is not supported", name))
+}
+
+pub fn operation_error(message: &str) -> Error {
+	dom_exception("OperationError", message.to_owned())
+}
+
+pub fn invalid_access_error(message: &str) -> Error {
+	dom_exception("InvalidAccessError", message.to_owned())
+}
+
+pub fn data_error(message: &str) -> Error {
+	dom_exception("DataError", message.to_owned())
+}
+
+/// Parameters for importing and generating HMAC keys.
+#[derive(FromValue)]
+pub struct HmacKeyParams {
+	pub hash: HashAlgorithm,
+	#[ion(convert = ConversionBehavior::EnforceRange)]
+	pub length: Option<u32>,
+}
+
+#[derive(FromValue)]
+pub struct AesKeyGenParams {
+	#[ion(convert = ConversionBehavior::EnforceRange)]
+	pub length: u16,
+}
+
+#[derive(FromValue)]
+pub struct AesGcmParams<'cx> {
+	#[ion(convert = false)]
+	pub iv: BufferSource<'cx>,
+	#[ion(convert = false)]
+	pub additional_data: Option<BufferSource<'cx>>,
+	#[ion(convert = ConversionBehavior::EnforceRange)]
+	pub tag_length: Option<u8>,
+}
+
+/// Parameters of AES-GCM copied from the script, so that they can be used after the operation is queued.
+pub struct AesGcmOptions {
+	pub iv: Vec<u8>,
+	pub additional_data: Vec<u8>,
+	pub tag_length: u8,
+}
+
+impl AesGcmParams<'_> {
+	pub fn into_options(self) -> Result<AesGcmOptions> {
+		let tag_length = self.tag_length.unwrap_or(128);
+		if !AES_GCM_VALID_TAG_LENGTHS.contains(&tag_length) {
+			return Err(operation_error("Invalid AES-GCM authentication tag length"));
+		}
+		if !AES_GCM_IV_LENGTHS.contains(&self.iv.len()) {
+			return Err(dom_exception(
+				"NotSupportedError",
+				"AES-GCM only supports initialisation vectors of 8, 12 or 16 bytes",
+			));
+		}
+		if !AES_GCM_TAG_LENGTHS.contains(&tag_length) {
+			return Err(dom_exception(
+				"NotSupportedError",
+				"AES-GCM only supports authentication tags of 96, 104, 112, 120 or 128 bits",
+			));
+		}
+		Ok(AesGcmOptions {
+			iv: self.iv.to_vec(),
+			additional_data: self.additional_data.map(|data| data.to_vec()).unwrap_or_default(),
+			tag_length,
+		})
+	}
+}
+
+/// Encrypts data with AES-GCM, returning the ciphertext followed by the authentication tag.
+pub fn aes_gcm_encrypt(key: &[u8], options: &AesGcmOptions, data: &[u8]) -> Result<Vec<u8>> {
+	aes_gcm(key, options, data, Operation::Encrypt)
+}
+
+/// Decrypts and authenticates data encrypted with AES-GCM.
+pub fn aes_gcm_decrypt(key: &[u8], options: &AesGcmOptions, data: &[u8]) -> Result<Vec<u8>> {
+	aes_gcm(key, options, data, Operation::Decrypt)
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+	Encrypt,
+	Decrypt,
+}
+
+fn aes_gcm(key: &[u8], options: &AesGcmOptions, data: &[u8], operation: Operation) -> Result<Vec<u8>> {
+	let result = match key.len() {
+		16 => aes_gcm_with_iv::<Aes128>(key, options, data, operation),
+		24 => aes_gcm_with_iv::<Aes192>(key, options, data, operation),
+		32 => aes_gcm_with_iv::<Aes256>(key, options, data, operation),
+		_ => return Err(operation_error("Invalid AES key length")),
+	};
+	result.ok_or_else(|| operation_error("AES-GCM operation failed"))
+}
+
+/// Selects the cipher for the length of the initialisation vector, which is part of the type of the cipher.
+fn aes_gcm_with_iv<A>(key: &[u8], options: &AesGcmOptions, data: &[u8], operation: Operation) -> Option<Vec<u8>>
+where
+	A: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+	match options.iv.len() {
+		8 => aes_gcm_with_tag::<A, U8>(key, options, data, operation),
+		12 => aes_gcm_with_tag::<A, U12>(key, options, data, operation),
+		16 => aes_gcm_with_tag::<A, U16>(key, options, data, operation),
+		_ => None,
+	}
+}
+
+/// Selects the cipher for the length of the authentication tag, which is part of the type of the cipher.
+fn aes_gcm_with_tag<A, N>(key: &[u8], options: &AesGcmOptions, data: &[u8], operation: Operation) -> Option<Vec<u8>>
+where
+	A: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+	N: ArrayLength<u8>,
+{
+	match options.tag_length {
+		96 => apply::<AesGcm<A, N, U12>>(key, options, data, operation),
+		104 => apply::<AesGcm<A, N, U13>>(key, options, data, operation),
+		112 => apply::<AesGcm<A, N, U14>>(key, options, data, operation),
+		120 => apply::<AesGcm<A, N, U15>>(key, options, data, operation),
+		128 => apply::<AesGcm<A, N, U16>>(key, options, data, operation),
+		_ => None,
+	}
+}
+
+fn apply<C: Aead + KeyInit>(key: &[u8], options: &AesGcmOptions, data: &[u8], operation: Operation) -> Option<Vec<u8>> {
+	let cipher = C::new_from_slice(key).ok()?;
+	let nonce = Nonce::<C>::from_slice(&options.iv);
+	let payload = Payload { msg: data, aad: &options.additional_data };
+	match operation {
+		Operation::Encrypt => cipher.encrypt(nonce, payload).ok(),
+		Operation::Decrypt => cipher.decrypt(nonce, payload).ok(),
+	}
+}
@@ -0,0 +1,281 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use mozjs::jsapi::JSObject;
+
+use ion::{Context, Error, ErrorKind, Object, Result, Value};
+use ion::class::Reflector;
+use ion::conversions::FromValue;
+
+use crate::globals::crypto::algorithm::{data_error, dom_exception, HashAlgorithm, invalid_access_error};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyUsage {
+	Encrypt,
+	Decrypt,
+	Sign,
+	Verify,
+	DeriveKey,
+	DeriveBits,
+	WrapKey,
+	UnwrapKey,
+}
+
+impl KeyUsage {
+	pub fn from_name(name: &str) -> Result<KeyUsage> {
+		match name {
+			"encrypt" => Ok(KeyUsage::Encrypt),
+			"decrypt" => Ok(KeyUsage::Decrypt),
+			"sign" => Ok(KeyUsage::Sign),
+			"verify" => Ok(KeyUsage::Verify),
+			"deriveKey" => Ok(KeyUsage::DeriveKey),
+			"deriveBits" => Ok(KeyUsage::DeriveBits),
+			"wrapKey" => Ok(KeyUsage::WrapKey),
+			"unwrapKey" => Ok(KeyUsage::UnwrapKey),
+			_ => Err(Error::new(format!("Invalid key usage: {}", name), ErrorKind::Type)),
+		}
+	}
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			KeyUsage::Encrypt => "encrypt",
+			KeyUsage::Decrypt => "decrypt",
+			KeyUsage::Sign => "sign",
+			KeyUsage::Verify => "verify",
+			KeyUsage::DeriveKey => "deriveKey",
+			KeyUsage::DeriveBits => "deriveBits",
+			KeyUsage::WrapKey => "wrapKey",
+			KeyUsage::UnwrapKey => "unwrapKey",
+		}
+	}
+}
+
+impl<'cx> FromValue<'cx> for KeyUsage {
+	type Config = ();
+
+	fn from_value(cx: &'cx Context, value: &Value, strict: bool, _: ()) -> Result<KeyUsage> {
+		KeyUsage::from_name(&String::from_value(cx, value, strict, ())?)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+	/// HMAC key with the length of the key in bits.
+	Hmac { hash: HashAlgorithm, length: usize },
+	/// AES-GCM key with the length of the key in bits.
+	AesGcm { length: usize },
+}
+
+impl KeyAlgorithm {
+	pub fn name(&self) -> &'static str {
+		match self {
+			KeyAlgorithm::Hmac { .. } => "HMAC",
+			KeyAlgorithm::AesGcm { .. } => "AES-GCM",
+		}
+	}
+
+	pub fn supported_usages(&self) -> &'static [KeyUsage] {
+		match self {
+			KeyAlgorithm::Hmac { .. } => &[KeyUsage::Sign, KeyUsage::Verify],
+			KeyAlgorithm::AesGcm { .. } => &[
+				KeyUsage::Encrypt,
+				KeyUsage::Decrypt,
+				KeyUsage::WrapKey,
+				KeyUsage::UnwrapKey,
+			],
+		}
+	}
+
+	/// Returns the name of the algorithm in JSON Web Keys.
+	pub fn jwk_name(&self) -> &'static str {
+		match self {
+			KeyAlgorithm::Hmac { hash, .. } => hash.jwk_hmac_name(),
+			KeyAlgorithm::AesGcm { length: 128 } => "A128GCM",
+			KeyAlgorithm::AesGcm { length: 192 } => "A192GCM",
+			KeyAlgorithm::AesGcm { .. } => "A256GCM",
+		}
+	}
+
+	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "name", self.name());
+		match self {
+			KeyAlgorithm::Hmac { hash, length } => {
+				let hash_object = Object::new(cx);
+				hash_object.set_as(cx, "name", hash.name());
+				object.set_as(cx, "hash", &hash_object);
+				object.set_as(cx, "length", &(*length as f64));
+			}
+			KeyAlgorithm::AesGcm { length } => {
+				object.set_as(cx, "length", &(*length as f64));
+			}
+		}
+		object
+	}
+}
+
+/// Secret key used by the operations of `crypto.subtle`.
+#[js_class]
+pub struct CryptoKey {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	pub(crate) algorithm: KeyAlgorithm,
+	pub(crate) extractable: bool,
+	#[trace(no_trace)]
+	pub(crate) usages: Vec<KeyUsage>,
+	#[trace(no_trace)]
+	pub(crate) secret: Vec<u8>,
+}
+
+impl CryptoKey {
+	pub fn new(
+		algorithm: KeyAlgorithm, extractable: bool, usages: Vec<KeyUsage>, secret: Vec<u8>,
+	) -> Result<CryptoKey> {
+		if usages.is_empty() {
+			return Err(dom_exception("SyntaxError", "Key usages cannot be empty"));
+		}
+		if let Some(usage) = usages.iter().find(|usage| !algorithm.supported_usages().contains(usage)) {
+			return Err(dom_exception(
+				"SyntaxError",
+				format!("Key usage {} is not supported by {}", usage.as_str(), algorithm.name()),
+			));
+		}
+
+		let mut deduplicated = Vec::with_capacity(usages.len());
+		for usage in usages {
+			if !deduplicated.contains(&usage) {
+				deduplicated.push(usage);
+			}
+		}
+		Ok(CryptoKey {
+			reflector: Reflector::default(),
+			algorithm,
+			extractable,
+			usages: deduplicated,
+			secret,
+		})
+	}
+
+	pub fn algorithm(&self) -> KeyAlgorithm {
+		self.algorithm
+	}
+
+	/// Checks that the key can be used for an operation with the given algorithm.
+	pub(crate) fn check_usage(&self, algorithm: &str, usage: KeyUsage) -> Result<()> {
+		if !self.algorithm.name().eq_ignore_ascii_case(algorithm) {
+			return Err(invalid_access_error(&format!(
+				"Key algorithm {} does not match {}",
+				self.algorithm.name(),
+				algorithm
+			)));
+		}
+		if !self.usages.contains(&usage) {
+			return Err(invalid_access_error(&format!(
+				"Key does not support {}",
+				usage.as_str()
+			)));
+		}
+		Ok(())
+	}
+}
+
+#[js_class]
+impl CryptoKey {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<CryptoKey> {
+		Err(Error::new("CryptoKey has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_type(&self) -> &'static str {
+		"secret"
+	}
+
+	#[ion(get)]
+	pub fn get_extractable(&self) -> bool {
+		self.extractable
+	}
+
+	#[ion(get)]
+	pub fn get_algorithm(&self, cx: &Context) -> *mut JSObject {
+		self.algorithm.to_object(cx).handle().get()
+	}
+
+	#[ion(get)]
+	pub fn get_usages(&self) -> Vec<&'static str> {
+		self.usages.iter().map(KeyUsage::as_str).collect()
+	}
+}
+
+/// Symmetric JSON Web Key, as used by `importKey` and `exportKey`.
+#[derive(FromValue)]
+pub struct JsonWebKey {
+	pub kty: String,
+	pub k: Option<String>,
+	pub alg: Option<String>,
+	pub ext: Option<bool>,
+	#[ion(name = "key_ops")]
+	pub key_ops: Option<Vec<String>>,
+}
+
+impl JsonWebKey {
+	pub fn from_key(key: &CryptoKey) -> JsonWebKey {
+		JsonWebKey {
+			kty: String::from("oct"),
+			k: Some(BASE64_URL_SAFE_NO_PAD.encode(&key.secret)),
+			alg: Some(String::from(key.algorithm.jwk_name())),
+			ext: Some(key.extractable),
+			key_ops: Some(key.usages.iter().map(|usage| String::from(usage.as_str())).collect()),
+		}
+	}
+
+	/// Decodes the secret of the key.
+	pub fn secret(&self) -> Result<Vec<u8>> {
+		if self.kty != "oct" {
+			return Err(data_error("JSON Web Key must have a key type of \"oct\""));
+		}
+		let k = self.k.as_ref().ok_or_else(|| data_error("JSON Web Key does not have a secret"))?;
+		BASE64_URL_SAFE_NO_PAD
+			.decode(k)
+			.map_err(|_| data_error("JSON Web Key secret is not valid base64url"))
+	}
+
+	/// Checks that the key can be imported with the given algorithm, extractability and usages.
+	pub fn validate(&self, algorithm: &KeyAlgorithm, extractable: bool, usages: &[KeyUsage]) -> Result<()> {
+		if self.alg.as_ref().is_some_and(|alg| alg != algorithm.jwk_name()) {
+			return Err(data_error("JSON Web Key algorithm does not match"));
+		}
+		if extractable && self.ext == Some(false) {
+			return Err(data_error("JSON Web Key is not extractable"));
+		}
+		if let Some(key_ops) = &self.key_ops {
+			if usages.iter().any(|usage| !key_ops.iter().any(|op| op == usage.as_str())) {
+				return Err(data_error("JSON Web Key does not support the key usages"));
+			}
+		}
+		Ok(())
+	}
+
+	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "kty", &self.kty);
+		if let Some(k) = &self.k {
+			object.set_as(cx, "k", k);
+		}
+		if let Some(alg) = &self.alg {
+			object.set_as(cx, "alg", alg);
+		}
+		if let Some(ext) = self.ext {
+			object.set_as(cx, "ext", &ext);
+		}
+		if let Some(key_ops) = &self.key_ops {
+			object.set_as(cx, "key_ops", key_ops);
+		}
+		object
+	}
+}
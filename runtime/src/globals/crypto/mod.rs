@@ -0,0 +1,110 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::{Heap, JSObject, Type};
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Object, Result};
+use ion::class::Reflector;
+use ion::flags::PropertyFlags;
+use ion::typedarray::ArrayBufferView;
+pub use key::{CryptoKey, KeyAlgorithm, KeyUsage};
+pub use subtle::SubtleCrypto;
+
+use crate::globals::crypto::algorithm::{dom_exception, operation_error};
+
+pub mod algorithm;
+mod key;
+mod subtle;
+
+/// Maximum number of bytes which can be filled by `crypto.getRandomValues`.
+pub const MAX_RANDOM_BYTES: usize = 65536;
+
+pub(crate) fn fill_random(bytes: &mut [u8]) -> Result<()> {
+	getrandom::getrandom(bytes).map_err(|error| operation_error(&error.to_string()))
+}
+
+#[js_class]
+pub struct Crypto {
+	reflector: Reflector,
+	subtle: Box<Heap<*mut JSObject>>,
+}
+
+#[js_class]
+impl Crypto {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Crypto> {
+		Err(Error::new("Crypto has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_subtle(&self) -> *mut JSObject {
+		self.subtle.get()
+	}
+
+	#[ion(name = "getRandomValues")]
+	pub fn get_random_values(&self, cx: &Context, array: Object) -> Result<*mut JSObject> {
+		let view = ArrayBufferView::from(cx.root(array.handle().get()))
+			.ok_or_else(|| Error::new("Expected an integer typed array", ErrorKind::Type))?;
+		match view.view_type() {
+			Type::Int8
+			| Type::Uint8
+			| Type::Uint8Clamped
+			| Type::Int16
+			| Type::Uint16
+			| Type::Int32
+			| Type::Uint32
+			| Type::BigInt64
+			| Type::BigUint64 => {}
+			_ => return Err(Error::new("Expected an integer typed array", ErrorKind::Type)),
+		}
+
+		if view.byte_length() > MAX_RANDOM_BYTES {
+			return Err(dom_exception(
+				"QuotaExceededError",
+				format!("Cannot generate more than {} random bytes", MAX_RANDOM_BYTES),
+			));
+		}
+		fill_random(unsafe { view.as_mut_slice() })?;
+		Ok(array.handle().get())
+	}
+
+	#[ion(name = "randomUUID")]
+	pub fn random_uuid(&self) -> Result<String> {
+		let mut bytes = [0; 16];
+		fill_random(&mut bytes)?;
+		bytes[6] = (bytes[6] & 0x0F) | 0x40;
+		bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+		let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+		Ok(format!(
+			"{}-{}-{}-{}-{}",
+			&hex[0..8],
+			&hex[8..12],
+			&hex[12..16],
+			&hex[16..20],
+			&hex[20..32]
+		))
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	if !(CryptoKey::init_class(cx, global).0
+		&& SubtleCrypto::init_class(cx, global).0
+		&& Crypto::init_class(cx, global).0)
+	{
+		return false;
+	}
+
+	let subtle = SubtleCrypto::new_object(cx, Box::default());
+	let crypto = Crypto::new_object(
+		cx,
+		Box::new(Crypto {
+			reflector: Reflector::default(),
+			subtle: Heap::boxed(subtle),
+		}),
+	);
+	global.define_as(cx, "crypto", &crypto, PropertyFlags::ENUMERATE)
+}
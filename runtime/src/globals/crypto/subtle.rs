@@ -0,0 +1,232 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Promise, Result, Value};
+use ion::class::Reflector;
+use ion::conversions::{FromValue, IntoValue};
+use ion::typedarray::ArrayBufferWrapper;
+
+use crate::globals::crypto::algorithm::{
+	aes_gcm_decrypt, aes_gcm_encrypt, AesGcmParams, AesKeyGenParams, algorithm_name, data_error, HashAlgorithm,
+	HmacKeyParams, invalid_access_error, not_supported, operation_error,
+};
+use crate::globals::crypto::fill_random;
+use crate::globals::crypto::key::{CryptoKey, JsonWebKey, KeyAlgorithm, KeyUsage};
+use crate::globals::file::BufferSource;
+use crate::promise::future_to_promise;
+
+/// Queues the result of an operation, so that it settles the returned promise asynchronously.
+fn settle<T>(cx: &Context, result: Result<T>) -> Option<Promise>
+where
+	T: for<'cx> IntoValue<'cx> + 'static,
+{
+	unsafe { future_to_promise(cx, move |_| async move { result }) }
+}
+
+fn settle_key(cx: &Context, key: Result<CryptoKey>) -> Option<Promise> {
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			let key = key?;
+			Ok::<_, Error>(CryptoKey::new_object(&cx, Box::new(key)))
+		})
+	}
+}
+
+fn check_name(algorithm: &str, expected: &str) -> Result<()> {
+	if algorithm.eq_ignore_ascii_case(expected) {
+		Ok(())
+	} else {
+		Err(not_supported(algorithm))
+	}
+}
+
+#[derive(Default)]
+#[js_class]
+pub struct SubtleCrypto {
+	reflector: Reflector,
+}
+
+#[js_class]
+impl SubtleCrypto {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<SubtleCrypto> {
+		Err(Error::new("SubtleCrypto has no constructor.", ErrorKind::Type))
+	}
+
+	pub fn digest(
+		&self, cx: &Context, algorithm: Value, #[ion(convert = false)] data: BufferSource,
+	) -> Option<Promise> {
+		let result = HashAlgorithm::from_value(cx, &algorithm, true, ());
+		let data = data.to_vec();
+		settle(cx, result.map(|hash| ArrayBufferWrapper::from(hash.digest(&data))))
+	}
+
+	pub fn sign(
+		&self, cx: &Context, algorithm: Value, key: &CryptoKey, #[ion(convert = false)] data: BufferSource,
+	) -> Option<Promise> {
+		let result = hmac_key(cx, &algorithm, key, KeyUsage::Sign)
+			.map(|hash| ArrayBufferWrapper::from(hash.hmac_sign(&key.secret, &data.to_vec())));
+		settle(cx, result)
+	}
+
+	pub fn verify(
+		&self, cx: &Context, algorithm: Value, key: &CryptoKey, #[ion(convert = false)] signature: BufferSource,
+		#[ion(convert = false)] data: BufferSource,
+	) -> Option<Promise> {
+		let result = hmac_key(cx, &algorithm, key, KeyUsage::Verify)
+			.map(|hash| hash.hmac_verify(&key.secret, &signature.to_vec(), &data.to_vec()));
+		settle(cx, result)
+	}
+
+	pub fn encrypt(
+		&self, cx: &Context, algorithm: Value, key: &CryptoKey, #[ion(convert = false)] data: BufferSource,
+	) -> Option<Promise> {
+		let result = aes_gcm_key(cx, &algorithm, key, KeyUsage::Encrypt).and_then(|params| {
+			let options = params.into_options()?;
+			aes_gcm_encrypt(&key.secret, &options, &data.to_vec()).map(ArrayBufferWrapper::from)
+		});
+		settle(cx, result)
+	}
+
+	pub fn decrypt(
+		&self, cx: &Context, algorithm: Value, key: &CryptoKey, #[ion(convert = false)] data: BufferSource,
+	) -> Option<Promise> {
+		let result = aes_gcm_key(cx, &algorithm, key, KeyUsage::Decrypt).and_then(|params| {
+			let options = params.into_options()?;
+			aes_gcm_decrypt(&key.secret, &options, &data.to_vec()).map(ArrayBufferWrapper::from)
+		});
+		settle(cx, result)
+	}
+
+	#[ion(name = "importKey")]
+	pub fn import_key(
+		&self, cx: &Context, format: String, key_data: Value, algorithm: Value, extractable: bool,
+		usages: Vec<KeyUsage>,
+	) -> Option<Promise> {
+		let key = import_key(cx, &format, &key_data, &algorithm, extractable, usages);
+		settle_key(cx, key)
+	}
+
+	#[ion(name = "exportKey")]
+	pub fn export_key(&self, cx: &Context, format: String, key: &CryptoKey) -> Option<Promise> {
+		if !key.extractable {
+			return settle::<()>(cx, Err(invalid_access_error("Key is not extractable")));
+		}
+
+		match format.as_str() {
+			"raw" => settle(cx, Ok(ArrayBufferWrapper::from(key.secret.clone()))),
+			"jwk" => {
+				let jwk = JsonWebKey::from_key(key);
+				unsafe {
+					future_to_promise(cx, move |cx| async move {
+						Ok::<_, Error>(jwk.to_object(&cx).handle().get())
+					})
+				}
+			}
+			_ => settle::<()>(cx, Err(not_supported(&format))),
+		}
+	}
+
+	#[ion(name = "generateKey")]
+	pub fn generate_key(
+		&self, cx: &Context, algorithm: Value, extractable: bool, usages: Vec<KeyUsage>,
+	) -> Option<Promise> {
+		let key = generate_key(cx, &algorithm, extractable, usages);
+		settle_key(cx, key)
+	}
+}
+
+fn hmac_key(cx: &Context, algorithm: &Value, key: &CryptoKey, usage: KeyUsage) -> Result<HashAlgorithm> {
+	let name = algorithm_name(cx, algorithm)?;
+	check_name(&name, "HMAC")?;
+	key.check_usage(&name, usage)?;
+	match key.algorithm {
+		KeyAlgorithm::Hmac { hash, .. } => Ok(hash),
+		_ => unreachable!("Key algorithm was checked"),
+	}
+}
+
+fn aes_gcm_key<'cx>(
+	cx: &'cx Context, algorithm: &Value, key: &CryptoKey, usage: KeyUsage,
+) -> Result<AesGcmParams<'cx>> {
+	let name = algorithm_name(cx, algorithm)?;
+	check_name(&name, "AES-GCM")?;
+	key.check_usage(&name, usage)?;
+	AesGcmParams::from_value(cx, algorithm, true, ())
+}
+
+fn import_key(
+	cx: &Context, format: &str, key_data: &Value, algorithm: &Value, extractable: bool, usages: Vec<KeyUsage>,
+) -> Result<CryptoKey> {
+	let name = algorithm_name(cx, algorithm)?;
+	let (secret, jwk) = match format {
+		"raw" => (BufferSource::from_value(cx, key_data, true, false)?.to_vec(), None),
+		"jwk" => {
+			let jwk = JsonWebKey::from_value(cx, key_data, true, ())?;
+			(jwk.secret()?, Some(jwk))
+		}
+		_ => return Err(not_supported(format)),
+	};
+
+	let bits = secret.len() * 8;
+	let key_algorithm = match name.to_ascii_uppercase().as_str() {
+		"HMAC" => {
+			let params = HmacKeyParams::from_value(cx, algorithm, true, ())?;
+			if bits == 0 {
+				return Err(data_error("HMAC key cannot be empty"));
+			}
+			let length = match params.length {
+				Some(length) if length as usize > bits || (length as usize) + 8 <= bits => {
+					return Err(data_error("HMAC key length does not match the key data"));
+				}
+				Some(length) => length as usize,
+				None => bits,
+			};
+			KeyAlgorithm::Hmac { hash: params.hash, length }
+		}
+		"AES-GCM" => {
+			if ![128, 192, 256].contains(&bits) {
+				return Err(data_error("AES key must be 128, 192 or 256 bits"));
+			}
+			KeyAlgorithm::AesGcm { length: bits }
+		}
+		_ => return Err(not_supported(&name)),
+	};
+
+	if let Some(jwk) = jwk {
+		jwk.validate(&key_algorithm, extractable, &usages)?;
+	}
+	CryptoKey::new(key_algorithm, extractable, usages, secret)
+}
+
+fn generate_key(cx: &Context, algorithm: &Value, extractable: bool, usages: Vec<KeyUsage>) -> Result<CryptoKey> {
+	let name = algorithm_name(cx, algorithm)?;
+	let key_algorithm = match name.to_ascii_uppercase().as_str() {
+		"HMAC" => {
+			let params = HmacKeyParams::from_value(cx, algorithm, true, ())?;
+			let length = params.length.map(|length| length as usize).unwrap_or_else(|| params.hash.block_size());
+			if length == 0 {
+				return Err(operation_error("HMAC key length cannot be 0"));
+			}
+			KeyAlgorithm::Hmac { hash: params.hash, length }
+		}
+		"AES-GCM" => {
+			let params = AesKeyGenParams::from_value(cx, algorithm, true, ())?;
+			if ![128, 192, 256].contains(&params.length) {
+				return Err(operation_error("AES key length must be 128, 192 or 256 bits"));
+			}
+			KeyAlgorithm::AesGcm { length: params.length as usize }
+		}
+		_ => return Err(not_supported(&name)),
+	};
+
+	let length = match key_algorithm {
+		KeyAlgorithm::Hmac { length, .. } | KeyAlgorithm::AesGcm { length } => length,
+	};
+	let mut secret = vec![0; length.div_ceil(8)];
+	fill_random(&mut secret)?;
+	CryptoKey::new(key_algorithm, extractable, usages, secret)
+}
@@ -0,0 +1,97 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use ion::{Context, Function, Object, Symbol};
+use ion::class::ClassInfo;
+use ion::flags::PropertyFlags;
+
+/// Method which releases a resource, called at the end of the scope of a `using` or `await using` declaration.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Disposal {
+	/// `Symbol.dispose`, which releases the resource synchronously.
+	Sync,
+	/// `Symbol.asyncDispose`, which returns a promise that resolves once the resource is released.
+	Async,
+}
+
+impl Disposal {
+	/// Returns the name of the symbol as a property of the `Symbol` constructor.
+	pub const fn identifier(&self) -> &'static str {
+		match self {
+			Disposal::Sync => "dispose",
+			Disposal::Async => "asyncDispose",
+		}
+	}
+}
+
+/// Returns `Symbol.dispose` or `Symbol.asyncDispose`.
+///
+/// Engines without explicit resource management do not define these symbols, so they are created and defined on the
+/// `Symbol` constructor of the current global, allowing scripts and libraries which implement `using` to find them.
+pub fn dispose_symbol<'cx>(cx: &'cx Context, disposal: Disposal) -> Option<Symbol<'cx>> {
+	let global = Object::global(cx);
+	let constructor = global.get_as::<_, Object>(cx, "Symbol", true, ()).ok()??;
+	if let Ok(Some(symbol)) = constructor.get_as::<_, Symbol>(cx, disposal.identifier(), true, ()) {
+		return Some(symbol);
+	}
+
+	let symbol = Symbol::new(cx, &format!("Symbol.{}", disposal.identifier()));
+	constructor
+		.define_as(cx, disposal.identifier(), &symbol, PropertyFlags::empty())
+		.then_some(symbol)
+}
+
+/// Defines `[Symbol.dispose]` or `[Symbol.asyncDispose]` on a prototype, as an alias of one of its methods.
+/// Returns `false` if the method does not exist.
+pub fn define_disposal(cx: &Context, prototype: &Object, method: &str, disposal: Disposal) -> bool {
+	let Some(symbol) = dispose_symbol(cx, disposal) else {
+		return false;
+	};
+	match prototype.get_as::<_, Function>(cx, method, true, ()) {
+		Ok(Some(function)) => prototype.define_as(
+			cx,
+			symbol,
+			&function,
+			PropertyFlags::CONFIGURABLE | PropertyFlags::WRITABLE,
+		),
+		_ => false,
+	}
+}
+
+/// Defines `[Symbol.dispose]` or `[Symbol.asyncDispose]` on the prototype of a native class, as an alias of one of its
+/// methods. Takes the result of [init_class](ion::ClassDefinition::init_class), so that it can be chained after it.
+pub fn define_class_disposal(
+	cx: &Context, (initialised, info): (bool, &ClassInfo), method: &str, disposal: Disposal,
+) -> bool {
+	initialised && define_disposal(cx, &Object::from(cx.root(info.prototype)), method, disposal)
+}
+
+/// Readers and writers of streams release their lock when disposed, so that the stream can be read or written again.
+const STREAM_LOCKS: [&str; 3] = [
+	"ReadableStreamDefaultReader",
+	"ReadableStreamBYOBReader",
+	"WritableStreamDefaultWriter",
+];
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	if dispose_symbol(cx, Disposal::Sync).is_none() || dispose_symbol(cx, Disposal::Async).is_none() {
+		return false;
+	}
+
+	for name in STREAM_LOCKS {
+		// Classes which are not exposed by the engine are skipped.
+		let Ok(Some(class)) = global.get_as::<_, Object>(cx, name, true, ()) else {
+			continue;
+		};
+		let Ok(Some(prototype)) = class.get_as::<_, Object>(cx, "prototype", true, ()) else {
+			continue;
+		};
+		if !define_disposal(cx, &prototype, "releaseLock", Disposal::Sync) {
+			return false;
+		}
+	}
+	true
+}
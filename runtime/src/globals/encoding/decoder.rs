@@ -41,92 +41,86 @@ pub struct TextDecoder {
 	decoder: Decoder,
 	pub fatal: bool,
 	pub ignore_byte_order_mark: bool,
+	/// Whether the previous call to [TextDecoder::decode] was streaming, so its state is carried over.
+	do_not_flush: bool,
 }
 
-#[js_class]
 impl TextDecoder {
-	#[ion(constructor)]
-	pub fn constructor(Opt(label): Opt<String>, Opt(options): Opt<TextDecoderOptions>) -> Result<TextDecoder> {
-		let encoding;
-		if let Some(label) = label {
-			let enc = Encoding::for_label_no_replacement(label.as_bytes());
-			match enc {
-				None => {
-					return Err(Error::new(
-						format!("The given encoding '{}' is not supported.", label),
-						ErrorKind::Range,
-					));
-				}
-				Some(enc) => encoding = enc,
-			}
+	fn new_decoder(encoding: &'static Encoding, ignore_byte_order_mark: bool) -> Decoder {
+		// Only a byte order mark of the given encoding is removed, instead of sniffing for another encoding.
+		if ignore_byte_order_mark {
+			encoding.new_decoder_without_bom_handling()
 		} else {
-			encoding = UTF_8;
+			encoding.new_decoder_with_bom_removal()
 		}
+	}
+}
 
-		let options = options.unwrap_or_default();
-		let decoder = if options.ignore_byte_order_mark {
-			encoding.new_decoder_without_bom_handling()
-		} else {
-			encoding.new_decoder()
+#[js_class]
+impl TextDecoder {
+	#[ion(constructor)]
+	pub fn constructor(Opt(label): Opt<String>, Opt(options): Opt<TextDecoderOptions>) -> Result<TextDecoder> {
+		let encoding = match label {
+			// The replacement encoding is excluded, as it cannot be used for decoding.
+			Some(label) => Encoding::for_label_no_replacement(label.as_bytes()).ok_or_else(|| {
+				Error::new(
+					format!("The given encoding '{}' is not supported.", label),
+					ErrorKind::Range,
+				)
+			})?,
+			None => UTF_8,
 		};
 
+		let options = options.unwrap_or_default();
 		Ok(TextDecoder {
 			reflector: Reflector::default(),
 			encoding,
-			decoder,
+			decoder: TextDecoder::new_decoder(encoding, options.ignore_byte_order_mark),
 			fatal: options.fatal,
 			ignore_byte_order_mark: options.ignore_byte_order_mark,
+			do_not_flush: false,
 		})
 	}
 
 	pub fn decode(
 		&mut self, #[ion(convert = true)] Opt(buffer): Opt<BufferSource>, Opt(options): Opt<TextDecodeOptions>,
 	) -> Result<String> {
-		let mut string = String::with_capacity(
-			self.decoder
-				.max_utf8_buffer_length(buffer.as_ref().map(|b| b.len()).unwrap_or_default())
-				.unwrap(),
-		);
+		if !self.do_not_flush {
+			self.decoder = TextDecoder::new_decoder(self.encoding, self.ignore_byte_order_mark);
+		}
 		let stream = options.unwrap_or_default().stream;
-		if self.fatal {
-			let vec_buffer;
-			let buffer = match buffer {
-				Some(ref b) => {
-					if b.is_shared() {
-						vec_buffer = b.to_vec();
-						&vec_buffer
-					} else {
-						unsafe { b.as_slice() }
-					}
-				}
-				None => &[],
-			};
+		self.do_not_flush = stream;
 
+		let vec_buffer;
+		let buffer = match buffer {
+			Some(ref b) if b.is_shared() => {
+				vec_buffer = b.to_vec();
+				&vec_buffer
+			}
+			Some(ref b) => unsafe { b.as_slice() },
+			None => &[],
+		};
+
+		if self.fatal {
+			let mut string =
+				String::with_capacity(self.decoder.max_utf8_buffer_length_without_replacement(buffer.len()).unwrap());
 			let (result, _) = self.decoder.decode_to_string_without_replacement(buffer, &mut string, !stream);
 			if let DecoderResult::Malformed(_, _) = result {
+				// The decoder is reset by the next call, since the stream cannot continue after an error.
+				self.do_not_flush = false;
 				return Err(Error::new("TextDecoder.decode: Decoding Failed", ErrorKind::Type));
 			}
+			Ok(string)
 		} else {
-			let buffer = match buffer {
-				Some(ref b) => unsafe { b.as_slice() },
-				None => &[],
-			};
+			let mut string = String::with_capacity(self.decoder.max_utf8_buffer_length(buffer.len()).unwrap());
 			let (_, _, _) = self.decoder.decode_to_string(buffer, &mut string, !stream);
+			Ok(string)
 		}
-		if !stream {
-			// Replace the decoder, since it's now in finished state.
-			self.decoder = if self.ignore_byte_order_mark {
-				self.encoding.new_decoder_without_bom_handling()
-			} else {
-				self.encoding.new_decoder()
-			};
-		}
-		Ok(string)
 	}
 
 	#[ion(get)]
 	pub fn get_encoding(&self) -> String {
-		String::from(self.decoder.encoding().name()).to_ascii_lowercase()
+		String::from(self.encoding.name()).to_ascii_lowercase()
 	}
 
 	#[ion(get)]
@@ -48,15 +48,16 @@ impl TextEncoder {
 		let buf_len = self.encoder.max_buffer_length_from_utf8_if_no_unmappables(input.len()).unwrap();
 		let mut buf = Vec::with_capacity(buf_len);
 		let (_, _, _) = self.encoder.encode_from_utf8_to_vec(&input, &mut buf, true);
-		Uint8Array::copy_from_bytes(cx, buf.as_ref())
-			.ok_or_else(|| Error::new("Failed to allocate buffer", ErrorKind::Normal))
+		Uint8Array::from_vec(cx, buf).ok_or_else(|| Error::new("Failed to allocate buffer", ErrorKind::Normal))
 	}
 
+	/// Encodes the string into the destination, returning the number of UTF-16 code units read and bytes written.
+	/// Characters are never split, so encoding stops at the first character which does not fit.
 	#[ion(name = "encodeInto")]
 	pub fn encode_into(&mut self, input: String, destination: Uint8Array) -> EncodeResult {
 		let (_, read, written, _) = self.encoder.encode_from_utf8(&input, unsafe { destination.as_mut_slice() }, true);
 		EncodeResult {
-			read: read as u64,
+			read: input[..read].encode_utf16().count() as u64,
 			written: written as u64,
 		}
 	}
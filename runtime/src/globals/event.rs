@@ -0,0 +1,341 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use chrono::Utc;
+use mozjs::jsapi::JSObject;
+use mozjs::jsval::{JSVal, NullValue, UndefinedValue};
+
+use ion::{Array, ClassDefinition, Context, Heap, Object};
+use ion::class::Reflector;
+use ion::function::Opt;
+
+#[derive(Debug, Default, FromValue)]
+pub struct EventInit {
+	#[ion(default)]
+	pub bubbles: bool,
+	#[ion(default)]
+	pub cancelable: bool,
+	#[ion(default)]
+	pub composed: bool,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Traceable)]
+#[repr(u8)]
+pub enum EventPhase {
+	#[default]
+	None = 0,
+	Capturing = 1,
+	AtTarget = 2,
+	Bubbling = 3,
+}
+
+#[js_class]
+pub struct Event {
+	reflector: Reflector,
+	kind: String,
+	bubbles: bool,
+	cancelable: bool,
+	composed: bool,
+	trusted: bool,
+	time_stamp: f64,
+
+	pub(crate) phase: EventPhase,
+	pub(crate) target: Option<Heap<*mut JSObject>>,
+	pub(crate) current_target: Option<Heap<*mut JSObject>>,
+
+	pub(crate) dispatching: bool,
+	pub(crate) canceled: bool,
+	pub(crate) in_passive_listener: bool,
+	pub(crate) propagation_stopped: bool,
+	pub(crate) immediate_propagation_stopped: bool,
+}
+
+impl Event {
+	pub fn new(kind: String, init: EventInit) -> Event {
+		Event {
+			reflector: Reflector::default(),
+			kind,
+			bubbles: init.bubbles,
+			cancelable: init.cancelable,
+			composed: init.composed,
+			trusted: false,
+			time_stamp: Utc::now().timestamp_millis() as f64,
+
+			phase: EventPhase::None,
+			target: None,
+			current_target: None,
+
+			dispatching: false,
+			canceled: false,
+			in_passive_listener: false,
+			propagation_stopped: false,
+			immediate_propagation_stopped: false,
+		}
+	}
+
+	/// Creates an event dispatched by the runtime, rather than by a script.
+	pub fn new_trusted(kind: &str, init: EventInit) -> Event {
+		Event {
+			trusted: true,
+			..Event::new(String::from(kind), init)
+		}
+	}
+
+	pub fn kind(&self) -> &str {
+		&self.kind
+	}
+
+	/// Resets the dispatch state of the event after it has been dispatched.
+	pub(crate) fn finish_dispatch(&mut self) {
+		self.phase = EventPhase::None;
+		self.current_target = None;
+		self.dispatching = false;
+		self.in_passive_listener = false;
+		self.propagation_stopped = false;
+		self.immediate_propagation_stopped = false;
+	}
+}
+
+#[js_class]
+impl Event {
+	pub const NONE: i32 = EventPhase::None as u8 as i32;
+	pub const CAPTURING_PHASE: i32 = EventPhase::Capturing as u8 as i32;
+	pub const AT_TARGET: i32 = EventPhase::AtTarget as u8 as i32;
+	pub const BUBBLING_PHASE: i32 = EventPhase::Bubbling as u8 as i32;
+
+	#[ion(constructor)]
+	pub fn constructor(kind: String, Opt(init): Opt<EventInit>) -> Event {
+		Event::new(kind, init.unwrap_or_default())
+	}
+
+	#[ion(get)]
+	pub fn get_type(&self) -> String {
+		self.kind.clone()
+	}
+
+	#[ion(get)]
+	pub fn get_target(&self) -> Option<*mut JSObject> {
+		self.target.as_ref().map(Heap::get)
+	}
+
+	#[ion(get)]
+	pub fn get_current_target(&self) -> Option<*mut JSObject> {
+		self.current_target.as_ref().map(Heap::get)
+	}
+
+	#[ion(get)]
+	pub fn get_event_phase(&self) -> i32 {
+		self.phase as u8 as i32
+	}
+
+	#[ion(get)]
+	pub fn get_bubbles(&self) -> bool {
+		self.bubbles
+	}
+
+	#[ion(get)]
+	pub fn get_cancelable(&self) -> bool {
+		self.cancelable
+	}
+
+	#[ion(get)]
+	pub fn get_composed(&self) -> bool {
+		self.composed
+	}
+
+	#[ion(get)]
+	pub fn get_default_prevented(&self) -> bool {
+		self.canceled
+	}
+
+	#[ion(get)]
+	pub fn get_is_trusted(&self) -> bool {
+		self.trusted
+	}
+
+	#[ion(get)]
+	pub fn get_time_stamp(&self) -> f64 {
+		self.time_stamp
+	}
+
+	/// Returns the path of the event while it is being dispatched.
+	/// Event targets are not nested, so the path only contains the target.
+	#[ion(name = "composedPath")]
+	pub fn composed_path(&self, cx: &Context) -> *mut JSObject {
+		let path = Array::new(cx);
+		if self.dispatching {
+			if let Some(target) = &self.target {
+				path.set_as(cx, 0, &target.get());
+			}
+		}
+		path.into_local().get()
+	}
+
+	#[ion(name = "preventDefault")]
+	pub fn prevent_default(&mut self) {
+		if self.cancelable && !self.in_passive_listener {
+			self.canceled = true;
+		}
+	}
+
+	#[ion(name = "stopPropagation")]
+	pub fn stop_propagation(&mut self) {
+		self.propagation_stopped = true;
+	}
+
+	#[ion(name = "stopImmediatePropagation")]
+	pub fn stop_immediate_propagation(&mut self) {
+		self.propagation_stopped = true;
+		self.immediate_propagation_stopped = true;
+	}
+}
+
+#[derive(Default, FromValue)]
+pub struct CustomEventInit {
+	#[ion(inherit)]
+	pub event: EventInit,
+	pub detail: Option<JSVal>,
+}
+
+#[js_class]
+pub struct CustomEvent {
+	event: Event,
+	detail: Heap<JSVal>,
+}
+
+#[js_class]
+impl CustomEvent {
+	#[ion(constructor)]
+	pub fn constructor(kind: String, Opt(init): Opt<CustomEventInit>) -> CustomEvent {
+		let init = init.unwrap_or_default();
+		CustomEvent {
+			event: Event::new(kind, init.event),
+			detail: Heap::new(init.detail.filter(|detail| !detail.is_undefined()).unwrap_or_else(NullValue)),
+		}
+	}
+
+	#[ion(get)]
+	pub fn get_detail(&self) -> JSVal {
+		self.detail.get()
+	}
+}
+
+#[derive(Debug, Default, FromValue)]
+pub struct ProgressEventInit {
+	#[ion(inherit)]
+	pub event: EventInit,
+	#[ion(default)]
+	pub length_computable: bool,
+	#[ion(default)]
+	pub loaded: f64,
+	#[ion(default)]
+	pub total: f64,
+}
+
+/// Event dispatched to report the progress of reading a resource, such as by a `FileReader`.
+#[js_class]
+pub struct ProgressEvent {
+	event: Event,
+	length_computable: bool,
+	loaded: f64,
+	total: f64,
+}
+
+impl ProgressEvent {
+	pub fn new_trusted(kind: &str, loaded: u64, total: u64) -> ProgressEvent {
+		ProgressEvent {
+			event: Event::new_trusted(kind, EventInit::default()),
+			length_computable: true,
+			loaded: loaded as f64,
+			total: total as f64,
+		}
+	}
+}
+
+#[js_class]
+impl ProgressEvent {
+	#[ion(constructor)]
+	pub fn constructor(kind: String, Opt(init): Opt<ProgressEventInit>) -> ProgressEvent {
+		let init = init.unwrap_or_default();
+		ProgressEvent {
+			event: Event::new(kind, init.event),
+			length_computable: init.length_computable,
+			loaded: init.loaded,
+			total: init.total,
+		}
+	}
+
+	#[ion(get)]
+	pub fn get_length_computable(&self) -> bool {
+		self.length_computable
+	}
+
+	#[ion(get)]
+	pub fn get_loaded(&self) -> f64 {
+		self.loaded
+	}
+
+	#[ion(get)]
+	pub fn get_total(&self) -> f64 {
+		self.total
+	}
+}
+
+#[derive(FromValue)]
+pub struct PromiseRejectionEventInit<'cx> {
+	#[ion(inherit)]
+	pub event: EventInit,
+	pub promise: Object<'cx>,
+	pub reason: Option<JSVal>,
+}
+
+/// Event dispatched for promises which were rejected without a handler.
+#[js_class]
+pub struct PromiseRejectionEvent {
+	event: Event,
+	promise: Heap<*mut JSObject>,
+	reason: Heap<JSVal>,
+}
+
+impl PromiseRejectionEvent {
+	pub fn new_trusted(kind: &str, promise: *mut JSObject, reason: JSVal) -> PromiseRejectionEvent {
+		let init = EventInit { cancelable: true, ..EventInit::default() };
+		PromiseRejectionEvent {
+			event: Event::new_trusted(kind, init),
+			promise: Heap::new(promise),
+			reason: Heap::new(reason),
+		}
+	}
+}
+
+#[js_class]
+impl PromiseRejectionEvent {
+	#[ion(constructor)]
+	pub fn constructor(kind: String, init: PromiseRejectionEventInit) -> PromiseRejectionEvent {
+		PromiseRejectionEvent {
+			event: Event::new(kind, init.event),
+			promise: Heap::new(init.promise.handle().get()),
+			reason: Heap::new(init.reason.unwrap_or_else(UndefinedValue)),
+		}
+	}
+
+	#[ion(get)]
+	pub fn get_promise(&self) -> *mut JSObject {
+		self.promise.get()
+	}
+
+	#[ion(get)]
+	pub fn get_reason(&self) -> JSVal {
+		self.reason.get()
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	Event::init_class(cx, global).0
+		&& CustomEvent::init_class(cx, global).0
+		&& ProgressEvent::init_class(cx, global).0
+		&& PromiseRejectionEvent::init_class(cx, global).0
+}
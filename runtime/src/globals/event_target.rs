@@ -0,0 +1,242 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::JSObject;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, ErrorReport, Exception, Function, Heap, Object, ResultExc};
+use ion::class::Reflector;
+use ion::conversions::ToValue;
+use ion::function::Opt;
+
+use crate::cache::map::format_error_report;
+use crate::globals::event::{Event, EventPhase};
+
+#[derive(Debug, Default, FromValue)]
+pub struct EventListenerOptions {
+	#[ion(default)]
+	pub capture: bool,
+	#[ion(default)]
+	pub once: bool,
+	#[ion(default)]
+	pub passive: bool,
+}
+
+#[derive(FromValue)]
+pub enum ListenerOptions {
+	#[ion(inherit)]
+	Options(EventListenerOptions),
+	#[ion(inherit)]
+	Capture(bool),
+}
+
+impl ListenerOptions {
+	fn into_options(self) -> EventListenerOptions {
+		match self {
+			ListenerOptions::Options(options) => options,
+			ListenerOptions::Capture(capture) => EventListenerOptions {
+				capture,
+				..EventListenerOptions::default()
+			},
+		}
+	}
+}
+
+#[derive(Traceable)]
+struct EventListener {
+	id: u64,
+	kind: String,
+	/// Function, or object with a `handleEvent` method.
+	callback: Heap<*mut JSObject>,
+	capture: bool,
+	once: bool,
+	passive: bool,
+	/// Whether the listener was registered through an event handler property, such as `onabort`.
+	handler: bool,
+}
+
+/// Target of events, which can be extended by native classes to dispatch events to scripts.
+///
+/// Event targets are not nested, so events are only dispatched to the listeners of the target itself.
+#[js_class]
+#[derive(Default)]
+pub struct EventTarget {
+	reflector: Reflector,
+	listeners: Vec<EventListener>,
+	next_id: u64,
+}
+
+impl EventTarget {
+	fn add_listener(&mut self, kind: String, callback: *mut JSObject, options: EventListenerOptions, handler: bool) {
+		self.next_id += 1;
+		self.listeners.push(EventListener {
+			id: self.next_id,
+			kind,
+			callback: Heap::new(callback),
+			capture: options.capture,
+			once: options.once,
+			passive: options.passive,
+			handler,
+		});
+	}
+
+	/// Returns the callback of the event handler property for the given event type.
+	pub fn event_handler(&self, kind: &str) -> Option<*mut JSObject> {
+		self.listeners
+			.iter()
+			.find(|listener| listener.handler && listener.kind == kind)
+			.map(|listener| listener.callback.get())
+	}
+
+	/// Sets the callback of the event handler property for the given event type.
+	/// The handler keeps its position relative to other listeners when it is replaced.
+	pub fn set_event_handler(&mut self, cx: &Context, kind: &str, callback: Option<Function>) {
+		let position = self.listeners.iter().position(|listener| listener.handler && listener.kind == kind);
+		let callback = callback.map(|callback| callback.to_object(cx).handle().get());
+		match (position, callback) {
+			(Some(index), Some(callback)) => self.listeners[index].callback.set(callback),
+			(Some(index), None) => {
+				self.listeners.remove(index);
+			}
+			(None, Some(callback)) => {
+				self.add_listener(String::from(kind), callback, EventListenerOptions::default(), true)
+			}
+			(None, None) => {}
+		}
+	}
+
+	/// Dispatches an event to the listeners of the target.
+	/// Returns `false` if the event was cancelled by a listener.
+	pub fn dispatch(cx: &Context, target: &Object, event: &Object) -> ResultExc<bool> {
+		let kind = {
+			let event = Event::get_mut_private(cx, event)?;
+			if event.dispatching {
+				return Err(Error::new("Event is already being dispatched", ErrorKind::Normal).into());
+			}
+			event.dispatching = true;
+			event.target = Some(Heap::new(target.handle().get()));
+			event.current_target = Some(Heap::new(target.handle().get()));
+			event.phase = EventPhase::AtTarget;
+			String::from(event.kind())
+		};
+
+		// Listeners added during dispatch are not invoked, so the listeners are collected beforehand.
+		let listeners: Vec<_> = EventTarget::get_private(cx, target)?
+			.listeners
+			.iter()
+			.filter(|listener| listener.kind == kind)
+			.map(|listener| (listener.id, listener.capture, listener.callback.to_traced()))
+			.collect();
+
+		// Capturing listeners are invoked before non-capturing listeners at the target.
+		let ordered = listeners
+			.iter()
+			.filter(|(_, capture, _)| *capture)
+			.chain(listeners.iter().filter(|(_, capture, _)| !capture));
+		for (id, _, callback) in ordered {
+			if Event::get_private(cx, event)?.immediate_propagation_stopped {
+				break;
+			}
+
+			let this = EventTarget::get_mut_private(cx, target)?;
+			let Some(index) = this.listeners.iter().position(|listener| listener.id == *id) else {
+				continue;
+			};
+			let passive = this.listeners[index].passive;
+			if this.listeners[index].once {
+				this.listeners.remove(index);
+			}
+
+			Event::get_mut_private(cx, event)?.in_passive_listener = passive;
+			let callback = Object::from(callback.root(cx));
+			if let Err(Some(report)) = invoke_listener(cx, &callback, target, event) {
+				eprintln!(
+					"Uncaught exception in event listener: {}",
+					format_error_report(cx, report)
+				);
+			}
+			Event::get_mut_private(cx, event)?.in_passive_listener = false;
+		}
+
+		let event = Event::get_mut_private(cx, event)?;
+		event.finish_dispatch();
+		Ok(!event.canceled)
+	}
+}
+
+fn invoke_listener(
+	cx: &Context, callback: &Object, target: &Object, event: &Object,
+) -> Result<(), Option<ErrorReport>> {
+	let args = [event.as_value(cx)];
+	if let Some(function) = Function::from_object(cx, callback) {
+		return function.call(cx, target, &args).map(|_| ());
+	}
+
+	match callback.get_as::<_, Function>(cx, "handleEvent", true, ()) {
+		Ok(Some(function)) => function.call(cx, callback, &args).map(|_| ()),
+		Ok(None) => {
+			let error = Error::new("Event listener does not have a handleEvent method", ErrorKind::Type);
+			Err(Some(ErrorReport::from(Exception::Error(error), None)))
+		}
+		Err(error) => Err(Some(ErrorReport::from(Exception::Error(error), None))),
+	}
+}
+
+#[js_class]
+impl EventTarget {
+	#[ion(constructor)]
+	pub fn constructor() -> EventTarget {
+		EventTarget::default()
+	}
+
+	#[ion(name = "addEventListener")]
+	pub fn add_event_listener(&mut self, kind: String, callback: Option<Object>, Opt(options): Opt<ListenerOptions>) {
+		let Some(callback) = callback else {
+			return;
+		};
+		let options = options.map(ListenerOptions::into_options).unwrap_or_default();
+		let callback = callback.handle().get();
+
+		let duplicate = self.listeners.iter().any(|listener| {
+			!listener.handler
+				&& listener.kind == kind
+				&& listener.callback.get() == callback
+				&& listener.capture == options.capture
+		});
+		if !duplicate {
+			self.add_listener(kind, callback, options, false);
+		}
+	}
+
+	#[ion(name = "removeEventListener")]
+	pub fn remove_event_listener(
+		&mut self, kind: String, callback: Option<Object>, Opt(options): Opt<ListenerOptions>,
+	) {
+		let Some(callback) = callback else {
+			return;
+		};
+		let capture = options.map(ListenerOptions::into_options).unwrap_or_default().capture;
+		let callback = callback.handle().get();
+
+		self.listeners.retain(|listener| {
+			listener.handler
+				|| listener.kind != kind
+				|| listener.callback.get() != callback
+				|| listener.capture != capture
+		});
+	}
+
+	#[ion(name = "dispatchEvent")]
+	pub fn dispatch_event(cx: &Context, #[ion(this)] this: &Object, event: Object) -> ResultExc<bool> {
+		if !Event::instance_of(cx, &event) && !Event::has_instance(cx, &event)? {
+			return Err(Error::new("Expected Event", ErrorKind::Type).into());
+		}
+		EventTarget::dispatch(cx, this, &event)
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	EventTarget::init_class(cx, global).0
+}
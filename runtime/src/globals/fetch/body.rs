@@ -5,21 +5,24 @@
  */
 
 use std::convert::Infallible;
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
-use bytes::Bytes;
-use futures::{StreamExt, stream};
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt, stream};
 use http::header::CONTENT_TYPE;
 use http::{HeaderMap, HeaderValue};
 use hyper::body::HttpBody;
 use hyper::Body;
 use ion::class::NativeObject;
 use ion::function::Opt;
-use ion::typedarray::ArrayBuffer;
+use ion::typedarray::Uint8Array;
 use mozjs::c_str;
 use mozjs::jsapi::{CheckReadableStreamControllerCanCloseOrEnqueue, JSObject};
-use multipart::client::multipart;
-use mozjs::jsval::JSVal;
+use mozjs::jsval::{JSVal, ObjectValue};
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 use ion::{
 	ClassDefinition, Context, Error, ErrorKind, Exception, Function, Heap, Object, Promise, ReadableStream, Result,
@@ -27,10 +30,16 @@ use ion::{
 };
 use ion::conversions::{FromValue, ToValue};
 
-use crate::globals::file::{Blob, BufferSource, File, BlobPart, FileOptions, BlobOptions};
-use crate::globals::form_data::{FormData, FormDataEntryValue};
+use crate::globals::fetch::content_type::{check_json, form_format, FormFormat, MimeChecking};
+use crate::globals::fetch::error::{FetchError, FetchErrorPhase};
+use crate::globals::fetch::large_body::{self, LargeBodyOptions};
+use crate::globals::fetch::multipart::MultipartForm;
+use crate::globals::fetch::timing::ResponseTiming;
+use crate::globals::file::{disk_stream, Blob, BlobData, BufferSource, File, BlobPart, FileOptions, BlobOptions};
+use crate::globals::form_data::FormData;
 use crate::globals::streams::{NativeStreamSourceCallbacks, NativeStreamSource};
 use crate::globals::url::URLSearchParams;
+use crate::handle;
 use crate::promise::future_to_promise;
 
 use super::header::Header;
@@ -41,6 +50,8 @@ pub enum FetchBodyInner {
 	None,
 	Bytes(#[trace(no_trace)] Bytes),
 	Stream(#[trace(no_trace)] ReadableStream),
+	Multipart(#[trace(no_trace)] MultipartForm),
+	Reader(#[trace(no_trace)] AsyncReadBody),
 }
 
 impl FetchBodyInner {
@@ -49,8 +60,73 @@ impl FetchBodyInner {
 			Self::None => Self::None,
 			Self::Bytes(bytes) => Self::Bytes(bytes.clone()),
 			Self::Stream(stream) => Self::Stream(stream.try_clone(cx)?),
+			Self::Multipart(form) => Self::Multipart(form.clone()),
+			Self::Reader(reader) => Self::Reader(reader.clone()),
 		})
 	}
+
+	pub fn into_stream(self, cx: &Context) -> Result<ReadableStream> {
+		match self {
+			Self::None => Ok(ReadableStream::from_bytes(cx, Bytes::new())),
+			Self::Bytes(bytes) => Ok(ReadableStream::from_bytes(cx, bytes)),
+			Self::Stream(stream) => Ok(stream),
+			Self::Multipart(form) => hyper_body_to_stream(cx, Body::wrap_stream(form.into_stream()))
+				.ok_or_else(|| Error::new("Failed to create stream for form data", ErrorKind::Normal)),
+			Self::Reader(reader) => hyper_body_to_stream(cx, Body::wrap_stream(reader.into_stream()?))
+				.ok_or_else(|| Error::new("Failed to create stream for reader", ErrorKind::Normal)),
+		}
+	}
+}
+
+type BoxedAsyncRead = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Body which is read from a native [AsyncRead] source, such as a file or the body of an upload received by a server
+/// of the embedder, without passing the chunks through scripts.
+///
+/// The source can only be read once, so clones of the body share it, and only the first clone to be read receives it.
+#[derive(Clone)]
+pub struct AsyncReadBody {
+	reader: Arc<Mutex<Option<BoxedAsyncRead>>>,
+	length: Option<usize>,
+}
+
+impl AsyncReadBody {
+	/// Size of the chunks which are read from the source.
+	const CHUNK_SIZE: usize = 16 * 1024;
+
+	fn take(&self) -> Result<BoxedAsyncRead> {
+		self.reader
+			.lock()
+			.unwrap()
+			.take()
+			.ok_or_else(|| Error::new("Body was already read", ErrorKind::Type))
+	}
+
+	fn into_stream(self) -> Result<impl Stream<Item = io::Result<Bytes>> + Send + 'static> {
+		let reader = self.take()?;
+		Ok(stream::try_unfold(reader, |mut reader| async move {
+			let mut buffer = BytesMut::with_capacity(AsyncReadBody::CHUNK_SIZE);
+			match reader.read_buf(&mut buffer).await? {
+				0 => Ok(None),
+				_ => Ok(Some((buffer.freeze(), reader))),
+			}
+		}))
+	}
+
+	async fn into_bytes(self) -> Result<Bytes> {
+		let mut bytes = Vec::with_capacity(self.length.unwrap_or_default());
+		self.take()?
+			.read_to_end(&mut bytes)
+			.await
+			.map_err(|error| Error::new(format!("Failed to read body: {}", error), ErrorKind::Normal))?;
+		Ok(Bytes::from(bytes))
+	}
+}
+
+impl Debug for AsyncReadBody {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("AsyncReadBody").field("length", &self.length).finish_non_exhaustive()
+	}
 }
 
 impl Default for FetchBodyInner {
@@ -79,12 +155,18 @@ impl Display for FetchBodyKind {
 	}
 }
 
+/// Maximum capacity which is preallocated from the length hint of a body.
+const MAX_PREALLOCATED_CAPACITY: usize = 64 * 1024 * 1024;
+
 #[derive(Debug, Traceable, Default)]
 pub struct FetchBody {
 	#[trace(no_trace)]
 	pub body: FetchBodyInner,
 	pub source: Option<Heap<JSVal>>,
 	pub kind: Option<FetchBodyKind>,
+	/// Length of a stream body, when it is known from elsewhere, such as the `Content-Length` of a response.
+	#[trace(no_trace)]
+	pub length_hint: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -95,15 +177,49 @@ pub enum FetchBodyLength {
 }
 
 impl FetchBody {
+	/// Creates an `application/octet-stream` body which is streamed from a native source as it is sent, for requests
+	/// and responses created by the embedder.
+	///
+	/// If the length of the source is known, it is sent as the `Content-Length` of requests. Otherwise, requests are
+	/// sent with chunked encoding.
+	pub fn from_async_read<R: AsyncRead + Send + 'static>(reader: R, length: Option<usize>) -> FetchBody {
+		FetchBody {
+			body: FetchBodyInner::Reader(AsyncReadBody {
+				reader: Arc::new(Mutex::new(Some(Box::pin(reader)))),
+				length,
+			}),
+			source: None,
+			kind: Some(FetchBodyKind::Blob(String::from("application/octet-stream"))),
+			length_hint: None,
+		}
+	}
+
 	pub fn is_none(&self) -> bool {
 		matches!(self.body, FetchBodyInner::None)
 	}
 
+	/// Returns the length of the body in bytes, if it is known from the body itself or from its length hint.
+	pub fn length_hint(&self) -> Option<usize> {
+		match self.len() {
+			FetchBodyLength::None => Some(0),
+			FetchBodyLength::Known(length) => Some(length),
+			FetchBodyLength::Unknown => self.length_hint,
+		}
+	}
+
+	/// Returns the capacity to preallocate when reading the body, which is limited so that an incorrect length hint
+	/// cannot cause a large allocation.
+	fn preallocated_capacity(&self) -> usize {
+		self.length_hint.unwrap_or_default().min(MAX_PREALLOCATED_CAPACITY)
+	}
+
 	pub fn len(&self) -> FetchBodyLength {
 		match &self.body {
 			FetchBodyInner::None => FetchBodyLength::None,
 			FetchBodyInner::Bytes(bytes) => FetchBodyLength::Known(bytes.len()),
 			FetchBodyInner::Stream(_) => FetchBodyLength::Unknown,
+			FetchBodyInner::Multipart(form) => FetchBodyLength::Known(form.len()),
+			FetchBodyInner::Reader(reader) => reader.length.map_or(FetchBodyLength::Unknown, FetchBodyLength::Known),
 		}
 	}
 
@@ -116,6 +232,8 @@ impl FetchBody {
 		match self.body {
 			FetchBodyInner::None => Ok((Body::empty(), None)),
 			FetchBodyInner::Bytes(bytes) => Ok((Body::from(bytes), None)),
+			FetchBodyInner::Multipart(form) => Ok((Body::wrap_stream(form.into_stream()), None)),
+			FetchBodyInner::Reader(reader) => Ok((Body::wrap_stream(reader.into_stream()?), None)),
 			FetchBodyInner::Stream(stream) => {
 				let reader = stream.into_reader(&cx)?;
 				let mut stream = Box::pin(reader.into_rust_stream(cx.duplicate()));
@@ -145,27 +263,49 @@ impl FetchBody {
 	}
 
 	pub async fn into_bytes(self, cx: Context) -> Result<Option<Bytes>> {
+		let capacity = self.preallocated_capacity();
 		match self.body {
 			FetchBodyInner::None => Ok(None),
 			FetchBodyInner::Bytes(bytes) => Ok(Some(bytes)),
+			FetchBodyInner::Multipart(form) => Ok(Some(form.into_bytes().await?)),
+			FetchBodyInner::Reader(reader) => Ok(Some(reader.into_bytes().await?)),
 			FetchBodyInner::Stream(stream) => {
 				let reader = stream.into_reader(&cx)?;
-				let (_, bytes) = cx.await_native_cx(|cx| reader.read_to_end(cx)).await;
+				let (_, bytes) = cx.await_native_cx(|cx| reader.read_to_end_with_capacity(cx, capacity)).await;
 				Ok(Some(bytes.map_err(|e| e.to_error())?.into()))
 			}
 		}
 	}
 
 	pub async fn into_text(self, cx: Context) -> Result<String> {
-		let bytes = self.into_bytes(cx).await?;
-		String::from_utf8(bytes.unwrap_or_default().into())
+		let options = LargeBodyOptions::from_context(&cx);
+		let bytes = self.into_bytes(cx).await?.unwrap_or_default();
+		if options.is_large(bytes.len()) {
+			return large_body::decode_text(bytes).await;
+		}
+		String::from_utf8(bytes.into())
 			.map_err(|e| Error::new(format!("Invalid UTF-8 sequence: {}", e), ErrorKind::Normal))
 	}
 
-	pub async fn into_json(self, cx: Context) -> ResultExc<*mut JSObject> {
-		let (cx, text) = cx.await_native_cx(|cx| self.into_text(cx)).await;
-		let text = text?;
-		Ok((*ion::json::parse(&cx, text)?).get())
+	pub async fn into_json(self, cx: Context, content_type: Option<Header>) -> ResultExc<JSVal> {
+		check_json(MimeChecking::from_context(&cx), content_type.as_ref())?;
+		let options = LargeBodyOptions::from_context(&cx);
+		let (cx, bytes) = cx.await_native_cx(|cx| self.into_bytes(cx)).await;
+		let bytes = bytes?.unwrap_or_default();
+
+		let (cx, text) = if options.is_large(bytes.len()) {
+			let (cx, json) = cx.await_native(large_body::parse_json(bytes.clone())).await;
+			if let Some(json) = json {
+				return Ok(large_body::json_to_value(cx, json, options.segment_size).await?);
+			}
+			// Invalid JSON is parsed by the engine to report the error.
+			cx.await_native(large_body::decode_text(bytes)).await
+		} else {
+			let text = String::from_utf8(bytes.into())
+				.map_err(|e| Error::new(format!("Invalid UTF-8 sequence: {}", e), ErrorKind::Normal));
+			(cx, text)
+		};
+		Ok(ObjectValue((*ion::json::parse(&cx, text?)?).get()))
 	}
 
 	pub async fn into_blob(self, cx: Context, content_type: Option<Header>) -> Result<*mut JSObject> {
@@ -182,81 +322,67 @@ impl FetchBody {
 		let (cx, bytes) = cx.await_native_cx(|cx| self.into_bytes(cx)).await;
 		let bytes = bytes?.unwrap_or_default();
 
-		let content_type = content_type.to_string();
-
-		if content_type.starts_with("application/x-www-form-urlencoded") {
-			let parsed = form_urlencoded::parse(bytes.as_ref());
-			let mut form_data = FormData::constructor();
+		let boundary = match form_format(MimeChecking::from_context(&cx), &content_type)? {
+			FormFormat::UrlEncoded => {
+				let parsed = form_urlencoded::parse(bytes.as_ref());
+				let mut form_data = FormData::constructor();
 
-			for (key, val) in parsed {
-				form_data.append_native_string(key.into_owned(), val.into_owned());
-			}
+				for (key, val) in parsed {
+					form_data.append_native_string(key.into_owned(), val.into_owned());
+				}
 
-			Ok(FormData::new_object(&cx, Box::new(form_data)))
-		} else if content_type.starts_with("multipart/form-data") {
-			const MULTIPART_HEADER_WITH_BOUNDARY: &str = "multipart/form-data; boundary=";
-			if !content_type.starts_with(MULTIPART_HEADER_WITH_BOUNDARY) {
-				return Err(Error::new(
-					"multipart/form-data content without a boundary, cannot parse",
-					ErrorKind::Normal,
-				));
+				return Ok(FormData::new_object(&cx, Box::new(form_data)));
 			}
+			FormFormat::Multipart { boundary } => boundary,
+		};
 
-			let boundary = &content_type.as_str()[MULTIPART_HEADER_WITH_BOUNDARY.len()..];
-
-			// TODO: read asynchronously from stream bodies. This is complicated by the fact
-			// that multer expects a `Send` stream, but we're running on a single-threaded
-			// runtime.
-			let mut parser = multer::Multipart::new(
-				stream::once(async { std::result::Result::<_, Infallible>::Ok(bytes) }),
-				boundary,
-			);
-
-			let mut form_data = FormData::constructor();
-			let mut cx = cx;
-
-			while let Some(next) = parser.next_field().await? {
-				let name = next.name().unwrap_or("").to_string();
-				let file_name = next.file_name().map(|f| f.to_string());
-				let content_type = next.content_type().map(|c| c.to_string());
-
-				let bytes;
-				(cx, bytes) = cx.await_native(next.bytes()).await;
-				let bytes = bytes.map_err(|_| Error::new("Failed to read form data from body", ErrorKind::Normal))?;
-
-				match file_name {
-					Some(file_name) => {
-						let file = File::constructor(
-							vec![BlobPart(bytes)],
-							file_name,
-							Opt(Some(FileOptions {
-								last_modified: None,
-								blob: BlobOptions {
-									endings: crate::globals::file::Endings::Transparent,
-									kind: content_type,
-								},
-							})),
-						);
-						let file = cx.root(File::new_object(&cx, Box::new(file))).into();
-
-						form_data.append_native_file(name, File::get_private(&cx, &file).unwrap());
-					}
-
-					None => form_data.append_native_string(
-						name,
-						String::from_utf8(bytes.into())
-							.map_err(|_| Error::new("String contains invalid UTF-8 sequence", ErrorKind::Normal))?,
-					),
+		// TODO: read asynchronously from stream bodies. This is complicated by the fact
+		// that multer expects a `Send` stream, but we're running on a single-threaded
+		// runtime.
+		let mut parser = multer::Multipart::new(
+			stream::once(async { std::result::Result::<_, Infallible>::Ok(bytes) }),
+			boundary,
+		);
+
+		let mut form_data = FormData::constructor();
+		let mut cx = cx;
+
+		while let Some(next) = parser.next_field().await? {
+			let name = next.name().unwrap_or("").to_string();
+			let file_name = next.file_name().map(|f| f.to_string());
+			let content_type = next.content_type().map(|c| c.to_string());
+
+			let bytes;
+			(cx, bytes) = cx.await_native(next.bytes()).await;
+			let bytes = bytes.map_err(|_| Error::new("Failed to read form data from body", ErrorKind::Normal))?;
+
+			match file_name {
+				Some(file_name) => {
+					let file = File::constructor(
+						vec![BlobPart(bytes)],
+						file_name,
+						Opt(Some(FileOptions {
+							last_modified: None,
+							blob: BlobOptions {
+								endings: crate::globals::file::Endings::Transparent,
+								kind: content_type,
+							},
+						})),
+					);
+					let file = cx.root(File::new_object(&cx, Box::new(file))).into();
+
+					form_data.append_native_file(name, File::get_private(&cx, &file).unwrap());
 				}
-			}
 
-			Ok(FormData::new_object(&cx, Box::new(form_data)))
-		} else {
-			Err(Error::new(
-				"Invalid content-type, cannot decide form data format",
-				ErrorKind::Type,
-			))
+				None => form_data.append_native_string(
+					name,
+					String::from_utf8(bytes.into())
+						.map_err(|_| Error::new("String contains invalid UTF-8 sequence", ErrorKind::Normal))?,
+				),
+			}
 		}
+
+		Ok(FormData::new_object(&cx, Box::new(form_data)))
 	}
 
 	pub fn try_clone(&mut self, cx: &Context) -> Result<Self> {
@@ -264,10 +390,12 @@ impl FetchBody {
 			body: self.body.try_clone(cx)?,
 			source: self.source.as_ref().map(|s| Heap::new(s.get())),
 			kind: self.kind.clone(),
+			length_hint: self.length_hint,
 		})
 	}
 
 	pub async fn try_clone_with_cached_body(&mut self, cx: Context) -> Result<Self> {
+		let capacity = self.preallocated_capacity();
 		// Can't move out of a reference. We need to instead swap the body out and then back in again.
 		let mut my_body = FetchBodyInner::None;
 		std::mem::swap(&mut self.body, &mut my_body);
@@ -275,9 +403,17 @@ impl FetchBody {
 		let (mut my_body, cloned_body) = match my_body {
 			FetchBodyInner::None => (FetchBodyInner::None, FetchBodyInner::None),
 			FetchBodyInner::Bytes(bytes) => (FetchBodyInner::Bytes(bytes.clone()), FetchBodyInner::Bytes(bytes)),
+			FetchBodyInner::Multipart(form) => {
+				(FetchBodyInner::Multipart(form.clone()), FetchBodyInner::Multipart(form))
+			}
+			FetchBodyInner::Reader(reader) => {
+				let bytes = reader.into_bytes().await?;
+				(FetchBodyInner::Bytes(bytes.clone()), FetchBodyInner::Bytes(bytes))
+			}
 			FetchBodyInner::Stream(stream) => {
 				let reader = stream.into_reader(&cx)?;
-				let bytes: Bytes = reader.read_to_end(cx).await.map_err(|e| e.to_error())?.into();
+				let bytes: Bytes =
+					reader.read_to_end_with_capacity(cx, capacity).await.map_err(|e| e.to_error())?.into();
 				(FetchBodyInner::Bytes(bytes.clone()), FetchBodyInner::Bytes(bytes))
 			}
 		};
@@ -288,6 +424,7 @@ impl FetchBody {
 			body: cloned_body,
 			source: self.source.as_ref().map(|s| Heap::new(s.get())),
 			kind: self.kind.clone(),
+			length_hint: self.length_hint,
 		})
 	}
 
@@ -309,6 +446,7 @@ impl<'cx> FromValue<'cx> for FetchBody {
 				body: FetchBodyInner::Bytes(bytes),
 				source: Some(Heap::new(value.get())),
 				kind: Some(FetchBodyKind::String),
+				length_hint: None,
 			});
 		} else if value.handle().is_object() {
 			if let Some(stream) = ReadableStream::from_local(&value.to_object(cx)) {
@@ -316,6 +454,7 @@ impl<'cx> FromValue<'cx> for FetchBody {
 					body: FetchBodyInner::Stream(stream),
 					source: Some(Heap::new(value.get())),
 					kind: None,
+					length_hint: None,
 				});
 			}
 			if let Ok(source) = BufferSource::from_value(cx, value, strict, false) {
@@ -323,41 +462,30 @@ impl<'cx> FromValue<'cx> for FetchBody {
 					body: FetchBodyInner::Bytes(source.to_bytes()),
 					source: Some(Heap::new(value.get())),
 					kind: None,
+					length_hint: None,
 				});
 			} else if let Ok(blob) = <&Blob>::from_value(cx, value, strict, ()) {
-				let bytes = blob.as_bytes().clone();
+				let body = match blob.data() {
+					BlobData::Memory(bytes) => FetchBodyInner::Bytes(bytes.clone()),
+					BlobData::Disk(source) => FetchBodyInner::Stream(
+						disk_stream(cx, source)
+							.ok_or_else(|| Error::new("Failed to create stream for file", ErrorKind::Normal))?,
+					),
+				};
 				return Ok(FetchBody {
-					body: FetchBodyInner::Bytes(bytes),
+					body,
 					source: Some(Heap::new(value.get())),
 					kind: blob.kind().map(FetchBodyKind::Blob),
+					length_hint: None,
 				});
 			} else if let Ok(form_data) = <&FormData>::from_value(cx, value, strict, ()) {
-				let mut form = multipart::Form::default();
-				for kv in form_data.all_pairs() {
-					match &kv.value {
-						FormDataEntryValue::String(str) => form.add_text(kv.key.as_str(), str),
-						FormDataEntryValue::File(file) => {
-							// TODO: remove to_vec call
-							let file = File::get_private(cx, &file.root(cx).into()).unwrap();
-							form.add_reader_file(
-								kv.key.as_str(),
-								std::io::Cursor::new(file.blob.as_bytes().to_vec()),
-								&file.name,
-							)
-						}
-					}
-				}
+				let form = MultipartForm::from_form_data(cx, form_data)?;
 				let content_type = form.content_type();
-
-				// TODO: store the form directly
-				let builder = hyper::Request::builder();
-				let req = form.set_body::<multipart::Body>(builder).unwrap();
-				let bytes = futures::executor::block_on(hyper::body::to_bytes(req.into_body())).unwrap();
-
 				return Ok(FetchBody {
-					body: FetchBodyInner::Bytes(bytes),
+					body: FetchBodyInner::Multipart(form),
 					source: Some(Heap::new(value.handle().get())),
 					kind: Some(FetchBodyKind::FormData(content_type)),
+					length_hint: None,
 				});
 			} else if let Ok(search_params) = <&URLSearchParams>::from_value(cx, value, strict, ()) {
 				let bytes = Bytes::from(
@@ -369,6 +497,7 @@ impl<'cx> FromValue<'cx> for FetchBody {
 					body: FetchBodyInner::Bytes(bytes),
 					source: Some(Heap::new(value.get())),
 					kind: Some(FetchBodyKind::URLSearchParams),
+					length_hint: None,
 				});
 			}
 		}
@@ -377,12 +506,20 @@ impl<'cx> FromValue<'cx> for FetchBody {
 }
 
 pub fn hyper_body_to_stream(cx: &Context, body: Body) -> Option<ReadableStream> {
-	let source = HyperBodyStreamSource { body };
+	hyper_body_to_stream_with_timing(cx, body, None)
+}
+
+/// Converts the body of a response to a stream, which records when it is complete in the given timing.
+pub fn hyper_body_to_stream_with_timing(
+	cx: &Context, body: Body, timing: Option<ResponseTiming>,
+) -> Option<ReadableStream> {
+	let source = HyperBodyStreamSource { body, timing };
 	crate::globals::streams::readable_stream_from_callbacks(cx, Box::new(source))
 }
 
 struct HyperBodyStreamSource {
 	body: Body,
+	timing: Option<ResponseTiming>,
 }
 
 impl NativeStreamSourceCallbacks for HyperBodyStreamSource {
@@ -424,26 +561,35 @@ impl NativeStreamSourceCallbacks for HyperBodyStreamSource {
 				let controller = ion::Object::from(controller.root(&cx));
 				match chunk {
 					None => {
+						let source = NativeStreamSource::get_private(&cx, &stream_source.to_local().into()).unwrap();
+						if let Some(timing) = &source.get_typed_source::<Self>().timing {
+							timing.mark_body_complete();
+						}
+
 						let close_func =
 							Function::from_object(&cx, &controller.get(&cx, "close")?.unwrap().to_object(&cx)).unwrap();
-						close_func.call(&cx, &controller, &[]).map_err(|e| e.unwrap().exception)?;
+						close_func.call(&cx, &controller, &[]).map_err(|e| handle::call_exception(&cx, e))?;
 						ion::ResultExc::<_>::Ok(())
 					}
 
 					Some(chunk) => {
-						let chunk = chunk
-							.map_err(|_| Error::new("Failed to read request body from network", ErrorKind::Normal))?;
-						let array_buffer = Object::from(
-							ArrayBuffer::copy_from_bytes(&cx, chunk.as_ref())
-								.ok_or_else(|| Error::new("Failed to allocate array", ErrorKind::Normal))?
-								.into_local(),
-						)
-						.as_value(&cx);
+						let chunk = chunk.map_err(|error| {
+							let error = FetchError {
+								phase: Some(FetchErrorPhase::Body),
+								..FetchError::from_hyper(&error, None)
+							};
+							error.to_exception(&cx)
+						})?;
+						let array = Uint8Array::from_bytes(&cx, chunk)
+							.ok_or_else(|| Error::new("Failed to allocate array", ErrorKind::Normal))?
+							.as_value(&cx);
 
 						let enqueue_func =
 							Function::from_object(&cx, &controller.get(&cx, "enqueue")?.unwrap().to_object(&cx))
 								.unwrap();
-						enqueue_func.call(&cx, &controller, &[array_buffer]).map_err(|e| e.unwrap().exception)?;
+						enqueue_func
+							.call(&cx, &controller, &[array])
+							.map_err(|e| handle::call_exception(&cx, e))?;
 						Ok(())
 					}
 				}
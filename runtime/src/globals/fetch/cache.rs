@@ -0,0 +1,353 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{fmt, mem, str};
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, SystemTime};
+
+use bytes::{Bytes, BytesMut};
+use chrono::DateTime;
+use futures::Stream;
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use http::header::{
+	AGE, AUTHORIZATION, CACHE_CONTROL, CONNECTION, CONTENT_LENGTH, COOKIE, DATE, EXPIRES, HeaderName, SET_COOKIE,
+	TRANSFER_ENCODING, VARY,
+};
+use hyper::Body;
+use hyper::body::HttpBody;
+use indexmap::IndexMap;
+use url::Url;
+
+/// Maximum size of a response body stored in an [HttpCache]. Larger responses are streamed without being stored.
+pub const MAX_CACHED_BODY_SIZE: usize = 8 * 1024 * 1024;
+
+/// Represents the directives of a `Cache-Control` header.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CacheControl {
+	pub no_store: bool,
+	pub no_cache: bool,
+	pub max_age: Option<u64>,
+	pub public: bool,
+	pub private: bool,
+	pub must_revalidate: bool,
+}
+
+impl CacheControl {
+	pub fn from_headers(headers: &HeaderMap) -> CacheControl {
+		let mut control = CacheControl::default();
+		for value in headers.get_all(CACHE_CONTROL) {
+			let Ok(value) = value.to_str() else {
+				continue;
+			};
+			for directive in value.split(',') {
+				let (name, argument) = match directive.split_once('=') {
+					Some((name, argument)) => (name.trim(), Some(argument.trim().trim_matches('"'))),
+					None => (directive.trim(), None),
+				};
+				match name.to_ascii_lowercase().as_str() {
+					"no-store" => control.no_store = true,
+					"no-cache" => control.no_cache = true,
+					"max-age" => control.max_age = argument.and_then(|a| a.parse().ok()),
+					"public" => control.public = true,
+					"private" => control.private = true,
+					"must-revalidate" => control.must_revalidate = true,
+					_ => {}
+				}
+			}
+		}
+		control
+	}
+}
+
+/// Represents a response stored in an [HttpCache], along with the metadata used to determine its freshness.
+#[derive(Clone, Debug)]
+pub struct CachedResponse {
+	pub status: StatusCode,
+	pub status_text: Option<String>,
+	pub headers: HeaderMap,
+	pub body: Bytes,
+	/// Values of the request headers nominated by the `Vary` header of the response.
+	pub vary: Vec<(HeaderName, Option<HeaderValue>)>,
+	pub response_time: SystemTime,
+}
+
+impl CachedResponse {
+	/// Creates a [CachedResponse], if the response can be stored for the given request.
+	pub fn new(
+		method: &Method, request_headers: &HeaderMap, status: StatusCode, status_text: Option<String>,
+		headers: &HeaderMap, body: Bytes,
+	) -> Option<CachedResponse> {
+		if !is_storable(method, request_headers, status, headers) {
+			return None;
+		}
+
+		let vary = vary_headers(headers)?
+			.into_iter()
+			.map(|name| {
+				let value = request_headers.get(&name).cloned();
+				(name, value)
+			})
+			.collect();
+
+		let mut headers = headers.clone();
+		for name in [CONNECTION, TRANSFER_ENCODING, CONTENT_LENGTH] {
+			headers.remove(name);
+		}
+		remove_cookie_headers(&mut headers);
+
+		Some(
+			CachedResponse {
+				status,
+				status_text,
+				headers,
+				body: Bytes::new(),
+				vary,
+				response_time: SystemTime::now(),
+			}
+			.with_body(body),
+		)
+	}
+
+	fn with_body(mut self, body: Bytes) -> CachedResponse {
+		self.headers.insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+		self.body = body;
+		self
+	}
+
+	/// Checks if the request headers nominated by the `Vary` header match those of the stored request.
+	pub fn matches(&self, request_headers: &HeaderMap) -> bool {
+		self.vary.iter().all(|(name, value)| request_headers.get(name) == value.as_ref())
+	}
+
+	/// Returns the freshness lifetime of the response, from `max-age` or `Expires`.
+	/// Responses without either are always revalidated.
+	pub fn freshness_lifetime(&self) -> Duration {
+		if let Some(max_age) = CacheControl::from_headers(&self.headers).max_age {
+			return Duration::from_secs(max_age);
+		}
+
+		let date = header_date(&self.headers, DATE).unwrap_or(self.response_time);
+		header_date(&self.headers, EXPIRES)
+			.and_then(|expires| expires.duration_since(date).ok())
+			.unwrap_or_default()
+	}
+
+	/// Returns the current age of the response, including the `Age` it was received with.
+	pub fn current_age(&self) -> Duration {
+		let age = self
+			.headers
+			.get(AGE)
+			.and_then(|age| age.to_str().ok())
+			.and_then(|age| age.parse().ok())
+			.map(Duration::from_secs)
+			.unwrap_or_default();
+		age + SystemTime::now().duration_since(self.response_time).unwrap_or_default()
+	}
+
+	/// Checks if the response can be used without revalidation.
+	pub fn is_fresh(&self) -> bool {
+		let control = CacheControl::from_headers(&self.headers);
+		!control.no_cache && self.freshness_lifetime() > self.current_age()
+	}
+
+	/// Updates the stored headers with those of a `304 Not Modified` response to a revalidation request.
+	pub fn refresh(&mut self, headers: &HeaderMap) {
+		for name in headers.keys() {
+			if *name == CONTENT_LENGTH || *name == TRANSFER_ENCODING || *name == CONNECTION {
+				continue;
+			}
+			self.headers.remove(name);
+			for value in headers.get_all(name) {
+				self.headers.append(name, value.clone());
+			}
+		}
+		self.headers.remove(AGE);
+		remove_cookie_headers(&mut self.headers);
+		self.response_time = SystemTime::now();
+	}
+}
+
+/// Removes the cookies set by a response, so that they are not set again by later requests which use it.
+fn remove_cookie_headers(headers: &mut HeaderMap) {
+	headers.remove(SET_COOKIE);
+	headers.remove("set-cookie2");
+}
+
+/// Storage for responses to HTTP requests, keyed by URL.
+///
+/// Each [Client](crate::globals::fetch::Client) has its own cache, which defaults to a [MemoryCache]. It can be replaced
+/// with a persistent implementation through [ClientOptions::http_cache](crate::globals::fetch::ClientOptions).
+pub trait HttpCache: Send + Sync {
+	fn get(&self, url: &Url) -> Option<CachedResponse>;
+
+	fn put(&self, url: &Url, response: CachedResponse);
+
+	fn remove(&self, url: &Url);
+}
+
+impl Debug for dyn HttpCache {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("HttpCache")
+	}
+}
+
+pub fn default_http_cache() -> Arc<dyn HttpCache> {
+	Arc::new(MemoryCache::new(MemoryCache::DEFAULT_CAPACITY))
+}
+
+/// In-memory [HttpCache] which evicts the least recently used response once it holds `capacity` responses.
+pub struct MemoryCache {
+	capacity: usize,
+	entries: Mutex<IndexMap<String, CachedResponse>>,
+}
+
+impl MemoryCache {
+	pub const DEFAULT_CAPACITY: usize = 256;
+
+	pub fn new(capacity: usize) -> MemoryCache {
+		MemoryCache {
+			capacity,
+			entries: Mutex::new(IndexMap::new()),
+		}
+	}
+}
+
+impl HttpCache for MemoryCache {
+	fn get(&self, url: &Url) -> Option<CachedResponse> {
+		let mut entries = self.entries.lock().unwrap();
+		let (key, response) = entries.shift_remove_entry(&cache_key(url))?;
+		entries.insert(key, response.clone());
+		Some(response)
+	}
+
+	fn put(&self, url: &Url, response: CachedResponse) {
+		if self.capacity == 0 {
+			return;
+		}
+		let mut entries = self.entries.lock().unwrap();
+		let key = cache_key(url);
+		entries.shift_remove(&key);
+		entries.insert(key, response);
+		while entries.len() > self.capacity {
+			entries.shift_remove_index(0);
+		}
+	}
+
+	fn remove(&self, url: &Url) {
+		self.entries.lock().unwrap().shift_remove(&cache_key(url));
+	}
+}
+
+fn cache_key(url: &Url) -> String {
+	let mut url = url.clone();
+	url.set_fragment(None);
+	String::from(url)
+}
+
+/// Checks if a response to a request can be stored, without considering its body.
+pub fn is_storable(method: &Method, request_headers: &HeaderMap, status: StatusCode, headers: &HeaderMap) -> bool {
+	if *method != Method::GET || status == StatusCode::PARTIAL_CONTENT || status.is_informational() {
+		return false;
+	}
+
+	let request_control = CacheControl::from_headers(request_headers);
+	let control = CacheControl::from_headers(headers);
+	if request_control.no_store || control.no_store {
+		return false;
+	}
+	// Responses for a single user are not stored, as the cache can be shared by the runtimes using a client
+	if control.private {
+		return false;
+	}
+	// Responses to requests with credentials are only shared with other requests if they are explicitly public
+	let credentials = request_headers.contains_key(AUTHORIZATION) || request_headers.contains_key(COOKIE);
+	if credentials && !control.public {
+		return false;
+	}
+
+	control.max_age.is_some() || headers.contains_key(EXPIRES) || control.public
+}
+
+/// Wraps the body of a response, so that it is stored in the cache along with `response` once it has been read
+/// completely. The body is still streamed to the reader as it is received.
+///
+/// Bodies larger than [MAX_CACHED_BODY_SIZE], and bodies which fail or are not read to the end, are not stored.
+pub(crate) fn store_body(cache: Arc<dyn HttpCache>, url: Url, response: CachedResponse, body: Body) -> Body {
+	if body.size_hint().lower() > MAX_CACHED_BODY_SIZE as u64 {
+		return body;
+	}
+	Body::wrap_stream(CacheWriter {
+		body,
+		cache,
+		url,
+		response: Some(response),
+		chunks: BytesMut::new(),
+	})
+}
+
+struct CacheWriter {
+	body: Body,
+	cache: Arc<dyn HttpCache>,
+	url: Url,
+	response: Option<CachedResponse>,
+	chunks: BytesMut,
+}
+
+impl Stream for CacheWriter {
+	type Item = hyper::Result<Bytes>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<hyper::Result<Bytes>>> {
+		let writer = &mut *self;
+		let chunk = ready!(Pin::new(&mut writer.body).poll_next(cx));
+		match &chunk {
+			Some(Ok(bytes)) if writer.response.is_some() => {
+				if writer.chunks.len() + bytes.len() > MAX_CACHED_BODY_SIZE {
+					writer.response = None;
+					writer.chunks = BytesMut::new();
+				} else {
+					writer.chunks.extend_from_slice(bytes);
+				}
+			}
+			Some(Ok(_)) => {}
+			Some(Err(_)) => writer.response = None,
+			None => {
+				if let Some(response) = writer.response.take() {
+					let body = mem::take(&mut writer.chunks).freeze();
+					writer.cache.put(&writer.url, response.with_body(body));
+				}
+			}
+		}
+		Poll::Ready(chunk)
+	}
+}
+
+/// Returns the request headers nominated by the `Vary` header, or [None] if the response varies on everything.
+pub fn vary_headers(headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+	let mut names = Vec::new();
+	for value in headers.get_all(VARY) {
+		let Ok(value) = value.to_str() else {
+			continue;
+		};
+		for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+			if name == "*" {
+				return None;
+			}
+			if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+				names.push(name);
+			}
+		}
+	}
+	Some(names)
+}
+
+fn header_date(headers: &HeaderMap, name: HeaderName) -> Option<SystemTime> {
+	let date = headers.get(name)?.to_str().ok()?;
+	let date = DateTime::parse_from_rfc2822(date).ok()?;
+	Some(SystemTime::from(date))
+}
@@ -0,0 +1,370 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::{Mutex, OnceLock};
+
+use bytes::Bytes;
+use http::{HeaderMap, Method, StatusCode};
+use indexmap::IndexMap;
+use mozjs::jsapi::JSObject;
+use url::Url;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Object, Promise, Result};
+use ion::class::Reflector;
+use ion::function::Opt;
+
+use crate::globals::fetch::{Headers, HeadersKind, Request, RequestInfo, Response, vary_headers};
+use crate::promise::future_to_promise;
+
+/// Request and response pair stored in a cache of the `caches` global.
+#[derive(Clone, Debug)]
+pub struct CacheEntry {
+	pub url: Url,
+	pub request_headers: HeaderMap,
+	pub status: StatusCode,
+	pub status_text: Option<String>,
+	pub response_headers: HeaderMap,
+	pub body: Bytes,
+}
+
+impl CacheEntry {
+	/// Checks if the entry was stored for the request, following the options of `Cache.match`.
+	pub fn matches(&self, method: &Method, url: &Url, headers: &HeaderMap, options: &CacheQueryOptions) -> bool {
+		if !options.ignore_method && *method != Method::GET {
+			return false;
+		}
+
+		let mut url = url.clone();
+		let mut cached_url = self.url.clone();
+		url.set_fragment(None);
+		cached_url.set_fragment(None);
+		if options.ignore_search {
+			url.set_query(None);
+			cached_url.set_query(None);
+		}
+		if url != cached_url {
+			return false;
+		}
+
+		options.ignore_vary
+			|| vary_headers(&self.response_headers)
+				.is_some_and(|names| names.iter().all(|name| headers.get(name) == self.request_headers.get(name)))
+	}
+}
+
+/// Storage for the named caches of the `caches` global, which are listed in the order they were created.
+///
+/// The default [MemoryCacheStorage] can be replaced with a persistent implementation, such as one backed by files or a
+/// key-value store, by setting [GLOBAL_CACHE_STORAGE] before fetch is defined.
+pub trait CacheStorageBackend: Send + Sync {
+	fn names(&self) -> Vec<String>;
+
+	/// Returns the entries of the cache, in the order they were stored, or [None] if it does not exist.
+	fn get(&self, name: &str) -> Option<Vec<CacheEntry>>;
+
+	/// Replaces the entries of the cache, creating it if it does not exist.
+	fn set(&self, name: &str, entries: Vec<CacheEntry>);
+
+	/// Deletes the cache. Returns `false` if it did not exist.
+	fn delete(&self, name: &str) -> bool;
+}
+
+pub static GLOBAL_CACHE_STORAGE: OnceLock<Box<dyn CacheStorageBackend>> = OnceLock::new();
+
+pub fn default_cache_storage() -> Box<dyn CacheStorageBackend> {
+	Box::<MemoryCacheStorage>::default()
+}
+
+/// In-memory [CacheStorageBackend], whose caches are lost when the process exits.
+#[derive(Default)]
+pub struct MemoryCacheStorage {
+	caches: Mutex<IndexMap<String, Vec<CacheEntry>>>,
+}
+
+impl CacheStorageBackend for MemoryCacheStorage {
+	fn names(&self) -> Vec<String> {
+		self.caches.lock().unwrap().keys().cloned().collect()
+	}
+
+	fn get(&self, name: &str) -> Option<Vec<CacheEntry>> {
+		self.caches.lock().unwrap().get(name).cloned()
+	}
+
+	fn set(&self, name: &str, entries: Vec<CacheEntry>) {
+		self.caches.lock().unwrap().insert(String::from(name), entries);
+	}
+
+	fn delete(&self, name: &str) -> bool {
+		self.caches.lock().unwrap().shift_remove(name).is_some()
+	}
+}
+
+fn backend() -> &'static dyn CacheStorageBackend {
+	GLOBAL_CACHE_STORAGE.get_or_init(default_cache_storage).as_ref()
+}
+
+#[derive(Debug, Default, FromValue)]
+pub struct CacheQueryOptions {
+	#[ion(default)]
+	pub ignore_search: bool,
+	#[ion(default)]
+	pub ignore_method: bool,
+	#[ion(default)]
+	pub ignore_vary: bool,
+}
+
+#[derive(Debug, Default, FromValue)]
+pub struct MultiCacheQueryOptions {
+	#[ion(inherit)]
+	pub query: CacheQueryOptions,
+	pub cache_name: Option<String>,
+}
+
+/// Method, URL and headers of the request that entries are matched against.
+struct Query {
+	method: Method,
+	url: Url,
+	headers: HeaderMap,
+}
+
+impl Query {
+	fn new(cx: &Context, info: RequestInfo) -> Result<Query> {
+		let request = Request::constructor(cx, info, Opt(None))?;
+		Ok(Query {
+			method: request.method.clone(),
+			url: request.url().clone(),
+			headers: request.headers(cx).clone(),
+		})
+	}
+
+	fn matches(&self, entry: &CacheEntry, options: &CacheQueryOptions) -> bool {
+		entry.matches(&self.method, &self.url, &self.headers, options)
+	}
+}
+
+fn find_entries(
+	cx: &Context, name: &str, info: Option<RequestInfo>, options: &CacheQueryOptions,
+) -> Result<Vec<CacheEntry>> {
+	let entries = backend().get(name).unwrap_or_default();
+	match info {
+		Some(info) => {
+			let query = Query::new(cx, info)?;
+			Ok(entries.into_iter().filter(|entry| query.matches(entry, options)).collect())
+		}
+		None => Ok(entries),
+	}
+}
+
+fn entry_response(cx: &Context, entry: CacheEntry) -> *mut JSObject {
+	let mut response = Response::new_from_bytes(cx, entry.body, entry.url);
+	response.status = Some(entry.status);
+	response.status_text = entry.status_text;
+	let headers = Headers {
+		reflector: Reflector::default(),
+		headers: entry.response_headers,
+		kind: HeadersKind::Immutable,
+	};
+	response.headers.set(Headers::new_object(cx, Box::new(headers)));
+	Response::new_object(cx, Box::new(response))
+}
+
+fn entry_request(cx: &Context, entry: CacheEntry) -> Result<*mut JSObject> {
+	let request = Request::constructor(cx, RequestInfo::String(String::from(entry.url)), Opt(None))?;
+	let headers = Object::from(request.headers.to_local());
+	Headers::get_mut_private(cx, &headers)?.headers = entry.request_headers;
+	Ok(Request::new_object(cx, Box::new(request)))
+}
+
+/// Named cache of request and response pairs, opened with `caches.open`.
+#[js_class]
+pub struct Cache {
+	reflector: Reflector,
+	name: String,
+}
+
+impl Cache {
+	fn put_entry(&self, cx: &Context, request: RequestInfo, response: &Object) -> Result<Promise> {
+		let query = Query::new(cx, request)?;
+		if !matches!(query.url.scheme(), "http" | "https") {
+			return Err(Error::new(
+				"Cache.put only accepts http and https URLs",
+				ErrorKind::Type,
+			));
+		}
+		if query.method != Method::GET {
+			return Err(Error::new("Cache.put only accepts GET requests", ErrorKind::Type));
+		}
+
+		let response = Response::get_mut_private(cx, response)?;
+		if response.kind().is_error() {
+			return Err(Error::new("Cache.put does not accept network errors", ErrorKind::Type));
+		}
+		let status = StatusCode::from_u16(response.get_status()).unwrap_or(StatusCode::OK);
+		if status == StatusCode::PARTIAL_CONTENT {
+			return Err(Error::new(
+				"Cache.put does not accept partial responses",
+				ErrorKind::Type,
+			));
+		}
+		let response_headers = response.headers(cx).clone();
+		if vary_headers(&response_headers).is_none() {
+			return Err(Error::new(
+				"Cache.put does not accept responses which vary on all headers",
+				ErrorKind::Type,
+			));
+		}
+		let status_text = response.status_text.clone();
+		let body = response.take_body()?;
+
+		let name = self.name.clone();
+		let promise = unsafe {
+			future_to_promise(cx, move |cx| async move {
+				let (_, body) = cx.await_native_cx(|cx| body.into_bytes(cx)).await;
+				let mut url = query.url.clone();
+				url.set_fragment(None);
+				let entry = CacheEntry {
+					url,
+					request_headers: query.headers.clone(),
+					status,
+					status_text,
+					response_headers,
+					body: body?.unwrap_or_default(),
+				};
+
+				let backend = backend();
+				let mut entries = backend.get(&name).unwrap_or_default();
+				entries.retain(|stored| !query.matches(stored, &CacheQueryOptions::default()));
+				entries.push(entry);
+				backend.set(&name, entries);
+				Ok::<_, Error>(())
+			})
+		};
+		promise.ok_or_else(|| Error::new("Future queue is not running", ErrorKind::Normal))
+	}
+}
+
+#[js_class]
+impl Cache {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Cache> {
+		Err(Error::new("Cache has no constructor.", ErrorKind::Type))
+	}
+
+	/// Resolves with the first response stored for the request, or `undefined` if there is none.
+	#[ion(name = "match")]
+	pub fn match_request(&self, cx: &Context, request: RequestInfo, Opt(options): Opt<CacheQueryOptions>) -> Promise {
+		let options = options.unwrap_or_default();
+		let response = find_entries(cx, &self.name, Some(request), &options)
+			.map(|entries| entries.into_iter().next().map(|entry| entry_response(cx, entry)));
+		Promise::from_result(cx, response)
+	}
+
+	#[ion(name = "matchAll")]
+	pub fn match_all(
+		&self, cx: &Context, Opt(request): Opt<RequestInfo>, Opt(options): Opt<CacheQueryOptions>,
+	) -> Promise {
+		let options = options.unwrap_or_default();
+		let responses = find_entries(cx, &self.name, request, &options)
+			.map(|entries| entries.into_iter().map(|entry| entry_response(cx, entry)).collect::<Vec<_>>());
+		Promise::from_result(cx, responses)
+	}
+
+	/// Stores the response for the request, replacing the responses already stored for it.
+	/// Resolves once the body of the response has been read.
+	pub fn put(&self, cx: &Context, request: RequestInfo, response: Object) -> Promise {
+		self.put_entry(cx, request, &response)
+			.unwrap_or_else(|error| Promise::rejected(cx, error))
+	}
+
+	/// Deletes the responses stored for the request. Resolves with `false` if there were none.
+	pub fn delete(&self, cx: &Context, request: RequestInfo, Opt(options): Opt<CacheQueryOptions>) -> Promise {
+		let options = options.unwrap_or_default();
+		let deleted = Query::new(cx, request).map(|query| {
+			let backend = backend();
+			let mut entries = backend.get(&self.name).unwrap_or_default();
+			let length = entries.len();
+			entries.retain(|entry| !query.matches(entry, &options));
+			let deleted = entries.len() != length;
+			backend.set(&self.name, entries);
+			deleted
+		});
+		Promise::from_result(cx, deleted)
+	}
+
+	/// Resolves with the requests which responses are stored for, in the order they were stored.
+	pub fn keys(&self, cx: &Context, Opt(request): Opt<RequestInfo>, Opt(options): Opt<CacheQueryOptions>) -> Promise {
+		let options = options.unwrap_or_default();
+		let requests = find_entries(cx, &self.name, request, &options)
+			.and_then(|entries| entries.into_iter().map(|entry| entry_request(cx, entry)).collect::<Result<Vec<_>>>());
+		Promise::from_result(cx, requests)
+	}
+}
+
+/// Provides the named caches of the `caches` global, which are stored by the [CacheStorageBackend] in
+/// [GLOBAL_CACHE_STORAGE].
+#[js_class]
+pub struct CacheStorage {
+	reflector: Reflector,
+}
+
+#[js_class]
+impl CacheStorage {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<CacheStorage> {
+		Err(Error::new("CacheStorage has no constructor.", ErrorKind::Type))
+	}
+
+	/// Resolves with the cache with the given name, which is created if it does not exist.
+	pub fn open(&self, cx: &Context, name: String) -> Promise {
+		let backend = backend();
+		if backend.get(&name).is_none() {
+			backend.set(&name, Vec::new());
+		}
+		let cache = Cache { reflector: Reflector::default(), name };
+		Promise::resolved(cx, Cache::new_object(cx, Box::new(cache)))
+	}
+
+	pub fn has(&self, cx: &Context, name: String) -> Promise {
+		Promise::resolved(cx, backend().get(&name).is_some())
+	}
+
+	pub fn delete(&self, cx: &Context, name: String) -> Promise {
+		Promise::resolved(cx, backend().delete(&name))
+	}
+
+	pub fn keys(&self, cx: &Context) -> Promise {
+		Promise::resolved(cx, backend().names())
+	}
+
+	/// Resolves with the first response stored for the request in any cache, or in the cache named by `cacheName`.
+	#[ion(name = "match")]
+	pub fn match_request(
+		&self, cx: &Context, request: RequestInfo, Opt(options): Opt<MultiCacheQueryOptions>,
+	) -> Promise {
+		let options = options.unwrap_or_default();
+		let names = match options.cache_name {
+			Some(name) => vec![name],
+			None => backend().names(),
+		};
+		let response = Query::new(cx, request).map(|query| {
+			names.iter().find_map(|name| {
+				let entries = backend().get(name)?;
+				let entry = entries.into_iter().find(|entry| query.matches(entry, &options.query))?;
+				Some(entry_response(cx, entry))
+			})
+		});
+		Promise::from_result(cx, response)
+	}
+}
+
+pub(crate) fn define(cx: &Context, global: &Object) -> bool {
+	let _ = GLOBAL_CACHE_STORAGE.set(default_cache_storage());
+	if !(Cache::init_class(cx, global).0 && CacheStorage::init_class(cx, global).0) {
+		return false;
+	}
+	let caches = CacheStorage::new_object(cx, Box::new(CacheStorage { reflector: Reflector::default() }));
+	global.set_as(cx, "caches", &caches)
+}
@@ -4,30 +4,217 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::sync::OnceLock;
-use std::time::Duration;
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
-use hyper::client::HttpConnector;
+use http::{HeaderValue, Uri};
+use hyper::{Body, Request, Response};
+use hyper::client::Builder;
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use rustls::ClientConfig;
+use url::Url;
 
-pub type Client = hyper::Client<HttpsConnector<HttpConnector>>;
+use crate::globals::fetch::cache::{default_http_cache, HttpCache};
+use crate::globals::fetch::connection::InstrumentedConnector;
+use crate::globals::fetch::cookie::CookieJar;
+use crate::globals::fetch::error::FetchError;
+use crate::globals::fetch::limit::{OriginLimiter, OriginLimits, OriginPermit};
+use crate::globals::fetch::proxy::{ProxyConfig, ProxyConnector};
+use crate::globals::fetch::timeout::{ConnectProgress, FetchTimeout, FetchTimeouts, timeout_after};
+use crate::globals::fetch::tls::TlsOptions;
 
+pub type HyperClient = hyper::Client<InstrumentedConnector>;
+
+/// HTTP client used by fetch, along with the [CookieJar] that requests with credentials read and write.
+#[derive(Clone, Debug)]
+pub struct Client {
+	client: HyperClient,
+	cookie_jar: Arc<CookieJar>,
+	proxy: Arc<ProxyConfig>,
+	tls: Arc<ClientConfig>,
+	timeouts: FetchTimeouts,
+	limiter: Arc<OriginLimiter>,
+	http_cache: Arc<dyn HttpCache>,
+	options: Arc<ClientOptions>,
+}
+
+impl Client {
+	/// Creates a [Client] from a hyper client, where `proxy` must be the configuration used by the connector of `client`.
+	/// The remaining options are the defaults of [ClientOptions].
+	#[deprecated(note = "use `client_with_options` instead")]
+	pub fn new(client: HyperClient, cookie_jar: Arc<CookieJar>, proxy: Arc<ProxyConfig>) -> Client {
+		let defaults = client_with_options(&ClientOptions::default()).unwrap();
+		Client { client, cookie_jar, proxy, ..defaults }
+	}
+
+	/// Returns a client which uses the same connection pool, but the given [CookieJar].
+	pub fn with_cookie_jar(&self, cookie_jar: Arc<CookieJar>) -> Client {
+		Client { cookie_jar, ..self.clone() }
+	}
+
+	/// Returns the [CookieJar] of the client, which embedders can use to inspect or seed cookies.
+	pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+		&self.cookie_jar
+	}
+
+	/// Returns the [HttpCache] which stores the responses to requests sent with the client.
+	pub fn http_cache(&self) -> &Arc<dyn HttpCache> {
+		&self.http_cache
+	}
+
+	pub fn proxy(&self) -> &ProxyConfig {
+		&self.proxy
+	}
+
+	/// Returns the `Proxy-Authorization` header for a request to the given URI, if it is forwarded through an HTTP
+	/// proxy with credentials. Tunnelled requests are authorised when the tunnel is established instead.
+	pub fn proxy_authorization(&self, uri: &Uri) -> Option<HeaderValue> {
+		if uri.scheme() == Some(&http::uri::Scheme::HTTPS) {
+			return None;
+		}
+		self.proxy.proxy_for(uri)?.authorization()
+	}
+
+	pub fn timeouts(&self) -> &FetchTimeouts {
+		&self.timeouts
+	}
+
+	pub fn origin_limits(&self) -> OriginLimits {
+		self.limiter.limits()
+	}
+
+	/// Waits until a request to the origin of `url` can be sent without exceeding the [OriginLimits] of the client.
+	/// Returns [Err] if the queue timeout elapses first.
+	pub async fn acquire(&self, url: &Url) -> Result<OriginPermit, FetchError> {
+		self.limiter.acquire(url).await
+	}
+
+	/// Sends a request, applying the connect, TLS and first byte timeouts of the client.
+	/// Errors are classified by the phase they occurred in, with `url` as the URL of the request.
+	pub async fn send(&self, request: Request<Body>, url: Option<Url>) -> Result<Response<Body>, FetchError> {
+		let progress = Arc::new(ConnectProgress::default());
+		let sent = Instant::now();
+		let response = timeout_after(
+			progress.scope(self.client.request(request)),
+			|| progress.first_byte_start(sent),
+			self.timeouts.first_byte,
+			|| FetchTimeout::FirstByte,
+		);
+		match response.await {
+			Ok(Ok(response)) => Ok(response),
+			Ok(Err(error)) => Err(FetchError::from_hyper(&error, url)),
+			Err(timeout) => Err(FetchError::from_timeout(timeout, url)),
+		}
+	}
+}
+
+impl Deref for Client {
+	type Target = HyperClient;
+
+	fn deref(&self) -> &HyperClient {
+		&self.client
+	}
+}
+
+/// Client used by runtimes which were not given one with [client](crate::RuntimeBuilder::client) or
+/// [client_options](crate::RuntimeBuilder::client_options). If it is not set, each runtime creates a [default_client].
 pub static GLOBAL_CLIENT: OnceLock<Client> = OnceLock::new();
 
+/// Configures the connections made by a [Client].
+#[derive(Clone, Debug)]
+pub struct ClientOptions {
+	/// Only use HTTP/2, including for plaintext connections. Otherwise, HTTP/2 is negotiated with ALPN over TLS.
+	pub http2_only: bool,
+	/// Duration after which idle connections are closed, or [None] to keep them open.
+	pub pool_idle_timeout: Option<Duration>,
+	pub max_idle_per_host: usize,
+	/// Proxies which requests are sent through. Defaults to the configuration from the environment.
+	pub proxy: ProxyConfig,
+	pub tls: TlsOptions,
+	pub timeouts: FetchTimeouts,
+	pub origin_limits: OriginLimits,
+	/// Storage for responses, or [None] to store them in a new [MemoryCache](crate::globals::fetch::MemoryCache).
+	/// Clients only share responses if they are given the same cache.
+	pub http_cache: Option<Arc<dyn HttpCache>>,
+}
+
+impl Default for ClientOptions {
+	fn default() -> ClientOptions {
+		ClientOptions {
+			http2_only: false,
+			pool_idle_timeout: Some(Duration::from_secs(60)),
+			max_idle_per_host: usize::MAX,
+			proxy: ProxyConfig::from_env(),
+			tls: TlsOptions::default(),
+			timeouts: FetchTimeouts::default(),
+			origin_limits: OriginLimits::default(),
+			http_cache: None,
+		}
+	}
+}
+
 pub fn default_client() -> Client {
-	let https = HttpsConnectorBuilder::new()
-		.with_provider_and_webpki_roots(rustls::crypto::ring::default_provider())
-		.unwrap()
-		.https_or_http()
-		.enable_http1()
-		.build();
+	client_with_options(&ClientOptions::default()).unwrap()
+}
+
+/// Creates a client with the given options.
+/// Returns [Err] if the TLS configuration is invalid.
+pub fn client_with_options(options: &ClientOptions) -> Result<Client, rustls::Error> {
+	let mut client = client_builder(options);
+	client.http2_only(options.http2_only);
+	let proxy = Arc::new(options.proxy.clone());
+	let tls = Arc::new(options.tls.client_config()?);
+	let connector = https_connector(&proxy, &tls, options.timeouts, !options.http2_only, true);
+	Ok(Client {
+		client: client.build(connector),
+		cookie_jar: Arc::default(),
+		proxy,
+		tls,
+		timeouts: options.timeouts,
+		limiter: Arc::new(OriginLimiter::new(options.origin_limits)),
+		http_cache: options.http_cache.clone().unwrap_or_else(default_http_cache),
+		options: Arc::new(options.clone()),
+	})
+}
+
+/// Creates a client which writes header names in Title-Case, and preserves the casing of received headers.
+/// The proxy, TLS configuration, pool settings and cookies of the given client are shared.
+///
+/// Header names are normalised to lowercase when stored, so the original casing supplied by the script cannot be
+/// recovered, but this matches what most case-sensitive servers expect. HTTP/2 is not used, as its header names are
+/// always lowercase.
+pub fn raw_header_case_client(base: &Client) -> Client {
+	let mut client = client_builder(&base.options);
+	client.http1_title_case_headers(true);
+	client.http1_preserve_header_case(true);
+	let connector = https_connector(&base.proxy, &base.tls, base.timeouts, true, false);
+	Client {
+		client: client.build(connector),
+		..base.clone()
+	}
+}
+
+fn https_connector(
+	proxy: &Arc<ProxyConfig>, tls: &ClientConfig, timeouts: FetchTimeouts, http1: bool, http2: bool,
+) -> InstrumentedConnector {
+	let connector = ProxyConnector::new(Arc::clone(proxy), timeouts.connect);
+	let builder = HttpsConnectorBuilder::new().with_tls_config(tls.clone()).https_or_http();
+	let https: HttpsConnector<_> = match (http1, http2) {
+		(true, true) => builder.enable_http1().enable_http2().wrap_connector(connector),
+		(false, true) => builder.enable_http2().wrap_connector(connector),
+		_ => builder.enable_http1().wrap_connector(connector),
+	};
+	InstrumentedConnector::new(https, timeouts.tls)
+}
 
+fn client_builder(options: &ClientOptions) -> Builder {
 	let mut client = hyper::Client::builder();
 
-	client.pool_idle_timeout(Duration::from_secs(60));
-	client.pool_max_idle_per_host(usize::MAX);
+	client.pool_idle_timeout(options.pool_idle_timeout);
+	client.pool_max_idle_per_host(options.max_idle_per_host);
 	client.retry_canceled_requests(true);
 	client.set_host(false);
 
-	client.build(https)
+	client
 }
@@ -0,0 +1,215 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use http::{Uri, Version};
+use hyper::client::connect::{Connected, Connection};
+use hyper::service::Service;
+use hyper_rustls::{HttpsConnector, MaybeHttpsStream};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use ion::Object;
+
+use crate::globals::fetch::proxy::{ProxyConnector, ProxyStream};
+use crate::globals::fetch::timeout::{ConnectProgress, FetchTimeout, timeout_after};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Metadata of the connection a response was received on, captured when the connection was established.
+///
+/// Connections are pooled, so the durations describe when the connection was first established, which may have been
+/// for an earlier request.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionInfo {
+	pub remote_address: Option<SocketAddr>,
+	pub local_address: Option<SocketAddr>,
+	/// HTTP version of the response. This is only set once a response has been received.
+	pub version: Option<Version>,
+	pub tls_version: Option<&'static str>,
+	pub cipher_suite: Option<&'static str>,
+	/// Whether the connection is through a proxy, in which case the remote address is that of the proxy.
+	pub proxied: bool,
+	pub dns_duration: Option<Duration>,
+	/// Duration taken to establish the connection, including the DNS lookup and any proxy handshake.
+	pub connect_duration: Option<Duration>,
+	pub tls_duration: Option<Duration>,
+}
+
+impl ConnectionInfo {
+	fn new(stream: &MaybeHttpsStream<ProxyStream>, tls_duration: Duration) -> ConnectionInfo {
+		let (stream, tls) = match stream {
+			MaybeHttpsStream::Http(stream) => (stream, None),
+			MaybeHttpsStream::Https(stream) => {
+				let (stream, connection) = stream.get_ref();
+				(stream, Some(connection))
+			}
+		};
+		ConnectionInfo {
+			remote_address: stream.remote_address(),
+			local_address: stream.local_address(),
+			version: None,
+			tls_version: tls.and_then(|tls| tls.protocol_version()).and_then(|version| version.as_str()),
+			cipher_suite: tls.and_then(|tls| tls.negotiated_cipher_suite()).and_then(|suite| suite.suite().as_str()),
+			proxied: stream.is_proxied(),
+			dns_duration: stream.dns_duration(),
+			connect_duration: Some(stream.connect_duration()),
+			tls_duration: tls.map(|_| tls_duration),
+		}
+	}
+
+	/// Returns the protocol of the response, as an ALPN protocol identifier.
+	pub fn protocol(&self) -> Option<&'static str> {
+		match self.version? {
+			Version::HTTP_09 => Some("http/0.9"),
+			Version::HTTP_10 => Some("http/1.0"),
+			Version::HTTP_11 => Some("http/1.1"),
+			Version::HTTP_2 => Some("h2"),
+			Version::HTTP_3 => Some("h3"),
+			_ => None,
+		}
+	}
+
+	/// Creates the object returned by `response.connection`, with durations in milliseconds.
+	/// `ttfb` is the duration from the start of the fetch until the response headers were received.
+	pub fn to_object<'cx>(&self, cx: &'cx ion::Context, ttfb: Option<f64>) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(
+			cx,
+			"remoteAddress",
+			&self.remote_address.map(|address| address.ip().to_string()),
+		);
+		object.set_as(
+			cx,
+			"remotePort",
+			&self.remote_address.map(|address| u32::from(address.port())),
+		);
+		object.set_as(
+			cx,
+			"localAddress",
+			&self.local_address.map(|address| address.ip().to_string()),
+		);
+		object.set_as(
+			cx,
+			"localPort",
+			&self.local_address.map(|address| u32::from(address.port())),
+		);
+		object.set_as(cx, "protocol", &self.protocol());
+		object.set_as(cx, "tlsVersion", &self.tls_version);
+		object.set_as(cx, "cipherSuite", &self.cipher_suite);
+		object.set_as(cx, "proxied", &self.proxied);
+
+		let timing = Object::new(cx);
+		timing.set_as(cx, "dns", &self.dns_duration.map(milliseconds));
+		timing.set_as(cx, "connect", &self.connect_duration.map(milliseconds));
+		timing.set_as(cx, "tls", &self.tls_duration.map(milliseconds));
+		timing.set_as(cx, "ttfb", &ttfb);
+		object.set_as(cx, "timing", &timing);
+		object
+	}
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+	duration.as_secs_f64() * 1000.0
+}
+
+/// Connector which records the [ConnectionInfo] of each connection, which hyper adds to the extensions of responses.
+#[derive(Clone, Debug)]
+pub struct InstrumentedConnector {
+	https: HttpsConnector<ProxyConnector>,
+	tls_timeout: Option<Duration>,
+}
+
+impl InstrumentedConnector {
+	/// Creates a connector which fails with [FetchTimeout::Tls] if the TLS handshake takes longer than `tls_timeout`.
+	pub fn new(https: HttpsConnector<ProxyConnector>, tls_timeout: Option<Duration>) -> InstrumentedConnector {
+		InstrumentedConnector { https, tls_timeout }
+	}
+}
+
+impl Service<Uri> for InstrumentedConnector {
+	type Response = InstrumentedStream;
+	type Error = BoxError;
+	type Future = Pin<Box<dyn Future<Output = Result<InstrumentedStream, BoxError>> + Send>>;
+
+	fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), BoxError>> {
+		self.https.poll_ready(cx)
+	}
+
+	fn call(&mut self, destination: Uri) -> Self::Future {
+		let progress = ConnectProgress::current();
+		let connecting = progress.sync_scope(|| self.https.call(destination));
+		let tls_timeout = self.tls_timeout;
+		Box::pin(async move {
+			let start = Instant::now();
+			// The TLS handshake starts once the underlying connection is established.
+			let connecting = timeout_after(connecting, || progress.connected(), tls_timeout, || FetchTimeout::Tls);
+			let stream = connecting.await??;
+			let elapsed = start.elapsed();
+			progress.mark_established();
+
+			// The TLS handshake is the time spent connecting beyond establishing the underlying connection.
+			let connect_duration = match &stream {
+				MaybeHttpsStream::Http(stream) => stream.connect_duration(),
+				MaybeHttpsStream::Https(stream) => stream.get_ref().0.connect_duration(),
+			};
+			let info = ConnectionInfo::new(&stream, elapsed.saturating_sub(connect_duration));
+			Ok(InstrumentedStream { stream, info })
+		})
+	}
+}
+
+/// Connection which provides its [ConnectionInfo] to hyper.
+#[derive(Debug)]
+pub struct InstrumentedStream {
+	stream: MaybeHttpsStream<ProxyStream>,
+	info: ConnectionInfo,
+}
+
+impl InstrumentedStream {
+	pub fn info(&self) -> &ConnectionInfo {
+		&self.info
+	}
+}
+
+impl Connection for InstrumentedStream {
+	fn connected(&self) -> Connected {
+		self.stream.connected().extra(self.info.clone())
+	}
+}
+
+impl AsyncRead for InstrumentedStream {
+	fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+	}
+}
+
+impl AsyncWrite for InstrumentedStream {
+	fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+		Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+	}
+
+	fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+	}
+
+	fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[io::IoSlice]) -> Poll<io::Result<usize>> {
+		Pin::new(&mut self.get_mut().stream).poll_write_vectored(cx, bufs)
+	}
+
+	fn is_write_vectored(&self) -> bool {
+		self.stream.is_write_vectored()
+	}
+}
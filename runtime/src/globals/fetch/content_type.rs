@@ -0,0 +1,132 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mime::{APPLICATION, BOUNDARY, FORM_DATA, JSON, Mime, MULTIPART, TEXT, WWW_FORM_URLENCODED};
+
+use ion::{Context, Error, ErrorKind, Result};
+
+use crate::ContextExt;
+use crate::globals::fetch::header::Header;
+
+/// Configures how strictly `json()` and `formData()` of requests and responses check the `Content-Type` of bodies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MimeChecking {
+	/// `json()` parses bodies regardless of their `Content-Type`, and `formData()` only checks its prefix.
+	#[default]
+	Lenient,
+	/// `json()` rejects bodies without a JSON `Content-Type`, and `formData()` rejects malformed `Content-Type`s and
+	/// boundaries.
+	Strict,
+}
+
+impl MimeChecking {
+	pub(crate) fn from_context(cx: &Context) -> MimeChecking {
+		unsafe { cx.get_private().mime_checking }
+	}
+}
+
+/// Format of a form data body, as decided by its `Content-Type`.
+pub(crate) enum FormFormat {
+	UrlEncoded,
+	Multipart { boundary: String },
+}
+
+/// Returns `true` for `application/json`, `text/json` and types with the `+json` suffix.
+fn is_json(mime: &Mime) -> bool {
+	((mime.type_() == APPLICATION || mime.type_() == TEXT) && mime.subtype() == JSON) || mime.suffix() == Some(JSON)
+}
+
+/// Checks that the body of `json()` can be parsed as JSON according to its `Content-Type`.
+pub(crate) fn check_json(checking: MimeChecking, content_type: Option<&Header>) -> Result<()> {
+	if checking == MimeChecking::Lenient {
+		return Ok(());
+	}
+
+	let Some(content_type) = content_type else {
+		return Err(Error::new(
+			"No content-type header, expected a JSON MIME type",
+			ErrorKind::Type,
+		));
+	};
+	let content_type = content_type.to_string();
+	match content_type.parse::<Mime>() {
+		Ok(mime) if is_json(&mime) => Ok(()),
+		_ => Err(Error::new(
+			format!("Expected a JSON MIME type, but content-type is {}", content_type),
+			ErrorKind::Type,
+		)),
+	}
+}
+
+/// Checks that a boundary only contains the characters allowed by RFC 2046, and is at most 70 characters long.
+fn is_valid_boundary(boundary: &str) -> bool {
+	const SPECIALS: &[u8] = b"'()+_,-./:=? ";
+
+	(1..=70).contains(&boundary.len())
+		&& !boundary.ends_with(' ')
+		&& boundary.bytes().all(|b| b.is_ascii_alphanumeric() || SPECIALS.contains(&b))
+}
+
+pub(crate) fn form_format(checking: MimeChecking, content_type: &Header) -> Result<FormFormat> {
+	let content_type = content_type.to_string();
+	match checking {
+		MimeChecking::Lenient => lenient_form_format(&content_type),
+		MimeChecking::Strict => strict_form_format(&content_type),
+	}
+}
+
+fn lenient_form_format(content_type: &str) -> Result<FormFormat> {
+	const MULTIPART_HEADER_WITH_BOUNDARY: &str = "multipart/form-data; boundary=";
+
+	if content_type.starts_with("application/x-www-form-urlencoded") {
+		Ok(FormFormat::UrlEncoded)
+	} else if content_type.starts_with("multipart/form-data") {
+		match content_type.strip_prefix(MULTIPART_HEADER_WITH_BOUNDARY) {
+			Some(boundary) => Ok(FormFormat::Multipart { boundary: String::from(boundary) }),
+			None => Err(Error::new(
+				"multipart/form-data content without a boundary, cannot parse",
+				ErrorKind::Normal,
+			)),
+		}
+	} else {
+		Err(Error::new(
+			"Invalid content-type, cannot decide form data format",
+			ErrorKind::Type,
+		))
+	}
+}
+
+fn strict_form_format(content_type: &str) -> Result<FormFormat> {
+	let Ok(mime) = content_type.parse::<Mime>() else {
+		return Err(Error::new(
+			format!("Invalid content-type {}, cannot decide form data format", content_type),
+			ErrorKind::Type,
+		));
+	};
+
+	if mime.type_() == APPLICATION && mime.subtype() == WWW_FORM_URLENCODED {
+		Ok(FormFormat::UrlEncoded)
+	} else if mime.type_() == MULTIPART && mime.subtype() == FORM_DATA {
+		match mime.get_param(BOUNDARY) {
+			Some(boundary) if is_valid_boundary(boundary.as_str()) => Ok(FormFormat::Multipart {
+				boundary: String::from(boundary.as_str()),
+			}),
+			Some(boundary) => Err(Error::new(
+				format!("Invalid multipart/form-data boundary: {}", boundary),
+				ErrorKind::Type,
+			)),
+			None => Err(Error::new(
+				"multipart/form-data content without a boundary, cannot parse",
+				ErrorKind::Type,
+			)),
+		}
+	} else {
+		Err(Error::new(
+			format!("Expected a form data MIME type, but content-type is {}", content_type),
+			ErrorKind::Type,
+		))
+	}
+}
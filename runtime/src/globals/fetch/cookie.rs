@@ -0,0 +1,212 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use chrono::DateTime;
+use url::Url;
+
+/// Represents a cookie stored in a [CookieJar].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cookie {
+	pub name: String,
+	pub value: String,
+	pub domain: String,
+	/// Whether the cookie is only sent to the host which set it, rather than also to its subdomains.
+	pub host_only: bool,
+	pub path: String,
+	pub expires: Option<SystemTime>,
+	pub secure: bool,
+	pub http_only: bool,
+}
+
+impl Cookie {
+	/// Parses the value of a `Set-Cookie` header received from the given URL, following RFC 6265.
+	pub fn parse(url: &Url, header: &str) -> Option<Cookie> {
+		let host = url.host_str()?.to_ascii_lowercase();
+		let mut attributes = header.split(';');
+		let (name, value) = attributes.next()?.split_once('=')?;
+		let (name, value) = (name.trim(), value.trim());
+		if name.is_empty() {
+			return None;
+		}
+
+		let mut cookie = Cookie {
+			name: String::from(name),
+			value: String::from(value),
+			domain: host.clone(),
+			host_only: true,
+			path: default_path(url),
+			expires: None,
+			secure: false,
+			http_only: false,
+		};
+
+		let mut max_age = None;
+		for attribute in attributes {
+			let (key, value) = match attribute.split_once('=') {
+				Some((key, value)) => (key.trim(), value.trim()),
+				None => (attribute.trim(), ""),
+			};
+			match key.to_ascii_lowercase().as_str() {
+				"expires" => {
+					if let Ok(expires) = DateTime::parse_from_rfc2822(value) {
+						cookie.expires = Some(SystemTime::from(expires));
+					}
+				}
+				"max-age" => {
+					if let Ok(seconds) = value.parse::<i64>() {
+						max_age = Some(seconds);
+					}
+				}
+				"domain" => {
+					let domain = value.trim_start_matches('.').to_ascii_lowercase();
+					if !domain.is_empty() {
+						if !domain_matches(&host, &domain) {
+							return None;
+						}
+						cookie.domain = domain;
+						cookie.host_only = false;
+					}
+				}
+				"path" if value.starts_with('/') => cookie.path = String::from(value),
+				"secure" => cookie.secure = true,
+				"httponly" => cookie.http_only = true,
+				_ => {}
+			}
+		}
+
+		if let Some(seconds) = max_age {
+			cookie.expires = Some(if seconds <= 0 {
+				SystemTime::UNIX_EPOCH
+			} else {
+				SystemTime::now() + Duration::from_secs(seconds as u64)
+			});
+		}
+
+		if cookie.secure && url.scheme() != "https" {
+			return None;
+		}
+		Some(cookie)
+	}
+
+	pub fn is_expired(&self, now: SystemTime) -> bool {
+		self.expires.map(|expires| expires <= now).unwrap_or_default()
+	}
+
+	/// Checks if the cookie should be sent with a request to the given URL.
+	pub fn matches(&self, url: &Url) -> bool {
+		let Some(host) = url.host_str() else {
+			return false;
+		};
+		let host = host.to_ascii_lowercase();
+		let domain_matches = if self.host_only {
+			host == self.domain
+		} else {
+			domain_matches(&host, &self.domain)
+		};
+		domain_matches && path_matches(url.path(), &self.path) && (!self.secure || url.scheme() == "https")
+	}
+}
+
+/// Stores cookies received from `Set-Cookie` headers, and provides the `Cookie` header for subsequent requests.
+///
+/// Domains are not checked against the public suffix list, so embedders which fetch from untrusted origins should
+/// seed and inspect the jar themselves.
+#[derive(Debug, Default)]
+pub struct CookieJar {
+	cookies: Mutex<Vec<Cookie>>,
+}
+
+impl CookieJar {
+	pub fn new() -> CookieJar {
+		CookieJar::default()
+	}
+
+	/// Stores a cookie, replacing any with the same name, domain and path. Expired cookies are removed instead.
+	pub fn insert(&self, cookie: Cookie) {
+		let mut cookies = self.cookies.lock().unwrap();
+		cookies.retain(|c| !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path));
+		if !cookie.is_expired(SystemTime::now()) {
+			cookies.push(cookie);
+		}
+	}
+
+	/// Parses and stores the value of a `Set-Cookie` header received from the given URL.
+	/// Returns `false` if the header is invalid or not allowed to be set by the URL.
+	pub fn set_cookie(&self, url: &Url, header: &str) -> bool {
+		match Cookie::parse(url, header) {
+			Some(cookie) => {
+				self.insert(cookie);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Returns the cookies which should be sent with a request to the given URL, with the most specific paths first.
+	pub fn cookies(&self, url: &Url) -> Vec<Cookie> {
+		let now = SystemTime::now();
+		let mut cookies = self.cookies.lock().unwrap();
+		cookies.retain(|cookie| !cookie.is_expired(now));
+
+		let mut matching: Vec<_> = cookies.iter().filter(|cookie| cookie.matches(url)).cloned().collect();
+		matching.sort_by(|a, b| b.path.len().cmp(&a.path.len()));
+		matching
+	}
+
+	/// Returns the value of the `Cookie` header for a request to the given URL, if any cookies match.
+	pub fn cookie_header(&self, url: &Url) -> Option<String> {
+		let cookies = self.cookies(url);
+		if cookies.is_empty() {
+			return None;
+		}
+		let cookies: Vec<_> = cookies.iter().map(|cookie| format!("{}={}", cookie.name, cookie.value)).collect();
+		Some(cookies.join("; "))
+	}
+
+	/// Returns all unexpired cookies in the jar.
+	pub fn all(&self) -> Vec<Cookie> {
+		let now = SystemTime::now();
+		let mut cookies = self.cookies.lock().unwrap();
+		cookies.retain(|cookie| !cookie.is_expired(now));
+		cookies.clone()
+	}
+
+	pub fn remove(&self, name: &str, domain: &str, path: &str) {
+		let mut cookies = self.cookies.lock().unwrap();
+		cookies.retain(|c| !(c.name == name && c.domain == domain && c.path == path));
+	}
+
+	pub fn clear(&self) {
+		self.cookies.lock().unwrap().clear();
+	}
+}
+
+fn default_path(url: &Url) -> String {
+	let path = url.path();
+	match path.rfind('/') {
+		Some(0) | None => String::from("/"),
+		Some(index) => String::from(&path[..index]),
+	}
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+	if host == domain {
+		return true;
+	}
+	host.parse::<IpAddr>().is_err()
+		&& host.len() > domain.len()
+		&& host.ends_with(domain)
+		&& host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+}
+
+fn path_matches(path: &str, cookie_path: &str) -> bool {
+	path == cookie_path
+		|| (path.starts_with(cookie_path) && (cookie_path.ends_with('/') || path[cookie_path.len()..].starts_with('/')))
+}
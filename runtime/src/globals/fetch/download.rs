@@ -0,0 +1,197 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+
+use futures::future::{Either, select};
+use http::header::CONTENT_LENGTH;
+use mozjs::jsapi::{JSFunction, JSObject};
+use tokio::fs::{File, remove_file, rename};
+use tokio::io::AsyncWriteExt;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Exception, Function, Object, Promise, ResultExc, TracedHeap};
+use ion::conversions::ToValue;
+use ion::function::Opt;
+
+use crate::globals::abort::AbortSignal;
+use crate::globals::fetch::{Client, fetch_internal, new_fetch_request, Request, RequestInfo, RequestInit, Response};
+use crate::globals::streams::StreamCounters;
+use crate::handle;
+use crate::promise::future_to_promise;
+use crate::security::can_write;
+use crate::wasi_polyfills::canonicalize;
+
+#[derive(Default, FromValue)]
+pub struct DownloadOptions<'cx> {
+	#[ion(inherit)]
+	init: RequestInit<'cx>,
+	on_progress: Option<Function<'cx>>,
+}
+
+/// Fetches a resource and streams its body into a file, without exposing the chunks to scripts.
+///
+/// The body is written to a temporary file next to the destination, which replaces the destination once the body has
+/// been fully written. Resolves with the number of bytes written.
+#[js_fn]
+pub(crate) fn download(
+	cx: &Context, resource: RequestInfo, path: String, Opt(options): Opt<DownloadOptions>,
+) -> Option<Promise> {
+	let DownloadOptions { init, on_progress } = options.unwrap_or_default();
+	let prepared = download_path(cx, Path::new(&path))
+		.map_err(Exception::Error)
+		.and_then(|path| new_fetch_request(cx, resource, Opt(Some(init))).map(|request| (path, request)));
+	let (path, (request, client)) = match prepared {
+		Ok(prepared) => prepared,
+		Err(exception) => return Some(Promise::rejected(cx, exception.as_value(cx))),
+	};
+	let on_progress = on_progress.map(|on_progress| TracedHeap::new(on_progress.get()));
+
+	unsafe {
+		future_to_promise(cx, move |mut cx| async move {
+			let partial = partial_path(&path);
+			let mut result;
+			(cx, result) = cx
+				.await_native_cx(|cx| download_to_file(cx, &request, client, &partial, on_progress.as_ref()))
+				.await;
+			if let Ok(written) = result {
+				let renamed;
+				(cx, renamed) = cx.await_native(rename(&partial, &path)).await;
+				result = renamed.map(|_| written).map_err(|error| write_error(&path, error).into());
+			}
+			if result.is_err() {
+				let _ = cx.await_native(remove_file(&partial)).await;
+			}
+			result
+		})
+	}
+}
+
+async fn download_to_file(
+	mut cx: Context, request: &TracedHeap<*mut JSObject>, client: Client, partial: &Path,
+	on_progress: Option<&TracedHeap<*mut JSFunction>>,
+) -> ResultExc<u64> {
+	let signal = {
+		let request = Object::from(request.to_local());
+		let request = Request::get_private(&cx, &request)?;
+		let signal = Object::from(request.signal_object.to_local());
+		AbortSignal::get_private(&cx, &signal)?.signal.clone().poll()
+	};
+	let mut signal = pin!(signal);
+
+	let response;
+	(cx, response) = cx
+		.await_native_cx(|cx| async move {
+			let request = Object::from(request.to_local());
+			fetch_internal(cx, &request, client).await
+		})
+		.await;
+	let response = Object::from(cx.root(response?));
+	let response = Response::get_mut_private(&cx, &response)?;
+	if !response.get_ok() {
+		return Err(Error::new(
+			format!(
+				"Download failed with status {} {}",
+				response.get_status(),
+				response.get_status_text()
+			),
+			ErrorKind::Normal,
+		)
+		.into());
+	}
+
+	let total = response
+		.headers(&cx)
+		.get(CONTENT_LENGTH)
+		.and_then(|length| length.to_str().ok())
+		.and_then(|length| length.parse::<u64>().ok());
+	let reader = response.take_body()?.body.into_stream(&cx)?.into_reader(&cx)?;
+
+	let mut file;
+	(cx, file) = cx.await_native(File::create(partial)).await;
+	let mut file = file.map_err(|error| write_error(partial, error))?;
+
+	let reader = &reader;
+	let counters = StreamCounters::default();
+	loop {
+		let chunk;
+		(cx, chunk) = cx
+			.await_native_cx(|cx| {
+				let read = Box::pin(async move {
+					let chunk = unsafe { reader.read_chunk(cx).await };
+					chunk.map(|chunk| chunk.map(|chunk| chunk.into_owned()))
+				});
+				select(read, signal.as_mut())
+			})
+			.await;
+		let chunk = match chunk {
+			Either::Left((chunk, _)) => chunk?,
+			Either::Right((reason, _)) => return Err(Exception::Other(reason)),
+		};
+		let Some(chunk) = chunk else {
+			break;
+		};
+
+		let written;
+		(cx, written) = cx.await_native(file.write_all(&chunk)).await;
+		written.map_err(|error| write_error(partial, error))?;
+		counters.record(chunk.len());
+
+		if let Some(on_progress) = on_progress {
+			let progress = Object::new(&cx);
+			progress.set_as(&cx, "loaded", &counters.bytes());
+			progress.set_as(&cx, "total", &total);
+			let on_progress = Function::from(on_progress.root(&cx));
+			on_progress
+				.call_with(&cx, &Object::global(&cx), [progress])
+				.map_err(|error| handle::call_exception(&cx, error))?;
+		}
+	}
+
+	let (_, flushed) = cx.await_native(file.flush()).await;
+	flushed.map_err(|error| write_error(partial, error))?;
+	Ok(counters.bytes())
+}
+
+/// Resolves the destination of a download, which must be in an existing directory that scripts can write to.
+fn download_path(cx: &Context, path: &Path) -> ion::Result<PathBuf> {
+	let file_name = path
+		.file_name()
+		.ok_or_else(|| Error::new("Download path must name a file", ErrorKind::Type))?;
+	let directory = match path.parent() {
+		Some(directory) if !directory.as_os_str().is_empty() => directory,
+		_ => Path::new("."),
+	};
+	let directory = canonicalize(directory).map_err(|error| {
+		Error::new(
+			format!("Invalid download directory {}: {}", directory.display(), error),
+			ErrorKind::Type,
+		)
+	})?;
+
+	let path = directory.join(file_name);
+	if !can_write(cx, &path) {
+		return Err(Error::new(
+			format!("Write access denied: {}", path.display()),
+			ErrorKind::Normal,
+		));
+	}
+	Ok(path)
+}
+
+fn write_error(path: &Path, error: io::Error) -> Error {
+	Error::new(
+		format!("Failed to write {}: {}", path.display(), error),
+		ErrorKind::Normal,
+	)
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+	let mut file_name = path.file_name().unwrap_or_default().to_owned();
+	file_name.push(".part");
+	path.with_file_name(file_name)
+}
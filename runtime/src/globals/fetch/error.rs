@@ -0,0 +1,164 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use std::{fmt, io};
+
+use url::Url;
+
+use ion::{Context, Error, ErrorKind, Exception, Object};
+
+use crate::globals::fetch::timeout::FetchTimeout;
+
+/// Represents the stage of a fetch at which a network error occurred.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FetchErrorPhase {
+	Dns,
+	Connect,
+	Tls,
+	Request,
+	Response,
+	Body,
+}
+
+impl Display for FetchErrorPhase {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		use FetchErrorPhase as FEP;
+		f.write_str(match self {
+			FEP::Dns => "dns",
+			FEP::Connect => "connect",
+			FEP::Tls => "tls",
+			FEP::Request => "request",
+			FEP::Response => "response",
+			FEP::Body => "body",
+		})
+	}
+}
+
+/// Holds the details of a network error, which are exposed on the [TypeError](ErrorKind::Type) that
+/// `fetch` is rejected with, as the `phase`, `ioErrorKind`, `code` and `url` properties.
+#[derive(Clone, Debug)]
+pub struct FetchError {
+	pub phase: Option<FetchErrorPhase>,
+	pub io_error_kind: Option<io::ErrorKind>,
+	/// Timeout which elapsed, if the error was caused by a timeout.
+	pub timeout: Option<FetchTimeout>,
+	pub url: Option<Url>,
+	pub message: Option<String>,
+}
+
+impl FetchError {
+	pub fn new(phase: Option<FetchErrorPhase>, url: Option<Url>) -> FetchError {
+		FetchError {
+			phase,
+			io_error_kind: None,
+			timeout: None,
+			url,
+			message: None,
+		}
+	}
+
+	pub fn from_timeout(timeout: FetchTimeout, url: Option<Url>) -> FetchError {
+		FetchError {
+			timeout: Some(timeout),
+			..FetchError::new(Some(timeout.phase()), url).message(timeout.to_string())
+		}
+	}
+
+	pub fn message(self, message: impl Into<String>) -> FetchError {
+		FetchError { message: Some(message.into()), ..self }
+	}
+
+	/// Classifies an error from the HTTP client by walking its chain of sources.
+	pub fn from_hyper(error: &hyper::Error, url: Option<Url>) -> FetchError {
+		let mut io_error_kind = None;
+		let mut timeout = None;
+		let mut is_tls = false;
+		let mut is_dns = false;
+
+		let mut source: Option<&(dyn StdError + 'static)> = error.source();
+		while let Some(error) = source {
+			if let Some(error) = error.downcast_ref::<FetchTimeout>() {
+				timeout = Some(*error);
+			}
+			if error.is::<rustls::Error>() {
+				is_tls = true;
+			}
+			if let Some(error) = error.downcast_ref::<io::Error>() {
+				io_error_kind.get_or_insert(error.kind());
+				if error.get_ref().map(|inner| inner.is::<rustls::Error>()).unwrap_or_default() {
+					is_tls = true;
+				}
+			}
+			// The HTTP connector does not expose its resolver errors as a type.
+			if error.to_string().starts_with("dns error") {
+				is_dns = true;
+			}
+			source = error.source();
+		}
+
+		if let Some(timeout) = timeout {
+			return FetchError::from_timeout(timeout, url);
+		}
+
+		let phase = if is_dns {
+			FetchErrorPhase::Dns
+		} else if is_tls {
+			FetchErrorPhase::Tls
+		} else if error.is_connect() {
+			FetchErrorPhase::Connect
+		} else if error.is_body_write() || error.is_user() {
+			FetchErrorPhase::Request
+		} else {
+			FetchErrorPhase::Response
+		};
+
+		FetchError {
+			phase: Some(phase),
+			io_error_kind,
+			timeout: None,
+			url,
+			message: Some(error.to_string()),
+		}
+	}
+
+	/// Creates the [TypeError](ErrorKind::Type) object for the error, with its details as properties.
+	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Option<Object<'cx>> {
+		let message = match &self.url {
+			Some(url) => format!("Network Error: Failed to fetch from {}", url),
+			None => String::from("Network Error: Failed to fetch"),
+		};
+		let message = match (&self.phase, &self.message) {
+			(Some(phase), Some(cause)) => format!("{} ({} error: {})", message, phase, cause),
+			(Some(phase), None) => format!("{} ({} error)", message, phase),
+			(None, Some(cause)) => format!("{} ({})", message, cause),
+			(None, None) => message,
+		};
+
+		let error = Error::new(message, ErrorKind::Type).to_object(cx)?;
+		if let Some(phase) = self.phase {
+			error.set_as(cx, "phase", &phase.to_string());
+		}
+		if let Some(kind) = self.io_error_kind {
+			error.set_as(cx, "ioErrorKind", &format!("{:?}", kind));
+		}
+		if let Some(timeout) = self.timeout {
+			error.set_as(cx, "code", &timeout.code());
+		}
+		if let Some(url) = &self.url {
+			error.set_as(cx, "url", &url.to_string());
+		}
+		Some(error)
+	}
+
+	pub fn to_exception(&self, cx: &Context) -> Exception {
+		match self.to_object(cx) {
+			Some(error) => Exception::Other(error.as_value(cx).get()),
+			None => Exception::Error(Error::none()),
+		}
+	}
+}
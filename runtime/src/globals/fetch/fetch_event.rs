@@ -0,0 +1,158 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::{JSFunctionSpec, JSObject};
+
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Heap, Object, Promise, PromiseFuture, Result, ResultExc,
+	TracedHeap, Value,
+};
+use ion::function::Opt;
+
+use crate::ContextExt;
+use crate::globals::event::{Event, EventInit};
+use crate::globals::event_target::{EventTarget, ListenerOptions};
+use crate::globals::fetch::{Request, Response};
+
+#[derive(FromValue)]
+pub struct FetchEventInit<'cx> {
+	#[ion(inherit)]
+	pub event: EventInit,
+	pub request: Object<'cx>,
+}
+
+/// Event dispatched to the service worker scope for each request passed to [dispatch_fetch].
+#[js_class]
+pub struct FetchEvent {
+	event: Event,
+	request: Heap<*mut JSObject>,
+	response: Option<Heap<*mut JSObject>>,
+}
+
+#[js_class]
+impl FetchEvent {
+	#[ion(constructor)]
+	pub fn constructor(cx: &Context, kind: String, init: FetchEventInit) -> Result<FetchEvent> {
+		if !Request::instance_of(cx, &init.request) {
+			return Err(Error::new("Expected Request for FetchEvent request", ErrorKind::Type));
+		}
+		Ok(FetchEvent {
+			event: Event::new(kind, init.event),
+			request: Heap::new(init.request.handle().get()),
+			response: None,
+		})
+	}
+
+	#[ion(get)]
+	pub fn get_request(&self) -> *mut JSObject {
+		self.request.get()
+	}
+
+	/// Responds to the request with a [Response], or a promise which resolves to one.
+	/// Must be called synchronously from a listener, and stops the event from reaching other listeners.
+	#[ion(name = "respondWith")]
+	pub fn respond_with(&mut self, cx: &Context, response: Value) -> Result<()> {
+		if !self.event.dispatching {
+			return Err(Error::new(
+				"FetchEvent.respondWith must be called while the event is dispatched",
+				ErrorKind::Normal,
+			));
+		}
+		if self.response.is_some() {
+			return Err(Error::new(
+				"FetchEvent.respondWith has already been called",
+				ErrorKind::Normal,
+			));
+		}
+
+		self.event.stop_immediate_propagation();
+		self.response = Some(Heap::new(Promise::resolved(cx, response).get()));
+		Ok(())
+	}
+}
+
+/// Dispatches a `fetch` event for the request to the listeners of the service worker scope, and waits for the response
+/// passed to `respondWith`.
+///
+/// Resolves with [None] if no listener responded, so that the embedder can handle the request itself.
+/// Requires the scope to be enabled with [service_worker_scope](crate::RuntimeBuilder::service_worker_scope).
+pub async fn dispatch_fetch(cx: Context, request: Request) -> ResultExc<Option<TracedHeap<*mut JSObject>>> {
+	let promise = {
+		let target = scope_target(&cx)?;
+		let request = cx.root(Request::new_object(&cx, Box::new(request)));
+		let event = FetchEvent {
+			event: Event::new_trusted("fetch", EventInit { cancelable: true, ..EventInit::default() }),
+			request: Heap::new(request.get()),
+			response: None,
+		};
+		let event = Object::from(cx.root(FetchEvent::new_object(&cx, Box::new(event))));
+		EventTarget::dispatch(&cx, &target, &event)?;
+
+		match &FetchEvent::get_private(&cx, &event)?.response {
+			Some(response) => Promise::from(cx.root(response.get())).unwrap(),
+			None => return Ok(None),
+		}
+	};
+
+	let (cx, result) = PromiseFuture::new(cx, &promise).await;
+	let response = Value::from(cx.root(result.map_err(|error| Exception::Other(error.get()))?.get()));
+	if response.handle().is_object() {
+		let response = response.to_object(&cx);
+		if Response::instance_of(&cx, &response) {
+			return Ok(Some(TracedHeap::from_local(&response)));
+		}
+	}
+	Err(Error::new("FetchEvent.respondWith must be given a Response", ErrorKind::Type).into())
+}
+
+fn scope_target(cx: &Context) -> Result<Object> {
+	let target = unsafe { cx.get_private() }.service_worker_scope.as_ref();
+	target
+		.map(|target| Object::from(target.root(cx)))
+		.ok_or_else(|| Error::new("Service worker scope is not enabled", ErrorKind::Normal))
+}
+
+#[js_fn]
+fn add_event_listener(
+	cx: &Context, kind: String, callback: Option<Object>, options: Opt<ListenerOptions>,
+) -> Result<()> {
+	let target = scope_target(cx)?;
+	EventTarget::get_mut_private(cx, &target)?.add_event_listener(kind, callback, options);
+	Ok(())
+}
+
+#[js_fn]
+fn remove_event_listener(
+	cx: &Context, kind: String, callback: Option<Object>, options: Opt<ListenerOptions>,
+) -> Result<()> {
+	let target = scope_target(cx)?;
+	EventTarget::get_mut_private(cx, &target)?.remove_event_listener(kind, callback, options);
+	Ok(())
+}
+
+#[js_fn]
+fn dispatch_event(cx: &Context, event: Object) -> ResultExc<bool> {
+	let target = scope_target(cx)?;
+	EventTarget::dispatch_event(cx, &target, event)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(add_event_listener, "addEventListener", 2),
+	function_spec!(remove_event_listener, "removeEventListener", 2),
+	function_spec!(dispatch_event, "dispatchEvent", 1),
+	JSFunctionSpec::ZERO,
+];
+
+/// Defines `addEventListener`, `removeEventListener` and `dispatchEvent` on the global, which manage the listeners of a
+/// hidden [EventTarget] that receives the events of the scope.
+pub(crate) fn define_service_worker_scope(cx: &Context, global: &Object) -> Option<TracedHeap<*mut JSObject>> {
+	if unsafe { global.define_methods(cx, FUNCTIONS) } {
+		let target = cx.root(EventTarget::new_object(cx, Box::default()));
+		Some(TracedHeap::from_local(&target))
+	} else {
+		None
+	}
+}
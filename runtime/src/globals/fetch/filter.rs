@@ -0,0 +1,105 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use http::{HeaderMap, HeaderName, Method, StatusCode};
+use http::header::{
+	ACCESS_CONTROL_EXPOSE_HEADERS, CACHE_CONTROL, CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE, EXPIRES,
+	LAST_MODIFIED, PRAGMA, RANGE,
+};
+
+use crate::globals::fetch::header::{FORBIDDEN_RESPONSE_HEADERS, remove_all_header_entries};
+use crate::globals::fetch::response::{ResponseKind, ResponseTaint};
+
+/// Response headers which are always exposed by CORS filtered responses.
+static CORS_SAFELISTED_RESPONSE_HEADERS: [HeaderName; 7] = [
+	CACHE_CONTROL,
+	CONTENT_LANGUAGE,
+	CONTENT_LENGTH,
+	CONTENT_TYPE,
+	EXPIRES,
+	LAST_MODIFIED,
+	PRAGMA,
+];
+
+/// Returns the kind of the filtered response for a response tainted with the given taint.
+pub fn filtered_kind(taint: ResponseTaint, opaque_redirect: bool) -> ResponseKind {
+	if opaque_redirect {
+		return ResponseKind::OpaqueRedirect;
+	}
+	match taint {
+		ResponseTaint::Basic => ResponseKind::Basic,
+		ResponseTaint::Cors => ResponseKind::Cors,
+		ResponseTaint::Opaque => ResponseKind::Opaque,
+	}
+}
+
+/// Removes the headers of a response which are not exposed by its filtered response.
+pub fn filter_headers(headers: &mut HeaderMap, kind: ResponseKind, credentials: bool) {
+	match kind {
+		ResponseKind::Basic => filter_basic_headers(headers),
+		ResponseKind::Cors => filter_cors_headers(headers, credentials),
+		ResponseKind::Opaque | ResponseKind::OpaqueRedirect => headers.clear(),
+		ResponseKind::Default | ResponseKind::Error => {}
+	}
+}
+
+/// Removes the forbidden response headers, such as `Set-Cookie`.
+pub fn filter_basic_headers(headers: &mut HeaderMap) {
+	for name in &FORBIDDEN_RESPONSE_HEADERS {
+		remove_all_header_entries(headers, name);
+	}
+}
+
+/// Keeps the CORS-safelisted response headers and the headers listed by `Access-Control-Expose-Headers`.
+/// A wildcard exposes every header, unless the request included credentials.
+pub fn filter_cors_headers(headers: &mut HeaderMap, credentials: bool) {
+	let exposed = exposed_header_names(headers);
+	let exposes_all = !credentials && exposed.iter().any(|name| name == "*");
+
+	if !exposes_all {
+		let removed: Vec<_> = headers
+			.keys()
+			.filter(|name| {
+				!CORS_SAFELISTED_RESPONSE_HEADERS.contains(name)
+					&& !exposed.iter().any(|exposed| exposed.eq_ignore_ascii_case(name.as_str()))
+			})
+			.cloned()
+			.collect();
+		for name in removed {
+			remove_all_header_entries(headers, &name);
+		}
+	}
+	filter_basic_headers(headers);
+}
+
+/// Returns the header names listed by the `Access-Control-Expose-Headers` headers.
+pub fn exposed_header_names(headers: &HeaderMap) -> Vec<String> {
+	headers
+		.get_all(ACCESS_CONTROL_EXPOSE_HEADERS)
+		.iter()
+		.filter_map(|value| value.to_str().ok())
+		.flat_map(|value| value.split(','))
+		.map(str::trim)
+		.filter(|name| !name.is_empty())
+		.map(String::from)
+		.collect()
+}
+
+/// Checks if a response must have a null body, due to its status or the method of the request.
+pub fn has_null_body(method: &Method, status: Option<StatusCode>) -> bool {
+	matches!(*method, Method::HEAD | Method::CONNECT)
+		|| matches!(status.map(|status| status.as_u16()), Some(101 | 103 | 204 | 205 | 304))
+}
+
+/// Checks if an opaque partial response must be replaced with a network error, as the request did not ask for a range.
+pub fn is_blocked_range_response(
+	taint: ResponseTaint, status: Option<StatusCode>, range_requested: bool, request_headers: &HeaderMap,
+) -> bool {
+	taint == ResponseTaint::Opaque
+		&& status == Some(StatusCode::PARTIAL_CONTENT)
+		&& range_requested
+		&& !request_headers.contains_key(RANGE)
+}
@@ -9,7 +9,6 @@
 use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter};
-use std::str;
 use std::str::FromStr;
 use std::vec;
 
@@ -98,6 +97,9 @@ pub enum HeadersInit<'cx> {
 	Array(Vec<HeaderEntry>),
 	#[ion(inherit)]
 	Object(HeadersObject),
+	/// Headers created natively, which are not validated.
+	#[ion(skip)]
+	Map(HeaderMap),
 	#[default]
 	#[ion(skip)]
 	Empty,
@@ -115,6 +117,14 @@ impl HeadersInit<'_> {
 				})
 			}
 			HeadersInit::Array(vec) => Headers::from_array(vec, headers, kind),
+			HeadersInit::Map(map) => {
+				headers.extend(map);
+				Ok(Headers {
+					reflector: Reflector::default(),
+					headers,
+					kind,
+				})
+			}
 			HeadersInit::Object(object) => {
 				let mut name = None;
 				for (nm, value) in object.0 {
@@ -190,6 +200,67 @@ impl Headers {
 		self.headers.iter()
 	}
 
+	pub fn kind(&self) -> HeadersKind {
+		self.kind
+	}
+
+	pub fn is_immutable(&self) -> bool {
+		self.kind == HeadersKind::Immutable
+	}
+
+	/// Makes the headers immutable, such as before they are exposed as the headers of a response received by `fetch`.
+	pub fn make_immutable(&mut self) {
+		self.kind = HeadersKind::Immutable;
+	}
+
+	/// Returns a mutable copy of the headers. Copies of immutable headers have no guard, and the guard of other
+	/// headers is kept.
+	pub fn to_mutable(&self) -> Headers {
+		let kind = match self.kind {
+			HeadersKind::Immutable => HeadersKind::None,
+			kind => kind,
+		};
+		Headers {
+			reflector: Reflector::default(),
+			headers: self.headers.clone(),
+			kind,
+		}
+	}
+
+	/// Applies a closure to the underlying [HeaderMap], such as to rewrite many headers at once.
+	/// Like [HeadersInit::Map], headers added natively are not validated against the guard of the headers.
+	///
+	/// Returns an error if the headers are immutable.
+	pub fn with_header_map<T>(&mut self, f: impl FnOnce(&mut HeaderMap) -> T) -> Result<T> {
+		if self.kind == HeadersKind::Immutable {
+			return Err(Error::new("Headers cannot be modified", ErrorKind::Type));
+		}
+		Ok(f(&mut self.headers))
+	}
+
+	fn append_entry(&mut self, name: HeaderName, value: HeaderValue) -> Result<()> {
+		if self.kind != HeadersKind::Immutable {
+			self.headers.append(name, value);
+			Ok(())
+		} else {
+			Err(Error::new("Cannot Modify Readonly Headers", None))
+		}
+	}
+
+	fn set_entry(&mut self, name: HeaderName, value: HeaderValue) -> Result<()> {
+		if !validate_header(&name, &HeaderValue::from_static(""), self.kind)? {
+			return Ok(());
+		}
+		if self.kind == HeadersKind::RequestNoCors
+			&& !validate_no_cors_safelisted_request_header(&mut self.headers, &name, &value)
+		{
+			return Ok(());
+		}
+		self.headers.insert(name, value);
+		remove_privileged_no_cors_headers(&mut self.headers, self.kind);
+		Ok(())
+	}
+
 	fn js_iterator(&self, cx: &Context, mode: HeadersIteratorMode) -> ion::Iterator {
 		let cookies: Vec<_> = self.headers.get_all(&SET_COOKIE).iter().map(HeaderValue::clone).collect();
 
@@ -220,14 +291,27 @@ impl Headers {
 	}
 
 	pub fn append(&mut self, name: ByteString<VisibleAscii>, value: ByteString<VisibleAscii>) -> Result<()> {
-		if self.kind != HeadersKind::Immutable {
-			let name = HeaderName::from_bytes(&name)?;
-			let value = HeaderValue::from_bytes(&value)?;
-			self.headers.append(name, value);
-			Ok(())
-		} else {
-			Err(Error::new("Cannot Modify Readonly Headers", None))
+		let name = HeaderName::from_bytes(&name)?;
+		let value = HeaderValue::from_bytes(&value)?;
+		self.append_entry(name, value)
+	}
+
+	/// Appends each entry in order. All entries are converted before any are appended.
+	#[ion(name = "appendAll")]
+	pub fn append_all(&mut self, entries: Vec<HeaderEntry>) -> Result<()> {
+		let entries = entries
+			.into_iter()
+			.map(|entry| {
+				Ok((
+					HeaderName::from_bytes(&entry.name)?,
+					HeaderValue::from_bytes(&entry.value)?,
+				))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		for (name, value) in entries {
+			self.append_entry(name, value)?;
 		}
+		Ok(())
 	}
 
 	pub fn delete(&mut self, name: ByteString<VisibleAscii>) -> Result<()> {
@@ -269,16 +353,20 @@ impl Headers {
 	pub fn set(&mut self, name: ByteString<VisibleAscii>, value: ByteString<VisibleAscii>) -> Result<()> {
 		let name = HeaderName::from_bytes(&name)?;
 		let value = HeaderValue::from_bytes(&value)?;
-		if !validate_header(&name, &HeaderValue::from_static(""), self.kind)? {
-			return Ok(());
-		}
-		if self.kind == HeadersKind::RequestNoCors
-			&& !validate_no_cors_safelisted_request_header(&mut self.headers, &name, &value)
-		{
-			return Ok(());
+		self.set_entry(name, value)
+	}
+
+	/// Sets each property of the object as a header, joining array values with commas.
+	/// All properties are converted before any headers are set.
+	#[ion(name = "setAll")]
+	pub fn set_all(&mut self, headers: HeadersObject) -> Result<()> {
+		let mut name = None;
+		for (nm, value) in headers.0 {
+			if let nm @ Some(_) = nm {
+				name = nm;
+			}
+			self.set_entry(name.clone().unwrap(), value)?;
 		}
-		self.headers.insert(name, value);
-		remove_privileged_no_cors_headers(&mut self.headers, self.kind);
 		Ok(())
 	}
 
@@ -347,9 +435,10 @@ impl JSIterator for HeadersIterator {
 			if key == SET_COOKIE.as_str() {
 				self.cookies
 					.next()
-					.map(|value| self.to_return_value(cx, key.as_str(), value.to_str().unwrap()))
+					.map(|value| self.to_return_value(cx, key.as_str(), &String::from_utf8_lossy(value.as_bytes())))
 			} else {
-				get_header(&headers.headers, &HeaderName::from_bytes(key.as_bytes()).unwrap())
+				let name = HeaderName::from_bytes(key.as_bytes()).ok()?;
+				get_header(&headers.headers, &name)
 					.map(|value| self.to_return_value(cx, key.as_str(), &value.to_string()))
 			}
 		})
@@ -424,7 +513,9 @@ fn validate_no_cors_safelisted_request_header(headers: &mut HeaderMap, name: &He
 	}
 
 	let temp = get_header(headers, name);
-	let str = value.to_str().unwrap();
+	let Ok(str) = value.to_str() else {
+		return false;
+	};
 	let temp = match temp {
 		Some(temp) => format!("{}, {}", temp, str),
 		None => String::from(str),
@@ -574,7 +665,7 @@ fn split_value(value: &HeaderValue) -> Vec<String> {
 	let mut escaped = false;
 	let mut result = vec![String::new()];
 
-	for char in str::from_utf8(value.as_bytes()).unwrap().chars() {
+	for char in String::from_utf8_lossy(value.as_bytes()).chars() {
 		let len = result.len();
 		if char == '"' && !escaped {
 			quoted = !quoted;
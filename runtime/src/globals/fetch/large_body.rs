@@ -0,0 +1,192 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::panic::resume_unwind;
+
+use bytes::Bytes;
+use mozjs::jsapi::JSObject;
+use mozjs::jsval::{BooleanValue, JSVal, NullValue, ObjectValue};
+use serde_json::Value as JsonValue;
+use tokio::runtime::Handle;
+use tokio::task::{spawn_blocking, yield_now};
+
+use ion::{Array, Context, Error, ErrorKind, Object, Result, TracedHeap, Value};
+use ion::conversions::ToValue;
+use ion::flags::PropertyFlags;
+
+use crate::ContextExt;
+
+/// Configures how `json()` and `text()` handle large bodies, so that they do not stall the event loop.
+///
+/// Large bodies are decoded and parsed on a blocking thread. The parsed JSON is then converted to JavaScript values
+/// in segments, yielding to the event loop between each segment.
+#[derive(Clone, Copy, Debug)]
+pub struct LargeBodyOptions {
+	/// Minimum size of a body in bytes for it to be handled as a large body. [None] disables it.
+	pub threshold: Option<usize>,
+	/// Number of JSON values converted in each segment.
+	pub segment_size: usize,
+}
+
+impl LargeBodyOptions {
+	pub const DEFAULT_THRESHOLD: usize = 8 * 1024 * 1024;
+	pub const DEFAULT_SEGMENT_SIZE: usize = 64 * 1024;
+
+	pub(crate) fn from_context(cx: &Context) -> LargeBodyOptions {
+		unsafe { cx.get_private().large_body }
+	}
+
+	pub(crate) fn is_large(&self, length: usize) -> bool {
+		self.threshold.is_some_and(|threshold| length >= threshold)
+	}
+}
+
+impl Default for LargeBodyOptions {
+	fn default() -> LargeBodyOptions {
+		LargeBodyOptions {
+			threshold: Some(LargeBodyOptions::DEFAULT_THRESHOLD),
+			segment_size: LargeBodyOptions::DEFAULT_SEGMENT_SIZE,
+		}
+	}
+}
+
+/// Runs a function on a blocking thread if there is a tokio runtime, otherwise on the current thread.
+async fn run_blocking<T: Send + 'static>(function: impl FnOnce() -> T + Send + 'static) -> T {
+	if Handle::try_current().is_err() {
+		return function();
+	}
+	match spawn_blocking(function).await {
+		Ok(result) => result,
+		Err(error) => resume_unwind(error.into_panic()),
+	}
+}
+
+pub(crate) async fn decode_text(bytes: Bytes) -> Result<String> {
+	run_blocking(move || String::from_utf8(bytes.to_vec()))
+		.await
+		.map_err(|e| Error::new(format!("Invalid UTF-8 sequence: {}", e), ErrorKind::Normal))
+}
+
+/// Parses JSON on a blocking thread.
+/// Returns [None] if the body could not be parsed, in which case it should be parsed by the engine to report the error.
+pub(crate) async fn parse_json(bytes: Bytes) -> Option<JsonValue> {
+	run_blocking(move || serde_json::from_slice(&bytes).ok()).await
+}
+
+enum Frame {
+	Array {
+		array: TracedHeap<*mut JSObject>,
+		items: std::vec::IntoIter<JsonValue>,
+		index: u32,
+	},
+	Object {
+		object: TracedHeap<*mut JSObject>,
+		entries: serde_json::map::IntoIter,
+		key: String,
+	},
+}
+
+impl Frame {
+	fn object(&self) -> *mut JSObject {
+		match self {
+			Frame::Array { array, .. } => array.get(),
+			Frame::Object { object, .. } => object.get(),
+		}
+	}
+}
+
+/// Converts parsed JSON to a JavaScript value, yielding to the event loop after each segment of values.
+pub(crate) async fn json_to_value(mut cx: Context, json: JsonValue, segment_size: usize) -> Result<JSVal> {
+	let mut stack = Vec::new();
+	let mut next = Some(json);
+	let mut converted = 0;
+
+	loop {
+		if let Some(json) = next.take() {
+			match json {
+				JsonValue::Array(items) => {
+					let array = Array::new_with_length(&cx, items.len());
+					stack.push(Frame::Array {
+						array: TracedHeap::new(array.into_local().get()),
+						items: items.into_iter(),
+						index: 0,
+					});
+				}
+				JsonValue::Object(entries) => {
+					let object = Object::new(&cx);
+					stack.push(Frame::Object {
+						object: TracedHeap::new(object.handle().get()),
+						entries: entries.into_iter(),
+						key: String::new(),
+					});
+				}
+				primitive => {
+					let value = primitive_to_value(&cx, primitive);
+					if let Some(result) = attach(&cx, &mut stack, value)? {
+						return Ok(result);
+					}
+				}
+			}
+
+			converted += 1;
+			if converted % segment_size.max(1) == 0 {
+				(cx, _) = cx.await_native(yield_now()).await;
+			}
+		}
+
+		let Some(frame) = stack.last_mut() else {
+			return Err(Error::new("Failed to convert JSON", ErrorKind::Normal));
+		};
+		match frame {
+			Frame::Array { items, .. } => next = items.next(),
+			Frame::Object { entries, key, .. } => {
+				next = entries.next().map(|(entry_key, value)| {
+					*key = entry_key;
+					value
+				})
+			}
+		}
+
+		if next.is_none() {
+			let frame = stack.pop().unwrap();
+			if let Some(result) = attach(&cx, &mut stack, ObjectValue(frame.object()))? {
+				return Ok(result);
+			}
+		}
+	}
+}
+
+fn primitive_to_value(cx: &Context, json: JsonValue) -> JSVal {
+	match json {
+		JsonValue::Null => NullValue(),
+		JsonValue::Bool(boolean) => BooleanValue(boolean),
+		JsonValue::Number(number) => number.as_f64().unwrap_or(f64::NAN).as_value(cx).get(),
+		JsonValue::String(string) => string.as_value(cx).get(),
+		JsonValue::Array(_) | JsonValue::Object(_) => unreachable!(),
+	}
+}
+
+/// Defines a converted value in its parent. Returns the value if it has no parent, as it is the result.
+fn attach(cx: &Context, stack: &mut [Frame], value: JSVal) -> Result<Option<JSVal>> {
+	let value = Value::from(cx.root(value));
+	let defined = match stack.last_mut() {
+		None => return Ok(Some(value.get())),
+		Some(Frame::Array { array, index, .. }) => {
+			let array = Object::from(array.root(cx));
+			*index += 1;
+			array.define(cx, *index - 1, &value, PropertyFlags::ENUMERATE)
+		}
+		Some(Frame::Object { object, key, .. }) => {
+			let object = Object::from(object.root(cx));
+			object.define(cx, key.as_str(), &value, PropertyFlags::ENUMERATE)
+		}
+	};
+	if defined {
+		Ok(None)
+	} else {
+		Err(Error::new("Failed to define JSON property", ErrorKind::Normal))
+	}
+}
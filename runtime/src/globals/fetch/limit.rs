@@ -0,0 +1,113 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures::Stream;
+use hyper::Body;
+use hyper::body::HttpBody;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
+
+use crate::globals::fetch::error::FetchError;
+use crate::globals::fetch::timeout::{FetchTimeout, timeout_after};
+
+/// Limits the number of requests to each origin which are in flight at once, configured in
+/// [ClientOptions](crate::globals::fetch::ClientOptions).
+///
+/// Requests over the limit wait in a queue for their origin, and are sent in the order they were made.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OriginLimits {
+	/// Maximum number of requests to an origin which are sent at once, or [None] to send all requests immediately.
+	pub max_concurrent: Option<usize>,
+	/// Maximum duration a request waits in the queue before it fails, or [None] to wait indefinitely.
+	pub queue_timeout: Option<Duration>,
+}
+
+/// Permit to send a request to an origin, which allows the next queued request to be sent when dropped.
+#[derive(Debug)]
+pub struct OriginPermit {
+	_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl OriginPermit {
+	/// Wraps the body of a response, so that the permit is held until the body has been read completely, fails, or is
+	/// dropped. Otherwise, responses whose bodies are still being received would not count towards the limit.
+	pub(crate) fn hold_until_read(self, body: Body) -> Body {
+		if self._permit.is_none() || body.is_end_stream() {
+			return body;
+		}
+		Body::wrap_stream(PermitBody { body, permit: Some(self) })
+	}
+}
+
+struct PermitBody {
+	body: Body,
+	permit: Option<OriginPermit>,
+}
+
+impl Stream for PermitBody {
+	type Item = hyper::Result<Bytes>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<hyper::Result<Bytes>>> {
+		let chunk = ready!(Pin::new(&mut self.body).poll_next(cx));
+		if !matches!(chunk, Some(Ok(_))) {
+			self.permit = None;
+		}
+		Poll::Ready(chunk)
+	}
+}
+
+/// Queues of the requests to each origin, which are shared between clients using the same connection pool.
+#[derive(Debug, Default)]
+pub(crate) struct OriginLimiter {
+	limits: OriginLimits,
+	origins: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl OriginLimiter {
+	pub(crate) fn new(limits: OriginLimits) -> OriginLimiter {
+		OriginLimiter { limits, origins: Mutex::default() }
+	}
+
+	pub(crate) fn limits(&self) -> OriginLimits {
+		self.limits
+	}
+
+	/// Waits until a request to the origin of `url` can be sent.
+	/// Returns [Err] if the queue timeout elapses first.
+	pub(crate) async fn acquire(&self, url: &Url) -> Result<OriginPermit, FetchError> {
+		let Some(max_concurrent) = self.limits.max_concurrent else {
+			return Ok(OriginPermit { _permit: None });
+		};
+
+		let semaphore = {
+			let mut origins = self.origins.lock().unwrap();
+			// Origins without requests in flight are forgotten, so that the map does not grow indefinitely.
+			origins.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+			let origin = url.origin().ascii_serialization();
+			Arc::clone(origins.entry(origin).or_insert_with(|| Arc::new(Semaphore::new(max_concurrent.max(1)))))
+		};
+
+		let queued = Instant::now();
+		let permit = timeout_after(
+			semaphore.acquire_owned(),
+			|| Some(queued),
+			self.limits.queue_timeout,
+			|| FetchTimeout::Queue,
+		);
+		match permit.await {
+			Ok(Ok(permit)) => Ok(OriginPermit { _permit: Some(permit) }),
+			Ok(Err(_)) => Err(FetchError::new(None, Some(url.clone())).message("Request queue was closed")),
+			Err(timeout) => Err(FetchError::from_timeout(timeout, Some(url.clone()))),
+		}
+	}
+}
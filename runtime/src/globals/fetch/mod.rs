@@ -4,9 +4,10 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::io;
 use std::iter::once;
-use std::str;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use async_recursion::async_recursion;
 use bytes::Bytes;
@@ -15,13 +16,12 @@ use data_url::DataUrl;
 use futures::future::{Either, select};
 use http::{HeaderMap, HeaderValue, Method, StatusCode};
 use http::header::{
-	ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, ACCESS_CONTROL_ALLOW_HEADERS, CACHE_CONTROL, CONTENT_ENCODING,
-	CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_LOCATION, CONTENT_TYPE, HOST, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH,
-	IF_RANGE, IF_UNMODIFIED_SINCE, LOCATION, PRAGMA, RANGE, REFERER, REFERRER_POLICY, USER_AGENT,
+	ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, AGE, CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, ETAG, HOST,
+	IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE, IF_UNMODIFIED_SINCE, LAST_MODIFIED, LOCATION, PRAGMA,
+	PROXY_AUTHORIZATION, RANGE, REFERER, REFERRER_POLICY, SET_COOKIE, USER_AGENT,
 };
 use mozjs::jsapi::JSObject;
 use sys_locale::get_locales;
-use tokio::fs::read;
 use url::Url;
 
 use ion::{ClassDefinition, Context, Error, ErrorKind, Exception, Object, Promise, ResultExc, TracedHeap, Result};
@@ -30,27 +30,93 @@ use ion::conversions::ToValue;
 use ion::flags::PropertyFlags;
 use ion::function::Opt;
 
-pub use body::{FetchBody, FetchBodyInner, FetchBodyKind, FetchBodyLength, hyper_body_to_stream};
-pub use client::{default_client, GLOBAL_CLIENT};
-pub use header::{Headers, HeaderEntry, HeadersInit, HeadersObject};
+pub use body::{
+	AsyncReadBody, FetchBody, FetchBodyInner, FetchBodyKind, FetchBodyLength, hyper_body_to_stream,
+	hyper_body_to_stream_with_timing,
+};
+pub use cache::{
+	CacheControl, CachedResponse, default_http_cache, HttpCache, is_storable, MAX_CACHED_BODY_SIZE, MemoryCache,
+	vary_headers,
+};
+pub use cache_storage::{
+	Cache, CacheEntry, CacheQueryOptions, CacheStorage, CacheStorageBackend, default_cache_storage,
+	GLOBAL_CACHE_STORAGE, MemoryCacheStorage, MultiCacheQueryOptions,
+};
+pub use client::{
+	Client, ClientOptions, client_with_options, default_client, HyperClient, raw_header_case_client, GLOBAL_CLIENT,
+};
+pub use connection::{ConnectionInfo, InstrumentedConnector, InstrumentedStream};
+pub use content_type::MimeChecking;
+pub use cookie::{Cookie, CookieJar};
+pub use error::{FetchError, FetchErrorPhase};
+pub use fetch_event::{dispatch_fetch, FetchEvent};
+pub use filter::{
+	exposed_header_names, filter_basic_headers, filter_cors_headers, filter_headers, filtered_kind, has_null_body,
+	is_blocked_range_response,
+};
+pub use header::{Headers, HeaderEntry, HeadersInit, HeadersKind, HeadersObject};
+pub use large_body::LargeBodyOptions;
+pub use limit::{OriginLimits, OriginPermit};
+pub use multipart::MultipartForm;
+pub use paginate::PageIterator;
+pub use proxy::{NoProxy, Proxy, ProxyConfig, ProxyConnector, ProxyScheme, ProxyStream};
+pub use redirect::{
+	can_resend_body, CREDENTIAL_HEADERS, is_cross_origin_redirect, redirect_changes_to_get, REQUEST_BODY_HEADERS,
+	strip_credential_headers, strip_request_body_headers,
+};
 pub use request::{Request, RequestInfo, RequestInit};
-pub use response::Response;
+pub use response::{Response, ResponseInit, ResponseKind, ResponseTaint};
+pub use scheme::{SchemeFuture, SchemeHandler, SchemeRequest};
+pub use timing::{ResponseTiming, ServerTiming};
+pub use timeout::{FetchTimeout, FetchTimeouts};
+pub use tls::{ClientIdentity, TlsOptions};
 
+use crate::ContextExt;
 use crate::globals::abort::AbortSignal;
-use crate::globals::fetch::client::Client;
-use crate::globals::fetch::header::{FORBIDDEN_RESPONSE_HEADERS, HeadersKind, remove_all_header_entries};
+use crate::globals::fetch::cache::store_body;
+use crate::globals::fetch::download::download;
+pub(crate) use crate::globals::fetch::fetch_event::define_service_worker_scope;
+use crate::globals::fetch::filter::{filter_headers, filtered_kind, has_null_body, is_blocked_range_response};
+use crate::globals::fetch::header::HeadersKind;
+use crate::globals::fetch::paginate::paginate;
 use crate::globals::fetch::request::{
 	Referrer, ReferrerPolicy, RequestCache, RequestCredentials, RequestMode, RequestRedirect,
 };
-use crate::globals::fetch::response::{network_error, ResponseKind, ResponseTaint};
+use crate::globals::fetch::response::{network_error, network_error_with_cause, status_text};
+pub(crate) use crate::globals::fetch::scheme::is_registrable_scheme;
+use crate::globals::fetch::scheme::scheme_handler;
+use crate::globals::file::{disk_stream, DiskSource};
+use crate::globals::shutdown::KeepaliveGuard;
+use crate::mime_type;
 use crate::promise::future_to_promise;
+use crate::security::can_read;
 use crate::VERSION;
+use crate::wasi_polyfills::canonicalize;
 
 mod body;
+mod cache;
+mod cache_storage;
 mod client;
+mod connection;
+mod content_type;
+mod cookie;
+mod download;
+mod error;
+mod fetch_event;
+mod filter;
 mod header;
+mod large_body;
+mod limit;
+mod multipart;
+mod paginate;
+mod proxy;
+mod redirect;
 mod request;
 mod response;
+mod scheme;
+mod timeout;
+mod timing;
+mod tls;
 
 const DEFAULT_USER_AGENT: &str = concatcp!("WinterJS/", VERSION);
 
@@ -58,22 +124,42 @@ const DEFAULT_USER_AGENT: &str = concatcp!("WinterJS/", VERSION);
 
 #[js_fn]
 fn fetch(cx: &Context, resource: RequestInfo, init: Opt<RequestInit>) -> Option<Promise> {
-	let request = match Request::constructor(cx, resource, init) {
+	let (request, client) = match new_fetch_request(cx, resource, init) {
 		Ok(request) => request,
-		Err(error) => {
-			return Some(Promise::rejected(cx, error.as_value(cx)));
-		}
+		Err(exception) => return Some(Promise::rejected(cx, exception.as_value(cx))),
+	};
+
+	let keepalive = match Request::get_private(cx, &Object::from(request.to_local())) {
+		Ok(request) => request.keepalive.then(|| KeepaliveGuard::new(cx)),
+		Err(error) => return Some(Promise::rejected(cx, error.as_value(cx))),
 	};
 
+	unsafe {
+		future_to_promise(cx, move |cx| async move {
+			let request = Object::from(request.to_local());
+			let (_, res) = cx.await_native_cx(|cx| fetch_internal(cx, &request, client)).await;
+			drop(keepalive);
+			res
+		})
+	}
+}
+
+/// Creates the [Request] for a fetch, with default headers, and selects the client to send it with.
+/// Fails with the abort reason if the signal of the request has already been aborted.
+pub(crate) fn new_fetch_request(
+	cx: &Context, resource: RequestInfo, init: Opt<RequestInit>,
+) -> ResultExc<(TracedHeap<*mut JSObject>, Client)> {
+	let request = Request::constructor(cx, resource, init)?;
+
 	let signal = Object::from(request.signal_object.to_local());
-	let signal = AbortSignal::get_private(cx, &signal).unwrap();
+	let signal = AbortSignal::get_private(cx, &signal)?;
 	let signal_reason = signal.get_reason();
 	if !signal_reason.is_undefined() {
-		return Some(Promise::rejected(cx, signal_reason));
+		return Err(Exception::Other(signal_reason));
 	}
 
 	let headers = Object::from(request.headers.to_local());
-	let headers = Headers::get_mut_private(cx, &headers).unwrap();
+	let headers = Headers::get_mut_private(cx, &headers)?;
 	if !headers.headers.contains_key(ACCEPT) {
 		headers.headers.append(ACCEPT, HeaderValue::from_static("*/*"));
 	}
@@ -90,16 +176,20 @@ fn fetch(cx: &Context, resource: RequestInfo, init: Opt<RequestInit>) -> Option<
 		headers.headers.append(ACCEPT_LANGUAGE, HeaderValue::from_str(&locale_string).unwrap());
 	}
 
-	let request = TracedHeap::new(Request::new_object(cx, Box::new(request)));
-	unsafe {
-		future_to_promise(cx, move |cx| async move {
-			let request = Object::from(request.to_local());
-			let (_, res) = cx
-				.await_native_cx(|cx| fetch_internal(cx, &request, GLOBAL_CLIENT.get().unwrap().clone()))
-				.await;
-			res
-		})
-	}
+	let private = unsafe { cx.get_private() };
+	let client = private
+		.client
+		.get_or_insert_with(|| GLOBAL_CLIENT.get().cloned().unwrap_or_else(default_client));
+	let client = if request.raw_header_case {
+		private
+			.raw_header_case_client
+			.get_or_insert_with(|| raw_header_case_client(client))
+			.clone()
+	} else {
+		client.clone()
+	};
+
+	Ok((TracedHeap::new(Request::new_object(cx, Box::new(request))), client))
 }
 
 pub async fn fetch_internal<'o>(cx: Context, request: &Object<'o>, client: Client) -> ResultExc<*mut JSObject> {
@@ -115,10 +205,9 @@ pub async fn fetch_internal<'o>(cx: Context, request: &Object<'o>, client: Clien
 	}?;
 
 	if response.kind == ResponseKind::Error {
-		Err(Exception::Error(Error::new(
-			format!("Network Error: Failed to fetch from {}", request_url),
-			ErrorKind::Type,
-		)))
+		let mut error = response.error.unwrap_or_else(|| FetchError::new(None, None));
+		error.url.get_or_insert(request_url);
+		Err(error.to_exception(&cx))
 	} else {
 		Ok(Response::new_object(&cx, Box::new(response)))
 	}
@@ -218,7 +307,8 @@ async fn main_fetch(cx: Context, request: &mut Request, client: Client, redirect
 	let mut taint = ResponseTaint::default();
 	let mut opaque_redirect = false;
 	let (cx, response) = {
-		if request.mode == RequestMode::SameOrigin {
+		// Requests with 'only-if-cached' never reach the network, so they cannot leak cross-origin responses.
+		if request.mode == RequestMode::SameOrigin && request.cache != RequestCache::OnlyIfCached {
 			let response = network_error(&cx);
 			(cx, Ok(response))
 		} else if SCHEMES.contains(&scheme) {
@@ -226,7 +316,8 @@ async fn main_fetch(cx: Context, request: &mut Request, client: Client, redirect
 		} else if scheme == "https" || scheme == "http" {
 			if let Some(port) = request.url().port() {
 				if BAD_PORTS.contains(&port) {
-					return Ok(network_error(&cx));
+					let error = FetchError::new(None, None).message(format!("Port {} is blocked", port));
+					return Ok(network_error_with_cause(&cx, Some(error)));
 				}
 			}
 			if request.mode == RequestMode::NoCors {
@@ -240,6 +331,14 @@ async fn main_fetch(cx: Context, request: &mut Request, client: Client, redirect
 				cx.await_native_cx(|cx| http_fetch(cx, request, client, taint, redirections)).await;
 			opaque_redirect = opaque;
 			(cx, response)
+		} else if let Some(handler) = scheme_handler(&cx, scheme) {
+			let request = SchemeRequest {
+				method: request.method.clone(),
+				url: request.url().clone(),
+				headers: request.headers(&cx).clone(),
+				body: request.body.take(),
+			};
+			cx.await_native_cx(|cx| handler(cx, request)).await
 		} else {
 			let response = network_error(&cx);
 			(cx, Ok(response))
@@ -255,14 +354,8 @@ async fn main_fetch(cx: Context, request: &mut Request, client: Client, redirect
 
 	response.url.get_or_insert(request.url().clone());
 
-	let headers = Object::from(response.headers.to_local());
-	let headers = Headers::get_mut_private(&cx, &headers).unwrap();
-
 	if !opaque_redirect
-		&& taint == ResponseTaint::Opaque
-		&& response.status == Some(StatusCode::PARTIAL_CONTENT)
-		&& response.range_requested
-		&& !headers.headers.contains_key(RANGE)
+		&& is_blocked_range_response(taint, response.status, response.range_requested, request.headers(&cx))
 	{
 		let url = response.url.take().unwrap();
 		response = network_error(&cx);
@@ -272,85 +365,23 @@ async fn main_fetch(cx: Context, request: &mut Request, client: Client, redirect
 		return Ok(response);
 	}
 
-	if !opaque_redirect
-		&& (matches!(request.method, Method::HEAD | Method::CONNECT)
-		|| response.status.as_ref().map(StatusCode::as_u16) == Some(103) // Early Hints
-		|| matches!(
-				response.status,
-				Some(StatusCode::SWITCHING_PROTOCOLS)
-					| Some(StatusCode::NO_CONTENT)
-					| Some(StatusCode::RESET_CONTENT)
-					| Some(StatusCode::NOT_MODIFIED)
-			)) {
+	if !opaque_redirect && has_null_body(&request.method, response.status) {
 		response.body = Some(FetchBody::default());
 	}
 
-	if opaque_redirect {
-		response.kind = ResponseKind::OpaqueRedirect;
+	response.kind = filtered_kind(taint, opaque_redirect);
+	if response.kind.is_opaque() {
 		response.url = None;
 		response.status = None;
 		response.status_text = None;
 		response.body = Some(FetchBody::default());
-
-		headers.headers.clear();
-	} else {
-		match taint {
-			ResponseTaint::Basic => {
-				response.kind = ResponseKind::Basic;
-
-				for name in &FORBIDDEN_RESPONSE_HEADERS {
-					remove_all_header_entries(&mut headers.headers, name);
-				}
-			}
-			ResponseTaint::Cors => {
-				response.kind = ResponseKind::Cors;
-
-				let mut allows_all = false;
-				let allowed: Vec<_> = headers
-					.headers
-					.get_all(ACCESS_CONTROL_ALLOW_HEADERS)
-					.into_iter()
-					.map(|v| {
-						if v == "*" {
-							allows_all = true
-						}
-						v.clone()
-					})
-					.collect();
-				let mut to_remove = Vec::new();
-				if request.credentials != RequestCredentials::Include && allows_all {
-					for name in headers.headers.keys() {
-						if headers.headers.get_all(name).into_iter().size_hint().1.is_none() {
-							to_remove.push(name.clone());
-						}
-					}
-				} else {
-					for name in headers.headers.keys() {
-						let allowed = allowed.iter().any(|allowed| allowed.as_bytes() == name.as_str().as_bytes());
-						if allowed {
-							to_remove.push(name.clone());
-						}
-					}
-				}
-				for name in to_remove {
-					remove_all_header_entries(&mut headers.headers, &name);
-				}
-				for name in &FORBIDDEN_RESPONSE_HEADERS {
-					remove_all_header_entries(&mut headers.headers, name);
-				}
-			}
-			ResponseTaint::Opaque => {
-				response.kind = ResponseKind::Opaque;
-				response.url = None;
-				response.status = None;
-				response.status_text = None;
-				response.body = Some(FetchBody::default());
-
-				headers.headers.clear();
-			}
-		}
 	}
 
+	let headers = Object::from(response.headers.to_local());
+	let headers = Headers::get_mut_private(&cx, &headers).unwrap();
+	let credentials = request.credentials == RequestCredentials::Include;
+	filter_headers(&mut headers.headers, response.kind, credentials);
+
 	Ok(response)
 }
 
@@ -393,19 +424,99 @@ async fn scheme_fetch(cx: Context, scheme: &str, url: Url) -> Result<Response> {
 			Ok(response)
 		}
 		"file" => {
-			let path = url.to_file_path().unwrap();
-			let (cx, read) = cx.await_native(read(path)).await;
-			match read {
-				Ok(bytes) => {
-					let response = Response::new_from_bytes(&cx, Bytes::from(bytes), url);
-					let headers = Headers::new(HeadersKind::Immutable);
-					response.headers.set(Headers::new_object(&cx, Box::new(headers)));
-					Ok(response)
-				}
-				Err(_) => Ok(network_error(&cx)),
+			let Ok(path) = url.to_file_path() else {
+				return Ok(file_error_response(
+					&cx,
+					url,
+					StatusCode::BAD_REQUEST,
+					"Invalid file path",
+				));
+			};
+			// Permission is checked before the file system is accessed, so that scripts cannot tell whether files
+			// outside the allowed paths exist, and again after symbolic links are resolved.
+			if !can_read(&cx, &path) {
+				return Ok(file_read_denied_response(&cx, url));
+			}
+			let path = match canonicalize(&path) {
+				Ok(path) => path,
+				Err(error) => return Ok(file_io_error_response(&cx, url, error)),
+			};
+			if !can_read(&cx, &path) {
+				return Ok(file_read_denied_response(&cx, url));
 			}
+			if path.is_dir() {
+				return Ok(file_error_response(
+					&cx,
+					url,
+					StatusCode::FORBIDDEN,
+					"Directory listings are disabled",
+				));
+			}
+
+			let content_type = mime_type::from_path(&path);
+			let source = match DiskSource::new(path) {
+				Ok(source) => source,
+				Err(error) => return Ok(file_io_error_response(&cx, url, error)),
+			};
+			// The file is read as the body is pulled, so that large files are not buffered in memory.
+			let stream = disk_stream(&cx, &source)
+				.ok_or_else(|| Error::new("Failed to create stream for file", ErrorKind::Normal))?;
+			let mut response = Response::new_from_bytes(&cx, Bytes::new(), url);
+			response.body = Some(FetchBody {
+				body: FetchBodyInner::Stream(stream),
+				..Default::default()
+			});
+			let headers = Headers {
+				reflector: Reflector::default(),
+				headers: HeaderMap::from_iter([
+					(CONTENT_TYPE, HeaderValue::from_static(content_type)),
+					(CONTENT_LENGTH, HeaderValue::from(source.len())),
+				]),
+				kind: HeadersKind::Immutable,
+			};
+			response.headers.set(Headers::new_object(&cx, Box::new(headers)));
+			Ok(response)
+		}
+		_ => {
+			let error = FetchError::new(None, None).message(format!("Unsupported scheme: {}", scheme));
+			Ok(network_error_with_cause(&cx, Some(error)))
+		}
+	}
+}
+
+/// Creates the response for a `file:` URL outside the paths scripts are allowed to read, whether it exists or not.
+fn file_read_denied_response(cx: &Context, url: Url) -> Response {
+	file_error_response(cx, url, StatusCode::FORBIDDEN, "Read access denied")
+}
+
+/// Creates the response for a `file:` URL which could not be read, with the error message as the body.
+fn file_error_response(cx: &Context, url: Url, status: StatusCode, message: &str) -> Response {
+	let mut response = Response::new_from_bytes(cx, Bytes::from(String::from(message)), url);
+	response.status = Some(status);
+	response.status_text = status.canonical_reason().map(String::from);
+	let headers = Headers {
+		reflector: Reflector::default(),
+		headers: HeaderMap::from_iter(once((
+			CONTENT_TYPE,
+			HeaderValue::from_static("text/plain;charset=UTF-8"),
+		))),
+		kind: HeadersKind::Immutable,
+	};
+	response.headers.set(Headers::new_object(cx, Box::new(headers)));
+	response
+}
+
+fn file_io_error_response(cx: &Context, url: Url, error: io::Error) -> Response {
+	match error.kind() {
+		io::ErrorKind::NotFound => file_error_response(cx, url, StatusCode::NOT_FOUND, "File not found"),
+		io::ErrorKind::PermissionDenied => file_error_response(cx, url, StatusCode::FORBIDDEN, "Permission denied"),
+		kind => {
+			let error = FetchError {
+				io_error_kind: Some(kind),
+				..FetchError::new(None, None).message(error.to_string())
+			};
+			network_error_with_cause(cx, Some(error))
 		}
-		_ => Ok(network_error(&cx)),
 	}
 }
 
@@ -431,6 +542,7 @@ async fn http_fetch(
 
 #[async_recursion(?Send)]
 async fn http_network_fetch(cx: Context, req: &mut Request, client: Client, is_new: bool) -> Result<Response> {
+	let timing = ResponseTiming::started();
 	let request = req.try_clone(&cx)?;
 	let headers = Object::from(req.headers.to_local());
 	let headers = Headers::get_mut_private(&cx, &headers).unwrap();
@@ -467,6 +579,15 @@ async fn http_network_fetch(cx: Context, req: &mut Request, client: Client, is_n
 		headers.append(REFERER, HeaderValue::from_str(url.as_str()).unwrap());
 	}
 
+	let include_credentials = request.credentials != RequestCredentials::Omit;
+	if include_credentials && !headers.contains_key(COOKIE) {
+		if let Some(cookies) = client.cookie_jar().cookie_header(request.url()) {
+			if let Ok(cookies) = HeaderValue::from_str(&cookies) {
+				headers.append(COOKIE, cookies);
+			}
+		}
+	}
+
 	if !headers.contains_key(USER_AGENT) {
 		headers.append(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
 	}
@@ -514,36 +635,132 @@ async fn http_network_fetch(cx: Context, req: &mut Request, client: Client, is_n
 		headers.append(HOST, HeaderValue::from_str(&host).unwrap());
 	}
 
-	if request.cache == RequestCache::OnlyIfCached {
-		return Ok(network_error(&cx));
+	let http_cache = Arc::clone(client.http_cache());
+	let stored = if cache != RequestCache::NoStore && cache != RequestCache::Reload && request.method == Method::GET {
+		http_cache.get(req.url()).filter(|stored| stored.matches(headers))
+	} else {
+		None
+	};
+
+	match stored {
+		Some(stored)
+			if matches!(cache, RequestCache::ForceCache | RequestCache::OnlyIfCached)
+				|| (cache == RequestCache::Default && stored.is_fresh()) =>
+		{
+			return Ok(cached_response(&cx, stored, req.url().clone()));
+		}
+		None if cache == RequestCache::OnlyIfCached => {
+			let error = FetchError::new(None, None).message("No cached response is available");
+			return Ok(network_error_with_cause(&cx, Some(error)));
+		}
+		_ => {}
+	}
+
+	// Stale responses are revalidated with a conditional request
+	if let Some(stored) = &stored {
+		if let Some(etag) = stored.headers.get(ETAG) {
+			if !headers.contains_key(IF_NONE_MATCH) {
+				headers.append(IF_NONE_MATCH, etag.clone());
+			}
+		}
+		if let Some(modified) = stored.headers.get(LAST_MODIFIED) {
+			if !headers.contains_key(IF_MODIFIED_SINCE) {
+				headers.append(IF_MODIFIED_SINCE, modified.clone());
+			}
+		}
 	}
 
 	let range_requested = headers.contains_key(RANGE);
+	let request_headers = headers.clone();
+
+	// Requests over the concurrency limit of their origin wait until the responses to earlier requests have been read
+	let (cx, permit) = cx.await_native(client.acquire(req.url())).await;
+	let permit = match permit {
+		Ok(permit) => permit,
+		Err(error) => return Ok(network_error_with_cause(&cx, Some(error))),
+	};
 
 	// We check for the existence of a request body above, so we can safely unwrap here
 	let hyper_body = request.body.unwrap().into_http_body(cx.duplicate());
 	let (hyper_body, body_fut) = hyper_body?;
-	let Ok(hyper_request) = request_builder.body(hyper_body) else {
+	let Ok(mut hyper_request) = request_builder.body(hyper_body) else {
 		return Ok(network_error(&cx));
 	};
 
+	// Proxy credentials are only added to the outgoing request, so that they are never visible to scripts
+	if let Some(authorization) = client.proxy_authorization(hyper_request.uri()) {
+		hyper_request.headers_mut().insert(PROXY_AUTHORIZATION, authorization);
+	}
+
+	let url = Some(req.url().clone());
 	let (cx, hyper_response) = match body_fut {
-		None => cx.await_native(client.request(hyper_request)).await,
+		None => cx.await_native(client.send(hyper_request, url)).await,
 		Some(f) => {
 			// See comment on [FetchBody::into_http_body]. We have to run
 			// both futures simultaneously, giving us this lovely bit of
 			// code.
 			let cx2 = cx.duplicate();
 			drop(cx);
-			let res = futures::join!(client.request(hyper_request), f);
+			let res = futures::join!(client.send(hyper_request, url), f);
 			(cx2, res.0)
 		}
 	};
-	let hyper_response = hyper_response?;
-	let mut response = Response::from_hyper_response(&cx, hyper_response, req.url().clone())?;
+	let mut hyper_response = match hyper_response {
+		Ok(hyper_response) => hyper_response,
+		Err(error) => return Ok(network_error_with_cause(&cx, Some(error))),
+	};
+	timing.mark_headers_received();
+
+	// Storable responses are stored once their body has been read, as it is streamed to the script
+	let status = hyper_response.status();
+	let revalidated = stored.is_some() && status == StatusCode::NOT_MODIFIED;
+	if cache != RequestCache::NoStore && !revalidated {
+		let response = CachedResponse::new(
+			&request.method,
+			&request_headers,
+			status,
+			status_text(&hyper_response),
+			hyper_response.headers(),
+			Bytes::new(),
+		);
+		if let Some(response) = response {
+			let url = req.url().clone();
+			hyper_response = hyper_response.map(|body| store_body(Arc::clone(&http_cache), url, response, body));
+		}
+	}
+	let hyper_response = hyper_response.map(|body| permit.hold_until_read(body));
+
+	let mut response = Response::from_hyper_response_with_timing(
+		&cx,
+		hyper_response,
+		req.url().clone(),
+		&request.method,
+		Some(timing.clone()),
+	)?;
+
+	if include_credentials {
+		let cookie_jar = client.cookie_jar();
+		for cookie in response.headers(&cx).get_all(SET_COOKIE) {
+			if let Ok(cookie) = cookie.to_str() {
+				cookie_jar.set_cookie(req.url(), cookie);
+			}
+		}
+	}
 
 	response.range_requested = range_requested;
 
+	if let Some(mut stored) = stored {
+		if response.status == Some(StatusCode::NOT_MODIFIED) {
+			stored.refresh(response.headers(&cx));
+			http_cache.put(req.url(), stored.clone());
+			return Ok(cached_response(&cx, stored, req.url().clone()));
+		}
+	}
+
+	if !request.method.is_safe() && !status.is_client_error() && !status.is_server_error() {
+		http_cache.remove(req.url());
+	}
+
 	if response.status == Some(StatusCode::PROXY_AUTHENTICATION_REQUIRED) && !req.client_window {
 		return Ok(network_error(&cx));
 	}
@@ -555,6 +772,28 @@ async fn http_network_fetch(cx: Context, req: &mut Request, client: Client, is_n
 	Ok(response)
 }
 
+fn cached_response(cx: &Context, stored: CachedResponse, url: Url) -> Response {
+	let age = stored.current_age().as_secs();
+	let mut response = Response::new_from_bytes(cx, stored.body, url);
+	response.status = Some(stored.status);
+	response.status_text = stored.status_text;
+
+	let timing = ResponseTiming::started();
+	timing.mark_headers_received();
+	timing.mark_body_complete();
+	response.timing = Some(timing);
+
+	let mut headers = stored.headers;
+	headers.insert(AGE, HeaderValue::from(age));
+	let headers = Headers {
+		reflector: Reflector::default(),
+		headers,
+		kind: HeadersKind::Immutable,
+	};
+	response.headers.set(Headers::new_object(cx, Box::new(headers)));
+	response
+}
+
 async fn http_redirect_fetch(
 	cx: Context, request: &mut Request, response: Response, client: Client, taint: ResponseTaint, redirections: u8,
 ) -> Result<Response> {
@@ -565,11 +804,10 @@ async fn http_redirect_fetch(
 		Some(0) => return Ok(response),
 		None => return Ok(network_error(&cx)),
 		_ => {
-			let location = location.next().unwrap();
-			match Url::options()
-				.base_url(response.url.as_ref())
-				.parse(str::from_utf8(location.as_bytes()).unwrap())
-			{
+			let Ok(location) = location.next().unwrap().to_str() else {
+				return Ok(network_error(&cx));
+			};
+			match Url::options().base_url(response.url.as_ref()).parse(location) {
 				Ok(mut url) => {
 					if url.fragment().is_none() {
 						url.set_fragment(response.url.as_ref().and_then(Url::fragment));
@@ -586,26 +824,36 @@ async fn http_redirect_fetch(
 	}
 
 	if redirections >= 20 {
-		return Ok(network_error(&cx));
+		let error = FetchError::new(Some(FetchErrorPhase::Response), None).message("Too many redirects");
+		return Ok(network_error_with_cause(&cx, Some(error)));
 	}
 
 	if taint == ResponseTaint::Cors && (location.username() != "" || location.password().is_some()) {
 		return Ok(network_error(&cx));
 	}
 
-	if ((response.status == Some(StatusCode::MOVED_PERMANENTLY) || response.status == Some(StatusCode::FOUND))
-		&& request.method == Method::POST)
-		|| (response.status == Some(StatusCode::SEE_OTHER)
-			&& (request.method != Method::GET || request.method != Method::HEAD))
-	{
-		request.method = Method::GET;
-		request.body = Some(FetchBody::default());
+	let status = response.status.unwrap_or_default();
+	let changes_to_get = redirect_changes_to_get(status, &request.method);
+	if !changes_to_get && !can_resend_body(request.body.as_ref()) {
+		let error = FetchError::new(Some(FetchErrorPhase::Response), None)
+			.message("Cannot follow redirect which resends a streamed body");
+		return Ok(network_error_with_cause(&cx, Some(error)));
+	}
+
+	let cross_origin = is_cross_origin_redirect(request.url(), &location);
+	if changes_to_get || cross_origin {
 		let headers = Object::from(request.headers.to_local());
 		let headers = Headers::get_mut_private(&cx, &headers).unwrap();
-		remove_all_header_entries(&mut headers.headers, &CONTENT_ENCODING);
-		remove_all_header_entries(&mut headers.headers, &CONTENT_LANGUAGE);
-		remove_all_header_entries(&mut headers.headers, &CONTENT_LOCATION);
-		remove_all_header_entries(&mut headers.headers, &CONTENT_TYPE);
+		if changes_to_get {
+			strip_request_body_headers(&mut headers.headers);
+		}
+		if cross_origin {
+			strip_credential_headers(&mut headers.headers);
+		}
+	}
+	if changes_to_get {
+		request.method = Method::GET;
+		request.body = Some(FetchBody::default());
 	}
 
 	request.locations.push(location.clone());
@@ -613,7 +861,7 @@ async fn http_redirect_fetch(
 	let policy = headers.headers.get_all(REFERRER_POLICY).into_iter().rev();
 	let policy = policy
 		.filter(|v| !v.is_empty())
-		.find_map(|v| ReferrerPolicy::from_str(str::from_utf8(v.as_bytes()).unwrap()).ok());
+		.find_map(|v| v.to_str().ok().and_then(|v| ReferrerPolicy::from_str(v).ok()));
 	if let Some(policy) = policy {
 		request.referrer_policy = policy;
 	}
@@ -622,7 +870,14 @@ async fn http_redirect_fetch(
 }
 
 pub fn define(cx: &Context, global: &Object) -> bool {
-	let _ = GLOBAL_CLIENT.set(default_client());
-	global.define_method(cx, "fetch", fetch, 1, PropertyFlags::empty());
-	Headers::init_class(cx, global).0 && Request::init_class(cx, global).0 && Response::init_class(cx, global).0
+	let fetch = global.define_method(cx, "fetch", fetch, 1, PropertyFlags::empty());
+	let fetch = fetch.to_object(cx);
+	fetch.define_method(cx, "download", download, 2, PropertyFlags::empty());
+	fetch.define_method(cx, "paginate", paginate, 1, PropertyFlags::empty());
+	Headers::init_class(cx, global).0
+		&& Request::init_class(cx, global).0
+		&& Response::init_class(cx, global).0
+		&& FetchEvent::init_class(cx, global).0
+		&& PageIterator::init_class(cx, global).0
+		&& cache_storage::define(cx, global)
 }
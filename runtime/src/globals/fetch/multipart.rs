@@ -0,0 +1,148 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{future, io};
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt, stream, TryStreamExt};
+
+use ion::{Context, Error, ErrorKind, Result};
+use ion::class::NativeObject;
+
+use crate::globals::file::{BlobData, File};
+use crate::globals::form_data::{FormData, FormDataEntryValue};
+
+const CRLF: &[u8] = b"\r\n";
+
+#[derive(Clone, Debug)]
+struct MultipartFile {
+	name: String,
+	content_type: String,
+}
+
+#[derive(Clone, Debug)]
+struct MultipartPart {
+	name: String,
+	file: Option<MultipartFile>,
+	body: BlobData,
+}
+
+impl MultipartPart {
+	fn header(&self, boundary: &str) -> Bytes {
+		let header = match &self.file {
+			None => format!(
+				"--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+				boundary,
+				escape(&self.name)
+			),
+			Some(file) => format!(
+				"--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+				boundary,
+				escape(&self.name),
+				escape(&file.name),
+				file.content_type
+			),
+		};
+		Bytes::from(header)
+	}
+}
+
+/// `multipart/form-data` body of a [FormData], which is serialised as it is read.
+///
+/// File contents are shared with their [Blob](crate::globals::file::Blob) instead of being copied,
+/// and files on disk are streamed as the body is read.
+#[derive(Clone, Debug)]
+pub struct MultipartForm {
+	boundary: String,
+	parts: Vec<MultipartPart>,
+}
+
+impl MultipartForm {
+	pub fn from_form_data(cx: &Context, form_data: &FormData) -> Result<MultipartForm> {
+		let parts = form_data
+			.all_pairs()
+			.map(|kv| match &kv.value {
+				FormDataEntryValue::String(value) => MultipartPart {
+					name: kv.key.clone(),
+					file: None,
+					body: Bytes::from(value.clone()),
+				},
+				FormDataEntryValue::File(file) => {
+					let file = File::get_private(cx, &file.root(cx).into()).unwrap();
+					let content_type = file
+						.blob
+						.kind()
+						.filter(|kind| !kind.is_empty())
+						.unwrap_or_else(|| String::from("application/octet-stream"));
+					MultipartPart {
+						name: kv.key.clone(),
+						file: Some(MultipartFile { name: file.name.clone(), content_type }),
+						body: file.blob.data().clone(),
+					}
+				}
+			})
+			.collect();
+
+		Ok(MultipartForm { boundary: boundary()?, parts })
+	}
+
+	pub fn content_type(&self) -> String {
+		format!("multipart/form-data; boundary={}", self.boundary)
+	}
+
+	/// Returns the length of the serialised body, without serialising it.
+	pub fn len(&self) -> usize {
+		let parts: usize = self
+			.parts
+			.iter()
+			.map(|part| part.header(&self.boundary).len() + part.body.len() as usize + CRLF.len())
+			.sum();
+		parts + self.closing().len()
+	}
+
+	/// Serialised forms always contain the closing boundary, even without any parts.
+	pub fn is_empty(&self) -> bool {
+		false
+	}
+
+	pub fn into_stream(self) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+		let closing = self.closing();
+		let boundary = self.boundary;
+		stream::iter(self.parts)
+			.flat_map(move |part| {
+				let header = stream::once(future::ready(Ok(part.header(&boundary))));
+				let crlf = stream::once(future::ready(Ok(Bytes::from_static(CRLF))));
+				header.chain(part.body.stream()).chain(crlf)
+			})
+			.chain(stream::once(future::ready(Ok(closing))))
+	}
+
+	pub async fn into_bytes(self) -> io::Result<Bytes> {
+		let mut bytes = Vec::with_capacity(self.len());
+		let mut stream = Box::pin(self.into_stream());
+		while let Some(chunk) = stream.try_next().await? {
+			bytes.extend_from_slice(&chunk);
+		}
+		Ok(Bytes::from(bytes))
+	}
+
+	fn closing(&self) -> Bytes {
+		Bytes::from(format!("--{}--\r\n", self.boundary))
+	}
+}
+
+fn boundary() -> Result<String> {
+	let mut bytes = [0; 16];
+	getrandom::getrandom(&mut bytes)
+		.map_err(|error| Error::new(format!("Failed to generate boundary: {}", error), ErrorKind::Normal))?;
+	let random: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+	Ok(format!("----SpiderfireFormBoundary{}", random))
+}
+
+/// Escapes names and file names in the `Content-Disposition` header of a part.
+fn escape(name: &str) -> String {
+	name.replace('\n', "%0A").replace('\r', "%0D").replace('"', "%22")
+}
@@ -0,0 +1,216 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use http::HeaderMap;
+use http::header::LINK;
+use mozjs::jsapi::{JSFunction, JSObject};
+use url::Url;
+
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Function, Heap, Object, Promise, PromiseFuture, Result,
+	ResultExc, TracedHeap, Value,
+};
+use ion::class::Reflector;
+use ion::conversions::{FromValue, ToValue};
+use ion::function::Opt;
+use ion::symbol::WellKnownSymbolCode;
+
+use crate::globals::fetch::{fetch_internal, Headers, new_fetch_request, Request, RequestInfo, RequestInit, Response};
+use crate::promise::future_to_promise;
+
+#[derive(Default, FromValue)]
+pub struct PaginateOptions<'cx> {
+	#[ion(inherit)]
+	init: RequestInit<'cx>,
+	next_link: Option<Function<'cx>>,
+}
+
+/// Fetches the pages of a paginated resource lazily, as they are iterated with `for await`.
+///
+/// Each page is requested with the same options as the first, once the previous page has been yielded. The next page
+/// is the target of the `Link` header with `rel="next"`, or the URL returned by `nextLink`, which is called with each
+/// response and may return a promise. Iteration ends once there is no next page.
+#[js_fn]
+pub(crate) fn paginate(
+	cx: &Context, resource: RequestInfo, Opt(options): Opt<PaginateOptions>,
+) -> Result<*mut JSObject> {
+	let PaginateOptions { init, next_link } = options.unwrap_or_default();
+	let request = Request::constructor(cx, resource, Opt(Some(init)))?;
+	let url = request.url().clone();
+
+	let iterator = PageIterator {
+		reflector: Reflector::default(),
+		request: Heap::new(Request::new_object(cx, Box::new(request))),
+		next_link: next_link.map(|next_link| Heap::new(next_link.to_object(cx).handle().get())),
+		state: Rc::new(RefCell::new(PageState { next: Some(url), previous: None })),
+	};
+	Ok(PageIterator::new_object(cx, Box::new(iterator)))
+}
+
+struct PageState {
+	next: Option<Url>,
+	previous: Option<Promise>,
+}
+
+#[js_class]
+pub struct PageIterator {
+	reflector: Reflector,
+	request: Heap<*mut JSObject>,
+	next_link: Option<Heap<*mut JSObject>>,
+	#[trace(no_trace)]
+	state: Rc<RefCell<PageState>>,
+}
+
+#[js_class]
+impl PageIterator {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<PageIterator> {
+		Err(Error::new("Cannot construct this type", ErrorKind::Type))
+	}
+
+	/// Fetches the next page, after the page requested by the previous call has been fetched.
+	pub fn next(&self, cx: &Context) -> Option<Promise> {
+		let request = TracedHeap::new(self.request.get());
+		let next_link = self.next_link.as_ref().map(|next_link| {
+			let next_link = Function::from_object(cx, &next_link.root(cx)).unwrap();
+			TracedHeap::new(next_link.get())
+		});
+		let state = Rc::clone(&self.state);
+		let previous = state.borrow_mut().previous.take();
+
+		let promise = unsafe {
+			future_to_promise(cx, move |cx| async move {
+				let cx = match previous {
+					Some(previous) => PromiseFuture::new(cx, &previous).await.0,
+					None => cx,
+				};
+				let url = state.borrow_mut().next.take();
+				match url {
+					Some(url) => fetch_page(cx, &request, next_link.as_ref(), &state, url).await,
+					None => Ok(iterator_result(&cx, None)),
+				}
+			})
+		}?;
+		self.state.borrow_mut().previous = Some(promise.clone());
+		Some(promise)
+	}
+
+	/// Stops the iteration, such as when a `for await` loop is exited early.
+	#[ion(name = "return")]
+	pub fn r#return(&self, cx: &Context) -> Promise {
+		self.state.borrow_mut().next = None;
+		Promise::resolved(cx, iterator_result(cx, None))
+	}
+
+	#[ion(name = WellKnownSymbolCode::AsyncIterator)]
+	pub fn async_iterator(&self) -> *mut JSObject {
+		self.reflector.get()
+	}
+}
+
+async fn fetch_page(
+	cx: Context, template: &TracedHeap<*mut JSObject>, next_link: Option<&TracedHeap<*mut JSFunction>>,
+	state: &RefCell<PageState>, url: Url,
+) -> ResultExc<*mut JSObject> {
+	let (request, client) = {
+		let template = Object::from(template.to_local());
+		let template = Request::get_private(&cx, &template)?;
+		new_fetch_request(&cx, RequestInfo::Request(template), Opt(None))?
+	};
+	let request = Object::from(request.to_local());
+	Request::get_mut_private(&cx, &request)?.locations = vec![url.clone()];
+
+	let (cx, response) = cx.await_native_cx(|cx| fetch_internal(cx, &request, client)).await;
+	let response = TracedHeap::new(response?);
+
+	let (cx, next) = match next_link {
+		Some(next_link) => extract_next_link(cx, next_link, &response).await,
+		None => {
+			let response = Object::from(response.to_local());
+			let response = Response::get_private(&cx, &response)?;
+			let headers = Object::from(response.headers.root(&cx));
+			let next = link_header_next(&Headers::get_private(&cx, &headers)?.headers);
+			(cx, Ok(next))
+		}
+	};
+
+	let base = Response::get_private(&cx, &Object::from(response.to_local()))?.url.clone().unwrap_or(url);
+	if let Some(next) = next? {
+		state.borrow_mut().next = Some(base.join(&next)?);
+	}
+	Ok(iterator_result(&cx, Some(response.get())))
+}
+
+/// Calls `nextLink` with the response, and waits for the URL of the next page, if there is one.
+async fn extract_next_link(
+	cx: Context, next_link: &TracedHeap<*mut JSFunction>, response: &TracedHeap<*mut JSObject>,
+) -> (Context, ResultExc<Option<String>>) {
+	let result = {
+		let next_link = Function::from(next_link.root(&cx));
+		let response = Value::object(&cx, &Object::from(response.root(&cx)));
+		next_link.call(&cx, &Object::global(&cx), &[response]).map_err(|report| {
+			report.map(|report| report.exception).unwrap_or_else(|| {
+				Exception::Error(Error::new("Unknown failure in nextLink callback", ErrorKind::Normal))
+			})
+		})
+	};
+	let promise = match result {
+		Ok(result) => Promise::resolved(&cx, result),
+		Err(exception) => return (cx, Err(exception)),
+	};
+
+	let (cx, result) = PromiseFuture::new(cx, &promise).await;
+	let result = match result {
+		Ok(next) => {
+			let next = Value::from(cx.root(next.get()));
+			if next.handle().is_null_or_undefined() {
+				Ok(None)
+			} else {
+				String::from_value(&cx, &next, false, ()).map(Some).map_err(Exception::Error)
+			}
+		}
+		Err(exception) => Err(Exception::Other(exception.get())),
+	};
+	(cx, result)
+}
+
+/// Finds the target of the first link with `rel="next"` in the `Link` headers.
+fn link_header_next(headers: &HeaderMap) -> Option<String> {
+	headers.get_all(LINK).iter().filter_map(|value| value.to_str().ok()).find_map(|value| {
+		let mut rest = value;
+		while let Some(start) = rest.find('<') {
+			let end = start + rest[start..].find('>')?;
+			let target = &rest[start + 1..end];
+			rest = &rest[end + 1..];
+
+			let params = &rest[..rest.find(',').unwrap_or(rest.len())];
+			let is_next = params.split(';').filter_map(|param| param.split_once('=')).any(|(name, value)| {
+				name.trim().eq_ignore_ascii_case("rel")
+					&& value
+						.trim()
+						.trim_matches('"')
+						.split_ascii_whitespace()
+						.any(|rel| rel.eq_ignore_ascii_case("next"))
+			});
+			if is_next {
+				return Some(String::from(target));
+			}
+		}
+		None
+	})
+}
+
+fn iterator_result(cx: &Context, response: Option<*mut JSObject>) -> *mut JSObject {
+	let result = Object::new(cx);
+	if let Some(response) = response {
+		result.set_as(cx, "value", &response);
+	}
+	result.set_as(cx, "done", &response.is_none());
+	result.handle().get()
+}
@@ -0,0 +1,61 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use http::{HeaderMap, HeaderName, Method, StatusCode};
+use http::header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_LANGUAGE, CONTENT_LOCATION, CONTENT_TYPE, COOKIE};
+use url::Url;
+
+use crate::globals::fetch::{FetchBody, FetchBodyInner};
+use crate::globals::fetch::header::remove_all_header_entries;
+
+/// Headers which describe the body of a request, and are removed when a redirect drops the body.
+pub static REQUEST_BODY_HEADERS: [HeaderName; 4] = [CONTENT_ENCODING, CONTENT_LANGUAGE, CONTENT_LOCATION, CONTENT_TYPE];
+
+/// Headers which carry credentials, and are removed when a request is redirected to a different origin.
+pub static CREDENTIAL_HEADERS: [HeaderName; 2] = [AUTHORIZATION, COOKIE];
+
+/// Checks if following a redirect with the status changes the method of the request to `GET` and drops its body.
+///
+/// `301` and `302` only change `POST` requests, and `303` changes all requests except `GET` and `HEAD`.
+/// `307` and `308` always preserve the method and body.
+pub fn redirect_changes_to_get(status: StatusCode, method: &Method) -> bool {
+	match status {
+		StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => method == Method::POST,
+		StatusCode::SEE_OTHER => method != Method::GET && method != Method::HEAD,
+		_ => false,
+	}
+}
+
+/// Checks if a redirect from one URL to another crosses origins, which requires credentials to be removed.
+pub fn is_cross_origin_redirect(from: &Url, to: &Url) -> bool {
+	from.origin() != to.origin()
+}
+
+/// Checks if the body can be sent again after a redirect which preserves it.
+///
+/// Streams which were not created from a source, such as the bodies of incoming requests, and bodies read from native
+/// sources can only be read once.
+pub fn can_resend_body(body: Option<&FetchBody>) -> bool {
+	match body {
+		Some(FetchBody {
+			body: FetchBodyInner::Stream(_), source, ..
+		}) => source.is_some(),
+		Some(FetchBody { body: FetchBodyInner::Reader(_), .. }) => false,
+		_ => true,
+	}
+}
+
+pub fn strip_request_body_headers(headers: &mut HeaderMap) {
+	for name in &REQUEST_BODY_HEADERS {
+		remove_all_header_entries(headers, name);
+	}
+}
+
+pub fn strip_credential_headers(headers: &mut HeaderMap) {
+	for name in &CREDENTIAL_HEADERS {
+		remove_all_header_entries(headers, name);
+	}
+}
@@ -6,23 +6,22 @@
 
 use std::str::FromStr;
 
-use bytes::Bytes;
-use http::HeaderMap;
-use http::header::CONTENT_TYPE;
-use hyper::Method;
-use ion::string::byte::ByteString;
+use http::{HeaderMap, HeaderValue};
+use http::header::{CONTENT_TYPE, HOST};
+use hyper::{Body, Method};
+use ion::string::byte::header;
 use ion::{TracedHeap, HeapPointer, Heap, Object};
 use ion::typedarray::{ArrayBufferWrapper, Uint8ArrayWrapper};
 use mozjs::jsapi::JSObject;
 use url::Url;
 
-use ion::{ClassDefinition, Context, Error, ErrorKind, Result, Promise};
+use ion::{ClassDefinition, Context, Error, ErrorKind, Result, ResultExc, Promise, Value};
 use ion::class::{Reflector, NativeObject};
 use ion::function::Opt;
 pub use options::*;
 
 use crate::globals::abort::AbortSignal;
-use crate::globals::fetch::body::FetchBody;
+use crate::globals::fetch::body::{FetchBody, hyper_body_to_stream};
 use crate::globals::fetch::header::HeadersKind;
 use crate::globals::fetch::Headers;
 use crate::promise::future_to_promise;
@@ -68,9 +67,71 @@ pub struct Request {
 
 	pub(crate) client_window: bool,
 	pub(crate) signal_object: Heap<*mut JSObject>,
+
+	pub(crate) raw_header_case: bool,
 }
 
 impl Request {
+	/// Creates a request from one received by a hyper server, so that it can be handled by scripts.
+	///
+	/// The URL is taken from the request target if it is absolute, or from the `Host` header otherwise.
+	/// The body is streamed from the incoming request as it is read, rather than being buffered.
+	pub fn from_hyper_request(cx: &Context, request: hyper::Request<Body>) -> Result<Request> {
+		let url = if request.uri().scheme().is_some() {
+			Url::parse(&request.uri().to_string())?
+		} else {
+			let host = request.headers().get(HOST).and_then(|host| host.to_str().ok());
+			let host = host.ok_or_else(|| Error::new("Received request without a Host header", ErrorKind::Type))?;
+			let path = request.uri().path_and_query().map_or("/", |path| path.as_str());
+			Url::parse(&format!("http://{}{}", host, path))?
+		};
+
+		let (parts, body) = request.into_parts();
+		let headers = Headers {
+			reflector: Reflector::default(),
+			headers: parts.headers,
+			kind: HeadersKind::Request,
+		};
+
+		let body = if parts.method == Method::GET || parts.method == Method::HEAD {
+			FetchBody::default()
+		} else {
+			FetchBody {
+				body: FetchBodyInner::Stream(hyper_body_to_stream(cx, body).ok_or_else(Error::none)?),
+				..Default::default()
+			}
+		};
+
+		Ok(Request {
+			reflector: Reflector::default(),
+
+			method: parts.method,
+			headers: Heap::new(Headers::new_object(cx, Box::new(headers))),
+			body: Some(body),
+			body_used: false,
+
+			locations: vec![url],
+
+			referrer: Referrer::default(),
+			referrer_policy: ReferrerPolicy::default(),
+
+			mode: RequestMode::default(),
+			credentials: RequestCredentials::default(),
+			cache: RequestCache::default(),
+			redirect: RequestRedirect::default(),
+
+			integrity: String::new(),
+
+			unsafe_request: false,
+			keepalive: false,
+
+			client_window: false,
+			signal_object: Heap::new(AbortSignal::new_object(cx, Box::default())),
+
+			raw_header_case: false,
+		})
+	}
+
 	pub fn url(&self) -> &Url {
 		self.locations.last().unwrap()
 	}
@@ -104,6 +165,7 @@ impl Request {
 				body: FetchBodyInner::None,
 				source: None,
 				kind: None,
+				length_hint: None,
 			});
 		}
 		match self.body.take() {
@@ -152,6 +214,8 @@ impl Request {
 
 			client_window: self.client_window,
 			signal_object: Heap::new(self.signal_object.get()),
+
+			raw_header_case: self.raw_header_case,
 		})
 	}
 
@@ -192,6 +256,8 @@ impl Request {
 
 			client_window: self.client_window,
 			signal_object: Heap::new(self.signal_object.get()),
+
+			raw_header_case: self.raw_header_case,
 		})
 	}
 }
@@ -240,6 +306,8 @@ impl Request {
 
 					client_window: true,
 					signal_object: Heap::new(AbortSignal::new_object(cx, Box::default())),
+
+					raw_header_case: false,
 				}
 			}
 		};
@@ -263,11 +331,7 @@ impl Request {
 				request.referrer_policy = policy;
 			}
 
-			let mode = init.mode.or(fallback_cors.then_some(RequestMode::Cors));
-			if let Some(mode) = mode {
-				if mode == RequestMode::Navigate {
-					return Err(Error::new("Received 'navigate' mode", ErrorKind::Type));
-				}
+			if let Some(mode) = init.mode.or(fallback_cors.then_some(RequestMode::Cors)) {
 				request.mode = mode;
 			}
 
@@ -291,6 +355,10 @@ impl Request {
 				request.signal_object.set(signal_object);
 			}
 
+			if let Some(raw_header_case) = init.raw_header_case {
+				request.raw_header_case = raw_header_case;
+			}
+
 			if let Some(mut method) = init.method {
 				method.make_ascii_uppercase();
 				let method = Method::from_str(&method)?;
@@ -348,6 +416,32 @@ impl Request {
 		Ok(request)
 	}
 
+	/// Creates a request with the JSON serialisation of `data` as its body.
+	/// The method defaults to `POST`, and the `Content-Type` header to `application/json`.
+	#[ion(name = "json")]
+	pub fn static_json(
+		cx: &Context, info: RequestInfo, data: Value, Opt(init): Opt<RequestInit>,
+	) -> ResultExc<*mut JSObject> {
+		let text = ion::json::stringify(cx, data)?;
+		let text_bytes: Vec<_> = text.into();
+		let body = FetchBody {
+			body: FetchBodyInner::Bytes(text_bytes.into()),
+			..Default::default()
+		};
+
+		let mut init = init.unwrap_or_default();
+		init.method.get_or_insert_with(|| String::from("POST"));
+		init.body = Some(body);
+
+		let request = Request::constructor(cx, info, Opt(Some(init)))?;
+		let headers = &mut request.get_headers_object_mut(cx).headers;
+		if !headers.contains_key(CONTENT_TYPE) {
+			headers.append(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+		}
+
+		Ok(Request::new_object(cx, Box::new(request)))
+	}
+
 	#[ion(get)]
 	pub fn get_method(&self) -> String {
 		self.method.to_string()
@@ -358,6 +452,14 @@ impl Request {
 		self.url().to_string()
 	}
 
+	#[ion(inspect)]
+	pub fn inspect<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "method", &self.get_method());
+		object.set_as(cx, "url", &self.get_url());
+		object
+	}
+
 	#[ion(get)]
 	pub fn get_headers(&self) -> *mut JSObject {
 		self.headers.get()
@@ -408,6 +510,11 @@ impl Request {
 		self.keepalive
 	}
 
+	#[ion(get)]
+	pub fn get_raw_header_case(&self) -> bool {
+		self.raw_header_case
+	}
+
 	#[ion(get)]
 	pub fn get_is_reload_navigation(&self) -> bool {
 		false
@@ -431,11 +538,7 @@ impl Request {
 	#[ion(get)]
 	pub fn get_body(&mut self, cx: &Context) -> ion::Result<*mut JSObject> {
 		let body = self.take_body()?;
-		let stream = match body.body {
-			FetchBodyInner::None => ion::ReadableStream::from_bytes(cx, Bytes::from(vec![])),
-			FetchBodyInner::Bytes(bytes) => ion::ReadableStream::from_bytes(cx, bytes),
-			FetchBodyInner::Stream(stream) => stream,
-		};
+		let stream = body.body.into_stream(cx)?;
 		Ok(stream.get())
 	}
 
@@ -453,7 +556,7 @@ impl Request {
 				let body = this.take_body()?;
 				let (_, bytes) = cx.await_native_cx(|cx| body.into_bytes(cx)).await;
 				let bytes = bytes?.unwrap_or_default();
-				Ok(ArrayBufferWrapper::from(bytes.as_ref()))
+				Ok(ArrayBufferWrapper::from_bytes(bytes))
 			})
 		}
 	}
@@ -466,7 +569,7 @@ impl Request {
 				let body = this.take_body()?;
 				let (_, bytes) = cx.await_native_cx(|cx| body.into_bytes(cx)).await;
 				let bytes = bytes?.unwrap_or_default();
-				Ok(Uint8ArrayWrapper::from(bytes.as_ref()))
+				Ok(Uint8ArrayWrapper::from_bytes(bytes))
 			})
 		}
 	}
@@ -478,7 +581,7 @@ impl Request {
 				let this = Self::get_mut_private(&cx, &this.root(&cx).into()).unwrap();
 				let body = this.take_body()?;
 				let headers = this.get_headers_object(&cx);
-				let header = headers.get(ByteString::from(CONTENT_TYPE.to_string().into()).unwrap()).unwrap();
+				let header = headers.get(header::CONTENT_TYPE.to_byte_string())?;
 				body.into_blob(cx, header).await
 			})
 		}
@@ -493,8 +596,9 @@ impl Request {
 		let this = TracedHeap::new(self.reflector.get());
 		unsafe {
 			future_to_promise(cx, move |cx| async move {
-				let body = Self::get_mut_private(&cx, &cx.root(this.to_ptr()).into()).unwrap().take_body()?;
-				body.into_json(cx).await
+				let this = Self::get_mut_private(&cx, &cx.root(this.to_ptr()).into()).unwrap();
+				let content_type = this.get_headers_object(&cx).get(header::CONTENT_TYPE.to_byte_string())?;
+				this.take_body()?.into_json(cx, content_type).await
 			})
 		}
 	}
@@ -506,8 +610,7 @@ impl Request {
 			future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 				let this = Self::get_mut_private(&cx, &Object::from(this.to_local())).unwrap();
 				let headers = this.get_headers_object(&cx);
-				let content_type_string = ByteString::from(CONTENT_TYPE.to_string().into_bytes()).unwrap();
-				let Some(content_type) = headers.get(content_type_string)? else {
+				let Some(content_type) = headers.get(header::CONTENT_TYPE.to_byte_string())? else {
 					return Err(Error::new(
 						"No content-type header, cannot decide form data format",
 						ErrorKind::Type,
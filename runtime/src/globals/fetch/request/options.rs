@@ -383,6 +383,7 @@ impl<'cx> FromValue<'cx> for RequestPriority {
 }
 
 #[derive(Default, FromValue)]
+#[ion(validate = RequestInit::validate)]
 pub struct RequestInit<'cx> {
 	pub method: Option<String>,
 	pub headers: Option<HeadersInit<'cx>>,
@@ -406,4 +407,16 @@ pub struct RequestInit<'cx> {
 	#[ion(default)]
 	pub priority: Option<RequestPriority>,
 	pub window: Option<JSVal>,
+
+	pub raw_header_case: Option<bool>,
+}
+
+impl RequestInit<'_> {
+	/// Checks the options which are invalid regardless of the request they are applied to.
+	fn validate(&self) -> Result<()> {
+		if self.mode == Some(RequestMode::Navigate) {
+			return Err(Error::new("Received 'navigate' mode", ErrorKind::Type));
+		}
+		Ok(())
+	}
 }
@@ -4,13 +4,17 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::error::Error as StdError;
+use std::result;
+
 use bytes::Bytes;
-use http::{HeaderValue, StatusCode};
-use http::header::{CONTENT_TYPE, LOCATION};
+use futures::Stream;
+use http::{HeaderValue, Method, StatusCode};
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE, LOCATION};
 use hyper::{Body, HeaderMap};
 use hyper::ext::ReasonPhrase;
 use ion::conversions::ToValue;
-use ion::string::byte::{ByteString, VisibleAscii};
+use ion::string::byte::{ByteString, header, VisibleAscii};
 use mozjs::conversions::ConversionBehavior;
 use mozjs::jsapi::JSObject;
 use url::Url;
@@ -22,12 +26,15 @@ use ion::typedarray::{ArrayBufferWrapper, Uint8ArrayWrapper};
 pub use options::*;
 
 use crate::globals::fetch::body::FetchBody;
+use crate::globals::fetch::connection::ConnectionInfo;
+use crate::globals::fetch::error::FetchError;
+use crate::globals::fetch::timing::{ResponseTiming, ServerTiming};
 use crate::globals::fetch::header::HeadersKind;
 use crate::globals::fetch::Headers;
 use crate::promise::future_to_promise;
 
 use super::HeadersInit;
-use super::body::{hyper_body_to_stream, FetchBodyInner};
+use super::body::{hyper_body_to_stream, hyper_body_to_stream_with_timing, FetchBodyInner};
 
 mod options;
 
@@ -48,16 +55,32 @@ pub struct Response {
 	pub(crate) status_text: Option<String>,
 
 	pub(crate) range_requested: bool,
+
+	#[trace(no_trace)]
+	pub(crate) error: Option<FetchError>,
+	#[trace(no_trace)]
+	pub(crate) timing: Option<ResponseTiming>,
+	#[trace(no_trace)]
+	pub(crate) connection: Option<ConnectionInfo>,
 }
 
 impl Response {
-	pub fn from_hyper_response(cx: &Context, mut response: hyper::Response<Body>, url: Url) -> Result<Response> {
+	/// Creates a [Response] from the response to a `GET` request.
+	pub fn from_hyper_response(cx: &Context, response: hyper::Response<Body>, url: Url) -> Result<Response> {
+		Response::from_hyper_response_with_timing(cx, response, url, &Method::GET, None)
+	}
+
+	/// Creates a [Response] from the response to a request with the given method.
+	pub fn from_hyper_response_with_timing(
+		cx: &Context, mut response: hyper::Response<Body>, url: Url, method: &Method, timing: Option<ResponseTiming>,
+	) -> Result<Response> {
 		let status = response.status();
-		let status_text = if let Some(reason) = response.extensions().get::<ReasonPhrase>() {
-			Some(String::from_utf8(reason.as_bytes().to_vec()).unwrap())
-		} else {
-			response.status().canonical_reason().map(String::from)
-		};
+		let status_text = status_text(&response);
+
+		// Content-Length describes the body of the equivalent GET request for HEAD requests and 304 responses.
+		let length_hint = can_have_body(method, status)
+			.then(|| response.headers().get(CONTENT_LENGTH)?.to_str().ok()?.parse().ok())
+			.flatten();
 
 		let headers = Headers {
 			reflector: Reflector::default(),
@@ -65,6 +88,11 @@ impl Response {
 			kind: HeadersKind::Immutable,
 		};
 
+		let connection = response.extensions().get::<ConnectionInfo>().map(|info| ConnectionInfo {
+			version: Some(response.version()),
+			..info.clone()
+		});
+
 		let body = response.into_body();
 
 		Ok(Response {
@@ -72,7 +100,10 @@ impl Response {
 
 			headers: Heap::new(Headers::new_object(cx, Box::new(headers))),
 			body: Some(FetchBody {
-				body: FetchBodyInner::Stream(hyper_body_to_stream(cx, body).ok_or_else(Error::none)?),
+				body: FetchBodyInner::Stream(
+					hyper_body_to_stream_with_timing(cx, body, timing.clone()).ok_or_else(Error::none)?,
+				),
+				length_hint,
 				..Default::default()
 			}),
 
@@ -84,6 +115,9 @@ impl Response {
 			status_text,
 
 			range_requested: false,
+			error: None,
+			timing,
+			connection,
 		})
 	}
 
@@ -105,9 +139,33 @@ impl Response {
 			status_text: Some(String::from("OK")),
 
 			range_requested: false,
+			error: None,
+			timing: None,
+			connection: None,
 		}
 	}
 
+	/// Creates a response whose body is read from a native stream, without passing the chunks through scripts.
+	///
+	/// The stream is polled on demand as the body is read, through the same bridge as network responses.
+	pub fn from_stream<S, E>(cx: &Context, stream: S, init: ResponseInit) -> Result<Response>
+	where
+		S: Stream<Item = result::Result<Bytes, E>> + Send + 'static,
+		E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+	{
+		let stream = hyper_body_to_stream(cx, Body::wrap_stream(stream)).ok_or_else(Error::none)?;
+		let body = FetchBody {
+			body: FetchBodyInner::Stream(stream),
+			..Default::default()
+		};
+		Response::constructor(cx, Opt(Some(body)), Opt(Some(init)))
+	}
+
+	/// Returns the kind of the response, which determines what is exposed to scripts.
+	pub fn kind(&self) -> ResponseKind {
+		self.kind
+	}
+
 	pub fn headers<'cx>(&self, cx: &'cx Context) -> &'cx HeaderMap {
 		&Headers::get_private(cx, &self.headers.root(cx).into()).unwrap().headers
 	}
@@ -117,12 +175,19 @@ impl Response {
 		Headers::get_private(cx, &obj).unwrap()
 	}
 
+	/// Returns the length of the body in bytes, if it is known from the body or the `Content-Length` header, and the
+	/// body has not been used.
+	pub fn content_length(&self) -> Option<usize> {
+		self.body.as_ref()?.length_hint()
+	}
+
 	pub fn take_body(&mut self) -> Result<FetchBody> {
 		if matches!(self.body, Some(FetchBody { ref body, .. }) if matches!(body, FetchBodyInner::None)) {
 			return Ok(FetchBody {
 				body: FetchBodyInner::None,
 				source: None,
 				kind: None,
+				length_hint: None,
 			});
 		}
 
@@ -157,6 +222,9 @@ impl Response {
 			status_text: self.status_text.clone(),
 
 			range_requested: self.range_requested,
+			error: self.error.clone(),
+			timing: self.timing.clone(),
+			connection: self.connection.clone(),
 		})
 	}
 
@@ -180,9 +248,43 @@ impl Response {
 			status_text: self.status_text.clone(),
 
 			range_requested: self.range_requested,
+			error: self.error.clone(),
+			timing: self.timing.clone(),
+			connection: self.connection.clone(),
 		})
 	}
 
+	/// Converts the response into one which can be sent by a hyper server.
+	///
+	/// Stream bodies are piped into the returned body as it is polled, by a future queued on the event loop, so the
+	/// event loop must be running for the body to be sent.
+	pub fn into_hyper_response(mut self, cx: &Context) -> Result<hyper::Response<Body>> {
+		if self.kind == ResponseKind::Error {
+			return Err(Error::new(
+				"Network errors cannot be sent as responses",
+				ErrorKind::Type,
+			));
+		}
+
+		let status = self.status.unwrap_or(StatusCode::OK);
+		let mut response = hyper::Response::builder().status(status);
+		if let Some(status_text) = self.status_text.as_deref().filter(|text| Some(*text) != status.canonical_reason()) {
+			if let Ok(reason) = ReasonPhrase::try_from(status_text.as_bytes().to_vec()) {
+				response = response.extension(reason);
+			}
+		}
+		if let Some(headers) = response.headers_mut() {
+			headers.extend(self.headers(cx).clone());
+		}
+
+		let (body, pipe) = self.take_body()?.into_http_body(cx.duplicate())?;
+		if let Some(pipe) = pipe {
+			unsafe { future_to_promise(cx, move |_| async move { Ok::<_, Error>(pipe.await) }) }
+				.ok_or_else(|| Error::new("Future queue is not running", ErrorKind::Normal))?;
+		}
+		Ok(response.body(body)?)
+	}
+
 	pub fn clone_with_body(&self, body: Option<FetchBody>) -> Self {
 		Self {
 			reflector: Default::default(),
@@ -198,6 +300,9 @@ impl Response {
 			status_text: self.status_text.clone(),
 
 			range_requested: self.range_requested,
+			error: self.error.clone(),
+			timing: self.timing.clone(),
+			connection: self.connection.clone(),
 		}
 	}
 }
@@ -222,6 +327,9 @@ impl Response {
 			status_text: init.status_text,
 
 			range_requested: false,
+			error: None,
+			timing: None,
+			connection: None,
 		};
 
 		let mut headers = init.headers.into_headers(HeaderMap::new(), HeadersKind::Response)?;
@@ -284,7 +392,7 @@ impl Response {
 		let mut headers = Headers::new(HeadersKind::Response);
 		headers.headers.append(
 			LOCATION,
-			HeaderValue::from_bytes(location.as_bytes())
+			HeaderValue::try_from(location.as_byte_str())
 				.map_err(|_| Error::new("Invalid Location header value", ErrorKind::Type))?,
 		);
 
@@ -330,18 +438,51 @@ impl Response {
 		self.status_text.clone().unwrap_or_default()
 	}
 
+	#[ion(inspect)]
+	pub fn inspect<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "type", &self.get_type());
+		object.set_as(cx, "status", &self.get_status());
+		object.set_as(cx, "statusText", &self.get_status_text());
+		object.set_as(cx, "url", &self.get_url());
+		object
+	}
+
 	#[ion(get)]
 	pub fn get_headers(&self) -> *mut JSObject {
 		self.headers.get()
 	}
 
+	#[ion(get, name = "serverTiming")]
+	pub fn get_server_timing(&self, cx: &Context) -> *mut JSObject {
+		let timings = ServerTiming::from_headers(self.headers(cx));
+		ServerTiming::to_array(cx, &timings).into_local().get()
+	}
+
+	/// Returns the milestones of the fetch, or `null` if the response was not fetched from the network.
+	#[ion(get)]
+	pub fn get_timing(&self, cx: &Context) -> Option<*mut JSObject> {
+		self.timing.as_ref().map(|timing| timing.to_object(cx).handle().get())
+	}
+
+	/// Returns the metadata of the connection the response was received on, or `null` if it was not received from the
+	/// network. This is non-standard.
+	#[ion(get)]
+	pub fn get_connection(&self, cx: &Context) -> Option<*mut JSObject> {
+		let ttfb = self.timing.as_ref().and_then(|timing| Some(timing.headers_received()? - timing.start()?));
+		self.connection.as_ref().map(|connection| connection.to_object(cx, ttfb).handle().get())
+	}
+
+	/// Returns the length of the body in bytes, or `null` if it is unknown or the body has been used. This is
+	/// non-standard.
+	#[ion(get, name = "contentLength")]
+	pub fn get_content_length(&self) -> Option<u64> {
+		self.content_length().map(|length| length as u64)
+	}
+
 	#[ion(get)]
 	pub fn get_body(&mut self, cx: &Context) -> Result<*mut JSObject> {
-		let stream = match self.take_body()?.body {
-			FetchBodyInner::None => ion::ReadableStream::from_bytes(cx, Bytes::from(vec![])),
-			FetchBodyInner::Bytes(bytes) => ion::ReadableStream::from_bytes(cx, bytes),
-			FetchBodyInner::Stream(stream) => stream,
-		};
+		let stream = self.take_body()?.body.into_stream(cx)?;
 		Ok(stream.get())
 	}
 
@@ -356,7 +497,7 @@ impl Response {
 		unsafe {
 			future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 				let bytes = Self::take_body_bytes(&this, cx).await?;
-				Ok(ArrayBufferWrapper::from(Vec::from(bytes)))
+				Ok(ArrayBufferWrapper::from_bytes(bytes))
 			})
 		}
 	}
@@ -369,7 +510,7 @@ impl Response {
 	// 	unsafe {
 	// 		future_to_promise::<_, _, _, Error>(cx, move |cx| async move {
 	// 			let bytes = Self::take_body_bytes(&this, cx).await?;
-	// 			Ok(Uint8ArrayWrapper::from(Vec::from(bytes)))
+	// 			Ok(Uint8ArrayWrapper::from_bytes(bytes))
 	// 		})
 	// 	}
 	// }
@@ -381,7 +522,7 @@ impl Response {
 				let this = Self::get_mut_private(&cx, &this.root(&cx).into()).unwrap();
 				let body = this.take_body()?;
 				let headers = this.get_headers_object(&cx);
-				let header = headers.get(ByteString::from(CONTENT_TYPE.to_string().into()).unwrap()).unwrap();
+				let header = headers.get(header::CONTENT_TYPE.to_byte_string())?;
 				body.into_blob(cx, header).await
 			})
 		}
@@ -398,8 +539,9 @@ impl Response {
 		let this = TracedHeap::new(self.reflector.get());
 		unsafe {
 			future_to_promise(cx, move |cx| async move {
-				let body = Self::get_mut_private(&cx, &cx.root(this.to_ptr()).into()).unwrap().take_body()?;
-				body.into_json(cx).await
+				let this = Self::get_mut_private(&cx, &cx.root(this.to_ptr()).into()).unwrap();
+				let content_type = this.get_headers_object(&cx).get(header::CONTENT_TYPE.to_byte_string())?;
+				this.take_body()?.into_json(cx, content_type).await
 			})
 		}
 	}
@@ -411,8 +553,7 @@ impl Response {
 			future_to_promise::<_, _, *mut JSObject, Error>(cx, move |cx| async move {
 				let this = Self::get_mut_private(&cx, &Object::from(this.to_local())).unwrap();
 				let headers = this.get_headers_object(&cx);
-				let content_type_string = ByteString::from(CONTENT_TYPE.to_string().into_bytes()).unwrap();
-				let Some(content_type) = headers.get(content_type_string)? else {
+				let Some(content_type) = headers.get(header::CONTENT_TYPE.to_byte_string())? else {
 					return Err(Error::new(
 						"No content-type header, cannot decide form data format",
 						ErrorKind::Type,
@@ -427,9 +568,23 @@ impl Response {
 		let cloned = self.try_clone(cx)?;
 		Ok(Response::new_object(cx, Box::new(cloned)))
 	}
+
+	/// Returns a copy of the response with its headers replaced, which are mutable. The body is moved to the copy, so
+	/// this response is marked as used.
+	#[ion(name = "withHeaders")]
+	pub fn with_headers(&mut self, cx: &Context, headers: HeadersInit) -> Result<*mut JSObject> {
+		let headers = headers.into_headers(HeaderMap::new(), HeadersKind::Response)?;
+		let mut response = self.clone_with_body(Some(self.take_body()?));
+		response.headers = Heap::new(Headers::new_object(cx, Box::new(headers)));
+		Ok(Response::new_object(cx, Box::new(response)))
+	}
 }
 
 pub fn network_error(cx: &Context) -> Response {
+	network_error_with_cause(cx, None)
+}
+
+pub fn network_error_with_cause(cx: &Context, error: Option<FetchError>) -> Response {
 	Response {
 		reflector: Reflector::default(),
 
@@ -444,5 +599,24 @@ pub fn network_error(cx: &Context) -> Response {
 		status_text: None,
 
 		range_requested: false,
+		error,
+		timing: None,
+		connection: None,
+	}
+}
+
+/// Returns the status text of a response, which is the reason phrase it was received with, if any.
+/// Checks if a response to a request with the given method and status can have a body.
+fn can_have_body(method: &Method, status: StatusCode) -> bool {
+	*method != Method::HEAD
+		&& !status.is_informational()
+		&& status != StatusCode::NO_CONTENT
+		&& status != StatusCode::NOT_MODIFIED
+}
+
+pub(crate) fn status_text<B>(response: &hyper::Response<B>) -> Option<String> {
+	match response.extensions().get::<ReasonPhrase>() {
+		Some(reason) => Some(String::from_utf8(reason.as_bytes().to_vec()).unwrap()),
+		None => response.status().canonical_reason().map(String::from),
 	}
 }
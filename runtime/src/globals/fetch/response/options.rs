@@ -18,12 +18,12 @@ use crate::globals::fetch::header::HeadersInit;
 #[derive(Default, FromValue)]
 pub struct ResponseInit<'cx> {
 	#[ion(default)]
-	pub(crate) headers: HeadersInit<'cx>,
+	pub headers: HeadersInit<'cx>,
 
 	#[ion(default, parser = |s| parse_status(cx, s))]
-	pub(crate) status: StatusCode,
+	pub status: StatusCode,
 	#[ion(default)]
-	pub(crate) status_text: Option<String>,
+	pub status_text: Option<String>,
 }
 
 fn parse_status(cx: &Context, status: Value) -> Result<StatusCode> {
@@ -45,6 +45,17 @@ pub enum ResponseKind {
 	OpaqueRedirect,
 }
 
+impl ResponseKind {
+	/// Checks if the response is opaque, which hides its status, headers and body from scripts.
+	pub fn is_opaque(&self) -> bool {
+		matches!(self, ResponseKind::Opaque | ResponseKind::OpaqueRedirect)
+	}
+
+	pub fn is_error(&self) -> bool {
+		*self == ResponseKind::Error
+	}
+}
+
 impl Display for ResponseKind {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
 		match self {
@@ -0,0 +1,45 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use http::{HeaderMap, Method};
+use url::Url;
+
+use ion::{Context, Result};
+
+use crate::ContextExt;
+use crate::globals::fetch::{FetchBody, Response};
+
+/// Schemes which are fetched by the runtime itself, and cannot be handled by the embedder.
+pub(crate) static RESERVED_SCHEMES: [&str; 6] = ["about", "blob", "data", "file", "http", "https"];
+
+/// Request passed to a [SchemeHandler].
+pub struct SchemeRequest {
+	pub method: Method,
+	pub url: Url,
+	pub headers: HeaderMap,
+	pub body: Option<FetchBody>,
+}
+
+pub type SchemeFuture = Pin<Box<dyn Future<Output = Result<Response>>>>;
+
+/// Handler for the requests to a custom scheme, registered with [register_scheme](crate::Runtime::register_scheme).
+pub type SchemeHandler = dyn Fn(Context, SchemeRequest) -> SchemeFuture;
+
+/// Checks if a scheme can be registered, which requires it to be a valid lowercase scheme that is not reserved.
+pub(crate) fn is_registrable_scheme(scheme: &str) -> bool {
+	let mut chars = scheme.chars();
+	chars.next().is_some_and(|c| c.is_ascii_lowercase())
+		&& chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '+' | '-' | '.'))
+		&& !RESERVED_SCHEMES.contains(&scheme)
+}
+
+pub(crate) fn scheme_handler(cx: &Context, scheme: &str) -> Option<Rc<SchemeHandler>> {
+	unsafe { cx.get_private() }.schemes.get(scheme).cloned()
+}
@@ -0,0 +1,177 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::future::{Future, poll_fn};
+use std::pin::{pin, Pin};
+use std::sync::{Arc, OnceLock};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use tokio::time::{sleep_until, Sleep};
+
+use crate::globals::fetch::error::FetchErrorPhase;
+
+tokio::task_local! {
+	/// Progress of the connection being established for the current fetch, if any.
+	static CONNECT_PROGRESS: Arc<ConnectProgress>;
+}
+
+/// Timeouts for each phase of a fetch, configured in [ClientOptions](crate::globals::fetch::ClientOptions).
+/// Phases without a timeout can take any amount of time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FetchTimeouts {
+	/// Maximum duration of the DNS lookup and establishing the connection, including any proxy handshake.
+	pub connect: Option<Duration>,
+	/// Maximum duration of the TLS handshake, once the connection is established.
+	pub tls: Option<Duration>,
+	/// Maximum duration from sending the request, or establishing its connection, until the response headers are
+	/// received.
+	pub first_byte: Option<Duration>,
+}
+
+/// Phase of a fetch which took longer than its timeout.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FetchTimeout {
+	/// The connect timeout elapsed before the DNS lookup completed.
+	Dns,
+	/// The connect timeout elapsed after the DNS lookup completed.
+	Connect,
+	Tls,
+	FirstByte,
+	/// The queue timeout of the [OriginLimits](crate::globals::fetch::OriginLimits) elapsed before the request could
+	/// be sent.
+	Queue,
+}
+
+impl FetchTimeout {
+	pub fn phase(&self) -> FetchErrorPhase {
+		match self {
+			FetchTimeout::Dns => FetchErrorPhase::Dns,
+			FetchTimeout::Connect => FetchErrorPhase::Connect,
+			FetchTimeout::Tls => FetchErrorPhase::Tls,
+			FetchTimeout::FirstByte => FetchErrorPhase::Response,
+			FetchTimeout::Queue => FetchErrorPhase::Connect,
+		}
+	}
+
+	/// Returns the code exposed as the `code` property of the error `fetch` is rejected with.
+	pub fn code(&self) -> &'static str {
+		match self {
+			FetchTimeout::Dns => "DNS_TIMEOUT",
+			FetchTimeout::Connect => "CONNECT_TIMEOUT",
+			FetchTimeout::Tls => "TLS_TIMEOUT",
+			FetchTimeout::FirstByte => "FIRST_BYTE_TIMEOUT",
+			FetchTimeout::Queue => "QUEUE_TIMEOUT",
+		}
+	}
+}
+
+impl Display for FetchTimeout {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str(match self {
+			FetchTimeout::Dns => "DNS lookup timed out",
+			FetchTimeout::Connect => "Connection timed out",
+			FetchTimeout::Tls => "TLS handshake timed out",
+			FetchTimeout::FirstByte => "Timed out waiting for response",
+			FetchTimeout::Queue => "Timed out waiting for other requests to the origin",
+		})
+	}
+}
+
+impl StdError for FetchTimeout {}
+
+/// Records when each step of establishing a connection completes, so that timeouts can be applied to each phase.
+#[derive(Debug, Default)]
+pub struct ConnectProgress {
+	started: OnceLock<Instant>,
+	resolved: OnceLock<Instant>,
+	connected: OnceLock<Instant>,
+	established: OnceLock<Instant>,
+}
+
+impl ConnectProgress {
+	/// Returns the progress of the connection being established for the current fetch.
+	/// Connectors used outside of a fetch receive a new [ConnectProgress].
+	pub(crate) fn current() -> Arc<ConnectProgress> {
+		CONNECT_PROGRESS.try_with(Arc::clone).unwrap_or_default()
+	}
+
+	/// Calls a connector, so that it records its progress to `self`.
+	pub(crate) fn sync_scope<R, F: FnOnce() -> R>(self: &Arc<Self>, f: F) -> R {
+		CONNECT_PROGRESS.sync_scope(Arc::clone(self), f)
+	}
+
+	/// Runs a future which sends a request, with connections established while it is polled reporting to `self`.
+	pub(crate) async fn scope<F: Future>(self: &Arc<Self>, future: F) -> F::Output {
+		CONNECT_PROGRESS.scope(Arc::clone(self), future).await
+	}
+
+	pub(crate) fn mark_started(&self) {
+		let _ = self.started.set(Instant::now());
+	}
+
+	pub(crate) fn mark_resolved(&self) {
+		let _ = self.resolved.set(Instant::now());
+	}
+
+	pub(crate) fn mark_connected(&self) {
+		let _ = self.connected.set(Instant::now());
+	}
+
+	pub(crate) fn mark_established(&self) {
+		let _ = self.established.set(Instant::now());
+	}
+
+	pub(crate) fn is_resolved(&self) -> bool {
+		self.resolved.get().is_some()
+	}
+
+	pub(crate) fn connected(&self) -> Option<Instant> {
+		self.connected.get().copied()
+	}
+
+	/// Returns when the first byte timeout starts, which is when the request is sent on an existing connection, or
+	/// when the new connection is established. Returns [None] while a connection is being established.
+	pub(crate) fn first_byte_start(&self, sent: Instant) -> Option<Instant> {
+		match (self.started.get(), self.established.get()) {
+			(None, _) => Some(sent),
+			(Some(_), established) => established.copied(),
+		}
+	}
+}
+
+/// Polls a future until it completes, or until the duration has elapsed since the instant returned by `start`.
+/// The timer does not start until `start` returns an instant, and no timeout is applied without a duration.
+pub(crate) async fn timeout_after<F, S, E>(
+	future: F, start: S, duration: Option<Duration>, timeout: E,
+) -> Result<F::Output, FetchTimeout>
+where
+	F: Future,
+	S: Fn() -> Option<Instant>,
+	E: Fn() -> FetchTimeout,
+{
+	let Some(duration) = duration else {
+		return Ok(future.await);
+	};
+
+	let mut future = pin!(future);
+	let mut sleep: Option<Pin<Box<Sleep>>> = None;
+	poll_fn(|cx| {
+		if let Poll::Ready(output) = future.as_mut().poll(cx) {
+			return Poll::Ready(Ok(output));
+		}
+		if sleep.is_none() {
+			sleep = start().map(|start| Box::pin(sleep_until((start + duration).into())));
+		}
+		match &mut sleep {
+			Some(sleep) if sleep.as_mut().poll(cx).is_ready() => Poll::Ready(Err(timeout())),
+			_ => Poll::Pending,
+		}
+	})
+	.await
+}
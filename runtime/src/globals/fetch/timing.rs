@@ -0,0 +1,180 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::HeaderMap;
+use http::header::HeaderName;
+
+use ion::{Array, Context, Object};
+
+pub const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// Represents a metric of a `Server-Timing` header.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ServerTiming {
+	pub name: String,
+	pub duration: Option<f64>,
+	pub description: Option<String>,
+}
+
+impl ServerTiming {
+	/// Parses all `Server-Timing` headers. Invalid metrics and parameters are ignored.
+	///
+	/// Commas and semicolons within quoted descriptions do not separate metrics or parameters.
+	pub fn from_headers(headers: &HeaderMap) -> Vec<ServerTiming> {
+		let mut timings = Vec::new();
+		for value in headers.get_all(SERVER_TIMING) {
+			let Ok(value) = value.to_str() else {
+				continue;
+			};
+			for metric in split_unquoted(value, ',') {
+				let mut parameters = split_unquoted(metric, ';').into_iter();
+				let name = parameters.next().unwrap_or_default().trim();
+				if name.is_empty() {
+					continue;
+				}
+
+				let mut timing = ServerTiming {
+					name: String::from(name),
+					..ServerTiming::default()
+				};
+				for parameter in parameters {
+					let Some((key, value)) = parameter.split_once('=') else {
+						continue;
+					};
+					let value = parameter_value(value);
+					match key.trim().to_ascii_lowercase().as_str() {
+						"dur" if timing.duration.is_none() => timing.duration = value.parse().ok(),
+						"desc" if timing.description.is_none() => timing.description = Some(value),
+						_ => {}
+					}
+				}
+				timings.push(timing);
+			}
+		}
+		timings
+	}
+
+	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "name", &self.name);
+		object.set_as(cx, "duration", &self.duration.unwrap_or_default());
+		object.set_as(cx, "description", &self.description.clone().unwrap_or_default());
+		object
+	}
+
+	pub fn to_array<'cx>(cx: &'cx Context, timings: &[ServerTiming]) -> Array<'cx> {
+		let array = Array::new_with_length(cx, timings.len());
+		for (index, timing) in timings.iter().enumerate() {
+			array.set_as(cx, index as u32, &timing.to_object(cx));
+		}
+		array
+	}
+}
+
+/// Splits a header value at each separator which is not within a quoted string.
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let mut start = 0;
+	let mut quoted = false;
+	let mut escaped = false;
+	for (index, char) in value.char_indices() {
+		if escaped {
+			escaped = false;
+			continue;
+		}
+		match char {
+			'\\' if quoted => escaped = true,
+			'"' => quoted = !quoted,
+			char if char == separator && !quoted => {
+				parts.push(&value[start..index]);
+				start = index + char.len_utf8();
+			}
+			_ => {}
+		}
+	}
+	parts.push(&value[start..]);
+	parts
+}
+
+/// Parses the value of a parameter, which is either a token or a quoted string with backslash escapes.
+fn parameter_value(value: &str) -> String {
+	let value = value.trim();
+	let Some(quoted) = value.strip_prefix('"') else {
+		return String::from(value);
+	};
+
+	let mut unquoted = String::with_capacity(quoted.len());
+	let mut chars = quoted.chars();
+	while let Some(char) = chars.next() {
+		match char {
+			'\\' => unquoted.extend(chars.next()),
+			'"' => break,
+			char => unquoted.push(char),
+		}
+	}
+	unquoted
+}
+
+/// Records when the milestones of a network fetch were reached, in milliseconds since the Unix epoch.
+///
+/// The timing is shared with the body stream of the response, which records when the body is complete.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseTiming {
+	inner: Rc<ResponseTimingInner>,
+}
+
+#[derive(Debug, Default)]
+struct ResponseTimingInner {
+	start: Cell<Option<f64>>,
+	headers_received: Cell<Option<f64>>,
+	body_complete: Cell<Option<f64>>,
+}
+
+impl ResponseTiming {
+	pub fn started() -> ResponseTiming {
+		let timing = ResponseTiming::default();
+		timing.inner.start.set(Some(now()));
+		timing
+	}
+
+	pub fn start(&self) -> Option<f64> {
+		self.inner.start.get()
+	}
+
+	pub fn headers_received(&self) -> Option<f64> {
+		self.inner.headers_received.get()
+	}
+
+	pub fn body_complete(&self) -> Option<f64> {
+		self.inner.body_complete.get()
+	}
+
+	pub fn mark_headers_received(&self) {
+		self.inner.headers_received.set(Some(now()));
+	}
+
+	pub fn mark_body_complete(&self) {
+		if self.inner.body_complete.get().is_none() {
+			self.inner.body_complete.set(Some(now()));
+		}
+	}
+
+	pub fn to_object<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "start", &self.start());
+		object.set_as(cx, "headersReceived", &self.headers_received());
+		object.set_as(cx, "bodyComplete", &self.body_complete());
+		object
+	}
+}
+
+fn now() -> f64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64() * 1000.0
+}
@@ -0,0 +1,139 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use rustls::{ClientConfig, DigitallySignedStruct, Error, RootCertStore, SignatureScheme};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+
+/// Certificate chain and private key presented to servers which request client authentication.
+#[derive(Debug)]
+pub struct ClientIdentity {
+	pub certificates: Vec<CertificateDer<'static>>,
+	pub key: PrivateKeyDer<'static>,
+}
+
+impl ClientIdentity {
+	pub fn new(certificates: Vec<CertificateDer<'static>>, key: PrivateKeyDer<'static>) -> ClientIdentity {
+		ClientIdentity { certificates, key }
+	}
+
+	/// Reads the certificate chain and the first private key from PEM-encoded data.
+	pub fn from_pem(certificates: &[u8], key: &[u8]) -> io::Result<ClientIdentity> {
+		let certificates = parse_pem_certificates(certificates)?;
+		let key = rustls_pemfile::private_key(&mut &*key)?
+			.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "No private key found"))?;
+		Ok(ClientIdentity { certificates, key })
+	}
+}
+
+impl Clone for ClientIdentity {
+	fn clone(&self) -> ClientIdentity {
+		ClientIdentity {
+			certificates: self.certificates.clone(),
+			key: self.key.clone_key(),
+		}
+	}
+}
+
+/// Configures the TLS connections made by a [Client](super::Client).
+#[derive(Clone, Debug)]
+pub struct TlsOptions {
+	/// Trust the Mozilla root certificates bundled with the runtime.
+	pub builtin_roots: bool,
+	/// Additional root certificates to trust, such as those of internal certificate authorities.
+	pub root_certificates: Vec<CertificateDer<'static>>,
+	pub identity: Option<ClientIdentity>,
+	/// Accept any server certificate, including expired and self-signed certificates, without verifying the hostname.
+	/// This makes connections vulnerable to interception, and should only be used for testing.
+	pub accept_invalid_certificates: bool,
+}
+
+impl TlsOptions {
+	/// Adds the root certificates from PEM-encoded data.
+	pub fn add_root_certificates_pem(&mut self, pem: &[u8]) -> io::Result<()> {
+		self.root_certificates.extend(parse_pem_certificates(pem)?);
+		Ok(())
+	}
+
+	/// Creates the [ClientConfig] for the options.
+	/// Returns [Err] if a root certificate or the client identity is invalid.
+	pub fn client_config(&self) -> Result<ClientConfig, Error> {
+		let provider = Arc::new(ring::default_provider());
+		let builder =
+			ClientConfig::builder_with_provider(Arc::clone(&provider)).with_safe_default_protocol_versions()?;
+
+		let builder = if self.accept_invalid_certificates {
+			builder
+				.dangerous()
+				.with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+		} else {
+			let mut roots = RootCertStore::empty();
+			if self.builtin_roots {
+				roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+			}
+			for certificate in &self.root_certificates {
+				roots.add(certificate.clone())?;
+			}
+			builder.with_root_certificates(roots)
+		};
+
+		match &self.identity {
+			Some(identity) => builder.with_client_auth_cert(identity.certificates.clone(), identity.key.clone_key()),
+			None => Ok(builder.with_no_client_auth()),
+		}
+	}
+}
+
+impl Default for TlsOptions {
+	fn default() -> TlsOptions {
+		TlsOptions {
+			builtin_roots: true,
+			root_certificates: Vec::new(),
+			identity: None,
+			accept_invalid_certificates: false,
+		}
+	}
+}
+
+fn parse_pem_certificates(mut pem: &[u8]) -> io::Result<Vec<CertificateDer<'static>>> {
+	let certificates = rustls_pemfile::certs(&mut pem).collect::<io::Result<Vec<_>>>()?;
+	if certificates.is_empty() {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "No certificates found"));
+	}
+	Ok(certificates)
+}
+
+/// Verifier which accepts any server certificate, but still checks handshake signatures.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+	fn verify_server_cert(
+		&self, _: &CertificateDer, _: &[CertificateDer], _: &ServerName, _: &[u8], _: UnixTime,
+	) -> Result<ServerCertVerified, Error> {
+		Ok(ServerCertVerified::assertion())
+	}
+
+	fn verify_tls12_signature(
+		&self, message: &[u8], certificate: &CertificateDer, dss: &DigitallySignedStruct,
+	) -> Result<HandshakeSignatureValid, Error> {
+		verify_tls12_signature(message, certificate, dss, &self.0.signature_verification_algorithms)
+	}
+
+	fn verify_tls13_signature(
+		&self, message: &[u8], certificate: &CertificateDer, dss: &DigitallySignedStruct,
+	) -> Result<HandshakeSignatureValid, Error> {
+		verify_tls13_signature(message, certificate, dss, &self.0.signature_verification_algorithms)
+	}
+
+	fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+		self.0.signature_verification_algorithms.supported_schemes()
+	}
+}
@@ -4,10 +4,13 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::{future, io};
 use std::str::FromStr;
 
 use bytes::Bytes;
 use encoding_rs::UTF_8;
+use futures::{StreamExt, stream};
+use futures::stream::BoxStream;
 use mozjs::jsapi::JSObject;
 
 use ion::{ClassDefinition, Context, Error, ErrorKind, Object, Promise, Result, Value, ReadableStream};
@@ -17,6 +20,7 @@ use ion::format::NEWLINE;
 use ion::function::{Clamp, Opt};
 use ion::typedarray::{ArrayBuffer, ArrayBufferView};
 
+use crate::globals::file::disk::{disk_stream, DiskSource};
 use crate::promise::future_to_promise;
 
 pub enum BufferSource<'cx> {
@@ -88,7 +92,8 @@ impl<'cx> FromValue<'cx> for BlobPart {
 			if let Ok(buffer_source) = BufferSource::from_value(cx, value, strict, false) {
 				return Ok(BlobPart(buffer_source.to_bytes()));
 			} else if let Ok(blob) = <&Blob>::from_value(cx, value, strict, ()) {
-				return Ok(BlobPart(blob.bytes.clone()));
+				// TODO: keep disk-backed blobs on disk instead of reading them
+				return Ok(BlobPart(blob.to_bytes()?));
 			}
 		}
 		Err(Error::new(
@@ -134,33 +139,98 @@ pub struct BlobOptions {
 	pub endings: Endings,
 }
 
+/// Contents of a [Blob], which are either in memory or read from disk when needed.
+#[derive(Clone, Debug)]
+pub enum BlobData {
+	Memory(Bytes),
+	Disk(DiskSource),
+}
+
+impl BlobData {
+	pub fn len(&self) -> u64 {
+		match self {
+			BlobData::Memory(bytes) => bytes.len() as u64,
+			BlobData::Disk(source) => source.len(),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	pub fn slice(&self, start: u64, end: u64) -> BlobData {
+		match self {
+			BlobData::Memory(bytes) => BlobData::Memory(bytes.slice(start as usize..end as usize)),
+			BlobData::Disk(source) => BlobData::Disk(source.slice(start, end)),
+		}
+	}
+
+	pub async fn read(&self) -> io::Result<Bytes> {
+		match self {
+			BlobData::Memory(bytes) => Ok(bytes.clone()),
+			BlobData::Disk(source) => source.read().await,
+		}
+	}
+
+	/// Reads the contents, blocking the current thread if they are on disk.
+	pub fn read_blocking(&self) -> io::Result<Bytes> {
+		match self {
+			BlobData::Memory(bytes) => Ok(bytes.clone()),
+			BlobData::Disk(source) => source.read_blocking(),
+		}
+	}
+
+	pub fn stream(&self) -> BoxStream<'static, io::Result<Bytes>> {
+		match self {
+			BlobData::Memory(bytes) => stream::once(future::ready(Ok(bytes.clone()))).boxed(),
+			BlobData::Disk(source) => source.stream().boxed(),
+		}
+	}
+}
+
+/// Resolves an index relative to the end of a blob if it is negative, and clamps it to the size of the blob.
+fn relative_index(index: i64, size: u64) -> u64 {
+	if index < 0 {
+		size.saturating_sub(index.unsigned_abs())
+	} else {
+		(index as u64).min(size)
+	}
+}
+
 #[js_class]
 pub struct Blob {
 	reflector: Reflector,
 	#[trace(no_trace)]
-	bytes: Bytes,
+	data: BlobData,
 	kind: Option<String>,
 }
 
 impl Blob {
 	pub fn new(bytes: Bytes) -> Self {
-		Self {
-			reflector: Default::default(),
-			bytes,
-			kind: None,
-		}
+		Blob::from_data(BlobData::Memory(bytes), None)
 	}
 
 	pub fn new_with_kind(bytes: Bytes, kind: String) -> Self {
+		Blob::from_data(BlobData::Memory(bytes), Some(kind))
+	}
+
+	pub fn from_data(data: BlobData, kind: Option<String>) -> Self {
 		Self {
 			reflector: Default::default(),
-			bytes,
-			kind: Some(kind),
+			data,
+			kind,
 		}
 	}
 
-	pub fn as_bytes(&self) -> &Bytes {
-		&self.bytes
+	pub fn data(&self) -> &BlobData {
+		&self.data
+	}
+
+	/// Returns the contents of the blob, reading them from disk if needed.
+	///
+	/// Disk-backed blobs are read synchronously, so [data](Blob::data) should be used to read them asynchronously.
+	pub fn to_bytes(&self) -> Result<Bytes> {
+		Ok(self.data.read_blocking()?)
 	}
 
 	pub fn kind(&self) -> Option<String> {
@@ -207,16 +277,12 @@ impl Blob {
 			}
 		}
 
-		Blob {
-			reflector: Reflector::default(),
-			bytes: Bytes::from(bytes),
-			kind: options.kind,
-		}
+		Blob::from_data(BlobData::Memory(Bytes::from(bytes)), options.kind)
 	}
 
 	#[ion(get)]
 	pub fn get_size(&self) -> u64 {
-		self.bytes.len() as u64
+		self.data.len()
 	}
 
 	#[ion(get)]
@@ -224,22 +290,20 @@ impl Blob {
 		self.kind.clone().unwrap_or_default()
 	}
 
+	#[ion(inspect)]
+	pub fn inspect<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "size", &self.get_size());
+		object.set_as(cx, "type", &self.get_type());
+		object
+	}
+
 	pub fn slice(
 		&self, cx: &Context, Opt(start): Opt<Clamp<i64>>, Opt(end): Opt<Clamp<i64>>, Opt(kind): Opt<String>,
 	) -> *mut JSObject {
-		let size = self.bytes.len() as i64;
-
-		let mut start = start.unwrap_or_default().0;
-		if start < 0 {
-			start = 0.max(size + start);
-		}
-		let start = start.min(size) as usize;
-
-		let mut end = end.unwrap_or(Clamp(size)).0;
-		if end < 0 {
-			end = 0.max(size + end);
-		}
-		let end = end.min(size) as usize;
+		let size = self.data.len();
+		let start = relative_index(start.map_or(0, |start| start.0), size);
+		let end = relative_index(end.map_or(size as i64, |end| end.0), size);
 
 		let kind = match kind {
 			Some(mut kind) if kind.as_bytes().iter().all(|&b| (0x20..=0x7E).contains(&b)) => {
@@ -249,34 +313,37 @@ impl Blob {
 			_ => None,
 		};
 
-		let span = 0.max(end - start);
-
-		let bytes = self.bytes.slice(start..start + span);
-
-		let blob = Blob {
-			reflector: Reflector::default(),
-			bytes,
-			kind,
-		};
+		let blob = Blob::from_data(self.data.slice(start, end.max(start)), kind);
 		Blob::new_object(cx, Box::new(blob))
 	}
 
 	pub fn text(&self, cx: &Context) -> Option<Promise> {
-		let bytes = self.bytes.clone();
-		unsafe { future_to_promise(cx, |_| async move { Ok::<_, ()>(UTF_8.decode(&bytes).0.into_owned()) }) }
+		let data = self.data.clone();
+		unsafe {
+			future_to_promise(cx, |_| async move {
+				let bytes = data.read().await?;
+				Ok::<_, Error>(UTF_8.decode(&bytes).0.into_owned())
+			})
+		}
 	}
 
 	#[ion(name = "arrayBuffer")]
 	pub fn array_buffer(&self, cx: &Context) -> Option<Promise> {
-		let bytes = self.bytes.clone();
+		let data = self.data.clone();
 		unsafe {
 			future_to_promise(cx, |_| async move {
-				Ok::<_, ()>(ion::typedarray::ArrayBufferWrapper::from(bytes.to_vec()))
+				let bytes = data.read().await?;
+				Ok::<_, Error>(ion::typedarray::ArrayBufferWrapper::from(bytes.to_vec()))
 			})
 		}
 	}
 
-	pub fn stream(&self, cx: &Context) -> ReadableStream {
-		ReadableStream::from_bytes(cx, self.bytes.clone())
+	pub fn stream(&self, cx: &Context) -> Result<ReadableStream> {
+		match &self.data {
+			BlobData::Memory(bytes) => Ok(ReadableStream::from_bytes(cx, bytes.clone())),
+			BlobData::Disk(source) => {
+				disk_stream(cx, source).ok_or_else(|| Error::new("Failed to create stream for file", ErrorKind::Normal))
+			}
+		}
 	}
 }
@@ -0,0 +1,136 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{fs, io};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use bytes::Bytes;
+use futures::{Stream, stream};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use ion::{Context, ReadableStream};
+
+use crate::globals::streams::readable_stream_from_byte_stream;
+
+/// Maximum size of the chunks produced when streaming a file from disk.
+pub const DISK_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Range of a file on disk, which is only opened when its contents are read.
+///
+/// Reads fail if the file was modified after the source was created, as the contents would no longer match its size.
+#[derive(Clone, Debug)]
+pub struct DiskSource {
+	path: Arc<Path>,
+	modified: Option<SystemTime>,
+	offset: u64,
+	len: u64,
+}
+
+impl DiskSource {
+	/// Creates a source for the whole of a file, reading only its metadata.
+	pub fn new(path: impl Into<PathBuf>) -> io::Result<DiskSource> {
+		let path = path.into();
+		let metadata = fs::metadata(&path)?;
+		if !metadata.is_file() {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, "Path is not a file"));
+		}
+		Ok(DiskSource {
+			path: Arc::from(path),
+			modified: metadata.modified().ok(),
+			offset: 0,
+			len: metadata.len(),
+		})
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn modified(&self) -> Option<SystemTime> {
+		self.modified
+	}
+
+	pub fn len(&self) -> u64 {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns a source for a range of this source, with bounds relative to its start.
+	pub fn slice(&self, start: u64, end: u64) -> DiskSource {
+		let end = end.min(self.len);
+		let start = start.min(end);
+		DiskSource {
+			path: Arc::clone(&self.path),
+			modified: self.modified,
+			offset: self.offset + start,
+			len: end - start,
+		}
+	}
+
+	/// Reads the whole source asynchronously.
+	pub async fn read(&self) -> io::Result<Bytes> {
+		let mut file = self.open().await?;
+		let mut bytes = vec![0; self.len as usize];
+		file.read_exact(&mut bytes).await?;
+		Ok(Bytes::from(bytes))
+	}
+
+	/// Reads the whole source, blocking the current thread.
+	pub fn read_blocking(&self) -> io::Result<Bytes> {
+		self.check_modified(fs::metadata(&self.path)?.modified().ok())?;
+		let mut file = fs::File::open(&self.path)?;
+		file.seek(SeekFrom::Start(self.offset))?;
+		let mut bytes = vec![0; self.len as usize];
+		file.read_exact(&mut bytes)?;
+		Ok(Bytes::from(bytes))
+	}
+
+	/// Streams the source in chunks of at most [DISK_CHUNK_SIZE] bytes.
+	pub fn stream(&self) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+		stream::try_unfold((self.clone(), None, 0), |(source, file, read)| async move {
+			if read >= source.len {
+				return Ok(None);
+			}
+			let mut file = match file {
+				Some(file) => file,
+				None => source.open().await?,
+			};
+
+			let mut chunk = vec![0; (source.len - read).min(DISK_CHUNK_SIZE as u64) as usize];
+			file.read_exact(&mut chunk).await?;
+			let read = read + chunk.len() as u64;
+			Ok(Some((Bytes::from(chunk), (source, Some(file), read))))
+		})
+	}
+
+	async fn open(&self) -> io::Result<tokio::fs::File> {
+		let mut file = tokio::fs::File::open(&self.path).await?;
+		self.check_modified(file.metadata().await?.modified().ok())?;
+		file.seek(SeekFrom::Start(self.offset)).await?;
+		Ok(file)
+	}
+
+	fn check_modified(&self, modified: Option<SystemTime>) -> io::Result<()> {
+		if self.modified.is_some() && modified != self.modified {
+			return Err(io::Error::new(
+				io::ErrorKind::Other,
+				"NotReadableError: File was modified after it was opened",
+			));
+		}
+		Ok(())
+	}
+}
+
+/// Creates a [ReadableStream] which reads a file from disk as it is pulled.
+pub fn disk_stream(cx: &Context, source: &DiskSource) -> Option<ReadableStream> {
+	readable_stream_from_byte_stream(cx, source.stream(), DISK_CHUNK_SIZE)
+}
@@ -4,15 +4,20 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::io;
+use std::path::PathBuf;
+
 use chrono::{DateTime, TimeZone, Utc};
 
-pub use blob::{Blob, BufferSource, BlobPart, BlobOptions, Endings};
+pub use blob::{Blob, BlobData, BufferSource, BlobPart, BlobOptions, Endings};
+pub use disk::{disk_stream, DiskSource, DISK_CHUNK_SIZE};
 use ion::{ClassDefinition, Context, Object};
 use ion::function::{Opt, Wrap};
 
 use crate::globals::file::reader::{FileReader, FileReaderSync};
 
 mod blob;
+mod disk;
 mod reader;
 
 #[derive(Debug, Default, FromValue)]
@@ -30,6 +35,25 @@ pub struct File {
 	pub modified: DateTime<Utc>,
 }
 
+impl File {
+	/// Creates a file which reads its contents from disk when they are needed, instead of holding them in memory.
+	/// Only the metadata of the file is read when it is created.
+	pub fn from_path(path: impl Into<PathBuf>, kind: Option<String>) -> io::Result<File> {
+		let source = DiskSource::new(path)?;
+		let name = source
+			.path()
+			.file_name()
+			.map(|name| name.to_string_lossy().into_owned())
+			.unwrap_or_default();
+		let modified = source.modified().map(DateTime::<Utc>::from).unwrap_or_else(Utc::now);
+		Ok(File {
+			blob: Blob::from_data(BlobData::Disk(source), kind),
+			name,
+			modified,
+		})
+	}
+}
+
 #[js_class]
 impl File {
 	#[ion(constructor)]
@@ -53,6 +77,16 @@ impl File {
 	pub fn get_last_modified(&self) -> i64 {
 		self.modified.timestamp_millis()
 	}
+
+	#[ion(inspect)]
+	pub fn inspect<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "name", &self.name);
+		object.set_as(cx, "size", &self.blob.get_size());
+		object.set_as(cx, "type", &self.blob.get_type());
+		object.set_as(cx, "lastModified", &self.get_last_modified());
+		object
+	}
 }
 
 pub fn define(cx: &Context, object: &Object) -> bool {
@@ -5,6 +5,7 @@
  */
 
 use std::cell::UnsafeCell;
+use std::ptr;
 use std::str::FromStr;
 
 use base64::Engine;
@@ -14,13 +15,15 @@ use mime::Mime;
 use mozjs::jsapi::{Heap, JSObject};
 use mozjs::jsval::{JSVal, NullValue};
 
-use ion::{ClassDefinition, Context, Error, ErrorKind, Object, Result, TracedHeap};
+use ion::{ClassDefinition, Context, Error, ErrorKind, Function, Object, Result, ResultExc, TracedHeap};
 use ion::class::{NativeObject, Reflector};
 use ion::conversions::ToValue;
 use ion::function::Opt;
 use ion::string::byte::{ByteString, Latin1};
 use ion::typedarray::ArrayBufferWrapper;
 
+use crate::globals::event::ProgressEvent;
+use crate::globals::event_target::EventTarget;
 use crate::globals::file::Blob;
 use crate::promise::future_to_promise;
 
@@ -56,13 +59,128 @@ impl FileReaderState {
 	}
 }
 
-#[derive(Debug)]
+/// Format which a [FileReader] reads the contents of a blob in.
+#[derive(Clone, Debug)]
+enum ReadFormat {
+	ArrayBuffer,
+	BinaryString,
+	Text { encoding: &'static Encoding },
+	DataUrl { mime: Option<String> },
+}
+
+impl ReadFormat {
+	fn text(encoding: Option<&str>, blob: &Blob) -> ReadFormat {
+		ReadFormat::Text {
+			encoding: encoding_from_string_mime(encoding, blob.kind().as_deref()),
+		}
+	}
+
+	fn data_url(blob: &Blob) -> ReadFormat {
+		ReadFormat::DataUrl { mime: blob.kind() }
+	}
+
+	fn to_value(&self, cx: &Context, bytes: &[u8]) -> JSVal {
+		match self {
+			ReadFormat::ArrayBuffer => ArrayBufferWrapper::from(bytes.to_vec()).as_value(cx).get(),
+			ReadFormat::BinaryString => {
+				let byte_string = unsafe { ByteString::<Latin1>::from_unchecked(bytes.to_vec()) };
+				byte_string.as_value(cx).get()
+			}
+			ReadFormat::Text { encoding } => encoding.decode_without_bom_handling(bytes).0.as_value(cx).get(),
+			ReadFormat::DataUrl { mime } => data_url(mime.as_deref(), bytes).as_value(cx).get(),
+		}
+	}
+}
+
+fn data_url(mime: Option<&str>, bytes: &[u8]) -> String {
+	let mime = mime.filter(|mime| !mime.is_empty()).unwrap_or("application/octet-stream");
+	format!("data:{};base64,{}", mime, BASE64_STANDARD.encode(bytes))
+}
+
+/// Reads the contents of blobs asynchronously, dispatching progress events as it reads.
+///
+/// Only one read can be in progress at a time. Aborting a read discards its result once it completes.
 #[js_class]
 pub struct FileReader {
-	reflector: Reflector,
+	event_target: EventTarget,
 	state: FileReaderState,
 	result: Heap<JSVal>,
 	error: Heap<*mut JSObject>,
+	/// Identifies the current read, so that the results of aborted reads are discarded.
+	read_id: u64,
+}
+
+impl FileReader {
+	fn read(&mut self, cx: &Context, blob: &Blob, format: ReadFormat) -> Result<()> {
+		self.state.validate()?;
+		self.result.set(NullValue());
+		self.error.set(ptr::null_mut());
+		self.read_id += 1;
+
+		let read_id = self.read_id;
+		let data = blob.data().clone();
+		let this = TracedHeap::new(self.reflector().get());
+
+		unsafe {
+			future_to_promise(cx, move |cx| async move {
+				let total = data.len();
+				if !FileReader::fire_if_current(&cx, &this, read_id, "loadstart", 0, total)? {
+					return Ok(());
+				}
+
+				let (cx, bytes) = cx.await_native(async move { data.read().await }).await;
+				let reader = Object::from(this.root(&cx));
+				let file_reader = FileReader::get_mut_private(&cx, &reader)?;
+				if file_reader.read_id != read_id || file_reader.state != FileReaderState::Loading {
+					return Ok(());
+				}
+				file_reader.state = FileReaderState::Done;
+
+				let kind = match bytes {
+					Ok(bytes) => {
+						file_reader.result.set(format.to_value(&cx, &bytes));
+						FileReader::fire(&cx, &reader, "progress", total, total)?;
+						"load"
+					}
+					Err(error) => {
+						let error = Error::new(format!("NotReadableError: {}", error), ErrorKind::Normal);
+						if let Some(error) = error.to_object(&cx) {
+							file_reader.error.set(error.handle().get());
+						}
+						"error"
+					}
+				};
+
+				FileReader::fire(&cx, &reader, kind, total, total)?;
+				// Listeners can start another read, in which case `loadend` is not dispatched.
+				if FileReader::get_private(&cx, &reader)?.state != FileReaderState::Loading {
+					FileReader::fire(&cx, &reader, "loadend", total, total)?;
+				}
+				ResultExc::<_>::Ok(())
+			})
+		};
+		Ok(())
+	}
+
+	/// Dispatches an event if the read is still in progress.
+	/// Returns `false` if the read was aborted or replaced by another read.
+	fn fire_if_current(
+		cx: &Context, this: &TracedHeap<*mut JSObject>, read_id: u64, kind: &str, loaded: u64, total: u64,
+	) -> ResultExc<bool> {
+		let reader = Object::from(this.root(cx));
+		let file_reader = FileReader::get_private(cx, &reader)?;
+		if file_reader.read_id != read_id || file_reader.state != FileReaderState::Loading {
+			return Ok(false);
+		}
+		FileReader::fire(cx, &reader, kind, loaded, total)?;
+		Ok(true)
+	}
+
+	fn fire(cx: &Context, reader: &Object, kind: &str, loaded: u64, total: u64) -> ResultExc<()> {
+		let event = ProgressEvent::new_trusted(kind, loaded, total);
+		let event = Object::from(cx.root(ProgressEvent::new_object(cx, Box::new(event))));
+		EventTarget::dispatch(cx, reader, &event).map(|_| ())
+	}
 }
 
 #[js_class]
@@ -93,97 +211,110 @@ impl FileReader {
 
 	#[ion(name = "readAsArrayBuffer")]
 	pub fn read_as_array_buffer(&mut self, cx: &Context, blob: &Blob) -> Result<()> {
-		self.state.validate()?;
-		let bytes = blob.as_bytes().clone();
-
-		let this = TracedHeap::new(self.reflector().get());
-
-		unsafe {
-			future_to_promise(cx, move |cx| async move {
-				let reader = Object::from(this.root(&cx));
-				let reader = FileReader::get_private(&cx, &reader).unwrap();
-				let array_buffer = ArrayBufferWrapper::from(bytes.to_vec());
-				reader.result.set(array_buffer.as_value(&cx).get());
-				Ok::<_, ()>(())
-			})
-		};
-		Ok(())
+		self.read(cx, blob, ReadFormat::ArrayBuffer)
 	}
 
 	#[ion(name = "readAsBinaryString")]
 	pub fn read_as_binary_string(&mut self, cx: &Context, blob: &Blob) -> Result<()> {
-		self.state.validate()?;
-		let bytes = blob.as_bytes().clone();
-
-		let this = TracedHeap::new(self.reflector().get());
-
-		unsafe {
-			future_to_promise(cx, move |cx| async move {
-				let reader = Object::from(this.root(&cx));
-				let reader = FileReader::get_private(&cx, &reader).unwrap();
-				let byte_string = ByteString::<Latin1>::from_unchecked(bytes.to_vec());
-				reader.result.set(byte_string.as_value(&cx).get());
-				Ok::<_, ()>(())
-			})
-		};
-		Ok(())
+		self.read(cx, blob, ReadFormat::BinaryString)
 	}
 
 	#[ion(name = "readAsText")]
 	pub fn read_as_text(&mut self, cx: &Context, blob: &Blob, Opt(encoding): Opt<String>) -> Result<()> {
-		self.state.validate()?;
-		let bytes = blob.as_bytes().clone();
-		let mime = blob.kind();
+		self.read(cx, blob, ReadFormat::text(encoding.as_deref(), blob))
+	}
 
-		let this = TracedHeap::new(self.reflector().get());
+	#[ion(name = "readAsDataURL")]
+	pub fn read_as_data_url(&mut self, cx: &Context, blob: &Blob) -> Result<()> {
+		self.read(cx, blob, ReadFormat::data_url(blob))
+	}
 
-		unsafe {
-			future_to_promise(cx, move |cx| async move {
-				let encoding = encoding_from_string_mime(encoding.as_deref(), mime.as_deref());
+	/// Aborts the read in progress, dispatching the `abort` and `loadend` events.
+	pub fn abort(cx: &Context, #[ion(this)] this: &Object) -> ResultExc<()> {
+		let reader = FileReader::get_mut_private(cx, this)?;
+		reader.result.set(NullValue());
+		if reader.state != FileReaderState::Loading {
+			return Ok(());
+		}
+		reader.state = FileReaderState::Done;
+		reader.read_id += 1;
 
-				let reader = Object::from(this.root(&cx));
-				let reader = FileReader::get_private(&cx, &reader).unwrap();
-				let str = encoding.decode_without_bom_handling(&bytes).0;
-				reader.result.set(str.as_value(&cx).get());
-				Ok::<_, ()>(())
-			})
-		};
+		FileReader::fire(cx, this, "abort", 0, 0)?;
+		if FileReader::get_private(cx, this)?.state != FileReaderState::Loading {
+			FileReader::fire(cx, this, "loadend", 0, 0)?;
+		}
 		Ok(())
 	}
 
-	#[ion(name = "readAsDataURL")]
-	pub fn read_as_data_url(&mut self, cx: &Context, blob: &Blob) -> Result<()> {
-		self.state.validate()?;
-		let bytes = blob.as_bytes().clone();
-		let mime = blob.kind();
+	#[ion(get)]
+	pub fn get_onloadstart(&self) -> Option<*mut JSObject> {
+		self.event_target.event_handler("loadstart")
+	}
 
-		let this = TracedHeap::new(self.reflector().get());
+	#[ion(set)]
+	pub fn set_onloadstart(&mut self, cx: &Context, handler: Option<Function>) {
+		self.event_target.set_event_handler(cx, "loadstart", handler);
+	}
 
-		unsafe {
-			future_to_promise(cx, move |cx| async move {
-				let reader = Object::from(this.root(&cx));
-				let reader = FileReader::get_private(&cx, &reader).unwrap();
-				let base64 = BASE64_STANDARD.encode(&bytes);
-				let data_url = match mime {
-					Some(mime) => format!("data:{};base64,{}", mime, base64),
-					None => format!("data:base64,{}", base64),
-				};
+	#[ion(get)]
+	pub fn get_onprogress(&self) -> Option<*mut JSObject> {
+		self.event_target.event_handler("progress")
+	}
 
-				reader.result.set(data_url.as_value(&cx).get());
-				Ok::<_, ()>(())
-			})
-		};
-		Ok(())
+	#[ion(set)]
+	pub fn set_onprogress(&mut self, cx: &Context, handler: Option<Function>) {
+		self.event_target.set_event_handler(cx, "progress", handler);
+	}
+
+	#[ion(get)]
+	pub fn get_onload(&self) -> Option<*mut JSObject> {
+		self.event_target.event_handler("load")
+	}
+
+	#[ion(set)]
+	pub fn set_onload(&mut self, cx: &Context, handler: Option<Function>) {
+		self.event_target.set_event_handler(cx, "load", handler);
+	}
+
+	#[ion(get)]
+	pub fn get_onabort(&self) -> Option<*mut JSObject> {
+		self.event_target.event_handler("abort")
+	}
+
+	#[ion(set)]
+	pub fn set_onabort(&mut self, cx: &Context, handler: Option<Function>) {
+		self.event_target.set_event_handler(cx, "abort", handler);
+	}
+
+	#[ion(get)]
+	pub fn get_onerror(&self) -> Option<*mut JSObject> {
+		self.event_target.event_handler("error")
+	}
+
+	#[ion(set)]
+	pub fn set_onerror(&mut self, cx: &Context, handler: Option<Function>) {
+		self.event_target.set_event_handler(cx, "error", handler);
+	}
+
+	#[ion(get)]
+	pub fn get_onloadend(&self) -> Option<*mut JSObject> {
+		self.event_target.event_handler("loadend")
+	}
+
+	#[ion(set)]
+	pub fn set_onloadend(&mut self, cx: &Context, handler: Option<Function>) {
+		self.event_target.set_event_handler(cx, "loadend", handler);
 	}
 }
 
 impl Default for FileReader {
 	fn default() -> FileReader {
 		FileReader {
-			reflector: Reflector::default(),
+			event_target: EventTarget::default(),
 			state: FileReaderState::default(),
 			result: Heap { ptr: UnsafeCell::from(NullValue()) },
 			error: Heap::default(),
+			read_id: 0,
 		}
 	}
 }
@@ -202,29 +333,23 @@ impl FileReaderSync {
 	}
 
 	#[ion(name = "readAsArrayBuffer")]
-	pub fn read_as_array_buffer(&mut self, blob: &Blob) -> ArrayBufferWrapper {
-		ArrayBufferWrapper::from(blob.as_bytes().to_vec())
+	pub fn read_as_array_buffer(&mut self, blob: &Blob) -> Result<ArrayBufferWrapper> {
+		Ok(ArrayBufferWrapper::from(blob.to_bytes()?.to_vec()))
 	}
 
 	#[ion(name = "readAsBinaryString")]
-	pub fn read_as_binary_string(&mut self, blob: &Blob) -> ByteString {
-		unsafe { ByteString::<Latin1>::from_unchecked(blob.as_bytes().to_vec()) }
+	pub fn read_as_binary_string(&mut self, blob: &Blob) -> Result<ByteString> {
+		Ok(unsafe { ByteString::<Latin1>::from_unchecked(blob.to_bytes()?.to_vec()) })
 	}
 
 	#[ion(name = "readAsText")]
-	pub fn read_as_text(&mut self, blob: &Blob, Opt(encoding): Opt<String>) -> String {
+	pub fn read_as_text(&mut self, blob: &Blob, Opt(encoding): Opt<String>) -> Result<String> {
 		let encoding = encoding_from_string_mime(encoding.as_deref(), blob.kind().as_deref());
-		encoding.decode_without_bom_handling(blob.as_bytes()).0.into_owned()
+		Ok(encoding.decode_without_bom_handling(&blob.to_bytes()?).0.into_owned())
 	}
 
 	#[ion(name = "readAsDataURL")]
-	pub fn read_as_data_url(&mut self, blob: &Blob) -> String {
-		let mime = blob.kind();
-
-		let base64 = BASE64_STANDARD.encode(blob.as_bytes());
-		match mime {
-			Some(mime) => format!("data:{};base64,{}", mime, base64),
-			None => format!("data:base64,{}", base64),
-		}
+	pub fn read_as_data_url(&mut self, blob: &Blob) -> Result<String> {
+		Ok(data_url(blob.kind().as_deref(), &blob.to_bytes()?))
 	}
 }
@@ -1,13 +1,14 @@
 use ion::{
 	class::{NativeObject, Reflector},
 	conversions::{FromValue, ToValue},
-	function::{Opt, Wrap},
+	function::Opt,
 	symbol::WellKnownSymbolCode,
 	ClassDefinition, Context, Error, ErrorKind, Function, JSIterator, Object, Result, ResultExc, TracedHeap, Value,
 };
+use chrono::Utc;
 use mozjs::jsapi::{JSObject, ToStringSlow};
 
-use super::file::{Blob, File, BlobPart, FileOptions, BlobOptions, Endings};
+use super::file::{Blob, File};
 
 // TODO: maintain the same File instance instead of Bytes
 #[derive(Clone)]
@@ -32,17 +33,11 @@ impl FormDataEntryValue {
 					let file = File::get_private(cx, &obj).unwrap();
 					cx.root(File::new_object(
 						cx,
-						Box::new(File::constructor(
-							vec![BlobPart(file.blob.as_bytes().clone())],
+						Box::new(File {
+							blob: Blob::from_data(file.blob.data().clone(), file.blob.kind()),
 							name,
-							Opt(Some(FileOptions {
-								last_modified: Some(Wrap(file.get_last_modified())),
-								blob: BlobOptions {
-									kind: file.blob.kind(),
-									endings: Endings::Transparent,
-								},
-							})),
-						)),
+							modified: file.modified,
+						}),
 					))
 					.into()
 				} else {
@@ -53,17 +48,11 @@ impl FormDataEntryValue {
 				let blob = Blob::get_private(cx, &obj).unwrap();
 				cx.root(File::new_object(
 					cx,
-					Box::new(File::constructor(
-						vec![BlobPart(blob.as_bytes().clone())],
+					Box::new(File {
+						blob: Blob::from_data(blob.data().clone(), blob.kind()),
 						name,
-						Opt(Some(FileOptions {
-							last_modified: None,
-							blob: BlobOptions {
-								kind: blob.kind(),
-								endings: Endings::Transparent,
-							},
-						})),
-					)),
+						modified: Utc::now(),
+					}),
 				))
 				.into()
 			};
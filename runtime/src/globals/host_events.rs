@@ -0,0 +1,259 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::task;
+use std::task::Poll;
+
+use chrono::Duration;
+use mozjs::jsapi::{JSFunction, JSObject};
+use tokio::sync::mpsc;
+pub use tokio::sync::mpsc::error::{SendError, TrySendError};
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Function, Heap, Object, Result, TracedHeap, Value};
+use ion::class::Reflector;
+use ion::conversions::ToValue;
+
+use crate::cache::map::format_error_report;
+use crate::event_loop::{EventLoop, EventLoopPollResult};
+use crate::event_loop::macrotasks::{Macrotask, SignalMacrotask};
+
+/// Event emitted by the embedder to the listeners of `hostEvents`, which receive its data as their only argument.
+#[derive(Clone, Debug)]
+pub struct HostEvent<T> {
+	pub name: String,
+	pub data: T,
+}
+
+/// Sending half of a [host event channel](host_event_channel), which can be cloned and sent to other threads.
+///
+/// Events are received one at a time, after the listeners of the previous event have run, so the number of
+/// [pending](HostEventSender::pending) events reflects how far behind scripts are.
+#[derive(Debug)]
+pub struct HostEventSender<T> {
+	sender: mpsc::Sender<HostEvent<T>>,
+}
+
+impl<T> HostEventSender<T> {
+	/// Emits an event, or returns it in [TrySendError::Full] if the channel is at capacity.
+	pub fn try_emit(&self, name: impl Into<String>, data: T) -> std::result::Result<(), TrySendError<HostEvent<T>>> {
+		self.sender.try_send(HostEvent { name: name.into(), data })
+	}
+
+	/// Emits an event, waiting for the channel to have capacity.
+	pub async fn emit(&self, name: impl Into<String>, data: T) -> std::result::Result<(), SendError<HostEvent<T>>> {
+		self.sender.send(HostEvent { name: name.into(), data }).await
+	}
+
+	/// Returns the number of events which have not been received by the runtime yet.
+	pub fn pending(&self) -> usize {
+		self.sender.max_capacity() - self.sender.capacity()
+	}
+
+	/// Returns `true` if the runtime has been dropped.
+	pub fn is_closed(&self) -> bool {
+		self.sender.is_closed()
+	}
+}
+
+impl<T> Clone for HostEventSender<T> {
+	fn clone(&self) -> HostEventSender<T> {
+		HostEventSender { sender: self.sender.clone() }
+	}
+}
+
+trait EventData {
+	fn to_js<'cx>(&self, cx: &'cx Context) -> Value<'cx>;
+}
+
+impl<T: for<'cx> ToValue<'cx>> EventData for T {
+	fn to_js<'cx>(&self, cx: &'cx Context) -> Value<'cx> {
+		self.as_value(cx)
+	}
+}
+
+trait EventSource {
+	fn poll_event(&mut self, wcx: &mut task::Context) -> Poll<Option<(String, Box<dyn EventData>)>>;
+}
+
+impl<T: for<'cx> ToValue<'cx> + 'static> EventSource for mpsc::Receiver<HostEvent<T>> {
+	fn poll_event(&mut self, wcx: &mut task::Context) -> Poll<Option<(String, Box<dyn EventData>)>> {
+		self.poll_recv(wcx)
+			.map(|event| event.map(|event| (event.name, Box::new(event.data) as Box<dyn EventData>)))
+	}
+}
+
+/// Receiving half of a [host event channel](host_event_channel), which is passed to
+/// [host_events](crate::RuntimeBuilder::host_events).
+pub struct HostEventReceiver {
+	source: Box<dyn EventSource>,
+}
+
+/// Creates a channel through which the embedder emits events to scripts, which can hold up to `capacity` events that
+/// have not been received by the runtime.
+///
+/// ### Panics
+/// Panics if `capacity` is 0.
+pub fn host_event_channel<T>(capacity: usize) -> (HostEventSender<T>, HostEventReceiver)
+where
+	T: for<'cx> ToValue<'cx> + Send + 'static,
+{
+	let (sender, receiver) = mpsc::channel(capacity);
+	(
+		HostEventSender { sender },
+		HostEventReceiver { source: Box::new(receiver) },
+	)
+}
+
+/// Polls the [host event channel](host_event_channel) from the event loop, and delivers its events through the
+/// macrotask queue.
+pub(crate) struct HostEventQueue {
+	source: Box<dyn EventSource>,
+	emitter: TracedHeap<*mut JSObject>,
+	listening: Rc<Cell<bool>>,
+	scheduled: Rc<Cell<bool>>,
+	closed: bool,
+}
+
+impl HostEventQueue {
+	pub(crate) fn poll_events(&mut self, cx: &Context, wcx: &mut task::Context) -> EventLoopPollResult {
+		if self.closed || self.scheduled.get() {
+			return EventLoopPollResult::NothingToDo;
+		}
+
+		match self.source.poll_event(wcx) {
+			Poll::Ready(Some((name, data))) => {
+				let Some(queue) = &mut EventLoop::from_context(cx).macrotasks else {
+					return EventLoopPollResult::NothingToDo;
+				};
+				self.scheduled.set(true);
+
+				let emitter = self.emitter.clone();
+				let scheduled = Rc::clone(&self.scheduled);
+				let callback = Box::new(move |cx: &Context| {
+					scheduled.set(false);
+					dispatch(cx, &Object::from(emitter.root(cx)), &name, &*data);
+				});
+				let terminate = Arc::new(AtomicBool::new(false));
+				queue.enqueue(
+					cx,
+					Macrotask::Signal(SignalMacrotask::new(callback, terminate, Duration::zero())),
+					None,
+				);
+				EventLoopPollResult::DidWork
+			}
+			Poll::Ready(None) => {
+				self.closed = true;
+				EventLoopPollResult::NothingToDo
+			}
+			Poll::Pending => EventLoopPollResult::NothingToDo,
+		}
+	}
+
+	/// Stops receiving events, even if the channel is still open.
+	pub(crate) fn close(&mut self) {
+		self.closed = true;
+	}
+
+	/// Returns `true` if the channel is open and scripts are listening, which keeps the event loop running.
+	pub(crate) fn is_active(&self) -> bool {
+		!self.closed && self.listening.get()
+	}
+}
+
+fn dispatch(cx: &Context, emitter: &Object, name: &str, data: &dyn EventData) {
+	let Ok(events) = HostEvents::get_private(cx, emitter) else {
+		return;
+	};
+	let callbacks: Vec<_> = events
+		.listeners
+		.iter()
+		.filter(|listener| listener.event == name)
+		.map(|listener| Function::from(cx.root(listener.callback.get())))
+		.collect();
+	if callbacks.is_empty() {
+		return;
+	}
+
+	let args = [data.to_js(cx)];
+	for callback in callbacks {
+		if let Err(Some(report)) = callback.call(cx, emitter, &args) {
+			eprintln!(
+				"Uncaught exception in host event listener: {}",
+				format_error_report(cx, report)
+			);
+		}
+	}
+}
+
+#[derive(Traceable)]
+struct HostListener {
+	event: String,
+	callback: Heap<*mut JSFunction>,
+}
+
+/// Emitter of the events sent by the embedder through a [host event channel](host_event_channel).
+///
+/// The event loop keeps running while the channel is open and at least one listener is registered.
+#[js_class]
+pub struct HostEvents {
+	reflector: Reflector,
+	listeners: Vec<HostListener>,
+	#[trace(no_trace)]
+	listening: Rc<Cell<bool>>,
+}
+
+#[js_class]
+impl HostEvents {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<HostEvents> {
+		Err(Error::new("HostEvents has no constructor.", ErrorKind::Type))
+	}
+
+	pub fn on(&mut self, event: String, callback: Function) {
+		self.listeners.push(HostListener {
+			event,
+			callback: Heap::new(callback.get()),
+		});
+		self.listening.set(true);
+	}
+
+	pub fn off(&mut self, event: String, callback: Function) {
+		let position = self
+			.listeners
+			.iter()
+			.position(|listener| listener.event == event && listener.callback.get() == callback.get());
+		if let Some(position) = position {
+			self.listeners.remove(position);
+		}
+		self.listening.set(!self.listeners.is_empty());
+	}
+}
+
+/// Defines the `hostEvents` global, whose listeners receive the events of the channel.
+pub(crate) fn define(cx: &Context, global: &Object, receiver: HostEventReceiver) -> Option<HostEventQueue> {
+	if !HostEvents::init_class(cx, global).0 {
+		return None;
+	}
+
+	let listening = Rc::new(Cell::new(false));
+	let events = HostEvents {
+		reflector: Reflector::default(),
+		listeners: Vec::new(),
+		listening: Rc::clone(&listening),
+	};
+	let emitter = cx.root(HostEvents::new_object(cx, Box::new(events)));
+	global.set_as(cx, "hostEvents", &emitter.get()).then(|| HostEventQueue {
+		source: receiver.source,
+		emitter: TracedHeap::new(emitter.get()),
+		listening,
+		scheduled: Rc::new(Cell::new(false)),
+		closed: false,
+	})
+}
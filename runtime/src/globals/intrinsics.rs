@@ -0,0 +1,109 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::jsapi::JS_DeepFreezeObject;
+
+use ion::{Array, Context, Object};
+use ion::script::Script;
+
+/// Globals of the ECMAScript standard library, which are frozen along with the objects reachable from their properties,
+/// such as their prototypes.
+const INTRINSICS: &[&str] = &[
+	"AggregateError",
+	"Array",
+	"ArrayBuffer",
+	"Atomics",
+	"BigInt",
+	"BigInt64Array",
+	"BigUint64Array",
+	"Boolean",
+	"DataView",
+	"Date",
+	"Error",
+	"EvalError",
+	"FinalizationRegistry",
+	"Float32Array",
+	"Float64Array",
+	"Function",
+	"Int16Array",
+	"Int32Array",
+	"Int8Array",
+	"Intl",
+	"Iterator",
+	"JSON",
+	"Map",
+	"Math",
+	"Number",
+	"Object",
+	"Promise",
+	"Proxy",
+	"RangeError",
+	"ReferenceError",
+	"Reflect",
+	"RegExp",
+	"Set",
+	"SharedArrayBuffer",
+	"String",
+	"Symbol",
+	"SyntaxError",
+	"TypeError",
+	"URIError",
+	"Uint16Array",
+	"Uint32Array",
+	"Uint8Array",
+	"Uint8ClampedArray",
+	"WeakMap",
+	"WeakRef",
+	"WeakSet",
+	"decodeURI",
+	"decodeURIComponent",
+	"encodeURI",
+	"encodeURIComponent",
+	"escape",
+	"isFinite",
+	"isNaN",
+	"parseFloat",
+	"parseInt",
+	"unescape",
+];
+
+/// Intrinsics which are not reachable from the properties of globals, such as the prototypes of generators, async
+/// functions and iterators.
+const HIDDEN_INTRINSICS: &str = r#"[
+	Object.getPrototypeOf(function* () {}),
+	Object.getPrototypeOf(async function () {}),
+	Object.getPrototypeOf(async function* () {}),
+	Object.getPrototypeOf(Int8Array),
+	Object.getPrototypeOf([][Symbol.iterator]()),
+	Object.getPrototypeOf(new Map()[Symbol.iterator]()),
+	Object.getPrototypeOf(new Set()[Symbol.iterator]()),
+	Object.getPrototypeOf(""[Symbol.iterator]()),
+	Object.getPrototypeOf(/(?:)/[Symbol.matchAll]("")),
+	Object.getPrototypeOf(Object.getPrototypeOf(async function* () {}).prototype),
+]"#;
+
+/// Freezes the intrinsics of the realm of the global, so that scripts cannot modify shared prototypes such as
+/// `Object.prototype`, preventing prototype pollution from persisting between requests which share the realm.
+///
+/// Globals named in `patchable` are left mutable, but objects which are also reachable from a frozen global are frozen
+/// regardless. The global object itself is never frozen, so scripts can still define their own globals.
+pub(crate) fn freeze(cx: &Context, global: &Object, patchable: &[String]) -> bool {
+	let hidden = match Script::compile_and_evaluate(cx, Path::new("intrinsics.js"), HIDDEN_INTRINSICS) {
+		Ok(hidden) => Array::from(cx, hidden.to_object(cx).into_local()).unwrap(),
+		Err(_) => return false,
+	};
+
+	let globals = INTRINSICS.iter().filter(|name| !patchable.iter().any(|patchable| patchable == *name));
+	let globals = globals.filter_map(|name| global.get(cx, *name).ok().flatten());
+	let hidden = (0..hidden.len(cx)).filter_map(|index| hidden.get(cx, index).ok().flatten());
+
+	globals.chain(hidden).filter(|value| value.handle().is_object()).all(|value| {
+		let object = value.to_object(cx);
+		unsafe { JS_DeepFreezeObject(cx.as_ptr(), object.handle().into()) }
+	})
+}
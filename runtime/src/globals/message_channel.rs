@@ -0,0 +1,298 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::mem::take;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use chrono::Duration;
+use mozjs::jsapi::{JSFunction, JSObject};
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Function, Heap, Object, Result, ResultExc, TracedHeap, Value};
+use ion::class::Reflector;
+use ion::clone::{structured_deserialize, structured_serialize};
+
+use crate::cache::map::format_error_report;
+use crate::event_loop::EventLoop;
+use crate::event_loop::macrotasks::{Macrotask, SignalMacrotask};
+
+/// Data of a message posted to a port, serialised with the structured clone algorithm so that it is copied rather than
+/// shared. It can be deserialised with [structured_deserialize].
+pub type MessageData = Vec<u8>;
+
+type SharedInbox = Rc<RefCell<Inbox>>;
+
+/// Messages received by a port, which are queued until the port is started.
+#[derive(Default)]
+struct Inbox {
+	messages: VecDeque<MessageData>,
+	started: bool,
+	closed: bool,
+	scheduled: bool,
+	/// Object of the receiving port, which is kept alive while it is started so that messages can be dispatched.
+	/// Host ports have no object, and messages are taken with [HostPort::try_recv] instead.
+	port: Option<TracedHeap<*mut JSObject>>,
+}
+
+fn deliver(cx: &Context, inbox: &SharedInbox, message: MessageData) {
+	let mut state = inbox.borrow_mut();
+	if !state.closed {
+		state.messages.push_back(message);
+		drop(state);
+		schedule(cx, inbox);
+	}
+}
+
+/// Schedules a macrotask which dispatches the queued messages of a started script port.
+fn schedule(cx: &Context, inbox: &SharedInbox) {
+	let mut state = inbox.borrow_mut();
+	if !state.started || state.scheduled || state.messages.is_empty() {
+		return;
+	}
+	if let Some(queue) = &mut EventLoop::from_context(cx).macrotasks {
+		state.scheduled = true;
+		let inbox = Rc::clone(inbox);
+		let callback = Box::new(move |cx: &Context| dispatch(cx, &inbox));
+		let terminate = Arc::new(AtomicBool::new(false));
+		queue.enqueue(
+			cx,
+			Macrotask::Signal(SignalMacrotask::new(callback, terminate, Duration::zero())),
+			None,
+		);
+	}
+}
+
+fn dispatch(cx: &Context, inbox: &SharedInbox) {
+	let (messages, port) = {
+		let mut state = inbox.borrow_mut();
+		state.scheduled = false;
+		if state.closed {
+			return;
+		}
+		let Some(port) = state.port.clone() else {
+			return;
+		};
+		(take(&mut state.messages), port)
+	};
+
+	let port = Object::from(port.root(cx));
+	for message in messages {
+		let Ok(this) = MessagePort::get_private(cx, &port) else {
+			return;
+		};
+		if this.inbox.borrow().closed {
+			return;
+		}
+
+		let (kind, data, handler) = match structured_deserialize(cx, &message) {
+			Ok(data) => ("message", data, this.on_message.as_ref().map(Heap::get)),
+			Err(_) => (
+				"messageerror",
+				Value::undefined(cx),
+				this.on_message_error.as_ref().map(Heap::get),
+			),
+		};
+		let Some(handler) = handler else {
+			continue;
+		};
+
+		let event = Object::new(cx);
+		event.set_as(cx, "type", kind);
+		event.set_as(cx, "data", &data);
+		event.set_as(cx, "target", &port);
+
+		let handler = Function::from(cx.root(handler));
+		if let Err(Some(report)) = handler.call_with(cx, &port, [event]) {
+			eprintln!(
+				"Uncaught exception in message handler: {}",
+				format_error_report(cx, report)
+			);
+		}
+	}
+}
+
+/// End of a message channel, which delivers the messages posted to its entangled port through the macrotask queue.
+///
+/// Messages are only dispatched once the port is started, either explicitly or by setting `onmessage`.
+/// Ports cannot be transferred in messages.
+#[js_class]
+pub struct MessagePort {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	inbox: SharedInbox,
+	#[trace(no_trace)]
+	remote: Option<SharedInbox>,
+	on_message: Option<Heap<*mut JSFunction>>,
+	on_message_error: Option<Heap<*mut JSFunction>>,
+}
+
+impl MessagePort {
+	fn new_entangled(cx: &Context, remote: Option<SharedInbox>) -> (*mut JSObject, SharedInbox) {
+		let inbox = SharedInbox::default();
+		let port = MessagePort {
+			reflector: Reflector::default(),
+			inbox: Rc::clone(&inbox),
+			remote,
+			on_message: None,
+			on_message_error: None,
+		};
+		(MessagePort::new_object(cx, Box::new(port)), inbox)
+	}
+
+	/// Creates a port for scripts, whose entangled port is held by the embedder.
+	pub fn new_host_pair(cx: &Context) -> (*mut JSObject, HostPort) {
+		let host = SharedInbox::default();
+		let (object, inbox) = MessagePort::new_entangled(cx, Some(Rc::clone(&host)));
+		(object, HostPort { inbox: host, remote: Some(inbox) })
+	}
+
+	fn start_port(&self, cx: &Context) {
+		{
+			let mut inbox = self.inbox.borrow_mut();
+			if inbox.started || inbox.closed {
+				return;
+			}
+			inbox.started = true;
+			inbox.port = Some(TracedHeap::new(self.reflector.get()));
+		}
+		schedule(cx, &self.inbox);
+	}
+}
+
+#[js_class]
+impl MessagePort {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<MessagePort> {
+		Err(Error::new("MessagePort has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(name = "postMessage")]
+	pub fn post_message(&self, cx: &Context, message: Value) -> ResultExc<()> {
+		let message = structured_serialize(cx, &message)?;
+		if let Some(remote) = &self.remote {
+			deliver(cx, remote, message);
+		}
+		Ok(())
+	}
+
+	pub fn start(&self, cx: &Context) {
+		self.start_port(cx);
+	}
+
+	/// Disentangles the port. Queued and subsequent messages to the port are discarded.
+	pub fn close(&mut self) {
+		let mut inbox = self.inbox.borrow_mut();
+		inbox.closed = true;
+		inbox.messages.clear();
+		inbox.port = None;
+		drop(inbox);
+		self.remote = None;
+	}
+
+	#[ion(get)]
+	pub fn get_onmessage(&self) -> Option<*mut JSFunction> {
+		self.on_message.as_ref().map(Heap::get)
+	}
+
+	#[ion(set)]
+	pub fn set_onmessage(&mut self, cx: &Context, handler: Option<Function>) {
+		self.on_message = handler.map(|handler| Heap::new(handler.get()));
+		if self.on_message.is_some() {
+			self.start_port(cx);
+		}
+	}
+
+	#[ion(get)]
+	pub fn get_onmessageerror(&self) -> Option<*mut JSFunction> {
+		self.on_message_error.as_ref().map(Heap::get)
+	}
+
+	#[ion(set)]
+	pub fn set_onmessageerror(&mut self, handler: Option<Function>) {
+		self.on_message_error = handler.map(|handler| Heap::new(handler.get()));
+	}
+}
+
+#[js_class]
+pub struct MessageChannel {
+	reflector: Reflector,
+	port1: Heap<*mut JSObject>,
+	port2: Heap<*mut JSObject>,
+}
+
+#[js_class]
+impl MessageChannel {
+	#[ion(constructor)]
+	pub fn constructor(cx: &Context) -> MessageChannel {
+		let (port1, inbox1) = MessagePort::new_entangled(cx, None);
+		let (port2, inbox2) = MessagePort::new_entangled(cx, Some(inbox1));
+		let port1 = Object::from(cx.root(port1));
+		MessagePort::get_mut_private(cx, &port1).unwrap().remote = Some(inbox2);
+
+		MessageChannel {
+			reflector: Reflector::default(),
+			port1: Heap::new(port1.handle().get()),
+			port2: Heap::new(port2),
+		}
+	}
+
+	#[ion(get)]
+	pub fn get_port1(&self) -> *mut JSObject {
+		self.port1.get()
+	}
+
+	#[ion(get)]
+	pub fn get_port2(&self) -> *mut JSObject {
+		self.port2.get()
+	}
+}
+
+/// Port held by the embedder, which is entangled with a [MessagePort] created by [MessagePort::new_host_pair].
+pub struct HostPort {
+	inbox: SharedInbox,
+	remote: Option<SharedInbox>,
+}
+
+impl HostPort {
+	/// Posts a message to the script port, which must have been serialised with [structured_serialize].
+	pub fn post_message(&self, cx: &Context, message: MessageData) {
+		if let Some(remote) = &self.remote {
+			deliver(cx, remote, message);
+		}
+	}
+
+	/// Serialises a value and posts it to the script port.
+	pub fn post_value(&self, cx: &Context, value: Value) -> ResultExc<()> {
+		let message = structured_serialize(cx, &value)?;
+		self.post_message(cx, message);
+		Ok(())
+	}
+
+	/// Returns the next message posted by the script, if any.
+	pub fn try_recv(&self) -> Option<MessageData> {
+		self.inbox.borrow_mut().messages.pop_front()
+	}
+
+	/// Returns the next message posted by the script, deserialised into a value, if any.
+	pub fn try_recv_value<'cx>(&self, cx: &'cx Context) -> Option<ResultExc<Value<'cx>>> {
+		self.try_recv().map(|message| structured_deserialize(cx, &message))
+	}
+
+	pub fn close(&mut self) {
+		let mut inbox = self.inbox.borrow_mut();
+		inbox.closed = true;
+		inbox.messages.clear();
+		drop(inbox);
+		self.remote = None;
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	MessageChannel::init_class(cx, global).0 && MessagePort::init_class(cx, global).0
+}
@@ -0,0 +1,55 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ffi::CStr;
+
+use mozjs::jsapi::{JS_DeepFreezeObject, JS_GetImplementationVersion};
+
+use ion::{Context, Object};
+use ion::flags::PropertyFlags;
+
+use crate::VERSION;
+
+/// Target triple the runtime was compiled for, such as `x86_64-unknown-linux-gnu`.
+pub const TARGET: &str = env!("SPIDERFIRE_TARGET");
+
+/// Returns the version of SpiderMonkey the runtime is linked against, such as `JavaScript-C115.0`.
+pub fn spidermonkey_version() -> String {
+	unsafe { CStr::from_ptr(JS_GetImplementationVersion()) }.to_string_lossy().into_owned()
+}
+
+/// Returns the cargo features the runtime was compiled with.
+pub fn features() -> Vec<&'static str> {
+	let mut features = Vec::new();
+	if cfg!(feature = "debugmozjs") {
+		features.push("debugmozjs");
+	}
+	if cfg!(feature = "fetch") {
+		features.push("fetch");
+	}
+	if cfg!(feature = "inspector") {
+		features.push("inspector");
+	}
+	features
+}
+
+/// Creates a frozen object with the `version`, `spidermonkey`, `features` and `target` of the runtime, which is exposed
+/// as the `spiderfire` global and the `spiderfire:meta` module so that bug reports can name the exact build.
+pub fn build_info(cx: &Context) -> Option<Object> {
+	let info = Object::new(cx);
+	let defined = info.set_as(cx, "version", VERSION)
+		&& info.set_as(cx, "spidermonkey", &spidermonkey_version())
+		&& info.set_as(cx, "features", &features())
+		&& info.set_as(cx, "target", TARGET);
+	(defined && unsafe { JS_DeepFreezeObject(cx.as_ptr(), info.handle().into()) }).then_some(info)
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	match build_info(cx) {
+		Some(info) => global.define_as(cx, "spiderfire", &info, PropertyFlags::CONSTANT),
+		None => false,
+	}
+}
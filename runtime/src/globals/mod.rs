@@ -4,29 +4,65 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use mozjs::jsapi::OnNewGlobalHookOption;
+use mozjs::rust::SIMPLE_GLOBAL_CLASS;
+
 use ion::{ClassDefinition, Context, Iterator, Object};
+use ion::object::new_global;
+use ion::realm::in_realm;
+
+use crate::event_loop::EventLoop;
 
 pub mod abort;
+pub mod async_local_storage;
 pub mod base64;
 pub mod console;
+pub mod crypto;
+pub mod dispose;
 pub mod encoding;
+pub mod event;
+pub mod event_target;
 #[cfg(feature = "fetch")]
 pub mod fetch;
 pub mod file;
 pub mod form_data;
+pub mod host_events;
+pub mod intrinsics;
+pub mod message_channel;
+pub mod meta;
 pub mod microtasks;
+pub mod performance;
+pub mod process;
+pub mod prompt;
+pub mod random;
+pub mod shadow_realm;
+pub mod shutdown;
+pub mod stack_frames;
+pub mod storage;
 pub mod streams;
+pub mod structured_clone;
 pub mod timers;
 pub mod url;
 
 pub fn init_globals(cx: &Context, global: &Object) -> bool {
-	let result = base64::define(cx, global)
+	let result = async_local_storage::define(cx, global)
+		&& base64::define(cx, global)
 		&& console::define(cx, global)
+		&& crypto::define(cx, global)
 		&& encoding::define(cx, global)
+		&& event::define(cx, global)
+		&& event_target::define(cx, global)
 		&& file::define(cx, global)
 		&& form_data::define(cx, global)
+		&& meta::define(cx, global)
+		&& performance::define(cx, global)
+		&& shadow_realm::define(cx, global)
+		&& shutdown::define(cx, global)
 		&& url::define(cx, global)
+		&& stack_frames::define(cx, global)
 		&& streams::define(cx, global)
+		&& dispose::define(cx, global)
+		&& structured_clone::define(cx, global)
 		&& Iterator::init_class(cx, global).0;
 	#[cfg(feature = "fetch")]
 	{
@@ -39,9 +75,35 @@ pub fn init_globals(cx: &Context, global: &Object) -> bool {
 }
 
 pub fn init_timers(cx: &Context, global: &Object) -> bool {
-	timers::define(cx, global) && abort::define(cx, global)
+	timers::define(cx, global) && abort::define(cx, global) && message_channel::define(cx, global)
 }
 
 pub fn init_microtasks(cx: &Context, global: &Object) -> bool {
 	microtasks::define(cx, global)
 }
+
+/// Creates a global in a new realm, and defines the globals of the runtime on it, along with the timers and microtasks
+/// if the event loop has their queues.
+pub(crate) fn create_realm(cx: &Context) -> Object {
+	let global = new_global(
+		cx,
+		&SIMPLE_GLOBAL_CLASS,
+		None,
+		OnNewGlobalHookOption::FireOnNewGlobalHook,
+		None,
+	);
+	in_realm(cx, &global, || {
+		let global_obj = global.handle().get();
+		global.set_as(cx, "global", &global_obj);
+		init_globals(cx, &global);
+
+		let event_loop = EventLoop::from_context(cx);
+		if event_loop.microtasks.is_some() {
+			init_microtasks(cx, &global);
+		}
+		if event_loop.macrotasks.is_some() {
+			init_timers(cx, &global);
+		}
+	});
+	global
+}
@@ -0,0 +1,392 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use mozjs::jsapi::JSObject;
+use mozjs::jsval::{JSVal, NullValue};
+
+use ion::{Array, ClassDefinition, Context, Error, ErrorKind, Heap, Object, Result, TracedHeap};
+use ion::class::Reflector;
+use ion::flags::PropertyFlags;
+use ion::function::Opt;
+
+use crate::ContextExt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Traceable)]
+pub enum EntryType {
+	Mark,
+	Measure,
+}
+
+impl EntryType {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			EntryType::Mark => "mark",
+			EntryType::Measure => "measure",
+		}
+	}
+
+	pub fn from_name(entry_type: &str) -> Option<EntryType> {
+		match entry_type {
+			"mark" => Some(EntryType::Mark),
+			"measure" => Some(EntryType::Measure),
+			_ => None,
+		}
+	}
+}
+
+/// Mark or measure recorded in the performance timeline.
+pub struct TimelineEntry {
+	pub name: String,
+	pub entry_type: EntryType,
+	/// Milliseconds since the time origin of the timeline.
+	pub start_time: f64,
+	/// Milliseconds between the start and end of a measure, which is always `0` for marks.
+	pub duration: f64,
+	detail: Option<TracedHeap<JSVal>>,
+}
+
+/// Buffer of marks and measures for a runtime, with a monotonic time origin.
+///
+/// It is shared between the `performance` global and the embedder, so that native code can add and read entries
+/// alongside scripts, such as for profiling.
+pub struct PerformanceTimeline {
+	origin: Instant,
+	time_origin: f64,
+	entries: Vec<TimelineEntry>,
+}
+
+impl PerformanceTimeline {
+	#[allow(clippy::mut_from_ref)]
+	pub fn from_context(cx: &Context) -> &mut PerformanceTimeline {
+		unsafe { &mut cx.get_private().performance }
+	}
+
+	/// Returns the milliseconds elapsed since the time origin.
+	pub fn now(&self) -> f64 {
+		self.origin.elapsed().as_secs_f64() * 1000.0
+	}
+
+	/// Returns the time origin in milliseconds since the Unix epoch.
+	pub fn time_origin(&self) -> f64 {
+		self.time_origin
+	}
+
+	pub fn entries(&self) -> &[TimelineEntry] {
+		&self.entries
+	}
+
+	pub fn entries_by_type(&self, entry_type: EntryType) -> impl Iterator<Item = &TimelineEntry> {
+		self.entries.iter().filter(move |entry| entry.entry_type == entry_type)
+	}
+
+	/// Returns the start time of the latest mark with the given name.
+	pub fn find_mark(&self, name: &str) -> Option<f64> {
+		self.entries_by_type(EntryType::Mark)
+			.filter(|entry| entry.name == name)
+			.last()
+			.map(|entry| entry.start_time)
+	}
+
+	/// Records a mark at the given time, or at the current time.
+	pub fn mark(&mut self, name: &str, start_time: Option<f64>) -> &TimelineEntry {
+		let start_time = start_time.unwrap_or_else(|| self.now());
+		self.push(String::from(name), EntryType::Mark, start_time, 0.0, None)
+	}
+
+	/// Records a measure between two times.
+	pub fn measure(&mut self, name: &str, start_time: f64, end_time: f64) -> &TimelineEntry {
+		self.push(
+			String::from(name),
+			EntryType::Measure,
+			start_time,
+			end_time - start_time,
+			None,
+		)
+	}
+
+	/// Removes the entries of the given type, or only those with the given name.
+	pub fn clear(&mut self, entry_type: EntryType, name: Option<&str>) {
+		self.entries
+			.retain(|entry| entry.entry_type != entry_type || name.is_some_and(|name| entry.name != name));
+	}
+
+	fn push(
+		&mut self, name: String, entry_type: EntryType, start_time: f64, duration: f64, detail: Option<JSVal>,
+	) -> &TimelineEntry {
+		self.entries.push(TimelineEntry {
+			name,
+			entry_type,
+			start_time,
+			duration,
+			detail: detail.filter(|detail| !detail.is_null_or_undefined()).map(TracedHeap::new),
+		});
+		self.entries.last().unwrap()
+	}
+}
+
+impl Default for PerformanceTimeline {
+	fn default() -> PerformanceTimeline {
+		let time_origin = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+		PerformanceTimeline {
+			origin: Instant::now(),
+			time_origin: time_origin.as_secs_f64() * 1000.0,
+			entries: Vec::new(),
+		}
+	}
+}
+
+#[derive(FromValue)]
+pub enum MarkReference {
+	#[ion(inherit)]
+	Time(#[ion(strict)] f64),
+	#[ion(inherit)]
+	Mark(String),
+}
+
+impl MarkReference {
+	fn resolve(&self, timeline: &PerformanceTimeline) -> Result<f64> {
+		match self {
+			MarkReference::Time(time) if *time < 0.0 => Err(Error::new("Time cannot be negative", ErrorKind::Type)),
+			MarkReference::Time(time) => Ok(*time),
+			MarkReference::Mark(name) => timeline
+				.find_mark(name)
+				.ok_or_else(|| Error::new(format!("Mark \"{}\" does not exist", name), ErrorKind::Syntax)),
+		}
+	}
+}
+
+#[derive(Default, FromValue)]
+pub struct PerformanceMarkOptions {
+	pub detail: Option<JSVal>,
+	pub start_time: Option<f64>,
+}
+
+#[derive(FromValue)]
+pub struct PerformanceMeasureOptions {
+	pub detail: Option<JSVal>,
+	pub start: Option<MarkReference>,
+	pub duration: Option<f64>,
+	pub end: Option<MarkReference>,
+}
+
+#[derive(FromValue)]
+pub enum MeasureStart {
+	#[ion(inherit)]
+	Options(PerformanceMeasureOptions),
+	#[ion(inherit)]
+	Start(MarkReference),
+}
+
+#[js_class]
+pub struct PerformanceEntry {
+	reflector: Reflector,
+	name: String,
+	entry_type: EntryType,
+	start_time: f64,
+	duration: f64,
+	detail: Heap<JSVal>,
+}
+
+impl PerformanceEntry {
+	pub fn new_from_entry(cx: &Context, entry: &TimelineEntry) -> *mut JSObject {
+		let entry = PerformanceEntry {
+			reflector: Reflector::default(),
+			name: entry.name.clone(),
+			entry_type: entry.entry_type,
+			start_time: entry.start_time,
+			duration: entry.duration,
+			detail: Heap::new(entry.detail.as_ref().map_or_else(NullValue, TracedHeap::get)),
+		};
+		PerformanceEntry::new_object(cx, Box::new(entry))
+	}
+
+	fn new_array<'a>(cx: &Context, entries: impl Iterator<Item = &'a TimelineEntry>) -> *mut JSObject {
+		let array = Array::new(cx);
+		for (index, entry) in entries.enumerate() {
+			array.set_as(cx, index as u32, &PerformanceEntry::new_from_entry(cx, entry));
+		}
+		array.into_local().get()
+	}
+}
+
+#[js_class]
+impl PerformanceEntry {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<PerformanceEntry> {
+		Err(Error::new("PerformanceEntry has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_name(&self) -> String {
+		self.name.clone()
+	}
+
+	#[ion(get)]
+	pub fn get_entry_type(&self) -> &'static str {
+		self.entry_type.as_str()
+	}
+
+	#[ion(get)]
+	pub fn get_start_time(&self) -> f64 {
+		self.start_time
+	}
+
+	#[ion(get)]
+	pub fn get_duration(&self) -> f64 {
+		self.duration
+	}
+
+	#[ion(get)]
+	pub fn get_detail(&self) -> JSVal {
+		self.detail.get()
+	}
+
+	#[ion(name = "toJSON")]
+	pub fn to_json(&self, cx: &Context) -> *mut JSObject {
+		let object = Object::new(cx);
+		object.set_as(cx, "name", &self.name);
+		object.set_as(cx, "entryType", &self.entry_type.as_str());
+		object.set_as(cx, "startTime", &self.start_time);
+		object.set_as(cx, "duration", &self.duration);
+		object.handle().get()
+	}
+}
+
+#[js_class]
+pub struct Performance {
+	reflector: Reflector,
+}
+
+#[js_class]
+impl Performance {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Performance> {
+		Err(Error::new("Performance has no constructor.", ErrorKind::Type))
+	}
+
+	pub fn now(&self, cx: &Context) -> f64 {
+		PerformanceTimeline::from_context(cx).now()
+	}
+
+	#[ion(get)]
+	pub fn get_time_origin(&self, cx: &Context) -> f64 {
+		PerformanceTimeline::from_context(cx).time_origin()
+	}
+
+	pub fn mark(&self, cx: &Context, name: String, Opt(options): Opt<PerformanceMarkOptions>) -> Result<*mut JSObject> {
+		let options = options.unwrap_or_default();
+		if options.start_time.is_some_and(|start_time| start_time < 0.0) {
+			return Err(Error::new("Start time cannot be negative", ErrorKind::Type));
+		}
+
+		let timeline = PerformanceTimeline::from_context(cx);
+		let start_time = options.start_time.unwrap_or_else(|| timeline.now());
+		let entry = timeline.push(name, EntryType::Mark, start_time, 0.0, options.detail);
+		Ok(PerformanceEntry::new_from_entry(cx, entry))
+	}
+
+	pub fn measure(
+		&self, cx: &Context, name: String, Opt(start): Opt<MeasureStart>, Opt(end_mark): Opt<String>,
+	) -> Result<*mut JSObject> {
+		let timeline = PerformanceTimeline::from_context(cx);
+		let (start_time, end_time, detail) = match start {
+			Some(MeasureStart::Options(options)) => {
+				if end_mark.is_some() {
+					return Err(Error::new(
+						"End mark cannot be used with measure options",
+						ErrorKind::Type,
+					));
+				}
+				if options.start.is_some() && options.end.is_some() && options.duration.is_some() {
+					return Err(Error::new(
+						"Measure options cannot include start, end and duration",
+						ErrorKind::Type,
+					));
+				}
+
+				let start_time = options.start.as_ref().map(|start| start.resolve(timeline)).transpose()?;
+				let end_time = match (&options.end, start_time, options.duration) {
+					(Some(end), _, _) => end.resolve(timeline)?,
+					(None, Some(start_time), Some(duration)) => start_time + duration,
+					_ => timeline.now(),
+				};
+				let start_time = match (start_time, options.duration) {
+					(Some(start_time), _) => start_time,
+					(None, Some(duration)) if options.end.is_some() => end_time - duration,
+					_ => 0.0,
+				};
+				(start_time, end_time, options.detail)
+			}
+			Some(MeasureStart::Start(start)) => {
+				let end_time = match end_mark {
+					Some(end_mark) => MarkReference::Mark(end_mark).resolve(timeline)?,
+					None => timeline.now(),
+				};
+				(start.resolve(timeline)?, end_time, None)
+			}
+			None => {
+				let end_time = match end_mark {
+					Some(end_mark) => MarkReference::Mark(end_mark).resolve(timeline)?,
+					None => timeline.now(),
+				};
+				(0.0, end_time, None)
+			}
+		};
+
+		let entry = timeline.push(name, EntryType::Measure, start_time, end_time - start_time, detail);
+		Ok(PerformanceEntry::new_from_entry(cx, entry))
+	}
+
+	#[ion(name = "getEntries")]
+	pub fn get_entries(&self, cx: &Context) -> *mut JSObject {
+		PerformanceEntry::new_array(cx, PerformanceTimeline::from_context(cx).entries().iter())
+	}
+
+	#[ion(name = "getEntriesByType")]
+	pub fn get_entries_by_type(&self, cx: &Context, entry_type: String) -> *mut JSObject {
+		let timeline = PerformanceTimeline::from_context(cx);
+		match EntryType::from_name(&entry_type) {
+			Some(entry_type) => PerformanceEntry::new_array(cx, timeline.entries_by_type(entry_type)),
+			None => Array::new(cx).into_local().get(),
+		}
+	}
+
+	#[ion(name = "getEntriesByName")]
+	pub fn get_entries_by_name(&self, cx: &Context, name: String, Opt(entry_type): Opt<String>) -> *mut JSObject {
+		let timeline = PerformanceTimeline::from_context(cx);
+		let entries = timeline.entries().iter().filter(|entry| {
+			entry.name == name && entry_type.as_ref().map_or(true, |entry_type| entry.entry_type.as_str() == entry_type)
+		});
+		PerformanceEntry::new_array(cx, entries)
+	}
+
+	#[ion(name = "clearMarks")]
+	pub fn clear_marks(&self, cx: &Context, Opt(name): Opt<String>) {
+		PerformanceTimeline::from_context(cx).clear(EntryType::Mark, name.as_deref());
+	}
+
+	#[ion(name = "clearMeasures")]
+	pub fn clear_measures(&self, cx: &Context, Opt(name): Opt<String>) {
+		PerformanceTimeline::from_context(cx).clear(EntryType::Measure, name.as_deref());
+	}
+
+	#[ion(name = "toJSON")]
+	pub fn to_json(&self, cx: &Context) -> *mut JSObject {
+		let object = Object::new(cx);
+		object.set_as(cx, "timeOrigin", &PerformanceTimeline::from_context(cx).time_origin());
+		object.handle().get()
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	if !(PerformanceEntry::init_class(cx, global).0 && Performance::init_class(cx, global).0) {
+		return false;
+	}
+	let performance = Performance::new_object(cx, Box::new(Performance { reflector: Reflector::default() }));
+	global.define_as(cx, "performance", &performance, PropertyFlags::ENUMERATE)
+}
@@ -0,0 +1,88 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{env, process};
+use std::io;
+use std::io::Write;
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Error, ErrorKind, Object, Result};
+use ion::flags::PropertyFlags;
+use ion::function::{Opt, Wrap};
+
+use crate::ContextExt;
+
+/// Handler for `process.exit`, which receives the exit code, instead of exiting the process.
+pub type ExitHandler = dyn Fn(i32);
+
+/// Environment variables which are exposed to scripts through `process.env`.
+#[derive(Clone, Debug, Default)]
+pub enum EnvAccess {
+	#[default]
+	None,
+	/// Exposes the variables with the given names, if they are set.
+	Allow(Vec<String>),
+	All,
+}
+
+impl EnvAccess {
+	/// Variables which are not valid unicode are not exposed.
+	fn variables(&self) -> Vec<(String, String)> {
+		match self {
+			EnvAccess::None => Vec::new(),
+			EnvAccess::Allow(names) => names
+				.iter()
+				.filter_map(|name| env::var(name).ok().map(|value| (name.clone(), value)))
+				.collect(),
+			EnvAccess::All => env::vars_os()
+				.filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+				.collect(),
+		}
+	}
+}
+
+/// Configures the `process` global.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessOptions {
+	/// Arguments of the script, which are usually its path followed by the arguments passed to it.
+	pub argv: Vec<String>,
+	/// Environment variables which are copied into `process.env` when the runtime is built.
+	pub env: EnvAccess,
+}
+
+#[js_fn]
+fn cwd() -> Result<String> {
+	env::current_dir()
+		.map(|dir| dir.to_string_lossy().into_owned())
+		.map_err(|error| Error::new(format!("Failed to get current directory: {}", error), ErrorKind::Normal))
+}
+
+#[js_fn]
+fn exit(cx: &Context, Opt(code): Opt<Wrap<i32>>) {
+	let code = code.map(|code| code.0).unwrap_or(0);
+	match unsafe { cx.get_private() }.exit_handler.clone() {
+		Some(handler) => handler(code),
+		None => {
+			let _ = io::stdout().flush();
+			process::exit(code);
+		}
+	}
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(cwd, 0), function_spec!(exit, 0), JSFunctionSpec::ZERO];
+
+pub fn define(cx: &Context, global: &Object, options: &ProcessOptions) -> bool {
+	let process = Object::new(cx);
+	let env = Object::new(cx);
+
+	options.env.variables().iter().all(|(name, value)| env.set_as(cx, name.as_str(), value))
+		&& process.set_as(cx, "argv", &options.argv)
+		&& process.set_as(cx, "env", &env)
+		&& process.set_as(cx, "platform", env::consts::OS)
+		&& unsafe { process.define_methods(cx, FUNCTIONS) }
+		&& global.define_as(cx, "process", &process, PropertyFlags::CONSTANT_ENUMERATED)
+}
@@ -0,0 +1,169 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::io;
+use std::io::{BufRead, IsTerminal, Write};
+use std::panic::resume_unwind;
+use std::sync::{Mutex, PoisonError};
+
+use mozjs::jsapi::JSFunctionSpec;
+use tokio::task::spawn_blocking;
+
+use ion::{Context, Error, ErrorKind, Object, Promise, Result};
+use ion::function::Opt;
+
+use crate::promise::future_to_promise;
+
+/// Serialises questions, so that concurrent prompts do not interleave their output or input.
+static INPUT: Mutex<()> = Mutex::new(());
+
+/// Writes the question to the output and reads a line from the input, without the line terminator.
+/// Returns [None] if the input has been closed.
+fn read_line<R: BufRead, W: Write>(mut input: R, mut output: W, question: &str) -> io::Result<Option<String>> {
+	output.write_all(question.as_bytes())?;
+	output.flush()?;
+
+	let mut line = String::new();
+	if input.read_line(&mut line)? == 0 {
+		return Ok(None);
+	}
+	let length = line.trim_end_matches(['\r', '\n']).len();
+	line.truncate(length);
+	Ok(Some(line))
+}
+
+fn read_stdin_line(question: &str) -> io::Result<Option<String>> {
+	let _guard = INPUT.lock().unwrap_or_else(PoisonError::into_inner);
+	read_line(io::stdin().lock(), io::stdout(), question)
+}
+
+fn prompt_question(message: Option<String>, default: Option<&str>) -> String {
+	let mut question = message.unwrap_or_default();
+	if let Some(default) = default {
+		question.push_str(&format!(" [{}]", default));
+	}
+	question.push(' ');
+	question
+}
+
+/// Returns the answer to a prompt, or the default if the answer is empty.
+fn prompt_answer(answer: Option<String>, default: Option<String>) -> Option<String> {
+	answer.map(|answer| match default {
+		Some(default) if answer.is_empty() => default,
+		_ => answer,
+	})
+}
+
+fn confirm_question(message: Option<String>) -> String {
+	format!("{} [y/N] ", message.unwrap_or_default())
+}
+
+/// Checks if the answer to a confirmation is `y` or `yes`, ignoring case and surrounding whitespace.
+fn is_confirmed(answer: Option<String>) -> bool {
+	answer.is_some_and(|answer| {
+		let answer = answer.trim();
+		answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes")
+	})
+}
+
+/// Asks a question on a blocking thread, so that the event loop keeps running while waiting for input.
+async fn ask(question: String) -> Result<Option<String>> {
+	match spawn_blocking(move || read_stdin_line(&question)).await {
+		Ok(line) => {
+			line.map_err(|error| Error::new(format!("Failed to read from stdin: {}", error), ErrorKind::Normal))
+		}
+		Err(error) => resume_unwind(error.into_panic()),
+	}
+}
+
+#[js_fn]
+fn prompt(cx: &Context, message: Opt<String>, default: Opt<String>) -> Option<Promise> {
+	let question = prompt_question(message.0, default.0.as_deref());
+
+	unsafe {
+		future_to_promise(cx, move |_| async move {
+			let answer = ask(question).await?;
+			Ok::<_, Error>(prompt_answer(answer, default.0))
+		})
+	}
+}
+
+#[js_fn]
+fn confirm(cx: &Context, message: Opt<String>) -> Option<Promise> {
+	let question = confirm_question(message.0);
+
+	unsafe {
+		future_to_promise(cx, move |_| async move {
+			let answer = ask(question).await?;
+			Ok::<_, Error>(is_confirmed(answer))
+		})
+	}
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(prompt, 0),
+	function_spec!(confirm, 0),
+	JSFunctionSpec::ZERO,
+];
+
+/// Checks if `prompt` and `confirm` can be defined, as they require stdin and stdout to be attached to a terminal.
+pub fn is_interactive() -> bool {
+	io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	unsafe { global.define_methods(cx, FUNCTIONS) }
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Cursor;
+
+	use crate::globals::prompt::{confirm_question, is_confirmed, prompt_answer, prompt_question, read_line};
+
+	fn ask(input: &str, question: &str) -> (Option<String>, String) {
+		let mut output = Vec::new();
+		let answer = read_line(Cursor::new(input), &mut output, question).unwrap();
+		(answer, String::from_utf8(output).unwrap())
+	}
+
+	#[test]
+	fn prompt() {
+		let question = prompt_question(Some(String::from("Name?")), Some("anonymous"));
+		let (answer, output) = ask("Alice\r\nBob\n", &question);
+		assert_eq!("Name? [anonymous] ", output);
+		assert_eq!(
+			Some("Alice"),
+			prompt_answer(answer, Some(String::from("anonymous"))).as_deref()
+		);
+
+		let (answer, output) = ask("\n", &prompt_question(None, None));
+		assert_eq!(" ", output);
+		assert_eq!(Some(""), prompt_answer(answer.clone(), None).as_deref());
+		assert_eq!(
+			Some("default"),
+			prompt_answer(answer, Some(String::from("default"))).as_deref()
+		);
+
+		let (answer, _) = ask("", &question);
+		assert_eq!(None, prompt_answer(answer, Some(String::from("anonymous"))));
+	}
+
+	#[test]
+	fn confirm() {
+		let question = confirm_question(Some(String::from("Continue?")));
+		let (answer, output) = ask(" Yes \n", &question);
+		assert_eq!("Continue? [y/N] ", output);
+		assert!(is_confirmed(answer));
+
+		for input in ["y\n", "Y\r\n"] {
+			assert!(is_confirmed(ask(input, &question).0));
+		}
+		for input in ["\n", "n\n", "yep\n", ""] {
+			assert!(!is_confirmed(ask(input, &question).0));
+		}
+	}
+}
@@ -0,0 +1,100 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use ion::{Context, Function, Object, Value};
+use ion::flags::PropertyFlags;
+
+/// State of the xorshift128+ generator used by seeded `Math.random`.
+///
+/// The state can be copied to snapshot the generator, and restored to replay the same sequence of numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RandomState {
+	state: [u64; 2],
+}
+
+impl RandomState {
+	/// Creates the state of a generator from a seed, by expanding it with SplitMix64.
+	pub fn from_seed(seed: u64) -> RandomState {
+		let mut seed = seed;
+		let mut split_mix = || {
+			seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+			let mut z = seed;
+			z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+			z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+			z ^ (z >> 31)
+		};
+		let state = [split_mix(), split_mix()];
+		RandomState::from_state(state).unwrap_or(RandomState { state: [1, 0] })
+	}
+
+	/// Restores a generator from a state returned by [state](RandomState::state).
+	/// Returns [None] if the state is all zeros, as the generator would only produce zeros.
+	pub fn from_state(state: [u64; 2]) -> Option<RandomState> {
+		(state != [0, 0]).then_some(RandomState { state })
+	}
+
+	pub fn state(&self) -> [u64; 2] {
+		self.state
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		let [mut s1, s0] = self.state;
+		s1 ^= s1 << 23;
+		s1 ^= s1 >> 17;
+		s1 ^= s0 ^ (s0 >> 26);
+		self.state = [s0, s1];
+		s0.wrapping_add(s1)
+	}
+
+	/// Returns a number in the range `[0, 1)`, with 53 bits of randomness.
+	pub fn next_f64(&mut self) -> f64 {
+		const MANTISSA_BITS: u32 = f64::MANTISSA_DIGITS;
+		(self.next_u64() & ((1 << MANTISSA_BITS) - 1)) as f64 / (1_u64 << MANTISSA_BITS) as f64
+	}
+}
+
+/// Handle to the generator of a realm with a seeded `Math.random`.
+#[derive(Clone, Debug)]
+pub struct SeededRandom {
+	state: Rc<Cell<RandomState>>,
+}
+
+impl SeededRandom {
+	/// Returns a snapshot of the state of the generator.
+	pub fn state(&self) -> RandomState {
+		self.state.get()
+	}
+
+	/// Restores the generator to a snapshot, so that `Math.random` repeats the numbers produced after it was taken.
+	pub fn set_state(&self, state: RandomState) {
+		self.state.set(state);
+	}
+}
+
+/// Replaces `Math.random` in the realm of the global with a generator starting from the given state.
+/// Returns [None] if the global does not have a `Math` object.
+pub fn seed_math_random(cx: &Context, global: &Object, state: RandomState) -> Option<SeededRandom> {
+	let math: Object = global.get_as(cx, "Math", true, ()).ok().flatten()?;
+	let random = SeededRandom { state: Rc::new(Cell::new(state)) };
+
+	let generator = Rc::clone(&random.state);
+	let function = Function::from_closure(
+		cx,
+		"random",
+		Box::new(move |args| {
+			let mut state = generator.get();
+			let number = state.next_f64();
+			generator.set(state);
+			Ok(Value::f64(args.cx(), number))
+		}),
+		0,
+		PropertyFlags::empty(),
+	);
+	math.define_as(cx, "random", &function, PropertyFlags::empty()).then_some(random)
+}
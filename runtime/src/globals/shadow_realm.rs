@@ -0,0 +1,212 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use mozjs::jsapi::{JSFunction, JSObject};
+use mozjs::jsval::JSVal;
+
+use ion::{
+	Arguments, ClassDefinition, Context, Error, ErrorKind, Exception, Function, Object, Promise, Result, ResultExc,
+	TracedHeap, Value,
+};
+use ion::class::Reflector;
+use ion::conversions::ToValue;
+use ion::flags::PropertyFlags;
+use ion::realm::{RealmGuard, wrap_value};
+use ion::script::Script;
+
+use crate::globals::create_realm;
+
+const SHADOW_REALM_PATH: &str = "ShadowRealm";
+
+/// Creates an error of the current realm from an exception thrown in another realm, whose objects cannot cross the
+/// boundary. Only the message of the exception is kept.
+fn boundary_error(exception: Option<Exception>, kind: ErrorKind) -> Error {
+	let message = exception
+		.map(|exception| exception.to_error().message)
+		.filter(|message| !message.is_empty())
+		.unwrap_or(Cow::Borrowed("Error thrown in ShadowRealm"));
+	Error::new(message, kind)
+}
+
+/// Prepares a value from another realm for use in the current realm.
+/// Primitives are copied, and functions are wrapped with functions of the current realm, which call them in their own
+/// realm. Other objects cannot cross the boundary.
+fn get_wrapped_value<'cx>(cx: &'cx Context, value: &Value) -> Result<Value<'cx>> {
+	if value.handle().is_object() {
+		let object = value.to_object(cx);
+		return match Function::from_object(cx, &object) {
+			Some(function) => Ok(wrap_function(cx, &function)),
+			None => Err(Error::new(
+				"Values crossing a ShadowRealm boundary must be primitives or callable",
+				ErrorKind::Type,
+			)),
+		};
+	}
+
+	let mut value = Value::from(cx.root(value.get()));
+	if wrap_value(cx, &mut value) {
+		Ok(value)
+	} else {
+		Err(Error::new("Failed to wrap value for ShadowRealm", ErrorKind::Type))
+	}
+}
+
+fn wrap_function<'cx>(cx: &'cx Context, target: &Function) -> Value<'cx> {
+	let name = target.name(cx).unwrap_or_default();
+	let nargs = u32::from(target.nargs());
+	let target = TracedHeap::new(target.get());
+	let wrapped = Function::from_closure(
+		cx,
+		&name,
+		Box::new(move |args| call_wrapped(args, &target)),
+		nargs,
+		PropertyFlags::empty(),
+	);
+	Value::object(cx, &wrapped.to_object(cx))
+}
+
+fn call_wrapped<'cx>(args: &mut Arguments<'cx>, target: &TracedHeap<*mut JSFunction>) -> ResultExc<Value<'cx>> {
+	let cx = args.cx();
+	let target = Function::from(target.root(cx));
+	let arguments: Vec<_> = (0..args.len()).filter_map(|index| args.value(index)).collect();
+
+	let result = {
+		let _realm = RealmGuard::enter(cx, &target.to_object(cx));
+		let arguments = arguments
+			.iter()
+			.map(|argument| get_wrapped_value(cx, argument))
+			.collect::<Result<Vec<_>>>()?;
+		target.call(cx, &Object::null(cx), &arguments)
+	};
+
+	match result {
+		Ok(result) => Ok(get_wrapped_value(cx, &result)?),
+		Err(report) => Err(boundary_error(report.map(|report| report.exception), ErrorKind::Type).into()),
+	}
+}
+
+fn settle(cx: &Context, promise: &Promise, result: Result<Value>) {
+	match result {
+		Ok(value) => promise.resolve(cx, &value),
+		Err(error) => {
+			let mut value = Value::undefined(cx);
+			error.to_value(cx, &mut value);
+			promise.reject(cx, &value)
+		}
+	};
+}
+
+/// Realm with its own global and intrinsics, in which code is evaluated synchronously.
+/// Only primitives and functions cross the boundary of the realm, and functions are wrapped so that they are called in
+/// the realm they were created in.
+#[js_class]
+pub struct ShadowRealm {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	global: TracedHeap<*mut JSObject>,
+}
+
+impl ShadowRealm {
+	fn global<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		Object::from(self.global.root(cx))
+	}
+}
+
+#[js_class]
+impl ShadowRealm {
+	#[ion(constructor)]
+	pub fn constructor(cx: &Context) -> ShadowRealm {
+		let global = create_realm(cx);
+		ShadowRealm {
+			reflector: Reflector::default(),
+			global: TracedHeap::from_local(&global),
+		}
+	}
+
+	/// Evaluates a script in the realm, and returns its result, which must be a primitive or a function.
+	pub fn evaluate(&self, cx: &Context, source: String) -> Result<JSVal> {
+		let result = {
+			let _realm = RealmGuard::enter(cx, &self.global(cx));
+			let script = Script::compile(cx, Path::new(SHADOW_REALM_PATH), &source)
+				.map_err(|report| boundary_error(Some(report.exception), ErrorKind::Syntax))?;
+			script
+				.evaluate(cx)
+				.map_err(|report| boundary_error(Some(report.exception), ErrorKind::Type))?
+		};
+		get_wrapped_value(cx, &result).map(|value| value.get())
+	}
+
+	/// Imports a module in the realm, and resolves with one of its exports, which must be a primitive or a function.
+	#[ion(name = "importValue")]
+	pub fn import_value(&self, cx: &Context, specifier: String, export_name: String) -> Result<Promise> {
+		let promise = Promise::new(cx);
+		let caller = TracedHeap::from_local(&Object::global(cx));
+
+		let _realm = RealmGuard::enter(cx, &self.global(cx));
+		let import = Script::compile_and_evaluate(cx, Path::new(SHADOW_REALM_PATH), "(specifier) => import(specifier)")
+			.map_err(|report| boundary_error(Some(report.exception), ErrorKind::Type))?;
+		let import = Function::from_object(cx, &import.to_object(cx)).unwrap();
+		let namespace = import
+			.call(cx, &Object::null(cx), &[Value::string(cx, &specifier)])
+			.map_err(|report| boundary_error(report.map(|report| report.exception), ErrorKind::Type))?;
+		let namespace = Promise::from(namespace.to_object(cx).into_local()).unwrap();
+
+		let on_resolved = {
+			let promise = promise.clone();
+			let caller = caller.clone();
+			Function::from_closure_once(
+				cx,
+				"",
+				Box::new(move |args| {
+					let cx = args.cx();
+					let namespace = args.value(0).unwrap().to_object(cx);
+					let export = namespace.get(cx, export_name.as_str());
+
+					let _realm = RealmGuard::enter(cx, &Object::from(caller.root(cx)));
+					let result = match export {
+						Ok(Some(export)) => get_wrapped_value(cx, &export),
+						Ok(None) => Err(Error::new(
+							format!("Module does not export {}", export_name),
+							ErrorKind::Type,
+						)),
+						Err(error) => Err(Error::new(error.message, ErrorKind::Type)),
+					};
+					settle(cx, &promise, result);
+					Ok(Value::undefined(cx))
+				}),
+				1,
+				PropertyFlags::empty(),
+			)
+		};
+		let on_rejected = {
+			let promise = promise.clone();
+			Function::from_closure_once(
+				cx,
+				"",
+				Box::new(move |args| {
+					let cx = args.cx();
+					let exception = args.value(0).and_then(|error| Exception::from_value(cx, &error).ok());
+
+					let _realm = RealmGuard::enter(cx, &Object::from(caller.root(cx)));
+					settle(cx, &promise, Err(boundary_error(exception, ErrorKind::Type)));
+					Ok(Value::undefined(cx))
+				}),
+				1,
+				PropertyFlags::empty(),
+			)
+		};
+		namespace.add_reactions(cx, Some(on_resolved), Some(on_rejected));
+
+		Ok(promise)
+	}
+}
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	ShadowRealm::init_class(cx, global).0
+}
@@ -0,0 +1,100 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use mozjs::jsapi::{JSFunction, JSFunctionSpec, JSObject, PromiseState};
+
+use ion::{Context, Error, ErrorKind, Function, Object, Promise, Result, TracedHeap};
+use ion::flags::PropertyFlags;
+
+use crate::cache::map::format_error_report;
+use crate::ContextExt;
+
+/// Callbacks registered with `runtime.onShutdown`, and the `keepalive` fetches which delay the shutdown.
+#[derive(Default)]
+pub(crate) struct ShutdownState {
+	callbacks: Vec<TracedHeap<*mut JSFunction>>,
+	keepalive: Rc<Cell<usize>>,
+	started: bool,
+}
+
+/// Guard of a `keepalive` fetch, which delays the shutdown of the runtime until it is dropped.
+pub(crate) struct KeepaliveGuard(Rc<Cell<usize>>);
+
+impl KeepaliveGuard {
+	pub(crate) fn new(cx: &Context) -> KeepaliveGuard {
+		let keepalive = Rc::clone(&unsafe { cx.get_private() }.shutdown.keepalive);
+		keepalive.set(keepalive.get() + 1);
+		KeepaliveGuard(keepalive)
+	}
+}
+
+impl Drop for KeepaliveGuard {
+	fn drop(&mut self) {
+		self.0.set(self.0.get() - 1);
+	}
+}
+
+/// Calls the callbacks in the order they were registered, and returns the promises they returned.
+/// Exceptions thrown by callbacks are reported, and do not prevent the remaining callbacks from being called.
+pub(crate) fn run_callbacks(cx: &Context) -> Vec<TracedHeap<*mut JSObject>> {
+	let state = &mut unsafe { cx.get_private() }.shutdown;
+	state.started = true;
+	let callbacks = std::mem::take(&mut state.callbacks);
+
+	let global = Object::global(cx);
+	callbacks
+		.into_iter()
+		.filter_map(|callback| {
+			let callback = Function::from(callback.root(cx));
+			match callback.call(cx, &global, &[]) {
+				Ok(result) => Some(TracedHeap::from_local(&Promise::resolved(cx, result))),
+				Err(report) => {
+					if let Some(report) = report {
+						eprintln!(
+							"Uncaught exception in shutdown callback: {}",
+							format_error_report(cx, report)
+						);
+					}
+					None
+				}
+			}
+		})
+		.collect()
+}
+
+/// Returns `true` if the promises returned by the callbacks are settled, and no `keepalive` fetches are in flight.
+pub(crate) fn is_complete(cx: &Context, promises: &[TracedHeap<*mut JSObject>]) -> bool {
+	let pending = promises.iter().any(|promise| {
+		let promise = Promise::from(promise.root(cx)).unwrap();
+		promise.state(cx) == PromiseState::Pending
+	});
+	!pending && unsafe { cx.get_private() }.shutdown.keepalive.get() == 0
+}
+
+/// Registers a callback which is called when the runtime shuts down, such as to flush logs or close connections.
+/// Shutdown waits for the promise it returns, up to the deadline set by the embedder.
+#[js_fn]
+fn on_shutdown(cx: &Context, callback: Function) -> Result<()> {
+	let state = &mut unsafe { cx.get_private() }.shutdown;
+	if state.started {
+		return Err(Error::new("Runtime is already shutting down", ErrorKind::Normal));
+	}
+	state.callbacks.push(TracedHeap::new(callback.get()));
+	Ok(())
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(on_shutdown, "onShutdown", 1), JSFunctionSpec::ZERO];
+
+/// Defines the `runtime` global, whose `onShutdown(callback)` registers the callbacks run by
+/// [shutdown](crate::Runtime::shutdown).
+pub fn define(cx: &Context, global: &Object) -> bool {
+	let runtime = Object::new(cx);
+	let defined = unsafe { runtime.define_methods(cx, FUNCTIONS) };
+	defined && global.define_as(cx, "runtime", &runtime, PropertyFlags::CONSTANT_ENUMERATED)
+}
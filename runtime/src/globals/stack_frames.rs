@@ -0,0 +1,38 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Object, Stack};
+use ion::function::Opt;
+
+use crate::cache::map::transform_stack_with_sourcemaps;
+
+/// Returns the frames of the stack of an error as objects with `function`, `file`, `line` and `column` properties,
+/// or the frames of the current stack if no error is given. Locations in transpiled files, such as TypeScript, are
+/// mapped back to their sources.
+///
+/// Returns an empty array if the value is not an error.
+#[js_fn]
+fn stackFrames(cx: &Context, Opt(error): Opt<Object>) -> Stack {
+	let stack = match error {
+		Some(error) => Stack::from_error(cx, &error),
+		None => Stack::from_capture(cx),
+	};
+	let mut stack = stack.unwrap_or(Stack { records: Vec::new(), object: None });
+	transform_stack_with_sourcemaps(&mut stack);
+	stack
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(stackFrames, 0), JSFunctionSpec::ZERO];
+
+/// Defines `Error.stackFrames` on the `Error` constructor of the global.
+pub fn define(cx: &Context, global: &Object) -> bool {
+	match global.get_as::<_, Object>(cx, "Error", true, ()) {
+		Ok(Some(error)) => unsafe { error.define_methods(cx, FUNCTIONS) },
+		_ => false,
+	}
+}
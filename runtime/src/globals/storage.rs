@@ -0,0 +1,74 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::rc::Rc;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Object, Result};
+use ion::class::Reflector;
+
+use crate::kv::{kv_error, KvStore, MemoryKvStore};
+
+/// Synchronous string storage of the `localStorage` and `sessionStorage` globals.
+///
+/// Items are only accessible through the methods, and not as named properties of the object.
+#[js_class]
+pub struct Storage {
+	reflector: Reflector,
+	#[trace(no_trace)]
+	store: Rc<dyn KvStore>,
+}
+
+#[js_class]
+impl Storage {
+	#[ion(constructor)]
+	pub fn constructor() -> Result<Storage> {
+		Err(Error::new("Storage has no constructor.", ErrorKind::Type))
+	}
+
+	#[ion(get)]
+	pub fn get_length(&self) -> Result<u32> {
+		Ok(self.store.keys().map_err(kv_error)?.len() as u32)
+	}
+
+	pub fn key(&self, index: u32) -> Result<Option<String>> {
+		Ok(self.store.keys().map_err(kv_error)?.into_iter().nth(index as usize))
+	}
+
+	#[ion(name = "getItem")]
+	pub fn get_item(&self, key: String) -> Result<Option<String>> {
+		let value = self.store.get(&key).map_err(kv_error)?;
+		Ok(value.map(|value| String::from_utf8_lossy(&value).into_owned()))
+	}
+
+	#[ion(name = "setItem")]
+	pub fn set_item(&self, key: String, value: String) -> Result<()> {
+		self.store.put(&key, value.into_bytes()).map_err(kv_error)
+	}
+
+	#[ion(name = "removeItem")]
+	pub fn remove_item(&self, key: String) -> Result<()> {
+		self.store.delete(&key).map(|_| ()).map_err(kv_error)
+	}
+
+	pub fn clear(&self) -> Result<()> {
+		self.store.clear().map_err(kv_error)
+	}
+}
+
+/// Defines `localStorage`, which is backed by the [KvStore] of the runtime, and `sessionStorage`, which is only kept in
+/// memory for the lifetime of the runtime.
+pub fn define(cx: &Context, global: &Object, store: Rc<dyn KvStore>) -> bool {
+	if !Storage::init_class(cx, global).0 {
+		return false;
+	}
+	let local = Storage::new_object(cx, Box::new(Storage { reflector: Reflector::default(), store }));
+	let session = Storage {
+		reflector: Reflector::default(),
+		store: Rc::new(MemoryKvStore::default()),
+	};
+	let session = Storage::new_object(cx, Box::new(session));
+	global.set_as(cx, "localStorage", &local) && global.set_as(cx, "sessionStorage", &session)
+}
@@ -0,0 +1,122 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::io;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use futures::stream;
+use futures::stream::LocalBoxStream;
+use mozjs::c_str;
+use mozjs::jsapi::CheckReadableStreamControllerCanCloseOrEnqueue;
+
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Function, Object, Promise, ReadableStream, ResultExc,
+	TracedHeap, Value,
+};
+use ion::class::NativeObject;
+use ion::conversions::ToValue;
+use ion::typedarray::Uint8Array;
+
+use crate::globals::streams::{NativeStreamSource, NativeStreamSourceCallbacks, readable_stream_from_callbacks};
+use crate::handle;
+use crate::promise::future_to_promise;
+
+/// Creates a [ReadableStream] which polls a stream of bytes as it is pulled, enqueueing each chunk as a
+/// [Uint8Array]. The stream is dropped when the [ReadableStream] is cancelled.
+///
+/// Chunks larger than `chunk_hint` bytes are split, without copying, into chunks of at most `chunk_hint` bytes.
+/// The stream is only polled when the queue of the [ReadableStream] is empty, so at most one chunk is buffered ahead
+/// of the reader.
+pub fn readable_stream_from_byte_stream<S>(cx: &Context, stream: S, chunk_hint: usize) -> Option<ReadableStream>
+where
+	S: Stream<Item = io::Result<Bytes>> + 'static,
+{
+	let stream = rechunk(stream, chunk_hint.max(1));
+	let source = ByteStreamSource { stream: stream.boxed_local() };
+	readable_stream_from_callbacks(cx, Box::new(source))
+}
+
+/// Splits the chunks of a stream of bytes into chunks of at most `chunk_size` bytes.
+fn rechunk<S>(stream: S, chunk_size: usize) -> impl Stream<Item = io::Result<Bytes>>
+where
+	S: Stream<Item = io::Result<Bytes>> + 'static,
+{
+	stream::unfold(
+		(stream.boxed_local(), Bytes::new()),
+		move |(mut stream, mut remaining)| async move {
+			while remaining.is_empty() {
+				match stream.next().await? {
+					Ok(chunk) => remaining = chunk,
+					Err(error) => return Some((Err(error), (stream, remaining))),
+				}
+			}
+			let chunk = remaining.split_to(chunk_size.min(remaining.len()));
+			Some((Ok(chunk), (stream, remaining)))
+		},
+	)
+}
+
+struct ByteStreamSource {
+	stream: LocalBoxStream<'static, io::Result<Bytes>>,
+}
+
+impl NativeStreamSourceCallbacks for ByteStreamSource {
+	fn start<'cx>(&self, _: &'cx NativeStreamSource, cx: &'cx Context, _: Object<'cx>) -> ResultExc<Value<'cx>> {
+		Ok(Value::undefined(cx))
+	}
+
+	fn pull<'cx>(
+		&self, source: &'cx NativeStreamSource, cx: &'cx Context, controller: Object<'cx>,
+	) -> ResultExc<Promise> {
+		unsafe {
+			if !CheckReadableStreamControllerCanCloseOrEnqueue(
+				cx.as_ptr(),
+				controller.handle().into(),
+				c_str!("enqueue"),
+			) {
+				return Err(Exception::Error(Error::new(
+					"Readable stream is already closed",
+					ErrorKind::Type,
+				)));
+			}
+
+			let stream_source = TracedHeap::new(source.reflector().get());
+			let controller = TracedHeap::from_local(&controller);
+
+			Ok(future_to_promise(cx, move |cx| async move {
+				let (cx, chunk) = cx
+					.await_native_cx(|cx| {
+						NativeStreamSource::get_mut_private(&cx, &stream_source.to_local().into())
+							.unwrap()
+							.get_typed_source_mut::<Self>()
+							.stream
+							.next()
+					})
+					.await;
+
+				let controller = Object::from(controller.root(&cx));
+				let (name, args) = match chunk {
+					None => ("close", Vec::new()),
+					Some(chunk) => {
+						let chunk = chunk.map_err(Error::from)?;
+						let array = Uint8Array::from_bytes(&cx, chunk)
+							.ok_or_else(|| Error::new("Failed to allocate array", ErrorKind::Normal))?;
+						("enqueue", vec![array.as_value(&cx)])
+					}
+				};
+				let function = Function::from_object(&cx, &controller.get(&cx, name)?.unwrap().to_object(&cx)).unwrap();
+				function.call(&cx, &controller, &args).map_err(|e| handle::call_exception(&cx, e))?;
+				ResultExc::<_>::Ok(())
+			})
+			.expect("Future queue should be running"))
+		}
+	}
+
+	fn cancel(self: Box<Self>, cx: &Context, _: Value) -> ResultExc<Promise> {
+		Ok(Promise::resolved(cx, Value::undefined(cx)))
+	}
+}
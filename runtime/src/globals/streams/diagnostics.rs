@@ -0,0 +1,33 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Error, ErrorKind, Object, ReadableStream, ReadableStreamDiagnostics, Result};
+
+use crate::globals::streams::instrumentation::{stream_counters, StreamCounters};
+
+/// Reports the state, queue size and desired size of a readable stream, along with its controller and reader.
+#[js_fn]
+fn stream_state(cx: &Context, stream: ReadableStream) -> Result<ReadableStreamDiagnostics> {
+	stream.diagnostics(cx)
+}
+
+/// Reports the number of chunks and bytes which have passed through a `CountingStream` or `ThrottleStream`.
+#[js_fn]
+fn stream_counters_of<'cx>(cx: &Context, stream: Object<'cx>) -> Result<&'cx StreamCounters> {
+	stream_counters(cx, &stream).ok_or_else(|| Error::new("Expected CountingStream or ThrottleStream", ErrorKind::Type))
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[
+	function_spec!(stream_state, "__streamState", 1),
+	function_spec!(stream_counters_of, "__streamCounters", 1),
+	JSFunctionSpec::ZERO,
+];
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	unsafe { global.define_methods(cx, FUNCTIONS) }
+}
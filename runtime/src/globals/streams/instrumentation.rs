@@ -0,0 +1,254 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use mozjs::jsapi::{JS_GetArrayBufferViewByteLength, JS_IsArrayBufferViewObject, JSObject};
+use tokio::time::sleep_until;
+
+use ion::{ClassDefinition, Context, Error, ErrorKind, Heap, Object, Promise, Result, ResultExc, TracedHeap, Value};
+use ion::class::{NativeObject, Reflector};
+use ion::conversions::{FromValue, ToValue};
+use ion::typedarray::ArrayBuffer;
+
+use crate::promise::future_to_promise;
+
+use super::{transform_stream_from_callbacks, TransformerCallbacks, TransformStream, TransformStreamDefaultController};
+
+/// Number of chunks and bytes which have passed through a stream.
+#[derive(Debug, Default)]
+pub struct StreamCounters {
+	chunks: Cell<u64>,
+	bytes: Cell<u64>,
+}
+
+impl StreamCounters {
+	/// Records a chunk with the given length in bytes.
+	pub fn record(&self, bytes: usize) {
+		self.chunks.set(self.chunks.get() + 1);
+		self.bytes.set(self.bytes.get() + bytes as u64);
+	}
+
+	pub fn chunks(&self) -> u64 {
+		self.chunks.get()
+	}
+
+	pub fn bytes(&self) -> u64 {
+		self.bytes.get()
+	}
+}
+
+impl<'cx> ToValue<'cx> for StreamCounters {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "chunks", &self.chunks());
+		object.set_as(cx, "bytes", &self.bytes());
+		object.to_value(cx, value);
+	}
+}
+
+/// Returns the length in bytes of a chunk, which is its byte length for buffers and views, and its length when encoded
+/// as UTF-8 for strings. Other chunks are counted as empty.
+fn chunk_byte_length(cx: &Context, chunk: &Value) -> usize {
+	let handle = chunk.handle();
+	if handle.is_string() {
+		String::from_value(cx, chunk, true, ()).map(|string| string.len()).unwrap_or_default()
+	} else if handle.is_object() {
+		let object = handle.to_object();
+		if unsafe { JS_IsArrayBufferViewObject(object) } {
+			unsafe { JS_GetArrayBufferViewByteLength(object) }
+		} else {
+			ArrayBuffer::from(cx.root(object)).map(|buffer| buffer.len()).unwrap_or_default()
+		}
+	} else {
+		0
+	}
+}
+
+fn new_transform_stream<'cx>(cx: &'cx Context, callbacks: impl TransformerCallbacks + 'static) -> Result<Object<'cx>> {
+	transform_stream_from_callbacks(cx, callbacks).map_err(|e| e.to_error())
+}
+
+struct Counting {
+	counters: Rc<StreamCounters>,
+}
+
+impl TransformerCallbacks for Counting {
+	fn transform(
+		&self, cx: &Context, chunk: Value, controller: &TransformStreamDefaultController,
+	) -> ResultExc<Option<Promise>> {
+		self.counters.record(chunk_byte_length(cx, &chunk));
+		controller.enqueue(cx, chunk)?;
+		Ok(None)
+	}
+}
+
+/// Transform stream which passes chunks through unchanged, while counting the chunks and bytes which pass through it.
+#[js_class]
+pub struct CountingStream {
+	reflector: Reflector,
+	transform_stream: Heap<*mut JSObject>,
+	#[trace(no_trace)]
+	counters: Rc<StreamCounters>,
+}
+
+impl CountingStream {
+	pub fn counters(&self) -> &StreamCounters {
+		&self.counters
+	}
+}
+
+#[js_class]
+impl CountingStream {
+	#[ion(constructor)]
+	pub fn constructor(cx: &Context) -> Result<CountingStream> {
+		let counters = Rc::new(StreamCounters::default());
+		let transform_stream = new_transform_stream(cx, Counting { counters: Rc::clone(&counters) })?;
+		Ok(CountingStream {
+			reflector: Reflector::default(),
+			transform_stream: Heap::from_local(&transform_stream),
+			counters,
+		})
+	}
+
+	#[ion(get)]
+	pub fn get_readable(&self, cx: &Context) -> *mut JSObject {
+		TransformStream::from_heap(cx, &self.transform_stream).get_readable()
+	}
+
+	#[ion(get)]
+	pub fn get_writable(&self, cx: &Context) -> *mut JSObject {
+		TransformStream::from_heap(cx, &self.transform_stream).get_writable()
+	}
+
+	#[ion(get)]
+	pub fn get_chunks(&self) -> u64 {
+		self.counters.chunks()
+	}
+
+	#[ion(get)]
+	pub fn get_bytes(&self) -> u64 {
+		self.counters.bytes()
+	}
+}
+
+struct Throttle {
+	counters: Rc<StreamCounters>,
+	bytes_per_second: f64,
+	next: Rc<Cell<Option<Instant>>>,
+}
+
+impl TransformerCallbacks for Throttle {
+	fn transform(
+		&self, cx: &Context, chunk: Value, controller: &TransformStreamDefaultController,
+	) -> ResultExc<Option<Promise>> {
+		let length = chunk_byte_length(cx, &chunk);
+		self.counters.record(length);
+
+		// Each chunk is delayed until the chunks before it have been sent at the configured rate.
+		let now = Instant::now();
+		let ready = self.next.get().filter(|next| *next > now).unwrap_or(now);
+		self.next.set(Some(
+			ready + Duration::from_secs_f64(length as f64 / self.bytes_per_second),
+		));
+
+		if ready <= now {
+			controller.enqueue(cx, chunk)?;
+			return Ok(None);
+		}
+
+		let chunk = TracedHeap::from_local(&chunk);
+		let controller = TracedHeap::new(controller.reflector().get());
+		let promise = unsafe {
+			future_to_promise(cx, move |cx| async move {
+				let (cx, _) = cx.await_native(sleep_until(ready.into())).await;
+				let controller = Object::from(controller.root(&cx));
+				let controller = TransformStreamDefaultController::get_private(&cx, &controller)?;
+				controller.enqueue(&cx, Value::from(chunk.root(&cx)))
+			})
+		};
+		Ok(promise)
+	}
+}
+
+/// Transform stream which limits the rate at which bytes pass through it, by delaying chunks once they would exceed
+/// `bytesPerSecond`. Chunks are not split, so a single large chunk is passed through before the delay it incurs.
+#[js_class]
+pub struct ThrottleStream {
+	reflector: Reflector,
+	transform_stream: Heap<*mut JSObject>,
+	#[trace(no_trace)]
+	counters: Rc<StreamCounters>,
+	bytes_per_second: f64,
+}
+
+impl ThrottleStream {
+	pub fn counters(&self) -> &StreamCounters {
+		&self.counters
+	}
+}
+
+#[js_class]
+impl ThrottleStream {
+	#[ion(constructor)]
+	pub fn constructor(cx: &Context, bytes_per_second: f64) -> Result<ThrottleStream> {
+		if bytes_per_second.is_nan() || bytes_per_second <= 0.0 {
+			return Err(Error::new("bytesPerSecond must be a positive number", ErrorKind::Range));
+		}
+
+		let counters = Rc::new(StreamCounters::default());
+		let throttle = Throttle {
+			counters: Rc::clone(&counters),
+			bytes_per_second,
+			next: Rc::default(),
+		};
+		let transform_stream = new_transform_stream(cx, throttle)?;
+		Ok(ThrottleStream {
+			reflector: Reflector::default(),
+			transform_stream: Heap::from_local(&transform_stream),
+			counters,
+			bytes_per_second,
+		})
+	}
+
+	#[ion(get)]
+	pub fn get_readable(&self, cx: &Context) -> *mut JSObject {
+		TransformStream::from_heap(cx, &self.transform_stream).get_readable()
+	}
+
+	#[ion(get)]
+	pub fn get_writable(&self, cx: &Context) -> *mut JSObject {
+		TransformStream::from_heap(cx, &self.transform_stream).get_writable()
+	}
+
+	#[ion(get)]
+	pub fn get_chunks(&self) -> u64 {
+		self.counters.chunks()
+	}
+
+	#[ion(get)]
+	pub fn get_bytes(&self) -> u64 {
+		self.counters.bytes()
+	}
+
+	#[ion(get)]
+	pub fn get_bytes_per_second(&self) -> f64 {
+		self.bytes_per_second
+	}
+}
+
+/// Returns the counters of a [CountingStream] or [ThrottleStream].
+pub fn stream_counters<'s>(cx: &Context, stream: &Object<'s>) -> Option<&'s StreamCounters> {
+	if CountingStream::instance_of(cx, stream) {
+		CountingStream::get_private(cx, stream).ok().map(CountingStream::counters)
+	} else if ThrottleStream::instance_of(cx, stream) {
+		ThrottleStream::get_private(cx, stream).ok().map(ThrottleStream::counters)
+	} else {
+		None
+	}
+}
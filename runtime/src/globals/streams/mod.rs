@@ -1,18 +1,28 @@
 use ion::{Context, Object, ClassDefinition};
 
+mod byte_stream_source;
+mod diagnostics;
+mod instrumentation;
 mod native_stream_sink;
 mod native_stream_source;
+mod pipeline;
 mod readable_stream_extensions;
 mod text_decoder_stream;
 mod text_encoder_stream;
 mod transform_stream;
 
-pub use native_stream_sink::{NativeStreamSink, NativeStreamSinkCallbacks};
+pub use byte_stream_source::readable_stream_from_byte_stream;
+pub(crate) use diagnostics::define as define_diagnostics;
+pub use instrumentation::{stream_counters, CountingStream, StreamCounters, ThrottleStream};
+pub use native_stream_sink::{writable_stream_from_callbacks, NativeStreamSink, NativeStreamSinkCallbacks};
 pub use native_stream_source::{NativeStreamSource, NativeStreamSourceCallbacks};
+pub use pipeline::{pipeline, Pipeline};
 pub use readable_stream_extensions::readable_stream_from_callbacks;
 pub use text_decoder_stream::TextDecoderStream;
 pub use text_encoder_stream::TextEncoderStream;
-pub use transform_stream::{TransformStream, TransformStreamDefaultController};
+pub use transform_stream::{
+	transform_stream_from_callbacks, TransformerCallbacks, TransformStream, TransformStreamDefaultController,
+};
 
 pub fn define(cx: &Context, global: &Object) -> bool {
 	readable_stream_extensions::define(cx, global)
@@ -24,4 +34,6 @@ pub fn define(cx: &Context, global: &Object) -> bool {
 		&& text_encoder_stream::TextEncoderStreamTransformer::init_class(cx, global).0
 		&& text_decoder_stream::TextDecoderStream::init_class(cx, global).0
 		&& text_decoder_stream::TextDecoderStreamTransformer::init_class(cx, global).0
+		&& instrumentation::CountingStream::init_class(cx, global).0
+		&& instrumentation::ThrottleStream::init_class(cx, global).0
 }
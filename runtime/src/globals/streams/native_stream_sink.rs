@@ -1,4 +1,8 @@
-use ion::{Object, Value, Promise, class::Reflector, Context, ResultExc};
+use ion::{ClassDefinition, Object, Value, Promise, class::Reflector, Context, ResultExc};
+use ion::object::WritableStream;
+use mozjs::jsapi::{HandleFunction, HandleObject, NewWritableDefaultStreamObject};
+
+use super::readable_stream_extensions::NULL_FUNCTION;
 
 #[js_class]
 pub struct NativeStreamSink {
@@ -44,3 +48,28 @@ impl NativeStreamSink {
 		self.callbacks.abort(cx, reason)
 	}
 }
+
+pub fn writable_stream_from_callbacks(
+	cx: &Context, callbacks: Box<dyn NativeStreamSinkCallbacks>,
+) -> Option<WritableStream> {
+	let sink_obj = cx.root(NativeStreamSink::new_object(
+		cx,
+		Box::new(NativeStreamSink::new(callbacks)),
+	));
+
+	let stream_obj = unsafe {
+		NewWritableDefaultStreamObject(
+			cx.as_ptr(),
+			sink_obj.handle().into(),
+			HandleFunction::from_marked_location(&NULL_FUNCTION),
+			1.0,
+			HandleObject::null(),
+		)
+	};
+
+	if stream_obj.is_null() {
+		None
+	} else {
+		WritableStream::new(stream_obj)
+	}
+}
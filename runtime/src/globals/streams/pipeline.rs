@@ -0,0 +1,186 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use mozjs::jsapi::JSObject;
+use mozjs::jsval::JSVal;
+
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Object, Promise, ReadableStream, Result, TracedHeap, Value,
+};
+use ion::conversions::ToValue;
+use ion::flags::PropertyFlags;
+use ion::function::Opt;
+use ion::future::PromiseFuture;
+use ion::object::WritableStream;
+
+use crate::globals::abort::AbortController;
+use crate::globals::streams::readable_stream_extensions::pipe_to;
+use crate::promise::future_to_promise;
+
+/// Streams piped together by [pipeline].
+pub struct Pipeline {
+	/// Settles once every stage has finished. Rejects with the error of the stage which failed, or an `AggregateError`
+	/// of the distinct errors if several stages failed.
+	pub promise: Promise,
+	/// `AbortController` whose signal aborts every stage of the pipeline.
+	pub controller: TracedHeap<*mut JSObject>,
+}
+
+impl<'cx> ToValue<'cx> for Pipeline {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "promise", &self.promise);
+		object.set_as(cx, "controller", &self.controller.get());
+		object.to_value(cx, value);
+	}
+}
+
+/// Pipes `source` through each of the `transforms`, which are objects with `readable` and `writable` streams such as
+/// `TransformStream`, and into `destination`.
+///
+/// Every stage is piped with the signal of a shared `AbortController`, which is aborted with the first error of any
+/// stage, so that all other stages are cancelled or aborted instead of being left locked.
+pub fn pipeline(cx: &Context, source: &Object, transforms: &[Object], destination: &Object) -> Result<Pipeline> {
+	if !ReadableStream::is_readable_stream(source.handle().get()) {
+		return Err(Error::new(
+			"Source of pipeline must be a ReadableStream",
+			ErrorKind::Type,
+		));
+	}
+	if ReadableStream::static_is_locked(cx, source) {
+		return Err(Error::new("Source of pipeline is already locked", ErrorKind::Type));
+	}
+	if !WritableStream::is_writable_stream(destination) {
+		return Err(Error::new(
+			"Destination of pipeline must be a WritableStream",
+			ErrorKind::Type,
+		));
+	}
+	if WritableStream::static_is_locked(cx, destination) {
+		return Err(Error::new("Destination of pipeline is already locked", ErrorKind::Type));
+	}
+
+	let mut readables = vec![source.handle().get()];
+	let mut writables = Vec::with_capacity(transforms.len() + 1);
+	for (index, transform) in transforms.iter().enumerate() {
+		let writable = transform_end(cx, transform, "writable", index)?;
+		if !WritableStream::is_writable_stream(&writable) {
+			return Err(transform_error(index));
+		}
+		let readable = transform_end(cx, transform, "readable", index)?;
+		if !ReadableStream::is_readable_stream(readable.handle().get()) {
+			return Err(transform_error(index));
+		}
+		writables.push(writable.handle().get());
+		readables.push(readable.handle().get());
+	}
+	writables.push(destination.handle().get());
+
+	let controller = AbortController::constructor(cx);
+	let options = Object::new(cx);
+	options.set_as(cx, "signal", &controller.get_signal());
+	let controller = Object::from(cx.root(AbortController::new_object(cx, Box::new(controller))));
+
+	let mut stages = Vec::with_capacity(readables.len());
+	for (readable, writable) in readables.into_iter().zip(writables) {
+		let readable = Object::from(cx.root(readable));
+		let writable = Object::from(cx.root(writable));
+		match pipe_to(cx, &readable, &writable, options.as_value(cx)) {
+			Ok(stage) => stages.push(stage),
+			Err(error) => {
+				abort(cx, &controller, error.as_value(cx));
+				return Err(error);
+			}
+		}
+	}
+
+	let settled: FuturesUnordered<_> = stages.iter().map(|stage| PromiseFuture::new(cx.duplicate(), stage)).collect();
+	let controller_heap = TracedHeap::from_local(&controller);
+	let promise = unsafe { future_to_promise(cx, move |cx| settle(cx, settled, controller_heap)) };
+
+	match promise {
+		Some(promise) => Ok(Pipeline {
+			promise,
+			controller: TracedHeap::from_local(&controller),
+		}),
+		None => Err(Error::new("Pipeline requires a future queue", ErrorKind::Normal)),
+	}
+}
+
+fn transform_end<'cx>(cx: &'cx Context, transform: &Object, key: &str, index: usize) -> Result<Object<'cx>> {
+	match transform.get(cx, key)? {
+		Some(end) if end.get().is_object() => Ok(end.to_object(cx)),
+		_ => Err(transform_error(index)),
+	}
+}
+
+fn transform_error(index: usize) -> Error {
+	Error::new(
+		format!(
+			"Transform {} of pipeline must be an object with a readable and a writable stream",
+			index
+		),
+		ErrorKind::Type,
+	)
+}
+
+fn abort(cx: &Context, controller: &Object, reason: Value) {
+	if let Ok(controller) = AbortController::get_private(cx, controller) {
+		if let Err(exception) = controller.abort(cx, Opt(Some(reason))) {
+			eprintln!("Uncaught exception in abort event: {}", exception.format(cx));
+		}
+	}
+}
+
+/// Waits for every stage to settle, aborting the pipeline once any of them fails.
+async fn settle(
+	cx: Context, mut settled: FuturesUnordered<PromiseFuture>, controller: TracedHeap<*mut JSObject>,
+) -> std::result::Result<(), Exception> {
+	drop(cx);
+
+	let mut errors: Vec<TracedHeap<JSVal>> = Vec::new();
+	let mut result = Ok(());
+	while let Some((cx, stage)) = settled.next().await {
+		if let Err(error) = stage {
+			let error = Value::from(cx.root(error.get()));
+			if errors.is_empty() {
+				abort(
+					&cx,
+					&Object::from(controller.root(&cx)),
+					Value::from(cx.root(error.get())),
+				);
+			}
+			if !errors.iter().any(|other| Value::from(cx.root(other.get())).is_same(&cx, &error)) {
+				errors.push(TracedHeap::from_local(&error));
+			}
+		}
+
+		if settled.is_empty() {
+			result = match errors.len() {
+				0 => Ok(()),
+				1 => Err(Exception::Other(errors[0].get())),
+				_ => Err(Exception::Other(aggregate_error(&cx, &errors))),
+			};
+		}
+	}
+	result
+}
+
+fn aggregate_error(cx: &Context, errors: &[TracedHeap<JSVal>]) -> JSVal {
+	let error = Error::new("Multiple stages of pipeline failed", ErrorKind::Aggregate);
+	let Some(object) = error.to_object(cx) else {
+		return errors[0].get();
+	};
+	let errors: Vec<_> = errors.iter().map(|error| Value::from(cx.root(error.get()))).collect();
+	object.define_as(
+		cx,
+		"errors",
+		&errors,
+		PropertyFlags::CONFIGURABLE | PropertyFlags::WRITABLE,
+	);
+	object.as_value(cx).get()
+}
@@ -6,6 +6,8 @@ use super::{NativeStreamSource, NativeStreamSourceCallbacks};
 mod pipe_through;
 mod tee;
 
+pub(crate) use pipe_through::pipe_to;
+
 pub const NULL_FUNCTION: *mut JSFunction = 0 as *mut JSFunction;
 
 pub fn readable_stream_from_callbacks(
@@ -60,6 +60,17 @@ fn pipe_through<'cx>(
 
 	let writable_end = writable_end.to_object(cx);
 
+	let promise = pipe_to(cx, this, &writable_end, options.unwrap_or_else(|| Value::undefined(cx)))?;
+
+	// Apparently, this sets the PromiseIsHandled slot.
+	promise.add_reactions_ignoring_unhandled_rejection(cx, None, None);
+
+	Ok(readable_end)
+}
+
+/// Pipes a readable stream to a writable stream with the original `ReadableStream.prototype.pipeTo`, so that it is not
+/// affected by scripts replacing it.
+pub(crate) fn pipe_to(cx: &Context, readable: &Object, writable: &Object, options: Value) -> Result<Promise> {
 	let pipe_to_fn = Function::from(STREAM_PIPE_TO.with(|l| {
 		l.borrow()
 			.as_ref()
@@ -67,23 +78,11 @@ fn pipe_through<'cx>(
 			.root(cx)
 	}));
 
-	let Ok(rval) = pipe_to_fn.call(
-		cx,
-		this,
-		&[
-			writable_end.as_value(cx),
-			options.unwrap_or_else(|| Value::undefined(cx)),
-		],
-	) else {
+	let Ok(rval) = pipe_to_fn.call(cx, readable, &[writable.as_value(cx), options]) else {
 		return Err(Error::none());
 	};
 
-	let promise = Promise::from(rval.to_object(cx).into_local()).expect("Return value of pipeTo should be a promise");
-
-	// Apparently, this sets the PromiseIsHandled slot.
-	promise.add_reactions_ignoring_unhandled_rejection(cx, None, None);
-
-	Ok(readable_end)
+	Ok(Promise::from(rval.to_object(cx).into_local()).expect("Return value of pipeTo should be a promise"))
 }
 
 pub(super) fn define(cx: &Context, global: &Object) -> bool {
@@ -15,6 +15,7 @@ use mozjs::{
 
 use crate::{
 	globals::streams::{NativeStreamSource, NativeStreamSourceCallbacks},
+	handle,
 	promise::future_to_promise,
 };
 
@@ -172,11 +173,11 @@ impl NativeStreamSourceCallbacks for TeedReadableStreamSource {
 						let enqueue_func =
 							Function::from_object(cx, &controller.get(cx, "enqueue")?.unwrap().to_object(cx)).unwrap();
 						enqueue_func
-							.call(cx, &controller, &[bytes_clone.as_value(cx)])
-							.map_err(|e| e.unwrap().exception)?;
+							.call_with(cx, &controller, [bytes_clone])
+							.map_err(|e| handle::call_exception(cx, e))?;
 						let close_func =
 							Function::from_object(cx, &controller.get(cx, "close")?.unwrap().to_object(cx)).unwrap();
-						close_func.call(cx, &controller, &[]).map_err(|e| e.unwrap().exception)?;
+						close_func.call(cx, &controller, &[]).map_err(|e| handle::call_exception(cx, e))?;
 						Ok(Value::undefined(cx))
 					}),
 					1,
@@ -191,7 +192,7 @@ impl NativeStreamSourceCallbacks for TeedReadableStreamSource {
 						let controller = Object::from(controller_heap2.root(cx));
 						let cancel_func =
 							Function::from_object(cx, &controller.get(cx, "cancel")?.unwrap().to_object(cx)).unwrap();
-						cancel_func.call(cx, &controller, &[reason]).map_err(|e| e.unwrap().exception)?;
+						cancel_func.call(cx, &controller, &[reason]).map_err(|e| handle::call_exception(cx, e))?;
 						Ok(Value::undefined(cx))
 					}),
 					1,
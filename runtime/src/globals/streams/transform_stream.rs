@@ -3,8 +3,8 @@ use ion::{
 	conversions::{FromValue, ToValue},
 	flags::PropertyFlags,
 	function::Opt,
-	js_class, ClassDefinition, Context, Error, ErrorKind, Exception, Function, Heap, Object, Promise, PromiseFuture,
-	Result, ResultExc, TracedHeap, Value,
+	js_class, ClassDefinition, Context, Error, ErrorKind, Exception, Function, Heap, Object, Promise, Result,
+	ResultExc, TracedHeap, TypedPromise, Value,
 };
 use mozjs::{
 	jsapi::{
@@ -23,7 +23,22 @@ use crate::{globals::streams::native_stream_sink::NativeStreamSink, promise::fut
 
 use super::{native_stream_sink::NativeStreamSinkCallbacks, NativeStreamSourceCallbacks, NativeStreamSource};
 
-// TODO: back-pressure
+/// Creates a [TransformStream] whose algorithms are implemented in Rust, without a JavaScript transformer object.
+pub fn transform_stream_from_callbacks<'cx>(
+	cx: &'cx Context, callbacks: impl TransformerCallbacks + 'static,
+) -> ResultExc<Object<'cx>> {
+	let mut this = Object::from(cx.root(TransformStream::new_raw_object(cx)));
+	let transformer = HeapTransformer::Native {
+		callbacks: Box::new(callbacks),
+		cleared: false,
+	};
+	let stream = TransformStream::initialise(cx, &this, transformer)?;
+	unsafe {
+		TransformStream::set_private(this.handle().get(), Box::new(stream));
+	}
+	TransformStream::call_start(cx, &mut this)?;
+	Ok(this)
+}
 
 #[derive(FromValue)]
 pub struct Transformer<'cx> {
@@ -33,6 +48,27 @@ pub struct Transformer<'cx> {
 	cancel: Option<Function<'cx>>,
 }
 
+/// Algorithms of a [TransformStream] implemented in Rust, used with [transform_stream_from_callbacks].
+///
+/// Each algorithm may return a promise if it completes asynchronously.
+pub trait TransformerCallbacks {
+	fn start(&self, _cx: &Context, _controller: &TransformStreamDefaultController) -> ResultExc<Option<Promise>> {
+		Ok(None)
+	}
+
+	fn transform(
+		&self, cx: &Context, chunk: Value, controller: &TransformStreamDefaultController,
+	) -> ResultExc<Option<Promise>>;
+
+	fn flush(&self, _cx: &Context, _controller: &TransformStreamDefaultController) -> ResultExc<Option<Promise>> {
+		Ok(None)
+	}
+
+	fn cancel(&self, _cx: &Context, _reason: Value) -> ResultExc<Option<Promise>> {
+		Ok(None)
+	}
+}
+
 // Needed to store the transformer instance and callbacks for use in the controller.
 #[derive(Traceable)]
 pub enum HeapTransformer {
@@ -44,6 +80,12 @@ pub enum HeapTransformer {
 		flush: Option<Heap<*mut JSFunction>>,
 		cancel: Option<Heap<*mut JSFunction>>,
 	},
+	Native {
+		#[trace(no_trace)]
+		callbacks: Box<dyn TransformerCallbacks>,
+		// The callbacks may be running when the algorithms are cleared, so they cannot be dropped.
+		cleared: bool,
+	},
 }
 
 impl HeapTransformer {
@@ -64,43 +106,83 @@ impl HeapTransformer {
 		}
 	}
 
-	fn start_function<'cx>(&self, cx: &'cx Context) -> Option<(Object<'cx>, Function<'cx>)> {
+	// Each algorithm returns None if the transformer does not implement it.
+	fn start<'cx>(&self, cx: &'cx Context, controller: &Object<'cx>) -> Option<AlgorithmResult<'cx>> {
 		match self {
-			Self::Null | Self::Object { start: None, .. } => None,
-			Self::Object { instance, start: Some(start), .. } => {
-				Some((instance.root(cx).into(), start.root(cx).into()))
+			Self::Null => None,
+			Self::Object { instance, start, .. } => start
+				.as_ref()
+				.map(|start| call_algorithm(cx, instance, start, &[controller.as_value(cx)])),
+			Self::Native { callbacks, .. } => {
+				let controller = TransformStreamDefaultController::get_private(cx, controller).unwrap();
+				Some(native_result(cx, callbacks.start(cx, controller)))
 			}
 		}
 	}
 
-	fn transform_function<'cx>(&self, cx: &'cx Context) -> Option<(Object<'cx>, Function<'cx>)> {
+	fn transform<'cx>(
+		&self, cx: &'cx Context, chunk: &Value, controller: &Object<'cx>,
+	) -> Option<AlgorithmResult<'cx>> {
+		let chunk = Value::from(cx.root(chunk.get()));
 		match self {
-			Self::Null | Self::Object { transform: None, .. } => None,
-			Self::Object { instance, transform: Some(transform), .. } => {
-				Some((instance.root(cx).into(), transform.root(cx).into()))
+			Self::Null | Self::Native { cleared: true, .. } => None,
+			Self::Object { instance, transform, .. } => transform
+				.as_ref()
+				.map(|transform| call_algorithm(cx, instance, transform, &[chunk, controller.as_value(cx)])),
+			Self::Native { callbacks, .. } => {
+				let controller = TransformStreamDefaultController::get_private(cx, controller).unwrap();
+				Some(native_result(cx, callbacks.transform(cx, chunk, controller)))
 			}
 		}
 	}
 
-	fn flush_function<'cx>(&self, cx: &'cx Context) -> Option<(Object<'cx>, Function<'cx>)> {
+	fn flush<'cx>(&self, cx: &'cx Context, controller: &Object<'cx>) -> Option<AlgorithmResult<'cx>> {
 		match self {
-			Self::Null | Self::Object { flush: None, .. } => None,
-			Self::Object { instance, flush: Some(flush), .. } => {
-				Some((instance.root(cx).into(), flush.root(cx).into()))
+			Self::Null | Self::Native { cleared: true, .. } => None,
+			Self::Object { instance, flush, .. } => flush
+				.as_ref()
+				.map(|flush| call_algorithm(cx, instance, flush, &[controller.as_value(cx)])),
+			Self::Native { callbacks, .. } => {
+				let controller = TransformStreamDefaultController::get_private(cx, controller).unwrap();
+				Some(native_result(cx, callbacks.flush(cx, controller)))
 			}
 		}
 	}
 
-	fn cancel_function<'cx>(&self, cx: &'cx Context) -> Option<(Object<'cx>, Function<'cx>)> {
+	fn cancel<'cx>(&self, cx: &'cx Context, reason: Value) -> Option<AlgorithmResult<'cx>> {
 		match self {
-			Self::Null | Self::Object { cancel: None, .. } => None,
-			Self::Object { instance, cancel: Some(cancel), .. } => {
-				Some((instance.root(cx).into(), cancel.root(cx).into()))
+			Self::Null => None,
+			Self::Object { instance, cancel, .. } => {
+				cancel.as_ref().map(|cancel| call_algorithm(cx, instance, cancel, &[reason]))
 			}
+			Self::Native { callbacks, .. } => Some(native_result(cx, callbacks.cancel(cx, reason))),
 		}
 	}
 }
 
+// The error is None if the algorithm failed without an exception.
+type AlgorithmResult<'cx> = std::result::Result<Value<'cx>, Option<Exception>>;
+
+fn call_algorithm<'cx>(
+	cx: &'cx Context, instance: &Heap<*mut JSObject>, function: &Heap<*mut JSFunction>, args: &[Value],
+) -> AlgorithmResult<'cx> {
+	let instance = Object::from(instance.root(cx));
+	let function = Function::from(function.root(cx));
+	function.call(cx, &instance, args).map_err(|e| e.map(|e| e.exception))
+}
+
+fn native_result(cx: &Context, result: ResultExc<Option<Promise>>) -> AlgorithmResult {
+	match result {
+		Ok(Some(promise)) => {
+			let mut value = Value::undefined(cx);
+			promise.to_value(cx, &mut value);
+			Ok(value)
+		}
+		Ok(None) => Ok(Value::undefined(cx)),
+		Err(e) => Err(Some(e)),
+	}
+}
+
 #[js_class]
 pub struct TransformStreamDefaultController {
 	reflector: Reflector,
@@ -186,6 +268,19 @@ impl TransformStreamDefaultController {
 			}
 		}
 
+		let mut desired_size = 0.0;
+		let mut has_desired_size = false;
+		if !unsafe {
+			ReadableStreamGetDesiredSize(cx.as_ptr(), readable.get(), &mut has_desired_size, &mut desired_size)
+		} {
+			return Err(Exception::Error(Error::none()));
+		}
+
+		let has_backpressure = has_desired_size && desired_size <= 0.0;
+		if has_backpressure && !stream.backpressure {
+			stream.set_backpressure(cx, true);
+		}
+
 		Ok(())
 	}
 
@@ -228,6 +323,7 @@ impl TransformStreamDefaultController {
 				*transform = None;
 				*flush = None;
 			}
+			HeapTransformer::Native { ref mut cleared, .. } => *cleared = true,
 			HeapTransformer::Null => (),
 		}
 	}
@@ -250,8 +346,10 @@ impl NativeStreamSourceCallbacks for Source {
 	fn pull<'cx>(
 		&self, _source: &'cx NativeStreamSource, cx: &'cx Context, _controller: Object<'cx>,
 	) -> ResultExc<Promise> {
-		// todo: implement backpressure
-		Ok(Promise::resolved(cx, Value::undefined(cx)))
+		// The readable side wants more chunks, so writes can be processed again.
+		let ts = TransformStream::from_traced_heap_mut(cx, &self.stream);
+		ts.set_backpressure(cx, false);
+		Ok(ts.backpressure_change_promise.clone())
 	}
 
 	fn cancel(self: Box<Self>, cx: &Context, reason: Value) -> ResultExc<Promise> {
@@ -321,66 +419,47 @@ impl NativeStreamSinkCallbacks for Sink {
 			)));
 		}
 
-		let controller = ts.get_controller(cx);
-		let controller_object = Object::from(ts.controller.root(cx)).as_value(cx);
-
-		let promise = match controller.transformer.transform_function(cx) {
-			None => {
-				controller.enqueue(cx, chunk)?;
-				Promise::resolved(cx, Value::undefined(cx))
-			}
-
-			Some((o, f)) => match f.call(cx, &o, &[chunk, controller_object]) {
-				Err(e) => Promise::rejected(
-					cx,
-					e.map(|e| e.exception).unwrap_or_else(|| {
-						Exception::Error(Error::new("Call to transformer.transform failed", ErrorKind::Normal))
-					}),
-				),
-				Ok(val) => {
-					if !val.get().is_object() {
-						Promise::resolved(cx, Value::undefined(cx))
-					} else {
-						match Promise::from(val.to_object(cx).into_local()) {
-							// The flush algorithm (erroneously) didn't return a promise
-							None => Promise::resolved(cx, Value::undefined(cx)),
-							Some(p) => p,
-						}
-					}
-				}
-			},
-		};
+		if !ts.backpressure {
+			return TransformStream::perform_transform(cx, &self.stream, chunk);
+		}
 
+		// Hold the write until the readable side has room for more chunks.
 		let ts_heap = self.stream.clone();
-
-		promise.add_reactions(
-			cx,
-			None,
-			Some(Function::from_closure(
+		let chunk = TracedHeap::from_local(&chunk);
+		ts.backpressure_change_promise
+			.then(
 				cx,
-				"__TransformStreamSinkWriteCallbackFailed",
-				Box::new(move |args| {
-					let mut accessor = args.access();
-					if accessor.is_empty() {
-						return Err(Exception::Error(Error::new(
-							"Bad arguments to promise.reject",
-							ErrorKind::Internal,
-						)));
-					}
-
-					let reason = accessor.value();
-
-					let ts = TransformStream::from_traced_heap_mut(args.cx(), &ts_heap);
-					ts.error(args.cx(), &reason)?;
-
-					Err(Exception::Other(reason.get()))
-				}),
-				1,
-				PropertyFlags::empty(),
-			)),
-		);
+				Some(Function::from_closure_once(
+					cx,
+					"",
+					Box::new(move |args| {
+						let cx = args.cx();
+						let ts = TransformStream::from_traced_heap(cx, &ts_heap);
+						let writable = ts.writable.root(cx);
+
+						if unsafe { WritableStreamGetState(cx.as_ptr(), writable.handle().into()) }
+							!= WritableStreamState::Writable
+						{
+							return Err(match ts.error {
+								Some(ref e) => Exception::Other(e.get()),
+								None => Exception::Error(Error::new(
+									"Writable half of TransformStream must be in writable state",
+									ErrorKind::Normal,
+								)),
+							});
+						}
 
-		Ok(promise)
+						let promise = TransformStream::perform_transform(cx, &ts_heap, chunk.root(cx).into())?;
+						let mut res = Value::undefined(cx);
+						promise.to_value(cx, &mut res);
+						Ok(res)
+					}),
+					1,
+					PropertyFlags::empty(),
+				)),
+				None,
+			)
+			.ok_or_else(|| Exception::Error(Error::none()))
 	}
 
 	fn close(&self, cx: &Context) -> ResultExc<Promise> {
@@ -396,20 +475,20 @@ impl NativeStreamSinkCallbacks for Sink {
 				let ts = TransformStream::from_traced_heap(&cx, &stream);
 				let controller_object = Object::from(ts.controller.root(&cx));
 				let controller = ts.get_controller(&cx);
-				let cx = match controller.transformer.flush_function(&cx) {
+				let cx = match controller.transformer.flush(&cx, &controller_object) {
 					// No flush algorithm, carry on.
 					None => cx,
 
 					// Run the flush algorithm
-					Some((o, f)) => {
-						let flush_result = match f.call(&cx, &o, &[controller_object.as_value(&cx)]) {
+					Some(flush_result) => {
+						let flush_result = match flush_result {
 							Err(Some(e)) => {
 								ReadableStreamError(
 									cx.as_ptr(),
 									ts.readable.root(&cx).handle().into(),
-									e.exception.as_value(&cx).handle().into(),
+									e.as_value(&cx).handle().into(),
 								);
-								return Err(e.exception);
+								return Err(e);
 							}
 							Err(None) => return Err(Error::none().into()),
 							Ok(f) => f,
@@ -424,10 +503,10 @@ impl NativeStreamSinkCallbacks for Sink {
 								None => cx,
 								Some(p) => {
 									// Wait for the promise to run...
-									let (cx, promise_result) = PromiseFuture::new(cx, &p).await;
+									let (cx, promise_result) = TypedPromise::<()>::new(p).wait(cx).await;
 									match promise_result {
-										Err(e) => {
-											let e = e.root(&cx).into();
+										Err(exception) => {
+											let e = exception.as_value(&cx);
 
 											// ... if it failed, fail the entire process
 											let ts = TransformStream::from_traced_heap_mut(&cx, &stream);
@@ -439,7 +518,7 @@ impl NativeStreamSinkCallbacks for Sink {
 												e.handle().into(),
 											);
 
-											return Err(Exception::Other(e.get()));
+											return Err(exception);
 										}
 										// ... it ran successfully, carry on
 										Ok(_) => cx,
@@ -545,6 +624,12 @@ pub struct TransformStream {
 	#[trace(no_trace)]
 	finish_promise: Option<Promise>,
 	error: Option<Heap<JSVal>>,
+
+	// Set while the readable side has no room for more chunks, writes wait on the
+	// change promise before running the transform algorithm.
+	backpressure: bool,
+	#[trace(no_trace)]
+	backpressure_change_promise: Promise,
 }
 
 impl TransformStream {
@@ -600,60 +685,57 @@ impl TransformStream {
 		match self.finish_promise {
 			Some(ref p) => (p.clone(), false),
 			None => {
-				let promise = match self.get_controller(cx).transformer.cancel_function(cx) {
+				let promise = match self.get_controller(cx).transformer.cancel(cx, reason) {
 					None => finish_promise_inner(self, cx),
-					Some((o, f)) => {
-						let result = f.call(cx, &o, &[reason]);
-						match result {
-							Err(Some(e)) => Promise::rejected(cx, e.exception.as_value(cx)),
-							Err(None) => Promise::rejected(cx, Value::undefined(cx)),
-							Ok(v) if v.get().is_object() => match Promise::from(v.to_object(cx).into_local()) {
-								Some(p) => {
-									let finish_promise = Promise::new(cx);
-									let fp1 = finish_promise.clone();
-									let fp2 = finish_promise.clone();
-									let this_heap1 = TracedHeap::new(self.reflector().get());
-									let this_heap2 = TracedHeap::new(self.reflector().get());
-									p.add_reactions(
+					Some(result) => match result {
+						Err(Some(e)) => Promise::rejected(cx, e.as_value(cx)),
+						Err(None) => Promise::rejected(cx, Value::undefined(cx)),
+						Ok(v) if v.get().is_object() => match Promise::from(v.to_object(cx).into_local()) {
+							Some(p) => {
+								let finish_promise = Promise::new(cx);
+								let fp1 = finish_promise.clone();
+								let fp2 = finish_promise.clone();
+								let this_heap1 = TracedHeap::new(self.reflector().get());
+								let this_heap2 = TracedHeap::new(self.reflector().get());
+								p.add_reactions(
+									cx,
+									Some(Function::from_closure(
 										cx,
-										Some(Function::from_closure(
-											cx,
-											"",
-											Box::new(move |args| {
-												let cx = args.cx();
-												let this = Self::from_traced_heap(cx, &this_heap1);
-												match this.error {
-													Some(ref e) => fp1.reject(cx, &e.root(cx).into()),
-													None => fp1.resolve(cx, &Value::undefined(cx)),
-												};
-												Ok(Value::undefined(cx))
-											}),
-											1,
-											PropertyFlags::empty(),
-										)),
-										Some(Function::from_closure(
-											cx,
-											"",
-											Box::new(move |args| {
-												let cx = args.cx();
-												let this = Self::from_traced_heap(cx, &this_heap2);
-												match this.error {
-													Some(ref e) => fp2.reject(cx, &e.root(cx).into()),
-													None => fp2.reject(cx, &args.access().value()),
-												};
-												Ok(Value::undefined(cx))
-											}),
-											1,
-											PropertyFlags::empty(),
-										)),
-									);
-									finish_promise
-								}
-								None => finish_promise_inner(self, cx),
-							},
-							Ok(_) => finish_promise_inner(self, cx),
-						}
-					}
+										"",
+										Box::new(move |args| {
+											let cx = args.cx();
+											let this = Self::from_traced_heap(cx, &this_heap1);
+											match this.error {
+												Some(ref e) => fp1.reject(cx, &e.root(cx).into()),
+												None => fp1.resolve(cx, &Value::undefined(cx)),
+											};
+											Ok(Value::undefined(cx))
+										}),
+										1,
+										PropertyFlags::empty(),
+									)),
+									Some(Function::from_closure(
+										cx,
+										"",
+										Box::new(move |args| {
+											let cx = args.cx();
+											let this = Self::from_traced_heap(cx, &this_heap2);
+											match this.error {
+												Some(ref e) => fp2.reject(cx, &e.root(cx).into()),
+												None => fp2.reject(cx, &args.access().value()),
+											};
+											Ok(Value::undefined(cx))
+										}),
+										1,
+										PropertyFlags::empty(),
+									)),
+								);
+								finish_promise
+							}
+							None => finish_promise_inner(self, cx),
+						},
+						Ok(_) => finish_promise_inner(self, cx),
+					},
 				};
 				self.finish_promise = Some(unsafe { Promise::from_unchecked(cx.root(promise.get())) });
 				(promise, true)
@@ -671,11 +753,21 @@ impl TransformStream {
 		self.error_writable_and_unblock_write(cx, e)
 	}
 
+	pub fn set_backpressure(&mut self, cx: &Context, backpressure: bool) {
+		self.backpressure_change_promise.resolve(cx, &Value::undefined(cx));
+		self.backpressure_change_promise = Promise::new(cx);
+		self.backpressure = backpressure;
+	}
+
 	pub fn error_writable_and_unblock_write(&mut self, cx: &Context, e: &Value) -> Result<()> {
 		self.error = Some(Heap::new(e.get()));
 
 		TransformStreamDefaultController::from_heap_mut(cx, &self.controller).clear_algorithms();
 
+		if self.backpressure {
+			self.set_backpressure(cx, false);
+		}
+
 		let writable = self.writable.root(cx);
 
 		unsafe {
@@ -689,41 +781,71 @@ impl TransformStream {
 		Ok(())
 	}
 
-	fn call_start(cx: &Context, this: &mut Object) -> ResultExc<()> {
-		let ts = Self::get_private(cx, this).unwrap();
-		let controller = TransformStreamDefaultController::get_private(cx, &ts.controller.root(cx).into()).unwrap();
-		let controller_value = Object::from(ts.controller.root(cx)).as_value(cx);
-		match controller.transformer.start_function(cx) {
-			Some((o, f)) => match f.call(cx, &o, &[controller_value]) {
+	fn perform_transform(cx: &Context, stream: &TracedHeap<*mut JSObject>, chunk: Value) -> ResultExc<Promise> {
+		let ts = TransformStream::from_traced_heap(cx, stream);
+		let controller = ts.get_controller(cx);
+		let controller_object = Object::from(ts.controller.root(cx));
+
+		let promise = match controller.transformer.transform(cx, &chunk, &controller_object) {
+			None => {
+				controller.enqueue(cx, chunk)?;
+				Promise::resolved(cx, Value::undefined(cx))
+			}
+
+			Some(result) => match result {
+				Err(e) => Promise::rejected(
+					cx,
+					e.unwrap_or_else(|| {
+						Exception::Error(Error::new("Call to transformer.transform failed", ErrorKind::Normal))
+					}),
+				),
 				Ok(val) => {
-					ts.start_promise.resolve(cx, &val);
-					Ok(())
-				}
-				Err(Some(e)) => {
-					ts.start_promise.reject(cx, &e.exception.as_value(cx));
-					Err(e.exception)
-				}
-				Err(None) => {
-					ts.start_promise.reject(cx, &Value::undefined(cx));
-					Err(Error::none().into())
+					if !val.get().is_object() {
+						Promise::resolved(cx, Value::undefined(cx))
+					} else {
+						match Promise::from(val.to_object(cx).into_local()) {
+							// The flush algorithm (erroneously) didn't return a promise
+							None => Promise::resolved(cx, Value::undefined(cx)),
+							Some(p) => p,
+						}
+					}
 				}
 			},
-			None => {
-				ts.start_promise.resolve(cx, &Value::undefined(cx));
-				Ok(())
-			}
-		}
-	}
-}
+		};
 
-#[js_class]
-impl TransformStream {
-	#[ion(constructor, post_construct = call_start)]
-	pub fn constructor<'cx>(
-		cx: &'cx Context, #[ion(this)] this: &Object<'cx>, Opt(transformer_object): Opt<Object<'cx>>,
-	) -> ResultExc<TransformStream> {
-		let transformer = HeapTransformer::from_transformer(cx, transformer_object)?;
+		let ts_heap = stream.clone();
+
+		promise.add_reactions(
+			cx,
+			None,
+			Some(Function::from_closure(
+				cx,
+				"__TransformStreamSinkWriteCallbackFailed",
+				Box::new(move |args| {
+					let mut accessor = args.access();
+					if accessor.is_empty() {
+						return Err(Exception::Error(Error::new(
+							"Bad arguments to promise.reject",
+							ErrorKind::Internal,
+						)));
+					}
+
+					let reason = accessor.value();
+
+					let ts = TransformStream::from_traced_heap_mut(args.cx(), &ts_heap);
+					ts.error(args.cx(), &reason)?;
+
+					Err(Exception::Other(reason.get()))
+				}),
+				1,
+				PropertyFlags::empty(),
+			)),
+		);
+
+		Ok(promise)
+	}
 
+	fn initialise(cx: &Context, this: &Object, transformer: HeapTransformer) -> ResultExc<TransformStream> {
 		let start_promise = Promise::new(cx);
 
 		let controller =
@@ -770,9 +892,49 @@ impl TransformStream {
 			writable: Heap::from_local(&writable),
 			finish_promise: None,
 			error: None,
+			backpressure: true,
+			backpressure_change_promise: Promise::new(cx),
 		})
 	}
 
+	fn call_start(cx: &Context, this: &mut Object) -> ResultExc<()> {
+		let ts = Self::get_private(cx, this).unwrap();
+		let controller = TransformStreamDefaultController::get_private(cx, &ts.controller.root(cx).into()).unwrap();
+		let controller_object = Object::from(ts.controller.root(cx));
+		match controller.transformer.start(cx, &controller_object) {
+			Some(result) => match result {
+				Ok(val) => {
+					ts.start_promise.resolve(cx, &val);
+					Ok(())
+				}
+				Err(Some(e)) => {
+					ts.start_promise.reject(cx, &e.as_value(cx));
+					Err(e)
+				}
+				Err(None) => {
+					ts.start_promise.reject(cx, &Value::undefined(cx));
+					Err(Error::none().into())
+				}
+			},
+			None => {
+				ts.start_promise.resolve(cx, &Value::undefined(cx));
+				Ok(())
+			}
+		}
+	}
+}
+
+#[js_class]
+impl TransformStream {
+	#[ion(constructor, post_construct = call_start)]
+	pub fn constructor<'cx>(
+		cx: &'cx Context, #[ion(this)] this: &Object<'cx>, Opt(transformer_object): Opt<Object<'cx>>,
+	) -> ResultExc<TransformStream> {
+		let transformer = HeapTransformer::from_transformer(cx, transformer_object)?;
+
+		TransformStream::initialise(cx, this, transformer)
+	}
+
 	#[ion(get)]
 	pub fn get_readable(&self) -> *mut JSObject {
 		self.readable.get()
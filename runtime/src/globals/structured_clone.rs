@@ -0,0 +1,31 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use mozjs::jsapi::JSFunctionSpec;
+
+use ion::{Context, Object, ResultExc, Value};
+use ion::clone::structured_clone as clone;
+use ion::function::Opt;
+
+#[derive(Default, FromValue)]
+pub struct StructuredSerializeOptions<'cx> {
+	#[ion(default)]
+	transfer: Vec<Object<'cx>>,
+}
+
+#[js_fn]
+fn structuredClone<'cx>(
+	cx: &'cx Context, value: Value, Opt(options): Opt<StructuredSerializeOptions>,
+) -> ResultExc<Value<'cx>> {
+	let options = options.unwrap_or_default();
+	clone(cx, &value, &options.transfer)
+}
+
+const FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(structuredClone, 1), JSFunctionSpec::ZERO];
+
+pub fn define(cx: &Context, global: &Object) -> bool {
+	unsafe { global.define_methods(cx, FUNCTIONS) }
+}
@@ -4,32 +4,25 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use chrono::Duration;
 use mozjs::jsapi::JSFunctionSpec;
 use mozjs::jsval::JSVal;
 
-use ion::{Context, Error, Function, Object, Result};
-use ion::function::{Clamp, Enforce, Opt, Rest};
+use ion::{Context, Error, Function, Object, Result, Value};
+use ion::conversions::ToValue;
+use ion::function::{Enforce, Opt, Rest, Wrap};
 
 use crate::ContextExt;
-use crate::event_loop::macrotasks::{Macrotask, TimerMacrotask, UserMacrotask};
-
-const MINIMUM_DELAY: i32 = 0;
-const MINIMUM_DELAY_NESTED: i32 = 4;
+use crate::event_loop::macrotasks::{Macrotask, TimerInfo, TimerMacrotask, UserMacrotask};
 
+/// Creates a timer with the delay converted as a WebIDL `long`, so that delays of 2<sup>31</sup> ms or longer wrap
+/// around. Negative, `NaN` and wrapped delays are clamped to the minimum delay, so the timer fires immediately.
 fn set_timer(
-	cx: &Context, callback: Function, duration: Option<Clamp<i32>>, arguments: &[JSVal], repeat: bool,
+	cx: &Context, callback: Function, duration: Option<Wrap<i32>>, arguments: &[JSVal], repeat: bool,
 ) -> Result<u32> {
 	let event_loop = unsafe { &mut cx.get_private().event_loop };
 	if let Some(queue) = &mut event_loop.macrotasks {
-		let minimum = if queue.nesting > 5 {
-			MINIMUM_DELAY_NESTED
-		} else {
-			MINIMUM_DELAY
-		};
-
-		let duration = duration.map(|t| t.0.max(minimum)).unwrap_or(minimum);
-		let timer = TimerMacrotask::new(callback, arguments, repeat, Duration::milliseconds(duration.into()));
+		let duration = queue.timer_options.clamp(duration.map(|t| t.0), queue.nesting);
+		let timer = TimerMacrotask::new(callback, arguments, repeat, duration);
 		Ok(queue.enqueue(cx, Macrotask::Timer(timer), None))
 	} else {
 		Err(Error::new("Macrotask Queue has not been initialized.", None))
@@ -40,7 +33,7 @@ fn clear_timer(cx: &Context, id: Option<Enforce<u32>>) -> Result<()> {
 	if let Some(id) = id {
 		let event_loop = unsafe { &mut cx.get_private().event_loop };
 		if let Some(queue) = &mut event_loop.macrotasks {
-			queue.remove(id.0);
+			queue.remove(cx, id.0);
 			Ok(())
 		} else {
 			Err(Error::new("Macrotask Queue has not been initialized.", None))
@@ -52,14 +45,14 @@ fn clear_timer(cx: &Context, id: Option<Enforce<u32>>) -> Result<()> {
 
 #[js_fn]
 fn setTimeout(
-	cx: &Context, callback: Function, Opt(duration): Opt<Clamp<i32>>, Rest(arguments): Rest<JSVal>,
+	cx: &Context, callback: Function, Opt(duration): Opt<Wrap<i32>>, Rest(arguments): Rest<JSVal>,
 ) -> Result<u32> {
 	set_timer(cx, callback, duration, &arguments, false)
 }
 
 #[js_fn]
 fn setInterval(
-	cx: &Context, callback: Function, Opt(duration): Opt<Clamp<i32>>, Rest(arguments): Rest<JSVal>,
+	cx: &Context, callback: Function, Opt(duration): Opt<Wrap<i32>>, Rest(arguments): Rest<JSVal>,
 ) -> Result<u32> {
 	set_timer(cx, callback, duration, &arguments, true)
 }
@@ -97,3 +90,35 @@ const FUNCTIONS: &[JSFunctionSpec] = &[
 pub fn define(cx: &Context, global: &Object) -> bool {
 	unsafe { global.define_methods(cx, FUNCTIONS) }
 }
+
+impl<'cx> ToValue<'cx> for TimerInfo {
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let object = Object::new(cx);
+		object.set_as(cx, "id", &self.id);
+		object.set_as(cx, "type", if self.repeat { "interval" } else { "timeout" });
+		object.set_as(cx, "label", &self.label);
+		object.set_as(cx, "due", &(self.due.timestamp_millis() as f64));
+		object.to_value(cx, value);
+	}
+}
+
+/// Returns the pending timers in the order they are due, with their ID, type, callback name and due time in
+/// milliseconds since the Unix epoch.
+#[js_fn]
+fn list(cx: &Context) -> Result<Vec<TimerInfo>> {
+	let event_loop = unsafe { &cx.get_private().event_loop };
+	if let Some(queue) = &event_loop.macrotasks {
+		Ok(queue.timers(cx))
+	} else {
+		Err(Error::new("Macrotask Queue has not been initialized.", None))
+	}
+}
+
+const INTROSPECTION_FUNCTIONS: &[JSFunctionSpec] = &[function_spec!(list, 0), JSFunctionSpec::ZERO];
+
+/// Defines the `timers` global, whose `list()` returns the pending timers.
+pub fn define_introspection(cx: &Context, global: &Object) -> bool {
+	let timers = Object::new(cx);
+	unsafe { timers.define_methods(cx, INTROSPECTION_FUNCTIONS) }
+	&&global.set_as(cx, "timers", &timers)
+}
@@ -102,6 +102,13 @@ impl URL {
 		self.url.to_string()
 	}
 
+	#[ion(inspect)]
+	pub fn inspect<'cx>(&self, cx: &'cx Context) -> Object<'cx> {
+		let object = Object::new(cx);
+		object.set_as(cx, "href", &self.get_href());
+		object
+	}
+
 	#[ion(set)]
 	pub fn set_href(&mut self, cx: &Context, input: String) -> Result<()> {
 		match url::Url::parse(&input) {
@@ -0,0 +1,136 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::Waker;
+
+use ion::{Context, Error, ErrorKind, ErrorReport, Exception};
+
+use crate::ContextExt;
+use crate::profiler::InterruptHandle;
+
+#[derive(Default)]
+struct Shared {
+	/// Context of the runtime, which is cleared when the runtime is dropped.
+	context: Mutex<Option<InterruptHandle>>,
+	waker: Mutex<Option<Waker>>,
+	requested: AtomicBool,
+	interrupted: AtomicBool,
+	terminated: AtomicBool,
+}
+
+/// Thread-safe handle to a [Runtime](crate::Runtime), which stops its scripts from other threads, such as when they are
+/// stuck in an infinite loop.
+///
+/// Scripts are stopped through the interrupt callback of the context, so native code which does not run scripts
+/// cannot be stopped until it returns.
+#[derive(Clone, Default)]
+pub struct RuntimeHandle {
+	shared: Arc<Shared>,
+}
+
+impl RuntimeHandle {
+	pub(crate) fn new(cx: &Context) -> RuntimeHandle {
+		let shared = Shared {
+			context: Mutex::new(Some(InterruptHandle::new(cx))),
+			..Shared::default()
+		};
+		RuntimeHandle { shared: Arc::new(shared) }
+	}
+
+	/// Stops the script which is currently running. The event loop reports it as an error, and continues running the
+	/// remaining tasks when it is run again.
+	/// Returns `false` if the runtime was dropped.
+	pub fn interrupt(&self) -> bool {
+		let context = self.shared.context.lock().unwrap();
+		match &*context {
+			Some(context) => {
+				self.shared.requested.store(true, Ordering::Release);
+				context.request();
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Stops the script which is currently running and clears the queues of the event loop, which then fails with a
+	/// cancellation error. Tasks which are scheduled afterwards are discarded as well.
+	/// Returns `false` if the runtime was dropped.
+	pub fn terminate(&self) -> bool {
+		self.shared.terminated.store(true, Ordering::Release);
+		if let Some(waker) = self.shared.waker.lock().unwrap().take() {
+			waker.wake();
+		}
+		self.interrupt()
+	}
+
+	pub fn is_terminated(&self) -> bool {
+		self.shared.terminated.load(Ordering::Acquire)
+	}
+
+	/// Prevents further interrupts, as the context is about to be destroyed.
+	pub(crate) fn detach(&self) {
+		*self.shared.context.lock().unwrap() = None;
+	}
+}
+
+/// Returns `true` if a [RuntimeHandle] requested to stop the running script, so that the interrupt callback stops it.
+pub(crate) fn take_request(cx: &Context) -> bool {
+	let shared = &unsafe { cx.get_private() }.handle.shared;
+	let requested = shared.requested.swap(false, Ordering::AcqRel);
+	if requested {
+		shared.interrupted.store(true, Ordering::Release);
+	}
+	requested
+}
+
+/// Returns the error which a task stopped by a [RuntimeHandle] is reported as.
+pub(crate) fn take_interruption(cx: &Context) -> Option<ErrorReport> {
+	let shared = &unsafe { cx.get_private() }.handle.shared;
+	if !shared.interrupted.swap(false, Ordering::AcqRel) {
+		return None;
+	}
+	if shared.terminated.load(Ordering::Acquire) {
+		Some(terminated_report())
+	} else {
+		Some(report("Execution was interrupted"))
+	}
+}
+
+pub(crate) fn terminated_report() -> ErrorReport {
+	report("Runtime was terminated")
+}
+
+/// Returns the exception which a call from native code failed with. Calls fail without an error report when they are
+/// stopped by a [RuntimeHandle], which is reported as a termination or interruption error instead.
+pub(crate) fn call_exception(cx: &Context, error: Option<ErrorReport>) -> Exception {
+	match error {
+		Some(error) => error.exception,
+		None if unsafe { cx.get_private() }.handle.is_terminated() => terminated_report().exception,
+		None => report("Execution was interrupted").exception,
+	}
+}
+
+fn report(message: &'static str) -> ErrorReport {
+	ErrorReport::from(Exception::Error(Error::new(message, ErrorKind::Normal)), None)
+}
+
+/// Returns `true` if the runtime was terminated. Otherwise, registers the waker of the event loop, so that it is woken
+/// up to stop once the runtime is terminated.
+pub(crate) fn poll_terminated(cx: &Context, waker: &Waker) -> bool {
+	let shared = &unsafe { cx.get_private() }.handle.shared;
+	if shared.terminated.load(Ordering::Acquire) {
+		return true;
+	}
+	let mut registered = shared.waker.lock().unwrap();
+	match &*registered {
+		Some(registered) if registered.will_wake(waker) => {}
+		_ => *registered = Some(waker.clone()),
+	}
+	drop(registered);
+	shared.terminated.load(Ordering::Acquire)
+}
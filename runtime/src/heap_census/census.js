@@ -0,0 +1,35 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+"use strict";
+
+// Takes a census of the heap with the Debugger API, in a global which is separate from the runtime, as debuggers
+// cannot share a compartment with their debuggees.
+// The breakdown matches the "Coarse Type" view of the memory tool of Firefox DevTools.
+
+const COUNT = { by: "count", count: true, bytes: true };
+const INTERNAL_TYPE = { by: "internalType", then: COUNT };
+
+const BREAKDOWN = {
+	by: "coarseType",
+	objects: { by: "objectClass", then: COUNT, other: COUNT },
+	strings: COUNT,
+	scripts: { by: "filename", then: INTERNAL_TYPE, noFilename: INTERNAL_TYPE },
+	other: INTERNAL_TYPE,
+};
+
+function takeCensus() {
+	const dbg = new Debugger();
+	dbg.addAllGlobalsAsDebuggees();
+	try {
+		return JSON.stringify({
+			breakdown: BREAKDOWN,
+			census: dbg.memory.takeCensus({ breakdown: BREAKDOWN }),
+		});
+	} finally {
+		dbg.removeAllDebuggees();
+	}
+}
@@ -0,0 +1,61 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use mozjs::jsapi::{GCReason, JS_DefineDebuggerObject, JS_GC, JSAutoRealm, OnNewGlobalHookOption};
+use mozjs::rust::SIMPLE_GLOBAL_CLASS;
+
+use ion::{Context, ErrorReport, Function};
+use ion::conversions::FromValue;
+use ion::object::new_global;
+use ion::script::Script;
+
+const SOURCE: &str = include_str!("census.js");
+
+/// Takes a census of the heap of every global, which counts the objects, strings, scripts and other cells by their type,
+/// along with the bytes they occupy. Garbage is collected first, so that only reachable cells are counted.
+///
+/// Returns JSON containing the `breakdown` the census was taken with, and the `census` returned by
+/// `Debugger.Memory.prototype.takeCensus`. This is a summary of the heap, rather than a snapshot of its graph, so it
+/// cannot be loaded by heap snapshot analysis tools.
+pub fn heap_census(cx: &Context) -> Result<String, Option<ErrorReport>> {
+	unsafe { JS_GC(cx.as_ptr(), GCReason::API) };
+
+	let global = new_global(
+		cx,
+		&SIMPLE_GLOBAL_CLASS,
+		None,
+		OnNewGlobalHookOption::DontFireOnNewGlobalHook,
+		None,
+	);
+	let _realm = JSAutoRealm::new(cx.as_ptr(), global.handle().get());
+	if !unsafe { JS_DefineDebuggerObject(cx.as_ptr(), global.handle().into()) } {
+		return Err(ErrorReport::new_with_exception_stack(cx).unwrap());
+	}
+
+	Script::compile_and_evaluate(cx, Path::new("census.js"), SOURCE).map_err(Some)?;
+	let take_census = match global.get_as::<_, Function>(cx, "takeCensus", true, ()) {
+		Ok(Some(take_census)) => take_census,
+		_ => return Err(None),
+	};
+	let census = take_census.call(cx, &global, &[])?;
+	String::from_value(cx, &census, true, ()).map_err(|_| None)
+}
+
+/// Takes a census of the heap with [heap_census], and writes it to a file.
+pub fn write_heap_census(cx: &Context, path: &Path) -> io::Result<()> {
+	let census = heap_census(cx).map_err(|report| {
+		let message = match report {
+			Some(report) => report.format(cx),
+			None => String::from("Failed to take census of heap"),
+		};
+		io::Error::other(message)
+	})?;
+	fs::write(path, census)
+}
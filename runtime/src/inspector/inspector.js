@@ -0,0 +1,641 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+"use strict";
+
+// Implements a subset of the Chrome DevTools Protocol with the Debugger API.
+// This script runs in the global of the debugger, and receives the global of the runtime as `inspector.debuggee`.
+
+const CONTEXT_ID = 1;
+
+const dbg = new Debugger();
+const debuggee = dbg.addDebuggee(inspector.debuggee);
+
+const state = {
+	enabled: false,
+	runtimeEnabled: false,
+	waiting: false,
+	breakpointsActive: true,
+	pauseOnExceptions: "none",
+	lastException: undefined,
+	paused: null,
+	stepping: [],
+};
+
+const sources = new Map();
+const sourceIds = new Map();
+let nextScriptId = 1;
+
+const breakpoints = new Map();
+let nextBreakpointId = 1;
+
+const objects = new Map();
+let nextObjectId = 1;
+
+function send(method, params) {
+	inspector.send(JSON.stringify({ method, params }));
+}
+
+function urlOf(source) {
+	const url = source.url ?? "";
+	return url.startsWith("/") ? `file://${url}` : url;
+}
+
+function scriptParsed(record) {
+	const { source } = record;
+	const lines = source.text.split("\n");
+	send("Debugger.scriptParsed", {
+		scriptId: record.id,
+		url: record.url,
+		startLine: 0,
+		startColumn: 0,
+		endLine: lines.length - 1,
+		endColumn: lines[lines.length - 1].length,
+		executionContextId: CONTEXT_ID,
+		hash: "",
+		sourceMapURL: source.sourceMapURL ?? "",
+	});
+}
+
+function allScripts(record) {
+	const scripts = [];
+	const visit = script => {
+		scripts.push(script);
+		script.getChildScripts().forEach(visit);
+	};
+	record.scripts.forEach(visit);
+	return scripts;
+}
+
+dbg.onNewScript = function (script) {
+	const { source } = script;
+	let id = sourceIds.get(source);
+	let record;
+	if (id === undefined) {
+		id = String(nextScriptId++);
+		record = { id, source, url: urlOf(source), scripts: [] };
+		sourceIds.set(source, id);
+		sources.set(id, record);
+		if (state.enabled) {
+			scriptParsed(record);
+		}
+	} else {
+		record = sources.get(id);
+	}
+	record.scripts.push(script);
+
+	for (const breakpoint of breakpoints.values()) {
+		if (breakpoint.matches(record)) {
+			const location = resolveBreakpoint(record, breakpoint);
+			if (location) {
+				send("Debugger.breakpointResolved", { breakpointId: breakpoint.id, location });
+			}
+		}
+	}
+};
+
+// Breakpoints
+
+function resolveBreakpoint(record, breakpoint) {
+	let best = null;
+	for (const script of allScripts(record)) {
+		for (const position of script.getPossibleBreakpoints({ line: breakpoint.lineNumber + 1 })) {
+			if (position.columnNumber < breakpoint.columnNumber) {
+				continue;
+			}
+			if (best === null || position.columnNumber < best.columnNumber) {
+				best = { script, offset: position.offset, columnNumber: position.columnNumber };
+			}
+		}
+	}
+	if (best === null) {
+		return null;
+	}
+
+	const handler = {
+		hit(frame) {
+			if (!state.breakpointsActive || breakpoint.condition && !evaluateCondition(frame, breakpoint.condition)) {
+				return undefined;
+			}
+			return pause(frame, "other", undefined, [breakpoint.id]);
+		},
+	};
+	best.script.setBreakpoint(best.offset, handler);
+	breakpoint.handlers.push({ script: best.script, handler });
+
+	const location = { scriptId: record.id, lineNumber: breakpoint.lineNumber, columnNumber: best.columnNumber };
+	breakpoint.locations.push(location);
+	return location;
+}
+
+function evaluateCondition(frame, condition) {
+	const completion = frame.eval(condition);
+	return completion !== null && "return" in completion && Boolean(completion.return);
+}
+
+function addBreakpoint(matches, params) {
+	const id = String(nextBreakpointId++);
+	const breakpoint = {
+		id,
+		matches,
+		lineNumber: params.lineNumber,
+		columnNumber: params.columnNumber ?? 0,
+		condition: params.condition || null,
+		handlers: [],
+		locations: [],
+	};
+	breakpoints.set(id, breakpoint);
+
+	for (const record of sources.values()) {
+		if (matches(record)) {
+			resolveBreakpoint(record, breakpoint);
+		}
+	}
+	return breakpoint;
+}
+
+function removeBreakpoint(breakpoint) {
+	for (const { script, handler } of breakpoint.handlers) {
+		script.clearBreakpoint(handler);
+	}
+	breakpoints.delete(breakpoint.id);
+}
+
+// Pausing
+
+function location(frame) {
+	const { lineNumber, columnNumber } = frame.script.getOffsetLocation(frame.offset);
+	return { scriptId: sourceIds.get(frame.script.source), lineNumber: lineNumber - 1, columnNumber };
+}
+
+function describeScopes(frame) {
+	const scopes = [];
+	let local = true;
+	for (let environment = frame.environment; environment; environment = environment.parent) {
+		let type;
+		if (environment.type === "object" && environment.object === debuggee) {
+			type = "global";
+		} else if (environment.type === "with") {
+			type = "with";
+		} else if (environment.callee) {
+			type = local ? "local" : "closure";
+			local = false;
+		} else {
+			type = "block";
+		}
+		const objectId = register({ environment }, "backtrace");
+		scopes.push({ type, object: { type: "object", className: "Object", description: "Object", objectId } });
+	}
+	return scopes;
+}
+
+function describeFrame(frame, index) {
+	const callee = frame.callee;
+	return {
+		callFrameId: String(index),
+		functionName: callee ? callee.displayName ?? callee.name ?? "" : "",
+		location: location(frame),
+		url: urlOf(frame.script.source),
+		scopeChain: describeScopes(frame),
+		this: remote(frame.this, "backtrace"),
+	};
+}
+
+function clearStepping() {
+	dbg.onEnterFrame = undefined;
+	for (const frame of state.stepping) {
+		if (frame.onStack) {
+			frame.onStep = undefined;
+			frame.onPop = undefined;
+		}
+	}
+	state.stepping = [];
+}
+
+function pause(frame, reason, data, hitBreakpoints = []) {
+	if (state.paused !== null || !state.enabled) {
+		return undefined;
+	}
+	clearStepping();
+
+	const frames = [];
+	for (let current = frame; current; current = current.older) {
+		if (current.script) {
+			frames.push(current);
+		}
+	}
+	state.paused = { frames };
+	send("Debugger.paused", {
+		callFrames: frames.map(describeFrame),
+		reason,
+		data,
+		hitBreakpoints,
+	});
+
+	while (state.paused !== null) {
+		dispatch(inspector.receive());
+	}
+
+	for (const [id, entry] of objects) {
+		if (entry.group === "backtrace") {
+			objects.delete(id);
+		}
+	}
+	if (state.enabled) {
+		send("Debugger.resumed", {});
+	}
+	return undefined;
+}
+
+function resume() {
+	state.paused = null;
+}
+
+function stepIn(frame, startLine) {
+	state.stepping.push(frame);
+	frame.onStep = function () {
+		const { lineNumber, isStepStart } = this.script.getOffsetMetadata(this.offset);
+		if (isStepStart && lineNumber !== startLine) {
+			return pause(this, "other");
+		}
+		return undefined;
+	};
+	frame.onPop = function () {
+		if (this.older) {
+			stepIn(this.older, -1);
+		}
+		return undefined;
+	};
+}
+
+function step(kind) {
+	const frame = state.paused.frames[0];
+	const { lineNumber } = frame.script.getOffsetLocation(frame.offset);
+	switch (kind) {
+		case "into":
+			dbg.onEnterFrame = entered => pause(entered, "other");
+			stepIn(frame, lineNumber);
+			break;
+		case "over":
+			stepIn(frame, lineNumber);
+			break;
+		case "out":
+			state.stepping.push(frame);
+			frame.onPop = function () {
+				if (this.older) {
+					stepIn(this.older, -1);
+				}
+				return undefined;
+			};
+			break;
+	}
+	resume();
+}
+
+dbg.onExceptionUnwind = function (frame, value) {
+	if (state.pauseOnExceptions === "none" || value === state.lastException) {
+		return undefined;
+	}
+	// SpiderMonkey does not know whether an exception will be caught when it is thrown, so uncaught exceptions are
+	// approximated by the exceptions which unwind the outermost frame.
+	if (state.pauseOnExceptions === "uncaught" && frame.older !== null) {
+		return undefined;
+	}
+	state.lastException = value;
+	return pause(frame, "exception", remote(value, "backtrace"));
+};
+
+// Remote Objects
+
+function register(entry, group) {
+	const objectId = String(nextObjectId++);
+	objects.set(objectId, { ...entry, group });
+	return objectId;
+}
+
+const SUBTYPES = {
+	Array: "array",
+	Error: "error",
+	RegExp: "regexp",
+	Date: "date",
+	Map: "map",
+	Set: "set",
+	WeakMap: "weakmap",
+	WeakSet: "weakset",
+	Promise: "promise",
+	ArrayBuffer: "arraybuffer",
+};
+
+function ownValue(object, key) {
+	const descriptor = object.getOwnPropertyDescriptor(key);
+	return descriptor && "value" in descriptor ? descriptor.value : undefined;
+}
+
+function remote(value, group = "console") {
+	switch (typeof value) {
+		case "undefined":
+			return { type: "undefined" };
+		case "string":
+		case "boolean":
+			return { type: typeof value, value };
+		case "number":
+			if (Number.isFinite(value) && !Object.is(value, -0)) {
+				return { type: "number", value, description: String(value) };
+			}
+			return {
+				type: "number",
+				unserializableValue: Object.is(value, -0) ? "-0" : String(value),
+				description: String(value),
+			};
+		case "bigint":
+			return { type: "bigint", unserializableValue: `${value}n`, description: `${value}n` };
+		case "symbol":
+			return { type: "symbol", description: value.toString() };
+	}
+	if (value === null) {
+		return { type: "object", subtype: "null", value: null };
+	}
+	if (!(value instanceof Debugger.Object)) {
+		// Variables which were optimised out, or are in their temporal dead zone.
+		return { type: "undefined", description: value.optimizedOut ? "<optimized out>" : "<unavailable>" };
+	}
+
+	const objectId = register({ object: value }, group);
+	if (value.callable) {
+		const name = value.displayName ?? value.name ?? "";
+		return { type: "function", className: "Function", description: `function ${name}()`, objectId };
+	}
+
+	const className = value.class;
+	const subtype = value.isProxy ? "proxy" : SUBTYPES[className];
+	let description = className;
+	if (className === "Array") {
+		description = `Array(${ownValue(value, "length")})`;
+	} else if (className === "Error") {
+		const message = ownValue(value, "message");
+		const name = value.proto ? ownValue(value.proto, "name") : undefined;
+		description = message ? `${name ?? "Error"}: ${message}` : name ?? "Error";
+	}
+	return { type: "object", subtype, className, description, objectId };
+}
+
+function completionResult(completion, group) {
+	if (completion === null) {
+		return {
+			result: { type: "undefined" },
+			exceptionDetails: { exceptionId: 0, text: "Terminated", lineNumber: 0, columnNumber: 0 },
+		};
+	}
+	if ("throw" in completion) {
+		const exception = remote(completion.throw, group);
+		return {
+			result: exception,
+			exceptionDetails: {
+				exceptionId: 0,
+				text: "Uncaught",
+				lineNumber: 0,
+				columnNumber: 0,
+				exception,
+				executionContextId: CONTEXT_ID,
+			},
+		};
+	}
+	return { result: remote(completion.return, group) };
+}
+
+function getProperties(entry) {
+	if (entry.environment) {
+		const { environment } = entry;
+		return environment.names().map(name => ({
+			name,
+			value: remote(environment.getVariable(name), entry.group),
+			writable: true,
+			configurable: false,
+			enumerable: true,
+			isOwn: true,
+		}));
+	}
+
+	const { object } = entry;
+	const properties = object.getOwnPropertyNames().map(name => {
+		const descriptor = object.getOwnPropertyDescriptor(name);
+		const property = {
+			name,
+			configurable: descriptor.configurable,
+			enumerable: descriptor.enumerable,
+			isOwn: true,
+		};
+		if ("value" in descriptor) {
+			property.value = remote(descriptor.value, entry.group);
+			property.writable = descriptor.writable;
+		} else {
+			property.get = remote(descriptor.get, entry.group);
+			property.set = remote(descriptor.set, entry.group);
+		}
+		return property;
+	});
+	return properties;
+}
+
+// Protocol
+
+function detach() {
+	for (const breakpoint of [...breakpoints.values()]) {
+		removeBreakpoint(breakpoint);
+	}
+	clearStepping();
+	objects.clear();
+	state.enabled = false;
+	state.runtimeEnabled = false;
+	state.waiting = false;
+	state.breakpointsActive = true;
+	state.pauseOnExceptions = "none";
+	state.lastException = undefined;
+	state.paused = null;
+}
+
+function pausedFrame(callFrameId) {
+	const frame = state.paused?.frames[Number(callFrameId)];
+	if (!frame) {
+		throw new Error("Could not find call frame with given id");
+	}
+	return frame;
+}
+
+const methods = {
+	"Runtime.enable"() {
+		state.runtimeEnabled = true;
+		send("Runtime.executionContextCreated", {
+			context: { id: CONTEXT_ID, origin: "", name: "spiderfire", uniqueId: String(CONTEXT_ID) },
+		});
+	},
+	"Runtime.disable"() {
+		state.runtimeEnabled = false;
+	},
+	"Runtime.runIfWaitingForDebugger"() {
+		state.waiting = false;
+	},
+	"Runtime.evaluate"({ expression, objectGroup = "console" }) {
+		return completionResult(debuggee.executeInGlobal(expression), objectGroup);
+	},
+	"Runtime.getProperties"({ objectId }) {
+		const entry = objects.get(objectId);
+		if (!entry) {
+			throw new Error("Could not find object with given id");
+		}
+		const internalProperties = [];
+		if (entry.object?.proto) {
+			internalProperties.push({ name: "[[Prototype]]", value: remote(entry.object.proto, entry.group) });
+		}
+		return { result: getProperties(entry), internalProperties };
+	},
+	"Runtime.releaseObject"({ objectId }) {
+		objects.delete(objectId);
+	},
+	"Runtime.releaseObjectGroup"({ objectGroup }) {
+		for (const [id, entry] of objects) {
+			if (entry.group === objectGroup) {
+				objects.delete(id);
+			}
+		}
+	},
+
+	"Debugger.enable"() {
+		state.enabled = true;
+		for (const record of sources.values()) {
+			scriptParsed(record);
+		}
+		return { debuggerId: "spiderfire" };
+	},
+	"Debugger.disable"() {
+		state.enabled = false;
+		resume();
+	},
+	"Debugger.getScriptSource"({ scriptId }) {
+		const record = sources.get(scriptId);
+		if (!record) {
+			throw new Error("No script for id: " + scriptId);
+		}
+		return { scriptSource: record.source.text };
+	},
+	"Debugger.getPossibleBreakpoints"({ start, end }) {
+		const record = sources.get(start.scriptId);
+		if (!record) {
+			throw new Error("No script for id: " + start.scriptId);
+		}
+		const before = (line, column, location) =>
+			line < location.lineNumber || line === location.lineNumber && column < (location.columnNumber ?? 0);
+		const locations = [];
+		for (const script of allScripts(record)) {
+			for (const { lineNumber, columnNumber } of script.getPossibleBreakpoints()) {
+				const line = lineNumber - 1;
+				if (before(line, columnNumber, start) || end && !before(line, columnNumber, end)) {
+					continue;
+				}
+				locations.push({ scriptId: record.id, lineNumber: line, columnNumber });
+			}
+		}
+		locations.sort((a, b) => a.lineNumber - b.lineNumber || a.columnNumber - b.columnNumber);
+		return { locations };
+	},
+	"Debugger.setBreakpointByUrl"(params) {
+		let matches;
+		if (params.url !== undefined) {
+			matches = record => record.url === params.url;
+		} else if (params.urlRegex !== undefined) {
+			const regex = new RegExp(params.urlRegex);
+			matches = record => regex.test(record.url);
+		} else {
+			throw new Error("Either url or urlRegex must be specified");
+		}
+		const breakpoint = addBreakpoint(matches, params);
+		return { breakpointId: breakpoint.id, locations: breakpoint.locations };
+	},
+	"Debugger.setBreakpoint"({ location, condition }) {
+		const breakpoint = addBreakpoint(record => record.id === location.scriptId, { ...location, condition });
+		if (breakpoint.locations.length === 0) {
+			removeBreakpoint(breakpoint);
+			throw new Error("Could not resolve breakpoint");
+		}
+		return { breakpointId: breakpoint.id, actualLocation: breakpoint.locations[0] };
+	},
+	"Debugger.removeBreakpoint"({ breakpointId }) {
+		const breakpoint = breakpoints.get(breakpointId);
+		if (breakpoint) {
+			removeBreakpoint(breakpoint);
+		}
+	},
+	"Debugger.setBreakpointsActive"({ active }) {
+		state.breakpointsActive = active;
+	},
+	"Debugger.setPauseOnExceptions"({ state: pauseOnExceptions }) {
+		state.pauseOnExceptions = pauseOnExceptions;
+	},
+	"Debugger.pause"() {
+		if (state.paused === null) {
+			dbg.onEnterFrame = frame => pause(frame, "other");
+		}
+	},
+	"Debugger.resume"() {
+		resume();
+	},
+	"Debugger.stepInto"() {
+		step("into");
+	},
+	"Debugger.stepOver"() {
+		step("over");
+	},
+	"Debugger.stepOut"() {
+		step("out");
+	},
+	"Debugger.evaluateOnCallFrame"({ callFrameId, expression, objectGroup = "console" }) {
+		return completionResult(pausedFrame(callFrameId).eval(expression), objectGroup);
+	},
+};
+
+// Methods which require a paused script.
+const PAUSED_METHODS = new Set(["Debugger.stepInto", "Debugger.stepOver", "Debugger.stepOut"]);
+
+// Called with each message from the client, or `null` when it disconnects.
+function dispatch(message) {
+	if (message === null) {
+		detach();
+		return;
+	}
+
+	let request;
+	try {
+		request = JSON.parse(message);
+	} catch {
+		return;
+	}
+	const { id, method, params = {} } = request;
+
+	const handler = methods[method];
+	if (!handler) {
+		inspector.send(JSON.stringify({ id, error: { code: -32601, message: `'${method}' wasn't found` } }));
+		return;
+	}
+	if (PAUSED_METHODS.has(method) && state.paused === null) {
+		inspector.send(JSON.stringify({ id, error: { code: -32000, message: "Can only perform operation while paused." } }));
+		return;
+	}
+
+	try {
+		const result = handler(params) ?? {};
+		inspector.send(JSON.stringify({ id, result }));
+	} catch (error) {
+		inspector.send(JSON.stringify({ id, error: { code: -32000, message: String(error?.message ?? error) } }));
+	}
+}
+
+// Called by the runtime before scripts run, when it is configured to wait for a client.
+function waitForDebugger() {
+	state.waiting = true;
+	while (state.waiting) {
+		dispatch(inspector.receive());
+	}
+}
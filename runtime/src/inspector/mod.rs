@@ -0,0 +1,190 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::future::poll_fn;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::task;
+use std::task::Poll;
+
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::executor::block_on;
+use futures::StreamExt;
+use mozjs::jsapi::{JS_DefineDebuggerObject, JSAutoRealm, JSFunction, JSObject, OnNewGlobalHookOption};
+use mozjs::rust::SIMPLE_GLOBAL_CLASS;
+
+use ion::{Context, Function, Object, TracedHeap, Value};
+use ion::conversions::{FromValue, ToValue};
+use ion::flags::PropertyFlags;
+use ion::object::new_global;
+use ion::script::Script;
+
+use crate::cache::map::format_error_report;
+use crate::inspector::transport::{Incoming, listen};
+
+mod transport;
+
+const SOURCE: &str = include_str!("inspector.js");
+
+/// Default address of the inspector, which matches the default port of other runtimes so that debuggers find it.
+pub const DEFAULT_INSPECTOR_ADDRESS: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9229);
+
+/// Options of the debugger server, which exposes a subset of the Chrome DevTools Protocol.
+#[derive(Clone, Copy, Debug)]
+pub struct InspectorOptions {
+	pub address: SocketAddr,
+	/// Blocks [build](crate::RuntimeBuilder::build) until a client connects and sends
+	/// `Runtime.runIfWaitingForDebugger`, so that breakpoints can be set before scripts run.
+	pub wait: bool,
+}
+
+impl Default for InspectorOptions {
+	fn default() -> InspectorOptions {
+		InspectorOptions {
+			address: DEFAULT_INSPECTOR_ADDRESS,
+			wait: false,
+		}
+	}
+}
+
+/// Session of the inspector, whose protocol is implemented with the `Debugger` API of SpiderMonkey in a separate
+/// global, as debuggers cannot share a compartment with their debuggees.
+///
+/// Messages are dispatched from the event loop while scripts are running, and from the debugger itself while they are
+/// paused.
+pub(crate) struct InspectorSession {
+	global: TracedHeap<*mut JSObject>,
+	dispatch: TracedHeap<*mut JSFunction>,
+	incoming: Rc<RefCell<UnboundedReceiver<Incoming>>>,
+}
+
+impl InspectorSession {
+	/// Dispatches the messages received from the client. Returns `true` if any messages were dispatched.
+	pub(crate) fn poll_messages(&self, cx: &Context, wcx: &mut task::Context) -> bool {
+		let mut dispatched = false;
+		loop {
+			let poll = self.incoming.borrow_mut().poll_next_unpin(wcx);
+			match poll {
+				Poll::Ready(Some(message)) => {
+					self.dispatch(cx, message);
+					dispatched = true;
+				}
+				Poll::Ready(None) | Poll::Pending => return dispatched,
+			}
+		}
+	}
+
+	/// Blocks until a client connects and allows scripts to run.
+	pub(crate) fn wait(&self, cx: &Context) {
+		let global = self.global.root(cx);
+		let _realm = JSAutoRealm::new(cx.as_ptr(), global.get());
+		let global = Object::from(global);
+		if let Ok(Some(wait)) = global.get_as::<_, Function>(cx, "waitForDebugger", true, ()) {
+			if let Err(Some(report)) = wait.call(cx, &global, &[]) {
+				eprintln!("Uncaught exception in inspector: {}", format_error_report(cx, report));
+			}
+		}
+	}
+
+	fn dispatch(&self, cx: &Context, message: Incoming) {
+		let global = self.global.root(cx);
+		let _realm = JSAutoRealm::new(cx.as_ptr(), global.get());
+		let message = match message {
+			Incoming::Message(message) => message.as_value(cx),
+			Incoming::Disconnected => Value::null(cx),
+		};
+		let dispatch = Function::from(self.dispatch.root(cx));
+		if let Err(Some(report)) = dispatch.call(cx, &Object::from(global), &[message]) {
+			eprintln!("Uncaught exception in inspector: {}", format_error_report(cx, report));
+		}
+	}
+}
+
+/// Starts the debugger server, and attaches a debugger to the global.
+/// Returns [None] if the address could not be bound.
+pub(crate) fn start(cx: &Context, debuggee: &Object, options: InspectorOptions) -> Option<InspectorSession> {
+	let transport = match listen(options.address) {
+		Ok(transport) => transport,
+		Err(error) => {
+			eprintln!("Failed to start inspector on {}: {}", options.address, error);
+			return None;
+		}
+	};
+	eprintln!("Debugger listening on {}", transport.url());
+
+	let global = new_global(
+		cx,
+		&SIMPLE_GLOBAL_CLASS,
+		None,
+		OnNewGlobalHookOption::DontFireOnNewGlobalHook,
+		None,
+	);
+	let _realm = JSAutoRealm::new(cx.as_ptr(), global.handle().get());
+	if !unsafe { JS_DefineDebuggerObject(cx.as_ptr(), global.handle().into()) } {
+		eprintln!("Failed to define Debugger for inspector");
+		return None;
+	}
+
+	let incoming = Rc::new(RefCell::new(transport.incoming));
+	let inspector = Object::new(cx);
+	inspector.set_as(cx, "debuggee", debuggee);
+	inspector.set_as(cx, "send", &send(cx, transport.outgoing));
+	inspector.set_as(cx, "receive", &receive(cx, Rc::clone(&incoming)));
+	global.set_as(cx, "inspector", &inspector);
+
+	if let Err(report) = Script::compile_and_evaluate(cx, Path::new("inspector.js"), SOURCE) {
+		eprintln!("Failed to initialise inspector: {}", report.format(cx));
+		return None;
+	}
+	let dispatch = match global.get_as::<_, Function>(cx, "dispatch", true, ()) {
+		Ok(Some(dispatch)) => dispatch,
+		_ => return None,
+	};
+
+	Some(InspectorSession {
+		global: TracedHeap::from_local(&global),
+		dispatch: TracedHeap::new(dispatch.get()),
+		incoming,
+	})
+}
+
+/// Creates `inspector.send(message)`, which sends a message to the client.
+fn send(cx: &Context, outgoing: Sender<String>) -> Function {
+	Function::from_closure(
+		cx,
+		"send",
+		Box::new(move |args| {
+			let cx = args.cx();
+			let message = String::from_value(cx, &args.access().value(), true, ())?;
+			let _ = outgoing.send(message);
+			Ok(Value::undefined(cx))
+		}),
+		1,
+		PropertyFlags::empty(),
+	)
+}
+
+/// Creates `inspector.receive()`, which blocks until a message is received from the client while scripts are paused.
+/// Returns `null` if the client disconnected.
+fn receive(cx: &Context, incoming: Rc<RefCell<UnboundedReceiver<Incoming>>>) -> Function {
+	Function::from_closure(
+		cx,
+		"receive",
+		Box::new(move |args| {
+			let cx = args.cx();
+			let message = block_on(poll_fn(|wcx| incoming.borrow_mut().poll_next_unpin(wcx)));
+			match message {
+				Some(Incoming::Message(message)) => Ok(message.as_value(cx)),
+				Some(Incoming::Disconnected) | None => Ok(Value::null(cx)),
+			}
+		}),
+		0,
+		PropertyFlags::empty(),
+	)
+}
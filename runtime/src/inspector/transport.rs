@@ -0,0 +1,245 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use tungstenite::{Message, WebSocket};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+
+use crate::VERSION;
+
+/// Interval at which the session checks for outgoing messages while it waits for messages from the client.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Duration after which a connection which has not sent its request is closed, so that idle connections do not block
+/// other clients of the inspector.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Message received from the client of the inspector.
+pub(crate) enum Incoming {
+	Message(String),
+	Disconnected,
+}
+
+/// Endpoint of the inspector, through which a single client at a time connects over a WebSocket.
+pub(crate) struct Transport {
+	pub(crate) address: SocketAddr,
+	pub(crate) id: String,
+	pub(crate) incoming: UnboundedReceiver<Incoming>,
+	pub(crate) outgoing: Sender<String>,
+}
+
+impl Transport {
+	/// Returns the URL clients connect to, such as `ws://127.0.0.1:9229/<id>`.
+	pub(crate) fn url(&self) -> String {
+		format!("ws://{}/{}", self.address, self.id)
+	}
+}
+
+/// Binds the listener of the inspector and serves clients on a separate thread, so that messages are received while
+/// scripts are paused.
+pub(crate) fn listen(address: SocketAddr) -> io::Result<Transport> {
+	let listener = TcpListener::bind(address)?;
+	let address = listener.local_addr()?;
+	let id = session_id()?;
+
+	let (incoming, receiver) = unbounded();
+	let (outgoing, outgoing_receiver) = mpsc::channel();
+	let server = Server { address, id: id.clone(), incoming };
+	thread::Builder::new()
+		.name(String::from("inspector"))
+		.spawn(move || server.serve(listener, outgoing_receiver))?;
+
+	Ok(Transport {
+		address,
+		id,
+		incoming: receiver,
+		outgoing,
+	})
+}
+
+fn session_id() -> io::Result<String> {
+	let mut bytes = [0; 16];
+	getrandom::getrandom(&mut bytes).map_err(io::Error::other)?;
+	let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+	Ok(format!(
+		"{}-{}-{}-{}-{}",
+		&hex[0..8],
+		&hex[8..12],
+		&hex[12..16],
+		&hex[16..20],
+		&hex[20..32]
+	))
+}
+
+struct Server {
+	address: SocketAddr,
+	id: String,
+	incoming: UnboundedSender<Incoming>,
+}
+
+impl Server {
+	fn serve(self, listener: TcpListener, outgoing: Receiver<String>) {
+		for stream in listener.incoming() {
+			let Ok(stream) = stream else {
+				continue;
+			};
+			match self.accept(stream) {
+				Ok(Some(socket)) => {
+					// Messages queued for a previous client are not meant for this one.
+					while outgoing.try_recv().is_ok() {}
+					if !self.session(socket, &outgoing) {
+						return;
+					}
+				}
+				Ok(None) => {}
+				Err(error) => eprintln!("Failed to accept inspector connection: {}", error),
+			}
+		}
+	}
+
+	/// Reads the HTTP request of the connection, and either upgrades it to a WebSocket or responds to the discovery
+	/// endpoints used by Chrome DevTools, `/json/list` and `/json/version`.
+	///
+	/// Requests whose `Host` or `Origin` is not a local address are rejected, as web pages could otherwise reach the
+	/// inspector through DNS rebinding.
+	fn accept(&self, mut stream: TcpStream) -> io::Result<Option<WebSocket<TcpStream>>> {
+		stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+		let mut reader = BufReader::new(&stream);
+		let mut request_line = String::new();
+		reader.read_line(&mut request_line)?;
+		let path = request_line.split_whitespace().nth(1).unwrap_or("/").to_owned();
+
+		let mut headers = HashMap::new();
+		loop {
+			let mut line = String::new();
+			if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+				break;
+			}
+			if let Some((name, value)) = line.split_once(':') {
+				headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+			}
+		}
+
+		let host = headers.get("host").is_some_and(|host| self.is_local_host(host));
+		let origin = headers.get("origin").map_or(true, |origin| self.is_local_origin(origin));
+		if !host || !origin {
+			respond(&mut stream, "403 Forbidden", "[]")?;
+			return Ok(None);
+		}
+
+		match path.as_str() {
+			"/json" | "/json/list" => {
+				respond(&mut stream, "200 OK", &self.targets())?;
+				Ok(None)
+			}
+			"/json/version" => {
+				let version = format!(r#"{{"Browser":"spiderfire/{}","Protocol-Version":"1.3"}}"#, VERSION);
+				respond(&mut stream, "200 OK", &version)?;
+				Ok(None)
+			}
+			_ if path == format!("/{}", self.id) && headers.contains_key("sec-websocket-key") => {
+				let accept = derive_accept_key(headers["sec-websocket-key"].as_bytes());
+				write!(
+					stream,
+					"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+					accept
+				)?;
+				stream.set_read_timeout(Some(POLL_INTERVAL))?;
+				Ok(Some(WebSocket::from_raw_socket(stream, Role::Server, None)))
+			}
+			_ => {
+				respond(&mut stream, "404 Not Found", "[]")?;
+				Ok(None)
+			}
+		}
+	}
+
+	/// Checks if the `Host` of a request is `localhost`, a loopback address or the address the inspector is bound to.
+	/// If the inspector is bound to all interfaces, any IP address is accepted, but domain names are not.
+	fn is_local_host(&self, host: &str) -> bool {
+		let hostname = match host.strip_prefix('[') {
+			Some(host) => host.split(']').next().unwrap_or(host),
+			None => host.rsplit_once(':').map_or(host, |(hostname, _)| hostname),
+		};
+		if hostname.eq_ignore_ascii_case("localhost") {
+			return true;
+		}
+		match hostname.parse::<IpAddr>() {
+			Ok(ip) => ip.is_loopback() || ip == self.address.ip() || self.address.ip().is_unspecified(),
+			Err(_) => false,
+		}
+	}
+
+	/// Checks if the `Origin` of a request is Chrome DevTools, or a page served from a local host.
+	fn is_local_origin(&self, origin: &str) -> bool {
+		if origin.starts_with("devtools://") {
+			return true;
+		}
+		match origin.split_once("://") {
+			Some((_, host)) => self.is_local_host(host.split('/').next().unwrap_or(host)),
+			None => false,
+		}
+	}
+
+	fn targets(&self) -> String {
+		let host = format!("{}/{}", self.address, self.id);
+		format!(
+			r#"[{{"description":"spiderfire instance","devtoolsFrontendUrl":"devtools://devtools/bundled/js_app.html?experiments=true&v8only=true&ws={host}","id":"{id}","title":"spiderfire","type":"node","url":"file://","webSocketDebuggerUrl":"ws://{host}"}}]"#,
+			host = host,
+			id = self.id
+		)
+	}
+
+	/// Relays messages between the client and the runtime until either disconnects.
+	/// Returns `false` if the runtime was dropped.
+	fn session(&self, mut socket: WebSocket<TcpStream>, outgoing: &Receiver<String>) -> bool {
+		loop {
+			match socket.read() {
+				Ok(Message::Text(text)) => {
+					if self.incoming.unbounded_send(Incoming::Message(text)).is_err() {
+						return false;
+					}
+				}
+				Ok(Message::Close(_)) => break,
+				Ok(_) => {}
+				Err(tungstenite::Error::Io(error))
+					if matches!(error.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {}
+				Err(_) => break,
+			}
+
+			loop {
+				match outgoing.try_recv() {
+					Ok(message) => {
+						if socket.send(Message::Text(message)).is_err() {
+							return self.incoming.unbounded_send(Incoming::Disconnected).is_ok();
+						}
+					}
+					Err(TryRecvError::Empty) => break,
+					Err(TryRecvError::Disconnected) => return false,
+				}
+			}
+		}
+		self.incoming.unbounded_send(Incoming::Disconnected).is_ok()
+	}
+}
+
+fn respond(stream: &mut TcpStream, status: &str, body: &str) -> io::Result<()> {
+	write!(
+		stream,
+		"HTTP/1.1 {}\r\nContent-Type: application/json; charset=UTF-8\r\nContent-Length: {}\r\n\r\n{}",
+		status,
+		body.len(),
+		body
+	)
+}
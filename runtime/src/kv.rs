@@ -0,0 +1,72 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+
+use ion::{Context, Error, ErrorKind};
+
+use crate::ContextExt;
+
+/// Key-value store behind the `localStorage` global and the `spiderfire:kv` module, which is supplied to the runtime
+/// with [kv_store](crate::RuntimeBuilder::kv_store).
+pub trait KvStore {
+	fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+	/// Stores the value, replacing the value already stored for the key.
+	fn put(&self, key: &str, value: Vec<u8>) -> io::Result<()>;
+
+	/// Deletes the value. Returns `false` if there was none.
+	fn delete(&self, key: &str) -> io::Result<bool>;
+
+	/// Returns the keys in the order they were first stored.
+	fn keys(&self) -> io::Result<Vec<String>>;
+
+	fn clear(&self) -> io::Result<()>;
+}
+
+/// In-memory [KvStore], whose values are lost when the runtime is dropped.
+#[derive(Default)]
+pub struct MemoryKvStore {
+	values: RefCell<IndexMap<String, Vec<u8>>>,
+}
+
+impl KvStore for MemoryKvStore {
+	fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+		Ok(self.values.borrow().get(key).cloned())
+	}
+
+	fn put(&self, key: &str, value: Vec<u8>) -> io::Result<()> {
+		self.values.borrow_mut().insert(String::from(key), value);
+		Ok(())
+	}
+
+	fn delete(&self, key: &str) -> io::Result<bool> {
+		Ok(self.values.borrow_mut().shift_remove(key).is_some())
+	}
+
+	fn keys(&self) -> io::Result<Vec<String>> {
+		Ok(self.values.borrow().keys().cloned().collect())
+	}
+
+	fn clear(&self) -> io::Result<()> {
+		self.values.borrow_mut().clear();
+		Ok(())
+	}
+}
+
+/// Returns the [KvStore] of the runtime, which defaults to a [MemoryKvStore].
+pub fn kv_store(cx: &Context) -> Rc<dyn KvStore> {
+	let private = unsafe { cx.get_private() };
+	Rc::clone(private.kv_store.get_or_insert_with(|| Rc::new(MemoryKvStore::default())))
+}
+
+pub fn kv_error(error: io::Error) -> Error {
+	Error::new(format!("Key-value store failed: {}", error), ErrorKind::Normal)
+}
@@ -9,17 +9,27 @@
 #[macro_use]
 extern crate ion;
 
-extern crate hyper_multipart_rfc7578 as multipart;
-
 pub use crate::runtime::*;
 
 pub mod cache;
 pub mod config;
 pub mod event_loop;
+pub mod gc;
 pub mod globals;
+pub mod handle;
+pub mod heap_census;
+#[cfg(feature = "inspector")]
+pub mod inspector;
+pub mod kv;
+pub mod limits;
+pub mod mime_type;
 pub mod module;
+pub mod profiler;
 pub mod promise;
 mod runtime;
+pub mod security;
+#[cfg(feature = "fetch")]
+pub mod server;
 pub mod typescript;
 pub mod wasi_polyfills;
 
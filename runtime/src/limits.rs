@@ -0,0 +1,189 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use mozjs::jsapi::{JS_SetGCParameter, JSGCParamKey};
+
+use ion::{Context, Error, ErrorKind, ErrorReport, Exception};
+use ion::module::ModuleError;
+
+use crate::ContextExt;
+use crate::handle;
+use crate::profiler::InterruptHandle;
+
+/// Limits on the resources used by scripts, which protect embedders from runaway scripts.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResourceLimits {
+	/// Maximum size of the heap in bytes, beyond which allocations fail with an out of memory error.
+	pub max_heap_size: Option<u32>,
+	/// Maximum time a single task of the event loop, such as a timer callback or a promise reaction, runs for before it
+	/// is terminated.
+	pub task_timeout: Option<Duration>,
+	/// Maximum time the evaluation of a module with [evaluate_module](crate::Runtime::evaluate_module) runs for,
+	/// including the evaluation of its imports. Code which runs after a top-level `await` is limited by
+	/// [task_timeout](ResourceLimits::task_timeout) instead.
+	pub module_timeout: Option<Duration>,
+}
+
+impl ResourceLimits {
+	fn has_timeouts(&self) -> bool {
+		self.task_timeout.is_some() || self.module_timeout.is_some()
+	}
+}
+
+#[derive(Default)]
+struct WatchdogState {
+	deadline: Option<Instant>,
+	expired: bool,
+	stopped: bool,
+}
+
+/// Watchdog, whose thread requests an interrupt of the context once the deadline of the running task passes, during
+/// which the task is terminated. Deadlines are not nested, so only the outermost deadline applies.
+pub(crate) struct Watchdog {
+	limits: ResourceLimits,
+	state: Arc<(Mutex<WatchdogState>, Condvar)>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+	fn start(cx: &Context, limits: ResourceLimits) -> Watchdog {
+		let state = Arc::new((Mutex::new(WatchdogState::default()), Condvar::new()));
+
+		let handle = InterruptHandle::new(cx);
+		let thread = {
+			let state = Arc::clone(&state);
+			thread::Builder::new().name(String::from("watchdog")).spawn(move || {
+				let (lock, condvar) = &*state;
+				let mut state = lock.lock().unwrap();
+				while !state.stopped {
+					state = match state.deadline {
+						Some(deadline) => {
+							let now = Instant::now();
+							if now >= deadline {
+								state.deadline = None;
+								state.expired = true;
+								handle.request();
+								state
+							} else {
+								condvar.wait_timeout(state, deadline - now).unwrap().0
+							}
+						}
+						None => condvar.wait(state).unwrap(),
+					};
+				}
+			})
+		};
+
+		Watchdog { limits, state, thread: thread.ok() }
+	}
+
+	/// Sets the deadline of the running task. Returns `false` if a deadline is already set.
+	fn arm(&self, timeout: Duration) -> bool {
+		let (lock, condvar) = &*self.state;
+		let mut state = lock.lock().unwrap();
+		if state.deadline.is_some() || state.expired {
+			return false;
+		}
+		state.deadline = Some(Instant::now() + timeout);
+		condvar.notify_one();
+		true
+	}
+
+	/// Clears the deadline of the running task. Returns `true` if it passed.
+	fn disarm(&self) -> bool {
+		let mut state = self.state.0.lock().unwrap();
+		state.deadline = None;
+		std::mem::take(&mut state.expired)
+	}
+
+	fn is_expired(&self) -> bool {
+		self.state.0.lock().unwrap().expired
+	}
+}
+
+impl Drop for Watchdog {
+	fn drop(&mut self) {
+		let (lock, condvar) = &*self.state;
+		lock.lock().unwrap().stopped = true;
+		condvar.notify_one();
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+/// Applies the limits to the context. The watchdog is only started if any timeouts are set.
+pub(crate) fn apply_limits(cx: &Context, limits: ResourceLimits) -> Option<Watchdog> {
+	if let Some(bytes) = limits.max_heap_size {
+		unsafe { JS_SetGCParameter(cx.as_ptr(), JSGCParamKey::JSGC_MAX_BYTES, bytes) };
+	}
+	limits.has_timeouts().then(|| Watchdog::start(cx, limits))
+}
+
+/// Returns `true` if the deadline of the running task passed, so that the interrupt callback terminates it.
+pub(crate) fn is_expired(cx: &Context) -> bool {
+	unsafe { cx.get_private() }.watchdog.as_ref().is_some_and(Watchdog::is_expired)
+}
+
+fn resource_limits(cx: &Context) -> ResourceLimits {
+	unsafe { cx.get_private() }
+		.watchdog
+		.as_ref()
+		.map(|watchdog| watchdog.limits)
+		.unwrap_or_default()
+}
+
+/// Runs a callback with a deadline, after which the scripts it runs are terminated.
+/// Returns `true` alongside the result of the callback if the deadline passed.
+fn with_deadline<T, F: FnOnce() -> T>(cx: &Context, timeout: Option<Duration>, callback: F) -> (T, bool) {
+	let watchdog = unsafe { cx.get_private() }.watchdog.as_ref();
+	let armed = match (watchdog, timeout) {
+		(Some(watchdog), Some(timeout)) => watchdog.arm(timeout),
+		_ => false,
+	};
+	let result = callback();
+	let expired = armed && unsafe { cx.get_private() }.watchdog.as_ref().is_some_and(Watchdog::disarm);
+	(result, expired)
+}
+
+fn timeout_report(what: &str, timeout: Duration) -> ErrorReport {
+	let message = format!("{} timed out after {}ms", what, timeout.as_millis());
+	ErrorReport::from(Exception::Error(Error::new(message, ErrorKind::Normal)), None)
+}
+
+/// Runs a task of the event loop, which is terminated if it exceeds the [task timeout](ResourceLimits::task_timeout).
+/// Tasks stopped by a [RuntimeHandle](crate::handle::RuntimeHandle) are reported as errors as well.
+pub(crate) fn limit_task<F>(cx: &Context, callback: F) -> Result<(), Option<ErrorReport>>
+where
+	F: FnOnce() -> Result<(), Option<ErrorReport>>,
+{
+	let timeout = resource_limits(cx).task_timeout;
+	match with_deadline(cx, timeout, callback) {
+		(Err(_), true) => Err(Some(timeout_report("Task", timeout.unwrap()))),
+		(Err(None), false) => Err(handle::take_interruption(cx)),
+		(result, _) => result,
+	}
+}
+
+/// Runs the evaluation of a module, which is terminated if it exceeds the
+/// [module timeout](ResourceLimits::module_timeout).
+pub(crate) fn limit_module<T, F>(cx: &Context, callback: F) -> Result<T, ModuleError>
+where
+	F: FnOnce() -> Result<T, ModuleError>,
+{
+	let timeout = resource_limits(cx).module_timeout;
+	match with_deadline(cx, timeout, callback) {
+		(Err(mut error), true) => {
+			error.report = timeout_report("Module evaluation", timeout.unwrap());
+			Err(error)
+		}
+		(result, _) => result,
+	}
+}
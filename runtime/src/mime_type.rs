@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Returns the MIME type commonly associated with a file extension, which is matched case-insensitively.
+pub fn from_extension(extension: &str) -> Option<&'static str> {
+	let mime = match extension.to_ascii_lowercase().as_str() {
+		"html" | "htm" => "text/html;charset=UTF-8",
+		"css" => "text/css;charset=UTF-8",
+		"js" | "mjs" | "cjs" => "text/javascript;charset=UTF-8",
+		"ts" | "mts" | "cts" => "text/typescript;charset=UTF-8",
+		"json" | "map" => "application/json",
+		"txt" | "log" => "text/plain;charset=UTF-8",
+		"md" => "text/markdown;charset=UTF-8",
+		"csv" => "text/csv;charset=UTF-8",
+		"xml" => "application/xml",
+		"svg" => "image/svg+xml",
+		"png" => "image/png",
+		"jpg" | "jpeg" => "image/jpeg",
+		"gif" => "image/gif",
+		"webp" => "image/webp",
+		"avif" => "image/avif",
+		"ico" => "image/x-icon",
+		"bmp" => "image/bmp",
+		"woff" => "font/woff",
+		"woff2" => "font/woff2",
+		"ttf" => "font/ttf",
+		"otf" => "font/otf",
+		"wasm" => "application/wasm",
+		"pdf" => "application/pdf",
+		"zip" => "application/zip",
+		"gz" => "application/gzip",
+		"tar" => "application/x-tar",
+		"mp3" => "audio/mpeg",
+		"wav" => "audio/wav",
+		"ogg" => "audio/ogg",
+		"mp4" => "video/mp4",
+		"webm" => "video/webm",
+		_ => return None,
+	};
+	Some(mime)
+}
+
+/// Returns the MIME type for a path from its extension, or `application/octet-stream` if it is unknown.
+pub fn from_path(path: &Path) -> &'static str {
+	path.extension()
+		.and_then(|extension| extension.to_str())
+		.and_then(from_extension)
+		.unwrap_or(OCTET_STREAM)
+}
@@ -0,0 +1,294 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs::{File, read_to_string};
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::module::BUILTIN_SCHEME;
+
+/// Marks the end of an executable with an embedded [Bundle].
+const BUNDLE_MAGIC: &[u8; 8] = b"SFBUNDLE";
+const TRAILER_LEN: u64 = BUNDLE_MAGIC.len() as u64 + 8;
+
+const FLAG_SCRIPT: u8 = 1 << 0;
+const FLAG_ALLOW_ENV: u8 = 1 << 1;
+const FLAG_ALLOW_NET: u8 = 1 << 2;
+
+/// Sources of an entry module and the modules it statically imports, which can be embedded into an executable.
+///
+/// Modules are keyed by their absolute paths when the bundle was collected, and are read from the bundle instead of
+/// disk when it is given to the [Loader](crate::module::Loader). Imports are found by scanning for string literal
+/// specifiers, so dynamic imports of computed specifiers are not bundled.
+#[derive(Clone, Debug, Default)]
+pub struct Bundle {
+	pub entry: PathBuf,
+	/// Evaluates the entry as a script instead of a module.
+	pub script: bool,
+	pub allow_env: bool,
+	pub allow_net: bool,
+	modules: BTreeMap<PathBuf, String>,
+}
+
+impl Bundle {
+	/// Collects the entry and the modules it imports, recursively.
+	pub fn collect(entry: &Path) -> io::Result<Bundle> {
+		let entry = crate::wasi_polyfills::canonicalize(entry)?;
+		let mut bundle = Bundle {
+			entry: entry.clone(),
+			..Bundle::default()
+		};
+
+		let mut pending = vec![entry];
+		while let Some(path) = pending.pop() {
+			if bundle.modules.contains_key(&path) {
+				continue;
+			}
+			let source = read_to_string(&path)?;
+			for specifier in import_specifiers(&source) {
+				if let Some(import) = resolve_on_disk(&path, &specifier) {
+					pending.push(import);
+				}
+			}
+			bundle.modules.insert(path, source);
+		}
+
+		Ok(bundle)
+	}
+
+	pub fn script(self, script: bool) -> Bundle {
+		Bundle { script, ..self }
+	}
+
+	pub fn allow_env(self, allow_env: bool) -> Bundle {
+		Bundle { allow_env, ..self }
+	}
+
+	pub fn allow_net(self, allow_net: bool) -> Bundle {
+		Bundle { allow_net, ..self }
+	}
+
+	/// Returns the source of the module at the given path, or of the path with a `.js` extension appended.
+	pub fn resolve(&self, path: &Path) -> Option<(PathBuf, &str)> {
+		let path = normalize(path);
+		if let Some(source) = self.modules.get(&path) {
+			return Some((path, source));
+		}
+		if path.extension() == Some(OsStr::new("js")) {
+			return None;
+		}
+
+		let mut file_name = path.file_name()?.to_owned();
+		file_name.push(".js");
+		let path = path.with_file_name(file_name);
+		self.modules.get(&path).map(|source| (path, source.as_str()))
+	}
+
+	pub fn entry_source(&self) -> Option<&str> {
+		self.modules.get(&self.entry).map(String::as_str)
+	}
+
+	pub fn modules(&self) -> impl Iterator<Item = &Path> {
+		self.modules.keys().map(PathBuf::as_path)
+	}
+
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		let mut flags = 0;
+		for (enabled, flag) in [
+			(self.script, FLAG_SCRIPT),
+			(self.allow_env, FLAG_ALLOW_ENV),
+			(self.allow_net, FLAG_ALLOW_NET),
+		] {
+			if enabled {
+				flags |= flag;
+			}
+		}
+		bytes.push(flags);
+		write_str(&mut bytes, &self.entry.to_string_lossy());
+
+		bytes.extend_from_slice(&(self.modules.len() as u32).to_le_bytes());
+		for (path, source) in &self.modules {
+			write_str(&mut bytes, &path.to_string_lossy());
+			write_str(&mut bytes, source);
+		}
+		bytes
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> io::Result<Bundle> {
+		let mut reader = BundleReader { bytes };
+		let flags = reader.take(1)?[0];
+		let entry = PathBuf::from(reader.read_str()?);
+
+		let count = reader.read_u32()?;
+		let mut modules = BTreeMap::new();
+		for _ in 0..count {
+			let path = PathBuf::from(reader.read_str()?);
+			modules.insert(path, reader.read_str()?);
+		}
+
+		Ok(Bundle {
+			entry,
+			script: flags & FLAG_SCRIPT != 0,
+			allow_env: flags & FLAG_ALLOW_ENV != 0,
+			allow_net: flags & FLAG_ALLOW_NET != 0,
+			modules,
+		})
+	}
+
+	/// Writes a copy of the executable with the bundle appended to it.
+	pub fn embed(&self, executable: &Path, output: &Path) -> io::Result<()> {
+		let mut binary = Vec::new();
+		File::open(executable)?.read_to_end(&mut binary)?;
+		if let Some(len) = embedded_len(&binary) {
+			// Replace the bundle of an executable which was itself compiled.
+			binary.truncate(binary.len() - (len + TRAILER_LEN) as usize);
+		}
+
+		let payload = self.to_bytes();
+		let mut file = File::create(output)?;
+		file.write_all(&binary)?;
+		file.write_all(&payload)?;
+		file.write_all(&(payload.len() as u64).to_le_bytes())?;
+		file.write_all(BUNDLE_MAGIC)?;
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			let permissions = File::open(executable)?.metadata()?.permissions();
+			file.set_permissions(std::fs::Permissions::from_mode(permissions.mode() | 0o111))?;
+		}
+		Ok(())
+	}
+
+	/// Reads the bundle embedded into an executable, if there is one.
+	pub fn from_executable(executable: &Path) -> io::Result<Option<Bundle>> {
+		let mut file = File::open(executable)?;
+		let size = file.metadata()?.len();
+		if size < TRAILER_LEN {
+			return Ok(None);
+		}
+
+		let mut trailer = [0; TRAILER_LEN as usize];
+		file.seek(SeekFrom::Start(size - TRAILER_LEN))?;
+		file.read_exact(&mut trailer)?;
+		let Some(len) = embedded_len(&trailer) else {
+			return Ok(None);
+		};
+		if len > size - TRAILER_LEN {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				"Embedded bundle is truncated",
+			));
+		}
+
+		let mut payload = vec![0; len as usize];
+		file.seek(SeekFrom::Start(size - TRAILER_LEN - len))?;
+		file.read_exact(&mut payload)?;
+		Bundle::from_bytes(&payload).map(Some)
+	}
+}
+
+/// Returns the length of the bundle embedded before the trailer at the end of the bytes.
+fn embedded_len(bytes: &[u8]) -> Option<u64> {
+	let trailer = bytes.get(bytes.len().checked_sub(TRAILER_LEN as usize)?..)?;
+	let (len, magic) = trailer.split_at(8);
+	(magic == BUNDLE_MAGIC).then(|| u64::from_le_bytes(len.try_into().unwrap()))
+}
+
+fn write_str(bytes: &mut Vec<u8>, str: &str) {
+	bytes.extend_from_slice(&(str.len() as u32).to_le_bytes());
+	bytes.extend_from_slice(str.as_bytes());
+}
+
+struct BundleReader<'b> {
+	bytes: &'b [u8],
+}
+
+impl<'b> BundleReader<'b> {
+	fn take(&mut self, len: usize) -> io::Result<&'b [u8]> {
+		if self.bytes.len() < len {
+			return Err(io::Error::new(
+				io::ErrorKind::UnexpectedEof,
+				"Embedded bundle is truncated",
+			));
+		}
+		let (taken, rest) = self.bytes.split_at(len);
+		self.bytes = rest;
+		Ok(taken)
+	}
+
+	fn read_u32(&mut self) -> io::Result<u32> {
+		Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+	}
+
+	fn read_str(&mut self) -> io::Result<String> {
+		let len = self.read_u32()? as usize;
+		String::from_utf8(self.take(len)?.to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+	}
+}
+
+/// Normalises `.` and `..` components of a path without accessing the file system.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				if !normalized.pop() {
+					normalized.push(component);
+				}
+			}
+			component => normalized.push(component),
+		}
+	}
+	normalized
+}
+
+fn resolve_on_disk(referencing: &Path, specifier: &str) -> Option<PathBuf> {
+	if specifier.starts_with(BUILTIN_SCHEME) || !(specifier.starts_with('.') || specifier.starts_with('/')) {
+		return None;
+	}
+
+	let path = referencing.parent()?.join(specifier);
+	crate::wasi_polyfills::canonicalize(&path).ok().or_else(|| {
+		let mut file_name = path.file_name()?.to_owned();
+		file_name.push(".js");
+		crate::wasi_polyfills::canonicalize(path.with_file_name(file_name)).ok()
+	})
+}
+
+/// Finds the string literal specifiers of `import` and `export ... from` statements, and of dynamic imports.
+fn import_specifiers(source: &str) -> Vec<String> {
+	let mut specifiers = Vec::new();
+	let mut rest = source;
+	while let Some(index) = rest.find(|c: char| c == 'i' || c == 'f') {
+		let preceded_by_identifier = source[..source.len() - rest.len() + index]
+			.chars()
+			.next_back()
+			.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.');
+		rest = &rest[index..];
+
+		let keyword = ["import", "from"].into_iter().find(|keyword| rest.starts_with(keyword));
+		let Some(keyword) = keyword.filter(|_| !preceded_by_identifier) else {
+			rest = &rest[1..];
+			continue;
+		};
+		rest = &rest[keyword.len()..];
+
+		let after = rest.trim_start();
+		let after = after.strip_prefix('(').map(str::trim_start).unwrap_or(after);
+		if let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') {
+			if let Some(end) = after[1..].find(quote) {
+				specifiers.push(String::from(&after[1..end + 1]));
+			}
+		}
+	}
+	specifiers
+}
@@ -0,0 +1,117 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Cycle of imports between modules, where each module imports the next, and the last module imports the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleCycle {
+	pub modules: Vec<PathBuf>,
+}
+
+impl ModuleCycle {
+	/// Checks if the cycle contains the same modules in the same order, regardless of which module it starts from.
+	fn is_rotation_of(&self, other: &ModuleCycle) -> bool {
+		let len = self.modules.len();
+		len == other.modules.len()
+			&& (0..len).any(|offset| (0..len).all(|i| self.modules[(i + offset) % len] == other.modules[i]))
+	}
+}
+
+impl Display for ModuleCycle {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		for module in &self.modules {
+			write!(f, "{} → ", module.display())?;
+		}
+		match self.modules.first() {
+			Some(first) => write!(f, "{}", first.display()),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Handle to the imports between the modules resolved by the [Loader](super::Loader), which remains usable after the
+/// loader is given to the runtime.
+#[derive(Clone, Default)]
+pub struct ModuleGraph {
+	inner: Rc<RefCell<ModuleGraphInner>>,
+}
+
+#[derive(Default)]
+struct ModuleGraphInner {
+	imports: HashMap<PathBuf, Vec<PathBuf>>,
+	cycles: Vec<ModuleCycle>,
+}
+
+impl ModuleGraph {
+	/// Returns the modules imported by a module, in the order they were resolved.
+	pub fn imports(&self, module: &Path) -> Vec<PathBuf> {
+		self.inner.borrow().imports.get(module).cloned().unwrap_or_default()
+	}
+
+	/// Returns the import cycles which have been detected, in the order they were detected.
+	pub fn cycles(&self) -> Vec<ModuleCycle> {
+		self.inner.borrow().cycles.clone()
+	}
+
+	/// Records an import of `imported` by `importer`.
+	/// Returns the cycle completed by the import, if it completes one which has not been detected before.
+	pub(crate) fn record_import(&self, importer: &Path, imported: &Path) -> Option<ModuleCycle> {
+		let mut inner = self.inner.borrow_mut();
+		let imports = inner.imports.entry(importer.to_path_buf()).or_default();
+		if imports.iter().any(|import| import == imported) {
+			return None;
+		}
+		imports.push(imported.to_path_buf());
+
+		let mut modules = vec![importer.to_path_buf()];
+		modules.extend(inner.find_path(imported, importer)?);
+		modules.pop();
+		let cycle = ModuleCycle { modules };
+
+		if inner.cycles.iter().any(|detected| detected.is_rotation_of(&cycle)) {
+			None
+		} else {
+			inner.cycles.push(cycle.clone());
+			Some(cycle)
+		}
+	}
+}
+
+impl ModuleGraphInner {
+	/// Finds the shortest chain of imports from `from` to `to`, including both modules.
+	fn find_path(&self, from: &Path, to: &Path) -> Option<Vec<PathBuf>> {
+		let mut previous: HashMap<&Path, &Path> = HashMap::new();
+		let mut visited = HashSet::from([from]);
+		let mut queue = VecDeque::from([from]);
+
+		while let Some(module) = queue.pop_front() {
+			if module == to {
+				let mut path = vec![module.to_path_buf()];
+				let mut current = module;
+				while let Some(&parent) = previous.get(current) {
+					path.push(parent.to_path_buf());
+					current = parent;
+				}
+				path.reverse();
+				return Some(path);
+			}
+
+			for import in self.imports.get(module).into_iter().flatten() {
+				let import = import.as_path();
+				if visited.insert(import) {
+					previous.insert(import, module);
+					queue.push_back(import);
+				}
+			}
+		}
+		None
+	}
+}
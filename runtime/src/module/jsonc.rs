@@ -0,0 +1,102 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use ion::{Error, ErrorKind, Result};
+
+/// Converts lenient JSON, which allows comments and trailing commas, to strict JSON.
+///
+/// Whitespace and comments are removed, so the result is on a single line.
+/// Other syntax errors are left in place, to be reported when the strict JSON is parsed.
+pub fn strip_jsonc(text: &str) -> Result<String> {
+	let mut json = String::with_capacity(text.len());
+	let mut chars = text.chars().peekable();
+	let mut pending_comma = false;
+
+	while let Some(char) = chars.next() {
+		match char {
+			'/' if chars.peek() == Some(&'/') => {
+				for char in chars.by_ref() {
+					if char == '\n' {
+						break;
+					}
+				}
+			}
+			'/' if chars.peek() == Some(&'*') => skip_block_comment(&mut chars)?,
+			char if char.is_whitespace() => {}
+			',' => {
+				if pending_comma {
+					json.push(',');
+				}
+				pending_comma = true;
+			}
+			char => {
+				if pending_comma && char != ']' && char != '}' {
+					json.push(',');
+				}
+				pending_comma = false;
+
+				json.push(char);
+				if char == '"' {
+					copy_string(&mut chars, &mut json)?;
+				}
+			}
+		}
+	}
+	if pending_comma {
+		json.push(',');
+	}
+	Ok(json)
+}
+
+fn skip_block_comment(chars: &mut Peekable<Chars>) -> Result<()> {
+	chars.next();
+	while let Some(char) = chars.next() {
+		if char == '*' && chars.peek() == Some(&'/') {
+			chars.next();
+			return Ok(());
+		}
+	}
+	Err(Error::new("Unterminated block comment in JSON", ErrorKind::Syntax))
+}
+
+fn copy_string(chars: &mut Peekable<Chars>, json: &mut String) -> Result<()> {
+	while let Some(char) = chars.next() {
+		json.push(char);
+		match char {
+			'\\' => {
+				if let Some(escaped) = chars.next() {
+					json.push(escaped);
+				}
+			}
+			'"' => return Ok(()),
+			_ => {}
+		}
+	}
+	Err(Error::new("Unterminated string in JSON", ErrorKind::Syntax))
+}
+
+/// Creates the source of a module which exports the value of lenient JSON as its default export.
+pub fn jsonc_module_source(text: &str) -> Result<String> {
+	let json = strip_jsonc(text)?;
+	let mut source = String::with_capacity(json.len() + 32);
+	source.push_str("export default JSON.parse(\"");
+	for char in json.chars() {
+		match char {
+			'"' => source.push_str("\\\""),
+			'\\' => source.push_str("\\\\"),
+			'\n' => source.push_str("\\n"),
+			'\r' => source.push_str("\\r"),
+			'\u{2028}' => source.push_str("\\u2028"),
+			'\u{2029}' => source.push_str("\\u2029"),
+			char => source.push(char),
+		}
+	}
+	source.push_str("\");\n");
+	Ok(source)
+}
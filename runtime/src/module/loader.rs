@@ -8,6 +8,8 @@ use std::collections::hash_map::{Entry, HashMap};
 use std::ffi::OsStr;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Instant;
 
 use mozjs::jsapi::JSObject;
 use url::Url;
@@ -18,10 +20,78 @@ use ion::module::{Module, ModuleData, ModuleLoader, ModuleRequest};
 use crate::cache::locate_in_cache;
 use crate::cache::map::save_sourcemap;
 use crate::config::Config;
+use crate::module::bundle::normalize;
+use crate::module::{jsonc_module_source, Bundle, LoaderEvent, LoaderProgress, LoaderStats, ModuleGraph, ModuleTiming};
+
+/// Scheme which can prefix the specifiers of built-in modules.
+pub const BUILTIN_SCHEME: &str = "spiderfire:";
 
 #[derive(Default)]
 pub struct Loader {
 	registry: HashMap<String, TracedHeap<*mut JSObject>>,
+	progress: LoaderProgress,
+	graph: ModuleGraph,
+	deny_cycles: bool,
+	bundle: Option<Rc<Bundle>>,
+}
+
+impl Loader {
+	/// Reads modules from the [Bundle] instead of disk, such as when running a compiled executable.
+	pub fn bundle(self, bundle: Rc<Bundle>) -> Loader {
+		Loader { bundle: Some(bundle), ..self }
+	}
+
+	/// Sets the callback which receives each [LoaderEvent], such as to display startup progress.
+	pub fn on_event(self, callback: impl Fn(&LoaderEvent) + 'static) -> Loader {
+		self.progress.set_callback(callback);
+		self
+	}
+
+	/// Returns a handle to the progress of the loader, which can be kept after the loader is given to the runtime.
+	pub fn progress(&self) -> LoaderProgress {
+		self.progress.clone()
+	}
+
+	pub fn stats(&self) -> LoaderStats {
+		self.progress.stats()
+	}
+
+	/// Fails to instantiate modules which import each other in a cycle, instead of only reporting the cycle with
+	/// [LoaderEvent::CycleDetected]. The error message contains the modules in the cycle.
+	pub fn deny_cycles(self) -> Loader {
+		Loader { deny_cycles: true, ..self }
+	}
+
+	/// Returns a handle to the imports between modules, which can be kept after the loader is given to the runtime.
+	pub fn graph(&self) -> ModuleGraph {
+		self.graph.clone()
+	}
+
+	/// Records the import of a module, and reports the import cycle it completes, if any.
+	fn record_import(&self, referencing_module: Option<&ModuleData>, path: &Path) -> ion::Result<()> {
+		let Some(importer) = referencing_module.and_then(|data| data.path.as_ref()) else {
+			return Ok(());
+		};
+		let importer = match &self.bundle {
+			Some(_) => normalize(Path::new(importer)),
+			None => canonicalize_path(importer).unwrap_or_else(|_| PathBuf::from(importer)),
+		};
+
+		match self.graph.record_import(&importer, path) {
+			Some(cycle) => {
+				self.progress.emit(&LoaderEvent::CycleDetected { cycle: &cycle });
+				if self.deny_cycles {
+					Err(Error::new(
+						format!("Circular import detected: {}", cycle),
+						ion::ErrorKind::Normal,
+					))
+				} else {
+					Ok(())
+				}
+			}
+			None => Ok(()),
+		}
+	}
 }
 
 impl ModuleLoader for Loader {
@@ -29,11 +99,16 @@ impl ModuleLoader for Loader {
 		&mut self, cx: &'cx Context, referencing_module: Option<&ModuleData>, request: &ModuleRequest,
 	) -> ion::Result<Module<'cx>> {
 		let specifier = request.specifier(cx).to_owned(cx)?;
+		let start = Instant::now();
+		self.progress.emit(&LoaderEvent::ResolveStart { specifier: &specifier });
 
 		// Do a registry look-up before canonicalizing paths, since the
 		// canonicalization process is incompatible with built-in modules
 		// that don't have an address on disk.
-		if let Some(heap) = self.registry.get(&specifier) {
+		// Built-in modules can also be imported with the `spiderfire:` scheme, such as `spiderfire:fs`.
+		let name = specifier.strip_prefix(BUILTIN_SCHEME).unwrap_or(&specifier);
+		if let Some(heap) = self.registry.get(name) {
+			self.progress.record_hit();
 			return Ok(Module::from_local(heap.root(cx)));
 		}
 
@@ -46,40 +121,48 @@ impl ModuleLoader for Loader {
 			Path::new(&specifier).to_path_buf()
 		};
 
-		let path = canonicalize_path(&path).or_else(|e| {
-			if path.extension() == Some(OsStr::new("js")) {
-				return Err(e);
+		let (path, bundled) = match &self.bundle {
+			Some(bundle) => {
+				let (path, source) = bundle.resolve(&path).ok_or_else(|| {
+					Error::new(
+						format!("Module `{}` was not found in the bundle", specifier),
+						ion::ErrorKind::Normal,
+					)
+				})?;
+				(path, Some(String::from(source)))
 			}
+			None => (resolve_path(&path)?, None),
+		};
 
-			// Try appending a .js extension
-			let Some(file_name) = path.file_name() else {
-				return Err(e);
-			};
-			let Some(parent) = path.parent() else {
-				return Err(e);
-			};
-
-			let mut file_name = file_name.to_owned();
-			file_name.push(".js");
-
-			canonicalize_path(&parent.join(file_name))
-		})?;
+		self.record_import(referencing_module, &path)?;
 
 		let str = String::from(path.to_str().unwrap());
 		match self.registry.get(&str) {
-			Some(heap) => Ok(Module::from_local(heap.root(cx))),
+			Some(heap) => {
+				self.progress.record_hit();
+				Ok(Module::from_local(heap.root(cx)))
+			}
 			None => {
-				let script = read_to_string(&path).map_err(|e| {
-					Error::new(
-						format!(
-							"Unable to read module `{}` from `{}` due to {:?}",
-							specifier,
-							path.display(),
-							e
-						),
-						None,
-					)
-				})?;
+				let script = match bundled {
+					Some(script) => script,
+					None => read_to_string(&path).map_err(|e| {
+						Error::new(
+							format!(
+								"Unable to read module `{}` from `{}` due to {:?}",
+								specifier,
+								path.display(),
+								e
+							),
+							None,
+						)
+					})?,
+				};
+				// Lenient JSON files are imported as modules with the parsed value as the default export.
+				let script = if path.extension() == Some(OsStr::new("jsonc")) {
+					jsonc_module_source(&script)?
+				} else {
+					script
+				};
 				let is_typescript = Config::global().typescript && path.extension() == Some(OsStr::new("ts"));
 				let (script, sourcemap) = is_typescript
 					.then(|| locate_in_cache(&path, &script))
@@ -90,7 +173,24 @@ impl ModuleLoader for Loader {
 					save_sourcemap(&path, sourcemap);
 				}
 
+				let resolve = start.elapsed();
+				self.progress.emit(&LoaderEvent::CompileStart { specifier: &specifier, path: &path });
+				let compile_start = Instant::now();
 				let module = Module::compile(cx, &specifier, Some(path.as_path()), &script);
+				let compile = compile_start.elapsed();
+				self.progress.emit(&LoaderEvent::CompileEnd {
+					specifier: &specifier,
+					path: &path,
+					duration: compile,
+					success: module.is_ok(),
+				});
+				self.progress.record_module(ModuleTiming {
+					specifier: specifier.clone(),
+					path: Some(path.clone()),
+					resolve: Some(resolve),
+					compile: Some(compile),
+					evaluate: None,
+				});
 
 				if let Ok(module) = module {
 					let request = ModuleRequest::new(cx, path.to_str().unwrap());
@@ -117,7 +217,10 @@ impl ModuleLoader for Loader {
 	fn metadata(&self, cx: &Context, data: Option<&ModuleData>, meta: &mut Object) -> ion::Result<()> {
 		if let Some(data) = data {
 			if let Some(path) = data.path.as_ref() {
-				let path = canonicalize_path(path)?;
+				let path = match &self.bundle {
+					Some(_) => normalize(Path::new(path)),
+					None => canonicalize_path(path)?,
+				};
 				let url = Url::from_file_path(path).unwrap();
 				if !meta.set_as(cx, "url", url.as_str()) {
 					return Err(Error::none());
@@ -129,6 +232,28 @@ impl ModuleLoader for Loader {
 	}
 }
 
+/// Canonicalises the path of a module, appending a `.js` extension if the path does not exist.
+fn resolve_path(path: &Path) -> ion::Result<PathBuf> {
+	canonicalize_path(path).or_else(|e| {
+		if path.extension() == Some(OsStr::new("js")) {
+			return Err(e);
+		}
+
+		// Try appending a .js extension
+		let Some(file_name) = path.file_name() else {
+			return Err(e);
+		};
+		let Some(parent) = path.parent() else {
+			return Err(e);
+		};
+
+		let mut file_name = file_name.to_owned();
+		file_name.push(".js");
+
+		canonicalize_path(&parent.join(file_name))
+	})
+}
+
 fn canonicalize_path(path: impl AsRef<Path> + Copy) -> ion::Result<PathBuf> {
 	crate::wasi_polyfills::canonicalize(path).map_err(|e| {
 		if e.kind() == std::io::ErrorKind::NotFound {
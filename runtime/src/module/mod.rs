@@ -4,8 +4,16 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+pub use bundle::Bundle;
+pub use graph::{ModuleCycle, ModuleGraph};
+pub use jsonc::*;
 pub use loader::*;
+pub use progress::*;
 pub use standard::*;
 
+pub mod bundle;
+pub mod graph;
+pub mod jsonc;
 pub mod loader;
+pub mod progress;
 pub mod standard;
@@ -0,0 +1,208 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ion::{Context, ErrorReport, Promise};
+use ion::module::Module;
+
+use crate::module::ModuleCycle;
+
+/// Progress of the [Loader](super::Loader) while it resolves, compiles and evaluates modules.
+#[derive(Clone, Copy, Debug)]
+pub enum LoaderEvent<'a> {
+	ResolveStart {
+		specifier: &'a str,
+	},
+	CompileStart {
+		specifier: &'a str,
+		path: &'a Path,
+	},
+	CompileEnd {
+		specifier: &'a str,
+		path: &'a Path,
+		duration: Duration,
+		success: bool,
+	},
+	/// Emitted when an import completes a cycle of imports, while the modules are being instantiated.
+	CycleDetected {
+		cycle: &'a ModuleCycle,
+	},
+	/// Emitted when the synchronous part of a module evaluated through [LoaderProgress::evaluate] has completed.
+	/// Dependencies are evaluated as part of the module which imports them.
+	EvaluateEnd {
+		specifier: &'a str,
+		duration: Duration,
+		success: bool,
+	},
+}
+
+/// Timings of a module loaded by the [Loader](super::Loader).
+#[derive(Clone, Debug)]
+pub struct ModuleTiming {
+	pub specifier: String,
+	pub path: Option<PathBuf>,
+	/// Duration taken to locate and read the module, including transpilation of TypeScript.
+	pub resolve: Option<Duration>,
+	pub compile: Option<Duration>,
+	pub evaluate: Option<Duration>,
+}
+
+impl ModuleTiming {
+	pub fn total(&self) -> Duration {
+		self.resolve.unwrap_or_default() + self.compile.unwrap_or_default() + self.evaluate.unwrap_or_default()
+	}
+}
+
+/// Aggregate timings of the modules loaded by the [Loader](super::Loader).
+#[derive(Clone, Debug, Default)]
+pub struct LoaderStats {
+	pub modules: Vec<ModuleTiming>,
+	/// Number of imports which were resolved from the registry of loaded modules.
+	pub registry_hits: u32,
+}
+
+impl LoaderStats {
+	pub fn resolve_duration(&self) -> Duration {
+		self.modules.iter().filter_map(|module| module.resolve).sum()
+	}
+
+	pub fn compile_duration(&self) -> Duration {
+		self.modules.iter().filter_map(|module| module.compile).sum()
+	}
+
+	pub fn evaluate_duration(&self) -> Duration {
+		self.modules.iter().filter_map(|module| module.evaluate).sum()
+	}
+
+	/// Returns the modules which took the longest to load, with the slowest first.
+	pub fn slowest(&self, count: usize) -> Vec<&ModuleTiming> {
+		let mut modules: Vec<_> = self.modules.iter().collect();
+		modules.sort_by_key(|module| std::cmp::Reverse(module.total()));
+		modules.truncate(count);
+		modules
+	}
+}
+
+impl Display for LoaderStats {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		writeln!(
+			f,
+			"Loaded {} modules ({} registry hits)",
+			self.modules.len(),
+			self.registry_hits
+		)?;
+		writeln!(f, "Resolve: {:?}", self.resolve_duration())?;
+		writeln!(f, "Compile: {:?}", self.compile_duration())?;
+		writeln!(f, "Evaluate: {:?}", self.evaluate_duration())?;
+		for module in self.slowest(10) {
+			let name = module.path.as_deref().map(Path::display);
+			match name {
+				Some(name) => writeln!(f, "  {:?} {}", module.total(), name)?,
+				None => writeln!(f, "  {:?} {}", module.total(), module.specifier)?,
+			}
+		}
+		Ok(())
+	}
+}
+
+type LoaderCallback = Rc<dyn Fn(&LoaderEvent)>;
+
+/// Handle to the progress of a [Loader](super::Loader), which remains usable after the loader is given to the runtime.
+#[derive(Clone, Default)]
+pub struct LoaderProgress {
+	inner: Rc<LoaderProgressInner>,
+}
+
+#[derive(Default)]
+struct LoaderProgressInner {
+	callback: RefCell<Option<LoaderCallback>>,
+	stats: RefCell<LoaderStats>,
+}
+
+impl LoaderProgress {
+	/// Sets the callback which receives each [LoaderEvent].
+	pub fn set_callback(&self, callback: impl Fn(&LoaderEvent) + 'static) {
+		*self.inner.callback.borrow_mut() = Some(Rc::new(callback));
+	}
+
+	pub fn stats(&self) -> LoaderStats {
+		self.inner.stats.borrow().clone()
+	}
+
+	/// Evaluates a module, and records the duration of its evaluation.
+	pub fn evaluate<'cx>(
+		&self, cx: &'cx Context, module: &Module<'cx>, specifier: &str,
+	) -> Result<Option<Promise>, ErrorReport> {
+		let start = Instant::now();
+		let result = module.evaluate(cx);
+		let duration = start.elapsed();
+		self.emit(&LoaderEvent::EvaluateEnd {
+			specifier,
+			duration,
+			success: result.is_ok(),
+		});
+
+		let mut stats = self.inner.stats.borrow_mut();
+		match stats.modules.iter_mut().find(|module| module.specifier == specifier) {
+			Some(timing) => timing.evaluate = Some(duration),
+			None => stats.modules.push(ModuleTiming {
+				specifier: String::from(specifier),
+				path: None,
+				resolve: None,
+				compile: None,
+				evaluate: Some(duration),
+			}),
+		}
+		result
+	}
+
+	pub(crate) fn emit(&self, event: &LoaderEvent) {
+		match event {
+			LoaderEvent::ResolveStart { specifier } => {
+				tracing::trace!(target: "spiderfire::loader", specifier, "resolve-start")
+			}
+			LoaderEvent::CompileStart { specifier, path } => {
+				tracing::trace!(target: "spiderfire::loader", specifier, path = %path.display(), "compile-start")
+			}
+			LoaderEvent::CompileEnd { specifier, path, duration, success } => tracing::debug!(
+				target: "spiderfire::loader",
+				specifier,
+				path = %path.display(),
+				?duration,
+				success,
+				"compile-end"
+			),
+			LoaderEvent::CycleDetected { cycle } => {
+				tracing::warn!(target: "spiderfire::loader", cycle = %cycle, "cycle-detected")
+			}
+			LoaderEvent::EvaluateEnd { specifier, duration, success } => tracing::debug!(
+				target: "spiderfire::loader",
+				specifier,
+				?duration,
+				success,
+				"evaluate-end"
+			),
+		}
+		// The callback is not borrowed while it runs, as it may replace itself or cause nested events
+		let callback = self.inner.callback.borrow().clone();
+		if let Some(callback) = callback {
+			callback(event);
+		}
+	}
+
+	pub(crate) fn record_hit(&self) {
+		self.inner.stats.borrow_mut().registry_hits += 1;
+	}
+
+	pub(crate) fn record_module(&self, timing: ModuleTiming) {
+		self.inner.stats.borrow_mut().modules.push(timing);
+	}
+}
@@ -0,0 +1,300 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use indexmap::IndexSet;
+use mozjs::jsapi::{JS_GetGCParameter, JS_RequestInterruptCallback, JSContext, JSGCParamKey};
+
+use ion::{Context, Object, Stack, Value};
+use ion::conversions::ToValue;
+
+use crate::cache::map::find_sourcemap;
+use crate::ContextExt;
+use crate::VERSION;
+
+/// Default interval between the samples of the profiler.
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Size of the heap managed by the garbage collector.
+#[derive(Clone, Copy, Debug, ToValue)]
+pub struct MemoryUsage {
+	/// Bytes allocated in the heap.
+	pub heap_bytes: u64,
+	/// Bytes the heap is allowed to grow to.
+	pub heap_limit: u64,
+	/// Chunks of memory allocated for the heap, including unused chunks.
+	pub chunks: u32,
+	/// Chunks which are kept to serve future allocations.
+	pub unused_chunks: u32,
+}
+
+/// Number of collections run by the garbage collector since the runtime was created.
+#[derive(Clone, Copy, Debug, ToValue)]
+pub struct GcStats {
+	pub collections: u32,
+	pub major_collections: u32,
+	pub minor_collections: u32,
+	pub incremental: bool,
+}
+
+fn gc_parameter(cx: &Context, key: JSGCParamKey) -> u32 {
+	unsafe { JS_GetGCParameter(cx.as_ptr(), key) }
+}
+
+pub fn memory_usage(cx: &Context) -> MemoryUsage {
+	MemoryUsage {
+		heap_bytes: u64::from(gc_parameter(cx, JSGCParamKey::JSGC_BYTES)),
+		heap_limit: u64::from(gc_parameter(cx, JSGCParamKey::JSGC_MAX_BYTES)),
+		chunks: gc_parameter(cx, JSGCParamKey::JSGC_TOTAL_CHUNKS),
+		unused_chunks: gc_parameter(cx, JSGCParamKey::JSGC_UNUSED_CHUNKS),
+	}
+}
+
+pub fn gc_stats(cx: &Context) -> GcStats {
+	GcStats {
+		collections: gc_parameter(cx, JSGCParamKey::JSGC_NUMBER),
+		major_collections: gc_parameter(cx, JSGCParamKey::JSGC_MAJOR_GC_NUMBER),
+		minor_collections: gc_parameter(cx, JSGCParamKey::JSGC_MINOR_GC_NUMBER),
+		incremental: gc_parameter(cx, JSGCParamKey::JSGC_INCREMENTAL_GC_ENABLED) != 0,
+	}
+}
+
+/// Frame of a [Profile].
+///
+/// Frames are identified by the name and file of their function, and anonymous functions are further identified by
+/// their line and column. The location of a named function is where it was first sampled.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProfileFrame {
+	pub name: String,
+	pub file: String,
+	pub line: u32,
+	pub column: u32,
+}
+
+/// Samples of the stack recorded by the profiler, with each sample listing the indices of its frames from the
+/// outermost to the innermost.
+#[derive(Clone, Debug)]
+pub struct Profile {
+	pub frames: Vec<ProfileFrame>,
+	pub samples: Vec<Vec<u32>>,
+	pub interval: Duration,
+	pub duration: Duration,
+}
+
+impl<'cx> ToValue<'cx> for Profile {
+	/// Converts the profile to the [speedscope file format](https://www.speedscope.app/file-format-schema.json).
+	fn to_value(&self, cx: &'cx Context, value: &mut Value) {
+		let frames: Vec<_> = self
+			.frames
+			.iter()
+			.map(|frame| {
+				let object = Object::new(cx);
+				object.set_as(cx, "name", &frame.name);
+				object.set_as(cx, "file", &frame.file);
+				object.set_as(cx, "line", &frame.line);
+				object.set_as(cx, "col", &frame.column);
+				object
+			})
+			.collect();
+		let shared = Object::new(cx);
+		shared.set_as(cx, "frames", &frames);
+
+		let interval = self.interval.as_secs_f64() * 1000.0;
+		let weights = vec![interval; self.samples.len()];
+		let profile = Object::new(cx);
+		profile.set_as(cx, "type", "sampled");
+		profile.set_as(cx, "name", "spiderfire");
+		profile.set_as(cx, "unit", "milliseconds");
+		profile.set_as(cx, "startValue", &0);
+		profile.set_as(cx, "endValue", &(interval * self.samples.len() as f64));
+		profile.set_as(cx, "samples", &self.samples);
+		profile.set_as(cx, "weights", &weights);
+
+		let file = Object::new(cx);
+		file.set_as(cx, "$schema", "https://www.speedscope.app/file-format-schema.json");
+		file.set_as(cx, "shared", &shared);
+		file.set_as(cx, "profiles", &vec![profile]);
+		file.set_as(cx, "name", "spiderfire profile");
+		file.set_as(cx, "activeProfileIndex", &0);
+		file.set_as(cx, "exporter", &format!("spiderfire@{}", VERSION));
+		file.to_value(cx, value);
+	}
+}
+
+/// Pointer to the context, which is only used to request interrupts from other threads.
+pub(crate) struct InterruptHandle(*mut JSContext);
+
+unsafe impl Send for InterruptHandle {}
+
+impl InterruptHandle {
+	pub(crate) fn new(cx: &Context) -> InterruptHandle {
+		InterruptHandle(cx.as_ptr())
+	}
+
+	pub(crate) fn request(&self) {
+		unsafe { JS_RequestInterruptCallback(self.0) };
+	}
+}
+
+/// Sampling profiler, whose thread periodically requests an interrupt of the context, during which the current stack
+/// is captured. Samples are only taken while scripts are running, as interrupts are handled by running scripts.
+pub(crate) struct Sampler {
+	interval: Duration,
+	started: Instant,
+	requested: Arc<AtomicBool>,
+	stopped: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>,
+	frames: IndexSet<ProfileFrame>,
+	/// Locations of named functions, keyed by their name and file.
+	locations: HashMap<(String, String), (u32, u32)>,
+	samples: Vec<Vec<u32>>,
+}
+
+impl Sampler {
+	fn start(cx: &Context, interval: Duration) -> Sampler {
+		let requested = Arc::new(AtomicBool::new(false));
+		let stopped = Arc::new(AtomicBool::new(false));
+
+		let handle = InterruptHandle::new(cx);
+		let thread = {
+			let requested = Arc::clone(&requested);
+			let stopped = Arc::clone(&stopped);
+			thread::Builder::new().name(String::from("profiler")).spawn(move || {
+				while !stopped.load(Ordering::Acquire) {
+					thread::park_timeout(interval);
+					requested.store(true, Ordering::Release);
+					handle.request();
+				}
+			})
+		};
+
+		Sampler {
+			interval,
+			started: Instant::now(),
+			requested,
+			stopped,
+			thread: thread.ok(),
+			frames: IndexSet::new(),
+			locations: HashMap::new(),
+			samples: Vec::new(),
+		}
+	}
+
+	fn sample(&mut self, cx: &Context) {
+		if !self.requested.swap(false, Ordering::AcqRel) {
+			return;
+		}
+		let Some(stack) = Stack::from_capture(cx) else {
+			return;
+		};
+		if stack.is_empty() {
+			return;
+		}
+
+		let sample = stack
+			.records
+			.iter()
+			.rev()
+			.map(|record| {
+				let location = &record.location;
+				let frame = match record.function.as_deref().filter(|function| !function.is_empty()) {
+					Some(function) => {
+						let key = (String::from(function), location.file.clone());
+						let (line, column) = *self.locations.entry(key).or_insert((location.lineno, location.column));
+						ProfileFrame {
+							name: String::from(function),
+							file: location.file.clone(),
+							line,
+							column,
+						}
+					}
+					None => ProfileFrame {
+						name: String::from("(anonymous)"),
+						file: location.file.clone(),
+						line: location.lineno,
+						column: location.column,
+					},
+				};
+				self.frames.insert_full(frame).0 as u32
+			})
+			.collect();
+		self.samples.push(sample);
+	}
+
+	fn stop(mut self) -> Profile {
+		let duration = self.started.elapsed();
+		self.stop_thread();
+
+		let mut sourcemaps = HashMap::new();
+		let frames = std::mem::take(&mut self.frames)
+			.into_iter()
+			.map(|mut frame| {
+				let sourcemap = sourcemaps.entry(frame.file.clone()).or_insert_with(|| find_sourcemap(&frame.file));
+				if let Some(sourcemap) = sourcemap {
+					if frame.line != 0 && frame.column != 0 {
+						if let Some(token) = sourcemap.lookup_token(frame.line - 1, frame.column - 1) {
+							frame.line = token.get_src_line() + 1;
+							frame.column = token.get_src_col() + 1;
+						}
+					}
+				}
+				frame
+			})
+			.collect();
+
+		Profile {
+			frames,
+			samples: std::mem::take(&mut self.samples),
+			interval: self.interval,
+			duration,
+		}
+	}
+
+	fn stop_thread(&mut self) {
+		self.stopped.store(true, Ordering::Release);
+		if let Some(thread) = self.thread.take() {
+			thread.thread().unpark();
+			let _ = thread.join();
+		}
+	}
+}
+
+impl Drop for Sampler {
+	fn drop(&mut self) {
+		self.stop_thread();
+	}
+}
+
+/// Starts the sampling profiler. Returns `false` if it is already running.
+pub fn start_profiler(cx: &Context, interval: Duration) -> bool {
+	let private = unsafe { cx.get_private() };
+	if private.sampler.is_some() {
+		return false;
+	}
+	private.sampler = Some(Sampler::start(cx, interval));
+	true
+}
+
+/// Stops the sampling profiler, and returns the samples it recorded. Returns [None] if it is not running.
+pub fn stop_profiler(cx: &Context) -> Option<Profile> {
+	unsafe { cx.get_private() }.sampler.take().map(Sampler::stop)
+}
+
+pub fn is_profiling(cx: &Context) -> bool {
+	unsafe { cx.get_private() }.sampler.is_some()
+}
+
+/// Samples the stack if the profiler requested the interrupt which is being handled.
+pub(crate) fn sample(cx: &Context) {
+	if let Some(sampler) = &mut unsafe { cx.get_private() }.sampler {
+		sampler.sample(cx);
+	}
+}
@@ -6,12 +6,11 @@
 
 use std::future::Future;
 
-use tokio::task::spawn_local;
-
 use ion::{Context, Promise, TracedHeap};
 use ion::conversions::{BoxedIntoValue, IntoValue};
 
 use crate::ContextExt;
+use crate::event_loop::async_context::AsyncContext;
 
 /// Returns None if no future queue has been initialised.
 ///
@@ -47,19 +46,20 @@ where
 {
 	let promise = Promise::new(cx);
 	let heap = TracedHeap::new(promise.get());
+	let context = AsyncContext::current(cx);
 	let cx2 = cx.duplicate();
 
-	let handle = spawn_local(async move {
+	let future = async move {
 		let result: Result<BoxedIntoValue, BoxedIntoValue> = match callback(cx2).await {
 			Ok(o) => Ok(Box::new(o)),
 			Err(e) => Err(Box::new(e)),
 		};
-		(result, heap)
-	});
+		(result, heap, context)
+	};
 
 	let event_loop = unsafe { &cx.get_private().event_loop };
 	event_loop.futures.as_ref().map(|futures| {
-		futures.enqueue(cx, handle);
+		futures.spawn(cx, future);
 		promise
 	})
 }
@@ -5,26 +5,87 @@
  */
 
 use std::any::Any;
+#[cfg(feature = "fetch")]
+use std::collections::HashMap;
+use std::future::{Future, poll_fn};
+use std::path::Path;
+use std::pin::pin;
 use std::ptr;
+use std::rc::Rc;
+use std::task::Poll;
+use std::time::Duration;
 
 use mozjs::glue::CreateJobQueue;
-use mozjs::jsapi::{ContextOptionsRef, JSAutoRealm, SetJobQueue, SetPromiseRejectionTrackerCallback, OnNewGlobalHookOption};
-
-use ion::{Context, ErrorReport, Object};
-use ion::module::{init_module_loader, ModuleLoader};
+use mozjs::jsapi::{
+	ContextOptionsRef, JS_AddInterruptCallback, JS_SetSecurityCallbacks, JSAutoRealm, JSContext, SetJobQueue,
+	SetPromiseRejectionTrackerCallback, OnNewGlobalHookOption,
+};
+#[cfg(feature = "fetch")]
+use mozjs::jsapi::JSObject;
+
+use ion::{Context, ErrorReport, Object, Promise, Value};
+#[cfg(feature = "fetch")]
+use ion::TracedHeap;
+use ion::module::{init_module_loader, Module, ModuleError, ModuleLoader};
 use ion::object::new_global;
+use ion::realm::{in_realm, wrap_value};
+use ion::script::Script;
 use mozjs::rust::{RealmOptions, SIMPLE_GLOBAL_CLASS};
 
-use crate::event_loop::{EventLoop, promise_rejection_tracker_callback};
+use crate::event_loop::{EventLoop, promise_rejection_tracker_callback, Spawner, TimerOptions};
 use crate::event_loop::future::FutureQueue;
 use crate::event_loop::macrotasks::MacrotaskQueue;
 use crate::event_loop::microtasks::{JOB_QUEUE_TRAPS, MicrotaskQueue};
-use crate::globals::{init_globals, init_microtasks, init_timers};
+use crate::globals::{
+	self, host_events, init_globals, intrinsics, init_microtasks, init_timers, prompt, storage, streams, timers,
+};
+use crate::globals::host_events::HostEventReceiver;
+use crate::globals::process::{self, ExitHandler, ProcessOptions};
+use crate::globals::performance::PerformanceTimeline;
+use crate::globals::random::{RandomState, seed_math_random, SeededRandom};
+use crate::globals::shutdown::{self, ShutdownState};
+#[cfg(feature = "fetch")]
+use crate::globals::fetch::{
+	Client, client_with_options, ClientOptions, define_service_worker_scope, LargeBodyOptions, MimeChecking,
+};
+use crate::gc::{self, GcOptions, GcReason, GcSlice, GcState};
+use crate::handle::{self, RuntimeHandle};
+use crate::heap_census;
+#[cfg(feature = "inspector")]
+use crate::inspector::{self, InspectorOptions};
+use crate::kv::{KvStore, MemoryKvStore};
 use crate::module::StandardModules;
+use crate::limits::{self, ResourceLimits, Watchdog};
+use crate::profiler::{self, GcStats, MemoryUsage, Profile, Sampler};
+use crate::security::{EvalPolicies, EvalPolicy, ReadPermission, SECURITY_CALLBACKS, WritePermission};
 
 #[derive(Default)]
 pub struct ContextPrivate {
 	pub(crate) event_loop: EventLoop,
+	pub(crate) eval_policies: EvalPolicies,
+	pub(crate) read_permission: ReadPermission,
+	pub(crate) write_permission: WritePermission,
+	pub(crate) performance: PerformanceTimeline,
+	pub(crate) exit_handler: Option<Rc<ExitHandler>>,
+	pub(crate) sampler: Option<Sampler>,
+	pub(crate) watchdog: Option<Watchdog>,
+	pub(crate) handle: RuntimeHandle,
+	pub(crate) gc: GcState,
+	pub(crate) shutdown: ShutdownState,
+	pub(crate) kv_store: Option<Rc<dyn KvStore>>,
+	#[cfg(feature = "fetch")]
+	pub(crate) large_body: LargeBodyOptions,
+	#[cfg(feature = "fetch")]
+	pub(crate) mime_checking: MimeChecking,
+	#[cfg(feature = "fetch")]
+	pub(crate) service_worker_scope: Option<TracedHeap<*mut JSObject>>,
+	#[cfg(feature = "fetch")]
+	pub(crate) schemes: HashMap<String, Rc<SchemeHandler>>,
+	#[cfg(feature = "fetch")]
+	pub(crate) client: Option<Client>,
+	/// Client used for requests with `rawHeaderCase` set, as header casing is a connection-level option.
+	#[cfg(feature = "fetch")]
+	pub(crate) raw_header_case_client: Option<Client>,
 	pub app_data: Option<Box<dyn Any>>,
 }
 
@@ -64,6 +125,7 @@ pub struct Runtime<'cx> {
 	cx: &'cx Context,
 	#[allow(dead_code)]
 	realm: JSAutoRealm,
+	random: Option<SeededRandom>,
 }
 
 impl<'cx> Runtime<'cx> {
@@ -79,12 +141,171 @@ impl<'cx> Runtime<'cx> {
 		&mut self.global
 	}
 
+	/// Returns a snapshot of the generator behind `Math.random`.
+	/// Returns [None] if it was not seeded with [random_seed](RuntimeBuilder::random_seed).
+	pub fn random_state(&self) -> Option<RandomState> {
+		self.random.as_ref().map(SeededRandom::state)
+	}
+
+	/// Restores the generator behind `Math.random` to a snapshot from [random_state](Runtime::random_state).
+	/// Returns `false` if it was not seeded with [random_seed](RuntimeBuilder::random_seed).
+	pub fn set_random_state(&self, state: RandomState) -> bool {
+		match &self.random {
+			Some(random) => {
+				random.set_state(state);
+				true
+			}
+			None => false,
+		}
+	}
+
 	pub async fn run_event_loop(&self) -> Result<(), Option<ErrorReport>> {
 		let event_loop = unsafe { &mut self.cx.get_private().event_loop };
 		let cx = self.cx.duplicate();
 		event_loop.run_to_end(&cx).await
 	}
 
+	/// Runs the callbacks registered with `runtime.onShutdown`, and then runs the event loop until the promises they
+	/// returned are settled and in-flight `keepalive` fetches complete, so that final requests such as beacons are
+	/// still delivered. Other pending tasks, such as timers, do not delay the shutdown, and are discarded afterwards.
+	///
+	/// Returns `false` if the shutdown did not complete within `timeout`.
+	pub async fn shutdown(&self, timeout: Duration) -> Result<bool, Option<ErrorReport>> {
+		let promises = shutdown::run_callbacks(self.cx);
+		let mut deadline = pin!(tokio::time::sleep(timeout));
+		let result = poll_fn(|wcx| {
+			self.step_event_loop(wcx)?;
+			if shutdown::is_complete(self.cx, &promises) {
+				Poll::Ready(Ok(true))
+			} else if deadline.as_mut().poll(wcx).is_ready() {
+				Poll::Ready(Ok(false))
+			} else {
+				Poll::Pending
+			}
+		})
+		.await;
+		EventLoop::from_context(self.cx).clear(self.cx);
+		result
+	}
+
+	/// Sets the handler for promises which were rejected without a handler, instead of printing them to stderr.
+	/// Rejections cancelled by the `onunhandledrejection` handler of the global are not passed to the handler.
+	pub fn set_unhandled_rejection_handler<F>(&self, handler: F)
+	where
+		F: for<'v> Fn(&'v Context, Value<'v>) + 'static,
+	{
+		let event_loop = unsafe { &mut self.cx.get_private().event_loop };
+		event_loop.unhandled_rejection_handler = Some(Rc::new(handler));
+	}
+
+	/// Sets the handler for `process.exit`, which is called with the exit code instead of exiting the process.
+	/// Scripts continue running after `process.exit` returns, unless the handler stops them.
+	pub fn set_exit_handler<F>(&self, handler: F)
+	where
+		F: Fn(i32) + 'static,
+	{
+		unsafe { self.cx.get_private() }.exit_handler = Some(Rc::new(handler));
+	}
+
+	/// Registers the handler for the requests to a custom scheme, such as `s3:`, which `fetch` passes to it instead of
+	/// failing with a network error. The handler can return a response with a native stream as its body, which is
+	/// created with [Response::from_stream](crate::globals::fetch::Response::from_stream).
+	///
+	/// Returns `false` if the scheme is invalid, or is handled by the runtime itself, such as `http:` or `file:`.
+	#[cfg(feature = "fetch")]
+	pub fn register_scheme<F, Fut>(&self, scheme: &str, handler: F) -> bool
+	where
+		F: Fn(Context, SchemeRequest) -> Fut + 'static,
+		Fut: Future<Output = ion::Result<Response>> + 'static,
+	{
+		if !is_registrable_scheme(scheme) {
+			return false;
+		}
+		let handler: Rc<SchemeHandler> = Rc::new(move |cx, request| Box::pin(handler(cx, request)) as SchemeFuture);
+		unsafe { self.cx.get_private() }.schemes.insert(String::from(scheme), handler);
+		true
+	}
+
+	/// Starts the sampling profiler, which records the stack of running scripts every `interval`.
+	/// Returns `false` if it is already running.
+	pub fn start_profiler(&self, interval: Duration) -> bool {
+		profiler::start_profiler(self.cx, interval)
+	}
+
+	/// Stops the sampling profiler, and returns the samples it recorded, which are converted to the speedscope file
+	/// format by [ToValue](ion::conversions::ToValue). Returns [None] if it is not running.
+	pub fn stop_profiler(&self) -> Option<Profile> {
+		profiler::stop_profiler(self.cx)
+	}
+
+	pub fn memory_usage(&self) -> MemoryUsage {
+		profiler::memory_usage(self.cx)
+	}
+
+	pub fn gc_stats(&self) -> GcStats {
+		profiler::gc_stats(self.cx)
+	}
+
+	/// Returns a handle which stops the scripts of the runtime from other threads.
+	pub fn handle(&self) -> RuntimeHandle {
+		unsafe { self.cx.get_private() }.handle.clone()
+	}
+
+	/// Compiles and evaluates a module, which is terminated if it exceeds the
+	/// [module timeout](ResourceLimits::module_timeout).
+	pub fn evaluate_module(
+		&self, filename: &str, path: Option<&Path>, script: &str,
+	) -> Result<(Module<'cx>, Option<Promise>), ModuleError> {
+		limits::limit_module(self.cx, || {
+			Module::compile_and_evaluate(self.cx, filename, path, script)
+		})
+	}
+
+	/// Creates a realm with its own global, on which the same globals are defined as on the global of the runtime, so
+	/// that scripts such as plugins are isolated from each other without running them in separate workers.
+	/// Realms share the event loop, module loader and [resource limits](ResourceLimits) of the runtime.
+	///
+	/// The global must only be used within its realm, such as with [in_realm](ion::realm::in_realm).
+	pub fn create_realm(&self) -> Object<'cx> {
+		globals::create_realm(self.cx)
+	}
+
+	/// Compiles and evaluates a script within a realm created with [create_realm](Runtime::create_realm).
+	/// The result is wrapped for use in the realm of the runtime.
+	pub fn evaluate_script_in(&self, realm: &Object, path: &Path, script: &str) -> Result<Value<'cx>, ErrorReport> {
+		let mut result = in_realm(self.cx, realm, || Script::compile_and_evaluate(self.cx, path, script))?;
+		wrap_value(self.cx, &mut result);
+		Ok(result)
+	}
+
+	/// Compiles and evaluates a module within a realm created with [create_realm](Runtime::create_realm), which is
+	/// terminated if it exceeds the [module timeout](ResourceLimits::module_timeout).
+	/// The module and its promise belong to the realm.
+	pub fn evaluate_module_in(
+		&self, realm: &Object, filename: &str, path: Option<&Path>, script: &str,
+	) -> Result<(Module<'cx>, Option<Promise>), ModuleError> {
+		in_realm(self.cx, realm, || self.evaluate_module(filename, path, script))
+	}
+
+	/// Runs a full collection, such as while the embedder is idle.
+	pub fn gc(&self, reason: GcReason) {
+		gc::gc(self.cx, reason);
+	}
+
+	/// Sets the handler which is called at the start and end of every slice and cycle of the garbage collector, such
+	/// as to record the duration of pauses. See [GcSliceHandler](gc::GcSliceHandler) for its restrictions.
+	pub fn set_gc_slice_handler<F>(&self, handler: F)
+	where
+		F: Fn(&GcSlice) + 'static,
+	{
+		gc::set_slice_handler(self.cx, Some(Rc::new(handler)));
+	}
+
+	/// Takes a census of the heap. See [heap_census](heap_census::heap_census) for its format.
+	pub fn heap_census(&self) -> Result<String, Option<ErrorReport>> {
+		heap_census::heap_census(self.cx)
+	}
+
 	pub fn step_event_loop(&self, wcx: &mut std::task::Context) -> Result<(), Option<ErrorReport>> {
 		let event_loop = unsafe { &mut self.cx.get_private().event_loop };
 		let cx = self.cx.duplicate();
@@ -99,6 +320,7 @@ impl<'cx> Runtime<'cx> {
 
 impl Drop for Runtime<'_> {
 	fn drop(&mut self) {
+		unsafe { self.cx.get_private() }.handle.detach();
 		let inner_private = self.cx.get_inner_data();
 		let _ = unsafe { Box::from_raw(inner_private.as_ptr()) };
 	}
@@ -107,10 +329,36 @@ impl Drop for Runtime<'_> {
 pub struct RuntimeBuilder<ML: ModuleLoader + 'static = (), Std: StandardModules + 'static = ()> {
 	microtask_queue: bool,
 	macrotask_queue: bool,
+	timer_options: TimerOptions,
+	timer_introspection: bool,
+	spawner: Option<Rc<dyn Spawner>>,
 	modules: Option<ML>,
 	standard_modules: Option<Std>,
 	hook_option: Option<OnNewGlobalHookOption>,
 	realm_options: Option<RealmOptions>,
+	eval_policy: EvalPolicy,
+	read_permission: ReadPermission,
+	write_permission: WritePermission,
+	console_input: bool,
+	stream_diagnostics: bool,
+	random_seed: Option<u64>,
+	host_events: Option<HostEventReceiver>,
+	process: Option<ProcessOptions>,
+	kv_store: Option<Rc<dyn KvStore>>,
+	resource_limits: ResourceLimits,
+	gc_options: GcOptions,
+	gc_global: bool,
+	frozen_intrinsics: Option<Vec<String>>,
+	#[cfg(feature = "inspector")]
+	inspector: Option<InspectorOptions>,
+	#[cfg(feature = "fetch")]
+	client: Option<Client>,
+	#[cfg(feature = "fetch")]
+	large_body: LargeBodyOptions,
+	#[cfg(feature = "fetch")]
+	mime_checking: MimeChecking,
+	#[cfg(feature = "fetch")]
+	service_worker_scope: bool,
 }
 
 impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> RuntimeBuilder<ML, Std> {
@@ -123,11 +371,31 @@ impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> RuntimeBuilder<
 		self
 	}
 
+	/// Configures the clamping and drift correction of timers in the macrotask queue.
+	pub fn timer_options(mut self, timer_options: TimerOptions) -> RuntimeBuilder<ML, Std> {
+		self.timer_options = timer_options;
+		self
+	}
+
+	/// Defines the `timers` global, whose `list()` returns the ID, type, callback name and due time of the pending
+	/// timers to debug timers which never fire or are never cleared. Requires the macrotask queue.
+	pub fn timer_introspection(mut self) -> RuntimeBuilder<ML, Std> {
+		self.timer_introspection = true;
+		self
+	}
+
 	pub fn microtask_queue(mut self) -> RuntimeBuilder<ML, Std> {
 		self.microtask_queue = true;
 		self
 	}
 
+	/// Sets the executor which drives native futures, for hosts which do not run the event loop within tokio.
+	/// Defaults to [TokioSpawner](crate::event_loop::TokioSpawner). Requires the microtask queue.
+	pub fn spawner(mut self, spawner: Rc<dyn Spawner>) -> RuntimeBuilder<ML, Std> {
+		self.spawner = Some(spawner);
+		self
+	}
+
 	pub fn modules(mut self, loader: ML) -> RuntimeBuilder<ML, Std> {
 		self.modules = Some(loader);
 		self
@@ -148,6 +416,152 @@ impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> RuntimeBuilder<
 		self
 	}
 
+	/// Sets whether `eval` and the `Function` constructor are allowed to compile code at runtime.
+	/// Blocked attempts are reported as warnings.
+	///
+	/// Individual realms can override this with [set_realm_eval_policy](crate::security::set_realm_eval_policy).
+	pub fn eval_policy(mut self, eval_policy: EvalPolicy) -> RuntimeBuilder<ML, Std> {
+		self.eval_policy = eval_policy;
+		self
+	}
+
+	/// Sets which files scripts can read, such as through `file:` URLs.
+	pub fn read_permission(mut self, read_permission: ReadPermission) -> RuntimeBuilder<ML, Std> {
+		self.read_permission = read_permission;
+		self
+	}
+
+	/// Sets which files scripts can write, such as through `fetch.download`.
+	pub fn write_permission(mut self, write_permission: WritePermission) -> RuntimeBuilder<ML, Std> {
+		self.write_permission = write_permission;
+		self
+	}
+
+	/// Defines the `prompt` and `confirm` globals, which read answers from stdin without blocking the event loop.
+	/// They are only defined if stdin and stdout are attached to a terminal, and require the microtask queue.
+	pub fn console_input(mut self) -> RuntimeBuilder<ML, Std> {
+		self.console_input = true;
+		self
+	}
+
+	/// Defines the `__streamState(stream)` global, which reports the state, queue size and desired size of a readable
+	/// stream to diagnose backpressure stalls. See [ReadableStream::diagnostics](ion::ReadableStream::diagnostics).
+	pub fn stream_diagnostics(mut self) -> RuntimeBuilder<ML, Std> {
+		self.stream_diagnostics = true;
+		self
+	}
+
+	/// Seeds the generator behind `Math.random`, so that it produces the same numbers every time the runtime is built.
+	/// Its state can be snapshotted and restored with [random_state](Runtime::random_state) to replay sequences.
+	///
+	/// `Math.random` is not cryptographically secure, even when unseeded. Use `crypto.getRandomValues` instead.
+	pub fn random_seed(mut self, seed: u64) -> RuntimeBuilder<ML, Std> {
+		self.random_seed = Some(seed);
+		self
+	}
+
+	/// Defines the `process` global, which exposes the arguments and allowed environment variables of the script, along
+	/// with `cwd()`, `exit(code)` and `platform`.
+	pub fn process(mut self, options: ProcessOptions) -> RuntimeBuilder<ML, Std> {
+		self.process = Some(options);
+		self
+	}
+
+	/// Defines the `hostEvents` global, whose listeners are registered with `on(event, callback)` and receive the
+	/// events emitted through the sender of the [channel](host_events::host_event_channel).
+	/// Requires the macrotask queue, through which the events are delivered.
+	pub fn host_events(mut self, receiver: HostEventReceiver) -> RuntimeBuilder<ML, Std> {
+		self.host_events = Some(receiver);
+		self
+	}
+
+	/// Sets the store behind the `localStorage` global and the `spiderfire:kv` module. Defaults to a [MemoryKvStore],
+	/// so that values are not persisted.
+	pub fn kv_store(mut self, store: Rc<dyn KvStore>) -> RuntimeBuilder<ML, Std> {
+		self.kv_store = Some(store);
+		self
+	}
+
+	/// Limits the size of the heap, the duration of each task of the event loop, and the duration of the evaluation of
+	/// modules. Tasks which exceed their limit are terminated, and reported as errors by the event loop.
+	pub fn resource_limits(mut self, limits: ResourceLimits) -> RuntimeBuilder<ML, Std> {
+		self.resource_limits = limits;
+		self
+	}
+
+	/// Configures the garbage collector, such as whether collections are incremental and when they are triggered.
+	pub fn gc_options(mut self, options: GcOptions) -> RuntimeBuilder<ML, Std> {
+		self.gc_options = options;
+		self
+	}
+
+	/// Defines the `gc(shrink)` global, which runs a full collection.
+	pub fn gc_global(mut self) -> RuntimeBuilder<ML, Std> {
+		self.gc_global = true;
+		self
+	}
+
+	/// Freezes the intrinsics of the global, such as `Object.prototype` and `Array.prototype`, once all globals and
+	/// standard modules are initialised, so that scripts sharing the realm cannot pollute prototypes used by others.
+	/// Globals named in `patchable` are left mutable.
+	///
+	/// Assigning to a property inherited from a frozen prototype, such as `toString`, fails even on unfrozen objects.
+	/// Use `Object.defineProperty` to define such properties instead.
+	pub fn freeze_intrinsics<S: Into<String>>(
+		mut self, patchable: impl IntoIterator<Item = S>,
+	) -> RuntimeBuilder<ML, Std> {
+		self.frozen_intrinsics = Some(patchable.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Starts a debugger server, which clients such as Chrome DevTools connect to over the Chrome DevTools Protocol to
+	/// set breakpoints, pause on exceptions and evaluate expressions in paused frames.
+	/// See [InspectorOptions] for the address and whether building waits for a client.
+	#[cfg(feature = "inspector")]
+	pub fn inspector(mut self, options: InspectorOptions) -> RuntimeBuilder<ML, Std> {
+		self.inspector = Some(options);
+		self
+	}
+
+	/// Configures the HTTP client used by `fetch`.
+	/// Returns [Err] if the TLS configuration is invalid.
+	#[cfg(feature = "fetch")]
+	pub fn client_options(mut self, client_options: ClientOptions) -> Result<RuntimeBuilder<ML, Std>, rustls::Error> {
+		self.client = Some(client_with_options(&client_options)?);
+		Ok(self)
+	}
+
+	/// Sets the HTTP client used by `fetch`, which can be created with [client_with_options].
+	#[cfg(feature = "fetch")]
+	pub fn client(mut self, client: Client) -> RuntimeBuilder<ML, Std> {
+		self.client = Some(client);
+		self
+	}
+
+	/// Configures how `json()` and `text()` of requests and responses handle large bodies.
+	#[cfg(feature = "fetch")]
+	pub fn large_body_options(mut self, large_body: LargeBodyOptions) -> RuntimeBuilder<ML, Std> {
+		self.large_body = large_body;
+		self
+	}
+
+	/// Configures how strictly `json()` and `formData()` of requests and responses check the `Content-Type` of bodies.
+	/// Defaults to [MimeChecking::Lenient].
+	#[cfg(feature = "fetch")]
+	pub fn mime_checking(mut self, mime_checking: MimeChecking) -> RuntimeBuilder<ML, Std> {
+		self.mime_checking = mime_checking;
+		self
+	}
+
+	/// Defines `addEventListener`, `removeEventListener` and `dispatchEvent` on the global, so that scripts can respond to
+	/// requests with `addEventListener("fetch", event => event.respondWith(response))`.
+	/// Requests are dispatched to the listeners with [dispatch_fetch](crate::globals::fetch::dispatch_fetch).
+	#[cfg(feature = "fetch")]
+	pub fn service_worker_scope(mut self) -> RuntimeBuilder<ML, Std> {
+		self.service_worker_scope = true;
+		self
+	}
+
 	pub fn build(self, cx: &Context) -> Runtime {
 		let global = new_global(
 			cx,
@@ -160,14 +574,42 @@ impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> RuntimeBuilder<
 
 		let global_obj = global.handle().get();
 		global.set_as(cx, "global", &global_obj);
+
 		init_globals(cx, &global);
+		if let Some(options) = &self.process {
+			process::define(cx, &global, options);
+		}
+		let random = self
+			.random_seed
+			.and_then(|seed| seed_math_random(cx, &global, RandomState::from_seed(seed)));
 
 		let mut private = Box::<ContextPrivate>::default();
+		private.eval_policies.default = self.eval_policy;
+		private.read_permission = self.read_permission;
+		private.write_permission = self.write_permission;
+		let kv_store = self.kv_store.unwrap_or_else(|| Rc::new(MemoryKvStore::default()));
+		storage::define(cx, &global, Rc::clone(&kv_store));
+		private.kv_store = Some(kv_store);
+		#[cfg(feature = "fetch")]
+		{
+			private.large_body = self.large_body;
+			private.mime_checking = self.mime_checking;
+			private.client = self.client;
+			if self.service_worker_scope {
+				private.service_worker_scope = define_service_worker_scope(cx, &global);
+			}
+		}
+		unsafe {
+			JS_SetSecurityCallbacks(cx.as_ptr(), &SECURITY_CALLBACKS);
+		}
 
 		if self.microtask_queue {
 			private.event_loop.microtasks = Some(MicrotaskQueue::default());
 			init_microtasks(cx, &global);
-			private.event_loop.futures = Some(FutureQueue::default());
+			private.event_loop.futures = Some(match self.spawner {
+				Some(spawner) => FutureQueue::new(spawner),
+				None => FutureQueue::default(),
+			});
 
 			unsafe {
 				SetJobQueue(
@@ -185,13 +627,40 @@ impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> RuntimeBuilder<
 			}
 		}
 		if self.macrotask_queue {
-			private.event_loop.macrotasks = Some(MacrotaskQueue::default());
+			private.event_loop.macrotasks = Some(MacrotaskQueue::new(self.timer_options));
 			init_timers(cx, &global);
+			if self.timer_introspection {
+				timers::define_introspection(cx, &global);
+			}
+
+			if let Some(receiver) = self.host_events {
+				private.event_loop.host_events = host_events::define(cx, &global, receiver);
+			}
+		}
+		if self.console_input && self.microtask_queue && prompt::is_interactive() {
+			prompt::define(cx, &global);
+		}
+		if self.stream_diagnostics {
+			streams::define_diagnostics(cx, &global);
+		}
+		self.gc_options.apply(cx);
+		if self.gc_global {
+			gc::define(cx, &global);
+		}
+
+		#[cfg(feature = "inspector")]
+		if let Some(options) = self.inspector {
+			private.event_loop.inspector = inspector::start(cx, &global, options);
 		}
 
 		let _options = unsafe { &mut *ContextOptionsRef(cx.as_ptr()) };
 
+		private.watchdog = limits::apply_limits(cx, self.resource_limits);
+		private.handle = RuntimeHandle::new(cx);
 		cx.set_private(private);
+		unsafe {
+			JS_AddInterruptCallback(cx.as_ptr(), Some(interrupt_callback));
+		}
 
 		let has_loader = self.modules.is_some();
 		if let Some(loader) = self.modules {
@@ -205,20 +674,64 @@ impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> RuntimeBuilder<
 				standard_modules.init_globals(cx, &global);
 			}
 		}
+		if let Some(patchable) = &self.frozen_intrinsics {
+			intrinsics::freeze(cx, &global, patchable);
+		}
+
+		#[cfg(feature = "inspector")]
+		if let (Some(options), Some(session)) = (self.inspector, &EventLoop::from_context(cx).inspector) {
+			if options.wait {
+				session.wait(cx);
+			}
+		}
 
-		Runtime { global, cx, realm }
+		Runtime { global, cx, realm, random }
 	}
 }
 
+/// Samples the stack for the profiler, and terminates the running task if it exceeded its
+/// [timeout](ResourceLimits::task_timeout) or a [RuntimeHandle] requested to stop it.
+unsafe extern "C" fn interrupt_callback(cx: *mut JSContext) -> bool {
+	let cx = unsafe { &Context::new_unchecked(cx) };
+	profiler::sample(cx);
+	!limits::is_expired(cx) && !handle::take_request(cx)
+}
+
 impl<ML: ModuleLoader + 'static, Std: StandardModules + 'static> Default for RuntimeBuilder<ML, Std> {
 	fn default() -> RuntimeBuilder<ML, Std> {
 		RuntimeBuilder {
 			microtask_queue: false,
 			macrotask_queue: false,
+			timer_options: TimerOptions::default(),
+			timer_introspection: false,
+			spawner: None,
 			modules: None,
 			standard_modules: None,
 			hook_option: None,
 			realm_options: None,
+			eval_policy: EvalPolicy::default(),
+			read_permission: ReadPermission::default(),
+			write_permission: WritePermission::default(),
+			console_input: false,
+			stream_diagnostics: false,
+			random_seed: None,
+			host_events: None,
+			process: None,
+			kv_store: None,
+			resource_limits: ResourceLimits::default(),
+			gc_options: GcOptions::default(),
+			gc_global: false,
+			frozen_intrinsics: None,
+			#[cfg(feature = "inspector")]
+			inspector: None,
+			#[cfg(feature = "fetch")]
+			client: None,
+			#[cfg(feature = "fetch")]
+			large_body: LargeBodyOptions::default(),
+			#[cfg(feature = "fetch")]
+			mime_checking: MimeChecking::default(),
+			#[cfg(feature = "fetch")]
+			service_worker_scope: false,
 		}
 	}
 }
@@ -0,0 +1,252 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ffi::c_void;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use mozjs::jsapi::{
+	GetCurrentRealmOrNull, GetObjectRealmOrNull, GetRealmPrivate, HandleString, JSContext, JSSecurityCallbacks, Realm,
+	RuntimeCode, SetRealmPrivate,
+};
+
+use ion::{Context, Error, ErrorKind, Local, Object};
+
+use crate::ContextExt;
+use crate::config::{Config, LogLevel};
+
+/// Determines whether code can be compiled at runtime, through `eval` and the `Function` constructor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EvalPolicy {
+	#[default]
+	Allow,
+	Disallow,
+}
+
+impl EvalPolicy {
+	pub fn is_allowed(&self) -> bool {
+		*self == EvalPolicy::Allow
+	}
+}
+
+static ALLOW: EvalPolicy = EvalPolicy::Allow;
+static DISALLOW: EvalPolicy = EvalPolicy::Disallow;
+
+/// Holds the runtime-wide [EvalPolicy].
+///
+/// Per-realm overrides are stored in the private data of the realm, pointing to a static policy, so that they are
+/// discarded along with the realm.
+#[derive(Default)]
+pub struct EvalPolicies {
+	pub(crate) default: EvalPolicy,
+}
+
+impl EvalPolicies {
+	pub(crate) fn policy_for(&self, realm: *mut Realm) -> EvalPolicy {
+		if realm.is_null() {
+			return self.default;
+		}
+		let policy = unsafe { GetRealmPrivate(realm) }.cast::<EvalPolicy>().cast_const();
+		unsafe { policy.as_ref() }.copied().unwrap_or(self.default)
+	}
+}
+
+/// Overrides the [EvalPolicy] of the realm of the given global.
+///
+/// Used to allow trusted tooling realms to compile code at runtime, when the runtime disallows it.
+pub fn set_realm_eval_policy(_: &Context, global: &Object, policy: EvalPolicy) {
+	let policy = match policy {
+		EvalPolicy::Allow => &ALLOW,
+		EvalPolicy::Disallow => &DISALLOW,
+	};
+	set_realm_private(global, (policy as *const EvalPolicy).cast_mut().cast());
+}
+
+/// Removes the [EvalPolicy] override of the realm of the given global.
+pub fn clear_realm_eval_policy(_: &Context, global: &Object) {
+	set_realm_private(global, ptr::null_mut());
+}
+
+fn set_realm_private(global: &Object, private: *mut c_void) {
+	let realm = unsafe { GetObjectRealmOrNull(global.handle().get()) };
+	if !realm.is_null() {
+		unsafe { SetRealmPrivate(realm, private) };
+	}
+}
+
+/// Determines which files scripts can read, through `file:` URLs and the `fs` module.
+#[derive(Clone, Debug, Default)]
+pub enum ReadPermission {
+	#[default]
+	All,
+	/// Only allows reading the given files, and files within the given directories.
+	Paths(Vec<PathBuf>),
+}
+
+impl ReadPermission {
+	/// Creates an allowlist of paths, which are kept both as given and canonicalised, so that they can be compared with
+	/// requested paths before and after symbolic links are resolved.
+	pub fn paths<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>) -> ReadPermission {
+		ReadPermission::Paths(allowed_paths(paths))
+	}
+
+	/// Checks if the given path can be read.
+	pub fn allows(&self, path: &Path) -> bool {
+		match self {
+			ReadPermission::All => true,
+			ReadPermission::Paths(paths) => paths.iter().any(|allowed| path.starts_with(allowed)),
+		}
+	}
+}
+
+fn allowed_paths<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>) -> Vec<PathBuf> {
+	let mut allowed = Vec::new();
+	for path in paths {
+		let path = path.into();
+		if let Ok(canonical) = crate::wasi_polyfills::canonicalize(&path) {
+			if canonical != path {
+				allowed.push(canonical);
+			}
+		}
+		allowed.push(path);
+	}
+	allowed
+}
+
+/// Checks if scripts running in the given context can read the given path.
+pub fn can_read(cx: &Context, path: &Path) -> bool {
+	unsafe { cx.get_private() }.read_permission.allows(path)
+}
+
+/// Determines which files scripts can write, through `fetch.download` and the `fs` module.
+/// Scripts cannot write any files unless they are allowed to, regardless of which files they can read.
+#[derive(Clone, Debug, Default)]
+pub enum WritePermission {
+	#[default]
+	None,
+	All,
+	/// Only allows writing the given files, and files within the given directories.
+	Paths(Vec<PathBuf>),
+}
+
+impl WritePermission {
+	/// Creates an allowlist of paths, with the same semantics as [ReadPermission::paths].
+	pub fn paths<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>) -> WritePermission {
+		WritePermission::Paths(allowed_paths(paths))
+	}
+
+	/// Checks if the given path can be written.
+	pub fn allows(&self, path: &Path) -> bool {
+		match self {
+			WritePermission::None => false,
+			WritePermission::All => true,
+			WritePermission::Paths(paths) => paths.iter().any(|allowed| path.starts_with(allowed)),
+		}
+	}
+}
+
+/// Checks if scripts running in the given context can write to the given canonical path.
+pub fn can_write(cx: &Context, path: &Path) -> bool {
+	unsafe { cx.get_private() }.write_permission.allows(path)
+}
+
+/// Resolves the location named by a path, resolving symbolic links in its parent directories, but not the path itself.
+/// Paths which do not exist yet are resolved from their closest existing ancestor.
+fn resolve_location(path: &Path) -> io::Result<PathBuf> {
+	let Some(file_name) = path.file_name() else {
+		return crate::wasi_polyfills::canonicalize(path);
+	};
+	let parent = match path.parent() {
+		Some(parent) if !parent.as_os_str().is_empty() => parent,
+		_ => Path::new("."),
+	};
+	match crate::wasi_polyfills::canonicalize(parent) {
+		Ok(parent) => Ok(parent.join(file_name)),
+		Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(resolve_location(parent)?.join(file_name)),
+		Err(error) => Err(error),
+	}
+}
+
+/// Checks that the location named by a path, and its target if it is an existing symbolic link, are allowed.
+fn check_path(path: &Path, allows: impl Fn(&Path) -> bool) -> bool {
+	let Ok(location) = resolve_location(path) else {
+		return false;
+	};
+	if !allows(&location) {
+		return false;
+	}
+	match crate::wasi_polyfills::canonicalize(path) {
+		Ok(target) => allows(&target),
+		Err(error) => error.kind() == io::ErrorKind::NotFound,
+	}
+}
+
+/// Checks if scripts running in the given context can read the given path, which does not need to be canonical.
+/// Throws an error if it is denied.
+pub fn check_read(cx: &Context, path: &Path) -> ion::Result<()> {
+	let allowed = match &unsafe { cx.get_private() }.read_permission {
+		ReadPermission::All => true,
+		permission @ ReadPermission::Paths(_) => check_path(path, |path| permission.allows(path)),
+	};
+	if allowed {
+		Ok(())
+	} else {
+		Err(Error::new(
+			format!("Read access denied: {}", path.display()),
+			ErrorKind::Normal,
+		))
+	}
+}
+
+/// Checks if scripts running in the given context can write to the given path, which does not need to be canonical
+/// or exist. Throws an error if it is denied.
+pub fn check_write(cx: &Context, path: &Path) -> ion::Result<()> {
+	let allowed = match &unsafe { cx.get_private() }.write_permission {
+		WritePermission::None => false,
+		WritePermission::All => true,
+		permission @ WritePermission::Paths(_) => check_path(path, |path| permission.allows(path)),
+	};
+	if allowed {
+		Ok(())
+	} else {
+		Err(Error::new(
+			format!("Write access denied: {}", path.display()),
+			ErrorKind::Normal,
+		))
+	}
+}
+
+pub(crate) static SECURITY_CALLBACKS: JSSecurityCallbacks = JSSecurityCallbacks {
+	contentSecurityPolicyAllows: Some(content_security_policy_allows),
+	subsumes: None,
+};
+
+unsafe extern "C" fn content_security_policy_allows(cx: *mut JSContext, kind: RuntimeCode, code: HandleString) -> bool {
+	let cx = unsafe { &Context::new_unchecked(cx) };
+	let realm = unsafe { GetCurrentRealmOrNull(cx.as_ptr()) };
+	let policy = unsafe { cx.get_private() }.eval_policies.policy_for(realm);
+
+	if policy.is_allowed() {
+		return true;
+	}
+
+	if Config::global().log_level >= LogLevel::Warn {
+		let kind = match kind {
+			RuntimeCode::JS => "eval",
+			RuntimeCode::WASM => "WebAssembly",
+		};
+		let code = ion::String::from(unsafe { Local::from_raw_handle(code) });
+		let code = code.to_owned(cx).unwrap_or_default();
+		let code = if code.chars().count() > 64 {
+			format!("{}...", code.chars().take(64).collect::<String>())
+		} else {
+			code
+		};
+		eprintln!("Blocked runtime code compilation ({}): {}", kind, code);
+	}
+	false
+}
@@ -0,0 +1,142 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::pin;
+use std::rc::Rc;
+
+use futures::{FutureExt, StreamExt};
+use futures::stream::FuturesUnordered;
+use http::{HeaderValue, StatusCode};
+use http::header::{CONTENT_TYPE, HOST};
+use hyper::Body;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use mozjs::jsapi::JSFunction;
+use mozjs::jsval::JSVal;
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::task::spawn_local;
+
+use ion::{
+	ClassDefinition, Context, Error, ErrorKind, Exception, Function, Object, Promise, PromiseFuture, ResultExc,
+	TracedHeap, Value,
+};
+
+use crate::globals::fetch::{Request, Response};
+use crate::handle;
+
+/// Executor for HTTP/2 streams, which are not used as connections are only served over HTTP/1.
+#[derive(Clone, Copy)]
+struct LocalExecutor;
+
+impl<F: Future + 'static> hyper::rt::Executor<F> for LocalExecutor {
+	fn execute(&self, future: F) {
+		spawn_local(future);
+	}
+}
+
+/// Serves HTTP/1 connections accepted by the listener, until `shutdown` is notified.
+///
+/// Each request is passed to the handler as a [Request], and the handler returns a [Response], or a promise which
+/// resolves to one. Response bodies are streamed to the connection as they are read. Connections which are still open
+/// when the server is shut down are closed.
+pub async fn serve(
+	cx: Context, listener: TcpListener, handler: TracedHeap<*mut JSFunction>, shutdown: Rc<Notify>,
+) -> ResultExc<()> {
+	let local_addr = listener
+		.local_addr()
+		.map_err(|error| Error::new(format!("Failed to get local address: {}", error), ErrorKind::Normal))?;
+	let handler = Rc::new(handler);
+	let mut connections = FuturesUnordered::new();
+	let mut shutdown = pin!(shutdown.notified().fuse());
+
+	loop {
+		let mut accept = pin!(listener.accept().fuse());
+		futures::select! {
+			_ = shutdown => return Ok(()),
+			accepted = accept => {
+				let stream = match accepted {
+					Ok((stream, _)) => stream,
+					Err(error) => {
+						eprintln!("Failed to accept connection: {}", error);
+						continue;
+					}
+				};
+
+				let cx = cx.duplicate();
+				let handler = Rc::clone(&handler);
+				let service = service_fn(move |request| {
+					let cx = cx.duplicate();
+					let handler = Rc::clone(&handler);
+					async move { Ok::<_, Infallible>(respond(cx, &handler, request, local_addr).await) }
+				});
+				let connection = Http::new()
+					.with_executor(LocalExecutor)
+					.http1_only(true)
+					.serve_connection(stream, service);
+				connections.push(connection);
+			}
+			_ = connections.select_next_some() => {}
+		}
+	}
+}
+
+async fn respond(
+	cx: Context, handler: &TracedHeap<*mut JSFunction>, mut request: hyper::Request<Body>, local_addr: SocketAddr,
+) -> hyper::Response<Body> {
+	if !request.headers().contains_key(HOST) {
+		if let Ok(host) = HeaderValue::from_str(&local_addr.to_string()) {
+			request.headers_mut().insert(HOST, host);
+		}
+	}
+
+	let promise = match call_handler(&cx, handler, request) {
+		Ok(promise) => promise,
+		Err(exception) => return error_response(&cx, exception),
+	};
+
+	let (cx, result) = PromiseFuture::new(cx, &promise).await;
+	let result = match result {
+		Ok(response) => to_hyper_response(&cx, response.get()),
+		Err(exception) => Err(Exception::Other(exception.get())),
+	};
+	result.unwrap_or_else(|exception| error_response(&cx, exception))
+}
+
+fn call_handler(
+	cx: &Context, handler: &TracedHeap<*mut JSFunction>, request: hyper::Request<Body>,
+) -> ResultExc<Promise> {
+	let request = Request::from_hyper_request(cx, request)?;
+	let request = Object::from(cx.root(Request::new_object(cx, Box::new(request))));
+	let handler = Function::from(handler.root(cx));
+	let response = handler
+		.call_with(cx, &Object::global(cx), [request])
+		.map_err(|error| handle::call_exception(cx, error))?;
+	Ok(Promise::resolved(cx, response))
+}
+
+fn to_hyper_response(cx: &Context, response: JSVal) -> ResultExc<hyper::Response<Body>> {
+	let response = Value::from(cx.root(response));
+	if !response.handle().is_object() {
+		return Err(Error::new("Request handler must return a Response", ErrorKind::Type).into());
+	}
+	let response = Response::get_mut_private(cx, &response.to_object(cx))?;
+	let body = response.take_body()?;
+	Ok(response.clone_with_body(Some(body)).into_hyper_response(cx)?)
+}
+
+fn error_response(cx: &Context, exception: Exception) -> hyper::Response<Body> {
+	eprintln!("Uncaught exception in request handler: {}", exception.format(cx));
+	let mut response = hyper::Response::new(Body::from("Internal Server Error"));
+	*response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+	response
+		.headers_mut()
+		.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain;charset=UTF-8"));
+	response
+}
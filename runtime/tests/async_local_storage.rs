@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use futures::executor::block_on;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "async-local-storage.js";
+const SCRIPT: &str = r#"
+const storage = new AsyncLocalStorage();
+globalThis.results = [];
+
+storage.run("request", () => {
+	Promise.resolve().then(() => results.push(`then:${storage.getStore()}`));
+	queueMicrotask(() => results.push(`microtask:${storage.getStore()}`));
+	setTimeout(() => results.push(`timeout:${storage.getStore()}`), 0);
+	(async () => {
+		await null;
+		results.push(`await:${storage.getStore()}`);
+	})();
+	storage.exit(() => results.push(`exit:${storage.getStore()}`));
+});
+results.push(`outside:${storage.getStore()}`);
+"#;
+
+#[test]
+fn async_local_storage() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	assert!(block_on(rt.run_event_loop()).is_ok());
+
+	let results = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let results = String::from_value(rt.cx(), &results, true, ()).unwrap();
+	assert_eq!(
+		"exit:undefined,outside:undefined,then:request,microtask:request,await:request,timeout:request",
+		results
+	);
+}
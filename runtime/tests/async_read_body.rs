@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{ClassDefinition, Context};
+use ion::conversions::FromValue;
+use ion::function::Opt;
+use ion::script::Script;
+use runtime::globals::fetch::{FetchBody, Request, RequestInfo, RequestInit};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "async-read-body.js";
+const BODY: &[u8] = b"uploaded from a native reader";
+const SCRIPT: &str = r#"
+globalThis.results = [request.method, request.headers.get("content-type")];
+request.text().then(text => results.push(text));
+"#;
+
+#[test]
+fn async_read_body() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let init = RequestInit {
+			method: Some(String::from("POST")),
+			body: Some(FetchBody::from_async_read(BODY, Some(BODY.len()))),
+			..RequestInit::default()
+		};
+		let info = RequestInfo::String(String::from("https://example.com/upload"));
+		let request = Request::constructor(rt.cx(), info, Opt(Some(init))).unwrap();
+		let request = Request::new_object(rt.cx(), Box::new(request));
+		rt.global().set_as(rt.cx(), "request", &request);
+
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("POST,application/octet-stream,uploaded from a native reader", result);
+}
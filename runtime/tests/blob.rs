@@ -0,0 +1,63 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "blob.js";
+const SCRIPT: &str = r#"
+const blob = new Blob(["hello ", new TextEncoder().encode("world")], { type: "text/plain" });
+globalThis.results = [blob.size, blob.type];
+
+(async () => {
+	results.push(await blob.slice().text());
+	results.push(await blob.slice(-5).text());
+	results.push(await blob.slice(2, -6).text());
+	results.push(await blob.slice(-100, 100).text());
+	results.push(blob.slice(8, 2).size, blob.slice(20).size);
+	results.push(blob.slice(0, 1, "Text/HTML").type, JSON.stringify(blob.slice(0, 1, "text/é").type));
+	results.push((await blob.slice(6).arrayBuffer()).byteLength);
+
+	let text = "";
+	const reader = blob.slice(0, 5).stream().getReader();
+	for (let chunk = await reader.read(); !chunk.done; chunk = await reader.read()) {
+		text += new TextDecoder().decode(chunk.value);
+	}
+	results.push(text);
+	results.push((await new Blob().stream().getReader().read()).done);
+})();
+"#;
+
+#[test]
+fn blob() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		r#"11,text/plain,hello world,world,llo,hello world,0,0,text/html,"",5,hello,true"#,
+		result
+	);
+}
@@ -0,0 +1,82 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::{env, fs, process};
+use std::rc::Rc;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::{ConversionBehavior, FromValue};
+use ion::module::Module;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::{Bundle, Loader};
+use runtime::RuntimeBuilder;
+
+const MAIN: &str = r#"
+import { value } from "./lib/value.js";
+import double from "./lib/double";
+import "spiderfire:fs";
+// import "./missing.js" is found, but skipped as it does not exist.
+globalThis.result = double(value);
+"#;
+const VALUE: &str = r#"export const value = 21;"#;
+const DOUBLE: &str = r#"
+import { value } from "../lib/value.js";
+export default function double(n) { return n * 2 + value - value; }
+"#;
+
+#[test]
+fn bundle() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug)).unwrap();
+
+	let dir = env::temp_dir().join(format!("spiderfire-bundle-{}", process::id()));
+	fs::create_dir_all(dir.join("lib")).unwrap();
+	fs::write(dir.join("main.js"), MAIN).unwrap();
+	fs::write(dir.join("lib/value.js"), VALUE).unwrap();
+	fs::write(dir.join("lib/double.js"), DOUBLE).unwrap();
+
+	let bundle = Bundle::collect(&dir.join("main.js")).unwrap().allow_net(true);
+	assert_eq!(3, bundle.modules().count());
+
+	let executable = dir.join("executable");
+	let compiled = dir.join("compiled");
+	fs::write(&executable, b"binary").unwrap();
+	bundle.embed(&executable, &compiled).unwrap();
+	assert!(Bundle::from_executable(&executable).unwrap().is_none());
+
+	// Recompiling replaces the embedded bundle.
+	bundle.embed(&compiled, &compiled.with_extension("again")).unwrap();
+	let bytes = fs::read(compiled.with_extension("again")).unwrap();
+	assert!(bytes.starts_with(b"binary"));
+	assert_eq!(fs::read(&compiled).unwrap(), bytes);
+
+	let embedded = Bundle::from_executable(&compiled).unwrap().unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(embedded.allow_net && !embedded.allow_env && !embedded.script);
+	assert_eq!(bundle.entry, embedded.entry);
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let embedded = Rc::new(embedded);
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<_, ()>::new()
+		.microtask_queue()
+		.modules(Loader::default().bundle(Rc::clone(&embedded)))
+		.build(cx);
+
+	let source = embedded.entry_source().unwrap().replace("import \"spiderfire:fs\";", "");
+	let result = Module::compile_and_evaluate(rt.cx(), "main.js", Some(&embedded.entry), &source);
+	assert!(result.is_ok(), "Exception was thrown in bundled main.js");
+
+	let result = Script::compile_and_evaluate(rt.cx(), &embedded.entry, "result").unwrap();
+	assert_eq!(
+		42,
+		i32::from_value(rt.cx(), &result, true, ConversionBehavior::Default).unwrap()
+	);
+}
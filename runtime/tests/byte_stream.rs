@@ -0,0 +1,70 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+use bytes::Bytes;
+use futures::stream::{self, StreamExt};
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::flags::PropertyFlags;
+use ion::script::Script;
+use runtime::globals::streams::readable_stream_from_byte_stream;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "byte-stream.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+
+(async () => {
+	const reader = stream.getReader();
+	for (let chunk = await reader.read(); !chunk.done; chunk = await reader.read()) {
+		results.push(new TextDecoder().decode(chunk.value));
+	}
+})();
+"#;
+
+#[test]
+fn byte_stream() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let polled = Rc::new(Cell::new(0));
+	let chunks = [Bytes::from_static(b"abcdefghij"), Bytes::from_static(b"xyz")];
+	let chunks = stream::iter(chunks).map(Ok::<_, io::Error>).inspect({
+		let polled = Rc::clone(&polled);
+		move |_| polled.set(polled.get() + 1)
+	});
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let stream = readable_stream_from_byte_stream(rt.cx(), chunks, 4).unwrap();
+		rt.global().define_as(rt.cx(), "stream", &stream, PropertyFlags::ENUMERATE);
+
+		// Only the chunk filling the queue of the stream is read before the stream is read by the script.
+		assert!(rt.run_event_loop().await.is_ok());
+		assert_eq!(1, polled.get());
+
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+	assert_eq!(2, polled.get());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("abcd,efgh,ij,xyz", result);
+}
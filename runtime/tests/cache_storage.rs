@@ -0,0 +1,69 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "cache-storage.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	const cache = await caches.open("v1");
+	await cache.put("https://example.com/page?id=1#top", new Response("page"));
+	await cache.put(
+		new Request("https://example.com/greeting", { headers: { "Accept-Language": "en" } }),
+		new Response("hello", { headers: { "Vary": "Accept-Language" } }),
+	);
+	await cache.put("file:///etc/passwd", new Response("file")).catch(error => results.push(error.name));
+
+	results.push(await caches.has("v1"), await caches.has("v2"), (await caches.keys()).join("|"));
+	results.push(await (await cache.match("https://example.com/page?id=1")).text());
+	results.push(String(await cache.match("https://example.com/page")));
+	results.push(await (await cache.match("https://example.com/page", { ignoreSearch: true })).text());
+
+	const greeting = "https://example.com/greeting";
+	results.push(String(await cache.match(greeting, { headers: { "Accept-Language": "fr" } })));
+	results.push(String(await cache.match(new Request(greeting, { headers: { "Accept-Language": "fr" } }))));
+	results.push(await (await caches.match(new Request(greeting, { headers: { "Accept-Language": "en" } }))).text());
+	results.push((await cache.keys()).map(request => request.url).join("|"));
+
+	results.push(await cache.delete("https://example.com/page?id=1"), (await cache.matchAll()).length);
+	results.push(await caches.delete("v1"), await caches.has("v1"));
+})();
+"#;
+
+#[test]
+fn cache_storage() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		"TypeError,true,false,v1,page,undefined,page,undefined,undefined,hello,\
+		https://example.com/page?id=1|https://example.com/greeting,true,1,true,false",
+		result
+	);
+}
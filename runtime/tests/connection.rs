@@ -0,0 +1,63 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use http::Version;
+use hyper::{Body, Request};
+
+use runtime::globals::fetch::{client_with_options, ClientOptions, ConnectionInfo, ProxyConfig};
+
+#[test]
+fn connection_info() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address = listener.local_addr().unwrap();
+	let server = thread::spawn(move || {
+		let (mut stream, _) = listener.accept().unwrap();
+		let mut request = [0; 1024];
+		let _ = stream.read(&mut request).unwrap();
+		stream
+			.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+			.unwrap();
+	});
+
+	let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let response = runtime.block_on(async {
+		// Proxies are not read from the environment, so that the connection is always made directly.
+		let options = ClientOptions {
+			proxy: ProxyConfig::default(),
+			..ClientOptions::default()
+		};
+		let client = client_with_options(&options).unwrap();
+		let request = Request::get(format!("http://{}/", address))
+			.header("Host", address.to_string())
+			.body(Body::empty())
+			.unwrap();
+		client.request(request).await.unwrap()
+	});
+	server.join().unwrap();
+
+	let info = response.extensions().get::<ConnectionInfo>().unwrap();
+	assert_eq!(Some(address), info.remote_address);
+	assert!(info.local_address.is_some());
+	assert!(!info.proxied);
+	assert!(info.connect_duration.is_some());
+	assert!(info.dns_duration.is_none());
+	assert!(info.tls_version.is_none());
+	assert!(info.cipher_suite.is_none());
+	assert!(info.tls_duration.is_none());
+
+	let info = ConnectionInfo {
+		version: Some(response.version()),
+		..info.clone()
+	};
+	assert_eq!(Version::HTTP_11, response.version());
+	assert_eq!(Some("http/1.1"), info.protocol());
+}
@@ -0,0 +1,77 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use http::{Method, StatusCode};
+use http::header::CONTENT_LENGTH;
+use hyper::Body;
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+use url::Url;
+
+use ion::{ClassDefinition, Context};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::Response;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "content-length.js";
+const SCRIPT: &str = r#"
+globalThis.results = [
+	response.contentLength,
+	new Response("hello").contentLength,
+	new Response(null).contentLength,
+	new Response(new ReadableStream()).contentLength,
+];
+response.arrayBuffer().then(buffer => results.push(buffer.byteLength, response.contentLength));
+"#;
+
+#[test]
+fn content_length() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let response = hyper::Response::builder()
+			.header(CONTENT_LENGTH, "11")
+			.body(Body::from("hello world"))
+			.unwrap();
+		let url = Url::parse("https://example.com/").unwrap();
+		let response = Response::from_hyper_response(rt.cx(), response, url.clone()).unwrap();
+		assert_eq!(Some(11), response.content_length());
+
+		// Responses which cannot have a body do not take their length from Content-Length.
+		let cases = [
+			(Method::HEAD, StatusCode::OK),
+			(Method::GET, StatusCode::NO_CONTENT),
+			(Method::GET, StatusCode::NOT_MODIFIED),
+		];
+		for (method, status) in cases {
+			let bodiless = hyper::Response::builder().status(status).header(CONTENT_LENGTH, "11").body(Body::empty());
+			let bodiless =
+				Response::from_hyper_response_with_timing(rt.cx(), bodiless.unwrap(), url.clone(), &method, None);
+			assert_eq!(None, bodiless.unwrap().content_length(), "{} {}", method, status);
+		}
+
+		let response = Response::new_object(rt.cx(), Box::new(response));
+		rt.global().set_as(rt.cx(), "response", &response);
+
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("11,5,0,,11,", result);
+}
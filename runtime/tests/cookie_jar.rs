@@ -0,0 +1,42 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use url::Url;
+
+use runtime::globals::fetch::CookieJar;
+
+#[test]
+fn cookie_jar() {
+	let jar = CookieJar::new();
+	let login = Url::parse("https://api.example.com/auth/login").unwrap();
+
+	assert!(jar.set_cookie(&login, "session=abc; Path=/; Secure; HttpOnly"));
+	assert!(jar.set_cookie(&login, "scope=auth"));
+	assert!(jar.set_cookie(&login, "shared=1; Domain=example.com"));
+	assert!(!jar.set_cookie(&login, "other=1; Domain=other.com"));
+	assert!(!jar.set_cookie(&Url::parse("http://api.example.com/").unwrap(), "insecure=1; Secure"));
+
+	let profile = Url::parse("https://api.example.com/users/me").unwrap();
+	assert_eq!(Some("session=abc; shared=1"), jar.cookie_header(&profile).as_deref());
+
+	let logout = Url::parse("https://api.example.com/auth/logout").unwrap();
+	assert_eq!(
+		Some("scope=auth; session=abc; shared=1"),
+		jar.cookie_header(&logout).as_deref()
+	);
+
+	let subdomain = Url::parse("https://www.example.com/").unwrap();
+	assert_eq!(Some("shared=1"), jar.cookie_header(&subdomain).as_deref());
+
+	let insecure = Url::parse("http://api.example.com/").unwrap();
+	assert_eq!(Some("shared=1"), jar.cookie_header(&insecure).as_deref());
+
+	assert!(jar.set_cookie(&login, "session=; Path=/; Max-Age=0"));
+	assert_eq!(Some("shared=1"), jar.cookie_header(&profile).as_deref());
+	assert_eq!(2, jar.all().len());
+}
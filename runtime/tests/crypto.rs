@@ -0,0 +1,97 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "crypto.js";
+const SCRIPT: &str = r#"
+const encoder = new TextEncoder();
+const hex = buffer => Array.from(new Uint8Array(buffer), byte => byte.toString(16).padStart(2, "0")).join("");
+
+globalThis.results = [];
+
+(async () => {
+	results.push(hex(await crypto.subtle.digest("SHA-256", encoder.encode("abc"))));
+
+	const hmac = await crypto.subtle.importKey("raw", encoder.encode("key"), { name: "HMAC", hash: "SHA-256" }, true, ["sign", "verify"]);
+	const signature = await crypto.subtle.sign("HMAC", hmac, encoder.encode("The quick brown fox jumps over the lazy dog"));
+	results.push(hex(signature));
+	results.push(await crypto.subtle.verify("HMAC", hmac, signature, encoder.encode("The quick brown fox")));
+
+	const aes = await crypto.subtle.generateKey({ name: "AES-GCM", length: 256 }, true, ["encrypt", "decrypt"]);
+	const iv = crypto.getRandomValues(new Uint8Array(12));
+	const ciphertext = await crypto.subtle.encrypt({ name: "AES-GCM", iv }, aes, encoder.encode("secret"));
+	const plaintext = await crypto.subtle.decrypt({ name: "AES-GCM", iv }, aes, ciphertext);
+	results.push(new TextDecoder().decode(plaintext));
+
+	const jwk = await crypto.subtle.exportKey("jwk", aes);
+	const imported = await crypto.subtle.importKey("jwk", jwk, "AES-GCM", false, ["decrypt"]);
+	results.push(jwk.alg, imported.algorithm.length, imported.usages.join("+"));
+	results.push(new TextDecoder().decode(await crypto.subtle.decrypt({ name: "AES-GCM", iv }, imported, ciphertext)));
+
+	await crypto.subtle.exportKey("raw", imported).catch(error => results.push(error.name));
+	await crypto.subtle.encrypt({ name: "AES-GCM", iv }, imported, ciphertext).catch(error => results.push(error.name));
+
+	const longIv = crypto.getRandomValues(new Uint8Array(16));
+	const shortTag = await crypto.subtle.encrypt({ name: "AES-GCM", iv: longIv, tagLength: 96 }, aes, encoder.encode("secret"));
+	results.push(shortTag.byteLength);
+	results.push(new TextDecoder().decode(await crypto.subtle.decrypt({ name: "AES-GCM", iv: longIv, tagLength: 96 }, aes, shortTag)));
+
+	const failures = [
+		crypto.subtle.encrypt({ name: "AES-GCM", iv, tagLength: 64 }, aes, encoder.encode("secret")),
+		crypto.subtle.encrypt({ name: "AES-GCM", iv: new Uint8Array(5) }, aes, encoder.encode("secret")),
+		crypto.subtle.encrypt({ name: "AES-GCM", iv, tagLength: 100 }, aes, encoder.encode("secret")),
+		crypto.subtle.decrypt({ name: "AES-GCM", iv: longIv, tagLength: 128 }, aes, shortTag),
+		crypto.subtle.digest("MD5", encoder.encode("abc")),
+		crypto.subtle.generateKey({ name: "AES-GCM", length: 256 }, true, []),
+	];
+	for (const failure of failures) {
+		await failure.catch(error => results.push(error.name));
+	}
+
+	try {
+		crypto.getRandomValues(new Uint8Array(65537));
+	} catch (error) {
+		results.push(error instanceof Error && error.name);
+	}
+})();
+"#;
+
+#[test]
+fn crypto() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad,\
+		 f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8,false,\
+		 secret,A256GCM,256,decrypt,secret,InvalidAccessError,InvalidAccessError,18,secret,\
+		 NotSupportedError,NotSupportedError,OperationError,OperationError,NotSupportedError,SyntaxError,\
+		 QuotaExceededError",
+		result
+	);
+}
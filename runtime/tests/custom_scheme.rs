@@ -0,0 +1,74 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::io;
+use std::path::Path;
+
+use bytes::Bytes;
+use futures::stream;
+use http::{HeaderMap, HeaderValue, StatusCode};
+use http::header::CONTENT_TYPE;
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{Context, Error};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{HeadersInit, Response, ResponseInit};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "custom-scheme.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	const response = await fetch("store://bucket/key", { headers: { "X-Version": "2" } });
+	results.push(response.status, response.url, response.headers.get("content-type"), await response.text());
+
+	await fetch("unknown://bucket/key").catch(() => results.push("unknown"));
+})();
+"#;
+
+#[test]
+fn custom_scheme() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	assert!(!rt.register_scheme("https", |_, _| async { Err(Error::none()) }));
+	assert!(rt.register_scheme("store", |cx, request| async move {
+		let version = request.headers.get("x-version").unwrap().to_str().unwrap().to_owned();
+		let chunks = [
+			request.url.host_str().unwrap().to_owned(),
+			request.url.path().to_owned(),
+			version,
+		];
+		let chunks = chunks.map(|chunk| Ok::<_, io::Error>(Bytes::from(chunk)));
+
+		let mut headers = HeaderMap::new();
+		headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+		let init = ResponseInit {
+			headers: HeadersInit::Map(headers),
+			status: StatusCode::OK,
+			status_text: None,
+		};
+		Response::from_stream(&cx, stream::iter(chunks), init)
+	}));
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("200,store://bucket/key,text/plain,bucket/key2,unknown", result);
+}
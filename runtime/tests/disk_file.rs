@@ -0,0 +1,75 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{ClassDefinition, Context};
+use ion::conversions::FromValue;
+use ion::flags::PropertyFlags;
+use ion::script::Script;
+use runtime::globals::file::{DISK_CHUNK_SIZE, File};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "disk-file.js";
+const SCRIPT: &str = r#"
+globalThis.results = [file.name, file.size, file.type];
+
+(async () => {
+	const text = await file.text();
+	results.push(text.length, text.slice(0, 6));
+	results.push(await file.slice(6, 11).text());
+	results.push((await file.slice(-5).arrayBuffer()).byteLength);
+
+	let chunks = 0;
+	let length = 0;
+	const reader = file.stream().getReader();
+	for (let chunk = await reader.read(); !chunk.done; chunk = await reader.read()) {
+		chunks++;
+		length += chunk.value.byteLength;
+	}
+	results.push(chunks, length);
+	results.push(new FileReaderSync().readAsText(file.slice(0, 5)));
+})();
+"#;
+
+#[test]
+fn disk_file() {
+	let contents = format!("hello world{}", "-".repeat(DISK_CHUNK_SIZE + 5));
+	let path = std::env::temp_dir().join(format!("spiderfire-disk-file-{}.txt", std::process::id()));
+	fs::write(&path, &contents).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let file = File::from_path(&path, Some(String::from("text/plain"))).unwrap();
+	let file = File::new_object(rt.cx(), Box::new(file));
+	rt.global().define_as(rt.cx(), "file", &file, PropertyFlags::ENUMERATE);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+	fs::remove_file(&path).unwrap();
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	let name = path.file_name().unwrap().to_str().unwrap();
+	let size = contents.len();
+	assert_eq!(
+		format!("{name},{size},text/plain,{size},hello ,world,5,2,{size},hello"),
+		result
+	);
+}
@@ -0,0 +1,50 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "dispose.js";
+const SCRIPT: &str = r#"
+const results = [];
+results.push(typeof Symbol.dispose, typeof Symbol.asyncDispose);
+
+const readable = new ReadableStream();
+const reader = readable.getReader();
+reader[Symbol.dispose]();
+results.push(readable.locked);
+
+const writable = new WritableStream();
+const writer = writable.getWriter();
+writer[Symbol.dispose]();
+results.push(writable.locked);
+
+const controller = new AbortController();
+controller[Symbol.dispose]();
+results.push(controller.signal.aborted);
+
+results.join();
+"#;
+
+#[test]
+fn dispose() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	let result = String::from_value(rt.cx(), &result.unwrap(), true, ()).unwrap();
+	assert_eq!("symbol,symbol,false,false,true", result);
+}
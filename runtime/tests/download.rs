@@ -0,0 +1,81 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::{env, fs};
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+use runtime::security::WritePermission;
+use runtime::wasi_polyfills::canonicalize;
+
+const FILE_NAME: &str = "download.js";
+
+#[test]
+fn download() {
+	let directory = canonicalize(env::temp_dir()).unwrap().join("spiderfire-download");
+	fs::create_dir_all(&directory).unwrap();
+	let allowed = directory.join("allowed");
+	fs::create_dir_all(&allowed).unwrap();
+	let target = allowed.join("download.txt");
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.write_permission(WritePermission::paths([allowed.clone()]))
+		.build(cx);
+
+	let script = format!(
+		r#"
+		globalThis.results = [];
+		(async () => {{
+			const progress = [];
+			const written = await fetch.download("data:text/plain,Hello%20World", {target:?}, {{
+				onProgress: ({{ loaded }}) => progress.push(loaded),
+			}});
+			results.push(written, progress.at(-1));
+
+			await fetch.download("data:text/plain,denied", {denied:?})
+				.catch(error => results.push(error.message.startsWith("Write access denied")));
+
+			const controller = new AbortController();
+			controller.abort("stopped");
+			await fetch.download("data:text/plain,aborted", {target:?}, {{ signal: controller.signal }})
+				.catch(reason => results.push(reason));
+		}})();
+		"#,
+		target = target.to_str().unwrap(),
+		denied = directory.join("denied.txt").to_str().unwrap(),
+	);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), &script).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("11,11,true,stopped", result);
+
+	assert_eq!("Hello World", fs::read_to_string(&target).unwrap());
+	assert!(!directory.join("denied.txt").exists());
+	assert!(!allowed.join("download.txt.part").exists());
+
+	fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,118 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::{env, fs, thread};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{ClientOptions, ProxyConfig};
+use runtime::RuntimeBuilder;
+use runtime::security::{ReadPermission, WritePermission};
+use runtime::wasi_polyfills::canonicalize;
+
+const FILE_NAME: &str = "download-network.js";
+const CHUNK: &str = "0123456789abcdef";
+const CHUNKS: usize = 65536;
+
+/// Serves a single response whose body is written in many chunks.
+fn serve(listener: TcpListener) {
+	let (mut stream, _) = listener.accept().unwrap();
+	let mut reader = BufReader::new(&stream);
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line).unwrap() == 0 || line.trim().is_empty() {
+			break;
+		}
+	}
+
+	let head = format!(
+		"HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+		CHUNK.len() * CHUNKS
+	);
+	stream.write_all(head.as_bytes()).unwrap();
+	for _ in 0..CHUNKS {
+		stream.write_all(CHUNK.as_bytes()).unwrap();
+		stream.flush().unwrap();
+	}
+}
+
+#[test]
+fn download_network() {
+	let directory = canonicalize(env::temp_dir()).unwrap().join("spiderfire-download-network");
+	fs::create_dir_all(&directory).unwrap();
+	let readable = directory.join("readable");
+	let writable = directory.join("writable");
+	fs::create_dir_all(&readable).unwrap();
+	fs::create_dir_all(&writable).unwrap();
+	let target = writable.join("download.txt");
+
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address = listener.local_addr().unwrap();
+	thread::spawn(move || serve(listener));
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let options = ClientOptions {
+		proxy: ProxyConfig::default(),
+		..ClientOptions::default()
+	};
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.client_options(options)
+		.unwrap()
+		.read_permission(ReadPermission::paths([readable.clone(), writable.clone()]))
+		.write_permission(WritePermission::paths([writable.clone()]))
+		.build(cx);
+
+	// Being able to read a directory does not allow writing to it.
+	let script = format!(
+		r#"
+		globalThis.results = [];
+		(async () => {{
+			await fetch.download("data:text/plain,denied", {denied:?})
+				.catch(error => results.push(error.message.startsWith("Write access denied")));
+
+			const progress = [];
+			const written = await fetch.download("http://{address}/", {target:?}, {{
+				onProgress: ({{ loaded, total }}) => progress.push([loaded, total]),
+			}});
+			results.push(written, progress.length > 1, progress.at(-1).join("/"));
+		}})();
+		"#,
+		denied = readable.join("denied.txt").to_str().unwrap(),
+		target = target.to_str().unwrap(),
+	);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), &script).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let length = CHUNK.len() * CHUNKS;
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(format!("true,{length},true,{length}/{length}"), result);
+
+	assert_eq!(CHUNK.repeat(CHUNKS), fs::read_to_string(&target).unwrap());
+	assert!(!readable.join("denied.txt").exists());
+	assert!(!writable.join("download.txt.part").exists());
+
+	fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::{env, fs, thread};
+use std::path::Path;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+use runtime::security::WritePermission;
+use runtime::wasi_polyfills::canonicalize;
+
+const FILE_NAME: &str = "download-terminate.js";
+
+#[test]
+fn download_terminate() {
+	let directory = canonicalize(env::temp_dir()).unwrap().join("spiderfire-download-terminate");
+	fs::create_dir_all(&directory).unwrap();
+	let target = directory.join("download.txt");
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.write_permission(WritePermission::paths([directory.clone()]))
+		.build(cx);
+
+	let script = format!(
+		r#"
+		fetch.download("data:text/plain,Hello%20World", {target:?}, {{
+			onProgress: () => {{ while (true) {{}} }},
+		}});
+		"#,
+		target = target.to_str().unwrap(),
+	);
+
+	let handle = rt.handle();
+	thread::spawn(move || {
+		thread::sleep(Duration::from_millis(50));
+		assert!(handle.terminate());
+	});
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	let report = local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), &script).unwrap();
+		rt.run_event_loop().await.unwrap_err().unwrap()
+	});
+	assert!(report.format(rt.cx()).contains("Runtime was terminated"));
+	assert!(rt.handle().is_terminated());
+	assert!(!target.exists());
+
+	fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "encoding.js";
+const SCRIPT: &str = r#"
+const results = [];
+
+const { read, written } = new TextEncoder().encodeInto("a😀b", new Uint8Array(5));
+results.push(read, written);
+
+const windows1252 = new TextDecoder("windows-1252");
+results.push(windows1252.encoding, windows1252.decode(new Uint8Array([0x80, 0xE9])));
+results.push(new TextDecoder(" Latin1 ").encoding);
+
+const streaming = new TextDecoder();
+results.push(streaming.decode(new Uint8Array([0xF0, 0x9F]), { stream: true }) + streaming.decode(new Uint8Array([0x98, 0x80])));
+
+const bom = new Uint8Array([0xEF, 0xBB, 0xBF, 0x41]);
+results.push(new TextDecoder().decode(bom).length, new TextDecoder("utf-8", { ignoreBOM: true }).decode(bom).length);
+results.push(new TextDecoder("windows-1252").decode(bom).length);
+
+const fatal = new TextDecoder("utf-8", { fatal: true });
+try {
+	fatal.decode(new Uint8Array([0xF0, 0x9F]));
+} catch (error) {
+	results.push(error.name);
+}
+results.push(fatal.decode(new Uint8Array([0x41])));
+
+try {
+	new TextDecoder("replacement");
+} catch (error) {
+	results.push(error.name);
+}
+
+results.join();
+"#;
+
+#[test]
+fn encoding() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		"3,5,windows-1252,€é,windows-1252,😀,1,2,4,TypeError,A,RangeError",
+		result
+	);
+}
@@ -0,0 +1,63 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::ToValue;
+use ion::format::{Config, format_value};
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "error-cause.js";
+const SCRIPT: &str = r#"
+function parse() {
+	JSON.parse("{");
+}
+
+function body() {
+	try {
+		parse();
+	} catch (error) {
+		throw new TypeError("Failed to read body", { cause: error });
+	}
+}
+
+try {
+	body();
+} catch (error) {
+	const aggregate = new AggregateError([new RangeError("first"), error], "Failed to fetch");
+	aggregate.cause = aggregate;
+	throw aggregate;
+}
+"#;
+
+#[test]
+fn error_cause() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	let report = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap_err().unwrap();
+	assert_eq!(2, report.errors.len());
+	assert!(report.cause.is_none());
+
+	let formatted = report.format(rt.cx());
+	assert!(formatted.starts_with("Uncaught AggregateError"));
+	assert!(formatted.contains("\n  [errors][0]: RangeError at error-cause.js"));
+	assert!(formatted.contains("\n  [errors][1]: TypeError at error-cause.js:10"));
+	assert!(formatted.contains("\n    [cause]: SyntaxError"));
+	assert!(formatted.contains("\n      parse@error-cause.js:3"));
+
+	let exception = report.exception.as_value(rt.cx());
+	let logged = format_value(rt.cx(), Config::default(), &exception).to_string();
+	assert!(logged.starts_with("AggregateError at error-cause.js:17"));
+	assert!(logged.contains("[errors][1]: TypeError"));
+}
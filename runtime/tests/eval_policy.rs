@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::jsapi::{JSAutoRealm, OnNewGlobalHookOption};
+use mozjs::rust::{JSEngine, Runtime, SIMPLE_GLOBAL_CLASS};
+
+use ion::Context;
+use ion::object::new_global;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::RuntimeBuilder;
+use runtime::security::{clear_realm_eval_policy, EvalPolicy, set_realm_eval_policy};
+
+const FILE_NAME: &str = "eval.js";
+
+#[test]
+fn eval_policy() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().eval_policy(EvalPolicy::Disallow).build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "eval('1 + 1')");
+	assert!(result.is_err(), "eval was not blocked");
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "new Function('return 1')");
+	assert!(result.is_err(), "Function constructor was not blocked");
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "1 + 1");
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	let trusted = new_global(
+		rt.cx(),
+		&SIMPLE_GLOBAL_CLASS,
+		None,
+		OnNewGlobalHookOption::FireOnNewGlobalHook,
+		None,
+	);
+	set_realm_eval_policy(rt.cx(), &trusted, EvalPolicy::Allow);
+	let _realm = JSAutoRealm::new(rt.cx().as_ptr(), trusted.handle().get());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "eval('1 + 1')");
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	clear_realm_eval_policy(rt.cx(), &trusted);
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "eval('1 + 1')");
+	assert!(result.is_err(), "eval was not blocked after clearing the override");
+}
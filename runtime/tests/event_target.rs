@@ -0,0 +1,69 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "event-target.js";
+const SCRIPT: &str = r#"
+const results = [];
+const target = new EventTarget();
+
+const listener = event => results.push(`bubble:${event.eventPhase}:${event.target === target}`);
+target.addEventListener("test", listener);
+target.addEventListener("test", listener);
+target.addEventListener("test", () => results.push("capture"), true);
+target.addEventListener("test", () => results.push("once"), { once: true });
+target.addEventListener("test", { handleEvent: event => results.push(`object:${event.detail}`) });
+target.addEventListener("test", event => event.preventDefault(), { passive: true });
+
+results.push(`passive:${target.dispatchEvent(new CustomEvent("test", { cancelable: true, detail: 1 }))}`);
+target.removeEventListener("test", listener);
+results.push(`dispatched:${target.dispatchEvent(new CustomEvent("test", { detail: 2 }))}`);
+
+const cancellable = new Event("cancel", { cancelable: true });
+target.addEventListener("cancel", event => {
+	event.preventDefault();
+	event.stopImmediatePropagation();
+});
+target.addEventListener("cancel", () => results.push("stopped"));
+results.push(`cancelled:${!target.dispatchEvent(cancellable)}:${cancellable.defaultPrevented}:${cancellable.eventPhase}`);
+
+const controller = new AbortController();
+controller.signal.onabort = event => results.push(`onabort:${event.isTrusted}`);
+controller.signal.addEventListener("abort", () => results.push(`listener:${controller.signal.aborted}`));
+controller.abort();
+controller.abort();
+results.push(`signal:${controller.signal === controller.signal && controller.signal instanceof EventTarget}`);
+
+results.join();
+"#;
+
+#[test]
+fn event_target() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.as_ref().unwrap_err());
+	let results = String::from_value(rt.cx(), &result.unwrap(), true, ()).unwrap();
+	assert_eq!(
+		"capture,bubble:2:true,once,object:1,passive:true,capture,object:2,dispatched:true,cancelled:true:true:0,onabort:true,listener:true,signal:true",
+		results
+	);
+}
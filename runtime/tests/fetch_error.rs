@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::thread;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{ClientOptions, ProxyConfig};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "fetch-error.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+const describe = error => [error instanceof TypeError, error.phase, error.ioErrorKind, error.url].join("|");
+
+(async () => {
+	await fetch(`http://${refused}/`).catch(error => results.push(describe(error)));
+	await fetch("http://127.0.0.1:6000/").catch(error => results.push(describe(error), error.message.includes("Port 6000 is blocked")));
+
+	const response = await fetch(`http://${truncated}/`);
+	const reader = response.body.getReader();
+	try {
+		while (!(await reader.read()).done);
+	} catch (error) {
+		results.push(error.phase);
+	}
+})();
+"#;
+
+/// Serves a single response whose body is shorter than its Content-Length.
+fn serve_truncated(listener: TcpListener) {
+	let (mut stream, _) = listener.accept().unwrap();
+	let mut request = [0; 1024];
+	let _ = stream.read(&mut request).unwrap();
+	stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nab").unwrap();
+}
+
+#[test]
+fn fetch_error() {
+	let refused = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let truncated = listener.local_addr().unwrap();
+	thread::spawn(move || serve_truncated(listener));
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let options = ClientOptions {
+		proxy: ProxyConfig::default(),
+		..ClientOptions::default()
+	};
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.client_options(options)
+		.unwrap()
+		.build(cx);
+	rt.global().set_as(rt.cx(), "refused", &refused.to_string());
+	rt.global().set_as(rt.cx(), "truncated", &truncated.to_string());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		format!("true|connect|ConnectionRefused|http://{refused}/,true|||,true,body"),
+		result
+	);
+}
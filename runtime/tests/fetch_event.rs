@@ -0,0 +1,78 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use http::header::HOST;
+use hyper::Body;
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{ClassDefinition, Context, Object};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{dispatch_fetch, Request, Response};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "fetch-event.js";
+const SCRIPT: &str = r#"
+addEventListener("fetch", event => {
+	const { pathname } = new URL(event.request.url);
+	if (pathname === "/skip") {
+		return;
+	}
+	event.respondWith(event.request.text().then(body => new Response(`${event.request.method} ${pathname} ${body}`)));
+});
+addEventListener("fetch", event => reached.push(new URL(event.request.url).pathname));
+globalThis.reached = [];
+"#;
+
+fn request(cx: &Context, path: &str) -> Request {
+	let request = hyper::Request::post(path).header(HOST, "localhost").body(Body::from("body")).unwrap();
+	Request::from_hyper_request(cx, request).unwrap()
+}
+
+#[test]
+fn fetch_event() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.service_worker_scope()
+		.build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+
+		let skipped = dispatch_fetch(rt.cx().duplicate(), request(rt.cx(), "/skip")).await;
+		assert!(skipped.unwrap().is_none());
+
+		let (response, event_loop) = futures::join!(
+			dispatch_fetch(rt.cx().duplicate(), request(rt.cx(), "/echo")),
+			rt.run_event_loop()
+		);
+		assert!(event_loop.is_ok());
+		let response = Object::from(response.unwrap().unwrap().root(rt.cx()));
+		let response = Response::get_mut_private(rt.cx(), &response).unwrap();
+		let body = response.take_body().unwrap();
+		let response = response.clone_with_body(Some(body)).into_hyper_response(rt.cx()).unwrap();
+
+		let (body, event_loop) = futures::join!(hyper::body::to_bytes(response.into_body()), rt.run_event_loop());
+		assert!(event_loop.is_ok());
+		assert_eq!(&b"POST /echo body"[..], &body.unwrap()[..]);
+	});
+
+	let reached = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "reached.join()").unwrap();
+	let reached = String::from_value(rt.cx(), &reached, true, ()).unwrap();
+	assert_eq!("/skip", reached);
+}
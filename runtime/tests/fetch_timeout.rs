@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use hyper::{Body, Request};
+use url::Url;
+
+use runtime::globals::fetch::{
+	client_with_options, ClientOptions, FetchError, FetchErrorPhase, FetchTimeout, FetchTimeouts,
+};
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+fn send(timeouts: FetchTimeouts, url: &str) -> FetchError {
+	let url = Url::parse(url).unwrap();
+	let options = ClientOptions { timeouts, ..ClientOptions::default() };
+	let client = client_with_options(&options).unwrap();
+	let request = Request::get(url.as_str())
+		.header("Host", format!("{}:{}", url.host_str().unwrap(), url.port().unwrap()))
+		.body(Body::empty())
+		.unwrap();
+
+	let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	runtime.block_on(client.send(request, Some(url))).unwrap_err()
+}
+
+#[test]
+fn fetch_timeouts() {
+	// Connections are accepted by the listener's backlog, but nothing is ever sent.
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address = listener.local_addr().unwrap();
+
+	let timeouts = FetchTimeouts {
+		first_byte: Some(TIMEOUT),
+		..FetchTimeouts::default()
+	};
+	let error = send(timeouts, &format!("http://{}/", address));
+	assert_eq!(Some(FetchTimeout::FirstByte), error.timeout);
+	assert_eq!(Some(FetchErrorPhase::Response), error.phase);
+
+	// The first byte timeout does not start until the connection is established.
+	let timeouts = FetchTimeouts {
+		tls: Some(TIMEOUT),
+		first_byte: Some(TIMEOUT / 2),
+		..FetchTimeouts::default()
+	};
+	let error = send(timeouts, &format!("https://{}/", address));
+	assert_eq!(Some(FetchTimeout::Tls), error.timeout);
+	assert_eq!(Some(FetchErrorPhase::Tls), error.phase);
+
+	assert_eq!("DNS_TIMEOUT", FetchTimeout::Dns.code());
+	assert_eq!("CONNECT_TIMEOUT", FetchTimeout::Connect.code());
+	assert_eq!(FetchErrorPhase::Connect, FetchTimeout::Connect.phase());
+	drop(listener);
+}
@@ -0,0 +1,79 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+use url::Url;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+use runtime::security::ReadPermission;
+use runtime::wasi_polyfills::canonicalize;
+
+const FILE_NAME: &str = "file-permission.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	for (const url of urls) {
+		const response = await fetch(url);
+		results.push(response.status);
+	}
+})();
+"#;
+
+#[test]
+fn file_permission() {
+	let directory = canonicalize(env::temp_dir()).unwrap().join("spiderfire-file-permission");
+	let allowed = directory.join("allowed");
+	let denied = directory.join("denied");
+	fs::create_dir_all(&allowed).unwrap();
+	fs::create_dir_all(&denied).unwrap();
+	fs::write(allowed.join("file.txt"), "allowed").unwrap();
+	fs::write(denied.join("file.txt"), "denied").unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.read_permission(ReadPermission::paths([allowed.clone()]))
+		.build(cx);
+
+	// Paths outside the allowed directory are denied whether they exist or not.
+	let urls: Vec<_> = [
+		allowed.join("file.txt"),
+		allowed.join("missing.txt"),
+		denied.join("file.txt"),
+		denied.join("missing.txt"),
+		allowed.join("..").join("denied").join("file.txt"),
+	]
+	.iter()
+	.map(|path| Url::from_file_path(path).unwrap().to_string())
+	.collect();
+	rt.global().set_as(rt.cx(), "urls", &urls);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+	fs::remove_dir_all(&directory).unwrap();
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("200,404,403,403,403", result);
+}
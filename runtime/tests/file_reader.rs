@@ -0,0 +1,75 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "file-reader.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+const blob = new Blob(["hello"]);
+
+const reader = new FileReader();
+results.push(reader instanceof EventTarget, reader.readyState);
+reader.addEventListener("loadstart", event => results.push(event.type, event.total));
+reader.onload = event => {
+	results.push(event.type, event instanceof ProgressEvent, event.loaded, reader.result, reader.readyState);
+};
+reader.onloadend = () => {
+	results.push("loadend");
+
+	const dataReader = new FileReader();
+	dataReader.onload = () => results.push(dataReader.result);
+	dataReader.readAsDataURL(blob);
+};
+reader.readAsText(blob);
+results.push(reader.readyState);
+
+try {
+	reader.readAsArrayBuffer(blob);
+} catch (error) {
+	results.push(error instanceof TypeError);
+}
+
+const aborted = new FileReader();
+aborted.onload = () => results.push("unexpected load");
+aborted.onabort = event => results.push(event.type);
+aborted.onloadend = () => results.push(String(aborted.result), aborted.readyState);
+aborted.readAsArrayBuffer(blob);
+aborted.abort();
+"#;
+
+#[test]
+fn file_reader() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		"true,0,1,true,abort,null,2,loadstart,5,load,true,5,hello,2,loadend,\
+		 data:application/octet-stream;base64,aGVsbG8=",
+		result
+	);
+}
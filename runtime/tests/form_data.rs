@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "form-data.js";
+const SCRIPT: &str = r#"
+const form = new FormData();
+form.append("a", "1");
+form.append("b", "2");
+form.append("a", "3");
+form.set("a", "4");
+form.append("c", "5");
+form.delete("c");
+form.append("file", new Blob(["contents"], { type: "text/plain" }), "name\".txt");
+
+globalThis.results = [form.getAll("a").join("+"), form.has("c"), [...form.keys()].join("+")];
+
+(async () => {
+	const request = new Request("https://example.com", { method: "POST", body: form });
+	const contentType = request.headers.get("content-type");
+	const boundary = contentType.split("boundary=")[1];
+	const text = await request.text();
+	results.push(text.startsWith(`--${boundary}\r\n`), text.endsWith(`--${boundary}--\r\n`));
+	results.push(text.includes(`filename="name%22.txt"\r\nContent-Type: text/plain\r\n\r\ncontents\r\n`));
+
+	const parsed = await new Response(form).formData();
+	const file = parsed.get("file");
+	results.push([...parsed.values()].length, parsed.get("a"), file.name, await file.text());
+})();
+"#;
+
+#[test]
+fn form_data() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("4,false,a+b+file,true,true,true,3,4,name%22.txt,contents", result);
+}
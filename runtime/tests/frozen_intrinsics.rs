@@ -0,0 +1,47 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "frozen_intrinsics.js";
+const SCRIPT: &str = r#"
+const results = [];
+
+Object.prototype.polluted = true;
+results.push(({}).polluted === undefined);
+results.push(Object.isFrozen(Array.prototype));
+results.push(Object.isFrozen(Object.getPrototypeOf(async function () {})));
+
+Date.prototype.patched = true;
+results.push(new Date().patched);
+
+globalThis.defined = true;
+results.push(defined);
+
+results.join(",");
+"#;
+
+#[test]
+fn frozen_intrinsics() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().freeze_intrinsics(["Date"]).build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	assert_eq!(
+		"true,true,true,true,true",
+		String::from_value(rt.cx(), &result, true, ()).unwrap()
+	);
+}
@@ -0,0 +1,50 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::script::Script;
+use runtime::gc::{GcOptions, GcPhase, GcReason};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "gc.js";
+
+#[test]
+fn gc() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.gc_options(GcOptions {
+			incremental: Some(false),
+			..GcOptions::default()
+		})
+		.gc_global()
+		.build(cx);
+	assert!(!rt.gc_stats().incremental);
+
+	let slices = Rc::new(RefCell::new(Vec::new()));
+	let recorded = Rc::clone(&slices);
+	rt.set_gc_slice_handler(move |slice| recorded.borrow_mut().push(*slice));
+
+	let collections = rt.gc_stats().major_collections;
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "gc(); gc(true);");
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	rt.gc(GcReason::Api);
+	assert!(rt.gc_stats().major_collections >= collections + 3);
+
+	let slices = slices.borrow();
+	let cycles: Vec<_> = slices.iter().filter(|slice| slice.phase == GcPhase::CycleEnd).collect();
+	assert!(cycles.len() >= 3);
+	assert!(cycles.iter().all(|cycle| cycle.duration.is_some()));
+	assert_eq!(Some(GcPhase::CycleBegin), slices.first().map(|slice| slice.phase));
+}
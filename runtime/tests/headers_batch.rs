@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use http::header::{CACHE_CONTROL, SERVER};
+use http::HeaderValue;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{ClassDefinition, Context, Object};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{Headers, HeadersKind};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "headers-batch.js";
+const SCRIPT: &str = r#"
+const headers = new Headers({ "x-old": "1" });
+headers.setAll({ "x-old": "2", "x-list": ["a", "b"] });
+headers.appendAll([["x-append", "1"], ["x-append", "2"]]);
+
+let error = null;
+try {
+	headers.appendAll([["x-valid", "1"], ["invalid name", "2"]]);
+} catch (e) {
+	error = e.name;
+}
+
+globalThis.native = new Headers();
+[headers.get("x-old"), headers.get("x-list"), headers.get("x-append"), headers.has("x-valid"), error].join("|")
+"#;
+
+#[test]
+fn headers_batch() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("2|a, b|1, 2|false|Error", result);
+
+	let native: Object = rt.global().get_as(rt.cx(), "native", true, ()).unwrap().unwrap();
+	let headers = Headers::get_mut_private(rt.cx(), &native).unwrap();
+	headers
+		.with_header_map(|map| {
+			map.insert(SERVER, HeaderValue::from_static("spiderfire"));
+			map.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+		})
+		.unwrap();
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "[...native].join(';')").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("cache-control,no-store;server,spiderfire", result);
+
+	let mut immutable = Headers::new(HeadersKind::Immutable);
+	assert!(immutable.with_header_map(|map| map.clear()).is_err());
+}
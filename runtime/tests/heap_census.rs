@@ -0,0 +1,45 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "heap_census.js";
+const SCRIPT: &str = "globalThis.retained = Array.from({ length: 1000 }, () => new Map());";
+const CHECK: &str = r#"
+	const { breakdown, census } = JSON.parse(heapCensus);
+	[
+		breakdown.by,
+		census.objects.Map.count >= 1000,
+		census.objects.Map.bytes > 0,
+		census.strings.count > 0,
+	].join(",")
+"#;
+
+#[test]
+fn heap_census() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+
+	let census = rt.heap_census();
+	assert!(census.is_ok(), "Failed to take census of heap");
+	rt.global().set_as(rt.cx(), "heapCensus", &census.unwrap());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), CHECK).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("coarseType,true,true,true", result);
+}
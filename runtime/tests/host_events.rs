@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+use runtime::globals::host_events::{host_event_channel, TrySendError};
+
+const FILE_NAME: &str = "host-events.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+const ignored = () => results.push("ignored");
+hostEvents.on("reload", ignored);
+hostEvents.off("reload", ignored);
+hostEvents.on("reload", data => results.push(`reload:${data}`));
+hostEvents.on("shutdown", data => results.push(`shutdown:${data}`));
+"#;
+
+#[test]
+fn host_events() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let (sender, receiver) = host_event_channel::<String>(2);
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.host_events(receiver)
+		.build(cx);
+
+	sender.try_emit("reload", String::from("config.json")).unwrap();
+	sender.try_emit("shutdown", String::from("5s")).unwrap();
+	assert_eq!(2, sender.pending());
+	assert!(matches!(
+		sender.try_emit("reload", String::from("dropped")),
+		Err(TrySendError::Full(_))
+	));
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+		let emitter = sender.clone();
+		drop(sender);
+		tokio::task::spawn_local(async move {
+			emitter.emit("reload", String::from("again")).await.unwrap();
+		});
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("reload:config.json,shutdown:5s,reload:again", result);
+}
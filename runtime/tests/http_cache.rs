@@ -0,0 +1,152 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use http::header::{
+	ACCEPT_LANGUAGE, AUTHORIZATION, CACHE_CONTROL, COOKIE, ETAG, HeaderName, LAST_MODIFIED, SET_COOKIE, VARY,
+};
+use url::Url;
+
+use runtime::globals::fetch::{
+	CachedResponse, client_with_options, ClientOptions, default_http_cache, HttpCache, MemoryCache,
+};
+
+fn response(headers: &[(HeaderName, &'static str)], request_headers: &HeaderMap) -> Option<CachedResponse> {
+	let headers =
+		HeaderMap::from_iter(headers.iter().map(|(name, value)| (name.clone(), HeaderValue::from_static(value))));
+	CachedResponse::new(
+		&Method::GET,
+		request_headers,
+		StatusCode::OK,
+		Some(String::from("OK")),
+		&headers,
+		Bytes::from_static(b"body"),
+	)
+}
+
+#[test]
+fn freshness() {
+	let request_headers = HeaderMap::new();
+
+	let fresh = response(&[(CACHE_CONTROL, "max-age=60")], &request_headers).unwrap();
+	assert!(fresh.is_fresh());
+
+	let stale = response(&[(CACHE_CONTROL, "max-age=0"), (ETAG, "\"v1\"")], &request_headers).unwrap();
+	assert!(!stale.is_fresh());
+
+	let no_cache = response(&[(CACHE_CONTROL, "no-cache, max-age=60")], &request_headers).unwrap();
+	assert!(!no_cache.is_fresh());
+
+	assert!(response(&[(CACHE_CONTROL, "no-store")], &request_headers).is_none());
+	assert!(response(&[(CACHE_CONTROL, "max-age=60"), (VARY, "*")], &request_headers).is_none());
+
+	// Responses without an explicit lifetime are not stored heuristically.
+	assert!(response(&[], &request_headers).is_none());
+	assert!(response(&[(LAST_MODIFIED, "Thu, 01 Jan 2015 00:00:00 GMT")], &request_headers).is_none());
+}
+
+#[test]
+fn credentials() {
+	for (name, value) in [(AUTHORIZATION, "Bearer token"), (COOKIE, "session=1")] {
+		let mut request_headers = HeaderMap::new();
+		request_headers.insert(name, HeaderValue::from_static(value));
+
+		assert!(response(&[(CACHE_CONTROL, "max-age=60")], &request_headers).is_none());
+		assert!(response(&[(CACHE_CONTROL, "max-age=60, must-revalidate")], &request_headers).is_none());
+		assert!(response(&[(CACHE_CONTROL, "public, max-age=60")], &request_headers).is_some());
+	}
+}
+
+#[test]
+fn shared() {
+	let request_headers = HeaderMap::new();
+
+	// Responses for a single user, and the cookies they set, are not shared with other requests.
+	assert!(response(&[(CACHE_CONTROL, "private, max-age=60")], &request_headers).is_none());
+	assert!(response(&[(CACHE_CONTROL, "public, private, max-age=60")], &request_headers).is_none());
+
+	let stored = response(
+		&[
+			(CACHE_CONTROL, "max-age=60"),
+			(SET_COOKIE, "session=1"),
+			(HeaderName::from_static("set-cookie2"), "session=1"),
+		],
+		&request_headers,
+	)
+	.unwrap();
+	assert!(!stored.headers.contains_key(SET_COOKIE));
+	assert!(!stored.headers.contains_key("set-cookie2"));
+
+	let mut refreshed = stored.clone();
+	let mut headers = HeaderMap::new();
+	headers.insert(SET_COOKIE, HeaderValue::from_static("session=2"));
+	headers.insert(ETAG, HeaderValue::from_static("\"v2\""));
+	refreshed.refresh(&headers);
+	assert!(!refreshed.headers.contains_key(SET_COOKIE));
+	assert_eq!(Some(&HeaderValue::from_static("\"v2\"")), refreshed.headers.get(ETAG));
+}
+
+#[test]
+fn client_caches() {
+	let url = Url::parse("https://example.com/").unwrap();
+	let stored = || response(&[(CACHE_CONTROL, "max-age=60")], &HeaderMap::new()).unwrap();
+
+	// Each client stores responses in its own cache, unless they are given the same cache.
+	let first = client_with_options(&ClientOptions::default()).unwrap();
+	let second = client_with_options(&ClientOptions::default()).unwrap();
+	first.http_cache().put(&url, stored());
+	assert!(first.http_cache().get(&url).is_some());
+	assert!(second.http_cache().get(&url).is_none());
+
+	let options = ClientOptions {
+		http_cache: Some(default_http_cache()),
+		..ClientOptions::default()
+	};
+	let first = client_with_options(&options).unwrap();
+	let second = client_with_options(&options).unwrap();
+	first.http_cache().put(&url, stored());
+	assert!(second.http_cache().get(&url).is_some());
+}
+
+#[test]
+fn vary() {
+	let mut request_headers = HeaderMap::new();
+	request_headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en"));
+	let stored = response(
+		&[(CACHE_CONTROL, "max-age=60"), (VARY, "Accept-Language")],
+		&request_headers,
+	)
+	.unwrap();
+	assert!(stored.matches(&request_headers));
+
+	request_headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("fr"));
+	assert!(!stored.matches(&request_headers));
+}
+
+#[test]
+fn memory_cache() {
+	let cache = MemoryCache::new(2);
+	let request_headers = HeaderMap::new();
+	let first = Url::parse("https://example.com/first#fragment").unwrap();
+	let second = Url::parse("https://example.com/second").unwrap();
+	let third = Url::parse("https://example.com/third").unwrap();
+
+	let stored = || response(&[(CACHE_CONTROL, "max-age=60")], &request_headers).unwrap();
+	cache.put(&first, stored());
+	cache.put(&second, stored());
+	assert!(cache.get(&Url::parse("https://example.com/first").unwrap()).is_some());
+
+	cache.put(&third, stored());
+	assert!(cache.get(&first).is_some());
+	assert!(cache.get(&second).is_none());
+	assert!(cache.get(&third).is_some());
+
+	cache.remove(&third);
+	assert!(cache.get(&third).is_none());
+}
@@ -0,0 +1,128 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{ClientOptions, MAX_CACHED_BODY_SIZE, ProxyConfig};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "http-cache-stream.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	const requests = [
+		["cached", {}],
+		["cached", {}],
+		["large", {}],
+		["large", {}],
+		["authorized", { headers: { Authorization: "Bearer token" } }],
+		["authorized", { headers: { Authorization: "Bearer token" } }],
+		["heuristic", {}],
+		["heuristic", {}],
+	];
+	for (const [path, init] of requests) {
+		const response = await fetch(`http://${address}/${path}`, init);
+		const text = await response.text();
+		results.push(text.length > 16 ? text.length : text);
+	}
+})();
+"#;
+
+/// Serves each request on its own connection, counting the requests made to each path.
+fn serve(listener: TcpListener, requests: Arc<Mutex<HashMap<String, usize>>>) {
+	for stream in listener.incoming() {
+		let mut stream = stream.unwrap();
+		let path = read_path(&stream);
+		*requests.lock().unwrap().entry(path.clone()).or_default() += 1;
+
+		let (cache_control, body) = match path.as_str() {
+			"/large" => ("max-age=60", "a".repeat(MAX_CACHED_BODY_SIZE + 1)),
+			"/heuristic" => ("", String::from("heuristic")),
+			_ => ("max-age=60", String::from(&path[1..])),
+		};
+		let head = format!(
+			"HTTP/1.1 200 OK\r\nCache-Control: {}\r\nLast-Modified: Thu, 01 Jan 2015 00:00:00 GMT\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+			cache_control,
+			body.len()
+		);
+		let _ = stream.write_all(head.as_bytes()).and_then(|_| stream.write_all(body.as_bytes()));
+	}
+}
+
+fn read_path(stream: &TcpStream) -> String {
+	let mut reader = BufReader::new(stream);
+	let mut request_line = String::new();
+	reader.read_line(&mut request_line).unwrap();
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line).unwrap() == 0 || line.trim().is_empty() {
+			break;
+		}
+	}
+	request_line.split_whitespace().nth(1).unwrap().to_owned()
+}
+
+#[test]
+fn http_cache_stream() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address: SocketAddr = listener.local_addr().unwrap();
+	let requests = Arc::new(Mutex::new(HashMap::new()));
+	thread::spawn({
+		let requests = Arc::clone(&requests);
+		move || serve(listener, requests)
+	});
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let options = ClientOptions {
+		proxy: ProxyConfig::default(),
+		..ClientOptions::default()
+	};
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.client_options(options)
+		.unwrap()
+		.build(cx);
+	rt.global().set_as(rt.cx(), "address", &address.to_string());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	let large = MAX_CACHED_BODY_SIZE + 1;
+	assert_eq!(
+		format!("cached,cached,{large},{large},authorized,authorized,heuristic,heuristic"),
+		result
+	);
+
+	// Only the response with an explicit lifetime, a small body and no credentials is reused.
+	let requests = requests.lock().unwrap();
+	assert_eq!(Some(&1), requests.get("/cached"));
+	assert_eq!(Some(&2), requests.get("/large"));
+	assert_eq!(Some(&2), requests.get("/authorized"));
+	assert_eq!(Some(&2), requests.get("/heuristic"));
+}
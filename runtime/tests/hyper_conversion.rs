@@ -0,0 +1,76 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use http::StatusCode;
+use http::header::HOST;
+use hyper::Body;
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{ClassDefinition, Context, Object};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{Request, Response};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "hyper-conversion.js";
+const SCRIPT: &str = r#"
+globalThis.results = [request.method, request.url, request.headers.get("x-custom")];
+request.text().then(text => results.push(text));
+
+const encoder = new TextEncoder();
+globalThis.response = new Response(new ReadableStream({
+	start(controller) {
+		controller.enqueue(encoder.encode("streamed "));
+		controller.enqueue(encoder.encode("response"));
+		controller.close();
+	},
+}), { status: 202, statusText: "Queued", headers: { "X-Custom": "response" } });
+"#;
+
+#[test]
+fn hyper_conversion() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let request = hyper::Request::post("/upload?id=1")
+			.header(HOST, "localhost:8080")
+			.header("X-Custom", "request")
+			.body(Body::from("request body"))
+			.unwrap();
+		let request = Request::from_hyper_request(rt.cx(), request).unwrap();
+		let request = Request::new_object(rt.cx(), Box::new(request));
+		rt.global().set_as(rt.cx(), "request", &request);
+
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+
+		let response = rt.global().get(rt.cx(), "response").unwrap().unwrap();
+		let response = Object::from_value(rt.cx(), &response, true, ()).unwrap();
+		let response = Response::get_mut_private(rt.cx(), &response).unwrap();
+		let body = response.take_body().unwrap();
+		let response = response.clone_with_body(Some(body)).into_hyper_response(rt.cx()).unwrap();
+
+		assert_eq!(StatusCode::ACCEPTED, response.status());
+		assert_eq!("response", response.headers()["x-custom"]);
+		let (body, event_loop) = futures::join!(hyper::body::to_bytes(response.into_body()), rt.run_event_loop());
+		assert!(event_loop.is_ok());
+		assert_eq!(&b"streamed response"[..], &body.unwrap()[..]);
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("POST,http://localhost:8080/upload?id=1,request,request body", result);
+}
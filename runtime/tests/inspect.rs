@@ -0,0 +1,48 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::format::{Config, format_value};
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "inspect.js";
+
+fn inspect(rt: &runtime::Runtime, source: &str) -> String {
+	let value = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), source).unwrap();
+	let cfg = Config::default().multiline(false);
+	format_value(rt.cx(), cfg, &value).to_string()
+}
+
+#[test]
+fn inspect_native_classes() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	let blob = inspect(&rt, r#"new Blob(["hello"], { type: "text/plain" })"#);
+	assert!(blob.contains("Blob"), "{}", blob);
+	assert!(blob.contains("size"), "{}", blob);
+	assert!(blob.contains("text/plain"), "{}", blob);
+
+	let file = inspect(&rt, r#"new File(["hello"], "hello.txt", { lastModified: 1000 })"#);
+	assert!(file.contains("File"), "{}", file);
+	assert!(file.contains("hello.txt"), "{}", file);
+	assert!(file.contains("lastModified"), "{}", file);
+
+	let url = inspect(&rt, r#"new URL("https://example.com/path")"#);
+	assert!(url.contains("URL"), "{}", url);
+	assert!(url.contains("https://example.com/path"), "{}", url);
+
+	let object = inspect(&rt, "({ a: 1 })");
+	assert!(!object.contains("href"), "{}", object);
+}
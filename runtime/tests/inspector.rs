@@ -0,0 +1,114 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "inspector")]
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tungstenite::Message;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::inspector::InspectorOptions;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "debuggee.js";
+const SCRIPT: &str = "const value = 21;\nglobalThis.result = value;\n";
+
+const COMMANDS: [&str; 4] = [
+	r#"{"id":1,"method":"Debugger.enable"}"#,
+	r#"{"id":2,"method":"Runtime.enable"}"#,
+	r#"{"id":3,"method":"Debugger.setBreakpointByUrl","params":{"url":"debuggee.js","lineNumber":1}}"#,
+	r#"{"id":4,"method":"Runtime.runIfWaitingForDebugger"}"#,
+];
+
+fn free_address() -> SocketAddr {
+	TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+}
+
+/// Connects to the inspector, sets a breakpoint, and evaluates an expression in the paused frame.
+/// Returns the messages received from the runtime.
+fn client(address: SocketAddr) -> Vec<String> {
+	let mut stream = loop {
+		match TcpStream::connect(address) {
+			Ok(stream) => break stream,
+			Err(_) => thread::sleep(Duration::from_millis(10)),
+		}
+	};
+	stream.write_all(b"GET /json/list HTTP/1.1\r\nHost: attacker.example\r\n\r\n").unwrap();
+	let mut rejected = String::new();
+	stream.read_to_string(&mut rejected).unwrap();
+	assert!(rejected.starts_with("HTTP/1.1 403 Forbidden"));
+
+	let mut stream = TcpStream::connect(address).unwrap();
+	write!(stream, "GET /json/list HTTP/1.1\r\nHost: {}\r\n\r\n", address).unwrap();
+	let mut list = String::new();
+	stream.read_to_string(&mut list).unwrap();
+	let url = list.split("\"webSocketDebuggerUrl\":\"").nth(1).unwrap().split('"').next().unwrap();
+
+	let (mut socket, _) = tungstenite::connect(url).unwrap();
+	for command in COMMANDS {
+		socket.send(Message::Text(String::from(command))).unwrap();
+	}
+
+	let mut messages = Vec::new();
+	loop {
+		let Message::Text(message) = socket.read().unwrap() else {
+			continue;
+		};
+		if message.contains(r#""method":"Debugger.paused""#) {
+			let evaluate = r#"{"id":5,"method":"Debugger.evaluateOnCallFrame","params":{"callFrameId":"0","expression":"value * 2"}}"#;
+			socket.send(Message::Text(String::from(evaluate))).unwrap();
+		} else if message.starts_with(r#"{"id":5"#) {
+			socket
+				.send(Message::Text(String::from(r#"{"id":6,"method":"Debugger.resume"}"#)))
+				.unwrap();
+		}
+		let resumed = message.contains(r#""method":"Debugger.resumed""#);
+		messages.push(message);
+		if resumed {
+			return messages;
+		}
+	}
+}
+
+#[test]
+fn inspector() {
+	let address = free_address();
+	let client = thread::spawn(move || client(address));
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.inspector(InspectorOptions { address, wait: true })
+		.build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "result").unwrap();
+	assert_eq!(21.0, f64::from_value(rt.cx(), &result, true, ()).unwrap());
+
+	let messages = client.join().unwrap();
+	let parsed = messages.iter().find(|message| message.contains(r#""method":"Debugger.scriptParsed""#));
+	assert!(parsed.unwrap().contains(r#""url":"debuggee.js""#));
+
+	let paused = messages.iter().find(|message| message.contains(r#""method":"Debugger.paused""#));
+	assert!(paused.unwrap().contains(r#""lineNumber":1"#));
+
+	let evaluated = messages.iter().find(|message| message.starts_with(r#"{"id":5"#));
+	assert!(evaluated.unwrap().contains(r#""value":42"#));
+}
@@ -0,0 +1,37 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use runtime::module::{jsonc_module_source, strip_jsonc};
+
+const CONFIG: &str = r#"{
+	// Line comment
+	"name": "spiderfire", /* Block comment */
+	"url": "https://example.com/*not-a-comment*/",
+	"escaped": "quote \" // still a string",
+	"list": [1, 2, 3,],
+}
+"#;
+
+#[test]
+fn strip() {
+	assert_eq!(
+		r#"{"name":"spiderfire","url":"https://example.com/*not-a-comment*/","escaped":"quote \" // still a string","list":[1,2,3]}"#,
+		strip_jsonc(CONFIG).unwrap()
+	);
+	assert_eq!("[1,,2]", strip_jsonc("[1, , 2]").unwrap());
+	assert_eq!("1,", strip_jsonc("1,").unwrap());
+
+	assert!(strip_jsonc("{ /* unterminated").is_err());
+	assert!(strip_jsonc("\"unterminated").is_err());
+}
+
+#[test]
+fn module_source() {
+	assert_eq!(
+		"export default JSON.parse(\"{\\\"path\\\":\\\"C:\\\\\\\\\\\"}\");\n",
+		jsonc_module_source("{ \"path\": \"C:\\\\\" }").unwrap()
+	);
+}
@@ -0,0 +1,59 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::globals::fetch::LargeBodyOptions;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "large-body.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+
+new Response('{"b":[1,2,{"c":null}],"a":"text","d":true,"e":{}}').json()
+	.then(json => results.push(`json:${JSON.stringify(json)}:${Object.keys(json)}`));
+new Response("text").text().then(text => results.push(`text:${text}`));
+new Response('{"a":').json().catch(error => results.push(`error:${error instanceof SyntaxError}`));
+"#;
+
+#[test]
+fn large_body() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let options = LargeBodyOptions { threshold: Some(0), segment_size: 2 };
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.large_body_options(options)
+		.build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.sort().join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		r#"error:true,json:{"b":[1,2,{"c":null}],"a":"text","d":true,"e":{}}:b,a,d,e,text:text"#,
+		result
+	);
+}
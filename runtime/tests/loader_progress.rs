@@ -0,0 +1,86 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::module::Module;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::module::{Loader, LoaderEvent};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "module-import.js";
+const SCRIPT: &str = include_str!("scripts/module-import.js");
+
+#[test]
+fn loader_progress() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let events = Rc::new(RefCell::new(Vec::new()));
+	let loader = Loader::default().on_event({
+		let events = Rc::clone(&events);
+		move |event| {
+			let event = match event {
+				LoaderEvent::ResolveStart { .. } => "resolve-start",
+				LoaderEvent::CompileStart { .. } => "compile-start",
+				LoaderEvent::CompileEnd { success, .. } => {
+					assert!(success);
+					"compile-end"
+				}
+				LoaderEvent::CycleDetected { .. } => "cycle-detected",
+				LoaderEvent::EvaluateEnd { .. } => "evaluate-end",
+			};
+			events.borrow_mut().push(event);
+		}
+	});
+	let progress = loader.progress();
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<_, ()>::new().microtask_queue().modules(loader).build(cx);
+
+	let path = format!("./tests/scripts/{}", FILE_NAME);
+	let module = Module::compile(rt.cx(), FILE_NAME, Some(Path::new(&path)), SCRIPT).unwrap();
+	module.instantiate(rt.cx()).unwrap();
+	progress.evaluate(rt.cx(), &module, FILE_NAME).unwrap();
+
+	assert_eq!(
+		["resolve-start", "compile-start", "compile-end", "evaluate-end"],
+		events.borrow().as_slice()
+	);
+
+	let stats = progress.stats();
+	assert_eq!(2, stats.modules.len());
+	let dependency = &stats.modules[0];
+	assert_eq!("../scripts/module-export.js", dependency.specifier);
+	assert!(dependency.path.as_ref().unwrap().ends_with("module-export.js"));
+	assert!(dependency.compile.is_some() && dependency.evaluate.is_none());
+	assert!(stats.modules[1].evaluate.is_some());
+	assert!(stats.to_string().starts_with("Loaded 2 modules"));
+
+	// Callbacks can replace themselves while they receive an event.
+	let replaced = Rc::new(RefCell::new(false));
+	progress.set_callback({
+		let progress = progress.clone();
+		let replaced = Rc::clone(&replaced);
+		move |_| {
+			let replaced = Rc::clone(&replaced);
+			progress.set_callback(move |_| *replaced.borrow_mut() = true);
+		}
+	});
+	for name in ["first.js", "second.js"] {
+		let module = Module::compile(rt.cx(), name, None, "export const value = 1;").unwrap();
+		module.instantiate(rt.cx()).unwrap();
+		progress.evaluate(rt.cx(), &module, name).unwrap();
+	}
+	assert!(*replaced.borrow());
+}
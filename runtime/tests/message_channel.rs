@@ -0,0 +1,80 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use futures::executor::block_on;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::globals::message_channel::MessagePort;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "message-channel.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+
+const channel = new MessageChannel();
+const message = { text: "hello" };
+channel.port2.postMessage(message);
+message.text = "changed";
+channel.port1.onmessage = event => {
+	results.push(`port1:${event.data.text}`);
+	channel.port1.postMessage(undefined);
+};
+channel.port2.onmessage = event => results.push(`port2:${event.data}`);
+
+hostPort.onmessage = event => results.push(`host:${event.data.reply}`);
+hostPort.postMessage({ request: 1 });
+
+const clones = new MessageChannel();
+clones.port1.onmessage = ({ data }) => {
+	const map = data.map instanceof Map && data.map.get("key");
+	const date = data.date instanceof Date && data.date.toISOString();
+	results.push(`clone:${map}/${date}/${data.cycle.self === data.cycle}`);
+};
+const cycle = {};
+cycle.self = cycle;
+clones.port2.postMessage({ map: new Map([["key", "value"]]), date: new Date(0), cycle });
+results.push("sync");
+"#;
+
+#[test]
+fn message_channel() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let (port, mut host) = MessagePort::new_host_pair(rt.cx());
+	rt.global().set_as(rt.cx(), "hostPort", &port);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	let request = host.try_recv_value(rt.cx()).unwrap().unwrap().to_object(rt.cx());
+	let request = request.get(rt.cx(), "request").unwrap().unwrap();
+	assert_eq!(1.0, f64::from_value(rt.cx(), &request, true, ()).unwrap());
+	assert!(host.try_recv().is_none());
+
+	let reply = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "({ reply: 2 })").unwrap();
+	host.post_value(rt.cx(), reply).unwrap();
+	assert!(block_on(rt.run_event_loop()).is_ok());
+	host.close();
+
+	let results = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let results = String::from_value(rt.cx(), &results, true, ()).unwrap();
+	assert_eq!(
+		"sync,port1:hello,clone:value/1970-01-01T00:00:00.000Z/true,host:2,port2:undefined",
+		results
+	);
+}
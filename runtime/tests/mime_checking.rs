@@ -0,0 +1,64 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::MimeChecking;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "mime-checking.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+
+const response = (body, type) => new Response(body, { headers: { "Content-Type": type } });
+const multipart = "--abc\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n--abc--\r\n";
+
+(async () => {
+	results.push((await Response.json({ a: 1 }).json()).a);
+	results.push((await response('{"b":2}', "application/vnd.api+json; charset=utf-8").json()).b);
+	await new Response('{"c":3}').json().catch(error => results.push(`json:${error instanceof TypeError}`));
+	await response("{}", "application/jsonp").json().catch(error => results.push(`jsonp:${error instanceof TypeError}`));
+
+	results.push((await response("a=1", "application/x-www-form-urlencoded").formData()).get("a"));
+	results.push((await response(multipart, 'multipart/form-data; boundary="abc"').formData()).get("a"));
+	await response(multipart, "multipart/form-data; boundary=a{b}c").formData()
+		.catch(error => results.push(`boundary:${error instanceof TypeError}`));
+	await response("a=1", "application/x-www-form-urlencodedx").formData()
+		.catch(error => results.push(`form:${error instanceof TypeError}`));
+})();
+"#;
+
+#[test]
+fn strict_mime_checking() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.mime_checking(MimeChecking::Strict)
+		.build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("1,2,json:true,jsonp:true,1,1,boundary:true,form:true", result);
+}
@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::module::Module;
+use runtime::module::{Loader, LoaderEvent};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "cycle-a.js";
+const SCRIPT: &str = include_str!("scripts/cycle-a.js");
+
+#[test]
+fn module_cycles() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let reported = Rc::new(RefCell::new(Vec::new()));
+	let loader = Loader::default().on_event({
+		let reported = Rc::clone(&reported);
+		move |event| {
+			if let LoaderEvent::CycleDetected { cycle } = event {
+				reported.borrow_mut().push(cycle.to_string());
+			}
+		}
+	});
+	let graph = loader.graph();
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<_, ()>::new().modules(loader).build(cx);
+
+	let path = format!("./tests/scripts/{}", FILE_NAME);
+	let module = Module::compile(rt.cx(), FILE_NAME, Some(Path::new(&path)), SCRIPT).unwrap();
+	module.instantiate(rt.cx()).unwrap();
+
+	let cycles = graph.cycles();
+	assert_eq!(1, cycles.len());
+	let mut names: Vec<_> = cycles[0].modules.iter().map(|module| module.file_name().unwrap()).collect();
+	names.sort();
+	assert_eq!(["cycle-a.js", "cycle-b.js", "cycle-c.js"], names.as_slice());
+
+	let reported = reported.borrow();
+	assert_eq!(1, reported.len());
+	assert_eq!(3, reported[0].matches(" → ").count());
+	assert!(reported[0].starts_with(cycles[0].modules[0].to_str().unwrap()));
+}
@@ -0,0 +1,145 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+use url::Url;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{
+	client_with_options, ClientOptions, FetchErrorPhase, FetchTimeout, OriginLimits, ProxyConfig,
+};
+use runtime::RuntimeBuilder;
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+const FILE_NAME: &str = "origin-limit.js";
+const UNREAD_BODIES: &str = r#"
+globalThis.results = [];
+(async () => {
+	const url = `http://${address}/`;
+	const unread = await fetch(url);
+	await fetch(url).catch(error => results.push(error.code));
+	results.push(await unread.text());
+	const next = await fetch(url);
+	results.push(await next.text());
+})();
+"#;
+
+/// Responds to each request with a short body, closing the connection afterwards.
+fn serve(listener: TcpListener) {
+	for stream in listener.incoming() {
+		let mut stream = stream.unwrap();
+		let mut reader = BufReader::new(&stream);
+		loop {
+			let mut line = String::new();
+			if reader.read_line(&mut line).unwrap() == 0 || line.trim().is_empty() {
+				break;
+			}
+		}
+		let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello");
+	}
+}
+
+#[test]
+fn origin_limit() {
+	let options = ClientOptions {
+		origin_limits: OriginLimits {
+			max_concurrent: Some(1),
+			queue_timeout: Some(TIMEOUT),
+		},
+		..ClientOptions::default()
+	};
+	let client = client_with_options(&options).unwrap();
+	let origin = Url::parse("http://localhost:8080/first").unwrap();
+	let same_origin = Url::parse("http://localhost:8080/second").unwrap();
+	let other_origin = Url::parse("http://localhost:8081/").unwrap();
+
+	let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	runtime.block_on(async {
+		let permit = client.acquire(&origin).await.unwrap();
+
+		let error = client.acquire(&same_origin).await.unwrap_err();
+		assert_eq!(Some(FetchTimeout::Queue), error.timeout);
+		assert_eq!(Some(FetchErrorPhase::Connect), error.phase);
+		assert_eq!(Some(same_origin.clone()), error.url);
+
+		// Other origins have their own queue.
+		drop(client.acquire(&other_origin).await.unwrap());
+
+		// Queued requests are sent in the order they were made.
+		let order = Arc::new(Mutex::new(Vec::new()));
+		let tasks: Vec<_> = (0..3)
+			.map(|index| {
+				let client = client.clone();
+				let url = same_origin.clone();
+				let order = Arc::clone(&order);
+				tokio::spawn(async move {
+					let _permit = client.acquire(&url).await.unwrap();
+					order.lock().unwrap().push(index);
+				})
+			})
+			.collect();
+		tokio::task::yield_now().await;
+		drop(permit);
+		for task in tasks {
+			task.await.unwrap();
+		}
+		assert_eq!(vec![0, 1, 2], *order.lock().unwrap());
+	});
+
+	assert_eq!("QUEUE_TIMEOUT", FetchTimeout::Queue.code());
+}
+
+#[test]
+fn unread_bodies() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address = listener.local_addr().unwrap();
+	thread::spawn(move || serve(listener));
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let options = ClientOptions {
+		proxy: ProxyConfig::default(),
+		origin_limits: OriginLimits {
+			max_concurrent: Some(1),
+			queue_timeout: Some(TIMEOUT),
+		},
+		..ClientOptions::default()
+	};
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.client_options(options)
+		.unwrap()
+		.build(cx);
+	rt.global().set_as(rt.cx(), "address", &address.to_string());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), UNREAD_BODIES).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	// Requests wait for the bodies of earlier responses to be read, not only for their headers.
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("QUEUE_TIMEOUT,hello,hello", result);
+}
@@ -0,0 +1,64 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "paginate.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	const pages = fetch.paginate("data:text/plain,1", {
+		nextLink: async response => {
+			const page = Number(response.url.split(",")[1]);
+			return page < 3 ? `data:text/plain,${page + 1}` : null;
+		},
+	});
+	for await (const page of pages) {
+		results.push(await page.text());
+	}
+
+	for await (const page of fetch.paginate("data:text/plain,single")) {
+		results.push(await page.text());
+	}
+
+	const early = fetch.paginate("data:text/plain,a", { nextLink: () => "data:text/plain,b" });
+	for await (const page of early) {
+		results.push(await page.text());
+		break;
+	}
+	results.push((await early.next()).done);
+})();
+"#;
+
+#[test]
+fn paginate() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("1,2,3,single,a,true", result);
+}
@@ -0,0 +1,58 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::performance::{EntryType, PerformanceTimeline};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "performance.js";
+const SCRIPT: &str = r#"
+	performance.mark("start", { startTime: 10, detail: { label: "start" } });
+	performance.mark("end", { startTime: 25 });
+	const measure = performance.measure("between", "start", "end");
+	performance.measure("options", { start: 5, duration: 10 });
+	[
+		measure.duration,
+		performance.getEntriesByType("mark").length,
+		performance.getEntriesByName("start")[0].detail.label,
+		performance.getEntriesByType("measure")[1].startTime,
+		performance.now() >= 0,
+	].join(",")
+"#;
+
+#[test]
+fn performance() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("15,2,start,5,true", result);
+
+	let timeline = PerformanceTimeline::from_context(rt.cx());
+	timeline.mark("native", None);
+	assert_eq!(3, timeline.entries_by_type(EntryType::Mark).count());
+
+	timeline.clear(EntryType::Mark, Some("start"));
+	assert_eq!(None, timeline.find_mark("start"));
+	assert_eq!(Some(25.0), timeline.find_mark("end"));
+
+	timeline.clear(EntryType::Measure, None);
+	assert_eq!(0, timeline.entries_by_type(EntryType::Measure).count());
+
+	let result =
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "performance.getEntries().length").unwrap();
+	assert_eq!(2, i32::from_value(rt.cx(), &result, true, ()).unwrap());
+}
@@ -0,0 +1,58 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::env;
+use std::path::Path;
+use std::rc::Rc;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::process::{EnvAccess, ProcessOptions};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "process.js";
+const SCRIPT: &str = r#"
+process.exit(3);
+[
+	process.argv.join(" "),
+	process.env.SPIDERFIRE_PROCESS_ALLOWED,
+	"SPIDERFIRE_PROCESS_DENIED" in process.env,
+	process.cwd() === CWD,
+	typeof process.platform,
+].join();
+"#;
+
+#[test]
+fn process() {
+	env::set_var("SPIDERFIRE_PROCESS_ALLOWED", "allowed");
+	env::set_var("SPIDERFIRE_PROCESS_DENIED", "denied");
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let options = ProcessOptions {
+		argv: vec![String::from("main.js"), String::from("--flag")],
+		env: EnvAccess::Allow(vec![String::from("SPIDERFIRE_PROCESS_ALLOWED")]),
+	};
+	let rt = RuntimeBuilder::<()>::new().process(options).build(cx);
+
+	let exit_code = Rc::new(Cell::new(None));
+	let code = Rc::clone(&exit_code);
+	rt.set_exit_handler(move |exit_code| code.set(Some(exit_code)));
+
+	let cwd = env::current_dir().unwrap();
+	let source = format!("const CWD = {:?};\n{}", cwd.to_str().unwrap(), SCRIPT);
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), &source).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+
+	assert_eq!(Some(3), exit_code.get());
+	assert_eq!("main.js --flag,allowed,false,true,string", result);
+}
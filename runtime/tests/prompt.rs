@@ -0,0 +1,52 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+use std::thread;
+
+use mozjs::rust::{JSEngine, JSEngineHandle, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::prompt::is_interactive;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "prompt.js";
+const SCRIPT: &str = "[typeof prompt, typeof confirm].join()";
+
+fn globals(engine: JSEngineHandle, console_input: bool) -> String {
+	let rt = Runtime::new(engine);
+
+	let cx = &mut Context::from_runtime(&rt);
+	let mut builder = RuntimeBuilder::<()>::new().microtask_queue();
+	if console_input {
+		builder = builder.console_input();
+	}
+	let rt = builder.build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	String::from_value(rt.cx(), &result, true, ()).unwrap()
+}
+
+#[test]
+fn prompt() {
+	let engine = JSEngine::init().unwrap();
+
+	let handle = engine.handle();
+	let result = thread::spawn(move || globals(handle, false)).join().unwrap();
+	assert_eq!("undefined,undefined", result);
+
+	// The globals are only defined when stdin and stdout are attached to a terminal.
+	let expected = if is_interactive() {
+		"function,function"
+	} else {
+		"undefined,undefined"
+	};
+	let handle = engine.handle();
+	let result = thread::spawn(move || globals(handle, true)).join().unwrap();
+	assert_eq!(expected, result);
+}
@@ -0,0 +1,48 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::random::RandomState;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "random-seed.js";
+const SEED: u64 = 42;
+
+#[test]
+fn random_seed() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().random_seed(SEED).build(cx);
+
+	let random = |rt: &runtime::Runtime| {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "[Math.random(), Math.random()]");
+		Vec::<f64>::from_value(rt.cx(), &result.unwrap(), true, ()).unwrap()
+	};
+
+	let mut expected = RandomState::from_seed(SEED);
+	let first = random(&rt);
+	assert_eq!(vec![expected.next_f64(), expected.next_f64()], first);
+	assert!(first.iter().all(|number| (0.0..1.0).contains(number)));
+
+	let snapshot = rt.random_state().unwrap();
+	assert_eq!(expected, snapshot);
+	let second = random(&rt);
+	assert_ne!(first, second);
+
+	assert!(rt.set_random_state(snapshot));
+	assert_eq!(second, random(&rt));
+
+	assert!(RandomState::from_state([0, 0]).is_none());
+	assert_eq!(Some(snapshot), RandomState::from_state(snapshot.state()));
+}
@@ -0,0 +1,41 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::env;
+use std::path::Path;
+
+use runtime::mime_type;
+use runtime::security::ReadPermission;
+use runtime::wasi_polyfills::canonicalize;
+
+#[test]
+fn read_permission() {
+	let directory = canonicalize(env::temp_dir()).unwrap();
+	assert!(ReadPermission::All.allows(&directory.join("file.txt")));
+
+	let permission = ReadPermission::paths([directory.clone()]);
+	assert!(permission.allows(&directory));
+	assert!(permission.allows(&directory.join("nested").join("file.txt")));
+	assert!(!permission.allows(Path::new("/")));
+
+	let sibling = format!("{}-sibling", directory.display());
+	assert!(!permission.allows(Path::new(&sibling)));
+
+	assert!(!ReadPermission::paths(Vec::<&Path>::new()).allows(&directory));
+}
+
+#[test]
+fn mime_type_from_path() {
+	assert_eq!("text/html;charset=UTF-8", mime_type::from_path(Path::new("index.HTML")));
+	assert_eq!("application/json", mime_type::from_path(Path::new("/data/config.json")));
+	assert_eq!("application/wasm", mime_type::from_path(Path::new("module.wasm")));
+	assert_eq!(
+		mime_type::OCTET_STREAM,
+		mime_type::from_path(Path::new("archive.unknown"))
+	);
+	assert_eq!(mime_type::OCTET_STREAM, mime_type::from_path(Path::new("Makefile")));
+	assert_eq!(None, mime_type::from_extension("exe"));
+}
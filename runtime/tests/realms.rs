@@ -0,0 +1,53 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "realms.js";
+
+#[test]
+fn realms() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let first = rt.create_realm();
+	let second = rt.create_realm();
+	let path = Path::new(FILE_NAME);
+
+	let result = rt.evaluate_script_in(&first, path, "globalThis.plugin = 'first'; typeof setTimeout");
+	assert_eq!(
+		"function",
+		String::from_value(rt.cx(), &result.unwrap(), true, ()).unwrap()
+	);
+
+	let result = rt.evaluate_script_in(&second, path, "typeof plugin + ',' + typeof URL").unwrap();
+	assert_eq!(
+		"undefined,function",
+		String::from_value(rt.cx(), &result, true, ()).unwrap()
+	);
+
+	let result = Script::compile_and_evaluate(rt.cx(), path, "typeof plugin").unwrap();
+	assert_eq!("undefined", String::from_value(rt.cx(), &result, true, ()).unwrap());
+
+	let result = rt.evaluate_script_in(&first, path, "({ plugin, values: [1, 2] })").unwrap();
+	let object = result.to_object(rt.cx());
+	let plugin: String = object.get_as(rt.cx(), "plugin", true, ()).unwrap().unwrap();
+	assert_eq!("first", plugin);
+	rt.global().set(rt.cx(), "fromRealm", &result);
+
+	let result = Script::compile_and_evaluate(rt.cx(), path, "fromRealm.values instanceof Array").unwrap();
+	assert!(!bool::from_value(rt.cx(), &result, true, ()).unwrap());
+}
@@ -0,0 +1,81 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, COOKIE};
+use url::Url;
+
+use runtime::globals::fetch::{
+	can_resend_body, FetchBody, FetchBodyInner, is_cross_origin_redirect, redirect_changes_to_get,
+	strip_credential_headers, strip_request_body_headers,
+};
+
+#[test]
+fn changes_to_get() {
+	for status in [StatusCode::MOVED_PERMANENTLY, StatusCode::FOUND] {
+		assert!(redirect_changes_to_get(status, &Method::POST));
+		assert!(!redirect_changes_to_get(status, &Method::PUT));
+		assert!(!redirect_changes_to_get(status, &Method::GET));
+	}
+
+	assert!(redirect_changes_to_get(StatusCode::SEE_OTHER, &Method::POST));
+	assert!(redirect_changes_to_get(StatusCode::SEE_OTHER, &Method::DELETE));
+	assert!(!redirect_changes_to_get(StatusCode::SEE_OTHER, &Method::GET));
+	assert!(!redirect_changes_to_get(StatusCode::SEE_OTHER, &Method::HEAD));
+
+	for status in [StatusCode::TEMPORARY_REDIRECT, StatusCode::PERMANENT_REDIRECT] {
+		for method in [Method::GET, Method::POST, Method::PUT, Method::PATCH] {
+			assert!(!redirect_changes_to_get(status, &method));
+		}
+	}
+}
+
+#[test]
+fn cross_origin() {
+	let from = Url::parse("https://example.com/a").unwrap();
+	let cross_origin = |to: &str| is_cross_origin_redirect(&from, &Url::parse(to).unwrap());
+	assert!(!cross_origin("https://example.com/b?c"));
+	assert!(!cross_origin("https://example.com:443/"));
+	assert!(cross_origin("http://example.com/a"));
+	assert!(cross_origin("https://api.example.com/a"));
+	assert!(cross_origin("https://example.com:8443/a"));
+}
+
+#[test]
+fn strip_headers() {
+	let mut headers = HeaderMap::new();
+	headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+	headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer token"));
+	headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+	headers.append(COOKIE, HeaderValue::from_static("a=1"));
+	headers.append(COOKIE, HeaderValue::from_static("b=2"));
+
+	strip_credential_headers(&mut headers);
+	assert!(!headers.contains_key(AUTHORIZATION));
+	assert!(!headers.contains_key(COOKIE));
+	assert!(headers.contains_key(CONTENT_TYPE));
+
+	strip_request_body_headers(&mut headers);
+	assert!(!headers.contains_key(CONTENT_TYPE));
+	assert_eq!(1, headers.len());
+}
+
+#[test]
+fn resend_body() {
+	assert!(can_resend_body(None));
+	assert!(can_resend_body(Some(&FetchBody::default())));
+
+	let body = FetchBody {
+		body: FetchBodyInner::Bytes(Bytes::from_static(b"body")),
+		source: None,
+		kind: None,
+		length_hint: None,
+	};
+	assert!(can_resend_body(Some(&body)));
+}
@@ -0,0 +1,61 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "request-json.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	const request = Request.json("https://example.com/", { a: 1, b: [true, null] });
+	results.push(request.method, request.headers.get("content-type"), await request.text());
+
+	const custom = Request.json("https://example.com/", "value", {
+		method: "put",
+		headers: { "Content-Type": "application/vnd.api+json" },
+	});
+	results.push(custom.method, custom.headers.get("content-type"), await custom.json());
+
+	try {
+		Request.json("https://example.com/", 1n);
+	} catch (error) {
+		results.push(error instanceof TypeError);
+	}
+})();
+"#;
+
+#[test]
+fn request_json() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join('|')").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		r#"POST|application/json|{"a":1,"b":[true,null]}|PUT|application/vnd.api+json|value|true"#,
+		result
+	);
+}
@@ -0,0 +1,53 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::limits::ResourceLimits;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "resource-limits.js";
+const MAX_HEAP_SIZE: u32 = 64 * 1024 * 1024;
+const TIMEOUT: Duration = Duration::from_millis(50);
+
+#[test]
+fn resource_limits() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.resource_limits(ResourceLimits {
+			max_heap_size: Some(MAX_HEAP_SIZE),
+			task_timeout: Some(TIMEOUT),
+			module_timeout: Some(TIMEOUT),
+		})
+		.build(cx);
+
+	assert_eq!(u64::from(MAX_HEAP_SIZE), rt.memory_usage().heap_limit);
+
+	let error = rt.evaluate_module("loop.js", None, "while (true) {}").unwrap_err();
+	assert!(error.format(rt.cx()).contains("Module evaluation timed out after 50ms"));
+
+	let script = "setTimeout(() => { while (true) {} }, 0);";
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), script);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let report = tokio.block_on(rt.run_event_loop()).unwrap_err().unwrap();
+	assert!(report.format(rt.cx()).contains("Task timed out after 50ms"));
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "1 + 1").unwrap();
+	assert_eq!(2.0, f64::from_value(rt.cx(), &result, true, ()).unwrap());
+}
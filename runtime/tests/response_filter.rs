@@ -0,0 +1,120 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use http::header::{
+	ACCEPT_ENCODING, ACCESS_CONTROL_EXPOSE_HEADERS, CONTENT_TYPE, ETAG, HeaderName, RANGE, SERVER, SET_COOKIE, VARY,
+};
+
+use runtime::globals::fetch::{
+	filter_headers, filtered_kind, has_null_body, is_blocked_range_response, ResponseKind, ResponseTaint, vary_headers,
+};
+
+fn headers(headers: &[(HeaderName, &'static str)]) -> HeaderMap {
+	HeaderMap::from_iter(headers.iter().map(|(name, value)| (name.clone(), HeaderValue::from_static(value))))
+}
+
+fn names(headers: &HeaderMap) -> Vec<&str> {
+	let mut names: Vec<_> = headers.keys().map(HeaderName::as_str).collect();
+	names.sort();
+	names
+}
+
+#[test]
+fn kind() {
+	assert_eq!(ResponseKind::Basic, filtered_kind(ResponseTaint::Basic, false));
+	assert_eq!(ResponseKind::Cors, filtered_kind(ResponseTaint::Cors, false));
+	assert_eq!(ResponseKind::Opaque, filtered_kind(ResponseTaint::Opaque, false));
+	assert_eq!(ResponseKind::OpaqueRedirect, filtered_kind(ResponseTaint::Cors, true));
+
+	assert!(ResponseKind::Opaque.is_opaque());
+	assert!(ResponseKind::OpaqueRedirect.is_opaque());
+	assert!(!ResponseKind::Cors.is_opaque());
+	assert!(ResponseKind::Error.is_error());
+}
+
+#[test]
+fn basic() {
+	let mut response = headers(&[(CONTENT_TYPE, "text/plain"), (SET_COOKIE, "a=b"), (SERVER, "test")]);
+	filter_headers(&mut response, ResponseKind::Basic, false);
+	assert_eq!(vec!["content-type", "server"], names(&response));
+}
+
+#[test]
+fn cors() {
+	let response = headers(&[
+		(CONTENT_TYPE, "text/plain"),
+		(ETAG, "\"v1\""),
+		(SERVER, "test"),
+		(SET_COOKIE, "a=b"),
+		(ACCESS_CONTROL_EXPOSE_HEADERS, "ETag, X-Unused"),
+	]);
+
+	let mut exposed = response.clone();
+	filter_headers(&mut exposed, ResponseKind::Cors, false);
+	assert_eq!(vec!["content-type", "etag"], names(&exposed));
+
+	let mut wildcard = headers(&[
+		(CONTENT_TYPE, "text/plain"),
+		(SERVER, "test"),
+		(SET_COOKIE, "a=b"),
+		(ACCESS_CONTROL_EXPOSE_HEADERS, "*"),
+	]);
+	let mut credentialed = wildcard.clone();
+	filter_headers(&mut wildcard, ResponseKind::Cors, false);
+	assert_eq!(
+		vec!["access-control-expose-headers", "content-type", "server"],
+		names(&wildcard)
+	);
+
+	filter_headers(&mut credentialed, ResponseKind::Cors, true);
+	assert_eq!(vec!["content-type"], names(&credentialed));
+}
+
+#[test]
+fn opaque() {
+	let mut response = headers(&[(CONTENT_TYPE, "text/plain"), (VARY, "Accept-Encoding")]);
+	filter_headers(&mut response, ResponseKind::Opaque, false);
+	assert!(response.is_empty());
+	assert_eq!(Some(Vec::new()), vary_headers(&response));
+
+	let request = headers(&[]);
+	let partial = Some(StatusCode::PARTIAL_CONTENT);
+	assert!(is_blocked_range_response(
+		ResponseTaint::Opaque,
+		partial,
+		true,
+		&request
+	));
+	assert!(!is_blocked_range_response(ResponseTaint::Cors, partial, true, &request));
+	assert!(!is_blocked_range_response(
+		ResponseTaint::Opaque,
+		partial,
+		true,
+		&headers(&[(RANGE, "bytes=0-")])
+	));
+}
+
+#[test]
+fn vary() {
+	let response = headers(&[(VARY, "Accept-Encoding, Accept-Language")]);
+	let vary = vary_headers(&response).unwrap();
+	assert_eq!(ACCEPT_ENCODING, vary[0]);
+	assert_eq!("accept-language", vary[1].as_str());
+
+	assert_eq!(None, vary_headers(&headers(&[(VARY, "*")])));
+}
+
+#[test]
+fn null_body() {
+	assert!(has_null_body(&Method::HEAD, Some(StatusCode::OK)));
+	assert!(has_null_body(&Method::GET, Some(StatusCode::NO_CONTENT)));
+	assert!(has_null_body(&Method::GET, Some(StatusCode::NOT_MODIFIED)));
+	assert!(!has_null_body(&Method::GET, Some(StatusCode::OK)));
+	assert!(!has_null_body(&Method::POST, None));
+}
@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::io;
+use std::path::Path;
+
+use bytes::Bytes;
+use futures::stream;
+use http::{HeaderMap, HeaderValue, StatusCode};
+use http::header::CONTENT_TYPE;
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{ClassDefinition, Context};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{HeadersInit, Response, ResponseInit};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "response-stream.js";
+const SCRIPT: &str = r#"
+globalThis.results = [response.status, response.headers.get("content-type")];
+response.text().then(text => results.push(text));
+"#;
+
+#[test]
+fn response_from_stream() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let chunks = ["streamed ", "native ", "body"].map(|chunk| Ok::<_, io::Error>(Bytes::from(chunk)));
+		let mut headers = HeaderMap::new();
+		headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+		let init = ResponseInit {
+			headers: HeadersInit::Map(headers),
+			status: StatusCode::CREATED,
+			status_text: None,
+		};
+
+		let response = Response::from_stream(rt.cx(), stream::iter(chunks), init).unwrap();
+		let response = Response::new_object(rt.cx(), Box::new(response));
+		rt.global().set_as(rt.cx(), "response", &response);
+
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("201,text/plain,streamed native body", result);
+}
@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{ClassDefinition, Context, Object};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{Headers, HeadersKind};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "response-with-headers.js";
+const SETUP: &str = r#"
+globalThis.response = new Response("body", { headers: { "x-old": "1" } });
+response.headers
+"#;
+const SCRIPT: &str = r#"
+let error = null;
+try {
+	response.headers.set("x-old", "2");
+} catch (e) {
+	error = e.name;
+}
+
+const replaced = response.withHeaders({ "x-new": "1" });
+replaced.headers.set("x-new", "2");
+replaced.text().then(text => {
+	globalThis.result = [error, response.bodyUsed, replaced.headers.get("x-new"), replaced.headers.has("x-old"), text]
+		.join("|");
+});
+"#;
+
+#[test]
+fn response_with_headers() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+	let headers = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SETUP).unwrap();
+	let headers = Object::from_value(rt.cx(), &headers, true, ()).unwrap();
+	let headers = Headers::get_mut_private(rt.cx(), &headers).unwrap();
+	headers.make_immutable();
+	assert!(headers.is_immutable());
+
+	let mut copy = headers.to_mutable();
+	assert_eq!(HeadersKind::None, copy.kind());
+	assert!(copy.with_header_map(|map| map.clear()).is_ok());
+	assert!(headers.iter().any(|(name, _)| name == "x-old"));
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	tokio.block_on(rt.run_event_loop()).unwrap();
+
+	let result: String = rt.global().get_as(rt.cx(), "result", true, ()).unwrap().unwrap();
+	assert_eq!("TypeError|true|2|false|body", result);
+}
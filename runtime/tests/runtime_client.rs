@@ -0,0 +1,102 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::Path;
+use std::thread;
+
+use mozjs::rust::{JSEngine, JSEngineHandle, Runtime};
+use tokio::task::LocalSet;
+use url::Url;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::fetch::{Client, client_with_options, ClientOptions, ProxyConfig};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "runtime-client.js";
+const SCRIPT: &str = r#"
+fetch(`http://${address}/`).then(response => response.text()).then(text => globalThis.result = text);
+"#;
+
+/// Responds to each request with the `Cookie` header it was sent with.
+fn serve(listener: TcpListener) {
+	for stream in listener.incoming() {
+		let mut stream = stream.unwrap();
+		let mut cookie = String::from("none");
+		let mut reader = BufReader::new(&stream);
+		loop {
+			let mut line = String::new();
+			if reader.read_line(&mut line).unwrap() == 0 || line.trim().is_empty() {
+				break;
+			}
+			if let Some((name, value)) = line.split_once(':') {
+				if name.eq_ignore_ascii_case("cookie") {
+					cookie = value.trim().to_owned();
+				}
+			}
+		}
+		let response = format!(
+			"HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+			cookie.len(),
+			cookie
+		);
+		let _ = stream.write_all(response.as_bytes());
+	}
+}
+
+fn fetch_cookie(engine: JSEngineHandle, address: SocketAddr, client: Option<Client>) -> String {
+	let rt = Runtime::new(engine);
+
+	let cx = &mut Context::from_runtime(&rt);
+	let mut builder = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue();
+	if let Some(client) = client {
+		builder = builder.client(client);
+	}
+	let rt = builder.build(cx);
+	rt.global().set_as(rt.cx(), "address", &address.to_string());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "result").unwrap();
+	String::from_value(rt.cx(), &result, true, ()).unwrap()
+}
+
+#[test]
+fn runtime_client() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address = listener.local_addr().unwrap();
+	thread::spawn(move || serve(listener));
+
+	let options = ClientOptions {
+		proxy: ProxyConfig::default(),
+		..ClientOptions::default()
+	};
+	let client = client_with_options(&options).unwrap();
+	let url = Url::parse(&format!("http://{}/", address)).unwrap();
+	assert!(client.cookie_jar().set_cookie(&url, "session=1"));
+
+	let engine = JSEngine::init().unwrap();
+
+	// Each runtime sends requests with its own client, rather than the client of the first runtime.
+	let handle = engine.handle();
+	let first = thread::spawn(move || fetch_cookie(handle, address, Some(client))).join().unwrap();
+	assert_eq!("session=1", first);
+
+	let client = client_with_options(&options).unwrap();
+	let handle = engine.handle();
+	let second = thread::spawn(move || fetch_cookie(handle, address, Some(client))).join().unwrap();
+	assert_eq!("none", second);
+}
@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::handle::RuntimeHandle;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "runtime-handle.js";
+const INTERRUPTED: &str = r#"
+globalThis.results = [];
+setTimeout(() => { while (true) {} });
+setTimeout(() => results.push("next"), 10);
+"#;
+const TERMINATED: &str = r#"
+setTimeout(() => { while (true) {} });
+setTimeout(() => results.push("discarded"), 10);
+"#;
+
+fn after_delay<F: FnOnce(&RuntimeHandle) -> bool + Send + 'static>(handle: RuntimeHandle, callback: F) {
+	thread::spawn(move || {
+		thread::sleep(Duration::from_millis(50));
+		assert!(callback(&handle));
+	});
+}
+
+#[test]
+fn runtime_handle() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), INTERRUPTED);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	after_delay(rt.handle(), RuntimeHandle::interrupt);
+
+	let report = tokio.block_on(rt.run_event_loop()).unwrap_err().unwrap();
+	assert!(report.format(rt.cx()).contains("Execution was interrupted"));
+	assert!(tokio.block_on(rt.run_event_loop()).is_ok());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), TERMINATED);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	after_delay(rt.handle(), RuntimeHandle::terminate);
+
+	let report = tokio.block_on(rt.run_event_loop()).unwrap_err().unwrap();
+	assert!(report.format(rt.cx()).contains("Runtime was terminated"));
+	assert!(rt.handle().is_terminated());
+	assert!(rt.event_loop_is_empty());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	assert_eq!("next", String::from_value(rt.cx(), &result, true, ()).unwrap());
+}
@@ -50,3 +50,13 @@ for (let i = 0; i < 576; i++) {
 }
 console.timeEnd();
 console.timeEnd("Timer");
+
+console.log("%s is %d years and %f days old", "Spiderfire", 4.5, 12.25);
+console.log("%5.1f|%3d|%s|%o", Math.PI, 7, Symbol("symbol"), {nested: {array: [1, 2]}});
+console.log("%cStyled%c text", "color: red", "font-weight: bold", "extra");
+console.log("100%% done", "%s");
+
+console.group("Table:");
+console.table([{a: 1, b: "x"}, {a: 2, c: true}]);
+console.table({first: [1, 2], second: [3]}, ["0"]);
+console.groupEnd();
@@ -0,0 +1,3 @@
+import { c } from "./cycle-c.js";
+
+export const b = () => c;
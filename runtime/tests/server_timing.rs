@@ -0,0 +1,67 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use http::{HeaderMap, HeaderValue};
+
+use runtime::globals::fetch::ServerTiming;
+
+fn parse(values: &[&'static str]) -> Vec<ServerTiming> {
+	let mut headers = HeaderMap::new();
+	for value in values {
+		headers.append("server-timing", HeaderValue::from_static(value));
+	}
+	ServerTiming::from_headers(&headers)
+}
+
+fn timing(name: &str, duration: Option<f64>, description: Option<&str>) -> ServerTiming {
+	ServerTiming {
+		name: String::from(name),
+		duration,
+		description: description.map(String::from),
+	}
+}
+
+#[test]
+fn metrics() {
+	let timings = parse(&[
+		"cache;desc=hit, db;dur=53.2",
+		"app ; dur = 47.1 ; desc = \"Application\"",
+	]);
+	assert_eq!(
+		vec![
+			timing("cache", None, Some("hit")),
+			timing("db", Some(53.2), None),
+			timing("app", Some(47.1), Some("Application")),
+		],
+		timings
+	);
+}
+
+#[test]
+fn quoted_separators() {
+	let timings = parse(&[r#"db;desc="Query; select a, b";dur=12, cache;desc="a \"quoted\" \\ value""#]);
+	assert_eq!(
+		vec![
+			timing("db", Some(12.0), Some("Query; select a, b")),
+			timing("cache", None, Some(r#"a "quoted" \ value"#)),
+		],
+		timings
+	);
+}
+
+#[test]
+fn invalid_parameters() {
+	let timings = parse(&[",;dur=1, total;dur=2;dur=3;desc;desc=first;desc=second, quote;desc=\"a,b"]);
+	assert_eq!(
+		vec![
+			timing("total", Some(2.0), Some("first")),
+			timing("quote", None, Some("a,b"))
+		],
+		timings
+	);
+}
@@ -0,0 +1,57 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "shadow_realm.js";
+const SCRIPT: &str = r#"
+globalThis.outer = true;
+const realm = new ShadowRealm();
+const results = [];
+
+results.push(realm.evaluate("1 + 2"));
+results.push(realm.evaluate("typeof outer"));
+
+const double = realm.evaluate("globalThis.count = 0; (x) => { count++; return x * 2; }");
+results.push(double(21));
+results.push(realm.evaluate("count"));
+
+try {
+	realm.evaluate("({})");
+} catch (error) {
+	results.push(error instanceof TypeError);
+}
+
+try {
+	realm.evaluate("throw new Error('inner')");
+} catch (error) {
+	results.push(error instanceof TypeError && error.message);
+}
+
+results.join(",");
+"#;
+
+#[test]
+fn shadow_realm() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	assert_eq!(
+		"3,undefined,42,1,true,inner",
+		String::from_value(rt.cx(), &result, true, ()).unwrap()
+	);
+}
@@ -0,0 +1,50 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "shutdown.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+runtime.onShutdown(() => results.push("sync"));
+runtime.onShutdown(() => { throw new Error("failed"); });
+runtime.onShutdown(() => new Promise(resolve => setTimeout(() => {
+	results.push("async");
+	resolve();
+}, 10)));
+setTimeout(() => results.push("discarded"), 1000);
+"#;
+const HANGING: &str = r#"runtime.onShutdown(() => new Promise(() => {}));"#;
+const REGISTERED: &str = r#"runtime.onShutdown(() => {});"#;
+
+#[test]
+fn shutdown() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+	for script in [SCRIPT, HANGING] {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), script);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+	}
+	assert!(!tokio.block_on(rt.shutdown(Duration::from_millis(200))).unwrap());
+	assert!(rt.event_loop_is_empty());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	assert_eq!("sync,async", String::from_value(rt.cx(), &result, true, ()).unwrap());
+	assert!(Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), REGISTERED).is_err());
+}
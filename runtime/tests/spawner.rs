@@ -0,0 +1,72 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+
+use futures::executor::LocalPool;
+use futures::future::LocalBoxFuture;
+use futures::task::{LocalSpawnExt, LocalSpawner};
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Object};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::event_loop::Spawner;
+use runtime::promise::future_to_promise;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "spawner.js";
+
+struct PoolSpawner {
+	spawner: LocalSpawner,
+	spawned: Rc<Cell<u32>>,
+}
+
+impl Spawner for PoolSpawner {
+	fn spawn_local(&self, future: LocalBoxFuture<'static, ()>) {
+		self.spawned.set(self.spawned.get() + 1);
+		self.spawner.spawn_local(future).unwrap();
+	}
+}
+
+#[test]
+fn spawner() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let mut pool = LocalPool::new();
+	let spawned = Rc::new(Cell::new(0));
+	let spawner = PoolSpawner {
+		spawner: pool.spawner(),
+		spawned: Rc::clone(&spawned),
+	};
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().spawner(Rc::new(spawner)).build(cx);
+
+	let resolved = unsafe { future_to_promise(rt.cx(), |_| async { Ok::<_, ()>(String::from("resolved")) }) };
+	let rejected = unsafe { future_to_promise(rt.cx(), |_| async { Err::<(), _>(String::from("rejected")) }) };
+	let global = Object::global(rt.cx());
+	global.set_as(rt.cx(), "resolved", &resolved.unwrap());
+	global.set_as(rt.cx(), "rejected", &rejected.unwrap());
+	assert_eq!(2, spawned.get());
+
+	let script = r#"
+		globalThis.results = [];
+		resolved.then(value => results.push(value));
+		rejected.catch(reason => results.push(reason));
+	"#;
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), script);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	assert!(pool.run_until(rt.run_event_loop()).is_ok());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("resolved,rejected", result);
+}
@@ -0,0 +1,57 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use sourcemap::SourceMapBuilder;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::cache::map::save_sourcemap;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "stack-frames.js";
+const SCRIPT: &str = r#"
+function inner() {
+	return new Error("inner");
+}
+
+function outer() {
+	return inner();
+}
+
+const error = outer();
+const frames = Error.stackFrames(error).map(frame => `${frame.function}:${frame.line}:${frame.column}`);
+const current = Error.stackFrames();
+[
+	frames.join(" "),
+	current.length,
+	current[0].file === Error.stackFrames(error)[0].file,
+	Error.stackFrames({}).length,
+].join();
+"#;
+
+#[test]
+fn stack_frames() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	// Maps each line of the script to the line 100 lines after it, at the same column.
+	let mut builder = SourceMapBuilder::new(Some(FILE_NAME));
+	for line in 0..SCRIPT.lines().count() as u32 {
+		builder.add(line, 0, line + 100, 0, Some("stack-frames.ts"), None);
+	}
+	assert!(save_sourcemap(FILE_NAME, builder.into_sourcemap()));
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("inner:103:1 outer:107:1 null:110:1,1,true,0", result);
+}
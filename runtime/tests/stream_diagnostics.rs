@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "stream-diagnostics.js";
+const SCRIPT: &str = r#"
+const describe = stream => {
+	const { state, controller, locked, reader, desiredSize, queueTotalSize } = __streamState(stream);
+	return [state, controller, locked, reader, desiredSize, queueTotalSize].join(":");
+};
+
+const stream = new ReadableStream({
+	start(controller) {
+		controller.enqueue("abc");
+		controller.enqueue("de");
+	},
+}, { highWaterMark: 4, size: chunk => chunk.length });
+const results = [describe(stream)];
+
+stream.getReader();
+results.push(describe(stream));
+
+const closed = new ReadableStream({ start: controller => controller.close() });
+const errored = new ReadableStream({ start: controller => controller.error(new Error()) });
+results.push(describe(closed), describe(errored));
+results.join();
+"#;
+
+#[test]
+fn stream_diagnostics() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().stream_diagnostics().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		"readable:default:false::-1:5,readable:default:true:default:-1:5,closed:default:false::0:0,errored:default:false:::0",
+		result
+	);
+}
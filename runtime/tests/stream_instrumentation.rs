@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "stream-instrumentation.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+
+(async () => {
+	const counting = new CountingStream();
+	const throttle = new ThrottleStream(1000);
+	const source = new ReadableStream({
+		start(controller) {
+			controller.enqueue("héllo");
+			controller.enqueue(new Uint8Array(10));
+			controller.enqueue(new ArrayBuffer(4));
+			controller.close();
+		},
+	});
+
+	const started = Date.now();
+	const reader = source.pipeThrough(counting).pipeThrough(throttle).getReader();
+	while (!(await reader.read()).done) {}
+
+	const { chunks, bytes } = __streamCounters(throttle);
+	results.push(counting.chunks, counting.bytes, chunks, bytes, Date.now() - started >= 10);
+})();
+"#;
+
+#[test]
+fn stream_instrumentation() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.stream_diagnostics()
+		.build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+		assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("3,20,3,20,true", result);
+}
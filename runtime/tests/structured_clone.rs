@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "structured-clone.js";
+const SCRIPT: &str = r#"
+const results = [];
+
+const original = {
+	map: new Map([["key", { nested: true }]]),
+	set: new Set([1, 2]),
+	date: new Date(0),
+	regexp: /a+/gi,
+};
+original.self = original;
+const clone = structuredClone(original);
+results.push(
+	clone !== original,
+	clone.self === clone,
+	clone.map.get("key").nested,
+	clone.map.get("key") !== original.map.get("key"),
+	clone.set.has(2),
+	clone.date.getTime(),
+	clone.regexp.flags,
+);
+
+const buffer = new Uint8Array([1, 2, 3]).buffer;
+const transferred = structuredClone({ buffer }, { transfer: [buffer] });
+results.push(buffer.byteLength, transferred.buffer.byteLength);
+
+try {
+	structuredClone({ fn() {} });
+} catch (error) {
+	results.push(error.message.split(":")[0]);
+}
+
+results.join();
+"#;
+
+#[test]
+fn structured_clone() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("true,true,true,true,true,0,gi,0,3,DataCloneError", result);
+}
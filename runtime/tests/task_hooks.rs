@@ -0,0 +1,69 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::event_loop::{add_task_hooks, remove_task_hooks, TaskHooks, TaskInfo};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "task-hooks.js";
+const SCRIPT: &str = "setTimeout(() => Promise.resolve().then(() => {}), 0);";
+
+#[derive(Default)]
+struct RecordingHooks(RefCell<Vec<String>>);
+
+impl TaskHooks for RecordingHooks {
+	fn init(&self, _: &Context, task: &TaskInfo) {
+		let event = format!("init:{}:{}:{}", task.id, task.kind.as_str(), task.trigger_id);
+		self.0.borrow_mut().push(event);
+	}
+
+	fn before(&self, _: &Context, task: &TaskInfo) {
+		self.0.borrow_mut().push(format!("before:{}", task.id));
+	}
+
+	fn after(&self, _: &Context, task: &TaskInfo, _: Duration) {
+		self.0.borrow_mut().push(format!("after:{}", task.id));
+	}
+
+	fn destroy(&self, _: &Context, task: &TaskInfo) {
+		self.0.borrow_mut().push(format!("destroy:{}", task.id));
+	}
+}
+
+#[test]
+fn task_hooks() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let hooks = Rc::new(RecordingHooks::default());
+	let id = add_task_hooks(rt.cx(), hooks.clone());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	assert!(tokio.block_on(rt.run_event_loop()).is_ok());
+
+	assert_eq!(
+		"init:1:timer:0,before:1,init:2:promise:1,after:1,destroy:1,before:2,after:2,destroy:2",
+		hooks.0.borrow().join(",")
+	);
+
+	assert!(remove_task_hooks(rt.cx(), id));
+	assert!(!remove_task_hooks(rt.cx(), id));
+}
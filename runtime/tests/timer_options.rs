@@ -0,0 +1,29 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use chrono::Duration;
+
+use runtime::event_loop::TimerOptions;
+
+#[test]
+fn clamp() {
+	let options = TimerOptions::default();
+	assert_eq!(Duration::zero(), options.clamp(None, 0));
+	assert_eq!(Duration::zero(), options.clamp(Some(-10), 1));
+	assert_eq!(Duration::milliseconds(2), options.clamp(Some(2), 5));
+	assert_eq!(Duration::milliseconds(4), options.clamp(Some(2), 6));
+	assert_eq!(Duration::milliseconds(4), options.clamp(None, 6));
+	assert_eq!(Duration::milliseconds(10), options.clamp(Some(10), 6));
+
+	let options = TimerOptions {
+		minimum_delay: Duration::milliseconds(1),
+		nested_minimum_delay: Duration::milliseconds(16),
+		nesting_threshold: 2,
+		drift_correction: false,
+	};
+	assert_eq!(Duration::milliseconds(1), options.clamp(Some(0), 2));
+	assert_eq!(Duration::milliseconds(16), options.clamp(Some(0), 3));
+}
@@ -0,0 +1,74 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "timers.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+
+function named() {}
+const timeout = setTimeout(named, 1000);
+const interval = setInterval(() => {}, 500);
+globalThis.listed = timers.list().map(timer => `${timer.id}:${timer.type}:${timer.label}`).join(" ");
+clearTimeout(timeout);
+clearInterval(interval);
+
+const start = Date.now();
+setTimeout(() => results.push("wrapped"), 2 ** 32 + 10);
+setTimeout(() => results.push("late"), 5);
+setTimeout(() => results.push("huge"), 2 ** 31);
+setTimeout(() => results.push("maximum"), Number.MAX_VALUE);
+setTimeout(() => results.push("infinite"), Infinity);
+setTimeout(() => results.push("nan"), NaN);
+setTimeout(() => results.push("negative"), -5);
+setTimeout(() => results.push("zero"), 0);
+globalThis.delayed = timers.list().filter(timer => timer.due - start > 100).length;
+"#;
+
+#[test]
+fn timers() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new()
+		.microtask_queue()
+		.macrotask_queue()
+		.timer_introspection()
+		.build(cx);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT);
+	assert!(result.is_ok(), "Error: {:?}", result.unwrap_err());
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	assert!(tokio.block_on(rt.run_event_loop()).is_ok());
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "listed").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("1:interval: 0:timeout:named", result);
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "delayed").unwrap();
+	assert_eq!(0.0, f64::from_value(rt.cx(), &result, true, ()).unwrap());
+
+	let script = "[results.slice(0, 6).sort().join(), results.slice(6).join()]";
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), script).unwrap();
+	let result = Vec::<String>::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!(
+		vec![
+			String::from("huge,infinite,maximum,nan,negative,zero"),
+			String::from("late,wrapped")
+		],
+		result
+	);
+}
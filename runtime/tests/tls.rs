@@ -0,0 +1,53 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "fetch")]
+
+use rustls::pki_types::CertificateDer;
+
+use runtime::globals::fetch::{client_with_options, ClientIdentity, ClientOptions, TlsOptions};
+use runtime::RuntimeBuilder;
+
+#[test]
+fn tls_options() {
+	assert!(TlsOptions::default().client_config().is_ok());
+
+	let options = TlsOptions {
+		builtin_roots: false,
+		accept_invalid_certificates: true,
+		..TlsOptions::default()
+	};
+	assert!(options.client_config().is_ok());
+
+	let mut options = TlsOptions::default();
+	assert!(options.add_root_certificates_pem(b"not a certificate").is_err());
+	assert!(ClientIdentity::from_pem(b"", b"").is_err());
+}
+
+#[test]
+fn client_tls_options() {
+	let options = ClientOptions {
+		tls: TlsOptions {
+			accept_invalid_certificates: true,
+			..TlsOptions::default()
+		},
+		..ClientOptions::default()
+	};
+	assert!(client_with_options(&options).is_ok());
+}
+
+#[test]
+fn invalid_client_options() {
+	let options = ClientOptions {
+		tls: TlsOptions {
+			root_certificates: vec![CertificateDer::from(vec![0; 4])],
+			..TlsOptions::default()
+		},
+		..ClientOptions::default()
+	};
+	assert!(client_with_options(&options).is_err());
+	assert!(RuntimeBuilder::<()>::new().client_options(options).is_err());
+}
@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "transform-backpressure.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+const tick = () => new Promise(resolve => setTimeout(resolve, 1));
+
+(async () => {
+	const transformed = [];
+	const stream = new TransformStream({
+		transform(chunk, controller) {
+			transformed.push(chunk);
+			controller.enqueue(chunk);
+		},
+	});
+
+	const writer = stream.writable.getWriter();
+	writer.write("a");
+	writer.write("b");
+	writer.write("c");
+	await tick();
+	// Chunks are only transformed while the readable side has room for them.
+	results.push(transformed.join(""), writer.desiredSize);
+
+	const reader = stream.readable.getReader();
+	for (let i = 0; i < 2; i++) {
+		results.push((await reader.read()).value);
+		await tick();
+		results.push(transformed.join(""));
+	}
+})();
+"#;
+
+#[test]
+fn transform_backpressure() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("a,-1,a,ab,b,abc", result);
+}
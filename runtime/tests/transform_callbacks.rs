@@ -0,0 +1,82 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{Context, Promise, ResultExc, Value};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::globals::streams::{transform_stream_from_callbacks, TransformerCallbacks, TransformStreamDefaultController};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "transform-callbacks.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+(async () => {
+	const source = new ReadableStream({
+		start(controller) {
+			controller.enqueue("hello");
+			controller.enqueue("world");
+			controller.close();
+		},
+	});
+	const reader = source.pipeThrough(transform).getReader();
+	for (let chunk = await reader.read(); !chunk.done; chunk = await reader.read()) {
+		results.push(chunk.value);
+	}
+})();
+"#;
+
+/// Converts string chunks to uppercase, and enqueues a final chunk when flushed.
+struct Uppercase {
+	flushed: Rc<Cell<bool>>,
+}
+
+impl TransformerCallbacks for Uppercase {
+	fn transform(
+		&self, cx: &Context, chunk: Value, controller: &TransformStreamDefaultController,
+	) -> ResultExc<Option<Promise>> {
+		let chunk = String::from_value(cx, &chunk, true, ())?;
+		controller.enqueue(cx, Value::string(cx, &chunk.to_uppercase()))?;
+		Ok(None)
+	}
+
+	fn flush(&self, cx: &Context, controller: &TransformStreamDefaultController) -> ResultExc<Option<Promise>> {
+		self.flushed.set(true);
+		controller.enqueue(cx, Value::string(cx, "!"))?;
+		Ok(None)
+	}
+}
+
+#[test]
+fn transform_callbacks() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let flushed = Rc::new(Cell::new(false));
+	let transform = transform_stream_from_callbacks(rt.cx(), Uppercase { flushed: Rc::clone(&flushed) }).unwrap();
+	rt.global().set(rt.cx(), "transform", &Value::object(rt.cx(), &transform));
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("HELLO,WORLD,!", result);
+	assert!(flushed.get());
+}
@@ -0,0 +1,57 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use futures::executor::block_on;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::{Context, Exception, TypedPromise, Value};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "typed-promise.js";
+
+fn evaluate(cx: &Context, source: &str) -> TypedPromise<String> {
+	let value = Script::compile_and_evaluate(cx, Path::new(FILE_NAME), source).unwrap();
+	TypedPromise::from_value(cx, &value, true, ()).unwrap()
+}
+
+#[test]
+fn typed_promise() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let resolved = evaluate(rt.cx(), "Promise.resolve('resolved')").wait(rt.cx().duplicate());
+	let rejected = evaluate(rt.cx(), "Promise.reject('rejected')").wait(rt.cx().duplicate());
+	let mismatched = evaluate(rt.cx(), "Promise.resolve(1)").wait_with(rt.cx().duplicate(), true, ());
+	assert!(block_on(rt.run_event_loop()).is_ok());
+
+	let (_, resolved) = block_on(resolved);
+	assert_eq!("resolved", resolved.unwrap());
+
+	let (cx, rejected) = block_on(rejected);
+	match rejected {
+		Err(Exception::Other(value)) => {
+			let value = Value::from(cx.root(value));
+			assert_eq!("rejected", String::from_value(&cx, &value, true, ()).unwrap());
+		}
+		_ => panic!("Expected promise to be rejected"),
+	}
+
+	let (_, mismatched) = block_on(mismatched);
+	assert!(matches!(mismatched, Err(Exception::Error(_))));
+
+	let value = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "1").unwrap();
+	assert!(TypedPromise::<String>::from_value(rt.cx(), &value, true, ()).is_err());
+}
@@ -0,0 +1,61 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use futures::executor::block_on;
+use mozjs::rust::{JSEngine, Runtime};
+
+use ion::Context;
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::config::{Config, CONFIG, LogLevel};
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "unhandled-rejection.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+globalThis.onunhandledrejection = event => {
+	results.push(`${event.type}:${event.reason}:${event.promise instanceof Promise}:${event.isTrusted}`);
+	if (event.reason === "cancelled") {
+		event.preventDefault();
+	}
+};
+
+Promise.reject("cancelled");
+Promise.reject("reported");
+Promise.reject("caught").catch(() => {});
+"#;
+
+#[test]
+fn unhandled_rejection() {
+	CONFIG.set(Config::default().log_level(LogLevel::Debug).script(true)).unwrap();
+
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let reported = Rc::new(RefCell::new(Vec::new()));
+	let handler_reported = Rc::clone(&reported);
+	rt.set_unhandled_rejection_handler(move |cx, reason| {
+		let reason = String::from_value(cx, &reason, true, ()).unwrap();
+		handler_reported.borrow_mut().push(reason);
+	});
+
+	Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+	assert!(block_on(rt.run_event_loop()).is_ok());
+
+	let results = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	assert_eq!(
+		"unhandledrejection:cancelled:true:true,unhandledrejection:reported:true:true",
+		String::from_value(rt.cx(), &results, true, ()).unwrap()
+	);
+	assert_eq!(vec![String::from("reported")], *reported.borrow());
+}
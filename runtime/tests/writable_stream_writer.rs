@@ -0,0 +1,88 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use mozjs::rust::{JSEngine, Runtime};
+use tokio::task::LocalSet;
+
+use ion::{Context, Exception, TracedHeap, Value, WritableStream};
+use ion::conversions::FromValue;
+use ion::script::Script;
+use runtime::promise::future_to_promise;
+use runtime::RuntimeBuilder;
+
+const FILE_NAME: &str = "writable-stream-writer.js";
+const SCRIPT: &str = r#"
+globalThis.results = [];
+globalThis.stream = new WritableStream(
+	{
+		write(chunk) {
+			results.push(chunk);
+			return new Promise(resolve => setTimeout(resolve, 1));
+		},
+		close() {
+			results.push("closed");
+		},
+	},
+	{ highWaterMark: 1 },
+);
+"#;
+
+#[test]
+fn writable_stream_writer() {
+	let engine = JSEngine::init().unwrap();
+	let rt = Runtime::new(engine.handle());
+
+	let cx = &mut Context::from_runtime(&rt);
+	let rt = RuntimeBuilder::<()>::new().microtask_queue().macrotask_queue().build(cx);
+
+	let tokio = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	let local = LocalSet::new();
+	local.block_on(&tokio, async {
+		Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), SCRIPT).unwrap();
+
+		let stream = rt.global().get(rt.cx(), "stream").unwrap().unwrap();
+		let stream = WritableStream::from_value(rt.cx(), &stream, true, ()).unwrap();
+		let writer = stream.into_writer(rt.cx()).unwrap();
+		assert_eq!(Some(1.0), writer.desired_size(rt.cx()).unwrap());
+
+		// Writing past the high water mark applies back-pressure until the queue drains.
+		writer.write_chunk_raw(rt.cx(), &Value::string(rt.cx(), "a")).unwrap();
+		writer.write_chunk_raw(rt.cx(), &Value::string(rt.cx(), "b")).unwrap();
+		assert_eq!(Some(-1.0), writer.desired_size(rt.cx()).unwrap());
+
+		let writing = unsafe {
+			future_to_promise(rt.cx(), move |cx| async move {
+				let (cx, result) = writer.ready(cx).await;
+				result?;
+				let desired_size = writer.desired_size(&cx)?;
+
+				let chunk = TracedHeap::new(Value::string(&cx, "c").get());
+				let (cx, result) = writer.write_chunk(cx, chunk).await;
+				result?;
+				let (cx, result) = writer.close(cx).await;
+				result?;
+
+				let stream = writer.into_stream(&cx)?;
+				Ok::<_, Exception>(format!("{:?},{}", desired_size, stream.is_locked(&cx)))
+			})
+		};
+		rt.global().set_as(rt.cx(), "writing", &writing.unwrap());
+		Script::compile_and_evaluate(
+			rt.cx(),
+			Path::new(FILE_NAME),
+			"writing.then(value => results.push(value))",
+		)
+		.unwrap();
+
+		assert!(rt.run_event_loop().await.is_ok());
+	});
+
+	let result = Script::compile_and_evaluate(rt.cx(), Path::new(FILE_NAME), "results.join()").unwrap();
+	let result = String::from_value(rt.cx(), &result, true, ()).unwrap();
+	assert_eq!("a,b,c,closed,Some(1.0),false", result);
+}